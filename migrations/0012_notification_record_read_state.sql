@@ -0,0 +1,3 @@
+ALTER TABLE NotificationRecord ADD COLUMN read_at TEXT;
+
+ALTER TABLE NotificationRecord ADD COLUMN github_read_synced_at TEXT;
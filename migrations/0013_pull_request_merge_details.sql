@@ -0,0 +1,3 @@
+ALTER TABLE PullRequest ADD COLUMN auto_merge_enabled INTEGER CHECK (auto_merge_enabled IN (0, 1));
+
+ALTER TABLE PullRequest ADD COLUMN merged_by_login TEXT;
@@ -0,0 +1 @@
+ALTER TABLE PullRequest ADD COLUMN head_branch TEXT;
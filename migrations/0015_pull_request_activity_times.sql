@@ -0,0 +1,2 @@
+ALTER TABLE PullRequest ADD COLUMN opened_at TEXT;
+ALTER TABLE PullRequest ADD COLUMN last_activity_at TEXT;
@@ -0,0 +1,2 @@
+ALTER TABLE PullRequest ADD COLUMN additions INTEGER;
+ALTER TABLE PullRequest ADD COLUMN deletions INTEGER;
@@ -0,0 +1 @@
+ALTER TABLE PullRequest ADD COLUMN linked_issues_json TEXT NOT NULL DEFAULT '[]';
@@ -0,0 +1 @@
+ALTER TABLE PullRequest ADD COLUMN project_status TEXT;
@@ -0,0 +1 @@
+ALTER TABLE PullRequest ADD COLUMN changed_file_paths_json TEXT NOT NULL DEFAULT '[]';
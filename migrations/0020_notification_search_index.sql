@@ -0,0 +1,46 @@
+CREATE VIRTUAL TABLE NotificationSearch USING fts5(
+  title,
+  body,
+  comments,
+  tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Comment bodies live on the normalized events a record was built from.
+CREATE TRIGGER notification_search_after_insert
+AFTER INSERT ON NotificationRecord
+BEGIN
+  INSERT INTO NotificationSearch (rowid, title, body, comments)
+  VALUES (
+    NEW.id,
+    NEW.title,
+    NEW.body,
+    (
+      SELECT COALESCE(group_concat(json_extract(normalized_event.payload_json, '$.bodyText'), char(10)), '')
+      FROM NormalizedEvent normalized_event
+      WHERE normalized_event.id = NEW.normalized_event_id
+        OR (NEW.event_bundle_id IS NOT NULL AND normalized_event.event_bundle_id = NEW.event_bundle_id)
+    )
+  );
+END;
+
+CREATE TRIGGER notification_search_after_delete
+AFTER DELETE ON NotificationRecord
+BEGIN
+  DELETE FROM NotificationSearch WHERE rowid = OLD.id;
+END;
+
+INSERT INTO NotificationSearch (rowid, title, body, comments)
+SELECT
+  notification_record.id,
+  notification_record.title,
+  notification_record.body,
+  (
+    SELECT COALESCE(group_concat(json_extract(normalized_event.payload_json, '$.bodyText'), char(10)), '')
+    FROM NormalizedEvent normalized_event
+    WHERE normalized_event.id = notification_record.normalized_event_id
+      OR (
+        notification_record.event_bundle_id IS NOT NULL
+        AND normalized_event.event_bundle_id = notification_record.event_bundle_id
+      )
+  )
+FROM NotificationRecord notification_record;
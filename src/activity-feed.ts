@@ -18,6 +18,8 @@ export interface ActivityFeedFilters {
   pullRequestStates: PullRequestStateFilter[];
   repository: string;
   actorClass: "" | ActorClass;
+  // Full-text search over notification titles, bodies, and comment text.
+  search: string;
 }
 
 export interface PullRequestStateFilterMatchInput {
@@ -58,6 +60,7 @@ export const DEFAULT_ACTIVITY_FEED_FILTERS: ActivityFeedFilters = {
   pullRequestStates: [],
   repository: "",
   actorClass: "",
+  search: "",
 };
 
 export const DEFAULT_ACTIVITY_PAGE_SIZE = 50;
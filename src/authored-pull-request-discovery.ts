@@ -3,11 +3,18 @@ import { DatabaseSync } from "node:sqlite";
 import { Octokit } from "octokit";
 
 import type { GitHubAuthContext } from "./github.js";
+import {
+  listUnreadPullRequestNotifications,
+  type GitHubInboxSettings,
+} from "./github-notification-read-state.js";
 import { getLogger } from "./logger.js";
+import type { PullRequestActionProvider } from "./notification-actions.js";
 import type { NotificationDispatcher } from "./notification-dispatch.js";
 import { dispatchPullRequestNotifications } from "./notification-dispatch.js";
 import { NormalizedEventRepository } from "./normalized-event-repository.js";
 import { preparePullRequestNotifications } from "./notification-preparation.js";
+import { runOrderedPipeline } from "./ordered-pipeline.js";
+import type { PollerBackoff } from "./poller-state.js";
 import {
   createPullRequestUpsertInput,
   mapPullRequestSnapshot,
@@ -16,16 +23,25 @@ import {
 } from "./pull-request-snapshot.js";
 import { PullRequestRepository } from "./pull-request-repository.js";
 import type { PullRequestRecord } from "./pull-request-repository.js";
+import type { RepositoryOverride } from "./repository-overrides.js";
 
 const FIRST_RUN_DISCOVERY_COMPLETED_KEY = "first_run_pull_request_discovery_completed";
 const GITHUB_API_HEADERS = {
   "X-GitHub-Api-Version": "2022-11-28",
 };
 const SEARCH_PAGE_SIZE = 100;
+const RECEIVED_EVENTS_PAGE_SIZE = 100;
+const TIMELINE_PAGE_SIZE = 100;
+const DEFAULT_POLL_CONCURRENCY = 4;
+const REVIEW_REQUESTED_SEARCH_FAILURE_COUNT_KEY = "review_requested_search_failure_count";
+const REVIEW_REQUESTED_SEARCH_SUCCEEDED_AT_KEY = "review_requested_search_succeeded_at";
+const RECEIVED_EVENTS_FALLBACK_FAILURE_THRESHOLD = 3;
 const COMPLETED_STATE_VALUE = "true";
 const REVIEW_REQUESTED_NOTIFICATION_EVENT_TYPE = "review_requested";
+export const GITHUB_INBOX_TRACKING_REASON = "github_inbox";
+const MENTIONED_TRACKING_REASON = "mentioned";
 
-type PullRequestDiscoverySource = "authored" | "review_requested";
+type PullRequestDiscoverySource = "authored" | "review_requested" | "mentioned" | "github_inbox";
 
 interface DiscoveryCandidate {
   coordinates: PullRequestCoordinates;
@@ -35,6 +51,13 @@ interface DiscoveryCandidate {
 export type { PullRequestCoordinates } from "./pull-request-snapshot.js";
 export type DiscoveredPullRequest = PullRequestSnapshot;
 
+export interface PullRequestReviewRequest {
+  requesterLogin: string;
+  requestedAt: string;
+  // Set when the request went to one of the reviewer's teams rather than to them directly.
+  requestedTeamSlug?: string;
+}
+
 export interface DiscoverOpenAuthoredPullRequestsOptions<TClient = Octokit> {
   pullRequestRepository?: Pick<
     PullRequestRepository,
@@ -49,13 +72,40 @@ export interface DiscoverOpenAuthoredPullRequestsOptions<TClient = Octokit> {
     client: TClient,
     reviewerLogin: string,
   ) => Promise<PullRequestCoordinates[]>;
+  // Only used once review-requested search keeps failing. Events created before `since`, the last
+  // successful search, are left out since that search already covered them.
+  listPullRequestsFromReceivedEvents?: (
+    client: TClient,
+    login: string,
+    since: string | undefined,
+  ) => Promise<ReceivedEventPullRequests>;
+  listInboxPullRequests?: (
+    client: TClient,
+    inbox: GitHubInboxSettings,
+  ) => Promise<PullRequestCoordinates[]>;
+  // Also tracks the pull requests behind unread github.com notifications when set.
+  githubInbox?: GitHubInboxSettings;
   fetchPullRequestDetail?: (
     client: TClient,
     coordinates: PullRequestCoordinates,
   ) => Promise<DiscoveredPullRequest>;
+  fetchReviewRequest?: (
+    client: TClient,
+    coordinates: PullRequestCoordinates,
+    reviewerLogin: string,
+  ) => Promise<PullRequestReviewRequest | null>;
+  // How many discovered pull requests' details to fetch at once.
+  concurrency?: number;
   observedAt?: string;
   notificationDispatcher?: NotificationDispatcher;
   notificationDispatchedAt?: string;
+  pullRequestActions?: PullRequestActionProvider;
+  repositoryOverrides?: readonly RepositoryOverride[];
+}
+
+export interface ReceivedEventPullRequests {
+  reviewRequested: PullRequestCoordinates[];
+  mentioned: PullRequestCoordinates[];
 }
 
 export interface DiscoverOpenAuthoredPullRequestsResult {
@@ -71,15 +121,25 @@ export interface StartRecurringAuthoredPullRequestDiscoveryOptions<TClient = Oct
   extends DiscoverOpenAuthoredPullRequestsOptions<TClient> {
   intervalMs: number;
   onError?: (error: PullRequestDiscoveryError) => void;
+  // Called after every cycle with the error that failed it, or null when it succeeded.
+  onCycleSettled?: (failure: PullRequestDiscoveryError | null) => void;
+  // Defers cycles after repeated failures or while the rate limit is exhausted.
+  backoff?: PollerBackoff;
 }
 
 export interface RecurringAuthoredPullRequestDiscoveryHandle {
+  // Both apply from the next cycle on, e.g. after the config file changes.
+  setIntervalMs(intervalMs: number): void;
+  setRepositoryOverrides(repositoryOverrides: readonly RepositoryOverride[]): void;
+  // Skips cycles until resumed, e.g. while GitHub rejects the token.
+  pause(): void;
+  resume(): void;
   stop(): void;
 }
 
 export class PullRequestDiscoveryError extends Error {
-  constructor(message: string) {
-    super(message);
+  constructor(message: string, options?: ErrorOptions) {
+    super(message, options);
     this.name = "PullRequestDiscoveryError";
   }
 }
@@ -110,23 +170,37 @@ export function startRecurringAuthoredPullRequestDiscovery<TClient>(
   githubAuth: GitHubAuthContext<TClient>,
   options: StartRecurringAuthoredPullRequestDiscoveryOptions<TClient>,
 ): RecurringAuthoredPullRequestDiscoveryHandle {
-  const { intervalMs, onError, ...discoveryOptions } = options;
+  const { intervalMs, onError, onCycleSettled, backoff, ...initialDiscoveryOptions } = options;
 
-  if (!Number.isFinite(intervalMs) || intervalMs <= 0) {
-    throw new PullRequestDiscoveryError(
-      "Recurring pull request discovery interval must be greater than zero",
-    );
-  }
+  assertValidDiscoveryInterval(intervalMs);
 
+  let discoveryOptions: DiscoverOpenAuthoredPullRequestsOptions<TClient> = initialDiscoveryOptions;
   let isStopped = false;
+  let isPaused = false;
   let isRunning = false;
-  const timer = setInterval(() => {
-    void runDiscoveryCycle();
-  }, intervalMs);
-
-  timer.unref?.();
+  let timer = startTimer(intervalMs);
 
   return {
+    setIntervalMs(nextIntervalMs: number): void {
+      assertValidDiscoveryInterval(nextIntervalMs);
+
+      if (isStopped) {
+        return;
+      }
+
+      clearInterval(timer);
+      timer = startTimer(nextIntervalMs);
+      getLogger().info("Changed pull request discovery interval", { intervalMs: nextIntervalMs });
+    },
+    setRepositoryOverrides(repositoryOverrides): void {
+      discoveryOptions = { ...discoveryOptions, repositoryOverrides };
+    },
+    pause(): void {
+      isPaused = true;
+    },
+    resume(): void {
+      isPaused = false;
+    },
     stop(): void {
       if (isStopped) {
         return;
@@ -138,7 +212,14 @@ export function startRecurringAuthoredPullRequestDiscovery<TClient>(
   };
 
   async function runDiscoveryCycle(): Promise<void> {
-    if (isStopped || isRunning) {
+    if (isStopped || isPaused || isRunning) {
+      return;
+    }
+
+    const deferral = backoff?.readDeferral() ?? null;
+
+    if (deferral !== null) {
+      getLogger().debug("Deferred pull request discovery cycle", { ...deferral });
       return;
     }
 
@@ -152,19 +233,45 @@ export function startRecurringAuthoredPullRequestDiscovery<TClient>(
       } else {
         getLogger().debug("Pull request discovery cycle found no new pull requests", result);
       }
+
+      settleCycle(null);
     } catch (error) {
       const discoveryError =
         error instanceof PullRequestDiscoveryError
           ? error
           : new PullRequestDiscoveryError(
               `Failed to discover pull requests: ${getErrorMessage(error)}`,
+              { cause: error },
             );
 
       (onError ?? logRecurringDiscoveryError)(discoveryError);
+      settleCycle(discoveryError);
     } finally {
       isRunning = false;
     }
   }
+
+  function startTimer(timerIntervalMs: number): ReturnType<typeof setInterval> {
+    const nextTimer = setInterval(() => {
+      void runDiscoveryCycle();
+    }, timerIntervalMs);
+
+    nextTimer.unref?.();
+    return nextTimer;
+  }
+
+  function settleCycle(failure: PullRequestDiscoveryError | null): void {
+    backoff?.recordCycle(failure);
+    onCycleSettled?.(failure);
+  }
+}
+
+function assertValidDiscoveryInterval(intervalMs: number): void {
+  if (!Number.isFinite(intervalMs) || intervalMs <= 0) {
+    throw new PullRequestDiscoveryError(
+      "Recurring pull request discovery interval must be greater than zero",
+    );
+  }
 }
 
 export async function discoverOpenAuthoredPullRequests<TClient>(
@@ -187,6 +294,15 @@ export async function discoverOpenAuthoredPullRequests<TClient>(
         client as unknown as Octokit,
         reviewerLogin,
       ) as Promise<PullRequestCoordinates[]>);
+  const listPullRequestsFromReceivedEvents =
+    options.listPullRequestsFromReceivedEvents ??
+    ((client: TClient, login: string, since: string | undefined) =>
+      listPullRequestsFromGitHubReceivedEvents(client as unknown as Octokit, login, since));
+  const listInboxPullRequests =
+    options.listInboxPullRequests ??
+    ((client: TClient, inbox: GitHubInboxSettings) =>
+      listUnreadPullRequestNotifications(client as unknown as Octokit, inbox));
+  const githubInbox = options.githubInbox;
   const fetchPullRequestDetail =
     options.fetchPullRequestDetail ??
     ((client: TClient, coordinates: PullRequestCoordinates) =>
@@ -194,20 +310,35 @@ export async function discoverOpenAuthoredPullRequests<TClient>(
         client as unknown as Octokit,
         coordinates,
       ) as Promise<DiscoveredPullRequest>);
+  const fetchReviewRequest =
+    options.fetchReviewRequest ??
+    ((client: TClient, coordinates: PullRequestCoordinates, reviewerLogin: string) =>
+      fetchReviewRequestFromGitHub(client as unknown as Octokit, coordinates, reviewerLogin));
   const observedAt = options.observedAt ?? new Date().toISOString();
   const notificationDispatchedAt = options.notificationDispatchedAt ?? observedAt;
 
   let discoveryCandidates: DiscoveryCandidate[];
 
   try {
-    const [authoredCoordinates, reviewRequestedCoordinates] = await Promise.all([
+    const [authoredCoordinates, reviewRequested, inboxCoordinates] = await Promise.all([
       searchOpenAuthoredPullRequests(githubAuth.client, githubAuth.currentUserLogin),
-      searchOpenReviewRequestedPullRequests(githubAuth.client, githubAuth.currentUserLogin),
+      searchReviewRequestedPullRequestsWithFallback(
+        database,
+        observedAt,
+        () => searchOpenReviewRequestedPullRequests(githubAuth.client, githubAuth.currentUserLogin),
+        (since) =>
+          listPullRequestsFromReceivedEvents(githubAuth.client, githubAuth.currentUserLogin, since),
+      ),
+      githubInbox === undefined
+        ? Promise.resolve<PullRequestCoordinates[]>([])
+        : listInboxPullRequests(githubAuth.client, githubInbox),
     ]);
 
     discoveryCandidates = mergeDiscoveryCandidates({
       authoredCoordinates,
-      reviewRequestedCoordinates,
+      reviewRequestedCoordinates: reviewRequested.reviewRequested,
+      mentionedCoordinates: reviewRequested.mentioned,
+      inboxCoordinates,
     });
   } catch (error) {
     if (error instanceof PullRequestDiscoveryError) {
@@ -216,6 +347,7 @@ export async function discoverOpenAuthoredPullRequests<TClient>(
 
     throw new PullRequestDiscoveryError(
       `Failed to discover pull requests: ${getErrorMessage(error)}`,
+      { cause: error },
     );
   }
 
@@ -226,65 +358,99 @@ export async function discoverOpenAuthoredPullRequests<TClient>(
     reviewRequestedCount: discoveryCandidates.filter((candidate) =>
       candidate.sources.includes("review_requested"),
     ).length,
+    inboxCount: discoveryCandidates.filter((candidate) =>
+      candidate.sources.includes("github_inbox"),
+    ).length,
   });
 
-  for (const candidate of discoveryCandidates) {
-    const { coordinates } = candidate;
-    const existingPullRequest = pullRequestRepository.getPullRequestByRepositoryCoordinates(
-      coordinates.repositoryOwner,
-      coordinates.repositoryName,
-      coordinates.number,
-    );
-    let pullRequest: DiscoveredPullRequest;
-
-    try {
-      pullRequest = await fetchPullRequestDetail(githubAuth.client, coordinates);
-    } catch (error) {
-      if (error instanceof PullRequestDiscoveryError) {
-        throw error;
+  // Details are fetched concurrently, but candidates are stored and notified one at a time in
+  // discovery order, so a burst of new pull requests comes out the same as it would serially.
+  await runOrderedPipeline(discoveryCandidates, {
+    concurrency: options.concurrency ?? DEFAULT_POLL_CONCURRENCY,
+    process: async ({ coordinates }) => {
+      try {
+        return await fetchPullRequestDetail(githubAuth.client, coordinates);
+      } catch (error) {
+        if (error instanceof PullRequestDiscoveryError) {
+          throw error;
+        }
+
+        throw new PullRequestDiscoveryError(
+          `Failed to fetch pull request ${formatPullRequestLabel(coordinates)}: ${getErrorMessage(error)}`,
+          { cause: error },
+        );
       }
-
-      throw new PullRequestDiscoveryError(
-        `Failed to fetch pull request ${formatPullRequestLabel(coordinates)}: ${getErrorMessage(error)}`,
-      );
-    }
-
-    try {
-      const persistedPullRequest = pullRequestRepository.upsertPullRequest(
-        createPullRequestUpsertInput(pullRequest, {
-          lastSeenAt: observedAt,
-          graceUntil: null,
-        }),
+    },
+    consume: async (pullRequest, candidate) => {
+      const { coordinates } = candidate;
+      const existingPullRequest = pullRequestRepository.getPullRequestByRepositoryCoordinates(
+        coordinates.repositoryOwner,
+        coordinates.repositoryName,
+        coordinates.number,
       );
 
-      if (
-        existingPullRequest === undefined &&
-        candidate.sources.includes("review_requested") &&
-        !candidate.sources.includes("authored")
-      ) {
-        await createReviewRequestedNotification(database, persistedPullRequest, {
-          occurredAt: observedAt,
-          dispatchedAt: notificationDispatchedAt,
-          currentUserLogin: githubAuth.currentUserLogin,
-          ...(options.notificationDispatcher
-            ? { notificationDispatcher: options.notificationDispatcher }
-            : {}),
+      try {
+        const persistedPullRequest = pullRequestRepository.upsertPullRequest(
+          createPullRequestUpsertInput(pullRequest, {
+            lastSeenAt: observedAt,
+            graceUntil: null,
+            // Pull requests only a mention or the inbox turned up say so, which the popups explain.
+            ...(existingPullRequest === undefined &&
+            candidate.sources.every(
+              (source) => source === "mentioned" || source === "github_inbox",
+            )
+              ? {
+                  tracking: {
+                    isTracked: true,
+                    trackingReason: candidate.sources.includes("mentioned")
+                      ? MENTIONED_TRACKING_REASON
+                      : GITHUB_INBOX_TRACKING_REASON,
+                    isStickyUntracked: false,
+                  },
+                }
+              : {}),
+          }),
+        );
+
+        if (
+          existingPullRequest === undefined &&
+          candidate.sources.includes("review_requested") &&
+          !candidate.sources.includes("authored")
+        ) {
+          await createReviewRequestedNotification(database, persistedPullRequest, {
+            details: pullRequest,
+            reviewRequest: await loadReviewRequest(() =>
+              fetchReviewRequest(githubAuth.client, coordinates, githubAuth.currentUserLogin),
+            ),
+            occurredAt: observedAt,
+            dispatchedAt: notificationDispatchedAt,
+            currentUserLogin: githubAuth.currentUserLogin,
+            ...(options.notificationDispatcher
+              ? { notificationDispatcher: options.notificationDispatcher }
+              : {}),
+            ...(options.pullRequestActions
+              ? { pullRequestActions: options.pullRequestActions }
+              : {}),
+            ...(options.repositoryOverrides
+              ? { repositoryOverrides: options.repositoryOverrides }
+              : {}),
+          });
+        }
+
+        getLogger().debug("Persisted discovered pull request", {
+          pullRequest: formatPullRequestLabel(coordinates),
         });
+      } catch (error) {
+        if (error instanceof PullRequestDiscoveryError) {
+          throw error;
+        }
+
+        throw new PullRequestDiscoveryError(
+          `Failed to persist pull request ${formatPullRequestLabel(coordinates)}: ${getErrorMessage(error)}`,
+        );
       }
-
-      getLogger().debug("Persisted discovered pull request", {
-        pullRequest: formatPullRequestLabel(coordinates),
-      });
-    } catch (error) {
-      if (error instanceof PullRequestDiscoveryError) {
-        throw error;
-      }
-
-      throw new PullRequestDiscoveryError(
-        `Failed to persist pull request ${formatPullRequestLabel(coordinates)}: ${getErrorMessage(error)}`,
-      );
-    }
-  }
+    },
+  });
 
   return {
     discoveredCount: discoveryCandidates.length,
@@ -341,6 +507,171 @@ async function searchOpenReviewRequestedPullRequestsViaGitHub(
   }
 }
 
+async function searchReviewRequestedPullRequestsWithFallback(
+  database: DatabaseSync,
+  observedAt: string,
+  search: () => Promise<PullRequestCoordinates[]>,
+  listFromEvents: (since: string | undefined) => Promise<ReceivedEventPullRequests>,
+): Promise<ReceivedEventPullRequests> {
+  try {
+    const coordinatesList = await search();
+    deleteAppStateValue(database, REVIEW_REQUESTED_SEARCH_FAILURE_COUNT_KEY);
+    writeAppStateValue(database, REVIEW_REQUESTED_SEARCH_SUCCEEDED_AT_KEY, observedAt);
+    return { reviewRequested: coordinatesList, mentioned: [] };
+  } catch (error) {
+    const failureCount =
+      Number(readAppStateValue(database, REVIEW_REQUESTED_SEARCH_FAILURE_COUNT_KEY) ?? "0") + 1;
+    writeAppStateValue(database, REVIEW_REQUESTED_SEARCH_FAILURE_COUNT_KEY, String(failureCount));
+
+    if (failureCount < RECEIVED_EVENTS_FALLBACK_FAILURE_THRESHOLD) {
+      throw error;
+    }
+
+    getLogger().warn("Review-requested search keeps failing; falling back to received events", {
+      failureCount,
+      error,
+    });
+
+    return listFromEvents(readAppStateValue(database, REVIEW_REQUESTED_SEARCH_SUCCEEDED_AT_KEY));
+  }
+}
+
+// Recovers review requests and @mentions from the activity feed while search is degraded.
+async function listPullRequestsFromGitHubReceivedEvents(
+  client: Octokit,
+  login: string,
+  since: string | undefined,
+): Promise<ReceivedEventPullRequests> {
+  const response = await client.request("GET /users/{username}/received_events", {
+    username: login,
+    per_page: RECEIVED_EVENTS_PAGE_SIZE,
+    headers: GITHUB_API_HEADERS,
+  });
+
+  if (!Array.isArray(response.data)) {
+    throw new PullRequestDiscoveryError("received events response must be an array");
+  }
+
+  return readReceivedEventPullRequests(response.data as unknown[], login, since);
+}
+
+export function readReceivedEventPullRequests(
+  events: readonly unknown[],
+  login: string,
+  since: string | undefined,
+): ReceivedEventPullRequests {
+  const sinceMs = since === undefined ? undefined : Date.parse(since);
+  const result: ReceivedEventPullRequests = { reviewRequested: [], mentioned: [] };
+
+  for (const event of events) {
+    const value = requireRecord(event, "received event");
+    const createdAt = value.created_at;
+
+    if (
+      sinceMs !== undefined &&
+      (typeof createdAt !== "string" || Date.parse(createdAt) < sinceMs)
+    ) {
+      continue;
+    }
+
+    const payload = requireRecord(value.payload, "received event.payload");
+
+    if (isReviewRequestedEvent(value.type, payload, login)) {
+      result.reviewRequested.push(readReceivedEventCoordinates(value, payload.number));
+      continue;
+    }
+
+    const mention = readMentioningPullRequestText(value.type, payload);
+
+    if (
+      mention !== null &&
+      !sameLogin(readOptionalLogin(value.actor), login) &&
+      mentionsLogin(mention.text, login)
+    ) {
+      result.mentioned.push(readReceivedEventCoordinates(value, mention.number));
+    }
+  }
+
+  return result;
+}
+
+function isReviewRequestedEvent(
+  type: unknown,
+  payload: Record<string, unknown>,
+  login: string,
+): boolean {
+  return (
+    type === "PullRequestEvent" &&
+    payload.action === "review_requested" &&
+    sameLogin(readOptionalLogin(payload.requested_reviewer), login)
+  );
+}
+
+// The pull request number and text of events that can @mention someone on a pull request.
+function readMentioningPullRequestText(
+  type: unknown,
+  payload: Record<string, unknown>,
+): { number: unknown; text: unknown } | null {
+  const comment = payload.comment as Record<string, unknown> | undefined;
+  const pullRequest = payload.pull_request as Record<string, unknown> | undefined;
+  const issue = payload.issue as Record<string, unknown> | undefined;
+
+  switch (type) {
+    case "IssueCommentEvent":
+      return issue?.pull_request === undefined
+        ? null
+        : { number: issue.number, text: comment?.body };
+    case "PullRequestReviewCommentEvent":
+      return { number: pullRequest?.number, text: comment?.body };
+    case "PullRequestReviewEvent":
+      return {
+        number: pullRequest?.number,
+        text: (payload.review as Record<string, unknown> | undefined)?.body,
+      };
+    case "PullRequestEvent":
+      return payload.action === "opened"
+        ? { number: payload.number, text: pullRequest?.body }
+        : null;
+    default:
+      return null;
+  }
+}
+
+function mentionsLogin(text: unknown, login: string): boolean {
+  if (typeof text !== "string") {
+    return false;
+  }
+
+  const escapedLogin = login.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+
+  return new RegExp(`(?:^|[^\\w-])@${escapedLogin}(?![\\w-])`, "i").test(text);
+}
+
+function readReceivedEventCoordinates(
+  event: Record<string, unknown>,
+  number: unknown,
+): PullRequestCoordinates {
+  const repositoryFullName = readString(
+    requireRecord(event.repo, "received event.repo").name,
+    "received event.repo.name",
+  );
+  const [repositoryOwner, repositoryName] = repositoryFullName.split("/");
+
+  if (!repositoryOwner || !repositoryName) {
+    throw new PullRequestDiscoveryError(`Unsupported repository name: ${repositoryFullName}`);
+  }
+
+  return {
+    repositoryOwner,
+    repositoryName,
+    number: readInteger(number, "received event pull request number"),
+  };
+}
+
+function sameLogin(left: string | null, right: string): boolean {
+  return left !== null && left.toLowerCase() === right.toLowerCase();
+}
+
 export async function fetchPullRequestDetailFromGitHub(
   client: Octokit,
   coordinates: PullRequestCoordinates,
@@ -359,6 +690,101 @@ export async function fetchPullRequestDetailFromGitHub(
   );
 }
 
+// Finds who most recently asked the reviewer (directly or through a team) to review, and when.
+export async function fetchReviewRequestFromGitHub(
+  client: Octokit,
+  coordinates: PullRequestCoordinates,
+  reviewerLogin: string,
+): Promise<PullRequestReviewRequest | null> {
+  let latest: PullRequestReviewRequest | null = null;
+
+  for (let page = 1; ; page += 1) {
+    const response = await client.request("GET /repos/{owner}/{repo}/issues/{issue_number}/timeline", {
+      owner: coordinates.repositoryOwner,
+      repo: coordinates.repositoryName,
+      issue_number: coordinates.number,
+      per_page: TIMELINE_PAGE_SIZE,
+      page,
+      headers: GITHUB_API_HEADERS,
+    });
+
+    if (!Array.isArray(response.data)) {
+      throw new PullRequestDiscoveryError("timeline response must be an array");
+    }
+
+    for (const event of response.data as unknown[]) {
+      latest = readReviewRequestTimelineEvent(event, reviewerLogin) ?? latest;
+    }
+
+    if (response.data.length < TIMELINE_PAGE_SIZE) {
+      return latest;
+    }
+  }
+}
+
+function readReviewRequestTimelineEvent(
+  event: unknown,
+  reviewerLogin: string,
+): PullRequestReviewRequest | null {
+  if (typeof event !== "object" || event === null || Array.isArray(event)) {
+    return null;
+  }
+
+  const value = event as Record<string, unknown>;
+
+  if (value.event !== "review_requested" || typeof value.created_at !== "string") {
+    return null;
+  }
+
+  const requestedReviewer = readOptionalLogin(value.requested_reviewer);
+  const requestedTeam =
+    typeof value.requested_team === "object" && value.requested_team !== null
+      ? (value.requested_team as Record<string, unknown>)
+      : null;
+  const isTeamRequest = requestedTeam !== null;
+
+  if (
+    !isTeamRequest &&
+    (requestedReviewer === null || requestedReviewer.toLowerCase() !== reviewerLogin.toLowerCase())
+  ) {
+    return null;
+  }
+
+  const requesterLogin = readOptionalLogin(value.review_requester) ?? readOptionalLogin(value.actor);
+
+  if (requesterLogin === null) {
+    return null;
+  }
+
+  return {
+    requesterLogin,
+    requestedAt: value.created_at,
+    ...(typeof requestedTeam?.slug === "string" ? { requestedTeamSlug: requestedTeam.slug } : {}),
+  };
+}
+
+function readOptionalLogin(value: unknown): string | null {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return null;
+  }
+
+  const login = (value as Record<string, unknown>).login;
+  return typeof login === "string" && login.length > 0 ? login : null;
+}
+
+async function loadReviewRequest(
+  load: () => Promise<PullRequestReviewRequest | null>,
+): Promise<PullRequestReviewRequest | null> {
+  try {
+    return await load();
+  } catch (error) {
+    getLogger().warn("Failed to look up who requested review", {
+      error: getErrorMessage(error),
+    });
+    return null;
+  }
+}
+
 function readSearchItems(data: unknown): unknown[] {
   const value = requireRecord(data, "search response");
   const items = value.items;
@@ -409,11 +835,15 @@ function parseRepositoryApiUrl(
 function mergeDiscoveryCandidates(input: {
   authoredCoordinates: PullRequestCoordinates[];
   reviewRequestedCoordinates: PullRequestCoordinates[];
+  mentionedCoordinates: PullRequestCoordinates[];
+  inboxCoordinates: PullRequestCoordinates[];
 }): DiscoveryCandidate[] {
   const candidates = new Map<string, DiscoveryCandidate>();
 
   appendDiscoveryCandidates(candidates, input.authoredCoordinates, "authored");
   appendDiscoveryCandidates(candidates, input.reviewRequestedCoordinates, "review_requested");
+  appendDiscoveryCandidates(candidates, input.mentionedCoordinates, "mentioned");
+  appendDiscoveryCandidates(candidates, input.inboxCoordinates, "github_inbox");
 
   return [...candidates.values()];
 }
@@ -446,10 +876,14 @@ async function createReviewRequestedNotification(
   database: DatabaseSync,
   pullRequest: PullRequestRecord,
   options: {
+    details: Pick<DiscoveredPullRequest, "baseBranch" | "headBranch" | "bodyText">;
+    reviewRequest: PullRequestReviewRequest | null;
     occurredAt: string;
     dispatchedAt: string;
     currentUserLogin: string;
     notificationDispatcher?: NotificationDispatcher;
+    pullRequestActions?: PullRequestActionProvider;
+    repositoryOverrides?: readonly RepositoryOverride[];
   },
 ): Promise<void> {
   new NormalizedEventRepository(database).insertNormalizedEvent({
@@ -457,6 +891,14 @@ async function createReviewRequestedNotification(
     eventType: REVIEW_REQUESTED_NOTIFICATION_EVENT_TYPE,
     decisionState: "notified",
     notificationTiming: "immediate",
+    actorLogin: options.reviewRequest?.requesterLogin ?? null,
+    payloadJson: JSON.stringify({
+      requestedAt: options.reviewRequest?.requestedAt ?? null,
+      requestedTeamSlug: options.reviewRequest?.requestedTeamSlug ?? null,
+      baseBranch: options.details.baseBranch,
+      headBranch: options.details.headBranch ?? null,
+      descriptionText: options.details.bodyText ?? null,
+    }),
     occurredAt: options.occurredAt,
   });
 
@@ -465,6 +907,8 @@ async function createReviewRequestedNotification(
       dispatchedAt: options.dispatchedAt,
       currentUserLogin: options.currentUserLogin,
       notificationDispatcher: options.notificationDispatcher,
+      ...(options.pullRequestActions ? { pullRequestActions: options.pullRequestActions } : {}),
+      ...(options.repositoryOverrides ? { repositoryOverrides: options.repositoryOverrides } : {}),
     });
     return;
   }
@@ -497,6 +941,10 @@ function writeAppStateValue(database: DatabaseSync, key: string, value: string):
     .run(key, value);
 }
 
+function deleteAppStateValue(database: DatabaseSync, key: string): void {
+  database.prepare("DELETE FROM AppState WHERE key = ?").run(key);
+}
+
 function requireRecord(value: unknown, fieldName: string): Record<string, unknown> {
   if (typeof value !== "object" || value === null || Array.isArray(value)) {
     throw new PullRequestDiscoveryError(`${fieldName} must be an object`);
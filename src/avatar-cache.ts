@@ -26,7 +26,7 @@ export class FileAvatarCache implements AvatarImageCache {
   private readonly maxAgeMs: number;
 
   constructor(options: FileAvatarCacheOptions = {}) {
-    this.cacheDirPath = options.cacheDirPath ?? path.join(resolveAppPaths().stateDirPath, "avatars");
+    this.cacheDirPath = options.cacheDirPath ?? path.join(resolveAppPaths().cacheDirPath, "avatars");
     this.fetchImpl = options.fetchImpl ?? fetch;
     this.maxAgeMs = options.maxAgeMs ?? DEFAULT_AVATAR_CACHE_MAX_AGE_MS;
   }
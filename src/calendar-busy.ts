@@ -0,0 +1,468 @@
+import { readFile } from "node:fs/promises";
+
+import { getLogger } from "./logger.js";
+import { readIanaTimeZoneForWindowsZone } from "./windows-time-zones.js";
+
+const DEFAULT_REFRESH_INTERVAL_MS = 15 * 60_000;
+const EXPANSION_WINDOW_MS = 2 * 24 * 60 * 60_000;
+const DAY_MS = 24 * 60 * 60_000;
+const WEEKDAY_CODES = ["SU", "MO", "TU", "WE", "TH", "FR", "SA"] as const;
+const timeZoneFormatters = new Map<string, Intl.DateTimeFormat | null>();
+// Each refresh parses the whole calendar again; one warning per zone is enough.
+const warnedUnknownTimeZones = new Set<string>();
+
+export interface BusyInterval {
+  startMs: number;
+  endMs: number;
+}
+
+export interface CalendarBusyCheckerOptions {
+  source: string;
+  refreshIntervalMs?: number;
+  fetchImpl?: typeof fetch;
+  readTextFile?: (filePath: string) => Promise<string>;
+}
+
+export interface CalendarBusyChecker {
+  isBusy(now?: number): Promise<boolean>;
+}
+
+export class CalendarBusyError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "CalendarBusyError";
+  }
+}
+
+interface IcsProperty {
+  name: string;
+  params: Record<string, string>;
+  value: string;
+}
+
+// A DATE or DATE-TIME value as written, so recurrences can step in the event's own wall-clock
+// time. `timeZone` is "UTC" for Z times, null for floating times and otherwise the TZID.
+interface IcsDateTime {
+  year: number;
+  month: number;
+  day: number;
+  hour: number;
+  minute: number;
+  second: number;
+  timeZone: string | null;
+}
+
+export function createCalendarBusyChecker(
+  options: CalendarBusyCheckerOptions,
+): CalendarBusyChecker {
+  const refreshIntervalMs = options.refreshIntervalMs ?? DEFAULT_REFRESH_INTERVAL_MS;
+  const fetchImpl = options.fetchImpl ?? fetch;
+  const readTextFile = options.readTextFile ?? ((filePath: string) => readFile(filePath, "utf8"));
+  let cachedIntervals: BusyInterval[] = [];
+  let loadedAt: number | null = null;
+
+  return {
+    async isBusy(now = Date.now()): Promise<boolean> {
+      if (loadedAt === null || now - loadedAt >= refreshIntervalMs) {
+        try {
+          cachedIntervals = parseIcsBusyIntervals(await loadCalendarText(), {
+            fromMs: now - EXPANSION_WINDOW_MS,
+            toMs: now + EXPANSION_WINDOW_MS,
+          });
+        } catch (error) {
+          getLogger().warn("Failed to refresh calendar busy times", {
+            source: options.source,
+            error,
+          });
+        }
+
+        loadedAt = now;
+      }
+
+      return cachedIntervals.some((interval) => interval.startMs <= now && now < interval.endMs);
+    },
+  };
+
+  async function loadCalendarText(): Promise<string> {
+    if (!/^https?:\/\//i.test(options.source)) {
+      return readTextFile(options.source);
+    }
+
+    const response = await fetchImpl(options.source);
+
+    if (!response.ok) {
+      throw new CalendarBusyError(`Calendar request failed with status ${response.status}`);
+    }
+
+    return response.text();
+  }
+}
+
+// Supports the subset of RFC 5545 that meeting calendars actually export: single events plus
+// DAILY/WEEKLY recurrences with INTERVAL, COUNT, UNTIL, BYDAY, EXDATE, and RECURRENCE-ID
+// overrides. TZID-qualified times are converted from their IANA timezone, or the one a Windows
+// zone name maps to; events in a timezone this runtime doesn't know are skipped.
+export function parseIcsBusyIntervals(
+  text: string,
+  window: { fromMs: number; toMs: number },
+): BusyInterval[] {
+  const events: IcsProperty[][] = [];
+  let eventProperties: IcsProperty[] | null = null;
+
+  for (const line of unfoldIcsLines(text)) {
+    if (line === "BEGIN:VEVENT") {
+      eventProperties = [];
+    } else if (line === "END:VEVENT") {
+      if (eventProperties !== null) {
+        events.push(eventProperties);
+      }
+
+      eventProperties = null;
+    } else if (eventProperties !== null) {
+      const property = parseIcsProperty(line);
+
+      if (property !== null) {
+        eventProperties.push(property);
+      }
+    }
+  }
+
+  // Moved or cancelled occurrences of a recurring event come as separate VEVENTs sharing its UID,
+  // with RECURRENCE-ID naming the occurrence they replace.
+  const overriddenStarts = new Map<string, Set<number>>();
+
+  for (const properties of events) {
+    const uid = findIcsProperty(properties, "UID")?.value;
+    const recurrenceId = findIcsProperty(properties, "RECURRENCE-ID");
+
+    if (uid !== undefined && recurrenceId !== undefined) {
+      const starts = overriddenStarts.get(uid) ?? new Set<number>();
+      starts.add(parseIcsDateTime(recurrenceId));
+      overriddenStarts.set(uid, starts);
+    }
+  }
+
+  return events
+    .flatMap((properties) => {
+      const uid = findIcsProperty(properties, "UID")?.value;
+      const isOverride = findIcsProperty(properties, "RECURRENCE-ID") !== undefined;
+
+      return expandBusyEvent(
+        properties,
+        window,
+        uid === undefined || isOverride ? undefined : overriddenStarts.get(uid),
+      );
+    })
+    .sort((left, right) => left.startMs - right.startMs);
+}
+
+function expandBusyEvent(
+  properties: readonly IcsProperty[],
+  window: { fromMs: number; toMs: number },
+  overriddenStarts: ReadonlySet<number> = new Set(),
+): BusyInterval[] {
+  const findProperty = (name: string) => findIcsProperty(properties, name);
+  const start = findProperty("DTSTART");
+
+  if (
+    start === undefined ||
+    findProperty("TRANSP")?.value === "TRANSPARENT" ||
+    findProperty("STATUS")?.value === "CANCELLED" ||
+    findProperty("X-MICROSOFT-CDO-BUSYSTATUS")?.value === "FREE"
+  ) {
+    return [];
+  }
+
+  const startDateTime = readIcsDateTime(start);
+  const startMs = startDateTime === null ? Number.NaN : resolveIcsDateTime(startDateTime);
+  const end = findProperty("DTEND");
+  const endMs = end === undefined
+    ? startMs + (start.params.VALUE === "DATE" ? DAY_MS : 0)
+    : parseIcsDateTime(end);
+
+  if (startDateTime === null || Number.isNaN(startMs) || Number.isNaN(endMs) || endMs <= startMs) {
+    return [];
+  }
+
+  const durationMs = endMs - startMs;
+  const excludedStarts = new Set([
+    ...overriddenStarts,
+    ...properties
+      .filter((property) => property.name === "EXDATE")
+      .flatMap((property) =>
+        property.value.split(",").map((value) => parseIcsDateTime({ ...property, value })),
+      ),
+  ]);
+  const occurrenceStarts = expandRecurrence(
+    startDateTime,
+    findProperty("RRULE")?.value,
+    window.toMs,
+  );
+
+  return occurrenceStarts
+    .filter((occurrenceStartMs) => !excludedStarts.has(occurrenceStartMs))
+    .map((occurrenceStartMs) => ({
+      startMs: occurrenceStartMs,
+      endMs: occurrenceStartMs + durationMs,
+    }))
+    .filter((interval) => interval.endMs > window.fromMs && interval.startMs < window.toMs);
+}
+
+function expandRecurrence(
+  start: IcsDateTime,
+  rule: string | undefined,
+  untilMs: number,
+): number[] {
+  const startMs = resolveIcsDateTime(start);
+
+  if (rule === undefined) {
+    return [startMs];
+  }
+
+  const parts = new Map(
+    rule.split(";").map((part) => {
+      const [key = "", value = ""] = part.split("=");
+      return [key.toUpperCase(), value] as const;
+    }),
+  );
+  const frequency = parts.get("FREQ");
+
+  if (frequency !== "DAILY" && frequency !== "WEEKLY") {
+    return [startMs];
+  }
+
+  const interval = Math.max(1, Number(parts.get("INTERVAL") ?? 1) || 1);
+  const count = parts.has("COUNT") ? Number(parts.get("COUNT")) : Number.POSITIVE_INFINITY;
+  const ruleUntil = parts.get("UNTIL");
+  const lastStartMs = Math.min(
+    untilMs,
+    ruleUntil === undefined
+      ? Number.POSITIVE_INFINITY
+      : parseIcsDateTime({ name: "UNTIL", params: {}, value: ruleUntil }),
+  );
+  const byDay = new Set(
+    (parts.get("BYDAY") ?? "")
+      .split(",")
+      .map((value) => value.slice(-2))
+      .filter((value) => value.length === 2),
+  );
+  // Weekly intervals count weeks starting on WKST (Monday by default), not seven-day spans from
+  // DTSTART, so a Wednesday start still shares its week with the Monday before it.
+  const ruleWeekStart = WEEKDAY_CODES.findIndex((code) => code === parts.get("WKST"));
+  const weekStart = ruleWeekStart === -1 ? WEEKDAY_CODES.indexOf("MO") : ruleWeekStart;
+  const startDayOfWeek = (readIcsWeekday(start, 0) - weekStart + 7) % 7;
+  const starts: number[] = [];
+  const stepDays = frequency === "DAILY" ? interval : 1;
+
+  for (let dayOffset = 0; starts.length < count; dayOffset += stepDays) {
+    // Step in calendar days so the event's wall-clock time survives DST changes.
+    const occurrenceMs = resolveIcsDateTime(start, dayOffset);
+
+    if (Number.isNaN(occurrenceMs) || occurrenceMs > lastStartMs) {
+      break;
+    }
+
+    if (frequency === "WEEKLY") {
+      const weekIndex = Math.floor((startDayOfWeek + dayOffset) / 7);
+      const weekday = WEEKDAY_CODES[readIcsWeekday(start, dayOffset)];
+      const matchesDay =
+        byDay.size === 0 ? dayOffset % 7 === 0 : weekday !== undefined && byDay.has(weekday);
+
+      if (weekIndex % interval !== 0 || !matchesDay) {
+        continue;
+      }
+    }
+
+    starts.push(occurrenceMs);
+  }
+
+  return starts;
+}
+
+function parseIcsDateTime(property: IcsProperty): number {
+  const dateTime = readIcsDateTime(property);
+  return dateTime === null ? Number.NaN : resolveIcsDateTime(dateTime);
+}
+
+function readIcsDateTime(property: IcsProperty): IcsDateTime | null {
+  const match = property.value.match(/^(\d{4})(\d{2})(\d{2})(?:T(\d{2})(\d{2})(\d{2})(Z)?)?$/);
+
+  if (!match) {
+    return null;
+  }
+
+  const [, year, month, day, hour = "0", minute = "0", second = "0", utc] = match;
+  const timeZone = utc === "Z" ? "UTC" : resolveIcsTimeZone(property.params.TZID);
+
+  if (timeZone === undefined) {
+    return null;
+  }
+
+  return {
+    year: Number(year),
+    month: Number(month),
+    day: Number(day),
+    hour: Number(hour),
+    minute: Number(minute),
+    second: Number(second),
+    timeZone,
+  };
+}
+
+// Undefined when the zone is unknown, so the time can't be placed.
+function resolveIcsTimeZone(tzid: string | undefined): string | null | undefined {
+  if (tzid === undefined) {
+    return null;
+  }
+
+  if (readTimeZoneFormatter(tzid) !== null) {
+    return tzid;
+  }
+
+  const ianaTimeZone = readIanaTimeZoneForWindowsZone(tzid);
+
+  if (ianaTimeZone !== null && readTimeZoneFormatter(ianaTimeZone) !== null) {
+    return ianaTimeZone;
+  }
+
+  if (!warnedUnknownTimeZones.has(tzid)) {
+    warnedUnknownTimeZones.add(tzid);
+    getLogger().warn("Skipping calendar events in an unknown timezone", { timeZone: tzid });
+  }
+
+  return undefined;
+}
+
+function resolveIcsDateTime(dateTime: IcsDateTime, dayOffset = 0): number {
+  const { year, month, day, hour, minute, second, timeZone } = dateTime;
+
+  if (timeZone === null) {
+    return new Date(year, month - 1, day + dayOffset, hour, minute, second).getTime();
+  }
+
+  const wallClockMs = Date.UTC(year, month - 1, day + dayOffset, hour, minute, second);
+
+  if (timeZone === "UTC") {
+    return wallClockMs;
+  }
+
+  // The zone's offset at the wall-clock time read as UTC is right except within hours of a DST
+  // change, which the second pass settles.
+  const firstGuessMs = wallClockMs - readTimeZoneOffsetMs(wallClockMs, timeZone);
+  return wallClockMs - readTimeZoneOffsetMs(firstGuessMs, timeZone);
+}
+
+function readIcsWeekday(dateTime: IcsDateTime, dayOffset: number): number {
+  const { year, month, day } = dateTime;
+  return new Date(Date.UTC(year, month - 1, day + dayOffset)).getUTCDay();
+}
+
+function readTimeZoneOffsetMs(epochMs: number, timeZone: string): number {
+  const formatter = readTimeZoneFormatter(timeZone);
+
+  if (formatter === null) {
+    return Number.NaN;
+  }
+
+  const parts = formatter.formatToParts(new Date(epochMs));
+  const readPart = (type: Intl.DateTimeFormatPartTypes) =>
+    Number(parts.find((part) => part.type === type)?.value);
+  const wallClockMs = Date.UTC(
+    readPart("year"),
+    readPart("month") - 1,
+    readPart("day"),
+    readPart("hour"),
+    readPart("minute"),
+    readPart("second"),
+  );
+
+  return wallClockMs - Math.floor(epochMs / 1000) * 1000;
+}
+
+function readTimeZoneFormatter(timeZone: string): Intl.DateTimeFormat | null {
+  if (!timeZoneFormatters.has(timeZone)) {
+    try {
+      timeZoneFormatters.set(
+        timeZone,
+        new Intl.DateTimeFormat("en-US", {
+          timeZone,
+          hourCycle: "h23",
+          year: "numeric",
+          month: "numeric",
+          day: "numeric",
+          hour: "numeric",
+          minute: "numeric",
+          second: "numeric",
+        }),
+      );
+    } catch {
+      timeZoneFormatters.set(timeZone, null);
+    }
+  }
+
+  return timeZoneFormatters.get(timeZone) ?? null;
+}
+
+function findIcsProperty(
+  properties: readonly IcsProperty[],
+  name: string,
+): IcsProperty | undefined {
+  return properties.find((property) => property.name === name);
+}
+
+// Parameter values may be quoted, e.g. TZID="Europe/Berlin" or ALTREP="https://…", and quoted
+// values can hold ";", ":" and ",", so the line is split outside quotes only.
+function parseIcsProperty(line: string): IcsProperty | null {
+  const segments: string[] = [];
+  let segmentStart = 0;
+  let isQuoted = false;
+  let separatorIndex = -1;
+
+  for (let index = 0; index < line.length && separatorIndex === -1; index += 1) {
+    const character = line[index];
+
+    if (character === '"') {
+      isQuoted = !isQuoted;
+    } else if (!isQuoted && (character === ";" || character === ":")) {
+      segments.push(line.slice(segmentStart, index));
+      segmentStart = index + 1;
+
+      if (character === ":") {
+        separatorIndex = index;
+      }
+    }
+  }
+
+  const [name = "", ...paramParts] = segments;
+
+  if (separatorIndex <= 0 || name.length === 0) {
+    return null;
+  }
+
+  const params: Record<string, string> = {};
+
+  for (const paramPart of paramParts) {
+    const equalsIndex = paramPart.indexOf("=");
+
+    if (equalsIndex > 0) {
+      params[paramPart.slice(0, equalsIndex).toUpperCase()] = paramPart
+        .slice(equalsIndex + 1)
+        .replace(/^"(.*)"$/, "$1");
+    }
+  }
+
+  return {
+    name: name.toUpperCase(),
+    params,
+    value: line.slice(separatorIndex + 1).trim(),
+  };
+}
+
+function unfoldIcsLines(text: string): string[] {
+  return text
+    .replace(/\r\n/g, "\n")
+    .replace(/\n[ \t]/g, "")
+    .split("\n")
+    .map((line) => line.trimEnd())
+    .filter((line) => line.length > 0);
+}
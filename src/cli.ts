@@ -0,0 +1,456 @@
+import { existsSync } from "node:fs";
+import { DatabaseSync } from "node:sqlite";
+
+import { runConfigCommand } from "./config-command.js";
+import {
+  assertMinimumPollInterval,
+  isConfigProfileName,
+  loadConfig,
+  MIN_TRACKED_PULL_REQUEST_POLL_MS,
+  parseDuration,
+  resolveAppPaths,
+  type AppConfig,
+  type AppPaths,
+} from "./config.js";
+import { initializeDatabase } from "./database.js";
+import { createConfiguredGitHubClient } from "./github.js";
+import { openHistoryWindow } from "./history-window.js";
+import { createConsoleInitPrompter, runInitCommand } from "./init-command.js";
+import { LinuxNotificationAdapter } from "./linux-notification-adapter.js";
+import { isLogFormat, isLogLevel, type LogFormat, type LogLevel } from "./logger.js";
+import { runLoginCommand } from "./login-command.js";
+import { runExportCommand } from "./notification-export.js";
+import { runSearchCommand } from "./notification-search.js";
+import { withNotificationSourceLabel } from "./notification-source-label.js";
+import { runStatsCommand } from "./notification-stats.js";
+import { runProtocolHandler } from "./protocol-handler.js";
+import { runSelfUpdate } from "./self-update.js";
+import { DEFAULT_SERVER_HOST } from "./server.js";
+import {
+  COMPLETION_SHELLS,
+  isCompletionShell,
+  renderManPage,
+  renderShellCompletions,
+  type CommandLineReference,
+} from "./shell-completions.js";
+import { runShortcutCommand } from "./shortcut-command.js";
+import {
+  createGitHubSubscriptionsClient,
+  runSubscriptionsCommand,
+} from "./subscriptions-command.js";
+import { runTestNotificationCommand } from "./test-notification-command.js";
+
+const SUBCOMMANDS = [
+  "run",
+  "init",
+  "login",
+  "config",
+  "search",
+  "stats",
+  "export",
+  "subs",
+  "shortcut",
+  "window",
+  "protocol",
+  "self-update",
+  "test-notification",
+  "completions",
+  "manpage",
+] as const;
+// Commands that reach GitHub or the release server, and so need the configured proxy and CA
+// certificates in place before Node starts. init and config validate check the token with GitHub.
+const NETWORK_SUBCOMMANDS: readonly CliSubcommand[] = [
+  "run",
+  "init",
+  "login",
+  "config",
+  "subs",
+  "self-update",
+];
+const CLI_USAGE = [
+  "Usage: octopulse [options] [command] [command args]",
+  "",
+  "Commands:",
+  "  run                      Run the notifier (default)",
+  "  init                     Set up a config file and autostart interactively",
+  "  login                    Sign in to GitHub in the browser instead of using a token",
+  "  config validate          Check the config file, the files it names, and the GitHub token",
+  '  search "<text>"          Search notification history',
+  "  stats                    Show notification statistics",
+  "  export                   Export notification history",
+  "  subs                     List or remove GitHub subscriptions",
+  "  shortcut <action>        Ask the running notifier to open or pause notifications",
+  "  window [inbox]           Open notification history or the inbox in an app window",
+  "  protocol <url>           Handle an octopulse:// link, as the desktop entry does",
+  "  self-update [--check]    Check for, or install, a newer Octopulse release",
+  "  test-notification        Show sample popups to check notifications, icons, and sounds",
+  "  completions <shell>      Print bash, zsh, or fish completions",
+  "  manpage                  Print the octopulse(1) man page",
+  "",
+  "Options:",
+  "  --config <path>          Config file (default: ~/.config/octopulse/config.toml)",
+  "  --profile <name>         Use the [profiles.<name>] sections of the config file",
+  "  --token-file <path>      Read the GitHub token from a file instead of github.token",
+  '  --poll-interval <dur>    Tracked pull request poll interval, e.g. "30s" or "2m" (min 15s)',
+  "  --log-level <level>      debug, info, warn, or error, for both console and file logs",
+  "  --log-format <format>    Console log format: text or json",
+  "  --no-log-file            Log to the console only",
+  "  --once                   Poll GitHub once and exit: 0 if notifications were shown,",
+  "                           3 if there was nothing new, 1 if polling failed",
+  "  --dry-run                Log notifications instead of showing them and save no state",
+  "  --all-notifications      Track pull requests from all github.com notifications, including",
+  "                           watched repositories (notifications.github_inbox = \"all\")",
+  "  -h, --help               Show this help",
+].join("\n");
+
+export type CliSubcommand = (typeof SUBCOMMANDS)[number];
+
+// One command or option line of CLI_USAGE, with its wrapped continuation lines joined.
+interface UsageEntry {
+  term: string;
+  description: string;
+}
+
+export interface CommandLineOptions {
+  configPath?: string;
+  profile?: string;
+  githubTokenFilePath?: string;
+  trackedPullRequestPollMs?: number;
+  logLevel?: LogLevel;
+  logFormat?: LogFormat;
+  noLogFile?: boolean;
+  once?: boolean;
+  dryRun?: boolean;
+  allNotifications?: boolean;
+}
+
+export type CommandLine =
+  | { command: "help" }
+  | { command: CliSubcommand; options: CommandLineOptions; args: string[] };
+
+export class CommandLineError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "CommandLineError";
+  }
+}
+
+// Global options come before the command; everything after it is handed to the command as is.
+export function parseCommandLine(args: readonly string[]): CommandLine {
+  const options: CommandLineOptions = {};
+
+  for (let index = 0; index < args.length; index += 1) {
+    const arg = args[index]!;
+
+    if (arg === "-h" || arg === "--help") {
+      return { command: "help" };
+    }
+
+    if (!arg.startsWith("-")) {
+      if (!isCliSubcommand(arg)) {
+        throw new CommandLineError(`Unknown command "${arg}"\n\n${CLI_USAGE}`);
+      }
+
+      if (arg !== "run") {
+        const runOnlyFlag = options.once ? "--once" : options.dryRun ? "--dry-run" : undefined;
+
+        if (runOnlyFlag !== undefined) {
+          throw new CommandLineError(`${runOnlyFlag} only applies to the run command`);
+        }
+      }
+
+      return { command: arg, options, args: args.slice(index + 1) };
+    }
+
+    if (arg === "--once") {
+      options.once = true;
+      continue;
+    }
+
+    if (arg === "--dry-run") {
+      options.dryRun = true;
+      continue;
+    }
+
+    if (arg === "--no-log-file") {
+      options.noLogFile = true;
+      continue;
+    }
+
+    if (arg === "--all-notifications") {
+      options.allNotifications = true;
+      continue;
+    }
+
+    const [flag, inlineValue] = splitFlag(arg);
+    const value = inlineValue ?? args[index + 1];
+
+    if (value === undefined || (inlineValue === undefined && value.startsWith("-"))) {
+      throw new CommandLineError(`${flag} needs a value\n\n${CLI_USAGE}`);
+    }
+
+    if (inlineValue === undefined) {
+      index += 1;
+    }
+
+    switch (flag) {
+      case "--config":
+        options.configPath = value;
+        break;
+      case "--profile":
+        if (!isConfigProfileName(value)) {
+          throw new CommandLineError(`${flag} must only use letters, digits, "-", and "_"`);
+        }
+
+        options.profile = value;
+        break;
+      case "--token-file":
+        options.githubTokenFilePath = value;
+        break;
+      case "--poll-interval":
+        options.trackedPullRequestPollMs = parseCommandLinePollInterval(value, flag);
+        break;
+      case "--log-level":
+        if (!isLogLevel(value)) {
+          throw new CommandLineError(`${flag} must be one of debug, info, warn, or error`);
+        }
+
+        options.logLevel = value;
+        break;
+      case "--log-format":
+        if (!isLogFormat(value)) {
+          throw new CommandLineError(`${flag} must be one of text or json`);
+        }
+
+        options.logFormat = value;
+        break;
+      default:
+        throw new CommandLineError(`Unknown option ${flag}\n\n${CLI_USAGE}`);
+    }
+  }
+
+  return { command: "run", options, args: [] };
+}
+
+export function renderCommandLineHelp(): string {
+  return CLI_USAGE;
+}
+
+export function isNetworkCommand(command: CliSubcommand | "help"): boolean {
+  return NETWORK_SUBCOMMANDS.some((networkCommand) => networkCommand === command);
+}
+
+// Completions and the man page are generated from SUBCOMMANDS and CLI_USAGE, so they can't drift
+// from what parseCommandLine accepts.
+export function readCommandLineReference(): CommandLineReference {
+  const commandEntries = readUsageEntries("Commands:");
+  const [synopsis = ""] = CLI_USAGE.split("\n");
+
+  return {
+    synopsis: synopsis.replace(/^Usage: /, ""),
+    commands: SUBCOMMANDS.map((name) => {
+      const entry = commandEntries.find((candidate) => candidate.term.split(" ")[0] === name);
+
+      return { name, term: entry?.term ?? name, description: entry?.description ?? "" };
+    }),
+    options: readUsageEntries("Options:"),
+  };
+}
+
+// Loads the config file named on the command line and lets flags override what it says.
+export function loadCommandLineConfig(options: CommandLineOptions): AppConfig {
+  const config = loadConfig({
+    ...(options.configPath === undefined ? {} : { configPath: options.configPath }),
+    ...(options.profile === undefined ? {} : { profile: options.profile }),
+    ...(options.githubTokenFilePath === undefined
+      ? {}
+      : { githubTokenFilePath: options.githubTokenFilePath }),
+  });
+
+  return {
+    ...config,
+    logging: {
+      ...config.logging,
+      ...(options.logLevel === undefined
+        ? {}
+        : { consoleLevel: options.logLevel, fileLevel: options.logLevel }),
+      ...(options.logFormat === undefined ? {} : { format: options.logFormat }),
+      ...(options.noLogFile ? { fileEnabled: false } : {}),
+    },
+    timings: {
+      ...config.timings,
+      ...(options.trackedPullRequestPollMs === undefined
+        ? {}
+        : { trackedPullRequestPollMs: options.trackedPullRequestPollMs }),
+    },
+    notifications: {
+      ...config.notifications,
+      githubInbox: options.allNotifications ? "all" : config.notifications.githubInbox,
+    },
+  };
+}
+
+// Runs every command except "run", which starts the long-lived notifier in main.ts.
+export async function runSubcommand(
+  command: Exclude<CliSubcommand, "run">,
+  options: CommandLineOptions,
+  args: readonly string[],
+): Promise<string> {
+  switch (command) {
+    case "init":
+      return runInitCommand({
+        configPath: resolveCommandLinePaths(options).configPath,
+        prompter: createConsoleInitPrompter(),
+      });
+    case "login":
+      return runLoginCommand(args, {
+        githubTokenPath: resolveCommandLinePaths(options).githubTokenPath,
+      });
+    case "config":
+      return runConfigCommand(args, {
+        configPath: resolveCommandLinePaths(options).configPath,
+        loadConfig: () => loadCommandLineConfig(options),
+      });
+    case "search":
+      return withDatabase(options, (database) => runSearchCommand(args, database));
+    case "stats":
+      return withDatabase(options, (database) => runStatsCommand(args, database));
+    case "export":
+      return withDatabase(options, (database) => runExportCommand(args, database));
+    case "subs": {
+      const config = loadCommandLineConfig(options);
+
+      return runSubscriptionsCommand(
+        args,
+        createGitHubSubscriptionsClient(createConfiguredGitHubClient(config)),
+      );
+    }
+    case "shortcut": {
+      const serverOrigin = readCommandLineServerOrigin(options);
+
+      return runShortcutCommand(args, serverOrigin === undefined ? {} : { serverOrigin });
+    }
+    case "window": {
+      const serverOrigin = readCommandLineServerOrigin(options);
+      const url = await openHistoryWindow({
+        ...(serverOrigin === undefined ? {} : { serverOrigin }),
+        ...(args[0] === "inbox" ? { page: "inbox" as const } : {}),
+      });
+
+      return `Opened ${url}`;
+    }
+    case "protocol": {
+      const serverOrigin = readCommandLineServerOrigin(options);
+
+      return runProtocolHandler(args[0] ?? "", serverOrigin === undefined ? {} : { serverOrigin });
+    }
+    case "self-update":
+      return runSelfUpdate({ checkOnly: args.includes("--check") });
+    case "completions": {
+      const [shell] = args;
+
+      if (shell === undefined || !isCompletionShell(shell)) {
+        throw new CommandLineError(`completions needs a shell: ${COMPLETION_SHELLS.join(", ")}`);
+      }
+
+      return renderShellCompletions(shell, readCommandLineReference());
+    }
+    case "manpage":
+      return renderManPage(readCommandLineReference());
+    case "test-notification": {
+      // Packagers may run this before anyone has written a config file.
+      const config = existsSync(resolveCommandLinePaths(options).configPath)
+        ? loadCommandLineConfig(options)
+        : undefined;
+
+      return runTestNotificationCommand(args, {
+        notificationDispatcher: withNotificationSourceLabel(
+          new LinuxNotificationAdapter({
+            ...(config === undefined
+              ? {}
+              : { avatarCacheMaxAgeMs: config.timings.avatarCacheTtlMs }),
+            ...(config?.notifications.bodyTemplate === undefined
+              ? {}
+              : { bodyTemplate: config.notifications.bodyTemplate }),
+          }),
+          config?.githubSourceLabel,
+        ),
+        ...(config === undefined ? {} : { notificationSounds: config.sounds }),
+      });
+    }
+  }
+}
+
+function resolveCommandLinePaths(options: CommandLineOptions): AppPaths {
+  return resolveAppPaths({
+    ...(options.configPath === undefined ? {} : { configPath: options.configPath }),
+    ...(options.profile === undefined ? {} : { profile: options.profile }),
+  });
+}
+
+// The running notifier may listen on server.port, e.g. one port per profile.
+function readCommandLineServerOrigin(options: CommandLineOptions): string | undefined {
+  if (!existsSync(resolveCommandLinePaths(options).configPath)) {
+    return undefined;
+  }
+
+  return `http://${DEFAULT_SERVER_HOST}:${loadCommandLineConfig(options).server.port}`;
+}
+
+function withDatabase(
+  options: CommandLineOptions,
+  run: (database: DatabaseSync) => string,
+): string {
+  const database = initializeDatabase(resolveCommandLinePaths(options));
+
+  try {
+    return run(database);
+  } finally {
+    database.close();
+  }
+}
+
+function splitFlag(arg: string): [string, string | undefined] {
+  const separatorIndex = arg.indexOf("=");
+
+  return separatorIndex === -1
+    ? [arg, undefined]
+    : [arg.slice(0, separatorIndex), arg.slice(separatorIndex + 1)];
+}
+
+function parseCommandLinePollInterval(value: string, flag: string): number {
+  try {
+    return assertMinimumPollInterval(
+      parseDuration(value, flag),
+      MIN_TRACKED_PULL_REQUEST_POLL_MS,
+      flag,
+    );
+  } catch (error) {
+    throw new CommandLineError(error instanceof Error ? error.message : String(error));
+  }
+}
+
+function isCliSubcommand(value: string): value is CliSubcommand {
+  return SUBCOMMANDS.some((command) => command === value);
+}
+
+function readUsageEntries(heading: "Commands:" | "Options:"): UsageEntry[] {
+  const lines = CLI_USAGE.split("\n");
+  const entries: UsageEntry[] = [];
+
+  for (const line of lines.slice(lines.indexOf(heading) + 1)) {
+    if (line.trim() === "") {
+      break;
+    }
+
+    const match = line.match(/^ {2}(\S.*?) {2,}(\S.*)$/);
+
+    if (match) {
+      entries.push({ term: match[1]!, description: match[2]! });
+    } else if (entries.length > 0) {
+      entries[entries.length - 1]!.description += ` ${line.trim()}`;
+    }
+  }
+
+  return entries;
+}
@@ -0,0 +1,308 @@
+import { readFileSync } from "node:fs";
+import http, { type ClientRequestArgs, type IncomingHttpHeaders } from "node:http";
+import https from "node:https";
+import type { Duplex } from "node:stream";
+import tls from "node:tls";
+
+import type { ClientCertificatePaths } from "./config.js";
+
+const MAX_REDIRECTS = 5;
+const REDIRECT_STATUSES = new Set([301, 302, 303, 307, 308]);
+const NULL_BODY_STATUSES = new Set([204, 205, 304]);
+
+export interface ClientCertificateFetchOptions {
+  readFile?: (filePath: string) => Buffer;
+  requestImpl?: typeof https.request;
+  env?: NodeJS.ProcessEnv;
+}
+
+export class ClientCertificateError extends Error {
+  constructor(message: string, options?: ErrorOptions) {
+    super(message, options);
+    this.name = "ClientCertificateError";
+  }
+}
+
+interface RawResponse {
+  status: number;
+  statusText: string;
+  headers: IncomingHttpHeaders;
+  body: Buffer;
+}
+
+interface EnvironmentProxy {
+  url: URL;
+  noProxy: string[];
+}
+
+// An https.Agent whose connections run through an HTTP CONNECT tunnel, so the client certificate
+// is presented to GitHub end to end and the proxy only relays the encrypted stream.
+class ProxyTunnelAgent extends https.Agent {
+  constructor(
+    private readonly proxyUrl: URL,
+    options: https.AgentOptions,
+  ) {
+    super(options);
+  }
+
+  createConnection(
+    options: ClientRequestArgs,
+    callback?: (error: Error | null, socket?: Duplex) => void,
+  ): undefined {
+    const target = `${options.host ?? "localhost"}:${options.port ?? 443}`;
+    const connectRequest = (this.proxyUrl.protocol === "https:" ? https : http).request({
+      host: this.proxyUrl.hostname.replace(/^\[|\]$/g, ""),
+      port: this.proxyUrl.port || (this.proxyUrl.protocol === "https:" ? 443 : 80),
+      method: "CONNECT",
+      path: target,
+      headers: { host: target, ...readProxyAuthorization(this.proxyUrl) },
+      agent: false,
+    });
+
+    connectRequest.once("connect", (response, socket) => {
+      if (response.statusCode !== 200) {
+        socket.destroy();
+        callback?.(
+          new ClientCertificateError(
+            `Proxy ${this.proxyUrl.host} refused the tunnel to ${target}: ${
+              response.statusCode ?? "no status"
+            } ${response.statusMessage ?? ""}`.trimEnd(),
+          ),
+        );
+        return;
+      }
+
+      callback?.(null, tls.connect({ ...(options as tls.ConnectionOptions), socket }));
+    });
+    connectRequest.once("error", (error) => {
+      callback?.(
+        new ClientCertificateError(
+          `Failed to reach proxy ${this.proxyUrl.host}: ${error.message}`,
+          { cause: error },
+        ),
+      );
+    });
+    connectRequest.end();
+
+    return undefined;
+  }
+}
+
+// Node's fetch can only present a client certificate through undici's Agent, which isn't a
+// dependency, so GitHub API requests to servers that require mutual TLS go through https.request.
+// It covers what Octokit and the GitHub App token provider use: methods, headers, string or binary
+// bodies, abort signals, and redirects. Plain http URLs use fetch as usual. When Node's own proxy
+// support is on, https requests tunnel through the same proxy unless NO_PROXY names the host.
+export function createClientCertificateFetch(
+  clientCertificate: ClientCertificatePaths,
+  options: ClientCertificateFetchOptions = {},
+): typeof fetch {
+  const readFile = options.readFile ?? ((filePath: string) => readFileSync(filePath));
+  const requestImpl = options.requestImpl ?? https.request;
+  const proxy = readEnvironmentProxy(options.env ?? process.env);
+  let directAgent: https.Agent | undefined;
+  let proxyAgent: https.Agent | undefined;
+  const selectAgent = (target: URL): https.Agent =>
+    proxy === undefined || isNoProxyHost(target, proxy.noProxy)
+      ? (directAgent ??= new https.Agent(readAgentOptions(clientCertificate, readFile)))
+      : (proxyAgent ??= new ProxyTunnelAgent(
+          proxy.url,
+          readAgentOptions(clientCertificate, readFile),
+        ));
+
+  const clientCertificateFetch = async (
+    input: string | URL | Request,
+    init?: RequestInit,
+  ): Promise<Response> => {
+    const request = new Request(input, init);
+    let url = new URL(request.url);
+
+    if (url.protocol !== "https:") {
+      return fetch(request);
+    }
+
+    let method = request.method;
+    let body =
+      method === "GET" || method === "HEAD"
+        ? undefined
+        : Buffer.from(await request.arrayBuffer());
+    const headers: Record<string, string> = {};
+
+    request.headers.forEach((value, name) => {
+      headers[name] = value;
+    });
+
+    for (let redirects = 0; ; redirects += 1) {
+      const response = await sendRequest(requestImpl, selectAgent(url), url, {
+        method,
+        headers: { ...headers },
+        body,
+        signal: request.signal,
+      });
+      const location = response.headers.location;
+
+      if (
+        !REDIRECT_STATUSES.has(response.status) ||
+        location === undefined ||
+        request.redirect === "manual"
+      ) {
+        return toResponse(response, url);
+      }
+
+      if (request.redirect === "error" || redirects >= MAX_REDIRECTS) {
+        throw new TypeError(`Too many or disallowed redirects fetching ${request.url}`);
+      }
+
+      const redirectUrl = new URL(location, url);
+
+      // Like fetch, the token isn't sent on to another host.
+      if (redirectUrl.origin !== url.origin) {
+        delete headers.authorization;
+      }
+
+      url = redirectUrl;
+
+      if (
+        (response.status === 303 && method !== "HEAD") ||
+        ((response.status === 301 || response.status === 302) && method === "POST")
+      ) {
+        method = "GET";
+        body = undefined;
+        delete headers["content-type"];
+        delete headers["content-length"];
+      }
+    }
+  };
+
+  return clientCertificateFetch as typeof fetch;
+}
+
+function readAgentOptions(
+  clientCertificate: ClientCertificatePaths,
+  readFile: (filePath: string) => Buffer,
+): https.AgentOptions {
+  const readPemFile = (filePath: string) => {
+    try {
+      return readFile(filePath);
+    } catch (error) {
+      throw new ClientCertificateError(
+        `Failed to read the client certificate file at ${filePath}: ${
+          error instanceof Error ? error.message : String(error)
+        }`,
+        { cause: error },
+      );
+    }
+  };
+
+  return {
+    keepAlive: true,
+    cert: readPemFile(clientCertificate.certificatePath),
+    key: readPemFile(clientCertificate.keyPath),
+  };
+}
+
+// Mirrors Node's NODE_USE_ENV_PROXY handling, which the network settings turn on at startup.
+function readEnvironmentProxy(env: NodeJS.ProcessEnv): EnvironmentProxy | undefined {
+  const proxy = env.HTTPS_PROXY ?? env.https_proxy;
+
+  if (env.NODE_USE_ENV_PROXY !== "1" || proxy === undefined || proxy.trim().length === 0) {
+    return undefined;
+  }
+
+  return {
+    url: new URL(proxy),
+    noProxy: (env.NO_PROXY ?? env.no_proxy ?? "")
+      .split(/[\s,]+/)
+      .map((host) => host.toLowerCase())
+      .filter((host) => host.length > 0),
+  };
+}
+
+function isNoProxyHost(url: URL, noProxy: readonly string[]): boolean {
+  const hostname = url.hostname.replace(/^\[|\]$/g, "").toLowerCase();
+
+  return noProxy.some((entry) => {
+    if (entry === "*") {
+      return true;
+    }
+
+    const hostWithPort = /^([^:]+):(\d+)$/.exec(entry);
+    const port = hostWithPort?.[2];
+    const suffix = (hostWithPort?.[1] ?? entry).replace(/^\*?\./, "");
+
+    return (
+      (port === undefined || port === (url.port || "443")) &&
+      (hostname === suffix || hostname.endsWith(`.${suffix}`))
+    );
+  });
+}
+
+function readProxyAuthorization(proxyUrl: URL): Record<string, string> {
+  if (proxyUrl.username === "") {
+    return {};
+  }
+
+  const credentials = `${decodeURIComponent(proxyUrl.username)}:${decodeURIComponent(
+    proxyUrl.password,
+  )}`;
+
+  return { "proxy-authorization": `Basic ${Buffer.from(credentials).toString("base64")}` };
+}
+
+function sendRequest(
+  requestImpl: typeof https.request,
+  agent: https.Agent,
+  url: URL,
+  options: {
+    method: string;
+    headers: Record<string, string>;
+    body: Buffer | undefined;
+    signal: AbortSignal;
+  },
+): Promise<RawResponse> {
+  return new Promise((resolve, reject) => {
+    const request = requestImpl(
+      url,
+      { method: options.method, headers: options.headers, agent, signal: options.signal },
+      (response) => {
+        const chunks: Buffer[] = [];
+
+        response.on("data", (chunk: Buffer) => {
+          chunks.push(chunk);
+        });
+        response.once("error", reject);
+        response.once("end", () => {
+          resolve({
+            status: response.statusCode ?? 502,
+            statusText: response.statusMessage ?? "",
+            headers: response.headers,
+            body: Buffer.concat(chunks),
+          });
+        });
+      },
+    );
+
+    request.once("error", reject);
+    request.end(options.body);
+  });
+}
+
+function toResponse(rawResponse: RawResponse, url: URL): Response {
+  const headers = new Headers();
+
+  for (const [name, value] of Object.entries(rawResponse.headers)) {
+    for (const entry of Array.isArray(value) ? value : value === undefined ? [] : [value]) {
+      headers.append(name, entry);
+    }
+  }
+
+  const response = new Response(
+    NULL_BODY_STATUSES.has(rawResponse.status) ? null : new Uint8Array(rawResponse.body),
+    { status: rawResponse.status, statusText: rawResponse.statusText, headers },
+  );
+
+  // Octokit reports the final URL, which a constructed Response leaves empty.
+  Object.defineProperty(response, "url", { value: url.href });
+
+  return response;
+}
@@ -67,13 +67,17 @@ export async function loadPullRequestsPageData(
 }
 
 export async function loadNotificationHistoryPageData(
-  routeState: Pick<RouteState, "uiFilters" | "activityPage">,
+  routeState: Pick<RouteState, "uiFilters" | "activityPage"> & Partial<Pick<RouteState, "currentPage">>,
   fetcher: typeof apiFetch = apiFetch,
 ): Promise<NotificationHistoryPageData> {
   const [baseData, notificationHistoryResponse] = await Promise.all([
     loadPullRequestBasePageData(fetcher),
     fetcher<{ notificationHistory: NotificationHistoryEntry[]; pagination: PaginationState }>(
-      buildActivityApiPath("/api/notification-history", routeState.uiFilters, routeState.activityPage),
+      buildActivityApiPath(
+        routeState.currentPage === "inbox" ? "/api/notification-inbox" : "/api/notification-history",
+        routeState.uiFilters,
+        routeState.activityPage,
+      ),
     ),
   ]);
 
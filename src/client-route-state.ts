@@ -13,7 +13,8 @@ import {
 
 export type LogLevel = "debug" | "info" | "warn" | "error";
 export type LogLevelFilter = "all" | LogLevel;
-export type AppPage = "pull-requests" | "logs" | "notification-history";
+export type AppPage = "pull-requests" | "inbox" | "logs" | "notification-history";
+export type ActivityPage = "inbox" | "notification-history";
 export type PrSubTab = "my-prs" | "review-requested";
 
 export interface RouteState {
@@ -27,10 +28,16 @@ export interface RouteState {
 type PageFilterField = keyof UiFilterValues;
 
 const PULL_REQUEST_FILTER_FIELDS: readonly PageFilterField[] = ["pullRequestStates", "repository"];
-const ACTIVITY_FILTER_FIELDS: readonly PageFilterField[] = ["pullRequestStates", "repository", "actorClass"];
+const ACTIVITY_FILTER_FIELDS: readonly PageFilterField[] = [
+  "pullRequestStates",
+  "repository",
+  "actorClass",
+  "search",
+];
 
 export const APP_PAGES: readonly AppPage[] = [
   "pull-requests",
+  "inbox",
   "notification-history",
   "logs",
 ];
@@ -127,7 +134,7 @@ export function buildPageHref(
 }
 
 export function buildActivityPageHref(
-  page: "notification-history",
+  page: ActivityPage,
   uiFilters: UiFilterValues,
   activityPage: number,
 ): string {
@@ -170,6 +177,10 @@ export function formatPageLabel(page: AppPage): string {
     return "Logs";
   }
 
+  if (page === "inbox") {
+    return "Inbox";
+  }
+
   if (page === "notification-history") {
     return "Notification History";
   }
@@ -230,6 +241,10 @@ function readPrSubTab(searchParams: URLSearchParams): PrSubTab {
 }
 
 function readDocumentPage(pathname: string): AppPage {
+  if (pathname === "/inbox") {
+    return "inbox";
+  }
+
   if (pathname === "/logs") {
     return "logs";
   }
@@ -283,6 +298,8 @@ function formatFilterSearchParamKey(field: PageFilterField): string {
       return "repo";
     case "actorClass":
       return "actor-type";
+    case "search":
+      return "q";
   }
 
   throw new Error(`Unsupported filter field: ${field}`);
@@ -29,6 +29,7 @@ import {
   isLogLevel,
   readRouteState,
   togglePullRequestStateSelection,
+  type ActivityPage,
   type AppPage,
   type LogLevelFilter,
   type PrSubTab,
@@ -287,7 +288,7 @@ function App() {
         return;
       }
 
-      if (routeState.currentPage === "notification-history") {
+      if (routeState.currentPage === "notification-history" || routeState.currentPage === "inbox") {
         const pageData = await loadNotificationHistoryPageData(routeState);
 
         setTrackedPullRequests(pageData.trackedPullRequests);
@@ -422,6 +423,34 @@ function App() {
     }
   }
 
+  async function handleMarkNotificationRecordRead(notificationRecordId: number): Promise<void> {
+    try {
+      await apiFetch(`/api/notification-records/${notificationRecordId}/read`, {
+        method: "POST",
+      });
+      await loadCurrentPageData(route);
+    } catch (error) {
+      setFlashMessage({
+        kind: "error",
+        text: getErrorMessage(error),
+      });
+    }
+  }
+
+  async function handleMarkNotificationRecordUnread(notificationRecordId: number): Promise<void> {
+    try {
+      await apiFetch(`/api/notification-records/${notificationRecordId}/read`, {
+        method: "DELETE",
+      });
+      await loadCurrentPageData(route);
+    } catch (error) {
+      setFlashMessage({
+        kind: "error",
+        text: getErrorMessage(error),
+      });
+    }
+  }
+
   function handleFilterChange(event: FormEvent<HTMLFormElement>): void {
     const formData = new FormData(event.currentTarget);
     const searchParams = new URLSearchParams();
@@ -508,13 +537,16 @@ function App() {
               }
             />
           ) : null}
-          {route.currentPage === "notification-history" ? (
+          {route.currentPage === "notification-history" || route.currentPage === "inbox" ? (
             <NotificationHistoryPanel
+              currentPage={route.currentPage}
               notificationHistory={filteredNotificationHistory}
               pagination={notificationHistoryPagination}
               hasActiveFilters={hasActiveFilters}
               uiFilters={route.uiFilters}
               onResend={handleResendNotificationRecord}
+              onMarkRead={handleMarkNotificationRecordRead}
+              onMarkUnread={handleMarkNotificationRecordUnread}
               onNavigate={navigateToHref}
               onRefresh={handleBaseDataRefresh}
             />
@@ -688,6 +720,39 @@ function FilterPanel({
           ) : null}
         </div>
       </form>
+      {showsActivityFilters && !showsLogFilters ? (
+        <form
+          key={`search-${formKey}`}
+          method="get"
+          action={pagePath}
+          role="search"
+          className="filters-form"
+          onSubmit={(event) => {
+            event.preventDefault();
+            const search = new FormData(event.currentTarget).get("q");
+
+            onNavigate(
+              buildPageHref(
+                currentPage,
+                { ...uiFilters, search: typeof search === "string" ? search.trim() : "" },
+                logLevelFilter,
+                prSubTab,
+              ),
+            );
+          }}
+        >
+          <label className="filter-field">
+            <span className="input-label">Search</span>
+            <input
+              type="search"
+              name="q"
+              defaultValue={uiFilters.search}
+              placeholder="Titles, bodies, and comments"
+              className="text-input"
+            />
+          </label>
+        </form>
+      ) : null}
     </section>
   );
 }
@@ -728,26 +793,32 @@ function PageNavigation({
 }
 
 function NotificationHistoryPanel({
+  currentPage,
   notificationHistory,
   pagination,
   hasActiveFilters,
   uiFilters,
   onResend,
+  onMarkRead,
+  onMarkUnread,
   onNavigate,
   onRefresh,
 }: {
+  currentPage: ActivityPage;
   notificationHistory: NotificationHistoryEntry[];
   pagination: PaginationState;
   hasActiveFilters: boolean;
   uiFilters: UiFilterValues;
   onResend: (notificationRecordId: number) => Promise<void>;
+  onMarkRead: (notificationRecordId: number) => Promise<void>;
+  onMarkUnread: (notificationRecordId: number) => Promise<void>;
   onNavigate: (href: string) => void;
   onRefresh: () => void;
 }) {
   return (
     <section className="notification-history-panel">
       <ActivityPagination
-        currentPage="notification-history"
+        currentPage={currentPage}
         uiFilters={uiFilters}
         pagination={pagination}
         onNavigate={onNavigate}
@@ -755,11 +826,7 @@ function NotificationHistoryPanel({
         position="top"
       />
       {notificationHistory.length === 0 ? (
-        <p>
-          {hasActiveFilters
-            ? "No notification history matches current filters."
-            : "No notification history yet."}
-        </p>
+        <p>{formatNotificationHistoryEmptyMessage(currentPage, hasActiveFilters)}</p>
       ) : (
         <ul className="notification-history-list">
           {notificationHistory.map((entry) => (
@@ -812,6 +879,32 @@ function NotificationHistoryPanel({
                       Delivered {formatHistoryTimestamp(entry.deliveredAt)}
                     </span>
                   ) : null}
+                  {entry.readAt ? (
+                    <>
+                      <span className="history-pill notification-history-time-pill">
+                        Read {formatHistoryTimestamp(entry.readAt)}
+                      </span>
+                      <button
+                        type="button"
+                        className="action-button small-button"
+                        onClick={() => {
+                          void onMarkUnread(entry.id);
+                        }}
+                      >
+                        Mark unread
+                      </button>
+                    </>
+                  ) : entry.deliveredAt || currentPage === "inbox" ? (
+                    <button
+                      type="button"
+                      className="action-button small-button"
+                      onClick={() => {
+                        void onMarkRead(entry.id);
+                      }}
+                    >
+                      Mark read
+                    </button>
+                  ) : null}
                   <form
                     className="notification-history-resend-form"
                     method="post"
@@ -832,7 +925,7 @@ function NotificationHistoryPanel({
         </ul>
       )}
       <ActivityPagination
-        currentPage="notification-history"
+        currentPage={currentPage}
         uiFilters={uiFilters}
         pagination={pagination}
         onNavigate={onNavigate}
@@ -850,7 +943,7 @@ function ActivityPagination({
   onRefresh,
   position = "bottom",
 }: {
-  currentPage: "notification-history";
+  currentPage: ActivityPage;
   uiFilters: UiFilterValues;
   pagination: PaginationState;
   onNavigate: (href: string) => void;
@@ -894,7 +987,12 @@ function ActivityPagination({
               </button>
             </>
           ) : null}
-          {onRefresh ? <RefreshButton label="Refresh notification history" onClick={onRefresh} /> : null}
+          {onRefresh ? (
+            <RefreshButton
+              label={currentPage === "inbox" ? "Refresh inbox" : "Refresh notification history"}
+              onClick={onRefresh}
+            />
+          ) : null}
         </div>
       ) : null}
     </div>
@@ -2000,6 +2098,14 @@ function formatTrackingStateLabel(pullRequest: PullRequestRecord): string {
   return pullRequest.isTracked ? "Tracked" : "Untracked";
 }
 
+function formatNotificationHistoryEmptyMessage(page: ActivityPage, hasActiveFilters: boolean): string {
+  if (page === "inbox") {
+    return hasActiveFilters ? "No unread notifications match current filters." : "No unread notifications.";
+  }
+
+  return hasActiveFilters ? "No notification history matches current filters." : "No notification history yet.";
+}
+
 function formatNotificationHistoryHeaderText(entry: NotificationHistoryEntry): string {
   if (entry.pullRequestStateAssetUrlPath === null) {
     return entry.markupHeaderText;
@@ -0,0 +1,184 @@
+import { DatabaseSync } from "node:sqlite";
+
+import { Octokit } from "octokit";
+
+import { compilePathPattern } from "./path-patterns.js";
+
+const GITHUB_API_HEADERS = {
+  "X-GitHub-Api-Version": "2022-11-28",
+};
+const CODEOWNERS_CACHE_TTL_MS = 60 * 60 * 1000;
+const KEY_PREFIX = "codeowners";
+// The locations GitHub itself checks, in the order it checks them.
+const CODEOWNERS_PATHS = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"] as const;
+
+export interface CodeownersRule {
+  pattern: string;
+  owners: string[];
+}
+
+export class CodeownersError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "CodeownersError";
+  }
+}
+
+export function parseCodeowners(text: string): CodeownersRule[] {
+  const rules: CodeownersRule[] = [];
+
+  for (const line of text.split(/\r?\n/)) {
+    const content = line.replace(/(^|\s)#.*$/, "").trim();
+
+    if (content.length === 0) {
+      continue;
+    }
+
+    const [pattern, ...owners] = content.split(/\s+/);
+
+    if (pattern !== undefined) {
+      rules.push({ pattern, owners });
+    }
+  }
+
+  return rules;
+}
+
+// The last matching rule wins, so a later pattern with no owners un-owns a path.
+export function findCodeOwners(rules: readonly CodeownersRule[], filePath: string): string[] {
+  for (let index = rules.length - 1; index >= 0; index -= 1) {
+    const rule = rules[index];
+
+    if (rule !== undefined && compilePathPattern(rule.pattern).test(filePath)) {
+      return rule.owners;
+    }
+  }
+
+  return [];
+}
+
+// `owners` are "@login" or "@org/team" handles; comparison is case-insensitive like GitHub's.
+export function touchesOwnedPaths(
+  rules: readonly CodeownersRule[],
+  filePaths: readonly string[],
+  owners: readonly string[],
+): boolean {
+  const ownerHandles = new Set(owners.map((owner) => owner.toLowerCase()));
+
+  return filePaths.some((filePath) =>
+    findCodeOwners(rules, filePath).some((owner) => ownerHandles.has(owner.toLowerCase())),
+  );
+}
+
+export class CodeownersCache {
+  constructor(
+    private readonly database: DatabaseSync,
+    private readonly client: Octokit,
+  ) {}
+
+  async getRules(owner: string, repo: string, branch: string): Promise<CodeownersRule[] | null> {
+    const key = `${KEY_PREFIX}:${owner}/${repo}:${branch}`;
+    const cached = this.readCached(key);
+
+    if (cached !== undefined) {
+      return cached === null ? null : parseCodeowners(cached);
+    }
+
+    const text = await this.fetchFromGitHub(owner, repo, branch);
+    this.writeCache(key, text);
+    return text === null ? null : parseCodeowners(text);
+  }
+
+  private readCached(key: string): string | null | undefined {
+    const row = this.database
+      .prepare("SELECT value, updated_at FROM AppState WHERE key = ?")
+      .get(key);
+
+    if (row === undefined) {
+      return undefined;
+    }
+
+    const value = row as Record<string, unknown>;
+    const updatedAt = typeof value.updated_at === "string" ? value.updated_at : null;
+
+    if (updatedAt !== null) {
+      const age = Date.now() - new Date(updatedAt).getTime();
+
+      if (age > CODEOWNERS_CACHE_TTL_MS) {
+        return undefined;
+      }
+    }
+
+    if (typeof value.value !== "string") {
+      return undefined;
+    }
+
+    try {
+      const parsed = JSON.parse(value.value) as unknown;
+      return typeof parsed === "string" || parsed === null ? parsed : undefined;
+    } catch {
+      return undefined;
+    }
+  }
+
+  private writeCache(key: string, text: string | null): void {
+    this.database
+      .prepare(
+        `
+          INSERT INTO AppState (key, value)
+          VALUES (?, ?)
+          ON CONFLICT(key) DO UPDATE SET
+            value = excluded.value,
+            updated_at = CURRENT_TIMESTAMP
+        `,
+      )
+      .run(key, JSON.stringify(text));
+  }
+
+  private async fetchFromGitHub(owner: string, repo: string, branch: string): Promise<string | null> {
+    for (const path of CODEOWNERS_PATHS) {
+      try {
+        const response = await this.client.request("GET /repos/{owner}/{repo}/contents/{path}", {
+          owner,
+          repo,
+          path,
+          ref: branch,
+          headers: GITHUB_API_HEADERS,
+        });
+
+        return decodeFileContent(response.data as unknown, path);
+      } catch (error) {
+        if (isNotFoundError(error)) {
+          continue;
+        }
+
+        throw error;
+      }
+    }
+
+    return null;
+  }
+}
+
+function decodeFileContent(data: unknown, path: string): string {
+  if (typeof data !== "object" || data === null || Array.isArray(data)) {
+    throw new CodeownersError(`${path} must be a file`);
+  }
+
+  const file = data as Record<string, unknown>;
+
+  if (typeof file.content !== "string" || file.encoding !== "base64") {
+    throw new CodeownersError(`${path} must have base64-encoded content`);
+  }
+
+  return Buffer.from(file.content, "base64").toString("utf8");
+}
+
+function isNotFoundError(error: unknown): boolean {
+  if (typeof error !== "object" || error === null) {
+    return false;
+  }
+
+  const status = (error as Record<string, unknown>).status;
+  return status === 404;
+}
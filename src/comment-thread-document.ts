@@ -0,0 +1,163 @@
+import type { NormalizedEventRecord } from "./normalized-event-repository.js";
+import type { PullRequestRecord } from "./pull-request-repository.js";
+
+const COMMENT_EVENT_TYPES = new Set([
+  "issue_comment",
+  "review_inline_comment",
+  "review_submitted",
+  "review_approved",
+  "review_changes_requested",
+]);
+// Most notification daemons clip bodies well before this; past it the popup can't show the thread.
+const COMMENT_THREAD_VIEW_MIN_COMMENTS = 3;
+const COMMENT_THREAD_VIEW_MIN_TEXT_LENGTH = 300;
+
+type CommentThreadPullRequest = Pick<
+  PullRequestRecord,
+  "repositoryOwner" | "repositoryName" | "number" | "title" | "url"
+>;
+
+type CommentThreadEvent = Pick<
+  NormalizedEventRecord,
+  "actorLogin" | "eventType" | "occurredAt" | "payloadJson"
+>;
+
+interface CommentThreadEntry {
+  actorLogin: string | null;
+  actorAvatarUrl: string | null;
+  occurredAt: string;
+  bodyText: string;
+}
+
+export function shouldOfferCommentThreadView(events: readonly CommentThreadEvent[]): boolean {
+  const entries = readCommentThreadEntries(events);
+  const totalTextLength = entries.reduce((total, entry) => total + entry.bodyText.length, 0);
+
+  return (
+    entries.length >= COMMENT_THREAD_VIEW_MIN_COMMENTS ||
+    totalTextLength >= COMMENT_THREAD_VIEW_MIN_TEXT_LENGTH
+  );
+}
+
+export function renderCommentThreadDocument(
+  pullRequest: CommentThreadPullRequest,
+  events: readonly CommentThreadEvent[],
+): string {
+  const heading = `${pullRequest.repositoryOwner}/${pullRequest.repositoryName} #${pullRequest.number} ${pullRequest.title}`;
+  const articles = readCommentThreadEntries(events).map((entry) =>
+    [
+      "<article>",
+      "<header>",
+      entry.actorAvatarUrl === null
+        ? ""
+        : `<img src="${escapeHtml(entry.actorAvatarUrl)}" alt="" width="24" height="24" />`,
+      `<strong>${escapeHtml(entry.actorLogin ?? "unknown")}</strong>`,
+      `<time datetime="${escapeHtml(entry.occurredAt)}">${escapeHtml(entry.occurredAt)}</time>`,
+      "</header>",
+      renderCommentMarkdown(entry.bodyText),
+      "</article>",
+    ].join(""),
+  );
+
+  return [
+    "<!doctype html>",
+    '<html lang="en">',
+    "<head>",
+    '<meta charset="utf-8" />',
+    `<title>${escapeHtml(heading)}</title>`,
+    "<style>",
+    "body{font-family:system-ui,sans-serif;max-width:48rem;margin:1.5rem auto;padding:0 1rem;line-height:1.5}",
+    "article{border-top:1px solid #d0d7de;padding:0.75rem 0}",
+    "header{display:flex;align-items:center;gap:0.5rem;color:#57606a}",
+    "header img{border-radius:50%}",
+    "pre{background:#f6f8fa;padding:0.5rem;overflow:auto}",
+    "code{background:#f6f8fa;padding:0 0.2rem}",
+    "</style>",
+    "</head>",
+    "<body>",
+    `<h1><a href="${escapeHtml(pullRequest.url)}">${escapeHtml(heading)}</a></h1>`,
+    ...articles,
+    "</body>",
+    "</html>",
+    "",
+  ].join("\n");
+}
+
+function readCommentThreadEntries(events: readonly CommentThreadEvent[]): CommentThreadEntry[] {
+  return events.flatMap((event) => {
+    if (!COMMENT_EVENT_TYPES.has(event.eventType)) {
+      return [];
+    }
+
+    const payload = parsePayload(event.payloadJson);
+    const bodyText = typeof payload?.bodyText === "string" ? payload.bodyText.trim() : "";
+
+    if (bodyText.length === 0) {
+      return [];
+    }
+
+    return [
+      {
+        actorLogin: event.actorLogin,
+        actorAvatarUrl:
+          typeof payload?.actorAvatarUrl === "string" && payload.actorAvatarUrl.length > 0
+            ? payload.actorAvatarUrl
+            : null,
+        occurredAt: event.occurredAt,
+        bodyText,
+      },
+    ];
+  });
+}
+
+// Covers the markdown GitHub comments lean on most: fenced code, inline code, bold, and links.
+function renderCommentMarkdown(bodyText: string): string {
+  return `\n${bodyText}`
+    .replace(/\r\n?/g, "\n")
+    .split(/\n(`{3,})[^\n]*\n([\s\S]*?)\n\1(?=\n|$)/)
+    .map((segment, index) => {
+      // split() interleaves [text, fence, code, text, ...].
+      if (index % 3 === 1) {
+        return "";
+      }
+
+      if (index % 3 === 2) {
+        return `<pre><code>${escapeHtml(segment)}</code></pre>`;
+      }
+
+      return segment
+        .split(/\n{2,}/)
+        .filter((paragraph) => paragraph.trim().length > 0)
+        .map((paragraph) => `<p>${renderInlineMarkdown(paragraph).replaceAll("\n", "<br />")}</p>`)
+        .join("");
+    })
+    .join("");
+}
+
+function renderInlineMarkdown(text: string): string {
+  return escapeHtml(text)
+    .replace(/`([^`]+)`/g, "<code>$1</code>")
+    .replace(/\*\*([^*]+)\*\*/g, "<strong>$1</strong>")
+    .replace(/\[([^\]]+)\]\((https?:\/\/[^)\s]+)\)/g, '<a href="$2">$1</a>');
+}
+
+function escapeHtml(value: string): string {
+  return value
+    .replaceAll("&", "&amp;")
+    .replaceAll("<", "&lt;")
+    .replaceAll(">", "&gt;")
+    .replaceAll('"', "&quot;")
+    .replaceAll("'", "&#39;");
+}
+
+function parsePayload(payloadJson: string): Record<string, unknown> | null {
+  try {
+    const parsed = JSON.parse(payloadJson) as unknown;
+
+    return typeof parsed === "object" && parsed !== null && !Array.isArray(parsed)
+      ? (parsed as Record<string, unknown>)
+      : null;
+  } catch {
+    return null;
+  }
+}
@@ -0,0 +1,202 @@
+import { statSync } from "node:fs";
+
+import type { AppConfig } from "./config.js";
+import {
+  checkGitHubConnectivity,
+  findMissingTokenScopes,
+  formatMissingTokenScopesWarning,
+  initializeGitHubAuth,
+  listRequiredTokenScopes,
+  type GitHubAuthContext,
+} from "./github.js";
+
+const CONFIG_USAGE = "Usage: octopulse config validate";
+
+type ConfigCheckStatus = "ok" | "warn" | "error";
+
+interface ConfigCheck {
+  status: ConfigCheckStatus;
+  message: string;
+}
+
+export interface RunConfigCommandOptions {
+  configPath: string;
+  loadConfig: () => AppConfig;
+  checkGitHubAuth?: (
+    config: AppConfig,
+  ) => Promise<Pick<GitHubAuthContext<unknown>, "currentUserLogin" | "tokenScopes">>;
+}
+
+export class ConfigCommandError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "ConfigCommandError";
+  }
+}
+
+// Checks what loading the config can't: that the files it points at exist and that GitHub
+// accepts the token, so mistakes show up here instead of as silent popups or a failing service.
+export async function runConfigCommand(
+  args: readonly string[],
+  options: RunConfigCommandOptions,
+): Promise<string> {
+  if (args.length !== 1 || args[0] !== "validate") {
+    throw new ConfigCommandError(CONFIG_USAGE);
+  }
+
+  let config: AppConfig;
+
+  try {
+    config = options.loadConfig();
+  } catch (error) {
+    throw new ConfigCommandError(
+      formatConfigReport(options.configPath, [
+        { status: "error", message: error instanceof Error ? error.message : String(error) },
+      ]),
+    );
+  }
+
+  const checks: ConfigCheck[] = [
+    { status: "ok", message: "Config file parses" },
+    ...listConfiguredFiles(config).map(checkConfiguredFile),
+    ...(await checkGitHubToken(config, options.checkGitHubAuth ?? defaultCheckGitHubAuth)),
+  ];
+  const report = formatConfigReport(options.configPath, checks);
+
+  if (checks.some((check) => check.status === "error")) {
+    throw new ConfigCommandError(report);
+  }
+
+  return report;
+}
+
+function listConfiguredFiles(config: AppConfig): { fieldPath: string; filePath: string }[] {
+  const soundFields = [
+    ["sounds.approved", config.sounds.approved],
+    ["sounds.changes_requested", config.sounds.changesRequested],
+    ["sounds.comment", config.sounds.comment],
+  ] as const;
+
+  return [
+    ...soundFields.flatMap(([fieldPath, filePath]) =>
+      typeof filePath === "string" ? [{ fieldPath, filePath }] : [],
+    ),
+    ...config.channels.flatMap((channel, index) =>
+      channel.soundFile === undefined
+        ? []
+        : [{ fieldPath: `channels[${index}].sound`, filePath: channel.soundFile }],
+    ),
+    ...(config.githubApp === undefined
+      ? []
+      : [{ fieldPath: "github.app.private_key_path", filePath: config.githubApp.privateKeyPath }]),
+    ...(config.network.caCertificatesPath === undefined
+      ? []
+      : [
+          {
+            fieldPath: "network.ca_certificates_path",
+            filePath: config.network.caCertificatesPath,
+          },
+        ]),
+    ...(config.network.clientCertificate === undefined
+      ? []
+      : [
+          {
+            fieldPath: "network.client_certificate_path",
+            filePath: config.network.clientCertificate.certificatePath,
+          },
+          {
+            fieldPath: "network.client_key_path",
+            filePath: config.network.clientCertificate.keyPath,
+          },
+        ]),
+    ...(config.calendarIcsSource === undefined || /^https?:\/\//i.test(config.calendarIcsSource)
+      ? []
+      : [{ fieldPath: "calendar.ics", filePath: config.calendarIcsSource }]),
+  ];
+}
+
+function checkConfiguredFile(file: { fieldPath: string; filePath: string }): ConfigCheck {
+  try {
+    if (!statSync(file.filePath).isFile()) {
+      return { status: "error", message: `${file.fieldPath}: ${file.filePath} is not a file` };
+    }
+  } catch {
+    return { status: "error", message: `${file.fieldPath}: ${file.filePath} does not exist` };
+  }
+
+  return { status: "ok", message: `${file.fieldPath}: ${file.filePath}` };
+}
+
+async function checkGitHubToken(
+  config: AppConfig,
+  checkGitHubAuth: NonNullable<RunConfigCommandOptions["checkGitHubAuth"]>,
+): Promise<ConfigCheck[]> {
+  let auth: Awaited<ReturnType<typeof checkGitHubAuth>>;
+
+  try {
+    auth = await checkGitHubAuth(config);
+  } catch (error) {
+    return [{ status: "error", message: error instanceof Error ? error.message : String(error) }];
+  }
+
+  const { githubApp } = config;
+  const checks: ConfigCheck[] = [
+    {
+      status: "ok",
+      message:
+        githubApp === undefined
+          ? `GitHub accepts the token for ${auth.currentUserLogin}`
+          : `GitHub accepts installation ${githubApp.installationId} of app ${githubApp.appId}`,
+    },
+  ];
+
+  if (config.githubLogin !== undefined && config.githubLogin !== auth.currentUserLogin) {
+    checks.push({
+      status: "warn",
+      message: `github.login is ${config.githubLogin}, but the token belongs to ${auth.currentUserLogin}`,
+    });
+  }
+
+  const missingScopes =
+    auth.tokenScopes === undefined
+      ? []
+      : findMissingTokenScopes(auth.tokenScopes, listRequiredTokenScopes(config));
+
+  if (missingScopes.length > 0) {
+    checks.push({ status: "warn", message: formatMissingTokenScopesWarning(missingScopes) });
+  }
+
+  return checks;
+}
+
+// Asks GitHub who the token belongs to even when github.login is set, which startup skips. A
+// GitHub App has no user to ask about, so it only has to get an installation token accepted.
+async function defaultCheckGitHubAuth(
+  config: AppConfig,
+): Promise<Pick<GitHubAuthContext<unknown>, "currentUserLogin" | "tokenScopes">> {
+  if (config.githubApp === undefined) {
+    return initializeGitHubAuth({
+      ...(config.githubToken === undefined ? {} : { githubToken: config.githubToken }),
+      ...(config.githubApiUrl === undefined ? {} : { githubApiUrl: config.githubApiUrl }),
+      network: config.network,
+    });
+  }
+
+  const auth = await initializeGitHubAuth(config);
+
+  await checkGitHubConnectivity(auth.client, undefined);
+
+  return auth;
+}
+
+function formatConfigReport(configPath: string, checks: readonly ConfigCheck[]): string {
+  const errorCount = checks.filter((check) => check.status === "error").length;
+
+  return [
+    `Checked ${configPath}`,
+    ...checks.map((check) => `${check.status.padEnd(5)} ${check.message}`),
+    errorCount === 0
+      ? "The config looks good."
+      : `Found ${errorCount} ${errorCount === 1 ? "problem" : "problems"}.`,
+  ].join("\n");
+}
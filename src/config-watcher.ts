@@ -0,0 +1,112 @@
+import { watch } from "node:fs";
+import path from "node:path";
+
+import type { AppConfig } from "./config.js";
+import { getLogger } from "./logger.js";
+
+const DEFAULT_DEBOUNCE_MS = 500;
+
+// Applied to the running notifier; edits to any other section are picked up on the next restart.
+export const HOT_RELOADABLE_CONFIG_SECTIONS: readonly (keyof AppConfig)[] = [
+  "timings",
+  "logging",
+  "filters",
+  "channels",
+  "repositoryOverrides",
+  "sounds",
+];
+
+export interface ConfigWatcherOptions {
+  // Re-reads the config file; throws while it is missing or invalid.
+  load: () => AppConfig;
+  onChange: (config: AppConfig, previousConfig: AppConfig) => void;
+  // Editors often write a file in several steps, so changes settle before reloading.
+  debounceMs?: number;
+  watchDirectory?: (
+    directoryPath: string,
+    listener: (fileName: string | null) => void,
+  ) => ConfigDirectoryWatcher;
+}
+
+// The slice of fs.FSWatcher the config watcher uses, so tests can fake it.
+export interface ConfigDirectoryWatcher {
+  on(event: "error", listener: (error: Error) => void): unknown;
+  close(): void;
+}
+
+export interface ConfigWatcherHandle {
+  stop(): void;
+}
+
+// Reloads the config file whenever it changes. The directory is watched rather than the file,
+// because editors that save by renaming a temporary file over it would end a watch on the file.
+// An invalid edit is logged and ignored, so the running settings stay in place until it's fixed.
+export function watchConfigFile(
+  initialConfig: AppConfig,
+  options: ConfigWatcherOptions,
+): ConfigWatcherHandle {
+  const { configPath } = initialConfig.paths;
+  const configFileName = path.basename(configPath);
+  const debounceMs = options.debounceMs ?? DEFAULT_DEBOUNCE_MS;
+  const watchDirectory =
+    options.watchDirectory ??
+    ((directoryPath, listener) => watch(directoryPath, (_eventType, fileName) => listener(fileName)));
+  let currentConfig = initialConfig;
+  let reloadTimer: ReturnType<typeof setTimeout> | undefined;
+  const watcher = watchDirectory(path.dirname(configPath), (fileName) => {
+    if (fileName !== null && fileName !== configFileName) {
+      return;
+    }
+
+    clearTimeout(reloadTimer);
+    reloadTimer = setTimeout(reload, debounceMs);
+    reloadTimer.unref?.();
+  });
+
+  watcher.on("error", (error) => {
+    getLogger().warn("Stopped watching the config file for changes", { configPath, error });
+  });
+
+  return {
+    stop(): void {
+      clearTimeout(reloadTimer);
+      watcher.close();
+    },
+  };
+
+  function reload(): void {
+    let nextConfig: AppConfig;
+
+    try {
+      nextConfig = options.load();
+    } catch (error) {
+      getLogger().warn("Ignoring config file change; keeping the running settings", {
+        configPath,
+        error,
+      });
+      return;
+    }
+
+    if (listChangedConfigSections(currentConfig, nextConfig).length === 0) {
+      return;
+    }
+
+    const previousConfig = currentConfig;
+    currentConfig = nextConfig;
+    options.onChange(nextConfig, previousConfig);
+  }
+}
+
+export function listChangedConfigSections(
+  previousConfig: AppConfig,
+  nextConfig: AppConfig,
+): (keyof AppConfig)[] {
+  const sections = new Set([
+    ...Object.keys(previousConfig),
+    ...Object.keys(nextConfig),
+  ]) as Set<keyof AppConfig>;
+
+  return [...sections].filter(
+    (section) => JSON.stringify(previousConfig[section]) !== JSON.stringify(nextConfig[section]),
+  );
+}
@@ -4,43 +4,233 @@ import path from "node:path";
 
 import { parse } from "smol-toml";
 
-import { DEFAULT_LOG_RETENTION_MS, isLogLevel, type LogLevel } from "./logger.js";
+import { readGhCliToken } from "./gh-cli-token.js";
+import type { GitHubAppCredentials } from "./github-app-auth.js";
+import {
+  GITHUB_INBOX_MODES,
+  GITHUB_NOTIFICATION_REASONS,
+  type GitHubInboxMode,
+  type GitHubNotificationReason,
+} from "./github-notification-read-state.js";
+import {
+  DEFAULT_LOG_RETENTION_MS,
+  isLogFormat,
+  isLogLevel,
+  type LogFormat,
+  type LogLevel,
+} from "./logger.js";
+import {
+  DEFAULT_CONFIRMED_ACTION_KINDS,
+  MERGE_METHODS,
+  NOTIFICATION_ACTION_KINDS,
+  REACTION_EMOJI,
+  type MergeMethod,
+  type NotificationActionKind,
+  type ReactionContent,
+} from "./notification-actions.js";
+import {
+  compileNotificationBodyTemplate,
+  NotificationBodyTemplateError,
+} from "./notification-body-template.js";
+import type { NotificationChannel } from "./notification-channels.js";
+import type { NotificationSounds } from "./notification-dispatch.js";
+import {
+  PULL_REQUEST_ACTIVITY_APIS,
+  type PullRequestActivityApi,
+} from "./pull-request-graphql-activity.js";
+import type { RepositoryOverride } from "./repository-overrides.js";
+import { DEFAULT_SERVER_PORT } from "./server.js";
+import type { ScreenSharePolicy } from "./session-presence-buffer.js";
+import {
+  isValidTimeZone,
+  WEEKDAYS,
+  type WorkingHoursRange,
+  type WorkingHoursSchedule,
+} from "./working-hours.js";
 
 const DEFAULT_TRACKED_PULL_REQUEST_POLL_MS = 60_000;
 const DEFAULT_DISCOVERY_POLL_MS = 5 * 60_000;
+const DEFAULT_INBOX_REPOSITORY_POLL_MS = 60_000;
+// Shorter intervals mostly spend API quota: a tracked pull request poll costs several REST calls
+// per pull request, and the search API behind discovery allows 30 requests a minute.
+export const MIN_TRACKED_PULL_REQUEST_POLL_MS = 15_000;
+export const MIN_DISCOVERY_POLL_MS = 60_000;
 const DEFAULT_GRACE_PERIOD_MS = 7 * 24 * 60 * 60_000;
+const DEFAULT_AVATAR_CACHE_TTL_MS = 24 * 60 * 60_000;
+const DEFAULT_POLL_CONCURRENCY = 4;
+const DEFAULT_MAX_FETCHED_COMMENTS = 300;
+const DEFAULT_GITHUB_WEB_URL = "https://github.com";
+const CONFIG_SECTIONS = [
+  "github",
+  "openai",
+  "timings",
+  "logging",
+  "webhooks",
+  "notifications",
+  "sounds",
+  "calendar",
+  "schedule",
+  "performance",
+  "server",
+  "network",
+  "github_project",
+  "filters",
+  "channels",
+  "repositories",
+  "security_alerts",
+  "inbox_repositories",
+  "actions",
+] as const;
+const DEFAULT_GITHUB_PROJECT_STATUS_FIELD = "Status";
 
 type ConfigTable = Record<string, unknown>;
 
+const XDG_BASE_DIRECTORIES: Record<XdgBaseDirectory, { variable: string; defaultPath: string[] }> = {
+  config: { variable: "XDG_CONFIG_HOME", defaultPath: [".config"] },
+  state: { variable: "XDG_STATE_HOME", defaultPath: [".local", "state"] },
+  cache: { variable: "XDG_CACHE_HOME", defaultPath: [".cache"] },
+  data: { variable: "XDG_DATA_HOME", defaultPath: [".local", "share"] },
+};
+
 export interface ResolveAppPathsOptions {
   homeDir?: string;
+  // XDG base directory variables. Defaults to process.env unless homeDir is given, since an explicit
+  // home directory belongs to a different layout than the current user's variables describe.
+  env?: NodeJS.ProcessEnv;
   configPath?: string;
   stateDirPath?: string;
+  cacheDirPath?: string;
+  // Gives the profile its own database and logs under the state directory, so profiles can run
+  // side by side without marking each other's notifications as seen.
+  profile?: string;
 }
 
-export interface LoadConfigOptions extends ResolveAppPathsOptions {}
+export type XdgBaseDirectory = "config" | "state" | "cache" | "data";
+
+export interface LoadConfigOptions extends ResolveAppPathsOptions {
+  // Read the GitHub token from this file instead of github.token, e.g. a packaged secret.
+  githubTokenFilePath?: string;
+}
 
 export interface AppPaths {
   configPath: string;
   stateDirPath: string;
   databasePath: string;
   logsDirPath: string;
+  cacheDirPath: string;
+  // Where `octopulse login` saves the token it gets from GitHub.
+  githubTokenPath: string;
+}
+
+export interface GitHubAccountConfig {
+  // Prefixes the account's notification titles and names its state directory.
+  label: string;
+  token: string;
+  login?: string;
 }
 
 export interface AppConfig {
   paths: AppPaths;
-  githubToken: string;
+  profile?: string;
+  // Exactly one of these is set: a token, or a GitHub App that fetches installation tokens.
+  githubToken?: string;
+  githubApp?: GitHubAppCredentials;
   githubLogin?: string;
+  githubSourceLabel?: string;
+  // https://github.com, or the address of a GitHub Enterprise Server instance. Has no trailing "/".
+  githubWebUrl: string;
+  // Unset for github.com, where Octokit's default applies.
+  githubApiUrl?: string;
+  // More GitHub identities polled alongside the one above, e.g. a work and a personal account.
+  githubAccounts: GitHubAccountConfig[];
   openAiApiKey?: string;
+  webhookRelayUrl?: string;
+  calendarIcsSource?: string;
+  githubProject?: {
+    owner: string;
+    number: number;
+    statusField: string;
+  };
+  filters?: {
+    ownedPathsOnly: boolean;
+    ownerTeams: string[];
+    paths: string[];
+  };
+  channels: NotificationChannel[];
+  repositoryOverrides: RepositoryOverride[];
+  securityAlertRepositories: string[];
+  // Repositories whose notifications are read on their own, more often than discovery runs.
+  inboxRepositories: {
+    repositories: string[];
+    pollIntervalMs: number;
+  };
+  actions: {
+    confirm: NotificationActionKind[];
+    mergeMethod: MergeMethod;
+    reactions: ReactionContent[];
+    replies: string[];
+  };
+  workingHours?: WorkingHoursSchedule;
+  weekendMode?: {
+    timeZone: string;
+    repositories: string[];
+  };
+  notifications: {
+    markGitHubReadAfterDisplay: boolean;
+    syncGitHubReadState: boolean;
+    bufferWhileLocked: boolean;
+    holdWhenIdleMs?: number;
+    aggregationWindowMs?: number;
+    maxPopupsPerMinute?: number;
+    soundCooldownMs?: number;
+    whileScreenShared: ScreenSharePolicy;
+    ciFailureAlerts: boolean;
+    groupByRepository: boolean;
+    // Tracks pull requests from the github.com notification inbox as well as discovered ones.
+    githubInbox: GitHubInboxMode;
+    githubInboxReasons: GitHubNotificationReason[];
+    bodyTemplate?: string;
+  };
+  sounds: NotificationSounds;
   logging: {
-    level: LogLevel;
+    consoleLevel: LogLevel;
+    fileLevel: LogLevel;
+    format: LogFormat;
+    fileEnabled: boolean;
     retentionMs: number;
   };
   timings: {
     trackedPullRequestPollMs: number;
     discoveryPollMs: number;
     gracePeriodMs: number;
+    avatarCacheTtlMs: number;
+  };
+  performance: {
+    pollConcurrency: number;
+    // How many of a pull request's newest comments, review comments, and reviews each poll keeps.
+    maxFetchedComments: number;
+    // "graphql" reads comments and reviews in one request per pull request instead of three.
+    activityApi: PullRequestActivityApi;
   };
+  server: {
+    port: number;
+  };
+  network: {
+    // An HTTP(S) proxy for every outgoing request. Unset leaves HTTPS_PROXY and friends in charge.
+    proxy?: string;
+    // Hosts reached directly, in NO_PROXY syntax.
+    noProxy: string[];
+    // A PEM bundle of root certificates trusted alongside the system ones, e.g. for a corporate
+    // proxy that inspects TLS or an internally signed GitHub Enterprise Server certificate.
+    caCertificatesPath?: string;
+    // Presented to GitHub by servers that require mutual TLS.
+    clientCertificate?: ClientCertificatePaths;
+  };
+}
+
+export interface ClientCertificatePaths {
+  certificatePath: string;
+  keyPath: string;
 }
 
 export class ConfigError extends Error {
@@ -50,13 +240,28 @@ export class ConfigError extends Error {
   }
 }
 
+// Everything lives under the XDG base directories rather than the working directory, so
+// Octopulse behaves the same whether it's started from a terminal, systemd, or a desktop launcher.
 export function resolveAppPaths(options: ResolveAppPathsOptions = {}): AppPaths {
-  const homeDir = options.homeDir ?? os.homedir();
+  const baseDirectoryOptions = {
+    homeDir: options.homeDir ?? os.homedir(),
+    env: options.env ?? (options.homeDir === undefined ? process.env : {}),
+  };
   const configPath = path.resolve(
-    options.configPath ?? path.join(homeDir, ".config", "octopulse", "config.toml"),
+    options.configPath ??
+      path.join(resolveXdgBaseDirectory("config", baseDirectoryOptions), "octopulse", "config.toml"),
   );
-  const stateDirPath = path.resolve(
-    options.stateDirPath ?? path.join(homeDir, ".local", "state", "octopulse"),
+  const baseStateDirPath = path.resolve(
+    options.stateDirPath ??
+      path.join(resolveXdgBaseDirectory("state", baseDirectoryOptions), "octopulse"),
+  );
+  const stateDirPath =
+    options.profile === undefined
+      ? baseStateDirPath
+      : path.join(baseStateDirPath, "profiles", options.profile);
+  const cacheDirPath = path.resolve(
+    options.cacheDirPath ??
+      path.join(resolveXdgBaseDirectory("cache", baseDirectoryOptions), "octopulse"),
   );
 
   return {
@@ -64,9 +269,24 @@ export function resolveAppPaths(options: ResolveAppPathsOptions = {}): AppPaths
     stateDirPath,
     databasePath: path.join(stateDirPath, "octopulse.db"),
     logsDirPath: path.join(stateDirPath, "logs"),
+    cacheDirPath,
+    githubTokenPath: path.join(stateDirPath, "github-token"),
   };
 }
 
+// The spec says to ignore relative values, falling back to the default under the home directory.
+export function resolveXdgBaseDirectory(
+  kind: XdgBaseDirectory,
+  options: { homeDir: string; env: NodeJS.ProcessEnv },
+): string {
+  const { variable, defaultPath } = XDG_BASE_DIRECTORIES[kind];
+  const value = options.env[variable];
+
+  return value !== undefined && path.isAbsolute(value)
+    ? value
+    : path.join(options.homeDir, ...defaultPath);
+}
+
 export function loadConfig(options: LoadConfigOptions = {}): AppConfig {
   const paths = resolveAppPaths(options);
 
@@ -92,15 +312,151 @@ export function loadConfig(options: LoadConfigOptions = {}): AppConfig {
     throw new ConfigError(`Invalid TOML in ${paths.configPath}: ${getErrorMessage(error)}`);
   }
 
-  return validateConfig(parsedConfig, paths);
+  const githubToken =
+    options.githubTokenFilePath === undefined
+      ? undefined
+      : readGitHubTokenFile(options.githubTokenFilePath);
+  const readFallbackGitHubToken = createFallbackGitHubTokenReader(paths, options);
+
+  if (options.profile === undefined) {
+    return validateConfig(
+      selectConfigProfile(parsedConfig, undefined),
+      paths,
+      githubToken,
+      readFallbackGitHubToken,
+    );
+  }
+
+  const profileConfig = selectConfigProfile(parsedConfig, options.profile);
+
+  try {
+    return {
+      ...validateConfig(profileConfig, paths, githubToken, readFallbackGitHubToken),
+      profile: options.profile,
+    };
+  } catch (error) {
+    if (error instanceof ConfigError) {
+      throw new ConfigError(`Profile "${options.profile}": ${error.message}`);
+    }
+
+    throw error;
+  }
+}
+
+export function isConfigProfileName(value: string): boolean {
+  return /^[\w-]+$/.test(value);
+}
+
+// Each section a profile sets replaces the top-level one, so a profile's [github] doesn't inherit
+// the top-level login or label. Sections the profile leaves out are shared by every profile.
+function selectConfigProfile(parsedConfig: unknown, profile: string | undefined): ConfigTable {
+  const { profiles, ...sharedConfig } = requireTable(parsedConfig, "config");
+  const profileTables = profiles === undefined ? {} : requireTable(profiles, "profiles");
+
+  if (profile === undefined) {
+    return sharedConfig;
+  }
+
+  const profileConfig = profileTables[profile];
+
+  if (profileConfig === undefined) {
+    const profileNames = Object.keys(profileTables);
+
+    throw new ConfigError(
+      `Profile "${profile}" is not defined; ` +
+        (profileNames.length === 0
+          ? "the config file has no [profiles] section"
+          : `the config file defines ${profileNames.join(", ")}`),
+    );
+  }
+
+  const profileTable = requireTable(profileConfig, `profiles.${profile}`);
+  assertAllowedKeys(profileTable, [...CONFIG_SECTIONS], `profiles.${profile}`);
+
+  return { ...sharedConfig, ...profileTable };
+}
+
+// Where the token comes from when neither --token-file nor github.token sets one, in order: the
+// GITHUB_TOKEN variable, the token `octopulse login` saved, then the GitHub CLI's. Each host is
+// only looked up once per load.
+function createFallbackGitHubTokenReader(
+  paths: AppPaths,
+  options: LoadConfigOptions,
+): (hostname: string) => string | undefined {
+  const homeDir = options.homeDir ?? os.homedir();
+  const env = options.env ?? (options.homeDir === undefined ? process.env : {});
+  const environmentToken = env.GITHUB_TOKEN?.trim();
+  const savedGitHubToken = existsSync(paths.githubTokenPath)
+    ? readGitHubTokenFile(paths.githubTokenPath)
+    : undefined;
+  const ghConfigDirPath =
+    env.GH_CONFIG_DIR ?? path.join(resolveXdgBaseDirectory("config", { homeDir, env }), "gh");
+  const ghCliTokens = new Map<string, string | undefined>();
+
+  return (hostname) => {
+    if (environmentToken) {
+      return environmentToken;
+    }
+
+    if (savedGitHubToken !== undefined) {
+      return savedGitHubToken;
+    }
+
+    if (!ghCliTokens.has(hostname)) {
+      ghCliTokens.set(hostname, readGhCliToken(hostname, { configDirPath: ghConfigDirPath, env }));
+    }
+
+    return ghCliTokens.get(hostname);
+  };
+}
+
+function readGitHubTokenFile(tokenFilePath: string): string {
+  let token: string;
+
+  try {
+    token = readFileSync(path.resolve(tokenFilePath), "utf8").trim();
+  } catch (error) {
+    throw new ConfigError(
+      `Failed to read GitHub token file at ${tokenFilePath}: ${getErrorMessage(error)}`,
+    );
+  }
+
+  if (token.length === 0) {
+    throw new ConfigError(`GitHub token file at ${tokenFilePath} is empty`);
+  }
+
+  return token;
 }
 
-function validateConfig(parsedConfig: unknown, paths: AppPaths): AppConfig {
+function validateConfig(
+  parsedConfig: unknown,
+  paths: AppPaths,
+  githubTokenOverride: string | undefined,
+  // Finds a token for the GitHub host when github.token isn't set.
+  readFallbackGitHubToken: (hostname: string) => string | undefined,
+): AppConfig {
   const root = requireTable(parsedConfig, "config");
-  assertAllowedKeys(root, ["github", "openai", "timings", "logging"]);
+  assertAllowedKeys(root, [...CONFIG_SECTIONS]);
+
+  // A token from anywhere else makes the [github] section optional, since the token is its only
+  // required key.
+  const github =
+    optionalNestedTable(root, "github") ??
+    (githubTokenOverride === undefined &&
+    readFallbackGitHubToken(new URL(DEFAULT_GITHUB_WEB_URL).hostname) === undefined
+      ? requireNestedTable(root, "github")
+      : {});
+  assertAllowedKeys(
+    github,
+    ["token", "login", "label", "url", "api_url", "app", "accounts"],
+    "github",
+  );
 
-  const github = requireNestedTable(root, "github");
-  assertAllowedKeys(github, ["token", "login"], "github");
+  const githubAppTable =
+    github.app === undefined ? undefined : requireTable(github.app, "github.app");
+  if (githubAppTable) {
+    assertAllowedKeys(githubAppTable, ["id", "installation_id", "private_key_path"], "github.app");
+  }
 
   const openai = optionalNestedTable(root, "openai");
   if (openai) {
@@ -111,28 +467,339 @@ function validateConfig(parsedConfig: unknown, paths: AppPaths): AppConfig {
   if (timings) {
     assertAllowedKeys(
       timings,
-      ["tracked_poll_interval", "discovery_poll_interval", "grace_period"],
+      ["tracked_poll_interval", "discovery_poll_interval", "grace_period", "avatar_cache_ttl"],
       "timings",
     );
   }
 
   const logging = optionalNestedTable(root, "logging");
   if (logging) {
-    assertAllowedKeys(logging, ["level", "retention"], "logging");
+    assertAllowedKeys(
+      logging,
+      ["level", "console_level", "file_level", "format", "file", "retention"],
+      "logging",
+    );
+  }
+
+  const notifications = optionalNestedTable(root, "notifications");
+  if (notifications) {
+    assertAllowedKeys(
+      notifications,
+      [
+        "mark_github_read_after_display",
+        "sync_github_read_state",
+        "buffer_while_locked",
+        "hold_when_idle_for",
+        "while_screen_shared",
+        "ci_failure_alerts",
+        "group_by_repository",
+        "github_inbox",
+        "github_inbox_reasons",
+        "aggregation_window",
+        "max_popups_per_minute",
+        "sound_cooldown",
+        "body_template",
+      ],
+      "notifications",
+    );
+  }
+
+  const soundsTable = optionalNestedTable(root, "sounds");
+  if (soundsTable) {
+    assertAllowedKeys(soundsTable, ["approved", "changes_requested", "comment"], "sounds");
+  }
+
+  const performance = optionalNestedTable(root, "performance");
+  if (performance) {
+    assertAllowedKeys(
+      performance,
+      ["poll_concurrency", "max_fetched_comments", "activity_api"],
+      "performance",
+    );
+  }
+
+  const server = optionalNestedTable(root, "server");
+  if (server) {
+    assertAllowedKeys(server, ["port"], "server");
+  }
+
+  const network = optionalNestedTable(root, "network");
+  if (network) {
+    assertAllowedKeys(
+      network,
+      ["proxy", "no_proxy", "ca_certificates_path", "client_certificate_path", "client_key_path"],
+      "network",
+    );
+  }
+
+  const webhooks = optionalNestedTable(root, "webhooks");
+  if (webhooks) {
+    assertAllowedKeys(webhooks, ["relay_url"], "webhooks");
+  }
+
+  const calendar = optionalNestedTable(root, "calendar");
+  if (calendar) {
+    assertAllowedKeys(calendar, ["ics"], "calendar");
+  }
+
+  const githubProjectTable = optionalNestedTable(root, "github_project");
+  if (githubProjectTable) {
+    assertAllowedKeys(githubProjectTable, ["owner", "number", "status_field"], "github_project");
+  }
+
+  const filtersTable = optionalNestedTable(root, "filters");
+  if (filtersTable) {
+    assertAllowedKeys(filtersTable, ["owned_paths_only", "owner_teams", "paths"], "filters");
+  }
+
+  const securityAlerts = optionalNestedTable(root, "security_alerts");
+  if (securityAlerts) {
+    assertAllowedKeys(securityAlerts, ["repositories"], "security_alerts");
+  }
+
+  const inboxRepositories = optionalNestedTable(root, "inbox_repositories");
+  if (inboxRepositories) {
+    assertAllowedKeys(inboxRepositories, ["poll_interval", "repositories"], "inbox_repositories");
+  }
+
+  const actions = optionalNestedTable(root, "actions");
+  if (actions) {
+    assertAllowedKeys(actions, ["confirm", "merge_method", "reactions", "replies"], "actions");
+  }
+
+  const schedule = optionalNestedTable(root, "schedule");
+  if (schedule) {
+    assertAllowedKeys(schedule, [
+        "timezone",
+        "outside_hours",
+        "weekend_mode",
+        "weekend_repositories",
+        ...WEEKDAYS,
+      ], "schedule");
   }
 
   const openAiApiKey = openai
     ? optionalNonEmptyString(openai, "api_key", "openai.api_key")
     : undefined;
   const githubLogin = optionalNonEmptyString(github, "login", "github.login");
+  // A token file given on the command line still wins over the app.
+  const githubApp =
+    githubAppTable && githubTokenOverride === undefined
+      ? parseGitHubApp(githubAppTable, github, githubLogin)
+      : undefined;
+  const githubWebUrl = trimTrailingSlashes(
+    optionalHttpUrl(github, "url", "github.url") ?? DEFAULT_GITHUB_WEB_URL,
+  );
+  const githubToken =
+    githubApp !== undefined
+      ? undefined
+      : (githubTokenOverride ??
+        optionalNonEmptyString(github, "token", "github.token") ??
+        readFallbackGitHubToken(new URL(githubWebUrl).hostname) ??
+        requireNonEmptyString(github, "token", "github.token"));
+  const webhookRelayUrl = webhooks
+    ? optionalHttpUrl(webhooks, "relay_url", "webhooks.relay_url")
+    : undefined;
+  const githubSourceLabel = optionalNonEmptyString(github, "label", "github.label");
+  const configuredGitHubApiUrl = optionalHttpUrl(github, "api_url", "github.api_url");
+  // GitHub Enterprise Server serves the REST API under /api/v3 of the web address.
+  const githubApiUrl =
+    configuredGitHubApiUrl === undefined
+      ? githubWebUrl === DEFAULT_GITHUB_WEB_URL
+        ? undefined
+        : `${githubWebUrl}/api/v3`
+      : trimTrailingSlashes(configuredGitHubApiUrl);
+  const weekendModeEnabled = schedule
+    ? optionalBoolean(schedule, "weekend_mode", "schedule.weekend_mode", false)
+    : false;
+  const workingHours = schedule
+    ? parseWorkingHoursSchedule(schedule, weekendModeEnabled)
+    : undefined;
+  const weekendMode =
+    schedule && workingHours && weekendModeEnabled
+      ? {
+          timeZone: workingHours.timeZone,
+          repositories: optionalRepositoryList(
+            schedule,
+            "weekend_repositories",
+            "schedule.weekend_repositories",
+          ),
+        }
+      : undefined;
+  const calendarIcsSource = calendar
+    ? optionalNonEmptyString(calendar, "ics", "calendar.ics")
+    : undefined;
+  const githubProject = githubProjectTable
+    ? {
+        owner: requireNonEmptyString(githubProjectTable, "owner", "github_project.owner"),
+        number: requirePositiveInteger(githubProjectTable, "number", "github_project.number"),
+        statusField:
+          optionalNonEmptyString(githubProjectTable, "status_field", "github_project.status_field") ??
+          DEFAULT_GITHUB_PROJECT_STATUS_FIELD,
+      }
+    : undefined;
+  const filters = filtersTable
+    ? {
+        ownedPathsOnly: optionalBoolean(
+          filtersTable,
+          "owned_paths_only",
+          "filters.owned_paths_only",
+          false,
+        ),
+        ownerTeams: optionalTeamList(filtersTable, "owner_teams", "filters.owner_teams"),
+        paths: optionalPathPatternList(filtersTable, "paths", "filters.paths"),
+      }
+    : undefined;
+  const channels = optionalChannelList(root, "channels");
+  const repositoryOverrides = optionalRepositoryOverrides(root, "repositories");
+  const holdWhenIdleMs =
+    notifications?.hold_when_idle_for === undefined
+      ? undefined
+      : optionalDuration(notifications, "hold_when_idle_for", "notifications.hold_when_idle_for", 0);
+  const aggregationWindowMs =
+    notifications?.aggregation_window === undefined
+      ? undefined
+      : optionalDuration(notifications, "aggregation_window", "notifications.aggregation_window", 0);
+  const maxPopupsPerMinute =
+    notifications?.max_popups_per_minute === undefined
+      ? undefined
+      : optionalPositiveInteger(
+          notifications,
+          "max_popups_per_minute",
+          "notifications.max_popups_per_minute",
+          1,
+        );
+  const sounds: NotificationSounds = {};
+  if (soundsTable) {
+    for (const [key, field] of [
+      ["approved", "approved"],
+      ["changes_requested", "changesRequested"],
+      ["comment", "comment"],
+    ] as const) {
+      const soundFile = optionalSoundFile(soundsTable, key, `sounds.${key}`);
+
+      if (soundFile !== undefined) {
+        sounds[field] = soundFile;
+      }
+    }
+  }
+  const bodyTemplate = notifications
+    ? optionalBodyTemplate(notifications, "body_template", "notifications.body_template")
+    : undefined;
+  const soundCooldownMs =
+    notifications?.sound_cooldown === undefined
+      ? undefined
+      : optionalDuration(notifications, "sound_cooldown", "notifications.sound_cooldown", 0);
+  const logLevel = optionalLogLevel(logging, "level", "logging.level", "info");
 
   return {
     paths,
-    githubToken: requireNonEmptyString(github, "token", "github.token"),
+    ...(githubToken === undefined ? {} : { githubToken }),
+    ...(githubApp ? { githubApp } : {}),
     ...(githubLogin ? { githubLogin } : {}),
+    ...(githubSourceLabel ? { githubSourceLabel } : {}),
+    githubWebUrl,
+    ...(githubApiUrl === undefined ? {} : { githubApiUrl }),
+    githubAccounts: optionalGitHubAccountList(github, "accounts", githubSourceLabel),
     ...(openAiApiKey ? { openAiApiKey } : {}),
+    ...(webhookRelayUrl ? { webhookRelayUrl } : {}),
+    ...(calendarIcsSource ? { calendarIcsSource } : {}),
+    ...(githubProject ? { githubProject } : {}),
+    ...(filters ? { filters } : {}),
+    channels,
+    repositoryOverrides,
+    securityAlertRepositories: securityAlerts
+      ? optionalRepositoryList(securityAlerts, "repositories", "security_alerts.repositories")
+      : [],
+    inboxRepositories: {
+      repositories: inboxRepositories
+        ? optionalRepositoryList(
+            inboxRepositories,
+            "repositories",
+            "inbox_repositories.repositories",
+          )
+        : [],
+      pollIntervalMs: assertMinimumPollInterval(
+        optionalDuration(
+          inboxRepositories,
+          "poll_interval",
+          "inbox_repositories.poll_interval",
+          DEFAULT_INBOX_REPOSITORY_POLL_MS,
+        ),
+        MIN_TRACKED_PULL_REQUEST_POLL_MS,
+        "inbox_repositories.poll_interval",
+      ),
+    },
+    actions: {
+      confirm: optionalActionKindList(actions, "confirm", "actions.confirm", [
+        ...DEFAULT_CONFIRMED_ACTION_KINDS,
+      ]),
+      mergeMethod: optionalMergeMethod(actions, "merge_method", "actions.merge_method", "merge"),
+      reactions: optionalReactionList(actions, "reactions", "actions.reactions", ["+1", "hooray"]),
+      replies: actions ? optionalReplyList(actions, "replies", "actions.replies") : [],
+    },
+    ...(workingHours ? { workingHours } : {}),
+    ...(weekendMode ? { weekendMode } : {}),
+    notifications: {
+      markGitHubReadAfterDisplay: optionalBoolean(
+        notifications,
+        "mark_github_read_after_display",
+        "notifications.mark_github_read_after_display",
+        false,
+      ),
+      syncGitHubReadState: optionalBoolean(
+        notifications,
+        "sync_github_read_state",
+        "notifications.sync_github_read_state",
+        false,
+      ),
+      bufferWhileLocked: optionalBoolean(
+        notifications,
+        "buffer_while_locked",
+        "notifications.buffer_while_locked",
+        true,
+      ),
+      ...(holdWhenIdleMs === undefined ? {} : { holdWhenIdleMs }),
+      ...(aggregationWindowMs === undefined ? {} : { aggregationWindowMs }),
+      ...(maxPopupsPerMinute === undefined ? {} : { maxPopupsPerMinute }),
+      ...(soundCooldownMs === undefined ? {} : { soundCooldownMs }),
+      ...(bodyTemplate === undefined ? {} : { bodyTemplate }),
+      whileScreenShared: optionalScreenSharePolicy(
+        notifications,
+        "while_screen_shared",
+        "notifications.while_screen_shared",
+        "redact",
+      ),
+      ciFailureAlerts: optionalBoolean(
+        notifications,
+        "ci_failure_alerts",
+        "notifications.ci_failure_alerts",
+        false,
+      ),
+      groupByRepository: optionalBoolean(
+        notifications,
+        "group_by_repository",
+        "notifications.group_by_repository",
+        false,
+      ),
+      githubInbox: optionalGitHubInboxMode(
+        notifications,
+        "github_inbox",
+        "notifications.github_inbox",
+        "off",
+      ),
+      githubInboxReasons: optionalNotificationReasonList(
+        notifications,
+        "github_inbox_reasons",
+        "notifications.github_inbox_reasons",
+      ),
+    },
+    sounds,
     logging: {
-      level: optionalLogLevel(logging, "level", "logging.level", "info"),
+      consoleLevel: optionalLogLevel(logging, "console_level", "logging.console_level", logLevel),
+      fileLevel: optionalLogLevel(logging, "file_level", "logging.file_level", logLevel),
+      format: optionalLogFormat(logging, "format", "logging.format", "text"),
+      fileEnabled: optionalBoolean(logging, "file", "logging.file", true),
       retentionMs: optionalDuration(
         logging,
         "retention",
@@ -141,17 +808,25 @@ function validateConfig(parsedConfig: unknown, paths: AppPaths): AppConfig {
       ),
     },
     timings: {
-      trackedPullRequestPollMs: optionalDuration(
-        timings,
-        "tracked_poll_interval",
+      trackedPullRequestPollMs: assertMinimumPollInterval(
+        optionalDuration(
+          timings,
+          "tracked_poll_interval",
+          "timings.tracked_poll_interval",
+          DEFAULT_TRACKED_PULL_REQUEST_POLL_MS,
+        ),
+        MIN_TRACKED_PULL_REQUEST_POLL_MS,
         "timings.tracked_poll_interval",
-        DEFAULT_TRACKED_PULL_REQUEST_POLL_MS,
       ),
-      discoveryPollMs: optionalDuration(
-        timings,
-        "discovery_poll_interval",
+      discoveryPollMs: assertMinimumPollInterval(
+        optionalDuration(
+          timings,
+          "discovery_poll_interval",
+          "timings.discovery_poll_interval",
+          DEFAULT_DISCOVERY_POLL_MS,
+        ),
+        MIN_DISCOVERY_POLL_MS,
         "timings.discovery_poll_interval",
-        DEFAULT_DISCOVERY_POLL_MS,
       ),
       gracePeriodMs: optionalDuration(
         timings,
@@ -159,7 +834,101 @@ function validateConfig(parsedConfig: unknown, paths: AppPaths): AppConfig {
         "timings.grace_period",
         DEFAULT_GRACE_PERIOD_MS,
       ),
+      avatarCacheTtlMs: optionalDuration(
+        timings,
+        "avatar_cache_ttl",
+        "timings.avatar_cache_ttl",
+        DEFAULT_AVATAR_CACHE_TTL_MS,
+      ),
+    },
+    performance: {
+      pollConcurrency: optionalPositiveInteger(
+        performance,
+        "poll_concurrency",
+        "performance.poll_concurrency",
+        DEFAULT_POLL_CONCURRENCY,
+      ),
+      maxFetchedComments: optionalPositiveInteger(
+        performance,
+        "max_fetched_comments",
+        "performance.max_fetched_comments",
+        DEFAULT_MAX_FETCHED_COMMENTS,
+      ),
+      activityApi: optionalActivityApi(
+        performance,
+        "activity_api",
+        "performance.activity_api",
+        "rest",
+      ),
     },
+    server: {
+      port: optionalPort(server, "port", "server.port", DEFAULT_SERVER_PORT),
+    },
+    network: parseNetworkSettings(network),
+  };
+}
+
+function parseNetworkSettings(network: ConfigTable | undefined): AppConfig["network"] {
+  const proxy = network ? optionalNonEmptyString(network, "proxy", "network.proxy") : undefined;
+
+  // Node's built-in proxy support, which Octopulse relies on, only speaks HTTP CONNECT.
+  if (proxy !== undefined && !/^https?:\/\/[^/]/i.test(proxy)) {
+    throw new ConfigError(
+      /^socks/i.test(proxy)
+        ? "network.proxy must be an http or https URL; SOCKS proxies aren't supported"
+        : "network.proxy must be an http or https URL",
+    );
+  }
+
+  const caCertificatesPath = optionalNetworkFilePath(network, "ca_certificates_path");
+  const certificatePath = optionalNetworkFilePath(network, "client_certificate_path");
+  const keyPath = optionalNetworkFilePath(network, "client_key_path");
+
+  if ((certificatePath === undefined) !== (keyPath === undefined)) {
+    throw new ConfigError(
+      "network.client_certificate_path and network.client_key_path must be set together",
+    );
+  }
+
+  return {
+    ...(proxy === undefined ? {} : { proxy }),
+    noProxy: network ? optionalHostList(network, "no_proxy", "network.no_proxy") : [],
+    ...(caCertificatesPath === undefined ? {} : { caCertificatesPath }),
+    ...(certificatePath === undefined || keyPath === undefined
+      ? {}
+      : { clientCertificate: { certificatePath, keyPath } }),
+  };
+}
+
+function optionalNetworkFilePath(
+  network: ConfigTable | undefined,
+  key: string,
+): string | undefined {
+  const filePath = network ? optionalNonEmptyString(network, key, `network.${key}`) : undefined;
+
+  return filePath === undefined ? undefined : path.resolve(filePath);
+}
+
+function parseGitHubApp(
+  app: ConfigTable,
+  github: ConfigTable,
+  githubLogin: string | undefined,
+): GitHubAppCredentials {
+  if (github.token !== undefined) {
+    throw new ConfigError("github.token and github.app can't both be set");
+  }
+
+  // GET /user doesn't work with installation tokens, so the login can't be looked up.
+  if (githubLogin === undefined) {
+    throw new ConfigError("github.login is required with github.app");
+  }
+
+  return {
+    appId: requirePositiveInteger(app, "id", "github.app.id"),
+    installationId: requirePositiveInteger(app, "installation_id", "github.app.installation_id"),
+    privateKeyPath: path.resolve(
+      requireNonEmptyString(app, "private_key_path", "github.app.private_key_path"),
+    ),
   };
 }
 
@@ -228,6 +997,81 @@ function optionalNonEmptyString(
   return value;
 }
 
+function optionalBodyTemplate(
+  table: ConfigTable,
+  key: string,
+  fieldPath: string,
+): string | undefined {
+  const template = optionalNonEmptyString(table, key, fieldPath);
+
+  if (template !== undefined) {
+    try {
+      compileNotificationBodyTemplate(template);
+    } catch (error) {
+      if (error instanceof NotificationBodyTemplateError) {
+        throw new ConfigError(`${fieldPath} is invalid: ${error.message}`);
+      }
+
+      throw error;
+    }
+  }
+
+  return template;
+}
+
+// An empty string silences the sound instead of falling back to the bundled one.
+function optionalSoundFile(
+  table: ConfigTable,
+  key: string,
+  fieldPath: string,
+): string | null | undefined {
+  const value = table[key];
+
+  if (value === undefined) {
+    return undefined;
+  }
+
+  if (typeof value !== "string") {
+    throw new ConfigError(`${fieldPath} must be a file path, or "" to silence the sound`);
+  }
+
+  if (value.trim().length === 0) {
+    return null;
+  }
+
+  return value;
+}
+
+function trimTrailingSlashes(url: string): string {
+  return url.replace(/\/+$/, "");
+}
+
+function optionalHttpUrl(
+  table: ConfigTable,
+  key: string,
+  fieldPath: string,
+): string | undefined {
+  const value = optionalNonEmptyString(table, key, fieldPath);
+
+  if (value === undefined) {
+    return undefined;
+  }
+
+  let url: URL;
+
+  try {
+    url = new URL(value);
+  } catch {
+    throw new ConfigError(`${fieldPath} must be an http or https URL`);
+  }
+
+  if (url.protocol !== "http:" && url.protocol !== "https:") {
+    throw new ConfigError(`${fieldPath} must be an http or https URL`);
+  }
+
+  return value;
+}
+
 function optionalDuration(
   table: ConfigTable | undefined,
   key: string,
@@ -247,6 +1091,410 @@ function optionalDuration(
   return parseDuration(value, fieldPath);
 }
 
+function optionalBoolean(
+  table: ConfigTable | undefined,
+  key: string,
+  fieldPath: string,
+  defaultValue: boolean,
+): boolean {
+  const value = table?.[key];
+
+  if (value === undefined) {
+    return defaultValue;
+  }
+
+  if (typeof value !== "boolean") {
+    throw new ConfigError(`${fieldPath} must be true or false`);
+  }
+
+  return value;
+}
+
+function optionalPositiveInteger(
+  table: ConfigTable | undefined,
+  key: string,
+  fieldPath: string,
+  defaultValue: number,
+): number {
+  const value = table?.[key];
+
+  if (value === undefined) {
+    return defaultValue;
+  }
+
+  if (typeof value !== "number" || !Number.isSafeInteger(value) || value <= 0) {
+    throw new ConfigError(`${fieldPath} must be a positive integer`);
+  }
+
+  return value;
+}
+
+function optionalPort(
+  table: ConfigTable | undefined,
+  key: string,
+  fieldPath: string,
+  defaultValue: number,
+): number {
+  const port = optionalPositiveInteger(table, key, fieldPath, defaultValue);
+
+  if (port > 65_535) {
+    throw new ConfigError(`${fieldPath} must be at most 65535`);
+  }
+
+  return port;
+}
+
+function requirePositiveInteger(table: ConfigTable, key: string, fieldPath: string): number {
+  const value = table[key];
+
+  if (typeof value !== "number" || !Number.isSafeInteger(value) || value <= 0) {
+    throw new ConfigError(`${fieldPath} must be a positive integer`);
+  }
+
+  return value;
+}
+
+function optionalRepositoryList(table: ConfigTable, key: string, fieldPath: string): string[] {
+  const value = table[key];
+
+  if (value === undefined) {
+    return [];
+  }
+
+  if (
+    !Array.isArray(value) ||
+    !value.every((entry) => typeof entry === "string" && /^[\w.-]+\/[\w.-]+$/.test(entry))
+  ) {
+    throw new ConfigError(`${fieldPath} must be a list of "owner/repo" strings`);
+  }
+
+  return value;
+}
+
+function optionalHostList(table: ConfigTable, key: string, fieldPath: string): string[] {
+  const value = table[key];
+
+  if (value === undefined) {
+    return [];
+  }
+
+  if (
+    !Array.isArray(value) ||
+    !value.every((entry) => typeof entry === "string" && /^[^\s,]+$/.test(entry))
+  ) {
+    throw new ConfigError(`${fieldPath} must be a list of host names or domain suffixes`);
+  }
+
+  return value;
+}
+
+function optionalTeamList(table: ConfigTable, key: string, fieldPath: string): string[] {
+  const value = table[key];
+
+  if (value === undefined) {
+    return [];
+  }
+
+  if (
+    !Array.isArray(value) ||
+    !value.every((entry) => typeof entry === "string" && /^@[\w.-]+\/[\w.-]+$/.test(entry))
+  ) {
+    throw new ConfigError(`${fieldPath} must be a list of "@org/team" strings`);
+  }
+
+  return value;
+}
+
+function optionalPathPatternList(table: ConfigTable, key: string, fieldPath: string): string[] {
+  const value = table[key];
+
+  if (value === undefined) {
+    return [];
+  }
+
+  if (
+    !Array.isArray(value) ||
+    !value.every((entry) => typeof entry === "string" && entry.trim().length > 0)
+  ) {
+    throw new ConfigError(`${fieldPath} must be a list of non-empty path patterns`);
+  }
+
+  return value;
+}
+
+function optionalReplyList(table: ConfigTable, key: string, fieldPath: string): string[] {
+  const value = table[key];
+
+  if (value === undefined) {
+    return [];
+  }
+
+  if (
+    !Array.isArray(value) ||
+    !value.every((entry) => typeof entry === "string" && entry.trim().length > 0)
+  ) {
+    throw new ConfigError(`${fieldPath} must be a list of non-empty reply texts`);
+  }
+
+  return value;
+}
+
+function optionalGitHubAccountList(
+  github: ConfigTable,
+  key: string,
+  githubSourceLabel: string | undefined,
+): GitHubAccountConfig[] {
+  const value = github[key];
+
+  if (value === undefined) {
+    return [];
+  }
+
+  if (!Array.isArray(value)) {
+    throw new ConfigError(`github.${key} must be an array of tables ([[github.${key}]])`);
+  }
+
+  const labels = new Set(githubSourceLabel === undefined ? [] : [githubSourceLabel]);
+
+  return value.map((entry, index) => {
+    const fieldPath = `github.${key}[${index}]`;
+    const account = requireTable(entry, fieldPath);
+    assertAllowedKeys(account, ["label", "token", "login"], fieldPath);
+
+    const label = requireNonEmptyString(account, "label", `${fieldPath}.label`);
+
+    if (!isConfigProfileName(label)) {
+      throw new ConfigError(`${fieldPath}.label must only use letters, digits, "-", and "_"`);
+    }
+
+    // Each label has to tell the accounts' popups apart.
+    if (labels.has(label)) {
+      throw new ConfigError(`${fieldPath}.label "${label}" is already used by another account`);
+    }
+
+    labels.add(label);
+
+    const login = optionalNonEmptyString(account, "login", `${fieldPath}.login`);
+
+    return {
+      label,
+      token: requireNonEmptyString(account, "token", `${fieldPath}.token`),
+      ...(login ? { login } : {}),
+    };
+  });
+}
+
+// Each extra account gets a database of its own, so tracked pull requests and poller state never
+// mix between identities. Logs stay with the main account's.
+export function resolveGitHubAccountPaths(paths: AppPaths, label: string): AppPaths {
+  const stateDirPath = path.join(paths.stateDirPath, "accounts", label);
+
+  return {
+    ...paths,
+    stateDirPath,
+    databasePath: path.join(stateDirPath, "octopulse.db"),
+  };
+}
+
+function optionalChannelList(root: ConfigTable, key: string): NotificationChannel[] {
+  const value = root[key];
+
+  if (value === undefined) {
+    return [];
+  }
+
+  if (!Array.isArray(value)) {
+    throw new ConfigError(`${key} must be an array of tables ([[${key}]])`);
+  }
+
+  return value.map((entry, index) => {
+    const fieldPath = `${key}[${index}]`;
+    const channel = requireTable(entry, fieldPath);
+    assertAllowedKeys(channel, ["name", "paths", "sound", "urgency"], fieldPath);
+
+    const paths = optionalPathPatternList(channel, "paths", `${fieldPath}.paths`);
+
+    if (paths.length === 0) {
+      throw new ConfigError(`${fieldPath}.paths must list at least one path pattern`);
+    }
+
+    const soundFile = optionalNonEmptyString(channel, "sound", `${fieldPath}.sound`);
+    const urgency = channel.urgency;
+
+    if (urgency !== undefined && urgency !== "low" && urgency !== "normal" && urgency !== "critical") {
+      throw new ConfigError(`${fieldPath}.urgency must be one of low, normal, or critical`);
+    }
+
+    return {
+      name: requireNonEmptyString(channel, "name", `${fieldPath}.name`),
+      paths,
+      ...(soundFile ? { soundFile } : {}),
+      ...(urgency === undefined ? {} : { urgency }),
+    };
+  });
+}
+
+function optionalRepositoryOverrides(root: ConfigTable, key: string): RepositoryOverride[] {
+  const table = optionalNestedTable(root, key);
+
+  if (table === undefined) {
+    return [];
+  }
+
+  return Object.entries(table).map(([repository, value]) => {
+    const fieldPath = `${key}."${repository}"`;
+
+    if (!/^[A-Za-z0-9_.-]+(\/[A-Za-z0-9_.-]+)?$/.test(repository)) {
+      throw new ConfigError(`${fieldPath} must name a repository as owner/name or an owner`);
+    }
+
+    const override = requireTable(value, fieldPath);
+    assertAllowedKeys(override, ["mute", "sound", "urgency"], fieldPath);
+
+    const soundFile = optionalSoundFile(override, "sound", `${fieldPath}.sound`);
+    const urgency = override.urgency;
+
+    if (urgency !== undefined && urgency !== "low" && urgency !== "normal" && urgency !== "critical") {
+      throw new ConfigError(`${fieldPath}.urgency must be one of low, normal, or critical`);
+    }
+
+    return {
+      repository,
+      ...(override.mute === undefined
+        ? {}
+        : { mute: optionalBoolean(override, "mute", `${fieldPath}.mute`, false) }),
+      ...(soundFile === undefined ? {} : { soundFile }),
+      ...(urgency === undefined ? {} : { urgency }),
+    };
+  });
+}
+
+function optionalActionKindList(
+  table: ConfigTable | undefined,
+  key: string,
+  fieldPath: string,
+  defaultValue: NotificationActionKind[],
+): NotificationActionKind[] {
+  const value = table?.[key];
+
+  if (value === undefined) {
+    return defaultValue;
+  }
+
+  if (
+    !Array.isArray(value) ||
+    !value.every((entry) => (NOTIFICATION_ACTION_KINDS as readonly unknown[]).includes(entry))
+  ) {
+    throw new ConfigError(`${fieldPath} must be a list of: ${NOTIFICATION_ACTION_KINDS.join(", ")}`);
+  }
+
+  return value as NotificationActionKind[];
+}
+
+function optionalMergeMethod(
+  table: ConfigTable | undefined,
+  key: string,
+  fieldPath: string,
+  defaultValue: MergeMethod,
+): MergeMethod {
+  const value = table?.[key];
+
+  if (value === undefined) {
+    return defaultValue;
+  }
+
+  if (!(MERGE_METHODS as readonly unknown[]).includes(value)) {
+    throw new ConfigError(`${fieldPath} must be one of merge, squash, or rebase`);
+  }
+
+  return value as MergeMethod;
+}
+
+function optionalReactionList(
+  table: ConfigTable | undefined,
+  key: string,
+  fieldPath: string,
+  defaultValue: ReactionContent[],
+): ReactionContent[] {
+  const value = table?.[key];
+
+  if (value === undefined) {
+    return defaultValue;
+  }
+
+  if (
+    !Array.isArray(value) ||
+    !value.every((entry) => typeof entry === "string" && Object.hasOwn(REACTION_EMOJI, entry))
+  ) {
+    throw new ConfigError(
+      `${fieldPath} must be a list of: ${Object.keys(REACTION_EMOJI).join(", ")}`,
+    );
+  }
+
+  return value as ReactionContent[];
+}
+
+function optionalGitHubInboxMode(
+  table: ConfigTable | undefined,
+  key: string,
+  fieldPath: string,
+  defaultValue: GitHubInboxMode,
+): GitHubInboxMode {
+  const value = table?.[key];
+
+  if (value === undefined) {
+    return defaultValue;
+  }
+
+  if (!(GITHUB_INBOX_MODES as readonly unknown[]).includes(value)) {
+    throw new ConfigError(`${fieldPath} must be one of off, participating, or all`);
+  }
+
+  return value as GitHubInboxMode;
+}
+
+function optionalNotificationReasonList(
+  table: ConfigTable | undefined,
+  key: string,
+  fieldPath: string,
+): GitHubNotificationReason[] {
+  const value = table?.[key];
+
+  if (value === undefined) {
+    return [];
+  }
+
+  if (
+    !Array.isArray(value) ||
+    !value.every((entry) => (GITHUB_NOTIFICATION_REASONS as readonly unknown[]).includes(entry))
+  ) {
+    throw new ConfigError(
+      `${fieldPath} must be a list of: ${GITHUB_NOTIFICATION_REASONS.join(", ")}`,
+    );
+  }
+
+  return value as GitHubNotificationReason[];
+}
+
+function optionalActivityApi(
+  table: ConfigTable | undefined,
+  key: string,
+  fieldPath: string,
+  defaultValue: PullRequestActivityApi,
+): PullRequestActivityApi {
+  const value = table?.[key];
+
+  if (value === undefined) {
+    return defaultValue;
+  }
+
+  if (!(PULL_REQUEST_ACTIVITY_APIS as readonly unknown[]).includes(value)) {
+    throw new ConfigError(`${fieldPath} must be one of rest or graphql`);
+  }
+
+  return value as PullRequestActivityApi;
+}
+
 function optionalLogLevel(
   table: ConfigTable | undefined,
   key: string,
@@ -266,7 +1514,127 @@ function optionalLogLevel(
   return value;
 }
 
-function parseDuration(value: string, fieldPath: string): number {
+function optionalLogFormat(
+  table: ConfigTable | undefined,
+  key: string,
+  fieldPath: string,
+  defaultValue: LogFormat,
+): LogFormat {
+  const value = table?.[key];
+
+  if (value === undefined) {
+    return defaultValue;
+  }
+
+  if (typeof value !== "string" || !isLogFormat(value)) {
+    throw new ConfigError(`${fieldPath} must be one of text or json`);
+  }
+
+  return value;
+}
+
+function optionalScreenSharePolicy(
+  table: ConfigTable | undefined,
+  key: string,
+  fieldPath: string,
+  defaultValue: ScreenSharePolicy,
+): ScreenSharePolicy {
+  const value = table?.[key];
+
+  if (value === undefined) {
+    return defaultValue;
+  }
+
+  if (value !== "show" && value !== "redact" && value !== "suppress") {
+    throw new ConfigError(`${fieldPath} must be one of show, redact, or suppress`);
+  }
+
+  return value;
+}
+
+function parseWorkingHoursSchedule(
+  schedule: ConfigTable,
+  weekendModeEnabled: boolean,
+): WorkingHoursSchedule {
+  const timeZone =
+    optionalNonEmptyString(schedule, "timezone", "schedule.timezone") ??
+    Intl.DateTimeFormat().resolvedOptions().timeZone;
+
+  if (!isValidTimeZone(timeZone)) {
+    throw new ConfigError(`schedule.timezone must be an IANA timezone like "Europe/Berlin"`);
+  }
+
+  const outsideHoursMode = schedule.outside_hours ?? "silent";
+
+  if (outsideHoursMode !== "silent" && outsideHoursMode !== "off") {
+    throw new ConfigError("schedule.outside_hours must be one of silent or off");
+  }
+
+  const days: WorkingHoursSchedule["days"] = {};
+
+  for (const weekday of WEEKDAYS) {
+    const value = schedule[weekday];
+
+    if (value !== undefined) {
+      days[weekday] = parseWorkingHoursRanges(value, `schedule.${weekday}`);
+    }
+  }
+
+  // Weekend mode decides what breaks through on Saturday and Sunday, so the schedule must not
+  // silence or drop those notifications a second time.
+  if (weekendModeEnabled) {
+    days.saturday = [{ startMinute: 0, endMinute: 24 * 60 }];
+    days.sunday = [{ startMinute: 0, endMinute: 24 * 60 }];
+  }
+
+  return {
+    timeZone,
+    outsideHoursMode,
+    days,
+  };
+}
+
+function parseWorkingHoursRanges(value: unknown, fieldPath: string): WorkingHoursRange[] {
+  if (typeof value !== "string") {
+    throw new ConfigError(`${fieldPath} must be a string like "09:00-17:30" or "off"`);
+  }
+
+  if (value.trim() === "off") {
+    return [];
+  }
+
+  return value.split(",").map((rangeValue) => {
+    const match = rangeValue.trim().match(/^(\d{1,2}):(\d{2})\s*-\s*(\d{1,2}):(\d{2})$/);
+    const startMinute = match ? Number(match[1]) * 60 + Number(match[2]) : Number.NaN;
+    const endMinute = match ? Number(match[3]) * 60 + Number(match[4]) : Number.NaN;
+
+    if (
+      !match ||
+      Number(match[2]) >= 60 ||
+      Number(match[4]) >= 60 ||
+      endMinute > 24 * 60 ||
+      startMinute >= endMinute
+    ) {
+      throw new ConfigError(`${fieldPath} must use ranges like "09:00-17:30" within a single day`);
+    }
+
+    return { startMinute, endMinute };
+  });
+}
+
+export function assertMinimumPollInterval(
+  intervalMs: number,
+  minimumMs: number,
+  fieldPath: string,
+): number {
+  if (intervalMs < minimumMs) {
+    throw new ConfigError(`${fieldPath} must be at least ${minimumMs / 1000}s`);
+  }
+
+  return intervalMs;
+}
+
+export function parseDuration(value: string, fieldPath: string): number {
   const match = value.match(/^\s*(\d+(?:\.\d+)?)\s*([a-zA-Z]+)\s*$/);
 
   if (!match) {
@@ -1,11 +1,15 @@
+import path from "node:path";
 import { fileURLToPath } from "node:url";
 
 import { APP_ICON_PNG_URL } from "./app-icon.js";
 
 export const DESKTOP_ENTRY_ID = "octopulse";
 export const DESKTOP_ENTRY_FILE_NAME = `${DESKTOP_ENTRY_ID}.desktop`;
+export const PROTOCOL_MIME_TYPE = "x-scheme-handler/octopulse";
 
-export function renderDesktopEntry(): string {
+export const SYSTEM_NODE_EXECUTABLE_PATH = "/usr/bin/node";
+
+export function renderDesktopEntry(repoRoot?: string): string {
   return [
     "[Desktop Entry]",
     "Type=Application",
@@ -13,7 +17,10 @@ export function renderDesktopEntry(): string {
     "Name=Octopulse",
     "Comment=Local PR activity monitor",
     `Icon=${fileURLToPath(APP_ICON_PNG_URL)}`,
-    "Exec=/usr/bin/true",
+    repoRoot === undefined
+      ? "Exec=/usr/bin/true"
+      : `Exec=${SYSTEM_NODE_EXECUTABLE_PATH} ${path.join(repoRoot, "dist", "main.js")} protocol %u`,
+    ...(repoRoot === undefined ? [] : [`MimeType=${PROTOCOL_MIME_TYPE};`]),
     "Terminal=false",
     "NoDisplay=true",
     "X-GNOME-UsesNotifications=true",
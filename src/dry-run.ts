@@ -0,0 +1,63 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import type { DatabaseSync } from "node:sqlite";
+
+import type { AppPaths } from "./config.js";
+import { initializeDatabase } from "./database.js";
+import type {
+  LinuxNotification,
+  LinuxNotificationDispatchResult,
+} from "./linux-notification-adapter.js";
+import { getLogger, type AppLogger } from "./logger.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+
+const GITHUB_REPOSITORY_URL_PATTERN = /^https:\/\/github\.com\/([^/]+\/[^/?#]+)/;
+
+// Stands in for the desktop notification adapter: every popup is logged instead of shown.
+export function createDryRunNotificationDispatcher(
+  logger: Pick<AppLogger, "info"> = getLogger(),
+): NotificationDispatcher {
+  return {
+    dispatchNotification: async (notification): Promise<LinuxNotificationDispatchResult> => {
+      logger.info("Dry run: would show notification", describeDryRunNotification(notification));
+      return { openedClickUrl: false };
+    },
+  };
+}
+
+export function describeDryRunNotification(notification: LinuxNotification): {
+  title: string;
+  repository: string | null;
+  sound: string;
+} {
+  return {
+    title: notification.title,
+    repository: notification.clickUrl?.match(GITHUB_REPOSITORY_URL_PATTERN)?.[1] ?? null,
+    sound: notification.soundFile ?? "none",
+  };
+}
+
+// Opens a private copy of the database, so a dry run sees the real history and last-seen state
+// but never advances it. The copy lives in memory once opened and disappears when it's closed.
+export function openDryRunDatabase(paths: AppPaths): DatabaseSync {
+  const snapshotDirPath = mkdtempSync(path.join(os.tmpdir(), "octopulse-dry-run-"));
+  const snapshotPaths = { ...paths, databasePath: path.join(snapshotDirPath, "octopulse.db") };
+
+  try {
+    const database = initializeDatabase(paths);
+
+    try {
+      database.prepare("VACUUM INTO ?").run(snapshotPaths.databasePath);
+    } finally {
+      database.close();
+    }
+
+    const snapshot = initializeDatabase(snapshotPaths);
+    // Keeps the journal off disk, so the snapshot file can be deleted while it stays open.
+    snapshot.exec("PRAGMA journal_mode = MEMORY");
+    return snapshot;
+  } finally {
+    rmSync(snapshotDirPath, { recursive: true, force: true });
+  }
+}
@@ -0,0 +1,98 @@
+import { execFileSync } from "node:child_process";
+import { readFileSync } from "node:fs";
+import path from "node:path";
+
+const GH_AUTH_TOKEN_TIMEOUT_MS = 5_000;
+
+export interface ReadGhCliTokenOptions {
+  // The GitHub CLI's config directory, where hosts.yml lists the hosts it's signed in to.
+  configDirPath: string;
+  env: NodeJS.ProcessEnv;
+  runGhAuthToken?: (hostname: string, env: NodeJS.ProcessEnv) => string | undefined;
+}
+
+// Reuses the token the GitHub CLI signed in with. Older gh versions keep it in hosts.yml; newer ones
+// keep it in the system keyring, where only `gh auth token` can read it. gh is only run when
+// hosts.yml says it's signed in to the host, so machines without gh never spawn it.
+export function readGhCliToken(
+  hostname: string,
+  options: ReadGhCliTokenOptions,
+): string | undefined {
+  let hostsFileText: string;
+
+  try {
+    hostsFileText = readFileSync(path.join(options.configDirPath, "hosts.yml"), "utf8");
+  } catch {
+    return undefined;
+  }
+
+  const host = readHostsFileEntry(hostsFileText, hostname);
+
+  if (host === undefined) {
+    return undefined;
+  }
+
+  return host.oauthToken ?? (options.runGhAuthToken ?? runGhAuthToken)(hostname, options.env);
+}
+
+// hosts.yml maps each host to its settings, e.g. "github.com:\n    oauth_token: gho_...". Only the
+// host's own keys matter here, so a line-based read is enough.
+function readHostsFileEntry(
+  hostsFileText: string,
+  hostname: string,
+): { oauthToken?: string } | undefined {
+  let isInHost = false;
+  let hostKeyIndent: number | undefined;
+  let entry: { oauthToken?: string } | undefined;
+
+  for (const line of hostsFileText.split(/\r?\n/)) {
+    if (line.trim().length === 0 || line.trimStart().startsWith("#")) {
+      continue;
+    }
+
+    const indent = line.length - line.trimStart().length;
+
+    if (indent === 0) {
+      isInHost = unquoteYamlScalar(line.replace(/:\s*$/, "")) === hostname;
+      hostKeyIndent = undefined;
+      entry = isInHost ? (entry ?? {}) : entry;
+      continue;
+    }
+
+    if (!isInHost) {
+      continue;
+    }
+
+    hostKeyIndent ??= indent;
+
+    const match = indent === hostKeyIndent ? line.trim().match(/^oauth_token:\s*(.*)$/) : null;
+    const oauthToken = match?.[1] === undefined ? "" : unquoteYamlScalar(match[1]);
+
+    if (oauthToken.length > 0) {
+      entry = { oauthToken };
+    }
+  }
+
+  return entry;
+}
+
+function unquoteYamlScalar(value: string): string {
+  const trimmed = value.trim();
+
+  return /^(["']).*\1$/.test(trimmed) ? trimmed.slice(1, -1) : trimmed;
+}
+
+function runGhAuthToken(hostname: string, env: NodeJS.ProcessEnv): string | undefined {
+  try {
+    const token = execFileSync("gh", ["auth", "token", "--hostname", hostname], {
+      encoding: "utf8",
+      env,
+      stdio: ["ignore", "pipe", "ignore"],
+      timeout: GH_AUTH_TOKEN_TIMEOUT_MS,
+    }).trim();
+
+    return token.length === 0 ? undefined : token;
+  } catch {
+    return undefined;
+  }
+}
@@ -0,0 +1,212 @@
+import type { DatabaseSync } from "node:sqlite";
+
+import {
+  runFirstRunAuthoredPullRequestDiscovery,
+  startRecurringAuthoredPullRequestDiscovery,
+  type RecurringAuthoredPullRequestDiscoveryHandle,
+} from "./authored-pull-request-discovery.js";
+import {
+  resolveGitHubAccountPaths,
+  type AppConfig,
+  type GitHubAccountConfig,
+} from "./config.js";
+import { initializeDatabase } from "./database.js";
+import { openDryRunDatabase } from "./dry-run.js";
+import {
+  checkGitHubConnectivity,
+  findMissingTokenScopes,
+  formatMissingTokenScopesWarning,
+  initializeGitHubAuth,
+} from "./github.js";
+import { readGitHubInboxSettings } from "./github-notification-read-state.js";
+import { getLogger } from "./logger.js";
+import { createPullRequestActionProvider } from "./notification-actions.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+import { withNotificationSourceLabel } from "./notification-source-label.js";
+import {
+  createPersistentPollerBackoff,
+  PollerStateRepository,
+  watchGitHubRateLimit,
+} from "./poller-state.js";
+import { PullRequestRepository } from "./pull-request-repository.js";
+import {
+  startRecurringTrackedPullRequestPolling,
+  type RecurringTrackedPullRequestPollingHandle,
+  type StartRecurringTrackedPullRequestPollingOptions,
+} from "./tracked-pull-request-polling.js";
+
+// The tracked pull request settings every account shares; the rest is per account.
+export type SharedTrackedPullRequestPollingOptions = Omit<
+  StartRecurringTrackedPullRequestPollingOptions,
+  | "intervalMs"
+  | "backoff"
+  | "pullRequestRepository"
+  | "notificationDispatcher"
+  | "pullRequestActions"
+  | "onCycleSettled"
+>;
+
+export interface StartGitHubAccountPollingOptions {
+  config: AppConfig;
+  dryRun: boolean;
+  // Popups go through the same pipeline as the main account's, with the account label prefixed.
+  notificationDispatcher: NotificationDispatcher;
+  trackedPullRequestPolling: SharedTrackedPullRequestPollingOptions;
+  onCycleSettled: (source: string, failure: Error | null) => void;
+}
+
+export interface GitHubAccountPollingHandle {
+  label: string;
+  currentUserLogin: string;
+  discovery: RecurringAuthoredPullRequestDiscoveryHandle;
+  trackedPullRequestPolling: RecurringTrackedPullRequestPollingHandle;
+  // Stops both pollers and closes the account's database.
+  stop(): void;
+}
+
+// Runs discovery and tracked pull request polling for one of github.accounts, next to the main
+// account's pollers. The web UI, read-state sync, and security alerts stay with the main account.
+export async function startGitHubAccountPolling(
+  account: GitHubAccountConfig,
+  options: StartGitHubAccountPollingOptions,
+): Promise<GitHubAccountPollingHandle> {
+  const { config } = options;
+  const githubAuth = await initializeGitHubAuth({
+    githubToken: account.token,
+    ...(config.githubApiUrl === undefined ? {} : { githubApiUrl: config.githubApiUrl }),
+    ...(account.login === undefined ? {} : { githubLogin: account.login }),
+    network: config.network,
+  });
+
+  const tokenScopes =
+    account.login === undefined
+      ? githubAuth.tokenScopes
+      : await checkGitHubConnectivity(githubAuth.client, account.token);
+  const notificationDispatcher = withNotificationSourceLabel(
+    options.notificationDispatcher,
+    account.label,
+  );
+
+  if (tokenScopes !== undefined) {
+    warnAboutMissingTokenScopes(account, tokenScopes, config, notificationDispatcher);
+  }
+
+  const paths = resolveGitHubAccountPaths(config.paths, account.label);
+  const database = options.dryRun ? openDryRunDatabase(paths) : initializeDatabase(paths);
+
+  try {
+    const pullRequestActions = createPullRequestActionProvider(githubAuth.client, {
+      confirm: config.actions.confirm,
+      mergeMethod: config.actions.mergeMethod,
+      reactions: config.actions.reactions,
+      replies: config.actions.replies,
+    });
+    const firstRunDiscoveryResult = await runFirstRunAuthoredPullRequestDiscovery(
+      database,
+      githubAuth,
+      {
+        concurrency: config.performance.pollConcurrency,
+        notificationDispatcher,
+        pullRequestActions,
+      },
+    );
+    getLogger().info("Pull request discovery completed", {
+      account: account.label,
+      ...firstRunDiscoveryResult,
+    });
+
+    const pollerStateRepository = new PollerStateRepository(database);
+    const githubInbox = readGitHubInboxSettings(config.notifications);
+    watchGitHubRateLimit(githubAuth.client, pollerStateRepository, { notificationDispatcher });
+    const discovery = startRecurringAuthoredPullRequestDiscovery(database, githubAuth, {
+      intervalMs: config.timings.discoveryPollMs,
+      backoff: createPersistentPollerBackoff(pollerStateRepository, "discovery", {
+        intervalMs: config.timings.discoveryPollMs,
+      }),
+      concurrency: config.performance.pollConcurrency,
+      notificationDispatcher,
+      pullRequestActions,
+      repositoryOverrides: config.repositoryOverrides,
+      ...(githubInbox === undefined ? {} : { githubInbox }),
+      onCycleSettled: (failure) => {
+        options.onCycleSettled(`${account.label}:discovery`, failure);
+      },
+    });
+    const trackedPullRequestPolling = startRecurringTrackedPullRequestPolling(
+      database,
+      githubAuth,
+      {
+        ...options.trackedPullRequestPolling,
+        intervalMs: config.timings.trackedPullRequestPollMs,
+        backoff: createPersistentPollerBackoff(pollerStateRepository, "tracked_pull_requests", {
+          intervalMs: config.timings.trackedPullRequestPollMs,
+        }),
+        pullRequestRepository: new PullRequestRepository(database),
+        notificationDispatcher,
+        pullRequestActions,
+        onCycleSettled: (failure) => {
+          options.onCycleSettled(`${account.label}:tracked_pull_requests`, failure);
+        },
+      },
+    );
+
+    return {
+      label: account.label,
+      currentUserLogin: githubAuth.currentUserLogin,
+      discovery,
+      trackedPullRequestPolling,
+      stop: () => {
+        discovery.stop();
+        trackedPullRequestPolling.stop();
+        closeDatabase(database);
+      },
+    };
+  } catch (error) {
+    closeDatabase(database);
+    throw error;
+  }
+}
+
+// Extra accounts only discover and poll pull requests, which also covers project status and the
+// github.com notification inbox.
+function warnAboutMissingTokenScopes(
+  account: GitHubAccountConfig,
+  tokenScopes: readonly string[],
+  config: AppConfig,
+  notificationDispatcher: NotificationDispatcher,
+): void {
+  const missingTokenScopes = findMissingTokenScopes(tokenScopes, [
+    "repo",
+    ...(config.notifications.githubInbox === "off" ? [] : ["notifications"]),
+    ...(config.githubProject ? ["read:project"] : []),
+  ]);
+
+  if (missingTokenScopes.length === 0) {
+    return;
+  }
+
+  const warning = formatMissingTokenScopesWarning(
+    missingTokenScopes,
+    `the token of github.accounts "${account.label}"`,
+  );
+
+  getLogger().warn(warning, { account: account.label, missingTokenScopes });
+  notificationDispatcher
+    .dispatchNotification({
+      title: "GitHub token is missing scopes",
+      body: warning,
+      clickUrl: `${config.githubWebUrl}/settings/tokens`,
+    })
+    .catch((error: unknown) => {
+      getLogger().warn("Failed to show missing token scopes popup", {
+        account: account.label,
+        error,
+      });
+    });
+}
+
+function closeDatabase(database: DatabaseSync): void {
+  if (database.isOpen) {
+    database.close();
+  }
+}
@@ -0,0 +1,143 @@
+import { createPrivateKey, sign, type KeyObject } from "node:crypto";
+import { readFileSync } from "node:fs";
+
+const GITHUB_API_URL = "https://api.github.com";
+// GitHub rejects app JWTs that live longer than ten minutes or are issued in the future, so the
+// issue time is backdated to allow for clock drift.
+const APP_JWT_LIFETIME_S = 9 * 60;
+const APP_JWT_CLOCK_DRIFT_S = 60;
+// Installation tokens last an hour; a new one is fetched this long before the old one expires.
+const INSTALLATION_TOKEN_REFRESH_MARGIN_MS = 5 * 60_000;
+
+export interface GitHubAppCredentials {
+  appId: number;
+  installationId: number;
+  privateKeyPath: string;
+}
+
+export interface GitHubAppTokenProviderOptions {
+  // The REST API of a GitHub Enterprise Server instance instead of api.github.com.
+  apiUrl?: string;
+  fetchImpl?: typeof fetch;
+  now?: () => number;
+  readPrivateKey?: (privateKeyPath: string) => string;
+}
+
+export type GitHubAppTokenProvider = () => Promise<string>;
+
+export class GitHubAppAuthError extends Error {
+  // Set when GitHub answered, so a rejected app is told apart from an unreachable GitHub.
+  readonly status: number | undefined;
+
+  constructor(message: string, options?: ErrorOptions & { status?: number }) {
+    super(message, options);
+    this.name = "GitHubAppAuthError";
+    this.status = options?.status;
+  }
+}
+
+// Returns installation tokens for a GitHub App, fetching a new one shortly before the current one
+// expires. Concurrent callers share a single refresh.
+export function createGitHubAppTokenProvider(
+  credentials: GitHubAppCredentials,
+  options: GitHubAppTokenProviderOptions = {},
+): GitHubAppTokenProvider {
+  const apiUrl = options.apiUrl ?? GITHUB_API_URL;
+  const fetchImpl = options.fetchImpl ?? fetch;
+  const now = options.now ?? Date.now;
+  const readPrivateKey =
+    options.readPrivateKey ?? ((privateKeyPath: string) => readFileSync(privateKeyPath, "utf8"));
+  let privateKey: KeyObject | undefined;
+  let currentToken: { token: string; expiresAtMs: number } | undefined;
+  let pendingRefresh: Promise<string> | undefined;
+
+  const refresh = async (): Promise<string> => {
+    privateKey ??= loadPrivateKey(credentials.privateKeyPath, readPrivateKey);
+
+    const jwt = createGitHubAppJwt(credentials.appId, privateKey, now());
+    const response = await fetchImpl(
+      `${apiUrl}/app/installations/${credentials.installationId}/access_tokens`,
+      {
+        method: "POST",
+        headers: {
+          Accept: "application/vnd.github+json",
+          Authorization: `Bearer ${jwt}`,
+          "X-GitHub-Api-Version": "2022-11-28",
+        },
+      },
+    );
+    const payload = (await response.json().catch(() => ({}))) as {
+      token?: unknown;
+      expires_at?: unknown;
+      message?: unknown;
+    };
+
+    if (!response.ok || typeof payload.token !== "string") {
+      const reason = typeof payload.message === "string" ? payload.message : "no token returned";
+
+      throw new GitHubAppAuthError(
+        `GitHub App authentication failed with status ${response.status}: ${reason}`,
+        { status: response.status },
+      );
+    }
+
+    const expiresAtMs =
+      typeof payload.expires_at === "string" ? Date.parse(payload.expires_at) : Number.NaN;
+
+    currentToken = {
+      token: payload.token,
+      // Without a readable expiry, the token is treated as lasting the documented hour.
+      expiresAtMs: Number.isNaN(expiresAtMs) ? now() + 60 * 60_000 : expiresAtMs,
+    };
+
+    return payload.token;
+  };
+
+  return async () => {
+    if (
+      currentToken !== undefined &&
+      now() < currentToken.expiresAtMs - INSTALLATION_TOKEN_REFRESH_MARGIN_MS
+    ) {
+      return currentToken.token;
+    }
+
+    pendingRefresh ??= refresh().finally(() => {
+      pendingRefresh = undefined;
+    });
+
+    return pendingRefresh;
+  };
+}
+
+export function createGitHubAppJwt(appId: number, privateKey: KeyObject, nowMs: number): string {
+  const issuedAtS = Math.floor(nowMs / 1000) - APP_JWT_CLOCK_DRIFT_S;
+  const header = encodeJwtPart({ alg: "RS256", typ: "JWT" });
+  const claims = encodeJwtPart({
+    iat: issuedAtS,
+    exp: issuedAtS + APP_JWT_LIFETIME_S,
+    iss: String(appId),
+  });
+  const signature = sign("RSA-SHA256", Buffer.from(`${header}.${claims}`), privateKey);
+
+  return `${header}.${claims}.${signature.toString("base64url")}`;
+}
+
+function loadPrivateKey(
+  privateKeyPath: string,
+  readPrivateKey: (privateKeyPath: string) => string,
+): KeyObject {
+  try {
+    return createPrivateKey(readPrivateKey(privateKeyPath));
+  } catch (error) {
+    throw new GitHubAppAuthError(
+      `Failed to read the GitHub App private key at ${privateKeyPath}: ${
+        error instanceof Error ? error.message : String(error)
+      }`,
+      { cause: error },
+    );
+  }
+}
+
+function encodeJwtPart(value: Record<string, unknown>): string {
+  return Buffer.from(JSON.stringify(value)).toString("base64url");
+}
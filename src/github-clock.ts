@@ -0,0 +1,61 @@
+// GitHub's Date header only has one-second resolution.
+const DATE_HEADER_RESOLUTION_MS = 1_000;
+const MAX_SKEW_SAMPLES = 9;
+
+// Estimates how far GitHub's clock is ahead of the local one, so timestamps taken locally can be
+// turned into `since` parameters GitHub compares against its own clock. A local clock running
+// ahead would otherwise skip events, and one running behind would re-fetch old ones.
+export class GitHubClockSkewEstimator {
+  private readonly samples: number[] = [];
+
+  constructor(private readonly now: () => number = Date.now) {}
+
+  // Server time estimated from a response's Date header. The header truncates to the second, so
+  // the midpoint of that second is the best guess.
+  observeDateHeader(value: unknown, receivedAt: number = this.now()): void {
+    const serverTime = typeof value === "string" ? Date.parse(value) : Number.NaN;
+
+    if (!Number.isNaN(serverTime)) {
+      this.addSample(serverTime + DATE_HEADER_RESOLUTION_MS / 2 - receivedAt);
+    }
+  }
+
+  // A server timestamp such as a notification's updated_at can't be in GitHub's future, so it
+  // bounds the skew from below when the Date header samples say otherwise.
+  observeServerTimestamp(value: string, observedAt: number = this.now()): void {
+    const serverTime = Date.parse(value);
+
+    if (!Number.isNaN(serverTime) && serverTime - observedAt > this.skewMs) {
+      this.addSample(serverTime - observedAt);
+    }
+  }
+
+  get skewMs(): number {
+    if (this.samples.length === 0) {
+      return 0;
+    }
+
+    const sorted = [...this.samples].sort((left, right) => left - right);
+
+    return sorted[Math.floor(sorted.length / 2)]!;
+  }
+
+  // Backs off by the header resolution as well, so an event in the same second is never skipped.
+  toGitHubSince(localTimestamp: string): string {
+    const localTime = Date.parse(localTimestamp);
+
+    if (Number.isNaN(localTime)) {
+      return localTimestamp;
+    }
+
+    return new Date(localTime + this.skewMs - DATE_HEADER_RESOLUTION_MS).toISOString();
+  }
+
+  private addSample(skewMs: number): void {
+    this.samples.push(skewMs);
+
+    if (this.samples.length > MAX_SKEW_SAMPLES) {
+      this.samples.shift();
+    }
+  }
+}
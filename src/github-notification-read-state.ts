@@ -0,0 +1,419 @@
+import { Octokit } from "octokit";
+
+import type { AppConfig } from "./config.js";
+import { GitHubClockSkewEstimator } from "./github-clock.js";
+import { getLogger } from "./logger.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+import type { PullRequestCoordinates } from "./pull-request-snapshot.js";
+
+const GITHUB_API_HEADERS = {
+  "X-GitHub-Api-Version": "2022-11-28",
+};
+// GitHub serves at most 50 notification threads per page.
+const NOTIFICATIONS_PAGE_SIZE = 50;
+// `since` keeps the list short; this only guards against paging forever.
+const MAX_NOTIFICATION_PAGES = 40;
+
+export const GITHUB_INBOX_MODES = ["off", "participating", "all"] as const;
+export const GITHUB_NOTIFICATION_REASONS = [
+  "approval_requested",
+  "assign",
+  "author",
+  "ci_activity",
+  "comment",
+  "manual",
+  "mention",
+  "review_requested",
+  "state_change",
+  "subscribed",
+  "team_mention",
+] as const;
+
+export type GitHubInboxMode = (typeof GITHUB_INBOX_MODES)[number];
+export type GitHubNotificationReason = (typeof GITHUB_NOTIFICATION_REASONS)[number];
+
+export interface GitHubInboxSettings {
+  // False adds threads from watched repositories and team mentions to the ones you take part in.
+  participating: boolean;
+  // Empty keeps threads for every reason.
+  reasons: readonly GitHubNotificationReason[];
+}
+
+export interface UnreadPullRequestNotifications {
+  coordinates: PullRequestCoordinates[];
+  lastModified?: string;
+  // X-Poll-Interval, the least time GitHub asks clients to leave between polls.
+  pollIntervalMs?: number;
+}
+
+export interface PullRequestThreadReadState {
+  coordinates: PullRequestCoordinates;
+  unread: boolean;
+  lastReadAt: string | null;
+}
+
+export interface GitHubNotificationReadStateClientOptions {
+  clock?: GitHubClockSkewEstimator;
+}
+
+export interface GitHubNotificationReadStateClient {
+  markPullRequestThreadsRead(coordinates: PullRequestCoordinates): Promise<number>;
+  // Empty when GitHub reports that nothing changed since the previous call with the same `since`.
+  listPullRequestThreadReadStates(since: string): Promise<PullRequestThreadReadState[]>;
+  // The X-Poll-Interval GitHub last sent with the notifications list, the least time it asks
+  // clients to leave between polls.
+  readPollIntervalMs?(): number | undefined;
+}
+
+export function withGitHubMarkReadAfterDisplay(
+  notificationDispatcher: NotificationDispatcher,
+  readStateClient: Pick<GitHubNotificationReadStateClient, "markPullRequestThreadsRead">,
+): NotificationDispatcher {
+  return {
+    async dispatchNotification(notification) {
+      const result = await notificationDispatcher.dispatchNotification(notification);
+      const coordinates =
+        notification.clickUrl && wasNotificationShown(result)
+          ? parsePullRequestHtmlUrl(notification.clickUrl)
+          : null;
+
+      if (coordinates !== null) {
+        try {
+          const markedCount = await readStateClient.markPullRequestThreadsRead(coordinates);
+          getLogger().debug("Marked GitHub notification threads read after display", {
+            pullRequest: formatPullRequestLabel(coordinates),
+            markedCount,
+          });
+        } catch (error) {
+          getLogger().warn("Failed to mark GitHub notification threads read", {
+            pullRequest: formatPullRequestLabel(coordinates),
+            error,
+          });
+        }
+      }
+
+      return result;
+    },
+  };
+}
+
+// Wrappers that hold a popup back answer with { queued }, { skipped } or { suppressed } instead.
+function wasNotificationShown(result: unknown): boolean {
+  if (typeof result !== "object" || result === null) {
+    return true;
+  }
+
+  const value = result as Record<string, unknown>;
+  return value.queued !== true && value.skipped !== true && value.suppressed !== true;
+}
+
+export function createGitHubNotificationReadStateClient(
+  client: Octokit,
+  options: GitHubNotificationReadStateClientOptions = {},
+): GitHubNotificationReadStateClient {
+  const clock = options.clock ?? new GitHubClockSkewEstimator();
+  // GitHub answers 304 Not Modified to If-Modified-Since with the notifications list's previous
+  // Last-Modified, and those responses don't count against the rate limit.
+  let lastModified: { since: string; value: string } | undefined;
+  let pollIntervalMs: number | undefined;
+
+  return {
+    async markPullRequestThreadsRead(coordinates: PullRequestCoordinates): Promise<number> {
+      const threadIds: number[] = [];
+
+      // Only unread threads are listed, and marking them read doesn't shift the pages until the
+      // PATCH requests below.
+      for (let page = 1; page <= MAX_NOTIFICATION_PAGES; page += 1) {
+        const response = await client.request("GET /repos/{owner}/{repo}/notifications", {
+          owner: coordinates.repositoryOwner,
+          repo: coordinates.repositoryName,
+          per_page: NOTIFICATIONS_PAGE_SIZE,
+          page,
+          headers: GITHUB_API_HEADERS,
+        });
+        clock.observeDateHeader(response.headers.date);
+
+        threadIds.push(
+          ...response.data
+            .filter(
+              (thread) =>
+                thread.unread &&
+                thread.subject.type === "PullRequest" &&
+                thread.subject.url?.endsWith(`/pulls/${coordinates.number}`),
+            )
+            .map((thread) => Number(thread.id)),
+        );
+
+        if (response.data.length < NOTIFICATIONS_PAGE_SIZE) {
+          break;
+        }
+      }
+
+      for (const threadId of threadIds) {
+        await client.request("PATCH /notifications/threads/{thread_id}", {
+          thread_id: threadId,
+          headers: GITHUB_API_HEADERS,
+        });
+      }
+
+      return threadIds.length;
+    },
+    async listPullRequestThreadReadStates(since: string): Promise<PullRequestThreadReadState[]> {
+      const readStates: PullRequestThreadReadState[] = [];
+      // `since` is taken from the local clock but GitHub compares it against its own.
+      const githubSince = clock.toGitHubSince(since);
+      // Only remembered once every page was read, so a failed or cut-short read isn't skipped
+      // next time with a 304.
+      let firstPageLastModified: string | undefined;
+
+      for (let page = 1; page <= MAX_NOTIFICATION_PAGES; page += 1) {
+        const ifModifiedSince =
+          page === 1 && lastModified?.since === githubSince ? lastModified.value : undefined;
+        const response = await requestNotificationsPage(client, {
+          since: githubSince,
+          page,
+          ifModifiedSince,
+        });
+
+        if (response === "not_modified") {
+          return [];
+        }
+
+        clock.observeDateHeader(response.headers.date);
+
+        if (page === 1) {
+          firstPageLastModified = response.headers["last-modified"];
+          pollIntervalMs =
+            readPollIntervalHeaderMs(response.headers["x-poll-interval"]) ?? pollIntervalMs;
+        }
+
+        for (const thread of response.data) {
+          clock.observeServerTimestamp(thread.updated_at);
+
+          const coordinates =
+            thread.subject.type === "PullRequest"
+              ? parsePullRequestApiUrl(thread.subject.url)
+              : null;
+
+          if (coordinates !== null) {
+            readStates.push({
+              coordinates,
+              unread: thread.unread,
+              lastReadAt: thread.last_read_at,
+            });
+          }
+        }
+
+        if (response.data.length < NOTIFICATIONS_PAGE_SIZE) {
+          lastModified =
+            firstPageLastModified === undefined
+              ? undefined
+              : { since: githubSince, value: firstPageLastModified };
+
+          return readStates;
+        }
+      }
+
+      lastModified = undefined;
+      getLogger().warn("Stopped reading GitHub notifications at the page limit", {
+        since: githubSince,
+        pageCount: MAX_NOTIFICATION_PAGES,
+        threadCount: readStates.length,
+      });
+
+      return readStates;
+    },
+    readPollIntervalMs(): number | undefined {
+      return pollIntervalMs;
+    },
+  };
+}
+
+export function readGitHubInboxSettings(
+  notifications: Pick<AppConfig["notifications"], "githubInbox" | "githubInboxReasons">,
+): GitHubInboxSettings | undefined {
+  return notifications.githubInbox === "off"
+    ? undefined
+    : {
+        participating: notifications.githubInbox === "participating",
+        reasons: notifications.githubInboxReasons,
+      };
+}
+
+// Lists the pull requests behind unread github.com notification threads, newest first. A
+// repository, given as "owner/repo", limits the list to that repository's threads.
+export async function listUnreadPullRequestNotifications(
+  client: Octokit,
+  settings: GitHubInboxSettings,
+  repository?: string,
+): Promise<PullRequestCoordinates[]> {
+  const result = await readUnreadPullRequestNotifications(client, settings, repository);
+
+  return result === "not_modified" ? [] : result.coordinates;
+}
+
+// Sends the Last-Modified of a previous read as If-Modified-Since, so an unchanged list costs one
+// 304 that doesn't count against the rate limit. The result carries the headers the next read
+// needs; lastModified is left out when the page limit cut the read short.
+export async function readUnreadPullRequestNotifications(
+  client: Octokit,
+  settings: GitHubInboxSettings,
+  repository?: string,
+  ifModifiedSince?: string,
+): Promise<UnreadPullRequestNotifications | "not_modified"> {
+  const coordinatesByLabel = new Map<string, PullRequestCoordinates>();
+  const [owner = "", repo = ""] = repository?.split("/") ?? [];
+  let firstPageLastModified: string | undefined;
+  let pollIntervalMs: number | undefined;
+
+  for (let page = 1; page <= MAX_NOTIFICATION_PAGES; page += 1) {
+    const parameters = {
+      participating: settings.participating,
+      per_page: NOTIFICATIONS_PAGE_SIZE,
+      page,
+      headers:
+        page === 1 && ifModifiedSince !== undefined
+          ? { ...GITHUB_API_HEADERS, "If-Modified-Since": ifModifiedSince }
+          : GITHUB_API_HEADERS,
+    };
+    const response = await requestUnlessNotModified(() =>
+      repository === undefined
+        ? client.request("GET /notifications", parameters)
+        : client.request("GET /repos/{owner}/{repo}/notifications", {
+            ...parameters,
+            owner,
+            repo,
+          }),
+    );
+
+    if (response === "not_modified") {
+      return "not_modified";
+    }
+
+    if (page === 1) {
+      firstPageLastModified = response.headers["last-modified"];
+      pollIntervalMs = readPollIntervalHeaderMs(response.headers["x-poll-interval"]);
+    }
+
+    for (const thread of response.data) {
+      const coordinates =
+        thread.subject.type === "PullRequest" &&
+        (settings.reasons.length === 0 ||
+          (settings.reasons as readonly string[]).includes(thread.reason))
+          ? parsePullRequestApiUrl(thread.subject.url)
+          : null;
+
+      if (coordinates !== null) {
+        coordinatesByLabel.set(formatPullRequestLabel(coordinates), coordinates);
+      }
+    }
+
+    if (response.data.length < NOTIFICATIONS_PAGE_SIZE) {
+      return {
+        coordinates: [...coordinatesByLabel.values()],
+        ...(firstPageLastModified === undefined ? {} : { lastModified: firstPageLastModified }),
+        ...(pollIntervalMs === undefined ? {} : { pollIntervalMs }),
+      };
+    }
+  }
+
+  return {
+    coordinates: [...coordinatesByLabel.values()],
+    ...(pollIntervalMs === undefined ? {} : { pollIntervalMs }),
+  };
+}
+
+function requestNotificationsPage(
+  client: Octokit,
+  options: { since: string; page: number; ifModifiedSince: string | undefined },
+) {
+  return requestUnlessNotModified(() =>
+    client.request("GET /notifications", {
+      all: true,
+      since: options.since,
+      per_page: NOTIFICATIONS_PAGE_SIZE,
+      page: options.page,
+      headers:
+        options.ifModifiedSince === undefined
+          ? GITHUB_API_HEADERS
+          : { ...GITHUB_API_HEADERS, "If-Modified-Since": options.ifModifiedSince },
+    }),
+  );
+}
+
+async function requestUnlessNotModified<T>(request: () => Promise<T>): Promise<T | "not_modified"> {
+  try {
+    return await request();
+  } catch (error) {
+    // Octokit reports 304 Not Modified as a failed request.
+    if ((error as { status?: unknown }).status === 304) {
+      return "not_modified" as const;
+    }
+
+    throw error;
+  }
+}
+
+function readPollIntervalHeaderMs(value: unknown): number | undefined {
+  const seconds = value === undefined ? Number.NaN : Number(value);
+
+  return Number.isInteger(seconds) && seconds > 0 ? seconds * 1000 : undefined;
+}
+
+function parsePullRequestApiUrl(value: string | null): PullRequestCoordinates | null {
+  if (value === null) {
+    return null;
+  }
+
+  let url: URL;
+
+  try {
+    url = new URL(value);
+  } catch {
+    return null;
+  }
+
+  const match = url.pathname.match(/\/repos\/([^/]+)\/([^/]+)\/pulls\/(\d+)$/);
+  const repositoryOwner = match?.[1];
+  const repositoryName = match?.[2];
+  const number = match?.[3];
+
+  if (!repositoryOwner || !repositoryName || !number) {
+    return null;
+  }
+
+  return {
+    repositoryOwner,
+    repositoryName,
+    number: Number(number),
+  };
+}
+
+export function parsePullRequestHtmlUrl(value: string): PullRequestCoordinates | null {
+  let url: URL;
+
+  try {
+    url = new URL(value);
+  } catch {
+    return null;
+  }
+
+  const match = url.pathname.match(/^\/([^/]+)\/([^/]+)\/pull\/(\d+)(?:\/|$)/);
+  const repositoryOwner = match?.[1];
+  const repositoryName = match?.[2];
+  const number = match?.[3];
+
+  if (!repositoryOwner || !repositoryName || !number) {
+    return null;
+  }
+
+  return {
+    repositoryOwner,
+    repositoryName,
+    number: Number(number),
+  };
+}
+
+function formatPullRequestLabel(coordinates: PullRequestCoordinates): string {
+  return `${coordinates.repositoryOwner}/${coordinates.repositoryName}#${coordinates.number}`;
+}
@@ -0,0 +1,112 @@
+import { setTimeout as sleep } from "node:timers/promises";
+
+import { GitHubAuthError, readStatusCode } from "./github.js";
+
+const DEFAULT_RETRY_DELAYS_MS = [5_000, 15_000, 30_000, 60_000];
+const NETWORK_ERROR_CODES = new Set([
+  "ENOTFOUND",
+  "EAI_AGAIN",
+  "ECONNREFUSED",
+  "ECONNRESET",
+  "ETIMEDOUT",
+  "ENETUNREACH",
+  "EHOSTUNREACH",
+  "UND_ERR_CONNECT_TIMEOUT",
+]);
+const MAX_CAUSE_DEPTH = 5;
+
+export type GitHubConnectivityProblem = "no_network" | "bad_token" | "github_down";
+
+export interface WaitForGitHubReadinessOptions {
+  // Called after every failed attempt, before waiting to retry.
+  onProblem: (problem: GitHubConnectivityProblem, error: GitHubAuthError, attempt: number) => void;
+  // Delays between attempts; the last one repeats until GitHub is reachable.
+  retryDelaysMs?: readonly number[];
+  sleep?: (delayMs: number) => Promise<unknown>;
+}
+
+// Runs the startup GitHub check until it passes instead of exiting on the first failure, so a
+// laptop that boots before the network is up, or a GitHub outage, only delays startup.
+export async function waitForGitHubReadiness<T>(
+  check: () => Promise<T>,
+  options: WaitForGitHubReadinessOptions,
+): Promise<T> {
+  const retryDelaysMs = options.retryDelaysMs ?? DEFAULT_RETRY_DELAYS_MS;
+  const wait = options.sleep ?? sleep;
+
+  for (let attempt = 1; ; attempt += 1) {
+    try {
+      return await check();
+    } catch (error) {
+      if (!(error instanceof GitHubAuthError)) {
+        throw error;
+      }
+
+      options.onProblem(classifyGitHubConnectivityFailure(error), error, attempt);
+      await wait(
+        retryDelaysMs[Math.min(attempt, retryDelaysMs.length) - 1] ?? DEFAULT_RETRY_DELAYS_MS[0]!,
+      );
+    }
+  }
+}
+
+export function classifyGitHubConnectivityFailure(error: unknown): GitHubConnectivityProblem {
+  // Octokit reports a failed fetch as a 500, so network errors are looked for before status codes.
+  if (isNetworkError(error)) {
+    return "no_network";
+  }
+
+  const statusCode = readStatusCode(error) ?? readStatusCode(readCause(error));
+
+  if (statusCode === 401 || statusCode === 403) {
+    return "bad_token";
+  }
+
+  return "github_down";
+}
+
+export function describeGitHubConnectivityProblem(
+  problem: GitHubConnectivityProblem,
+  configPath: string,
+): { title: string; body: string } {
+  switch (problem) {
+    case "no_network":
+      return {
+        title: "Octopulse can't reach GitHub",
+        body: "There is no network connection to api.github.com. Octopulse keeps retrying and starts once it's back.",
+      };
+    case "bad_token":
+      return {
+        title: "GitHub rejected Octopulse's token",
+        body: `Check github.token in ${configPath}. Octopulse keeps retrying and picks up a corrected token on its own.`,
+      };
+    case "github_down":
+      return {
+        title: "GitHub is not responding normally",
+        body: "GitHub's API returned an error, possibly an outage (see githubstatus.com). Octopulse keeps retrying.",
+      };
+  }
+}
+
+function isNetworkError(error: unknown): boolean {
+  let current = error;
+
+  for (let depth = 0; depth < MAX_CAUSE_DEPTH && current instanceof Error; depth += 1) {
+    const code = "code" in current ? current.code : undefined;
+
+    if (
+      (typeof code === "string" && NETWORK_ERROR_CODES.has(code)) ||
+      /fetch failed/i.test(current.message)
+    ) {
+      return true;
+    }
+
+    current = current.cause;
+  }
+
+  return false;
+}
+
+function readCause(error: unknown): unknown {
+  return error instanceof Error ? error.cause : undefined;
+}
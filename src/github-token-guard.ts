@@ -0,0 +1,196 @@
+import { readStatusCode } from "./github.js";
+import { getLogger } from "./logger.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+
+export const GITHUB_TOKEN_EXPIRATION_HEADER = "github-authentication-token-expiration";
+export const GITHUB_TOKEN_SETTINGS_URL = "https://github.com/settings/tokens";
+
+const DEFAULT_RECHECK_INTERVAL_MS = 60_000;
+const DEFAULT_EXPIRY_WARNING_MS = 7 * 24 * 60 * 60_000;
+const DAY_MS = 24 * 60 * 60_000;
+
+// The slice of the Octokit client the guard hooks into, so tests can fake it.
+export interface GitHubRequestHookClient {
+  hook: {
+    after(name: "request", hook: (response: { headers: Record<string, unknown> }) => void): void;
+    error(name: "request", hook: (error: Error) => never | Promise<never>): void;
+  };
+}
+
+export interface GitHubTokenGuardOptions {
+  notificationDispatcher: NotificationDispatcher;
+  // Stops the pollers while GitHub rejects the token, so each cycle doesn't fail again.
+  onAuthenticationLost: () => void;
+  onAuthenticationRestored: () => void;
+  // Tries to authenticate again, e.g. with a token re-read from the config file. Rejects while
+  // GitHub still refuses it.
+  reauthenticate: () => Promise<void>;
+  configPath: string;
+  // Where the popups send you to replace the token, e.g. on a GitHub Enterprise Server instance.
+  tokenSettingsUrl?: string;
+  recheckIntervalMs?: number;
+  // How far ahead of a token's expiration date to warn about it.
+  expiryWarningMs?: number;
+  now?: () => number;
+}
+
+export interface GitHubTokenGuardHandle {
+  isAuthenticationLost(): boolean;
+  stop(): void;
+}
+
+// Watches every GitHub response for a rejected token or an approaching expiration date. A 401 pauses
+// polling behind one sticky popup that opens the token settings page, and polling resumes on its
+// own once a replacement token set in the config file is accepted.
+export function startGitHubTokenGuard(
+  client: GitHubRequestHookClient,
+  options: GitHubTokenGuardOptions,
+): GitHubTokenGuardHandle {
+  const now = options.now ?? Date.now;
+  const recheckIntervalMs = options.recheckIntervalMs ?? DEFAULT_RECHECK_INTERVAL_MS;
+  const expiryWarningMs = options.expiryWarningMs ?? DEFAULT_EXPIRY_WARNING_MS;
+  const tokenSettingsUrl = options.tokenSettingsUrl ?? GITHUB_TOKEN_SETTINGS_URL;
+  let recheckTimer: ReturnType<typeof setInterval> | undefined;
+  let isRechecking = false;
+  let isStopped = false;
+  let warnedExpiration: string | undefined;
+
+  client.hook.after("request", (response) => {
+    const expiration = response.headers[GITHUB_TOKEN_EXPIRATION_HEADER];
+
+    if (!isStopped && typeof expiration === "string") {
+      warnIfExpiringSoon(expiration);
+    }
+  });
+  client.hook.error("request", (error) => {
+    if (!isStopped && readStatusCode(error) === 401) {
+      loseAuthentication();
+    }
+
+    throw error;
+  });
+
+  return {
+    isAuthenticationLost(): boolean {
+      return recheckTimer !== undefined;
+    },
+    stop(): void {
+      if (isStopped) {
+        return;
+      }
+
+      isStopped = true;
+      stopRechecking();
+    },
+  };
+
+  function loseAuthentication(): void {
+    if (recheckTimer !== undefined) {
+      return;
+    }
+
+    getLogger().warn("GitHub rejected the configured token; pausing polling", {
+      recheckIntervalMs,
+    });
+    options.onAuthenticationLost();
+    recheckTimer = setInterval(() => {
+      void recheck();
+    }, recheckIntervalMs);
+    recheckTimer.unref?.();
+    show({
+      title: "GitHub token expired — click to re-authenticate",
+      body: `GitHub rejected Octopulse's token, so polling is paused. Create a new token, set github.token in ${options.configPath}, and polling resumes on its own.`,
+      clickUrl: tokenSettingsUrl,
+      sticky: true,
+      urgency: "critical",
+    });
+  }
+
+  async function recheck(): Promise<void> {
+    if (isStopped || isRechecking) {
+      return;
+    }
+
+    isRechecking = true;
+
+    try {
+      await options.reauthenticate();
+
+      if (isStopped) {
+        return;
+      }
+
+      stopRechecking();
+      getLogger().info("GitHub accepted the token again; resuming polling");
+      options.onAuthenticationRestored();
+      show({
+        title: "GitHub token accepted",
+        body: "Octopulse re-authenticated with GitHub and resumed polling.",
+      });
+    } catch (error) {
+      getLogger().debug("GitHub re-authentication attempt failed", { error });
+    } finally {
+      isRechecking = false;
+    }
+  }
+
+  function stopRechecking(): void {
+    if (recheckTimer !== undefined) {
+      clearInterval(recheckTimer);
+      recheckTimer = undefined;
+    }
+  }
+
+  function warnIfExpiringSoon(expiration: string): void {
+    const expiresAt = parseTokenExpiration(expiration);
+
+    if (expiresAt === null || warnedExpiration === expiration) {
+      return;
+    }
+
+    const remainingMs = expiresAt.getTime() - now();
+
+    if (remainingMs > expiryWarningMs) {
+      return;
+    }
+
+    warnedExpiration = expiration;
+    getLogger().warn("GitHub token expires soon", { expiresAt: expiresAt.toISOString() });
+    show({
+      title: `GitHub token expires ${formatRemainingTime(remainingMs)}`,
+      body: `Octopulse stops receiving notifications once it expires. Regenerate the token and set github.token in ${options.configPath}.`,
+      clickUrl: tokenSettingsUrl,
+    });
+  }
+
+  function show(notification: Parameters<NotificationDispatcher["dispatchNotification"]>[0]): void {
+    options.notificationDispatcher.dispatchNotification(notification).catch((error: unknown) => {
+      getLogger().warn("Failed to show GitHub token popup", { error });
+    });
+  }
+}
+
+// GitHub sends the expiration of tokens that have one as e.g. "2026-10-20 12:00:00 UTC".
+export function parseTokenExpiration(value: string): Date | null {
+  const match = /^(\d{4}-\d{2}-\d{2}) (\d{2}:\d{2}:\d{2}) (UTC|[+-]\d{4})$/.exec(value.trim());
+
+  if (match === null) {
+    return null;
+  }
+
+  const [, date, time, zone] = match;
+  const offset = zone === "UTC" ? "Z" : `${zone!.slice(0, 3)}:${zone!.slice(3)}`;
+  const expiresAt = new Date(`${date}T${time}${offset}`);
+
+  return Number.isNaN(expiresAt.getTime()) ? null : expiresAt;
+}
+
+function formatRemainingTime(remainingMs: number): string {
+  if (remainingMs <= 0) {
+    return "today";
+  }
+
+  const days = Math.ceil(remainingMs / DAY_MS);
+
+  return days === 1 ? "within a day" : `in ${days} days`;
+}
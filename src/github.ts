@@ -1,37 +1,135 @@
 import { Octokit } from "octokit";
 
+import { createClientCertificateFetch } from "./client-certificate-fetch.js";
 import type { AppConfig } from "./config.js";
+import { createGitHubAppTokenProvider, GitHubAppAuthError } from "./github-app-auth.js";
 
 export interface GitHubAuthContext<TClient = Octokit> {
   client: TClient;
   currentUserLogin: string;
+  // Scopes GitHub reported for a classic token. Fine-grained tokens don't report any.
+  tokenScopes?: string[];
+}
+
+// The slice of the Octokit client used for GraphQL-only lookups, so tests can fake it.
+export interface GitHubGraphqlClient {
+  graphql(query: string, parameters: Record<string, unknown>): Promise<unknown>;
+}
+
+// Either a token, or a GitHub App whose installation tokens are fetched as needed, for the API at
+// githubApiUrl, presenting network.clientCertificate when set.
+export type GitHubCredentials = Pick<AppConfig, "githubToken" | "githubApp" | "githubApiUrl"> &
+  Partial<Pick<AppConfig, "network">>;
+
+export interface GitHubClientOptions {
+  // The REST API of a GitHub Enterprise Server instance instead of api.github.com.
+  apiUrl?: string;
+  fetchImpl?: typeof fetch;
 }
 
 export interface InitializeGitHubAuthOptions<TClient = Octokit> {
-  clientFactory?: (token: string) => TClient;
-  currentUserResolver?: (client: TClient) => Promise<{ login: unknown }>;
+  clientFactory?: (credentials: GitHubCredentials) => TClient;
+  currentUserResolver?: (client: TClient) => Promise<CurrentUser>;
+}
+
+interface CurrentUser {
+  login: unknown;
+  scopes?: string[];
 }
 
+// Broader classic token scopes that cover narrower ones.
+const IMPLIED_TOKEN_SCOPES: Record<string, readonly string[]> = {
+  repo: [
+    "public_repo",
+    "repo:status",
+    "repo_deployment",
+    "repo:invite",
+    "security_events",
+    "notifications",
+  ],
+  project: ["read:project"],
+};
+
+const MISSING_TOKEN_SCOPE_EFFECTS: Record<string, string> = {
+  repo: "pull requests in private repositories are invisible",
+  notifications: "github.com notifications can't be read or marked read",
+  security_events: "code scanning and secret scanning alerts can't be read",
+  "read:project": "GitHub project status can't be read",
+};
+
 export class GitHubAuthError extends Error {
-  constructor(message: string) {
-    super(message);
+  constructor(message: string, options?: ErrorOptions) {
+    super(message, options);
     this.name = "GitHubAuthError";
   }
 }
 
-export function createGitHubClient(token: string): Octokit {
-  return new Octokit({ auth: token });
+// A token reader is called before every request, so a replaced token takes effect without
+// recreating the client everything else holds on to.
+export function createGitHubClient(
+  token: string | (() => string | Promise<string>),
+  options: GitHubClientOptions = {},
+): Octokit {
+  const clientOptions = {
+    ...(options.apiUrl === undefined ? {} : { baseUrl: options.apiUrl }),
+    ...(options.fetchImpl === undefined ? {} : { request: { fetch: options.fetchImpl } }),
+  };
+
+  if (typeof token === "string") {
+    return new Octokit({ auth: token, ...clientOptions });
+  }
+
+  const client = new Octokit(clientOptions);
+
+  client.hook.before("request", async (options) => {
+    options.headers.authorization = `token ${await token()}`;
+  });
+
+  return client;
+}
+
+export function createConfiguredGitHubClient(credentials: GitHubCredentials): Octokit {
+  const clientOptions = readGitHubClientOptions(credentials);
+
+  return createGitHubClient(
+    credentials.githubApp === undefined
+      ? requireGitHubToken(credentials)
+      : createGitHubAppTokenProvider(credentials.githubApp, clientOptions),
+    clientOptions,
+  );
+}
+
+// The API URL and, for servers that require mutual TLS, a fetch presenting the client certificate.
+export function readGitHubClientOptions(credentials: GitHubCredentials): GitHubClientOptions {
+  const clientCertificate = credentials.network?.clientCertificate;
+
+  return {
+    ...(credentials.githubApiUrl === undefined ? {} : { apiUrl: credentials.githubApiUrl }),
+    ...(clientCertificate === undefined
+      ? {}
+      : { fetchImpl: createClientCertificateFetch(clientCertificate) }),
+  };
+}
+
+// Config loading sets a token whenever no GitHub App is configured.
+export function requireGitHubToken(credentials: GitHubCredentials): string {
+  if (credentials.githubToken === undefined) {
+    throw new GitHubAuthError("GitHub authentication failed: no GitHub token is configured");
+  }
+
+  return credentials.githubToken;
 }
 
 export async function initializeGitHubAuth<TClient = Octokit>(
-  config: Pick<AppConfig, "githubToken" | "githubLogin">,
+  config: GitHubCredentials & Pick<AppConfig, "githubLogin">,
   options: InitializeGitHubAuthOptions<TClient> = {},
 ): Promise<GitHubAuthContext<TClient>> {
   const clientFactory =
-    options.clientFactory ?? ((token: string) => createGitHubClient(token) as TClient);
+    options.clientFactory ??
+    ((credentials: GitHubCredentials) => createConfiguredGitHubClient(credentials) as TClient);
   const currentUserResolver =
     options.currentUserResolver ?? ((client: TClient) => resolveCurrentUser(client as Octokit));
-  const client = clientFactory(config.githubToken);
+  const client = clientFactory(config);
 
   if (config.githubLogin !== undefined) {
     return {
@@ -46,6 +144,7 @@ export async function initializeGitHubAuth<TClient = Octokit>(
     return {
       client,
       currentUserLogin: readCurrentUserLogin(currentUser),
+      ...(currentUser.scopes === undefined ? {} : { tokenScopes: currentUser.scopes }),
     };
   } catch (error) {
     if (error instanceof GitHubAuthError) {
@@ -56,19 +155,115 @@ export async function initializeGitHubAuth<TClient = Octokit>(
   }
 }
 
-async function resolveCurrentUser(client: Octokit): Promise<{ login: unknown }> {
+// A cheap authenticated request for when the login is configured and GET /user is skipped.
+// Resolves to the scopes GitHub reported for a classic token, like initializeGitHubAuth.
+export async function checkGitHubConnectivity(
+  client: Octokit,
+  token: string | undefined,
+): Promise<string[] | undefined> {
+  try {
+    const response = await client.request("GET /rate_limit", {
+      headers: {
+        "X-GitHub-Api-Version": "2022-11-28",
+      },
+    });
+
+    return parseTokenScopesHeader(response.headers["x-oauth-scopes"]);
+  } catch (error) {
+    throw createGitHubAuthError(error, token);
+  }
+}
+
+// Checks a token with a client of its own, e.g. a replacement read from the config file.
+// Resolves to the login the token belongs to.
+export async function verifyGitHubToken(
+  token: string,
+  options: GitHubClientOptions = {},
+): Promise<string> {
+  try {
+    return readCurrentUserLogin(await resolveCurrentUser(createGitHubClient(token, options)));
+  } catch (error) {
+    if (error instanceof GitHubAuthError) {
+      throw error;
+    }
+
+    throw createGitHubAuthError(error, token);
+  }
+}
+
+// The token scopes the configured features rely on, so a classic token missing one is reported at
+// startup instead of showing up as an empty notification list.
+export function listRequiredTokenScopes(
+  config: Pick<AppConfig, "securityAlertRepositories" | "inboxRepositories" | "githubProject"> & {
+    notifications: Pick<
+      AppConfig["notifications"],
+      "markGitHubReadAfterDisplay" | "syncGitHubReadState" | "githubInbox"
+    >;
+  },
+): string[] {
+  return [
+    "repo",
+    ...(config.notifications.markGitHubReadAfterDisplay ||
+    config.notifications.syncGitHubReadState ||
+    config.notifications.githubInbox !== "off" ||
+    config.inboxRepositories.repositories.length > 0
+      ? ["notifications"]
+      : []),
+    ...(config.securityAlertRepositories.length > 0 ? ["security_events"] : []),
+    ...(config.githubProject ? ["read:project"] : []),
+  ];
+}
+
+export function findMissingTokenScopes(
+  grantedScopes: readonly string[],
+  requiredScopes: readonly string[],
+): string[] {
+  const coveredScopes = new Set(
+    grantedScopes.flatMap((scope) => [scope, ...(IMPLIED_TOKEN_SCOPES[scope] ?? [])]),
+  );
+
+  return requiredScopes.filter((scope) => !coveredScopes.has(scope));
+}
+
+export function formatMissingTokenScopesWarning(
+  missingScopes: readonly string[],
+  tokenFieldPath = "github.token",
+): string {
+  const effects = missingScopes.map(
+    (scope) => `${scope} (${MISSING_TOKEN_SCOPE_EFFECTS[scope] ?? "some features won't work"})`,
+  );
+
+  const isSingle = missingScopes.length === 1;
+
+  return `The GitHub token is missing ${isSingle ? "the scope" : "scopes"} ${effects.join(", ")}. Regenerate it with ${isSingle ? "this scope" : "these scopes"} and update ${tokenFieldPath}.`;
+}
+
+export function parseTokenScopesHeader(value: unknown): string[] | undefined {
+  if (typeof value !== "string") {
+    return undefined;
+  }
+
+  return value
+    .split(",")
+    .map((scope) => scope.trim())
+    .filter((scope) => scope.length > 0);
+}
+
+async function resolveCurrentUser(client: Octokit): Promise<CurrentUser> {
   const response = await client.request("GET /user", {
     headers: {
       "X-GitHub-Api-Version": "2022-11-28",
     },
   });
+  const scopes = parseTokenScopesHeader(response.headers["x-oauth-scopes"]);
 
   return {
     login: response.data.login,
+    ...(scopes === undefined ? {} : { scopes }),
   };
 }
 
-function readCurrentUserLogin(currentUser: { login: unknown }): string {
+function readCurrentUserLogin(currentUser: CurrentUser): string {
   if (typeof currentUser.login !== "string" || currentUser.login.trim().length === 0) {
     throw new GitHubAuthError(
       "GitHub authentication failed: GitHub did not return a valid user login",
@@ -78,27 +273,35 @@ function readCurrentUserLogin(currentUser: { login: unknown }): string {
   return currentUser.login;
 }
 
-function createGitHubAuthError(error: unknown, token: string): GitHubAuthError {
+function createGitHubAuthError(error: unknown, token: string | undefined): GitHubAuthError {
+  // Says whether the app itself or its private key was the problem.
+  if (error instanceof GitHubAppAuthError) {
+    return new GitHubAuthError(error.message, { cause: error });
+  }
+
   const statusCode = readStatusCode(error);
 
   if (statusCode === 401) {
     return new GitHubAuthError(
       "GitHub authentication failed: invalid token or insufficient github.com access",
+      { cause: error },
     );
   }
 
   if (statusCode === 403) {
     return new GitHubAuthError(
       "GitHub authentication failed: GitHub denied access for the configured token",
+      { cause: error },
     );
   }
 
   return new GitHubAuthError(
     `GitHub authentication failed: ${sanitizeErrorMessage(error, token)}`,
+    { cause: error },
   );
 }
 
-function readStatusCode(error: unknown): number | undefined {
+export function readStatusCode(error: unknown): number | undefined {
   if (typeof error !== "object" || error === null || !("status" in error)) {
     return undefined;
   }
@@ -107,10 +310,10 @@ function readStatusCode(error: unknown): number | undefined {
   return typeof status === "number" ? status : undefined;
 }
 
-function sanitizeErrorMessage(error: unknown, token: string): string {
+function sanitizeErrorMessage(error: unknown, token: string | undefined): string {
   if (!(error instanceof Error) || error.message.trim().length === 0) {
     return "unknown authentication error";
   }
 
-  return error.message.replaceAll(token, "[REDACTED]");
+  return token === undefined ? error.message : error.message.replaceAll(token, "[REDACTED]");
 }
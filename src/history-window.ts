@@ -0,0 +1,58 @@
+import { spawn } from "node:child_process";
+
+import { openUrl } from "./open-url.js";
+import { DEFAULT_SERVER_HOST, DEFAULT_SERVER_PORT } from "./server.js";
+
+// Chromium-family browsers render --app URLs in a bare window without tabs or an address bar.
+const APP_WINDOW_BROWSERS = ["chromium", "chromium-browser", "google-chrome", "brave-browser"];
+
+export type LaunchBrowser = (command: string, args: readonly string[]) => Promise<boolean>;
+export type HistoryWindowPage = "notification-history" | "inbox";
+
+export interface OpenHistoryWindowOptions {
+  serverOrigin?: string;
+  page?: HistoryWindowPage;
+  launchBrowser?: LaunchBrowser;
+  openUrl?: (url: string) => Promise<void>;
+}
+
+export function buildHistoryWindowUrl(
+  serverOrigin: string,
+  page: HistoryWindowPage = "notification-history",
+): string {
+  return `${serverOrigin}/${page}`;
+}
+
+export async function openHistoryWindow(options: OpenHistoryWindowOptions = {}): Promise<string> {
+  const url = buildHistoryWindowUrl(
+    options.serverOrigin ?? `http://${DEFAULT_SERVER_HOST}:${DEFAULT_SERVER_PORT}`,
+    options.page,
+  );
+  const launchBrowser = options.launchBrowser ?? launchDetachedBrowser;
+
+  for (const browser of APP_WINDOW_BROWSERS) {
+    if (await launchBrowser(browser, [`--app=${url}`])) {
+      return url;
+    }
+  }
+
+  await (options.openUrl ?? openUrl)(url);
+  return url;
+}
+
+function launchDetachedBrowser(command: string, args: readonly string[]): Promise<boolean> {
+  return new Promise((resolve) => {
+    const child = spawn(command, [...args], {
+      detached: true,
+      stdio: "ignore",
+    });
+
+    child.once("error", () => {
+      resolve(false);
+    });
+    child.once("spawn", () => {
+      child.unref();
+      resolve(true);
+    });
+  });
+}
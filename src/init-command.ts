@@ -0,0 +1,225 @@
+import { chmodSync, existsSync, mkdirSync, writeFileSync } from "node:fs";
+import path from "node:path";
+import { createInterface } from "node:readline/promises";
+
+import { loadConfig } from "./config.js";
+import { GitHubAuthError, verifyGitHubToken } from "./github.js";
+import { installUserService, type InstallUserServiceResult } from "./install-user-service.js";
+import { isValidTimeZone } from "./working-hours.js";
+
+const MAX_TOKEN_ATTEMPTS = 3;
+const WORKDAYS = ["monday", "tuesday", "wednesday", "thursday", "friday"] as const;
+const WORKING_HOURS_PATTERN = /^([01]\d|2[0-3]):[0-5]\d-([01]\d|2[0-3]):[0-5]\d$/;
+
+export interface InitPrompter {
+  ask(question: string): Promise<string>;
+  close(): void;
+}
+
+export interface InitAnswers {
+  githubToken: string;
+  playSounds: boolean;
+  workingHours?: {
+    timeZone: string;
+    hours: string;
+    outsideHours: "silent" | "off";
+  };
+}
+
+export interface RunInitCommandOptions {
+  configPath: string;
+  prompter: InitPrompter;
+  verifyToken?: (token: string) => Promise<string>;
+  installAutostart?: (configPath: string) => InstallUserServiceResult;
+  defaultTimeZone?: string;
+}
+
+export class InitCommandError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "InitCommandError";
+  }
+}
+
+// Walks a new user through the settings Octopulse can't guess, writes a working config file, and
+// optionally installs the systemd user service that starts it with the desktop session.
+export async function runInitCommand(options: RunInitCommandOptions): Promise<string> {
+  const { prompter } = options;
+  const verifyToken = options.verifyToken ?? verifyGitHubToken;
+  const output: string[] = [];
+
+  try {
+    if (
+      existsSync(options.configPath) &&
+      !(await askYesNo(prompter, `${options.configPath} already exists. Replace it?`, false))
+    ) {
+      return `Kept existing config at ${options.configPath}`;
+    }
+
+    const { githubToken, login } = await askGitHubToken(prompter, verifyToken);
+    output.push(`Authenticated as ${login}.`);
+
+    const answers: InitAnswers = {
+      githubToken,
+      playSounds: await askYesNo(
+        prompter,
+        "Play sounds for approvals, change requests, and comments on your pull requests?",
+        true,
+      ),
+    };
+    const workingHours = await askWorkingHours(
+      prompter,
+      options.defaultTimeZone ?? Intl.DateTimeFormat().resolvedOptions().timeZone,
+    );
+
+    if (workingHours !== undefined) {
+      answers.workingHours = workingHours;
+    }
+
+    writeInitConfig(options.configPath, answers);
+    // Parse what was written, so a wizard bug surfaces here instead of at the next start.
+    loadConfig({ configPath: options.configPath });
+    output.push(`Wrote ${options.configPath}`);
+
+    if (await askYesNo(prompter, "Start Octopulse automatically with your desktop session?", true)) {
+      const result = (options.installAutostart ?? installAutostart)(options.configPath);
+
+      output.push(
+        `Installed user service at ${result.paths.servicePath}`,
+        "Run: npm run build && systemctl --user daemon-reload && systemctl --user enable --now octopulse.service",
+      );
+    }
+
+    return output.join("\n");
+  } finally {
+    prompter.close();
+  }
+}
+
+export function renderInitConfig(answers: InitAnswers): string {
+  const lines = [
+    "# Written by `octopulse init`. See the README for every option.",
+    "[github]",
+    `token = ${JSON.stringify(answers.githubToken)}`,
+  ];
+
+  if (!answers.playSounds) {
+    lines.push("", "[sounds]", 'approved = ""', 'changes_requested = ""', 'comment = ""');
+  }
+
+  if (answers.workingHours !== undefined) {
+    lines.push(
+      "",
+      "[schedule]",
+      `timezone = ${JSON.stringify(answers.workingHours.timeZone)}`,
+      `outside_hours = ${JSON.stringify(answers.workingHours.outsideHours)}`,
+      ...WORKDAYS.map((day) => `${day} = ${JSON.stringify(answers.workingHours!.hours)}`),
+    );
+  }
+
+  return `${lines.join("\n")}\n`;
+}
+
+export function createConsoleInitPrompter(): InitPrompter {
+  const readline = createInterface({ input: process.stdin, output: process.stdout });
+
+  return {
+    ask: (question) => readline.question(question),
+    close: () => readline.close(),
+  };
+}
+
+async function askGitHubToken(
+  prompter: InitPrompter,
+  verifyToken: (token: string) => Promise<string>,
+): Promise<{ githubToken: string; login: string }> {
+  for (let attempt = 1; attempt <= MAX_TOKEN_ATTEMPTS; attempt += 1) {
+    const githubToken = (
+      await prompter.ask("GitHub personal access token (https://github.com/settings/tokens): ")
+    ).trim();
+
+    if (githubToken.length === 0) {
+      continue;
+    }
+
+    try {
+      return { githubToken, login: await verifyToken(githubToken) };
+    } catch (error) {
+      if (!(error instanceof GitHubAuthError)) {
+        throw error;
+      }
+
+      console.error(error.message);
+    }
+  }
+
+  throw new InitCommandError("No working GitHub token was entered; nothing was written.");
+}
+
+async function askWorkingHours(
+  prompter: InitPrompter,
+  defaultTimeZone: string,
+): Promise<InitAnswers["workingHours"]> {
+  let hours: string;
+
+  do {
+    hours = (
+      await prompter.ask("Weekday working hours for popups, e.g. 09:00-17:30 (blank for always): ")
+    ).replaceAll(" ", "");
+  } while (hours.length > 0 && !WORKING_HOURS_PATTERN.test(hours));
+
+  if (hours.length === 0) {
+    return undefined;
+  }
+
+  let timeZone: string;
+
+  do {
+    timeZone = (await prompter.ask(`Time zone [${defaultTimeZone}]: `)).trim() || defaultTimeZone;
+  } while (!isValidTimeZone(timeZone));
+
+  const outsideHours = (await askYesNo(
+    prompter,
+    "Outside those hours, still show popups without sound?",
+    true,
+  ))
+    ? "silent"
+    : "off";
+
+  return { timeZone, hours, outsideHours };
+}
+
+async function askYesNo(
+  prompter: InitPrompter,
+  question: string,
+  defaultAnswer: boolean,
+): Promise<boolean> {
+  for (;;) {
+    const answer = (await prompter.ask(`${question} ${defaultAnswer ? "[Y/n]" : "[y/N]"} `))
+      .trim()
+      .toLowerCase();
+
+    if (answer.length === 0) {
+      return defaultAnswer;
+    }
+
+    if (answer === "y" || answer === "yes") {
+      return true;
+    }
+
+    if (answer === "n" || answer === "no") {
+      return false;
+    }
+  }
+}
+
+function writeInitConfig(configPath: string, answers: InitAnswers): void {
+  mkdirSync(path.dirname(configPath), { recursive: true });
+  // The file holds the token, so only its owner may read it.
+  writeFileSync(configPath, renderInitConfig(answers), { encoding: "utf8", mode: 0o600 });
+  chmodSync(configPath, 0o600);
+}
+
+function installAutostart(configPath: string): InstallUserServiceResult {
+  return installUserService({ configPath });
+}
@@ -2,11 +2,19 @@ import { existsSync, mkdirSync, writeFileSync } from "node:fs";
 import os from "node:os";
 import path from "node:path";
 
-import { resolveAppPaths, type ResolveAppPathsOptions } from "./config.js";
-import { DESKTOP_ENTRY_FILE_NAME, renderDesktopEntry } from "./desktop-entry.js";
+import {
+  resolveAppPaths,
+  resolveXdgBaseDirectory,
+  type ResolveAppPathsOptions,
+} from "./config.js";
+import {
+  DESKTOP_ENTRY_FILE_NAME,
+  PROTOCOL_MIME_TYPE,
+  renderDesktopEntry,
+  SYSTEM_NODE_EXECUTABLE_PATH,
+} from "./desktop-entry.js";
 
 const SERVICE_NAME = "octopulse.service";
-const SYSTEM_NODE_EXECUTABLE_PATH = "/usr/bin/node";
 
 export interface InstallUserServicePaths {
   repoRoot: string;
@@ -32,9 +40,12 @@ export interface ResolveInstallUserServicePathsOptions extends ResolveAppPathsOp
 export function resolveInstallUserServicePaths(
   options: ResolveInstallUserServicePathsOptions = {},
 ): InstallUserServicePaths {
-  const homeDir = options.homeDir ?? os.homedir();
+  const baseDirectoryOptions = {
+    homeDir: options.homeDir ?? os.homedir(),
+    env: options.homeDir === undefined ? process.env : {},
+  };
   const appPaths = resolveAppPaths({
-    homeDir,
+    ...baseDirectoryOptions,
     ...(options.configPath ? { configPath: options.configPath } : {}),
     ...(options.stateDirPath ? { stateDirPath: options.stateDirPath } : {}),
   });
@@ -42,10 +53,21 @@ export function resolveInstallUserServicePaths(
   return {
     repoRoot: path.resolve(options.repoRoot ?? process.cwd()),
     servicePath: path.resolve(
-      options.servicePath ?? path.join(homeDir, ".config", "systemd", "user", SERVICE_NAME),
+      options.servicePath ??
+        path.join(
+          resolveXdgBaseDirectory("config", baseDirectoryOptions),
+          "systemd",
+          "user",
+          SERVICE_NAME,
+        ),
     ),
     desktopEntryPath: path.resolve(
-      options.desktopEntryPath ?? path.join(homeDir, ".local", "share", "applications", DESKTOP_ENTRY_FILE_NAME),
+      options.desktopEntryPath ??
+        path.join(
+          resolveXdgBaseDirectory("data", baseDirectoryOptions),
+          "applications",
+          DESKTOP_ENTRY_FILE_NAME,
+        ),
     ),
     configPath: appPaths.configPath,
     stateDirPath: appPaths.stateDirPath,
@@ -82,10 +104,34 @@ export function renderExampleConfig(): string {
   return [
     "[github]",
     'token = "ghp_replace_with_your_token"',
+    '# Optional. Prefixes notification titles, e.g. "[work] acme/app #7 ...".',
+    '#label = "work"',
+    "# Optional. A GitHub Enterprise Server instance instead of github.com. The API defaults to",
+    "# <url>/api/v3; set api_url when it lives elsewhere.",
+    '#url = "https://github.example.com"',
+    '#api_url = "https://github.example.com/api/v3"',
+    "# Optional. Authenticate as a GitHub App installation instead of with a token.",
+    "# Replaces token and needs login set to the name notifications should treat as you.",
+    "#[github.app]",
+    "#id = 12345",
+    "#installation_id = 67890",
+    '#private_key_path = "/home/me/.config/octopulse/app.private-key.pem"',
+    "# Optional. More accounts polled alongside the one above; repeat the block for each.",
+    "#[[github.accounts]]",
+    '#label = "personal"',
+    '#token = "ghp_replace_with_another_token"',
+    '#login = "octocat"',
     "",
-    "# Optional file logging settings.",
+    "# Optional logging settings.",
     "#[logging]",
     '#level = "info"',
+    "# Override level for the console or the log files only.",
+    '#console_level = "info"',
+    '#file_level = "debug"',
+    '# "json" prints one JSON object per console line, for journald or log shippers.',
+    '#format = "text"',
+    "# Set to false to log to the console only; the logs page then has nothing to show.",
+    "#file = true",
     '#retention = "14 days"',
     "",
     "# Optional. Use only if bot comment/review classification is enabled.",
@@ -94,9 +140,155 @@ export function renderExampleConfig(): string {
     "",
     "# Optional timing overrides.",
     "#[timings]",
-    '#tracked_poll_interval = "1m"',
-    '#discovery_poll_interval = "5m"',
+    '#tracked_poll_interval = "1m" # at least 15s',
+    '#discovery_poll_interval = "5m" # at least 1m',
     '#grace_period = "7 days"',
+    "# How long downloaded avatars are reused before being fetched again.",
+    '#avatar_cache_ttl = "24h"',
+    "",
+    "# Optional. How many pull requests to poll or discover at once; popups stay in order.",
+    "#[performance]",
+    "#poll_concurrency = 4",
+    "# How many of a pull request's newest comments and reviews to read; busy ones skip the oldest.",
+    "#max_fetched_comments = 300",
+    '# "graphql" reads comments and reviews in one request per pull request instead of three REST',
+    "# listings, which is faster and saves rate limit, but sees at most the newest 100 of each.",
+    '#activity_api = "rest"',
+    "",
+    "# Optional. Port of the local UI server; give each profile that runs at the same time its own.",
+    "#[server]",
+    "#port = 3000",
+    "",
+    "# Optional. An HTTP proxy for GitHub requests; HTTPS_PROXY and NO_PROXY are used when unset.",
+    "#[network]",
+    '#proxy = "http://proxy.example.com:3128"',
+    '#no_proxy = ["github.example.com", ".corp.example.com"]',
+    "# Root certificates to trust alongside the system ones, and a client certificate for mutual TLS.",
+    '#ca_certificates_path = "/home/me/.config/octopulse/corporate-ca.pem"',
+    '#client_certificate_path = "/home/me/.config/octopulse/client.pem"',
+    '#client_key_path = "/home/me/.config/octopulse/client.key"',
+    "",
+    "# Optional notification behavior.",
+    "#[notifications]",
+    "# Mark the pull request's github.com notification threads read once the popup is shown.",
+    "# Requires a token with the notifications scope.",
+    "#mark_github_read_after_display = false",
+    "# Keep local history read flags and github.com thread read state in sync. Checks at the tracked",
+    "# poll interval, or GitHub's X-Poll-Interval if longer, and only downloads changed notifications.",
+    "#sync_github_read_state = false",
+    "# Hold popups while the session is locked and deliver them (or a digest) on unlock.",
+    "#buffer_while_locked = true",
+    "# Hold non-sticky popups once the session has been idle this long; release them on return.",
+    '#hold_when_idle_for = "10m"',
+    "# Wait this long before showing a pull request's popup so rapid-fire activity on it becomes one popup.",
+    '#aggregation_window = "20s"',
+    "# Show at most this many popups per minute; the rest stay in history and the tray menu, followed by",
+    '# one "N more suppressed" popup. Critical popups are never held back.',
+    "#max_popups_per_minute = 6",
+    "# Play at most one notification sound per this interval; popups inside it are shown silently.",
+    '#sound_cooldown = "30s"',
+    '# While a screen cast or fullscreen window is active: "show", "redact" bodies, or "suppress" until it ends.',
+    '#while_screen_shared = "redact"',
+    "# Alert right away, with the failing workflow and a link to its logs, when CI fails on your pull requests.",
+    "#ci_failure_alerts = false",
+    '# Show one popup per repository summarizing what each poll found ("acme/api: 2 PRs updated, 1 approved").',
+    "#group_by_repository = false",
+    '# Also track pull requests from your github.com notifications: "participating" ones, or "all" to add',
+    '# watched repositories and team mentions. Reasons narrow it, e.g. ["team_mention", "subscribed"].',
+    '#github_inbox = "off"',
+    "#github_inbox_reasons = []",
+    '# Replace the popup body layout on notification servers that show markup; see "Popup Templates" in the README.',
+    '#body_template = "<b>{{pullRequest.title}}</b>{{#paragraphs}}\\n{{actor}}: {{text}}{{/paragraphs}}"',
+    "",
+    '# Optional. Replace the sounds played on your own pull requests; "" silences one.',
+    "#[sounds]",
+    '#approved = "/usr/share/sounds/freedesktop/stereo/complete.oga"',
+    '#comment = ""',
+    "",
+    '# Optional. Full notifications during working hours; "silent" (no sound) or "off" otherwise.',
+    "#[schedule]",
+    '#timezone = "Europe/Berlin"',
+    '#outside_hours = "silent"',
+    '#monday = "09:00-17:30"',
+    '#tuesday = "09:00-12:00, 13:00-17:30"',
+    '#friday = "09:00-15:00"',
+    "# On Saturday and Sunday only @mentions and these repositories break through; the rest waits for a Monday summary.",
+    "#weekend_mode = true",
+    '#weekend_repositories = ["acme/production"]',
+    "",
+    "# Optional. Hold popups during busy calendar events and show a catch-up digest afterward.",
+    "# Accepts an http(s) URL or a local file path.",
+    "#[calendar]",
+    '#ics = "https://calendar.example.com/you/basic.ics"',
+    "",
+    '# Optional. Show each pull request\'s status on a GitHub Projects board ("In review", "Blocked").',
+    "#[github_project]",
+    '#owner = "acme"',
+    "#number = 5",
+    '#status_field = "Status"',
+    "",
+    "# Optional. Only notify about other people's pull requests when they touch files that",
+    "# CODEOWNERS assigns to you or one of these teams, or that match one of these path globs.",
+    "# Review requests and assignments still notify.",
+    "#[filters]",
+    "#owned_paths_only = true",
+    '#owner_teams = ["@acme/payments"]',
+    '#paths = ["services/payments/**"]',
+    "",
+    "# Optional. Route pull requests by the files they change, each channel with its own sound file and",
+    "# urgency (low, normal, or critical). The first channel matching a changed file wins.",
+    "#[[channels]]",
+    '#name = "backend"',
+    '#paths = ["services/**"]',
+    '#sound = "/usr/share/sounds/freedesktop/stereo/bell.oga"',
+    '#urgency = "critical"',
+    "",
+    '# Optional. Per-repository or per-owner settings: a different sound ("" for none), urgency, or',
+    "# mute = true to keep the notifications in history and the inbox without popups. A repository's own",
+    "# section wins over its owner's.",
+    '#[repositories."acme/api"]',
+    '#urgency = "critical"',
+    '#[repositories."dependabot-org"]',
+    "#mute = true",
+    "",
+    '# Optional. Popup buttons that write to GitHub (such as "Approve") ask for confirmation in a',
+    "# follow-up popup first. List only the actions that should keep asking; [] turns it off.",
+    '# "Merge" is offered on your approved pull requests once every check passes, and',
+    '# "Re-request review" once you push after a change request; add "rerequest_review" to confirm it.',
+    "#[actions]",
+    '#confirm = ["approve", "merge"]',
+    '#merge_method = "squash" # merge, squash, or rebase',
+    "# Quick-reaction buttons on comment popups: +1, -1, laugh, confused, heart, hooray, rocket, eyes.",
+    '#reactions = ["+1", "hooray"]',
+    "# Canned replies offered as buttons; inline review comments get the reply in their thread.",
+    '#replies = ["LGTM, merging", "Will look today"]',
+    "",
+    "# Optional. Raise critical popups for new code-scanning and secret-scanning alerts in these",
+    "# repositories. The token needs the security_events scope (or Code scanning and Secret scanning",
+    "# alerts read access for fine-grained tokens).",
+    "#[security_alerts]",
+    '#repositories = ["acme/api"]',
+    "",
+    "# Optional. Read these repositories' github.com notifications on their own, every poll_interval",
+    "# (at least 15s), to start tracking their new pull requests long before discovery would. Honors",
+    '# notifications.github_inbox_reasons and github_inbox = "participating". The token needs the',
+    "# notifications scope.",
+    "#[inbox_repositories]",
+    '#repositories = ["acme/production"]',
+    '#poll_interval = "1m"',
+    "",
+    "# Optional. Poll immediately when a smee.io-style relay forwards a GitHub webhook.",
+    "#[webhooks]",
+    '#relay_url = "https://smee.io/your-channel"',
+    "",
+    "# Optional. Named profiles, selected with --profile. A section a profile sets replaces the",
+    "# top-level one; the others are shared.",
+    "#[profiles.oss.github]",
+    '#token = "ghp_replace_with_another_token"',
+    "#[profiles.oss.filters]",
+    '#paths = ["docs/"]',
+    "#[profiles.oss.server]",
+    "#port = 3001",
     "",
   ].join("\n");
 }
@@ -122,6 +314,7 @@ export function renderInstallUserServiceSummary(
     `4. Run: systemctl --user enable --now ${serviceName}`,
     `5. Run: systemctl --user status ${serviceName}`,
     `6. Follow logs: journalctl --user -u ${serviceName} -f`,
+    `7. Optional: xdg-mime default ${DESKTOP_ENTRY_FILE_NAME} ${PROTOCOL_MIME_TYPE}`,
   ].join("\n");
 }
 
@@ -137,7 +330,7 @@ export function installUserService(
   writeFileSync(paths.servicePath, renderUserServiceUnit(paths.repoRoot), "utf8");
 
   mkdirSync(desktopEntryDirectoryPath, { recursive: true });
-  writeFileSync(paths.desktopEntryPath, renderDesktopEntry(), "utf8");
+  writeFileSync(paths.desktopEntryPath, renderDesktopEntry(paths.repoRoot), "utf8");
 
   mkdirSync(configDirectoryPath, { recursive: true });
 
@@ -1,21 +1,69 @@
+import { mkdtemp, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { pathToFileURL } from "node:url";
+
 import freedesktopNotifications from "freedesktop-notifications";
 
 import { FileAvatarCache, type AvatarImageCache } from "./avatar-cache.js";
 import { DESKTOP_ENTRY_ID } from "./desktop-entry.js";
+import { getLogger } from "./logger.js";
+import {
+  compileNotificationBodyTemplate,
+  type NotificationBodyTemplate,
+  type NotificationBodyTemplateContext,
+} from "./notification-body-template.js";
 import type { NotificationMarkup } from "./notification-rendering.js";
 import { openUrl } from "./open-url.js";
 
+export type NotificationUrgency = "low" | "normal" | "critical";
+
+// A write action offered as a popup button, such as approving the pull request.
+export interface NotificationAction {
+  key: string;
+  label: string;
+  // When set, a follow-up popup asks this question and `run` only happens after "Confirm".
+  confirmation?: string;
+  run(): Promise<void>;
+}
+
 export interface LinuxNotification {
   title: string;
+  actions?: NotificationAction[];
   body: string;
   clickUrl?: string | null;
+  // Normalized event types behind the popup, so wrappers can summarize it without re-reading them.
+  eventTypes?: string[];
+  expandedHtml?: string;
   icon?: string | null;
   markup?: NotificationMarkup;
+  notificationRecordId?: number;
+  // Set on pull request popups, for custom body templates.
+  pullRequest?: NotificationPullRequestDetails;
+  reason?: string;
   soundFile?: string;
   sticky?: boolean;
+  urgency?: NotificationUrgency;
+}
+
+export interface NotificationPullRequestDetails {
+  repository: string;
+  number: number;
+  title: string;
+  url: string;
+  author: string;
+  state: string;
+  draft: boolean;
+  headBranch: string | null;
+  baseBranch: string | null;
+  additions: number | null;
+  deletions: number | null;
 }
 
 const AUTO_DISMISS_TIMEOUT_MS = 10_000;
+const SHOW_ALL_ACTION = "show-all";
+const CONFIRM_ACTION = "confirm";
+const CANCEL_ACTION = "cancel";
 
 export interface LinuxNotificationDispatchResult {
   openedClickUrl: boolean;
@@ -27,6 +75,10 @@ export interface LinuxNotificationAdapterOptions {
   ) => Promise<LinuxNotificationDispatchResult>;
   avatarCache?: AvatarImageCache;
   avatarCacheDirPath?: string;
+  avatarCacheMaxAgeMs?: number;
+  // Replaces the built-in body layout on notification servers that support markup.
+  bodyTemplate?: string;
+  onNotificationOpened?: (notification: LinuxNotification) => void;
 }
 
 export class LinuxNotificationAdapterError extends Error {
@@ -41,16 +93,26 @@ export class LinuxNotificationAdapter {
     notification: LinuxNotification,
   ) => Promise<LinuxNotificationDispatchResult>;
   private readonly avatarCache: AvatarImageCache;
+  private readonly bodyTemplate: NotificationBodyTemplate | undefined;
+  private readonly onNotificationOpened: ((notification: LinuxNotification) => void) | undefined;
   private capabilitiesPromise: Promise<readonly string[]> | null = null;
 
   constructor(options: LinuxNotificationAdapterOptions = {}) {
-    this.avatarCache = options.avatarCache ?? new FileAvatarCache(
-      options.avatarCacheDirPath === undefined
+    this.avatarCache = options.avatarCache ?? new FileAvatarCache({
+      ...(options.avatarCacheDirPath === undefined
         ? {}
-        : { cacheDirPath: options.avatarCacheDirPath },
-    );
+        : { cacheDirPath: options.avatarCacheDirPath }),
+      ...(options.avatarCacheMaxAgeMs === undefined
+        ? {}
+        : { maxAgeMs: options.avatarCacheMaxAgeMs }),
+    });
+    this.bodyTemplate =
+      options.bodyTemplate === undefined
+        ? undefined
+        : compileNotificationBodyTemplate(options.bodyTemplate);
     this.dispatchNotificationImpl =
       options.dispatchNotification ?? this.defaultDispatch.bind(this);
+    this.onNotificationOpened = options.onNotificationOpened;
   }
 
   async dispatchNotification(
@@ -72,10 +134,14 @@ export class LinuxNotificationAdapter {
       summary: renderedNotification.summary,
       body: renderedNotification.body,
       // freedesktop-notifications sends its own 30s anti-leak close request for normal urgency.
-      urgency: notification.sticky ? "critical" : "normal",
-      actions: notification.clickUrl
-        ? { default: "Open" }
-        : {},
+      urgency: notification.urgency ?? (notification.sticky ? "critical" : "normal"),
+      actions: {
+        ...(notification.clickUrl ? { default: "Open" } : {}),
+        ...(notification.expandedHtml ? { [SHOW_ALL_ACTION]: "Show all" } : {}),
+        ...Object.fromEntries(
+          (notification.actions ?? []).map((action) => [action.key, action.label]),
+        ),
+      },
       ...(notification.icon ? { icon: notification.icon } : {}),
       ...(notification.soundFile ? { "sound-file": notification.soundFile } : {}),
       ...(notification.sticky ? { timeout: 0 } : { timeout: AUTO_DISMISS_TIMEOUT_MS }),
@@ -87,17 +153,24 @@ export class LinuxNotificationAdapter {
       },
     );
 
-    if (!notification.clickUrl) {
+    const notificationActions = notification.actions ?? [];
+
+    if (!notification.clickUrl && !notification.expandedHtml && notificationActions.length === 0) {
       await notif.push();
       return { openedClickUrl: false };
     }
 
     notif.on("action", (action: string) => {
-      if (action !== "default") {
-        return;
-      }
+      const notificationAction = notificationActions.find((candidate) => candidate.key === action);
 
-      void openUrl(notification.clickUrl!).catch(() => undefined);
+      if (action === "default" && notification.clickUrl) {
+        this.onNotificationOpened?.(notification);
+        void openUrl(notification.clickUrl).catch(() => undefined);
+      } else if (action === SHOW_ALL_ACTION && notification.expandedHtml) {
+        void openExpandedHtml(notification.expandedHtml).catch(() => undefined);
+      } else if (notificationAction !== undefined) {
+        void this.triggerNotificationAction(notificationAction);
+      }
     });
 
     await notif.push();
@@ -105,6 +178,49 @@ export class LinuxNotificationAdapter {
     return { openedClickUrl: false };
   }
 
+  private async triggerNotificationAction(action: NotificationAction): Promise<void> {
+    if (action.confirmation === undefined) {
+      await this.runNotificationAction(action);
+      return;
+    }
+
+    const confirmation = new freedesktopNotifications.Notification({
+      appName: "Octopulse",
+      summary: `Confirm: ${action.label}`,
+      body: action.confirmation,
+      urgency: "critical",
+      actions: { [CONFIRM_ACTION]: "Confirm", [CANCEL_ACTION]: "Cancel" },
+      timeout: 0,
+    });
+
+    confirmation.on("action", (choice: string) => {
+      if (choice === CONFIRM_ACTION) {
+        void this.runNotificationAction(action);
+      }
+    });
+
+    await confirmation.push().catch((error: unknown) => {
+      getLogger().warn("Failed to show action confirmation", { action: action.key, error });
+    });
+  }
+
+  private async runNotificationAction(action: NotificationAction): Promise<void> {
+    try {
+      await action.run();
+    } catch (error) {
+      getLogger().warn("Notification action failed", { action: action.key, error });
+      await new freedesktopNotifications.Notification({
+        appName: "Octopulse",
+        summary: `${action.label} failed`,
+        body: error instanceof Error ? error.message : String(error),
+        urgency: "normal",
+        timeout: AUTO_DISMISS_TIMEOUT_MS,
+      })
+        .push()
+        .catch(() => undefined);
+    }
+  }
+
   private async renderForServer(notification: LinuxNotification): Promise<{
     summary: string;
     body: string;
@@ -114,16 +230,30 @@ export class LinuxNotificationAdapter {
     if (!capabilities.includes("body-markup") || notification.markup === undefined) {
       return {
         summary: notification.title,
-        body: notification.body,
+        body: notification.reason
+          ? `${notification.body}\n\n${notification.reason}`
+          : notification.body,
       };
     }
 
+    const images = await resolveMarkupImages(notification.markup, {
+      avatarCache: this.avatarCache,
+      supportsImages: capabilities.includes("body-images"),
+    });
+
+    if (this.bodyTemplate !== undefined) {
+      const context = buildBodyTemplateContext(notification, notification.markup, images);
+
+      return { summary: "", body: this.bodyTemplate(context) };
+    }
+
+    const markupBody = formatMarkupBody(notification.markup, images);
+
     return {
       summary: "",
-      body: await buildMarkupBody(notification.markup, {
-        avatarCache: this.avatarCache,
-        supportsImages: capabilities.includes("body-images"),
-      }),
+      body: notification.reason
+        ? `${markupBody}\n\n<i>${escapeMarkup(notification.reason)}</i>`
+        : markupBody,
     };
   }
 
@@ -136,40 +266,101 @@ export class LinuxNotificationAdapter {
   }
 }
 
-async function buildMarkupBody(
+// Each thread gets its own private folder (mkdtemp creates it 0700), preferably under the
+// per-user runtime directory, so other local users can neither read the thread nor plant a file
+// in its place.
+async function openExpandedHtml(html: string): Promise<void> {
+  const directoryPath = await mkdtemp(
+    path.join(process.env.XDG_RUNTIME_DIR || os.tmpdir(), "octopulse-thread-"),
+  );
+  const filePath = path.join(directoryPath, "thread.html");
+
+  await writeFile(filePath, html, { encoding: "utf8", mode: 0o600 });
+  await openUrl(pathToFileURL(filePath).href);
+}
+
+interface MarkupImages {
+  header: string | null;
+  // One per markup paragraph, in the same order.
+  paragraphs: (string | null)[];
+}
+
+async function resolveMarkupImages(
   markup: NotificationMarkup,
   options: { avatarCache: AvatarImageCache; supportsImages: boolean },
-): Promise<string> {
-  const headerImage = await resolveAvatarImage(
-    options.avatarCache,
-    options.supportsImages,
-    markup.headerAvatarKey,
-    markup.headerAvatarUrl,
-  );
-  const renderedParagraphs = await Promise.all(
-    markup.paragraphs.map(async (paragraph) => {
-      const image = await resolveAvatarImage(
+): Promise<MarkupImages> {
+  const [header, ...paragraphs] = await Promise.all([
+    resolveAvatarImage(
+      options.avatarCache,
+      options.supportsImages,
+      markup.headerAvatarKey,
+      markup.headerAvatarUrl,
+    ),
+    ...markup.paragraphs.map((paragraph) =>
+      resolveAvatarImage(
         options.avatarCache,
         options.supportsImages,
         paragraph.actorAvatarKey,
         paragraph.actorAvatarUrl,
-      );
+      ),
+    ),
+  ]);
 
-      return formatMarkupParagraph({
-        image,
-        actorLogin: paragraph.actorLogin,
-        text: paragraph.text,
-      });
+  return { header: header ?? null, paragraphs };
+}
+
+function formatMarkupBody(markup: NotificationMarkup, images: MarkupImages): string {
+  const renderedParagraphs = markup.paragraphs.map((paragraph, index) =>
+    formatMarkupParagraph({
+      image: images.paragraphs[index] ?? null,
+      actorLogin: paragraph.actorLogin,
+      text: paragraph.text,
     }),
   );
 
   return [
-    formatMarkupHeader(markup.headerText, headerImage),
+    formatMarkupHeader(markup.headerText, images.header),
     "<b> </b>",
     renderedParagraphs.join("\n\n"),
   ].join("\n");
 }
 
+function buildBodyTemplateContext(
+  notification: LinuxNotification,
+  markup: NotificationMarkup,
+  images: MarkupImages,
+): NotificationBodyTemplateContext {
+  const escapeNullable = (value: string | null | undefined) =>
+    value === null || value === undefined ? null : escapeMarkup(value);
+  const { pullRequest } = notification;
+
+  return {
+    title: escapeMarkup(notification.title),
+    body: escapeMarkup(notification.body),
+    reason: escapeNullable(notification.reason),
+    url: escapeNullable(notification.clickUrl),
+    header: { text: escapeMarkup(markup.headerText), avatar: escapeNullable(images.header) },
+    paragraphs: markup.paragraphs.map((paragraph, index) => ({
+      actor: escapeNullable(paragraph.actorLogin),
+      text: escapeMarkup(paragraph.text),
+      avatar: escapeNullable(images.paragraphs[index]),
+    })),
+    pullRequest:
+      pullRequest === undefined
+        ? null
+        : {
+            ...pullRequest,
+            repository: escapeMarkup(pullRequest.repository),
+            title: escapeMarkup(pullRequest.title),
+            url: escapeMarkup(pullRequest.url),
+            author: escapeMarkup(pullRequest.author),
+            state: escapeMarkup(pullRequest.state),
+            headBranch: escapeNullable(pullRequest.headBranch),
+            baseBranch: escapeNullable(pullRequest.baseBranch),
+          },
+  };
+}
+
 async function resolveAvatarImage(
   avatarCache: AvatarImageCache,
   supportsImages: boolean,
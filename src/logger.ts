@@ -3,13 +3,14 @@ import {
   existsSync,
   mkdirSync,
   readdirSync,
-  readFileSync,
   rmSync,
 } from "node:fs";
+import { readdir, readFile } from "node:fs/promises";
 import path from "node:path";
 
 export type LogLevel = "debug" | "info" | "warn" | "error";
 export type LogLevelFilter = "all" | LogLevel;
+export type LogFormat = "text" | "json";
 
 export interface LogContext {
   [key: string]: unknown;
@@ -39,6 +40,12 @@ export interface ConfigureAppLoggerOptions {
   minimumLevel: LogLevel;
   retentionMs: number;
   mirrorToConsole?: boolean;
+  // Per-layer levels override minimumLevel for that layer only.
+  consoleLevel?: LogLevel;
+  fileLevel?: LogLevel;
+  consoleFormat?: LogFormat;
+  // When false nothing is written to logsDirPath, and the log viewer has nothing to show.
+  writeToFile?: boolean;
 }
 
 export interface ReadRecentLogEntriesOptions {
@@ -62,14 +69,18 @@ const LOG_LEVEL_PRIORITY: Record<LogLevel, number> = {
 };
 
 let appLogger: AppLogger = createLogger({
-  minimumLevel: DEFAULT_GLOBAL_LOG_LEVEL,
+  consoleLevel: DEFAULT_GLOBAL_LOG_LEVEL,
+  fileLevel: DEFAULT_GLOBAL_LOG_LEVEL,
+  consoleFormat: "text",
   mirrorToConsole: true,
 });
 
 export function configureAppLogger(options: ConfigureAppLoggerOptions): AppLogger {
   appLogger = createLogger({
-    logsDirPath: options.logsDirPath,
-    minimumLevel: options.minimumLevel,
+    ...(options.writeToFile === false ? {} : { logsDirPath: options.logsDirPath }),
+    consoleLevel: options.consoleLevel ?? options.minimumLevel,
+    fileLevel: options.fileLevel ?? options.minimumLevel,
+    consoleFormat: options.consoleFormat ?? "text",
     retentionMs: options.retentionMs,
     mirrorToConsole: options.mirrorToConsole ?? true,
   });
@@ -83,7 +94,9 @@ export function getLogger(): AppLogger {
 
 export function resetAppLoggerForTesting(): void {
   appLogger = createLogger({
-    minimumLevel: DEFAULT_GLOBAL_LOG_LEVEL,
+    consoleLevel: DEFAULT_GLOBAL_LOG_LEVEL,
+    fileLevel: DEFAULT_GLOBAL_LOG_LEVEL,
+    consoleFormat: "text",
     mirrorToConsole: false,
   });
 }
@@ -92,6 +105,10 @@ export function isLogLevel(value: string): value is LogLevel {
   return value === "debug" || value === "info" || value === "warn" || value === "error";
 }
 
+export function isLogFormat(value: string): value is LogFormat {
+  return value === "text" || value === "json";
+}
+
 export function pruneLogFiles(
   logsDirPath: string,
   retentionMs: number,
@@ -120,7 +137,10 @@ export function pruneLogFiles(
   return prunedCount;
 }
 
-export function readRecentLogEntries(options: ReadRecentLogEntriesOptions): RecentLogEntry[] {
+// Log files can grow to megabytes, so the viewer reads them without blocking the event loop.
+export async function readRecentLogEntries(
+  options: ReadRecentLogEntriesOptions,
+): Promise<RecentLogEntry[]> {
   const limit = options.limit ?? DEFAULT_LOG_VIEWER_ENTRY_LIMIT;
 
   if (limit <= 0 || !existsSync(options.logsDirPath)) {
@@ -128,12 +148,13 @@ export function readRecentLogEntries(options: ReadRecentLogEntriesOptions): Rece
   }
 
   const recentEntries: RecentLogEntry[] = [];
+  const fileNames = (await readdir(options.logsDirPath, { withFileTypes: true }))
+    .filter((entry) => entry.isFile() && LOG_FILE_PATTERN.test(entry.name))
+    .map((entry) => entry.name);
 
-  for (const fileName of listLogFileNames(options.logsDirPath).sort((left, right) =>
-    right.localeCompare(left),
-  )) {
+  for (const fileName of fileNames.sort((left, right) => right.localeCompare(left))) {
     const filePath = path.join(options.logsDirPath, fileName);
-    const lines = readFileSync(filePath, "utf8").split(/\r?\n/);
+    const lines = (await readFile(filePath, "utf8")).split(/\r?\n/);
 
     for (let index = lines.length - 1; index >= 0; index -= 1) {
       const line = lines[index]?.trim();
@@ -168,11 +189,12 @@ export function readRecentLogEntries(options: ReadRecentLogEntriesOptions): Rece
 
 function createLogger(options: {
   logsDirPath?: string;
-  minimumLevel: LogLevel;
+  consoleLevel: LogLevel;
+  fileLevel: LogLevel;
+  consoleFormat: LogFormat;
   retentionMs?: number;
   mirrorToConsole: boolean;
 }): AppLogger {
-  const minimumLevel = options.minimumLevel;
   const mirrorToConsole = options.mirrorToConsole;
   const logsDirPath = options.logsDirPath;
   const retentionMs = options.retentionMs ?? DEFAULT_LOG_RETENTION_MS;
@@ -184,15 +206,26 @@ function createLogger(options: {
     lastPrunedDatePart = new Date().toISOString().slice(0, 10);
   }
 
+  const writeConsoleEntry = (entry: StoredLogEntry): void => {
+    if (options.consoleFormat === "json") {
+      writeConsoleLine(entry.level, serializeStoredLogEntry(entry));
+    } else {
+      writeConsoleLine(entry.level, formatConsoleMessage(entry), entry.context);
+    }
+  };
+
   const log = (level: LogLevel, message: string, context?: unknown): void => {
-    if (LOG_LEVEL_PRIORITY[level] < LOG_LEVEL_PRIORITY[minimumLevel]) {
+    const writesToConsole = mirrorToConsole && isAtLeastLevel(level, options.consoleLevel);
+    const writesToFile = logsDirPath !== undefined && isAtLeastLevel(level, options.fileLevel);
+
+    if (!writesToConsole && !writesToFile) {
       return;
     }
 
     const timestamp = new Date().toISOString();
     const entry = createStoredLogEntry(timestamp, level, message, context);
 
-    if (logsDirPath) {
+    if (logsDirPath && writesToFile) {
       const datePart = timestamp.slice(0, 10);
 
       try {
@@ -209,10 +242,8 @@ function createLogger(options: {
           "utf8",
         );
       } catch (error) {
-        writeConsoleLine(
-          "error",
-          `${timestamp} ERROR Failed to write Octopulse log entry`,
-          createLogContext({
+        writeConsoleEntry(
+          createStoredLogEntry(timestamp, "error", "Failed to write Octopulse log entry", {
             attemptedMessage: message,
             logFilePath: path.join(logsDirPath, renderLogFileName(datePart)),
             error,
@@ -221,8 +252,8 @@ function createLogger(options: {
       }
     }
 
-    if (mirrorToConsole) {
-      writeConsoleLine(level, formatConsoleMessage(entry), entry.context);
+    if (writesToConsole) {
+      writeConsoleEntry(entry);
     }
   };
 
@@ -243,6 +274,10 @@ function createLogger(options: {
   };
 }
 
+function isAtLeastLevel(level: LogLevel, minimumLevel: LogLevel): boolean {
+  return LOG_LEVEL_PRIORITY[level] >= LOG_LEVEL_PRIORITY[minimumLevel];
+}
+
 function ensureLogDirectory(logsDirPath: string): void {
   mkdirSync(logsDirPath, { recursive: true });
 }
@@ -0,0 +1,217 @@
+import { chmodSync, mkdirSync, writeFileSync } from "node:fs";
+import path from "node:path";
+import { setTimeout as sleep } from "node:timers/promises";
+
+import { GitHubAuthError, verifyGitHubToken } from "./github.js";
+import { openUrl } from "./open-url.js";
+
+const DEVICE_CODE_URL = "https://github.com/login/device/code";
+const ACCESS_TOKEN_URL = "https://github.com/login/oauth/access_token";
+const DEVICE_CODE_GRANT_TYPE = "urn:ietf:params:oauth:grant-type:device_code";
+// "repo" covers notifications and security alerts; "read:project" is for github_project.
+const LOGIN_SCOPES = ["repo", "read:project"];
+// GitHub asks clients that poll too fast to wait this much longer between polls.
+const SLOW_DOWN_INCREMENT_MS = 5_000;
+const CLIENT_ID_ENV_VAR = "OCTOPULSE_GITHUB_CLIENT_ID";
+const LOGIN_USAGE = "Usage: octopulse login [--client-id <id>]";
+
+export interface RunLoginCommandOptions {
+  // Where the token is saved; config loading reads it when github.token isn't set.
+  githubTokenPath: string;
+  env?: NodeJS.ProcessEnv;
+  fetchImpl?: typeof fetch;
+  sleep?: (delayMs: number) => Promise<unknown>;
+  openUrl?: (url: string) => Promise<void>;
+  verifyToken?: (token: string) => Promise<string>;
+  // Shows the code while the command is still waiting for it to be entered.
+  print?: (line: string) => void;
+}
+
+interface DeviceCode {
+  deviceCode: string;
+  userCode: string;
+  verificationUri: string;
+  expiresInMs: number;
+  intervalMs: number;
+}
+
+export class LoginCommandError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "LoginCommandError";
+  }
+}
+
+// Signs in with GitHub's device flow: the user enters a short code on github.com, and the
+// resulting OAuth token is saved next to the database, so no personal access token is needed.
+// The client ID belongs to an OAuth app with device flow enabled.
+export async function runLoginCommand(
+  args: readonly string[],
+  options: RunLoginCommandOptions,
+): Promise<string> {
+  const clientId = parseClientId(args) ?? (options.env ?? process.env)[CLIENT_ID_ENV_VAR];
+
+  if (!clientId) {
+    throw new LoginCommandError(
+      [
+        LOGIN_USAGE,
+        "",
+        "Register an OAuth app with device flow enabled at",
+        "https://github.com/settings/applications/new, then pass its client ID with --client-id",
+        `or ${CLIENT_ID_ENV_VAR}.`,
+      ].join("\n"),
+    );
+  }
+
+  const fetchImpl = options.fetchImpl ?? fetch;
+  const print = options.print ?? console.log;
+  const deviceCode = await requestDeviceCode(fetchImpl, clientId);
+
+  print(`Open ${deviceCode.verificationUri} and enter the code ${deviceCode.userCode}`);
+  // The URL is printed too, so a missing browser only means opening it by hand.
+  await (options.openUrl ?? openUrl)(deviceCode.verificationUri).catch(() => undefined);
+
+  const token = await waitForAccessToken(fetchImpl, clientId, deviceCode, options.sleep ?? sleep);
+  let login: string;
+
+  try {
+    login = await (options.verifyToken ?? verifyGitHubToken)(token);
+  } catch (error) {
+    if (error instanceof GitHubAuthError) {
+      throw new LoginCommandError(error.message);
+    }
+
+    throw error;
+  }
+
+  saveGitHubToken(options.githubTokenPath, token);
+
+  return [
+    `Authenticated as ${login}.`,
+    `Saved the token to ${options.githubTokenPath}. It's used when github.token isn't set.`,
+  ].join("\n");
+}
+
+function parseClientId(args: readonly string[]): string | undefined {
+  if (args.length === 0) {
+    return undefined;
+  }
+
+  const [flag, value, ...extraArgs] = args;
+
+  if (flag === "--client-id" && value !== undefined && extraArgs.length === 0) {
+    return value;
+  }
+
+  if (flag?.startsWith("--client-id=") && value === undefined) {
+    return flag.slice("--client-id=".length);
+  }
+
+  throw new LoginCommandError(LOGIN_USAGE);
+}
+
+async function requestDeviceCode(fetchImpl: typeof fetch, clientId: string): Promise<DeviceCode> {
+  const payload = await postForm(fetchImpl, DEVICE_CODE_URL, {
+    client_id: clientId,
+    scope: LOGIN_SCOPES.join(" "),
+  });
+  const { device_code, user_code, verification_uri, expires_in, interval } = payload;
+
+  if (
+    typeof device_code !== "string" ||
+    typeof user_code !== "string" ||
+    typeof verification_uri !== "string" ||
+    typeof expires_in !== "number" ||
+    typeof interval !== "number"
+  ) {
+    throw new LoginCommandError(
+      `GitHub didn't return a device code: ${describeOAuthError(payload)}`,
+    );
+  }
+
+  return {
+    deviceCode: device_code,
+    userCode: user_code,
+    verificationUri: verification_uri,
+    expiresInMs: expires_in * 1000,
+    intervalMs: interval * 1000,
+  };
+}
+
+async function waitForAccessToken(
+  fetchImpl: typeof fetch,
+  clientId: string,
+  deviceCode: DeviceCode,
+  wait: (delayMs: number) => Promise<unknown>,
+): Promise<string> {
+  const expiresAt = Date.now() + deviceCode.expiresInMs;
+  let intervalMs = deviceCode.intervalMs;
+
+  while (Date.now() < expiresAt) {
+    await wait(intervalMs);
+
+    const payload = await postForm(fetchImpl, ACCESS_TOKEN_URL, {
+      client_id: clientId,
+      device_code: deviceCode.deviceCode,
+      grant_type: DEVICE_CODE_GRANT_TYPE,
+    });
+
+    if (typeof payload.access_token === "string" && payload.access_token.length > 0) {
+      return payload.access_token;
+    }
+
+    switch (payload.error) {
+      case "authorization_pending":
+        break;
+      case "slow_down":
+        intervalMs =
+          typeof payload.interval === "number"
+            ? payload.interval * 1000
+            : intervalMs + SLOW_DOWN_INCREMENT_MS;
+        break;
+      case "expired_token":
+        throw new LoginCommandError("The code expired before it was entered. Run login again.");
+      case "access_denied":
+        throw new LoginCommandError("The login was cancelled on GitHub.");
+      default:
+        throw new LoginCommandError(`GitHub login failed: ${describeOAuthError(payload)}`);
+    }
+  }
+
+  throw new LoginCommandError("The code expired before it was entered. Run login again.");
+}
+
+async function postForm(
+  fetchImpl: typeof fetch,
+  url: string,
+  fields: Record<string, string>,
+): Promise<Record<string, unknown>> {
+  const response = await fetchImpl(url, {
+    method: "POST",
+    headers: {
+      Accept: "application/json",
+      "Content-Type": "application/x-www-form-urlencoded",
+    },
+    body: new URLSearchParams(fields).toString(),
+  });
+  const payload: unknown = await response.json().catch(() => undefined);
+
+  if (typeof payload !== "object" || payload === null) {
+    throw new LoginCommandError(`GitHub login request failed with status ${response.status}`);
+  }
+
+  return payload as Record<string, unknown>;
+}
+
+function describeOAuthError(payload: Record<string, unknown>): string {
+  const description = payload.error_description ?? payload.error;
+
+  return typeof description === "string" ? description : "unexpected response";
+}
+
+function saveGitHubToken(githubTokenPath: string, token: string): void {
+  mkdirSync(path.dirname(githubTokenPath), { recursive: true });
+  // Only the owner may read the token, the same as a config file written by init.
+  writeFileSync(githubTokenPath, `${token}\n`, { encoding: "utf8", mode: 0o600 });
+  chmodSync(githubTokenPath, 0o600);
+}
@@ -1,14 +1,66 @@
+#!/usr/bin/env node
 import type { Server } from "node:http";
 
+import type { Octokit } from "octokit";
+
 import {
   runFirstRunAuthoredPullRequestDiscovery,
   startRecurringAuthoredPullRequestDiscovery,
   type RecurringAuthoredPullRequestDiscoveryHandle,
 } from "./authored-pull-request-discovery.js";
+import { FileAvatarCache } from "./avatar-cache.js";
 import { createOpenAiBotActivityClassifier } from "./bot-activity-classification.js";
-import { loadConfig, resolveAppPaths } from "./config.js";
+import { createCalendarBusyChecker } from "./calendar-busy.js";
+import {
+  CommandLineError,
+  isNetworkCommand,
+  loadCommandLineConfig,
+  parseCommandLine,
+  renderCommandLineHelp,
+  runSubcommand,
+  type CommandLineOptions,
+} from "./cli.js";
+import {
+  HOT_RELOADABLE_CONFIG_SECTIONS,
+  listChangedConfigSections,
+  watchConfigFile,
+  type ConfigWatcherHandle,
+} from "./config-watcher.js";
+import { resolveAppPaths, type AppConfig } from "./config.js";
 import { initializeDatabase } from "./database.js";
-import { initializeGitHubAuth } from "./github.js";
+import { createDryRunNotificationDispatcher, openDryRunDatabase } from "./dry-run.js";
+import {
+  checkGitHubConnectivity,
+  createGitHubClient,
+  findMissingTokenScopes,
+  formatMissingTokenScopesWarning,
+  initializeGitHubAuth,
+  listRequiredTokenScopes,
+  readGitHubClientOptions,
+  requireGitHubToken,
+  verifyGitHubToken,
+} from "./github.js";
+import {
+  startGitHubAccountPolling,
+  type GitHubAccountPollingHandle,
+  type SharedTrackedPullRequestPollingOptions,
+} from "./github-accounts.js";
+import { createGitHubAppTokenProvider } from "./github-app-auth.js";
+import {
+  createGitHubNotificationReadStateClient,
+  readGitHubInboxSettings,
+  withGitHubMarkReadAfterDisplay,
+} from "./github-notification-read-state.js";
+import {
+  describeGitHubConnectivityProblem,
+  waitForGitHubReadiness,
+  type GitHubConnectivityProblem,
+} from "./github-readiness.js";
+import {
+  startGitHubTokenGuard,
+  type GitHubTokenGuardHandle,
+} from "./github-token-guard.js";
+import { openHistoryWindow } from "./history-window.js";
 import { LinuxNotificationAdapter } from "./linux-notification-adapter.js";
 import {
   configureAppLogger,
@@ -17,23 +69,89 @@ import {
   readRecentLogEntries,
 } from "./logger.js";
 import { trackPullRequestByUrl, untrackPullRequest } from "./manual-pull-request-tracking.js";
+import { relaunchWithEnvironment, resolveNetworkEnvironment } from "./network-environment.js";
+import { createPullRequestActionProvider } from "./notification-actions.js";
+import {
+  withAggregationWindow,
+  type AggregatingNotificationDispatcher,
+} from "./notification-aggregation.js";
 import { listNotificationHistory } from "./notification-history.js";
-import { resendNotificationRecord } from "./notification-dispatch.js";
-import { PullRequestRepository } from "./pull-request-repository.js";
+import { withNotificationPause } from "./notification-pause.js";
+import {
+  startRecurringNotificationReadStateSync,
+  type RecurringNotificationReadStateSyncHandle,
+} from "./notification-read-state-sync.js";
+import {
+  withDisplayRateLimit,
+  type RateLimitedNotificationDispatcher,
+} from "./notification-rate-limit.js";
+import { NotificationRecordRepository } from "./notification-record-repository.js";
+import { resendNotificationRecord, type NotificationDispatcher } from "./notification-dispatch.js";
+import { withNotificationSourceLabel } from "./notification-source-label.js";
+import { openUrl } from "./open-url.js";
+import { createPollerHealthMonitor } from "./poller-health.js";
+import {
+  createPersistentPollerBackoff,
+  PollerStateRepository,
+  watchGitHubRateLimit,
+} from "./poller-state.js";
+import { fetchProjectStatusFromGitHub } from "./pull-request-project-status.js";
+import { PullRequestRepository, type PullRequestRecord } from "./pull-request-repository.js";
 import { listPullRequestTimeline } from "./raw-events.js";
+import {
+  startRecurringRepositoryInboxPolling,
+  type RecurringRepositoryInboxPollingHandle,
+} from "./repository-inbox-polling.js";
+import {
+  RecentNotificationList,
+  withRecentNotificationTracking,
+} from "./recent-notifications.js";
+import {
+  startRecurringSecurityAlertPolling,
+  type RecurringSecurityAlertPollingHandle,
+} from "./security-alerts.js";
 import { readServerOrigin, startServer } from "./server.js";
+import {
+  createAppStateHeldNotificationStore,
+  withSessionPresenceBuffer,
+  type SessionPresenceBufferedNotificationDispatcher,
+} from "./session-presence-buffer.js";
+import {
+  formatSinglePollCycleResult,
+  readSinglePollCycleExitCode,
+  runSinglePollCycle,
+  SINGLE_POLL_CYCLE_EXIT_CODES,
+} from "./single-poll-cycle.js";
+import { withSoundCooldown } from "./sound-cooldown.js";
 import {
   startRecurringTrackedPullRequestPolling,
   type RecurringTrackedPullRequestPollingHandle,
 } from "./tracked-pull-request-polling.js";
 import { startTrayIcon, type TrayIconHandle } from "./tray-icon.js";
+import { startWebhookRelayClient, type WebhookRelayClientHandle } from "./webhook-relay.js";
+import {
+  withWeekendCatchUp,
+  type WeekendCatchUpNotificationDispatcher,
+} from "./weekend-mode.js";
+import { withWorkingHoursSchedule } from "./working-hours.js";
 
-async function main(): Promise<void> {
+async function main(commandLineOptions: CommandLineOptions): Promise<void> {
   let database: ReturnType<typeof initializeDatabase> | undefined;
   let server: Server | undefined;
   let recurringDiscovery: RecurringAuthoredPullRequestDiscoveryHandle | undefined;
   let recurringTrackedPullRequestPolling: RecurringTrackedPullRequestPollingHandle | undefined;
   let trayIcon: TrayIconHandle | undefined;
+  let webhookRelayClient: WebhookRelayClientHandle | undefined;
+  let recurringReadStateSync: RecurringNotificationReadStateSyncHandle | undefined;
+  let recurringSecurityAlertPolling: RecurringSecurityAlertPollingHandle | undefined;
+  let recurringRepositoryInboxPolling: RecurringRepositoryInboxPollingHandle | undefined;
+  let sessionPresenceBuffer: SessionPresenceBufferedNotificationDispatcher | undefined;
+  let weekendCatchUp: WeekendCatchUpNotificationDispatcher | undefined;
+  let aggregationWindow: AggregatingNotificationDispatcher | undefined;
+  let displayRateLimit: RateLimitedNotificationDispatcher | undefined;
+  let githubTokenGuard: GitHubTokenGuardHandle | undefined;
+  let configWatcher: ConfigWatcherHandle | undefined;
+  let githubAccountPollers: GitHubAccountPollingHandle[] = [];
   let isShuttingDown = false;
 
   const shutdown = async (reason: string): Promise<void> => {
@@ -43,10 +161,32 @@ async function main(): Promise<void> {
 
     isShuttingDown = true;
     getLogger().info("Octopulse shutdown initiated", { reason });
+    githubTokenGuard?.stop();
+    githubTokenGuard = undefined;
+    configWatcher?.stop();
+    configWatcher = undefined;
     recurringDiscovery?.stop();
     recurringDiscovery = undefined;
     recurringTrackedPullRequestPolling?.stop();
     recurringTrackedPullRequestPolling = undefined;
+    stopGitHubAccountPollers(githubAccountPollers);
+    githubAccountPollers = [];
+    webhookRelayClient?.stop();
+    webhookRelayClient = undefined;
+    recurringReadStateSync?.stop();
+    recurringReadStateSync = undefined;
+    recurringSecurityAlertPolling?.stop();
+    recurringSecurityAlertPolling = undefined;
+    recurringRepositoryInboxPolling?.stop();
+    recurringRepositoryInboxPolling = undefined;
+    sessionPresenceBuffer?.stop();
+    sessionPresenceBuffer = undefined;
+    weekendCatchUp?.stop();
+    weekendCatchUp = undefined;
+    aggregationWindow?.stop();
+    aggregationWindow = undefined;
+    displayRateLimit?.stop();
+    displayRateLimit = undefined;
     await closeTrayIconQuietly(trayIcon);
     trayIcon = undefined;
     await closeServerQuietly(server);
@@ -58,49 +198,284 @@ async function main(): Promise<void> {
   bindProcessSignal("SIGINT", shutdown);
   bindProcessSignal("SIGTERM", shutdown);
 
-  const defaultPaths = resolveAppPaths();
+  const defaultPaths = resolveAppPaths(
+    commandLineOptions.profile === undefined ? {} : { profile: commandLineOptions.profile },
+  );
   configureAppLogger({
     logsDirPath: defaultPaths.logsDirPath,
     minimumLevel: "info",
     retentionMs: DEFAULT_LOG_RETENTION_MS,
+    ...(commandLineOptions.logFormat === undefined
+      ? {}
+      : { consoleFormat: commandLineOptions.logFormat }),
+    writeToFile: !commandLineOptions.noLogFile,
   });
   getLogger().info("Octopulse startup initiated", {
     logsDirPath: defaultPaths.logsDirPath,
   });
 
   try {
-    const config = loadConfig();
-    configureAppLogger({
-      logsDirPath: config.paths.logsDirPath,
-      minimumLevel: config.logging.level,
-      retentionMs: config.logging.retentionMs,
-    });
+    const config = loadCommandLineConfig(commandLineOptions);
+    configureLogging(config);
     const logger = getLogger();
     logger.info("Octopulse configuration loaded", {
       configPath: config.paths.configPath,
+      profile: config.profile ?? null,
       stateDirPath: config.paths.stateDirPath,
       databasePath: config.paths.databasePath,
       logsDirPath: config.paths.logsDirPath,
-      logLevel: config.logging.level,
+      consoleLogLevel: config.logging.consoleLevel,
+      fileLogLevel: config.logging.fileEnabled ? config.logging.fileLevel : "off",
+      logFormat: config.logging.format,
       logRetentionMs: config.logging.retentionMs,
     });
-    const githubAuth = await initializeGitHubAuth(config);
+    // Shared with tracked polling, which fills it ahead of the popups.
+    const avatarCache = new FileAvatarCache({ maxAgeMs: config.timings.avatarCacheTtlMs });
+    const linuxNotificationAdapter = new LinuxNotificationAdapter({
+      avatarCache,
+      ...(config.notifications.bodyTemplate === undefined
+        ? {}
+        : { bodyTemplate: config.notifications.bodyTemplate }),
+      onNotificationOpened: (notification) => {
+        markOpenedNotificationRead(database, notification.notificationRecordId);
+      },
+    });
+    const desktopNotificationDispatcher = commandLineOptions.dryRun
+      ? createDryRunNotificationDispatcher()
+      : linuxNotificationAdapter;
+
+    if (commandLineOptions.dryRun) {
+      logger.info("Dry run: notifications are logged instead of shown, and no state is saved");
+    }
+
+    // Requests read the token each time, so a replacement picked up after a 401 applies everywhere.
+    // A GitHub App fetches installation tokens of its own as they expire.
+    let githubToken = config.githubToken;
+    const githubClientOptions = readGitHubClientOptions(config);
+    const readGitHubToken =
+      config.githubApp === undefined
+        ? () => requireGitHubToken({ ...(githubToken === undefined ? {} : { githubToken }) })
+        : createGitHubAppTokenProvider(config.githubApp, githubClientOptions);
+    let githubProblem: GitHubConnectivityProblem | undefined;
+    const githubAuth = await waitForGitHubReadiness(
+      async () => {
+        if (githubProblem === "bad_token" && config.githubApp === undefined) {
+          githubToken = readConfiguredGitHubToken(commandLineOptions, githubToken);
+        }
+
+        const auth = await initializeGitHubAuth(
+          { ...config, ...(githubToken === undefined ? {} : { githubToken }) },
+          { clientFactory: () => createGitHubClient(readGitHubToken, githubClientOptions) },
+        );
+
+        if (config.githubLogin === undefined) {
+          return auth;
+        }
+
+        // GET /user was skipped, so the token's scopes come from the connectivity check instead.
+        const tokenScopes = await checkGitHubConnectivity(auth.client, githubToken);
+
+        return tokenScopes === undefined ? auth : { ...auth, tokenScopes };
+      },
+      {
+        onProblem: (problem, error, attempt) => {
+          logger.error("GitHub startup check failed; retrying", {
+            problem,
+            attempt,
+            message: error.message,
+          });
+
+          if (problem !== githubProblem) {
+            showStartupPopup(
+              desktopNotificationDispatcher,
+              describeGitHubConnectivityProblem(problem, config.paths.configPath),
+            );
+          }
+
+          githubProblem = problem;
+        },
+      },
+    );
+
+    if (githubProblem !== undefined) {
+      logger.info("GitHub startup check passed after retrying");
+      showStartupPopup(desktopNotificationDispatcher, {
+        title: "Octopulse connected to GitHub",
+        body: "The startup check passed and polling is starting.",
+      });
+    }
+
+    // Opened ahead of the notification wrappers, which restore popups held before a restart.
+    const currentDatabase = commandLineOptions.dryRun
+      ? openDryRunDatabase(config.paths)
+      : initializeDatabase(config.paths);
+    database = currentDatabase;
     const botActivityClassifier = config.openAiApiKey
       ? createOpenAiBotActivityClassifier({ apiKey: config.openAiApiKey })
       : undefined;
-    const notificationDispatcher = new LinuxNotificationAdapter();
-    const currentDatabase = initializeDatabase(config.paths);
+    const recentNotifications = new RecentNotificationList();
+    const linuxNotificationDispatcher = withRecentNotificationTracking(
+      config.notifications.soundCooldownMs === undefined
+        ? desktopNotificationDispatcher
+        : withSoundCooldown(desktopNotificationDispatcher, {
+            cooldownMs: config.notifications.soundCooldownMs,
+          }),
+      recentNotifications,
+    );
+    // Summaries of held or suppressed popups open the local inbox once the UI is listening.
+    const overflowClickUrl = () =>
+      server?.listening
+        ? `${readServerOrigin(server)}/inbox`
+        : `${config.githubWebUrl}/notifications`;
+    const githubTokenSettingsUrl = `${config.githubWebUrl}/settings/tokens`;
+    const githubReadStateClient = createGitHubNotificationReadStateClient(githubAuth.client);
+    // Threads are only marked read for popups that actually reach the desktop, so the rate limiter
+    // sits outside and its suppressed popups stay unread on github.com. A dry run leaves github.com
+    // alone as well as the local state.
+    const shownNotificationDispatcher =
+      config.notifications.markGitHubReadAfterDisplay && !commandLineOptions.dryRun
+        ? withGitHubMarkReadAfterDisplay(linuxNotificationDispatcher, githubReadStateClient)
+        : linuxNotificationDispatcher;
+    displayRateLimit =
+      config.notifications.maxPopupsPerMinute === undefined
+        ? undefined
+        : withDisplayRateLimit(shownNotificationDispatcher, {
+            maxPerMinute: config.notifications.maxPopupsPerMinute,
+            onSuppressed: (notification) => {
+              recentNotifications.record(notification);
+            },
+            overflowClickUrl,
+          });
+    const displayNotificationDispatcher = displayRateLimit ?? shownNotificationDispatcher;
+    const calendarBusyChecker = config.calendarIcsSource
+      ? createCalendarBusyChecker({ source: config.calendarIcsSource })
+      : undefined;
+    sessionPresenceBuffer =
+      config.notifications.bufferWhileLocked ||
+      config.notifications.holdWhenIdleMs !== undefined ||
+      config.notifications.whileScreenShared !== "show" ||
+      calendarBusyChecker !== undefined
+        ? withSessionPresenceBuffer(displayNotificationDispatcher, {
+            bufferWhileLocked: config.notifications.bufferWhileLocked,
+            whileScreenShared: config.notifications.whileScreenShared,
+            overflowClickUrl,
+            heldNotificationStore: createAppStateHeldNotificationStore(
+              currentDatabase,
+              "session_presence_held_notifications",
+            ),
+            ...(calendarBusyChecker === undefined
+              ? {}
+              : { isCalendarBusy: () => calendarBusyChecker.isBusy() }),
+            ...(config.notifications.holdWhenIdleMs === undefined
+              ? {}
+              : { holdWhenIdleMs: config.notifications.holdWhenIdleMs }),
+          })
+        : undefined;
+    const bufferedNotificationDispatcher = sessionPresenceBuffer ?? displayNotificationDispatcher;
+    const scheduledNotificationDispatcher = config.workingHours
+      ? withWorkingHoursSchedule(bufferedNotificationDispatcher, config.workingHours, undefined, {
+          overflowClickUrl,
+        })
+      : bufferedNotificationDispatcher;
+    weekendCatchUp = config.weekendMode
+      ? withWeekendCatchUp(scheduledNotificationDispatcher, {
+          timeZone: config.weekendMode.timeZone,
+          repositories: config.weekendMode.repositories,
+          currentUserLogin: githubAuth.currentUserLogin,
+          overflowClickUrl,
+          heldNotificationStore: createAppStateHeldNotificationStore(
+            currentDatabase,
+            "weekend_catch_up_held_notifications",
+          ),
+        })
+      : undefined;
+    aggregationWindow =
+      config.notifications.aggregationWindowMs === undefined
+        ? undefined
+        : withAggregationWindow(weekendCatchUp ?? scheduledNotificationDispatcher, {
+            windowMs: config.notifications.aggregationWindowMs,
+          });
+    // Replaced when the config file is edited, like the polling settings below.
+    let notificationSounds = config.sounds;
+    const notificationDispatcher = withNotificationPause(
+      aggregationWindow ?? weekendCatchUp ?? scheduledNotificationDispatcher,
+      { overflowClickUrl },
+    );
+    // Labels go on at the top, so each account's popups carry only its own label.
+    const mainAccountNotificationDispatcher = withNotificationSourceLabel(
+      notificationDispatcher,
+      config.githubSourceLabel,
+    );
+    // Polling failures bypass pause and working hours: missing notifications is worth interrupting for.
+    const pollerHealth = createPollerHealthMonitor(bufferedNotificationDispatcher, {
+      clickUrl: () => (server?.listening ? `${readServerOrigin(server)}/logs` : null),
+    });
+    githubTokenGuard = startGitHubTokenGuard(githubAuth.client, {
+      notificationDispatcher: bufferedNotificationDispatcher,
+      configPath: config.paths.configPath,
+      tokenSettingsUrl: githubTokenSettingsUrl,
+      onAuthenticationLost: () => {
+        recurringDiscovery?.pause();
+        recurringTrackedPullRequestPolling?.pause();
+        recurringRepositoryInboxPolling?.pause();
+      },
+      onAuthenticationRestored: () => {
+        recurringDiscovery?.resume();
+        recurringTrackedPullRequestPolling?.resume();
+        recurringRepositoryInboxPolling?.resume();
+        recurringTrackedPullRequestPolling?.pollNow();
+      },
+      reauthenticate: async () => {
+        if (config.githubApp !== undefined) {
+          await checkGitHubConnectivity(githubAuth.client, undefined);
+          return;
+        }
+
+        const replacementToken = requireGitHubToken(loadCommandLineConfig(commandLineOptions));
+
+        await verifyGitHubToken(replacementToken, githubClientOptions);
+        githubToken = replacementToken;
+      },
+    });
+    const missingTokenScopes =
+      githubAuth.tokenScopes === undefined
+        ? []
+        : findMissingTokenScopes(githubAuth.tokenScopes, listRequiredTokenScopes(config));
+
+    if (missingTokenScopes.length > 0) {
+      const warning = formatMissingTokenScopesWarning(missingTokenScopes);
+
+      logger.warn(warning, { missingTokenScopes });
+      bufferedNotificationDispatcher
+        .dispatchNotification({
+          title: "GitHub token is missing scopes",
+          body: warning,
+          clickUrl: githubTokenSettingsUrl,
+        })
+        .catch((error: unknown) => {
+          logger.warn("Failed to show missing token scopes popup", { error });
+        });
+    }
+    const pullRequestActions = createPullRequestActionProvider(githubAuth.client, {
+      confirm: config.actions.confirm,
+      mergeMethod: config.actions.mergeMethod,
+      reactions: config.actions.reactions,
+      replies: config.actions.replies,
+    });
     const pullRequestRepository = new PullRequestRepository(currentDatabase);
-    database = currentDatabase;
+    const notificationRecordRepository = new NotificationRecordRepository(currentDatabase);
     const firstRunDiscoveryResult = await runFirstRunAuthoredPullRequestDiscovery(
       currentDatabase,
       githubAuth,
       {
-        notificationDispatcher,
+        concurrency: config.performance.pollConcurrency,
+        notificationDispatcher: mainAccountNotificationDispatcher,
+        pullRequestActions,
       },
     );
     logger.info("Pull request discovery completed", firstRunDiscoveryResult);
     server = await startServer({
+      port: config.server.port,
       listTrackedPullRequests: async () => pullRequestRepository.listTrackedPullRequests(),
       listInactivePullRequests: async () => pullRequestRepository.listInactivePullRequests(),
       listPullRequestTimeline: async () => listPullRequestTimeline(currentDatabase),
@@ -110,6 +485,13 @@ async function main(): Promise<void> {
           ...(page ? { page } : {}),
           ...(pageSize ? { pageSize } : {}),
         }),
+      listNotificationInbox: async ({ filters, page, pageSize }) =>
+        listNotificationHistory(currentDatabase, {
+          unreadOnly: true,
+          ...(filters ? { filters } : {}),
+          ...(page ? { page } : {}),
+          ...(pageSize ? { pageSize } : {}),
+        }),
       listRecentLogs: async ({ level, limit }) =>
         readRecentLogEntries({
           logsDirPath: config.paths.logsDirPath,
@@ -119,6 +501,7 @@ async function main(): Promise<void> {
       manualTrackPullRequestByUrl: (pullRequestUrl: string) =>
         trackPullRequestByUrl(currentDatabase, githubAuth, pullRequestUrl, {
           pullRequestRepository,
+          githubWebUrl: config.githubWebUrl,
         }),
       manualUntrackPullRequest: (githubPullRequestId: number) =>
         untrackPullRequest(currentDatabase, githubPullRequestId, {
@@ -128,47 +511,298 @@ async function main(): Promise<void> {
         resendNotificationRecord(currentDatabase, {
           notificationRecordId,
           currentUserLogin: githubAuth.currentUserLogin,
-          notificationDispatcher,
+          notificationDispatcher: mainAccountNotificationDispatcher,
+          notificationSounds,
+          pullRequestActions,
         }),
+      markNotificationRecordRead: (notificationRecordId: number) => {
+        notificationRecordRepository.markNotificationRecordRead(
+          notificationRecordId,
+          new Date().toISOString(),
+        );
+      },
+      markNotificationRecordUnread: (notificationRecordId: number) => {
+        notificationRecordRepository.markNotificationRecordUnread(notificationRecordId);
+      },
+      openLatestNotification: async () => {
+        const clickUrl = recentNotifications.latest()?.clickUrl ?? null;
+
+        if (clickUrl !== null) {
+          await openUrl(clickUrl);
+        }
+
+        return clickUrl;
+      },
+      openInbox: () => openHistoryWindow({ serverOrigin: readServerOrigin(server!), page: "inbox" }),
+      toggleNotificationPause: () => notificationDispatcher.togglePaused(),
       getCurrentUserLogin: () => githubAuth.currentUserLogin,
     });
     const serverOrigin = readServerOrigin(server);
     trayIcon = await startTrayIcon({
       serverOrigin,
+      recentNotifications,
       onQuitRequested: async () => {
         await shutdown("tray_quit");
         process.exit(0);
       },
     });
+    const pollerStateRepository = new PollerStateRepository(currentDatabase);
+    const githubInbox = readGitHubInboxSettings(config.notifications);
+    watchGitHubRateLimit(githubAuth.client, pollerStateRepository, {
+      notificationDispatcher: bufferedNotificationDispatcher,
+    });
     recurringDiscovery = startRecurringAuthoredPullRequestDiscovery(currentDatabase, githubAuth, {
       intervalMs: config.timings.discoveryPollMs,
-      notificationDispatcher,
+      backoff: createPersistentPollerBackoff(pollerStateRepository, "discovery", {
+        intervalMs: config.timings.discoveryPollMs,
+      }),
+      concurrency: config.performance.pollConcurrency,
+      notificationDispatcher: mainAccountNotificationDispatcher,
+      pullRequestActions,
+      repositoryOverrides: config.repositoryOverrides,
+      ...(githubInbox === undefined ? {} : { githubInbox }),
+      onCycleSettled: (failure) => {
+        pollerHealth.recordCycle("discovery", failure);
+      },
     });
     logger.info("Started recurring pull request discovery", {
       intervalMs: config.timings.discoveryPollMs,
       notificationsEnabled: true,
     });
+    const githubProject = config.githubProject;
+    const sharedTrackedPullRequestPolling: SharedTrackedPullRequestPollingOptions = {
+      concurrency: config.performance.pollConcurrency,
+      ...(commandLineOptions.dryRun ? {} : { avatarCache }),
+      maxFetchedComments: config.performance.maxFetchedComments,
+      activityApi: config.performance.activityApi,
+      immediateCiFailures: config.notifications.ciFailureAlerts,
+      groupByRepository: config.notifications.groupByRepository,
+      ...(githubProject
+        ? {
+            fetchProjectStatus: (client: Octokit, pullRequest: PullRequestRecord) =>
+              fetchProjectStatusFromGitHub(client, pullRequest, githubProject),
+          }
+        : {}),
+      ...(config.filters ? { notificationRules: config.filters } : {}),
+      ...(config.channels.length > 0 ? { notificationChannels: config.channels } : {}),
+      ...(config.repositoryOverrides.length > 0
+        ? { repositoryOverrides: config.repositoryOverrides }
+        : {}),
+      notificationSounds,
+      ...(botActivityClassifier ? { botActivityClassifier } : {}),
+    };
     recurringTrackedPullRequestPolling = startRecurringTrackedPullRequestPolling(
       currentDatabase,
       githubAuth,
       {
+        ...sharedTrackedPullRequestPolling,
         intervalMs: config.timings.trackedPullRequestPollMs,
+        backoff: createPersistentPollerBackoff(pollerStateRepository, "tracked_pull_requests", {
+          intervalMs: config.timings.trackedPullRequestPollMs,
+        }),
         pullRequestRepository,
-        notificationDispatcher,
-        ...(botActivityClassifier ? { botActivityClassifier } : {}),
+        notificationDispatcher: mainAccountNotificationDispatcher,
+        pullRequestActions,
+        onCycleSettled: (failure) => {
+          pollerHealth.recordCycle("tracked_pull_requests", failure);
+        },
       },
     );
     logger.info("Started recurring tracked pull request polling", {
       intervalMs: config.timings.trackedPullRequestPollMs,
+      intervalSource:
+        commandLineOptions.trackedPullRequestPollMs === undefined ? "config" : "--poll-interval",
+      concurrency: config.performance.pollConcurrency,
       notificationsEnabled: true,
       botActivityClassificationEnabled: Boolean(botActivityClassifier),
     });
 
+    // Accounts start side by side, and one that fails to start doesn't hold up the others.
+    const accountStarts = await Promise.allSettled(
+      config.githubAccounts.map((account) =>
+        startGitHubAccountPolling(account, {
+          config,
+          dryRun: commandLineOptions.dryRun,
+          notificationDispatcher,
+          trackedPullRequestPolling: sharedTrackedPullRequestPolling,
+          onCycleSettled: (source, failure) => {
+            pollerHealth.recordCycle(source, failure);
+          },
+        }),
+      ),
+    );
+
+    accountStarts.forEach((accountStart, index) => {
+      const label = config.githubAccounts[index]?.label;
+
+      if (accountStart.status === "fulfilled") {
+        githubAccountPollers.push(accountStart.value);
+        logger.info("Started polling for GitHub account", {
+          account: label,
+          githubUser: accountStart.value.currentUserLogin,
+        });
+        return;
+      }
+
+      const message =
+        accountStart.reason instanceof Error ? accountStart.reason.message : "Unknown error";
+
+      logger.error("GitHub account failed to start; continuing without it", {
+        account: label,
+        message,
+      });
+      showStartupPopup(desktopNotificationDispatcher, {
+        title: `Octopulse couldn't start the ${label} GitHub account`,
+        body: message,
+      });
+    });
+
+    if (config.notifications.syncGitHubReadState) {
+      recurringReadStateSync = startRecurringNotificationReadStateSync(
+        currentDatabase,
+        githubReadStateClient,
+        {
+          intervalMs: config.timings.trackedPullRequestPollMs,
+          notificationRecordRepository,
+          pullRequestRepository,
+        },
+      );
+      logger.info("Started recurring notification read-state sync", {
+        intervalMs: config.timings.trackedPullRequestPollMs,
+      });
+    }
+
+    if (config.securityAlertRepositories.length > 0) {
+      recurringSecurityAlertPolling = startRecurringSecurityAlertPolling(
+        currentDatabase,
+        githubAuth.client,
+        {
+          intervalMs: config.timings.discoveryPollMs,
+          repositories: config.securityAlertRepositories,
+          notificationDispatcher: mainAccountNotificationDispatcher,
+        },
+      );
+      logger.info("Started recurring security alert polling", {
+        intervalMs: config.timings.discoveryPollMs,
+        repositoryCount: config.securityAlertRepositories.length,
+      });
+    }
+
+    if (config.inboxRepositories.repositories.length > 0) {
+      recurringRepositoryInboxPolling = startRecurringRepositoryInboxPolling(
+        currentDatabase,
+        githubAuth.client,
+        {
+          intervalMs: config.inboxRepositories.pollIntervalMs,
+          backoff: createPersistentPollerBackoff(pollerStateRepository, "repository_inbox", {
+            intervalMs: config.inboxRepositories.pollIntervalMs,
+          }),
+          repositories: config.inboxRepositories.repositories,
+          inbox: {
+            participating: config.notifications.githubInbox === "participating",
+            reasons: config.notifications.githubInboxReasons,
+          },
+          onTracked: () => {
+            recurringTrackedPullRequestPolling?.pollNow();
+          },
+        },
+      );
+      logger.info("Started recurring repository inbox polling", {
+        intervalMs: config.inboxRepositories.pollIntervalMs,
+        repositoryCount: config.inboxRepositories.repositories.length,
+      });
+    }
+
+    if (config.webhookRelayUrl) {
+      webhookRelayClient = startWebhookRelayClient({
+        relayUrl: config.webhookRelayUrl,
+        onDelivery: () => {
+          recurringTrackedPullRequestPolling?.pollNow();
+        },
+      });
+      logger.info("Started webhook relay client", { relayUrl: config.webhookRelayUrl });
+    }
+
+    configWatcher = watchConfigFile(config, {
+      load: () => loadCommandLineConfig(commandLineOptions),
+      onChange: (nextConfig, previousConfig) => {
+        const changedSections = listChangedConfigSections(previousConfig, nextConfig);
+
+        configureLogging(nextConfig);
+
+        const discoveryPollers = [
+          ...(recurringDiscovery ? [recurringDiscovery] : []),
+          ...githubAccountPollers.map((poller) => poller.discovery),
+        ];
+        const trackedPullRequestPollers = [
+          ...(recurringTrackedPullRequestPolling ? [recurringTrackedPullRequestPolling] : []),
+          ...githubAccountPollers.map((poller) => poller.trackedPullRequestPolling),
+        ];
+
+        for (const discoveryPoller of discoveryPollers) {
+          if (nextConfig.timings.discoveryPollMs !== previousConfig.timings.discoveryPollMs) {
+            discoveryPoller.setIntervalMs(nextConfig.timings.discoveryPollMs);
+          }
+
+          discoveryPoller.setRepositoryOverrides(nextConfig.repositoryOverrides);
+        }
+
+        for (const trackedPullRequestPoller of trackedPullRequestPollers) {
+          if (
+            nextConfig.timings.trackedPullRequestPollMs !==
+            previousConfig.timings.trackedPullRequestPollMs
+          ) {
+            trackedPullRequestPoller.setIntervalMs(nextConfig.timings.trackedPullRequestPollMs);
+          }
+
+          trackedPullRequestPoller.setNotificationSettings({
+            ...(nextConfig.filters ? { notificationRules: nextConfig.filters } : {}),
+            ...(nextConfig.channels.length > 0
+              ? { notificationChannels: nextConfig.channels }
+              : {}),
+            ...(nextConfig.repositoryOverrides.length > 0
+              ? { repositoryOverrides: nextConfig.repositoryOverrides }
+              : {}),
+            notificationSounds: nextConfig.sounds,
+          });
+        }
+        notificationSounds = nextConfig.sounds;
+        getLogger().info("Applied config file changes", {
+          changedSections,
+          restartRequiredSections: changedSections.filter(
+            (section) => !HOT_RELOADABLE_CONFIG_SECTIONS.includes(section),
+          ),
+        });
+      },
+    });
+
     server.once("close", () => {
+      githubTokenGuard?.stop();
+      githubTokenGuard = undefined;
+      configWatcher?.stop();
+      configWatcher = undefined;
       recurringDiscovery?.stop();
       recurringDiscovery = undefined;
       recurringTrackedPullRequestPolling?.stop();
       recurringTrackedPullRequestPolling = undefined;
+      stopGitHubAccountPollers(githubAccountPollers);
+      githubAccountPollers = [];
+      webhookRelayClient?.stop();
+      webhookRelayClient = undefined;
+      recurringReadStateSync?.stop();
+      recurringReadStateSync = undefined;
+      recurringSecurityAlertPolling?.stop();
+      recurringSecurityAlertPolling = undefined;
+      recurringRepositoryInboxPolling?.stop();
+      recurringRepositoryInboxPolling = undefined;
+      sessionPresenceBuffer?.stop();
+      sessionPresenceBuffer = undefined;
+      weekendCatchUp?.stop();
+      weekendCatchUp = undefined;
+      aggregationWindow?.stop();
+      aggregationWindow = undefined;
+      displayRateLimit?.stop();
+      displayRateLimit = undefined;
       closeDatabaseQuietly(database);
       database = undefined;
     });
@@ -179,8 +813,19 @@ async function main(): Promise<void> {
       trayIconVisible: trayIcon.isVisible,
     });
   } catch (error) {
+    githubTokenGuard?.stop();
+    configWatcher?.stop();
     recurringDiscovery?.stop();
     recurringTrackedPullRequestPolling?.stop();
+    stopGitHubAccountPollers(githubAccountPollers);
+    webhookRelayClient?.stop();
+    recurringReadStateSync?.stop();
+    recurringSecurityAlertPolling?.stop();
+    recurringRepositoryInboxPolling?.stop();
+    sessionPresenceBuffer?.stop();
+    weekendCatchUp?.stop();
+    aggregationWindow?.stop();
+    displayRateLimit?.stop();
     await closeTrayIconQuietly(trayIcon);
     await closeServerQuietly(server);
     closeDatabaseQuietly(database);
@@ -193,7 +838,185 @@ async function main(): Promise<void> {
   }
 }
 
-void main();
+// One discovery pass and one tracked pull request poll without the UI server, tray, or timers.
+// Returns the process exit status.
+async function runOnce(commandLineOptions: CommandLineOptions): Promise<number> {
+  let database: ReturnType<typeof initializeDatabase> | undefined;
+
+  try {
+    const config = loadCommandLineConfig(commandLineOptions);
+    configureLogging(config);
+    const githubAuth = await initializeGitHubAuth(config);
+    const githubProject = config.githubProject;
+    const githubInbox = readGitHubInboxSettings(config.notifications);
+    database = commandLineOptions.dryRun
+      ? openDryRunDatabase(config.paths)
+      : initializeDatabase(config.paths);
+    const result = await runSinglePollCycle(database, githubAuth, {
+      notificationDispatcher: withNotificationSourceLabel(
+        commandLineOptions.dryRun
+          ? createDryRunNotificationDispatcher()
+          : new LinuxNotificationAdapter({
+              avatarCacheMaxAgeMs: config.timings.avatarCacheTtlMs,
+              ...(config.notifications.bodyTemplate === undefined
+                ? {}
+                : { bodyTemplate: config.notifications.bodyTemplate }),
+            }),
+        config.githubSourceLabel,
+      ),
+      discovery: {
+        concurrency: config.performance.pollConcurrency,
+        repositoryOverrides: config.repositoryOverrides,
+        ...(githubInbox === undefined ? {} : { githubInbox }),
+      },
+      polling: {
+        concurrency: config.performance.pollConcurrency,
+        maxFetchedComments: config.performance.maxFetchedComments,
+        activityApi: config.performance.activityApi,
+        immediateCiFailures: config.notifications.ciFailureAlerts,
+        groupByRepository: config.notifications.groupByRepository,
+        ...(githubProject
+          ? {
+              fetchProjectStatus: (client: Octokit, pullRequest: PullRequestRecord) =>
+                fetchProjectStatusFromGitHub(client, pullRequest, githubProject),
+            }
+          : {}),
+        ...(config.filters ? { notificationRules: config.filters } : {}),
+        ...(config.channels.length > 0 ? { notificationChannels: config.channels } : {}),
+        ...(config.repositoryOverrides.length > 0
+          ? { repositoryOverrides: config.repositoryOverrides }
+          : {}),
+        notificationSounds: config.sounds,
+        ...(config.openAiApiKey
+          ? {
+              botActivityClassifier: createOpenAiBotActivityClassifier({
+                apiKey: config.openAiApiKey,
+              }),
+            }
+          : {}),
+      },
+    });
+
+    getLogger().info("Single poll cycle completed", { ...result });
+    console.log(formatSinglePollCycleResult(result));
+
+    return readSinglePollCycleExitCode(result);
+  } catch (error) {
+    console.error(error instanceof Error ? error.message : String(error));
+    return SINGLE_POLL_CYCLE_EXIT_CODES.failed;
+  } finally {
+    closeDatabaseQuietly(database);
+  }
+}
+
+void runCommandLine(process.argv.slice(2));
+
+async function runCommandLine(args: readonly string[]): Promise<void> {
+  try {
+    const commandLine = parseCommandLine(args);
+
+    // Reading the config can shell out to gh for the token, so only the commands that go online
+    // pay for it, and they restart only when the network settings aren't in effect yet.
+    const networkEnvironment = isNetworkCommand(commandLine.command)
+      ? readNetworkEnvironment(commandLine.options)
+      : undefined;
+
+    if (networkEnvironment !== undefined) {
+      process.exit(await relaunchWithEnvironment(networkEnvironment));
+    } else if (commandLine.command === "help") {
+      console.log(renderCommandLineHelp());
+    } else if (commandLine.command === "run" && commandLine.options.once) {
+      // Exit explicitly: the notification server's session bus connection would keep Node alive.
+      process.exit(await runOnce(commandLine.options));
+    } else if (commandLine.command === "run") {
+      await main(commandLine.options);
+    } else {
+      console.log(await runSubcommand(commandLine.command, commandLine.options, commandLine.args));
+
+      if (commandLine.command === "test-notification") {
+        // Like --once, the session bus connection would otherwise keep the process running.
+        process.exit();
+      }
+    }
+  } catch (error) {
+    console.error(error instanceof Error ? error.message : String(error));
+    process.exitCode = error instanceof CommandLineError ? 2 : 1;
+  }
+}
+
+// A config file that doesn't load yet still leaves the environment's proxy to go by; the command
+// itself reports the config problem after the restart.
+function readNetworkEnvironment(
+  commandLineOptions: CommandLineOptions,
+): Record<string, string> | undefined {
+  let network: AppConfig["network"] = { noProxy: [] };
+
+  try {
+    network = loadCommandLineConfig(commandLineOptions).network;
+  } catch {
+    // Fall back to the environment alone.
+  }
+
+  return resolveNetworkEnvironment(network, process.env);
+}
+
+function markOpenedNotificationRead(
+  database: ReturnType<typeof initializeDatabase> | undefined,
+  notificationRecordId: number | undefined,
+): void {
+  if (!database?.isOpen || notificationRecordId === undefined) {
+    return;
+  }
+
+  try {
+    new NotificationRecordRepository(database).markNotificationRecordRead(
+      notificationRecordId,
+      new Date().toISOString(),
+    );
+  } catch (error) {
+    getLogger().warn("Failed to record notification open", { notificationRecordId, error });
+  }
+}
+
+function configureLogging(config: AppConfig): void {
+  configureAppLogger({
+    logsDirPath: config.paths.logsDirPath,
+    minimumLevel: config.logging.consoleLevel,
+    consoleLevel: config.logging.consoleLevel,
+    fileLevel: config.logging.fileLevel,
+    consoleFormat: config.logging.format,
+    writeToFile: config.logging.fileEnabled,
+    retentionMs: config.logging.retentionMs,
+  });
+}
+
+// Keeps the token in use when the config file can't be read mid-edit.
+function readConfiguredGitHubToken(
+  commandLineOptions: CommandLineOptions,
+  currentToken: string | undefined,
+): string | undefined {
+  try {
+    return loadCommandLineConfig(commandLineOptions).githubToken ?? currentToken;
+  } catch (error) {
+    getLogger().warn("Failed to re-read the GitHub token from the config file", { error });
+    return currentToken;
+  }
+}
+
+function stopGitHubAccountPollers(pollers: readonly GitHubAccountPollingHandle[]): void {
+  for (const poller of pollers) {
+    poller.stop();
+  }
+}
+
+function showStartupPopup(
+  notificationDispatcher: NotificationDispatcher,
+  notification: { title: string; body: string },
+): void {
+  notificationDispatcher.dispatchNotification(notification).catch((error: unknown) => {
+    getLogger().warn("Failed to show startup popup", { error });
+  });
+}
 
 function closeDatabaseQuietly(database: ReturnType<typeof initializeDatabase> | undefined): void {
   if (!database?.isOpen) {
@@ -27,6 +27,8 @@ export interface TrackPullRequestByUrlOptions<TClient = Octokit> {
     coordinates: PullRequestCoordinates,
   ) => Promise<DiscoveredPullRequest>;
   observedAt?: string;
+  // The GitHub Enterprise Server address pull request URLs must point at, instead of github.com.
+  githubWebUrl?: string;
 }
 
 export interface TrackPullRequestByUrlResult {
@@ -60,7 +62,7 @@ export async function trackPullRequestByUrl<TClient>(
   pullRequestUrl: string,
   options: TrackPullRequestByUrlOptions<TClient> = {},
 ): Promise<TrackPullRequestByUrlResult> {
-  const coordinates = parseGitHubPullRequestUrl(pullRequestUrl);
+  const coordinates = parseGitHubPullRequestUrl(pullRequestUrl, options.githubWebUrl);
   const pullRequestRepository = options.pullRequestRepository ?? new PullRequestRepository(database);
   const fetchPullRequestDetail =
     options.fetchPullRequestDetail ??
@@ -160,7 +162,10 @@ export async function untrackPullRequest(
   }
 }
 
-export function parseGitHubPullRequestUrl(pullRequestUrl: string): PullRequestCoordinates {
+export function parseGitHubPullRequestUrl(
+  pullRequestUrl: string,
+  githubWebUrl = "https://github.com",
+): PullRequestCoordinates {
   const normalizedPullRequestUrl = pullRequestUrl.trim();
   let url: URL;
 
@@ -172,9 +177,11 @@ export function parseGitHubPullRequestUrl(pullRequestUrl: string): PullRequestCo
     );
   }
 
-  if (url.hostname !== "github.com") {
+  const githubHostname = new URL(githubWebUrl).hostname;
+
+  if (url.hostname !== githubHostname) {
     throw new ManualPullRequestTrackingError(
-      "Manual tracking only supports github.com pull request URLs",
+      `Manual tracking only supports ${githubHostname} pull request URLs`,
     );
   }
 
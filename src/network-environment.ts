@@ -0,0 +1,86 @@
+import { spawn } from "node:child_process";
+
+import type { AppConfig } from "./config.js";
+
+// The local UI server and anything else on this machine never go through the proxy.
+const LOCAL_NO_PROXY_HOSTS = ["localhost", "127.0.0.1", "::1"];
+
+export interface RelaunchOptions {
+  spawnImpl?: typeof spawn;
+}
+
+// Node only honors a proxy, for fetch and with it Octokit, avatar downloads, and every other GitHub
+// request, when NODE_USE_ENV_PROXY is set as the process starts, and only reads extra trusted root
+// certificates from NODE_EXTRA_CA_CERTS at startup. Returns the variables to restart with, or
+// undefined when neither applies or the process already has them.
+export function resolveNetworkEnvironment(
+  network: AppConfig["network"],
+  env: NodeJS.ProcessEnv,
+): Record<string, string> | undefined {
+  const networkEnvironment = {
+    ...resolveProxyEnvironment(network, env),
+    ...(network.caCertificatesPath === undefined
+      ? {}
+      : { NODE_EXTRA_CA_CERTS: network.caCertificatesPath }),
+  };
+  const entries = Object.entries(networkEnvironment);
+
+  return entries.length === 0 || entries.every(([name, value]) => env[name] === value)
+    ? undefined
+    : networkEnvironment;
+}
+
+function resolveProxyEnvironment(
+  network: AppConfig["network"],
+  env: NodeJS.ProcessEnv,
+): Record<string, string> {
+  const proxy =
+    network.proxy ?? env.HTTPS_PROXY ?? env.https_proxy ?? env.HTTP_PROXY ?? env.http_proxy;
+
+  if (proxy === undefined || proxy.trim().length === 0) {
+    return {};
+  }
+
+  const configuredNoProxy =
+    network.noProxy.length > 0
+      ? network.noProxy
+      : (env.NO_PROXY ?? env.no_proxy ?? "").split(",").map((host) => host.trim());
+  const noProxy = [...new Set([...configuredNoProxy, ...LOCAL_NO_PROXY_HOSTS])]
+    .filter((host) => host.length > 0)
+    .join(",");
+
+  return {
+    NODE_USE_ENV_PROXY: "1",
+    HTTPS_PROXY: proxy,
+    HTTP_PROXY: proxy,
+    NO_PROXY: noProxy,
+  };
+}
+
+// Runs this same command again with the network variables set, passing signals through, and
+// resolves with its exit code.
+export function relaunchWithEnvironment(
+  environment: Record<string, string>,
+  options: RelaunchOptions = {},
+): Promise<number> {
+  const child = (options.spawnImpl ?? spawn)(
+    process.execPath,
+    [...process.execArgv, ...process.argv.slice(1)],
+    { stdio: "inherit", env: { ...process.env, ...environment } },
+  );
+  const forwardSignal = (signal: NodeJS.Signals) => {
+    child.kill(signal);
+  };
+
+  process.on("SIGINT", forwardSignal);
+  process.on("SIGTERM", forwardSignal);
+
+  return new Promise((resolve, reject) => {
+    child.once("error", reject);
+    child.once("exit", (code) => {
+      process.off("SIGINT", forwardSignal);
+      process.off("SIGTERM", forwardSignal);
+      resolve(code ?? 1);
+    });
+  });
+}
@@ -0,0 +1,348 @@
+import { Octokit } from "octokit";
+
+import type { NotificationAction } from "./linux-notification-adapter.js";
+import type { NormalizedEventRecord } from "./normalized-event-repository.js";
+import type { PullRequestRecord } from "./pull-request-repository.js";
+import type { PullRequestReviewStateRecord } from "./pull-request-review-state-repository.js";
+import type { RawEventRecord } from "./raw-event-repository.js";
+
+const GITHUB_API_HEADERS = {
+  "X-GitHub-Api-Version": "2022-11-28",
+};
+const MAX_REPLY_LABEL_LENGTH = 32;
+
+export const NOTIFICATION_ACTION_KINDS = [
+  "approve",
+  "merge",
+  "rerequest_review",
+  "react",
+  "reply",
+] as const;
+// Re-requesting a review is easy to undo, so it runs without asking unless configured to.
+export const DEFAULT_CONFIRMED_ACTION_KINDS = ["approve", "merge"] as const;
+export const MERGE_METHODS = ["merge", "squash", "rebase"] as const;
+// GitHub's reaction names, with the emoji each one is shown as on its popup button.
+export const REACTION_EMOJI = {
+  "+1": "👍",
+  "-1": "👎",
+  laugh: "😄",
+  confused: "😕",
+  heart: "❤️",
+  hooray: "🎉",
+  rocket: "🚀",
+  eyes: "👀",
+} as const;
+
+export type NotificationActionKind = (typeof NOTIFICATION_ACTION_KINDS)[number];
+export type MergeMethod = (typeof MERGE_METHODS)[number];
+export type ReactionContent = keyof typeof REACTION_EMOJI;
+
+export interface PullRequestActionContext {
+  pullRequest: PullRequestRecord;
+  events: readonly NormalizedEventRecord[];
+  reviewStates: readonly PullRequestReviewStateRecord[];
+  // Loaded only when an action needs the pull request's timeline.
+  listRawEvents: () => readonly RawEventRecord[];
+  currentUserLogin: string;
+}
+
+export type PullRequestActionProvider = (context: PullRequestActionContext) => NotificationAction[];
+
+export interface CreatePullRequestActionProviderOptions {
+  // Actions listed here ask for confirmation in a follow-up popup before calling GitHub.
+  confirm: readonly NotificationActionKind[];
+  mergeMethod?: MergeMethod;
+  // Quick reactions offered on comment popups, in button order.
+  reactions?: readonly ReactionContent[];
+  // Canned replies such as "Will look today", each offered as its own button.
+  replies?: readonly string[];
+}
+
+interface PullRequestActionCandidate {
+  kind: NotificationActionKind;
+  // Defaults to `kind`; set when one kind can appear more than once on a popup.
+  key?: string;
+  label: string;
+  confirmation: string;
+  run(): Promise<void>;
+}
+
+export function createPullRequestActionProvider(
+  client: Octokit,
+  options: CreatePullRequestActionProviderOptions,
+): PullRequestActionProvider {
+  const confirmedKinds = new Set(options.confirm);
+
+  return (context) =>
+    listPullRequestActionCandidates(client, context, {
+      mergeMethod: options.mergeMethod ?? "merge",
+      reactions: options.reactions ?? ["+1", "hooray"],
+      replies: options.replies ?? [],
+    }).map((candidate) => ({
+      key: candidate.key ?? candidate.kind,
+      label: candidate.label,
+      ...(confirmedKinds.has(candidate.kind) ? { confirmation: candidate.confirmation } : {}),
+      run: candidate.run,
+    }));
+}
+
+function listPullRequestActionCandidates(
+  client: Octokit,
+  context: PullRequestActionContext,
+  settings: {
+    mergeMethod: MergeMethod;
+    reactions: readonly ReactionContent[];
+    replies: readonly string[];
+  },
+): PullRequestActionCandidate[] {
+  const { mergeMethod } = settings;
+  const { pullRequest, events, reviewStates, listRawEvents, currentUserLogin } = context;
+  const label = formatPullRequestLabel(pullRequest);
+  const candidates: PullRequestActionCandidate[] = [];
+
+  if (pullRequest.state !== "open") {
+    return candidates;
+  }
+
+  if (
+    !sameLogin(pullRequest.authorLogin, currentUserLogin) &&
+    events.some((event) => event.eventType === "review_requested" || event.eventType === "ready_for_review")
+  ) {
+    candidates.push({
+      kind: "approve",
+      label: "Approve",
+      confirmation: `Approve ${label} "${pullRequest.title}"?`,
+      run: async () => {
+        await client.request("POST /repos/{owner}/{repo}/pulls/{pull_number}/reviews", {
+          owner: pullRequest.repositoryOwner,
+          repo: pullRequest.repositoryName,
+          pull_number: pullRequest.number,
+          event: "APPROVE",
+          headers: GITHUB_API_HEADERS,
+        });
+      },
+    });
+  }
+
+  if (
+    sameLogin(pullRequest.authorLogin, currentUserLogin) &&
+    !pullRequest.isDraft &&
+    isApproved(reviewStates) &&
+    // "clean" means GitHub sees no conflicts and every status check passing.
+    pullRequest.mergeableState === "clean"
+  ) {
+    candidates.push({
+      kind: "merge",
+      label: mergeMethod === "merge" ? "Merge" : `Merge (${mergeMethod})`,
+      confirmation: `Merge ${label} "${pullRequest.title}" using ${mergeMethod}?`,
+      run: async () => {
+        await client.request("PUT /repos/{owner}/{repo}/pulls/{pull_number}/merge", {
+          owner: pullRequest.repositoryOwner,
+          repo: pullRequest.repositoryName,
+          pull_number: pullRequest.number,
+          merge_method: mergeMethod,
+          ...(pullRequest.lastSeenHeadSha === null ? {} : { sha: pullRequest.lastSeenHeadSha }),
+          headers: GITHUB_API_HEADERS,
+        });
+      },
+    });
+  }
+
+  if (
+    sameLogin(pullRequest.authorLogin, currentUserLogin) &&
+    reviewStates.some((reviewState) => reviewState.reviewState === "CHANGES_REQUESTED")
+  ) {
+    for (const reviewerLogin of listReviewersAwaitingRerequest(listRawEvents(), currentUserLogin)) {
+      candidates.push({
+        kind: "rerequest_review",
+        key: `rerequest_review:${reviewerLogin}`,
+        label: `Re-request review from ${reviewerLogin}`,
+        confirmation: `Ask ${reviewerLogin} to review ${label} "${pullRequest.title}" again?`,
+        run: async () => {
+          await client.request(
+            "POST /repos/{owner}/{repo}/pulls/{pull_number}/requested_reviewers",
+            {
+              owner: pullRequest.repositoryOwner,
+              repo: pullRequest.repositoryName,
+              pull_number: pullRequest.number,
+              reviewers: [reviewerLogin],
+              headers: GITHUB_API_HEADERS,
+            },
+          );
+        },
+      });
+    }
+  }
+
+  const comment = findTriggeringComment(events, currentUserLogin);
+
+  if (comment !== null) {
+    const route =
+      comment.kind === "issue"
+        ? "POST /repos/{owner}/{repo}/issues/comments/{comment_id}/reactions"
+        : "POST /repos/{owner}/{repo}/pulls/comments/{comment_id}/reactions";
+
+    for (const content of settings.reactions) {
+      candidates.push({
+        kind: "react",
+        key: `react:${content}`,
+        label: REACTION_EMOJI[content],
+        confirmation: `React ${REACTION_EMOJI[content]} to ${comment.authorLogin}'s comment on ${label}?`,
+        run: async () => {
+          await client.request(route, {
+            owner: pullRequest.repositoryOwner,
+            repo: pullRequest.repositoryName,
+            comment_id: comment.commentId,
+            content,
+            headers: GITHUB_API_HEADERS,
+          });
+        },
+      });
+    }
+  }
+
+  // Replies go to the inline thread that triggered the popup, otherwise to the conversation.
+  settings.replies.forEach((reply, index) => {
+    candidates.push({
+      kind: "reply",
+      key: `reply:${index}`,
+      label: truncateLabel(reply),
+      confirmation: `Post "${reply}" on ${label}?`,
+      run: async () => {
+        if (comment?.kind === "review") {
+          await client.request(
+            "POST /repos/{owner}/{repo}/pulls/{pull_number}/comments/{comment_id}/replies",
+            {
+              owner: pullRequest.repositoryOwner,
+              repo: pullRequest.repositoryName,
+              pull_number: pullRequest.number,
+              comment_id: comment.commentId,
+              body: reply,
+              headers: GITHUB_API_HEADERS,
+            },
+          );
+          return;
+        }
+
+        await client.request("POST /repos/{owner}/{repo}/issues/{issue_number}/comments", {
+          owner: pullRequest.repositoryOwner,
+          repo: pullRequest.repositoryName,
+          issue_number: pullRequest.number,
+          body: reply,
+          headers: GITHUB_API_HEADERS,
+        });
+      },
+    });
+  });
+
+  return candidates;
+}
+
+function truncateLabel(text: string): string {
+  return text.length <= MAX_REPLY_LABEL_LENGTH ? text : `${text.slice(0, MAX_REPLY_LABEL_LENGTH - 1)}…`;
+}
+
+// The newest comment someone else left among the popup's events; reactions go to that one.
+function findTriggeringComment(
+  events: readonly NormalizedEventRecord[],
+  currentUserLogin: string,
+): { kind: "issue" | "review"; commentId: number; authorLogin: string } | null {
+  const comments = events
+    .filter(
+      (event) =>
+        (event.eventType === "issue_comment" || event.eventType === "review_inline_comment") &&
+        event.actorLogin !== null &&
+        !sameLogin(event.actorLogin, currentUserLogin),
+    )
+    .sort((left, right) => right.occurredAt.localeCompare(left.occurredAt) || right.id - left.id);
+
+  for (const event of comments) {
+    const commentId = readPayload(event).commentId;
+
+    if (typeof commentId === "number") {
+      return {
+        kind: event.eventType === "issue_comment" ? "issue" : "review",
+        commentId,
+        authorLogin: event.actorLogin ?? "",
+      };
+    }
+  }
+
+  return null;
+}
+
+function readPayload(event: Pick<NormalizedEventRecord, "payloadJson">): Record<string, unknown> {
+  try {
+    const payload = JSON.parse(event.payloadJson) as unknown;
+
+    return typeof payload === "object" && payload !== null && !Array.isArray(payload)
+      ? (payload as Record<string, unknown>)
+      : {};
+  } catch {
+    return {};
+  }
+}
+
+// Reviewers whose latest review requested changes and who have not reviewed again since
+// commits were pushed on top of it. Comment-only reviews leave a change request standing.
+export function listReviewersAwaitingRerequest(
+  rawEvents: readonly RawEventRecord[],
+  authorLogin: string,
+): string[] {
+  const reviewers = new Map<string, { login: string; pushedSince: boolean }>();
+  const orderedEvents = [...rawEvents].sort(
+    (left, right) => left.occurredAt.localeCompare(right.occurredAt) || left.id - right.id,
+  );
+
+  for (const rawEvent of orderedEvents) {
+    if (rawEvent.eventType === "committed" || rawEvent.eventType === "head_ref_force_pushed") {
+      for (const reviewer of reviewers.values()) {
+        reviewer.pushedSince = true;
+      }
+    } else if (
+      rawEvent.eventType === "pull_request_review" &&
+      rawEvent.actorLogin !== null &&
+      !sameLogin(rawEvent.actorLogin, authorLogin)
+    ) {
+      const reviewState = readReviewState(rawEvent);
+      const key = rawEvent.actorLogin.toLowerCase();
+
+      if (reviewState === "CHANGES_REQUESTED") {
+        reviewers.set(key, { login: rawEvent.actorLogin, pushedSince: false });
+      } else if (reviewState !== "COMMENTED") {
+        reviewers.delete(key);
+      }
+    }
+  }
+
+  return [...reviewers.values()]
+    .filter((reviewer) => reviewer.pushedSince)
+    .map((reviewer) => reviewer.login);
+}
+
+function readReviewState(rawEvent: Pick<RawEventRecord, "payloadJson">): string | null {
+  try {
+    const payload = JSON.parse(rawEvent.payloadJson) as { state?: unknown } | null;
+
+    return typeof payload?.state === "string" ? payload.state.toUpperCase() : null;
+  } catch {
+    return null;
+  }
+}
+
+function isApproved(reviewStates: readonly PullRequestReviewStateRecord[]): boolean {
+  return (
+    reviewStates.some((reviewState) => reviewState.reviewState === "APPROVED") &&
+    !reviewStates.some((reviewState) => reviewState.reviewState === "CHANGES_REQUESTED")
+  );
+}
+
+function sameLogin(left: string, right: string): boolean {
+  return left.toLowerCase() === right.toLowerCase();
+}
+
+function formatPullRequestLabel(
+  pullRequest: Pick<PullRequestRecord, "repositoryOwner" | "repositoryName" | "number">,
+): string {
+  return `${pullRequest.repositoryOwner}/${pullRequest.repositoryName}#${pullRequest.number}`;
+}
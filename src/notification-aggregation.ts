@@ -0,0 +1,95 @@
+import { parsePullRequestHtmlUrl } from "./github-notification-read-state.js";
+import type { LinuxNotification } from "./linux-notification-adapter.js";
+import { getLogger } from "./logger.js";
+import {
+  mergePullRequestNotifications,
+  type NotificationDispatcher,
+} from "./notification-dispatch.js";
+
+export interface AggregationWindowOptions {
+  windowMs: number;
+}
+
+export interface AggregatingNotificationDispatcher extends NotificationDispatcher {
+  stop(): void;
+}
+
+// Holds each pull request's first popup for `windowMs` and folds anything else that arrives for the
+// same pull request in the meantime into it, so a burst of comments shows up as one popup.
+export function withAggregationWindow(
+  notificationDispatcher: NotificationDispatcher,
+  options: AggregationWindowOptions,
+): AggregatingNotificationDispatcher {
+  const pendingByPullRequest = new Map<
+    string,
+    { notifications: LinuxNotification[]; timer: ReturnType<typeof setTimeout> }
+  >();
+  let isStopped = false;
+
+  return {
+    async dispatchNotification(notification) {
+      const key = readPullRequestKey(notification);
+
+      if (isStopped || key === null || notification.urgency === "critical") {
+        return notificationDispatcher.dispatchNotification(notification);
+      }
+
+      const pending = pendingByPullRequest.get(key);
+
+      if (pending !== undefined) {
+        pending.notifications.push(notification);
+        return { queued: true };
+      }
+
+      const timer = setTimeout(() => {
+        void release(key);
+      }, options.windowMs);
+
+      timer.unref?.();
+      pendingByPullRequest.set(key, { notifications: [notification], timer });
+      return { queued: true };
+    },
+    stop(): void {
+      if (isStopped) {
+        return;
+      }
+
+      isStopped = true;
+
+      for (const [key, pending] of pendingByPullRequest) {
+        clearTimeout(pending.timer);
+        void release(key);
+      }
+    },
+  };
+
+  async function release(key: string): Promise<void> {
+    const pending = pendingByPullRequest.get(key);
+
+    if (pending === undefined) {
+      return;
+    }
+
+    pendingByPullRequest.delete(key);
+
+    try {
+      await notificationDispatcher.dispatchNotification(
+        mergePullRequestNotifications(pending.notifications),
+      );
+    } catch (error) {
+      getLogger().warn("Failed to deliver aggregated notification", {
+        pullRequest: key,
+        heldCount: pending.notifications.length,
+        error,
+      });
+    }
+  }
+}
+
+function readPullRequestKey(notification: LinuxNotification): string | null {
+  const coordinates = notification.clickUrl ? parsePullRequestHtmlUrl(notification.clickUrl) : null;
+
+  return coordinates === null
+    ? null
+    : `${coordinates.repositoryOwner}/${coordinates.repositoryName}#${coordinates.number}`.toLowerCase();
+}
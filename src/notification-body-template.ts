@@ -0,0 +1,148 @@
+// A small Mustache-style language for the popup body shown by notification servers with markup:
+// {{name}} inserts a value, {{#name}}...{{/name}} repeats a block for each list item or shows it
+// when the value is set, and {{^name}}...{{/name}} shows a block when it isn't. Dots reach into
+// objects ({{pullRequest.title}}), and {{.}} is the current list item.
+
+import type { NotificationPullRequestDetails } from "./linux-notification-adapter.js";
+
+const TAG_PATTERN = /\{\{(.*?)\}\}/gs;
+const NAME_PATTERN = /^(?:\.|[A-Za-z_]\w*(?:\.[A-Za-z_]\w*)*)$/;
+
+// Every string is already escaped for the notification server's markup when it gets here.
+export interface NotificationBodyTemplateContext {
+  title: string;
+  body: string;
+  reason: string | null;
+  url: string | null;
+  header: { text: string; avatar: string | null };
+  paragraphs: { actor: string | null; text: string; avatar: string | null }[];
+  pullRequest: NotificationPullRequestDetails | null;
+}
+
+export type NotificationBodyTemplate = (context: NotificationBodyTemplateContext) => string;
+
+interface SectionNode {
+  kind: "section";
+  name: string;
+  inverted: boolean;
+  children: TemplateNode[];
+}
+
+type TemplateNode = { kind: "text"; text: string } | { kind: "value"; name: string } | SectionNode;
+
+export class NotificationBodyTemplateError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "NotificationBodyTemplateError";
+  }
+}
+
+// Parses the template once, so mistakes surface when the config is loaded instead of per popup.
+export function compileNotificationBodyTemplate(source: string): NotificationBodyTemplate {
+  const nodes = parseTemplate(source);
+
+  return (context) => renderNodes(nodes, [context]);
+}
+
+function parseTemplate(source: string): TemplateNode[] {
+  const root: TemplateNode[] = [];
+  const openSections: SectionNode[] = [];
+  let children = root;
+  let textStart = 0;
+
+  for (const match of source.matchAll(TAG_PATTERN)) {
+    children.push({ kind: "text", text: source.slice(textStart, match.index) });
+    textStart = match.index + match[0].length;
+
+    const tag = match[1]!.trim();
+    const sigil = tag[0] === "#" || tag[0] === "^" || tag[0] === "/" ? tag[0] : "";
+    const name = tag.slice(sigil.length).trim();
+
+    if (!NAME_PATTERN.test(name)) {
+      throw new NotificationBodyTemplateError(`Unsupported template tag {{${match[1]}}}`);
+    }
+
+    if (sigil === "/") {
+      const section = openSections.pop();
+
+      if (section?.name !== name) {
+        throw new NotificationBodyTemplateError(
+          section === undefined
+            ? `{{/${name}}} closes a section that was never opened`
+            : `{{/${name}}} closes {{#${section.name}}}`,
+        );
+      }
+
+      children = openSections.at(-1)?.children ?? root;
+    } else if (sigil === "") {
+      children.push({ kind: "value", name });
+    } else {
+      const section: SectionNode = { kind: "section", name, inverted: sigil === "^", children: [] };
+
+      children.push(section);
+      openSections.push(section);
+      children = section.children;
+    }
+  }
+
+  const unclosedSection = openSections.pop();
+
+  if (unclosedSection !== undefined) {
+    throw new NotificationBodyTemplateError(`{{#${unclosedSection.name}}} is never closed`);
+  }
+
+  children.push({ kind: "text", text: source.slice(textStart) });
+  return root;
+}
+
+function renderNodes(nodes: readonly TemplateNode[], scopes: readonly unknown[]): string {
+  return nodes
+    .map((node) => {
+      if (node.kind === "text") {
+        return node.text;
+      }
+
+      const value = lookUp(node.name, scopes);
+
+      if (node.kind === "value") {
+        return typeof value === "string" || typeof value === "number" ? String(value) : "";
+      }
+
+      if (node.inverted) {
+        return isSet(value) ? "" : renderNodes(node.children, scopes);
+      }
+
+      if (Array.isArray(value)) {
+        return value.map((item) => renderNodes(node.children, [...scopes, item])).join("");
+      }
+
+      return isSet(value) ? renderNodes(node.children, [...scopes, value]) : "";
+    })
+    .join("");
+}
+
+// Names resolve against the innermost list item or object first, then the enclosing ones.
+function lookUp(name: string, scopes: readonly unknown[]): unknown {
+  if (name === ".") {
+    return scopes.at(-1);
+  }
+
+  const [first, ...rest] = name.split(".");
+  const scope = scopes.findLast(
+    (candidate) => typeof candidate === "object" && candidate !== null && first! in candidate,
+  ) as Record<string, unknown> | undefined;
+
+  return rest.reduce<unknown>(
+    (value, key) =>
+      typeof value === "object" && value !== null
+        ? (value as Record<string, unknown>)[key]
+        : undefined,
+    scope?.[first!],
+  );
+}
+
+function isSet(value: unknown): boolean {
+  return Array.isArray(value)
+    ? value.length > 0
+    : value !== null && value !== undefined && value !== false && value !== "" && value !== 0;
+}
@@ -0,0 +1,40 @@
+import type { LinuxNotification, NotificationUrgency } from "./linux-notification-adapter.js";
+import { compilePathPattern } from "./path-patterns.js";
+import type { PullRequestRecord } from "./pull-request-repository.js";
+
+// A named route for pull requests in one part of a monorepo, e.g. "frontend" for `web/**`.
+export interface NotificationChannel {
+  name: string;
+  paths: string[];
+  soundFile?: string;
+  urgency?: NotificationUrgency;
+}
+
+// The first channel with a pattern matching any changed file wins, so list narrower channels first.
+export function resolveNotificationChannel(
+  pullRequest: Pick<PullRequestRecord, "changedFilePaths">,
+  channels: readonly NotificationChannel[],
+): NotificationChannel | null {
+  for (const channel of channels) {
+    const patterns = channel.paths.map((pattern) => compilePathPattern(pattern));
+
+    if (pullRequest.changedFilePaths.some((filePath) => patterns.some((pattern) => pattern.test(filePath)))) {
+      return channel;
+    }
+  }
+
+  return null;
+}
+
+export function applyNotificationChannel(
+  notification: LinuxNotification,
+  channel: NotificationChannel,
+): LinuxNotification {
+  return {
+    ...notification,
+    ...(channel.soundFile === undefined ? {} : { soundFile: channel.soundFile }),
+    ...(channel.urgency === undefined
+      ? {}
+      : { urgency: channel.urgency, sticky: channel.urgency === "critical" }),
+  };
+}
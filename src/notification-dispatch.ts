@@ -1,26 +1,61 @@
 import { DatabaseSync } from "node:sqlite";
 import { fileURLToPath } from "node:url";
 
+import {
+  renderCommentThreadDocument,
+  shouldOfferCommentThreadView,
+} from "./comment-thread-document.js";
 import {
   LinuxNotificationAdapter,
   type LinuxNotification,
+  type NotificationAction,
+  type NotificationPullRequestDetails,
 } from "./linux-notification-adapter.js";
 import { getLogger } from "./logger.js";
+import {
+  applyNotificationChannel,
+  resolveNotificationChannel,
+  type NotificationChannel,
+} from "./notification-channels.js";
 import { NormalizedEventRepository, type NormalizedEventRecord } from "./normalized-event-repository.js";
+import type { PullRequestActionProvider } from "./notification-actions.js";
 import { preparePullRequestNotifications } from "./notification-preparation.js";
 import { NotificationRecordRepository, type NotificationRecord } from "./notification-record-repository.js";
 import { renderNotificationMarkup } from "./notification-rendering.js";
+import { formatLinkedIssuesText } from "./pull-request-linked-issues.js";
 import { resolvePullRequestStateAssetFilePath } from "./pull-request-state-assets.js";
+import { formatPullRequestStateLabel } from "./pull-request-state.js";
 import { PullRequestRepository, type PullRequestRecord } from "./pull-request-repository.js";
+import {
+  PullRequestReviewStateRepository,
+  type PullRequestReviewStateRecord,
+} from "./pull-request-review-state-repository.js";
+import { RawEventRepository } from "./raw-event-repository.js";
+import {
+  applyRepositoryOverride,
+  resolveRepositoryOverride,
+  type RepositoryOverride,
+} from "./repository-overrides.js";
 
 export interface NotificationDispatcher {
   dispatchNotification(notification: LinuxNotification): Promise<unknown>;
 }
 
+// Sound files replacing the bundled ones played on your own pull requests; null silences one.
+export interface NotificationSounds {
+  approved?: string | null;
+  changesRequested?: string | null;
+  comment?: string | null;
+}
+
 export interface DispatchPullRequestNotificationsOptions {
   dispatchedAt?: string;
   currentUserLogin?: string;
   notificationDispatcher?: NotificationDispatcher;
+  notificationChannels?: readonly NotificationChannel[];
+  notificationSounds?: NotificationSounds;
+  repositoryOverrides?: readonly RepositoryOverride[];
+  pullRequestActions?: PullRequestActionProvider;
   notificationRecordRepository?: Pick<
     NotificationRecordRepository,
     "listPendingNotificationRecordsForPullRequest" | "updateNotificationRecordDelivery"
@@ -45,6 +80,9 @@ export class NotificationDispatchError extends Error {
 
 const APPROVED_SOUND_FILE_PATH = fileURLToPath(new URL("../assets/approved.wav", import.meta.url));
 const COMMENT_SOUND_FILE_PATH = fileURLToPath(new URL("../assets/comment.wav", import.meta.url));
+const CHANGES_REQUESTED_SOUND_FILE_PATH = fileURLToPath(
+  new URL("../assets/changes-requested.wav", import.meta.url),
+);
 
 export async function dispatchPullRequestNotifications(
   database: DatabaseSync,
@@ -56,38 +94,95 @@ export async function dispatchPullRequestNotifications(
   const notificationRecordRepository =
     options.notificationRecordRepository ?? new NotificationRecordRepository(database);
   const normalizedEventRepository = new NormalizedEventRepository(database);
+  const reviewStateRepository = new PullRequestReviewStateRepository(database);
+  const rawEventRepository = new RawEventRepository(database);
   const onError = options.onError ?? logNotificationDispatchError;
-  const preparation = preparePullRequestNotifications(database, pullRequest);
+  const preparation = preparePullRequestNotifications(
+    database,
+    pullRequest,
+    options.currentUserLogin === undefined ? {} : { currentUserLogin: options.currentUserLogin },
+  );
 
   let dispatchedCount = 0;
   let failedCount = 0;
 
-  for (const record of notificationRecordRepository.listPendingNotificationRecordsForPullRequest(
+  const records = notificationRecordRepository.listPendingNotificationRecordsForPullRequest(
     pullRequest.id,
-  )) {
+  );
+
+  if (records.length > 0) {
     try {
-      await notificationDispatcher.dispatchNotification(
-        buildDispatchNotification(
-          pullRequest,
-          record,
-          normalizedEventRepository,
-          options.currentUserLogin,
+      // Everything that became ready in this poll goes out as one popup with one sound.
+      const notification = appendPullRequestContext(
+        mergePullRequestNotifications(
+          records.map((record) =>
+            buildDispatchNotification(
+              pullRequest,
+              record,
+              normalizedEventRepository,
+              options.currentUserLogin,
+              options.notificationSounds,
+            ),
+          ),
         ),
+        pullRequest,
+        reviewStateRepository.listReviewStatesForPullRequest(pullRequest.id),
+        options.currentUserLogin,
       );
-      notificationRecordRepository.updateNotificationRecordDelivery(record.id, {
-        deliveryStatus: "sent",
-        deliveredAt: dispatchedAt,
-      });
-      dispatchedCount += 1;
+      const repositoryOverride = resolveRepositoryOverride(
+        pullRequest,
+        options.repositoryOverrides ?? [],
+      );
+      const channel = resolveNotificationChannel(pullRequest, options.notificationChannels ?? []);
+      const actions = listPullRequestActions(
+        pullRequest,
+        records,
+        normalizedEventRepository,
+        reviewStateRepository,
+        rawEventRepository,
+        options.pullRequestActions,
+        options.currentUserLogin,
+      );
+
+      const repositoryNotification =
+        repositoryOverride === null
+          ? notification
+          : applyRepositoryOverride(notification, repositoryOverride);
+
+      if (repositoryOverride?.mute) {
+        getLogger().debug("Skipped popup for muted repository", {
+          pullRequest: formatPullRequestLabel(pullRequest),
+          repositoryOverride: repositoryOverride.repository,
+        });
+      } else {
+        await notificationDispatcher.dispatchNotification({
+          ...(channel === null
+            ? repositoryNotification
+            : applyNotificationChannel(repositoryNotification, channel)),
+          ...(actions.length === 0 ? {} : { actions }),
+        });
+      }
+
+      for (const record of records) {
+        notificationRecordRepository.updateNotificationRecordDelivery(record.id, {
+          deliveryStatus: "sent",
+          deliveredAt: dispatchedAt,
+        });
+      }
+
+      dispatchedCount = records.length;
     } catch (error) {
-      notificationRecordRepository.updateNotificationRecordDelivery(record.id, {
-        deliveryStatus: "failed",
-        deliveredAt: null,
-      });
-      failedCount += 1;
+      for (const record of records) {
+        notificationRecordRepository.updateNotificationRecordDelivery(record.id, {
+          deliveryStatus: "failed",
+          deliveredAt: null,
+        });
+      }
+
+      failedCount = records.length;
       onError(
         new NotificationDispatchError(
-          `Failed to dispatch notification record ${record.id} for pull request ${formatPullRequestLabel(pullRequest)}: ${getErrorMessage(error)}`,
+          `Failed to dispatch notification record${records.length === 1 ? "" : "s"} ${records.map((record) => record.id).join(", ")} for pull request ${formatPullRequestLabel(pullRequest)}: ${getErrorMessage(error)}`,
         ),
       );
     }
@@ -118,6 +213,8 @@ export interface ResendNotificationRecordOptions {
   dispatchedAt?: string;
   currentUserLogin?: string;
   notificationDispatcher?: NotificationDispatcher;
+  notificationSounds?: NotificationSounds;
+  pullRequestActions?: PullRequestActionProvider;
   notificationRecordRepository?: Pick<
     NotificationRecordRepository,
     | "resetNotificationRecordDelivery"
@@ -158,14 +255,32 @@ export async function resendNotificationRecord(
       );
     }
 
-    await notificationDispatcher.dispatchNotification(
-      buildDispatchNotification(
+    const reviewStateRepository = new PullRequestReviewStateRepository(database);
+    const actions = listPullRequestActions(
+      pullRequest,
+      [record],
+      normalizedEventRepository,
+      reviewStateRepository,
+      new RawEventRepository(database),
+      options.pullRequestActions,
+      options.currentUserLogin,
+    );
+
+    await notificationDispatcher.dispatchNotification({
+      ...appendPullRequestContext(
+        buildDispatchNotification(
+          pullRequest,
+          record,
+          normalizedEventRepository,
+          options.currentUserLogin,
+          options.notificationSounds,
+        ),
         pullRequest,
-        record,
-        normalizedEventRepository,
+        reviewStateRepository.listReviewStatesForPullRequest(pullRequest.id),
         options.currentUserLogin,
       ),
-    );
+      ...(actions.length === 0 ? {} : { actions }),
+    });
     notificationRecordRepository.updateNotificationRecordDelivery(record.id, {
       deliveryStatus: "sent",
       deliveredAt: dispatchedAt,
@@ -193,28 +308,224 @@ function buildDispatchNotification(
     "getNormalizedEventById" | "listNormalizedEventsForBundle" | "listNormalizedEventsForPullRequest"
   >,
   currentUserLogin?: string,
+  notificationSounds: NotificationSounds = {},
 ): LinuxNotification {
   const events = resolveNotificationEvents(record, normalizedEventRepository);
   const allPullRequestEvents = normalizedEventRepository.listNormalizedEventsForPullRequest(
     pullRequest.id,
   );
-  const soundFile = resolveNotificationSoundFilePath(pullRequest, events, currentUserLogin);
+  const soundFile = resolveNotificationSoundFilePath(
+    pullRequest,
+    events,
+    currentUserLogin,
+    notificationSounds,
+  );
+  const reason = explainNotificationReason(
+    pullRequest,
+    events,
+    allPullRequestEvents,
+    currentUserLogin,
+  );
 
   return {
     title: record.title,
     body: record.body,
     clickUrl: record.clickUrl,
+    notificationRecordId: record.id,
+    pullRequest: describeNotificationPullRequest(pullRequest),
+    ...(events === null ? {} : { eventTypes: [...new Set(events.map((event) => event.eventType))] }),
     icon: resolvePullRequestStateAssetFilePath(pullRequest),
+    ...(reason === null ? {} : { reason }),
     ...(soundFile === undefined ? {} : { soundFile }),
     sticky: shouldKeepNotificationSticky(pullRequest, events, allPullRequestEvents, currentUserLogin),
+    ...(events !== null && shouldOfferCommentThreadView(events)
+      ? { expandedHtml: renderCommentThreadDocument(pullRequest, events) }
+      : {}),
     ...(events === null || events.length === 0 ? {} : { markup: renderNotificationMarkup(pullRequest, events) }),
   };
 }
 
+// Added once after merging so pull request context ("Closes #123", who still blocks the merge)
+// trails the popup instead of every event.
+function appendPullRequestContext(
+  notification: LinuxNotification,
+  pullRequest: PullRequestRecord,
+  reviewStates: readonly Pick<PullRequestReviewStateRecord, "reviewerLogin" | "reviewState">[],
+  currentUserLogin?: string,
+): LinuxNotification {
+  const lines = [
+    formatBlockingReviewersText(reviewStates, currentUserLogin),
+    formatLinkedIssuesText(pullRequest, pullRequest.linkedIssues),
+  ].filter((line): line is string => line !== null);
+
+  if (lines.length === 0) {
+    return notification;
+  }
+
+  return {
+    ...notification,
+    body: [notification.body, ...lines].join("\n\n"),
+    ...(notification.markup === undefined
+      ? {}
+      : {
+          markup: {
+            ...notification.markup,
+            paragraphs: [
+              ...notification.markup.paragraphs,
+              ...lines.map((text) => ({ actorLogin: null, actorAvatarKey: null, actorAvatarUrl: null, text })),
+            ],
+          },
+        }),
+  };
+}
+
+// Review states keep only each reviewer's latest review, so these are the outstanding change requests.
+function formatBlockingReviewersText(
+  reviewStates: readonly Pick<PullRequestReviewStateRecord, "reviewerLogin" | "reviewState">[],
+  currentUserLogin?: string,
+): string | null {
+  const blockingReviewers = reviewStates
+    .filter((reviewState) => reviewState.reviewState === "CHANGES_REQUESTED")
+    .map((reviewState) =>
+      currentUserLogin !== undefined && sameLogin(reviewState.reviewerLogin, currentUserLogin)
+        ? "you"
+        : reviewState.reviewerLogin,
+    );
+
+  return blockingReviewers.length === 0 ? null : `⛔ Changes requested by ${blockingReviewers.join(", ")}`;
+}
+
+export function mergePullRequestNotifications(
+  notifications: readonly LinuxNotification[],
+): LinuxNotification {
+  const [first, ...rest] = notifications;
+
+  if (first === undefined) {
+    throw new NotificationDispatchError("No notifications to merge");
+  }
+
+  if (rest.length === 0) {
+    return first;
+  }
+
+  const reasons = [...new Set(notifications.flatMap((notification) =>
+    notification.reason === undefined ? [] : [notification.reason],
+  ))];
+  const soundFile = notifications.find((notification) => notification.soundFile !== undefined)?.soundFile;
+  const expandedHtml = notifications.find((notification) => notification.expandedHtml !== undefined)
+    ?.expandedHtml;
+  const markups = notifications.flatMap((notification) =>
+    notification.markup === undefined ? [] : [notification.markup],
+  );
+  const eventTypes = [...new Set(notifications.flatMap((notification) => notification.eventTypes ?? []))];
+  // The newest popup's buttons act on the newest activity.
+  const actions = notifications.findLast((notification) => notification.actions !== undefined)?.actions;
+  const urgency = notifications.some((notification) => notification.urgency === "critical")
+    ? "critical"
+    : notifications.find((notification) => notification.urgency !== undefined)?.urgency;
+  const [firstMarkup] = markups;
+
+  return {
+    title: first.title,
+    body: notifications.map((notification) => notification.body).join("\n\n"),
+    clickUrl: notifications.find((notification) => notification.clickUrl)?.clickUrl ?? null,
+    ...(first.notificationRecordId === undefined
+      ? {}
+      : { notificationRecordId: first.notificationRecordId }),
+    ...(first.pullRequest === undefined ? {} : { pullRequest: first.pullRequest }),
+    ...(eventTypes.length === 0 ? {} : { eventTypes }),
+    ...(first.icon === undefined ? {} : { icon: first.icon }),
+    ...(reasons.length === 0 ? {} : { reason: reasons.join("\n") }),
+    ...(soundFile === undefined ? {} : { soundFile }),
+    sticky: notifications.some((notification) => notification.sticky === true),
+    ...(urgency === undefined ? {} : { urgency }),
+    ...(actions === undefined ? {} : { actions }),
+    ...(expandedHtml === undefined ? {} : { expandedHtml }),
+    ...(firstMarkup === undefined
+      ? {}
+      : {
+          markup: {
+            ...firstMarkup,
+            paragraphs: markups.flatMap((markup) => markup.paragraphs),
+          },
+        }),
+  };
+}
+
+function describeNotificationPullRequest(
+  pullRequest: PullRequestRecord,
+): NotificationPullRequestDetails {
+  return {
+    repository: `${pullRequest.repositoryOwner}/${pullRequest.repositoryName}`,
+    number: pullRequest.number,
+    title: pullRequest.title,
+    url: pullRequest.url,
+    author: pullRequest.authorLogin,
+    state: formatPullRequestStateLabel(pullRequest).toLowerCase(),
+    draft: pullRequest.isDraft,
+    headBranch: pullRequest.headBranch,
+    baseBranch: pullRequest.baseBranch,
+    additions: pullRequest.additions,
+    deletions: pullRequest.deletions,
+  };
+}
+
+function explainNotificationReason(
+  pullRequest: Pick<PullRequestRecord, "repositoryOwner" | "authorLogin" | "trackingReason">,
+  events: readonly NormalizedEventRecord[] | null,
+  allPullRequestEvents: readonly NormalizedEventRecord[],
+  currentUserLogin?: string,
+): string | null {
+  if (events === null || currentUserLogin === undefined) {
+    return null;
+  }
+
+  const reviewRequestedEvent = events.find((event) => event.eventType === "review_requested");
+
+  if (reviewRequestedEvent !== undefined) {
+    const teamSlug = parsePayload(reviewRequestedEvent.payloadJson)?.requestedTeamSlug;
+
+    return typeof teamSlug === "string"
+      ? `Your team @${pullRequest.repositoryOwner}/${teamSlug} was requested to review`
+      : "You were requested to review";
+  }
+
+  if (events.some((event) => event.eventType === "pr_assigned")) {
+    return "You were assigned to this pull request";
+  }
+
+  if (events.some((event) => event.eventType === "force_pushed_after_review")) {
+    return "You reviewed this pull request before it was force-pushed";
+  }
+
+  if (sameLogin(currentUserLogin, pullRequest.authorLogin)) {
+    return "You authored this pull request";
+  }
+
+  if (hasParticipantThreadComment(events, allPullRequestEvents, currentUserLogin)) {
+    return "You commented on this thread";
+  }
+
+  if (pullRequest.trackingReason === "manual") {
+    return "You are tracking this pull request";
+  }
+
+  if (pullRequest.trackingReason === "mentioned") {
+    return "You were mentioned on this pull request";
+  }
+
+  if (pullRequest.trackingReason === "github_inbox") {
+    return "This pull request is in your GitHub notifications";
+  }
+
+  return null;
+}
+
 function resolveNotificationSoundFilePath(
   pullRequest: Pick<PullRequestRecord, "authorLogin">,
   events: readonly NormalizedEventRecord[] | null,
-  currentUserLogin?: string,
+  currentUserLogin: string | undefined,
+  notificationSounds: NotificationSounds,
 ): string | undefined {
   if (
     events === null ||
@@ -224,8 +535,13 @@ function resolveNotificationSoundFilePath(
     return undefined;
   }
 
+  // Changes requested blocks the merge, so it wins over an approval arriving in the same popup.
+  if (events.some((event) => event.eventType === "review_changes_requested")) {
+    return pickSoundFile(notificationSounds.changesRequested, CHANGES_REQUESTED_SOUND_FILE_PATH);
+  }
+
   if (events.some((event) => event.eventType === "review_approved")) {
-    return APPROVED_SOUND_FILE_PATH;
+    return pickSoundFile(notificationSounds.approved, APPROVED_SOUND_FILE_PATH);
   }
 
   if (
@@ -236,12 +552,20 @@ function resolveNotificationSoundFilePath(
         event.eventType === "review_submitted",
     )
   ) {
-    return COMMENT_SOUND_FILE_PATH;
+    return pickSoundFile(notificationSounds.comment, COMMENT_SOUND_FILE_PATH);
   }
 
   return undefined;
 }
 
+function pickSoundFile(configuredSoundFile: string | null | undefined, bundledSoundFile: string): string | undefined {
+  if (configuredSoundFile === undefined) {
+    return bundledSoundFile;
+  }
+
+  return configuredSoundFile ?? undefined;
+}
+
 function shouldKeepNotificationSticky(
   pullRequest: Pick<PullRequestRecord, "authorLogin">,
   events: readonly NormalizedEventRecord[] | null,
@@ -258,7 +582,14 @@ function shouldKeepNotificationSticky(
     return false;
   }
 
-  if (events.some((event) => isReviewRequestEventType(event.eventType))) {
+  if (
+    events.some(
+      (event) =>
+        isReviewRequestEventType(event.eventType) ||
+        event.eventType === "pr_assigned" ||
+        event.eventType === "force_pushed_after_review",
+    )
+  ) {
     return true;
   }
 
@@ -276,6 +607,8 @@ function shouldKeepNotificationSticky(
 function isStickyNotificationEventType(eventType: string): boolean {
   return (
     isReviewRequestEventType(eventType) ||
+    eventType === "pr_assigned" ||
+    eventType === "force_pushed_after_review" ||
     eventType === "issue_comment" ||
     eventType === "review_inline_comment" ||
     eventType === "review_submitted" ||
@@ -361,6 +694,31 @@ function sameLogin(left: string, right: string): boolean {
   return left.localeCompare(right, undefined, { sensitivity: "accent" }) === 0;
 }
 
+function listPullRequestActions(
+  pullRequest: PullRequestRecord,
+  records: readonly NotificationRecord[],
+  normalizedEventRepository: Pick<
+    NormalizedEventRepository,
+    "getNormalizedEventById" | "listNormalizedEventsForBundle"
+  >,
+  reviewStateRepository: Pick<PullRequestReviewStateRepository, "listReviewStatesForPullRequest">,
+  rawEventRepository: Pick<RawEventRepository, "listRawEventsForPullRequest">,
+  pullRequestActions: PullRequestActionProvider | undefined,
+  currentUserLogin: string | undefined,
+): NotificationAction[] {
+  if (pullRequestActions === undefined || currentUserLogin === undefined) {
+    return [];
+  }
+
+  return pullRequestActions({
+    pullRequest,
+    events: records.flatMap((record) => resolveNotificationEvents(record, normalizedEventRepository) ?? []),
+    reviewStates: reviewStateRepository.listReviewStatesForPullRequest(pullRequest.id),
+    listRawEvents: () => rawEventRepository.listRawEventsForPullRequest(pullRequest.id),
+    currentUserLogin,
+  });
+}
+
 function resolveNotificationEvents(
   record: NotificationRecord,
   normalizedEventRepository: Pick<
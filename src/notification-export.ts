@@ -0,0 +1,240 @@
+import { DatabaseSync } from "node:sqlite";
+
+import { parseDuration, resolveAppPaths } from "./config.js";
+import { initializeDatabase } from "./database.js";
+
+const EXPORT_USAGE = "Usage: octopulse export [--format json|csv] [--since <duration>]";
+const CSV_COLUMNS = [
+  "id",
+  "created_at",
+  "delivered_at",
+  "read_at",
+  "delivery_status",
+  "repository",
+  "pull_request_number",
+  "pull_request_title",
+  "pull_request_author",
+  "actors",
+  "event_types",
+  "title",
+  "body",
+  "click_url",
+] as const;
+
+export type NotificationExportFormat = "json" | "csv";
+
+export interface NotificationExportRow {
+  id: number;
+  createdAt: string;
+  deliveredAt: string | null;
+  readAt: string | null;
+  deliveryStatus: string;
+  repository: string;
+  pullRequestNumber: number;
+  pullRequestTitle: string;
+  pullRequestAuthor: string;
+  actors: string[];
+  eventTypes: string[];
+  title: string;
+  body: string;
+  clickUrl: string | null;
+}
+
+export interface ListNotificationExportRowsOptions {
+  since?: Date;
+}
+
+export interface ExportCommandOptions {
+  format: NotificationExportFormat;
+  sinceMs: number | null;
+}
+
+export class NotificationExportError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "NotificationExportError";
+  }
+}
+
+export function listNotificationExportRows(
+  database: DatabaseSync,
+  options: ListNotificationExportRowsOptions = {},
+): NotificationExportRow[] {
+  const rows = database
+    .prepare(
+      [
+        "SELECT",
+        "  notification_record.id,",
+        "  notification_record.created_at,",
+        "  notification_record.delivered_at,",
+        "  notification_record.read_at,",
+        "  notification_record.delivery_status,",
+        "  notification_record.title,",
+        "  notification_record.body,",
+        "  notification_record.click_url,",
+        "  pull_request.repository_owner,",
+        "  pull_request.repository_name,",
+        "  pull_request.number AS pull_request_number,",
+        "  pull_request.title AS pull_request_title,",
+        "  pull_request.author_login AS pull_request_author,",
+        "  (",
+        "    SELECT group_concat(DISTINCT normalized_event.actor_login)",
+        "    FROM NormalizedEvent normalized_event",
+        "    WHERE normalized_event.id = notification_record.normalized_event_id",
+        "      OR normalized_event.event_bundle_id = notification_record.event_bundle_id",
+        "  ) AS actors,",
+        "  (",
+        "    SELECT group_concat(DISTINCT normalized_event.event_type)",
+        "    FROM NormalizedEvent normalized_event",
+        "    WHERE normalized_event.id = notification_record.normalized_event_id",
+        "      OR normalized_event.event_bundle_id = notification_record.event_bundle_id",
+        "  ) AS event_types",
+        "FROM NotificationRecord notification_record",
+        "INNER JOIN PullRequest pull_request",
+        "  ON pull_request.id = notification_record.pull_request_id",
+        // julianday() normalizes both SQLite's CURRENT_TIMESTAMP format and ISO strings.
+        options.since === undefined
+          ? ""
+          : "WHERE julianday(notification_record.created_at) >= julianday(?)",
+        "ORDER BY notification_record.created_at ASC, notification_record.id ASC",
+      ]
+        .filter((line) => line.length > 0)
+        .join("\n"),
+    )
+    .all(...(options.since === undefined ? [] : [options.since.toISOString()]));
+
+  return rows.map((row) => mapNotificationExportRow(row));
+}
+
+export function formatNotificationExport(
+  rows: readonly NotificationExportRow[],
+  format: NotificationExportFormat,
+): string {
+  if (format === "json") {
+    return JSON.stringify(rows, null, 2);
+  }
+
+  return [
+    CSV_COLUMNS.join(","),
+    ...rows.map((row) =>
+      [
+        row.id,
+        row.createdAt,
+        row.deliveredAt,
+        row.readAt,
+        row.deliveryStatus,
+        row.repository,
+        row.pullRequestNumber,
+        row.pullRequestTitle,
+        row.pullRequestAuthor,
+        row.actors.join(" "),
+        row.eventTypes.join(" "),
+        row.title,
+        row.body,
+        row.clickUrl,
+      ]
+        .map((value) => escapeCsvValue(value))
+        .join(","),
+    ),
+  ].join("\n");
+}
+
+export function parseExportCommandArgs(args: readonly string[]): ExportCommandOptions {
+  let format: NotificationExportFormat = "json";
+  let sinceMs: number | null = null;
+
+  for (let index = 0; index < args.length; index += 1) {
+    const flag = args[index];
+    const value = args[index + 1];
+
+    if ((flag !== "--format" && flag !== "--since") || value === undefined) {
+      throw new NotificationExportError(EXPORT_USAGE);
+    }
+
+    index += 1;
+
+    if (flag === "--format") {
+      if (value !== "json" && value !== "csv") {
+        throw new NotificationExportError(`--format must be "json" or "csv", received "${value}"`);
+      }
+
+      format = value;
+      continue;
+    }
+
+    sinceMs = parseDuration(value, "--since");
+  }
+
+  return { format, sinceMs };
+}
+
+export function runExportCommand(
+  args: readonly string[],
+  database: DatabaseSync,
+  now: Date = new Date(),
+): string {
+  const { format, sinceMs } = parseExportCommandArgs(args);
+  const rows = listNotificationExportRows(
+    database,
+    sinceMs === null ? {} : { since: new Date(now.getTime() - sinceMs) },
+  );
+
+  return formatNotificationExport(rows, format);
+}
+
+function mapNotificationExportRow(row: unknown): NotificationExportRow {
+  if (typeof row !== "object" || row === null) {
+    throw new NotificationExportError("Expected a notification export row from SQLite");
+  }
+
+  const value = row as Record<string, unknown>;
+
+  return {
+    id: Number(value.id),
+    createdAt: String(value.created_at),
+    deliveredAt: readNullableString(value.delivered_at),
+    readAt: readNullableString(value.read_at),
+    deliveryStatus: String(value.delivery_status),
+    repository: `${String(value.repository_owner)}/${String(value.repository_name)}`,
+    pullRequestNumber: Number(value.pull_request_number),
+    pullRequestTitle: String(value.pull_request_title),
+    pullRequestAuthor: String(value.pull_request_author),
+    actors: splitGroupConcat(value.actors),
+    eventTypes: splitGroupConcat(value.event_types),
+    title: String(value.title),
+    body: String(value.body),
+    clickUrl: readNullableString(value.click_url),
+  };
+}
+
+function readNullableString(value: unknown): string | null {
+  return typeof value === "string" ? value : null;
+}
+
+function splitGroupConcat(value: unknown): string[] {
+  return typeof value === "string" && value.length > 0 ? value.split(",") : [];
+}
+
+function escapeCsvValue(value: string | number | null): string {
+  if (value === null) {
+    return "";
+  }
+
+  const text = String(value);
+
+  return /[",\r\n]/.test(text) ? `"${text.replaceAll('"', '""')}"` : text;
+}
+
+if (import.meta.url === new URL(process.argv[1] ?? "", "file:").href) {
+  let database: DatabaseSync | undefined;
+
+  try {
+    database = initializeDatabase(resolveAppPaths());
+    console.log(runExportCommand(process.argv.slice(2), database));
+  } catch (error) {
+    console.error(error instanceof Error ? error.message : String(error));
+    process.exitCode = 1;
+  } finally {
+    database?.close();
+  }
+}
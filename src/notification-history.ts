@@ -44,6 +44,7 @@ export interface NotificationHistoryEntry {
   deliveryStatus: NotificationDeliveryStatus;
   createdAt: string;
   deliveredAt: string | null;
+  readAt: string | null;
   decisionStates: DecisionState[];
   eventTypes: string[];
   actorClasses: ActorClass[];
@@ -59,6 +60,8 @@ export interface NotificationHistoryEntry {
 
 export interface ListNotificationHistoryOptions {
   filters?: ListActivityFeedOptions["filters"];
+  // Lists only records not yet marked read, which is what the inbox page shows.
+  unreadOnly?: boolean;
   page?: number;
   pageSize?: number;
   notificationRecordRepository?: Pick<
@@ -82,8 +85,9 @@ export function listNotificationHistory(
     options.normalizedEventRepository ?? new NormalizedEventRepository(database);
   const pullRequestRepository = options.pullRequestRepository ?? new PullRequestRepository(database);
   const filters = options.filters ?? DEFAULT_ACTIVITY_FEED_FILTERS;
+  const unreadOnly = options.unreadOnly ?? false;
   const pagination = resolvePaginationWindow(
-    notificationRecordRepository.countNotificationRecords(filters),
+    notificationRecordRepository.countNotificationRecords(filters, { unreadOnly }),
     options.page ?? 1,
     options.pageSize ?? DEFAULT_ACTIVITY_PAGE_SIZE,
   );
@@ -92,6 +96,7 @@ export function listNotificationHistory(
     entries: notificationRecordRepository
       .listNotificationRecords({
         filters,
+        unreadOnly,
         limit: pagination.limit,
         offset: pagination.offset,
       })
@@ -126,6 +131,7 @@ function buildNotificationHistoryEntry(
     deliveryStatus: record.deliveryStatus,
     createdAt: record.createdAt,
     deliveredAt: record.deliveredAt,
+    readAt: record.readAt,
     decisionStates: collectDecisionStates(events),
     eventTypes: collectEventTypes(events),
     actorClasses: collectActorClasses(events),
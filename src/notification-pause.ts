@@ -0,0 +1,53 @@
+import type { LinuxNotification } from "./linux-notification-adapter.js";
+import { getLogger } from "./logger.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+import { buildAwayDigestNotification } from "./session-presence-buffer.js";
+
+export interface PausableNotificationDispatcher extends NotificationDispatcher {
+  isPaused(): boolean;
+  togglePaused(): Promise<boolean>;
+}
+
+export interface NotificationPauseOptions {
+  overflowClickUrl?: () => string;
+}
+
+export function withNotificationPause(
+  notificationDispatcher: NotificationDispatcher,
+  options: NotificationPauseOptions = {},
+): PausableNotificationDispatcher {
+  const queuedNotifications: LinuxNotification[] = [];
+  let isPaused = false;
+
+  return {
+    async dispatchNotification(notification) {
+      // Critical popups, like security alerts, still show while paused.
+      if (isPaused && notification.urgency !== "critical") {
+        queuedNotifications.push(notification);
+        return { queued: true };
+      }
+
+      return notificationDispatcher.dispatchNotification(notification);
+    },
+    isPaused(): boolean {
+      return isPaused;
+    },
+    async togglePaused(): Promise<boolean> {
+      isPaused = !isPaused;
+      getLogger().info(isPaused ? "Notifications paused" : "Notifications resumed", {
+        queuedCount: queuedNotifications.length,
+      });
+
+      if (!isPaused && queuedNotifications.length > 0) {
+        const notifications = queuedNotifications.splice(0, queuedNotifications.length);
+        await notificationDispatcher.dispatchNotification(
+          notifications.length === 1 && notifications[0] !== undefined
+            ? notifications[0]
+            : buildAwayDigestNotification(notifications, options.overflowClickUrl?.()),
+        );
+      }
+
+      return isPaused;
+    },
+  };
+}
@@ -2,12 +2,13 @@ import { DatabaseSync } from "node:sqlite";
 
 import { filterDisplayableNotificationEvents } from "./displayable-notification-events.js";
 import { EventBundleRepository } from "./event-bundling.js";
-import { NormalizedEventRepository } from "./normalized-event-repository.js";
+import { NormalizedEventRepository, type NormalizedEventRecord } from "./normalized-event-repository.js";
 import { NotificationRecordRepository } from "./notification-record-repository.js";
 import { renderNotification, type RenderedNotification } from "./notification-rendering.js";
 import type { PullRequestRecord } from "./pull-request-repository.js";
 
 export interface PreparePullRequestNotificationsOptions {
+  currentUserLogin?: string;
   eventBundleRepository?: Pick<EventBundleRepository, "listPendingUnnotifiedBundlesForPullRequest">;
   normalizedEventRepository?: Pick<
     NormalizedEventRepository,
@@ -52,7 +53,7 @@ export function preparePullRequestNotifications(
   )) {
     const displayableEvents = filterDisplayableNotificationEvents([event]);
 
-    if (displayableEvents.length === 0) {
+    if (displayableEvents.length === 0 || isOnlyOwnActivity(displayableEvents, options.currentUserLogin)) {
       continue;
     }
 
@@ -78,7 +79,7 @@ export function preparePullRequestNotifications(
 
     const displayableEvents = filterDisplayableNotificationEvents(events);
 
-    if (displayableEvents.length === 0) {
+    if (displayableEvents.length === 0 || isOnlyOwnActivity(displayableEvents, options.currentUserLogin)) {
       continue;
     }
 
@@ -101,3 +102,21 @@ export function preparePullRequestNotifications(
     createdCount: immediateCount + bundledCount,
   };
 }
+
+// CI outcomes on your own pushes are still worth a popup; anything else you did yourself is not.
+function isOnlyOwnActivity(
+  events: readonly Pick<NormalizedEventRecord, "actorLogin" | "eventType">[],
+  currentUserLogin: string | undefined,
+): boolean {
+  if (currentUserLogin === undefined) {
+    return false;
+  }
+
+  return events.every(
+    (event) =>
+      event.actorLogin !== null &&
+      event.eventType !== "ci_failed" &&
+      event.eventType !== "ci_succeeded" &&
+      event.actorLogin.localeCompare(currentUserLogin, undefined, { sensitivity: "accent" }) === 0,
+  );
+}
@@ -0,0 +1,131 @@
+import type { LinuxNotification } from "./linux-notification-adapter.js";
+import { getLogger } from "./logger.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+import { GITHUB_NOTIFICATIONS_URL } from "./session-presence-buffer.js";
+
+const RATE_LIMIT_WINDOW_MS = 60_000;
+const MAX_SUPPRESSED_SUMMARY_LINES = 5;
+
+export interface DisplayRateLimitOptions {
+  maxPerMinute: number;
+  // Called for each popup held back, e.g. to still list it in the tray menu.
+  onSuppressed?: (notification: LinuxNotification) => void;
+  // Where the summary of suppressed popups links, so they can be found again.
+  overflowClickUrl?: () => string;
+  now?: () => number;
+}
+
+export interface RateLimitedNotificationDispatcher extends NotificationDispatcher {
+  stop(): void;
+}
+
+// Caps how many popups are shown per minute. Anything over the cap is only recorded (it is already
+// in notification history), and once the minute has room again a single summary popup lists it.
+// Critical popups, such as security alerts, are never held back.
+export function withDisplayRateLimit(
+  notificationDispatcher: NotificationDispatcher,
+  options: DisplayRateLimitOptions,
+): RateLimitedNotificationDispatcher {
+  const now = options.now ?? Date.now;
+  const shownAt: number[] = [];
+  const suppressedNotifications: LinuxNotification[] = [];
+  let summaryTimer: ReturnType<typeof setTimeout> | undefined;
+  let isStopped = false;
+
+  return {
+    async dispatchNotification(notification) {
+      const currentTime = now();
+
+      dropExpired(currentTime);
+
+      if (isStopped || notification.urgency === "critical" || shownAt.length < options.maxPerMinute) {
+        shownAt.push(currentTime);
+        return notificationDispatcher.dispatchNotification(notification);
+      }
+
+      suppressedNotifications.push(notification);
+      options.onSuppressed?.(notification);
+      getLogger().debug("Suppressed popup over the display rate limit", {
+        suppressedCount: suppressedNotifications.length,
+      });
+      scheduleSummary(currentTime);
+      return { suppressed: true };
+    },
+    stop(): void {
+      if (isStopped) {
+        return;
+      }
+
+      isStopped = true;
+
+      if (summaryTimer !== undefined) {
+        clearTimeout(summaryTimer);
+        summaryTimer = undefined;
+      }
+    },
+  };
+
+  function dropExpired(currentTime: number): void {
+    while (shownAt[0] !== undefined && shownAt[0] <= currentTime - RATE_LIMIT_WINDOW_MS) {
+      shownAt.shift();
+    }
+  }
+
+  function scheduleSummary(currentTime: number): void {
+    if (summaryTimer !== undefined) {
+      return;
+    }
+
+    const oldestShownAt = shownAt[0] ?? currentTime;
+
+    summaryTimer = setTimeout(
+      () => {
+        summaryTimer = undefined;
+        void showSummary();
+      },
+      Math.max(0, oldestShownAt + RATE_LIMIT_WINDOW_MS - currentTime),
+    );
+    summaryTimer.unref?.();
+  }
+
+  async function showSummary(): Promise<void> {
+    if (isStopped || suppressedNotifications.length === 0) {
+      return;
+    }
+
+    const notifications = suppressedNotifications.splice(0, suppressedNotifications.length);
+
+    shownAt.push(now());
+
+    try {
+      await notificationDispatcher.dispatchNotification(
+        buildSuppressedSummaryNotification(notifications, options.overflowClickUrl?.()),
+      );
+    } catch (error) {
+      getLogger().warn("Failed to show suppressed notification summary", {
+        suppressedCount: notifications.length,
+        error,
+      });
+    }
+  }
+}
+
+export function buildSuppressedSummaryNotification(
+  notifications: readonly LinuxNotification[],
+  overflowClickUrl: string = GITHUB_NOTIFICATIONS_URL,
+): LinuxNotification {
+  const lines = notifications
+    .slice(0, MAX_SUPPRESSED_SUMMARY_LINES)
+    .map((notification) => notification.title);
+  const hiddenCount = notifications.length - lines.length;
+
+  if (hiddenCount > 0) {
+    lines.push(`…and ${hiddenCount} more`);
+  }
+
+  return {
+    title: `${notifications.length} more ${notifications.length === 1 ? "notification" : "notifications"} suppressed`,
+    body: lines.join("\n"),
+    clickUrl: overflowClickUrl,
+  };
+}
@@ -0,0 +1,182 @@
+import { DatabaseSync } from "node:sqlite";
+
+import type { GitHubNotificationReadStateClient } from "./github-notification-read-state.js";
+import { getLogger } from "./logger.js";
+import { NotificationRecordRepository } from "./notification-record-repository.js";
+import { PullRequestRepository } from "./pull-request-repository.js";
+
+export interface SyncNotificationReadStateOptions {
+  syncedAt?: string;
+  notificationRecordRepository?: Pick<
+    NotificationRecordRepository,
+    | "listReadNotificationRecordsPendingGitHubSync"
+    | "listUnreadDeliveredNotificationRecords"
+    | "markNotificationRecordsGitHubReadSynced"
+    | "markPullRequestNotificationRecordsReadFromGitHub"
+  >;
+  pullRequestRepository?: Pick<
+    PullRequestRepository,
+    "getPullRequestById" | "getPullRequestByRepositoryCoordinates"
+  >;
+}
+
+export interface SyncNotificationReadStateResult {
+  pushedCount: number;
+  pulledCount: number;
+}
+
+export interface StartRecurringNotificationReadStateSyncOptions
+  extends SyncNotificationReadStateOptions {
+  intervalMs: number;
+}
+
+export interface RecurringNotificationReadStateSyncHandle {
+  stop(): void;
+}
+
+export class NotificationReadStateSyncError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "NotificationReadStateSyncError";
+  }
+}
+
+export async function syncNotificationReadState(
+  database: DatabaseSync,
+  readStateClient: GitHubNotificationReadStateClient,
+  options: SyncNotificationReadStateOptions = {},
+): Promise<SyncNotificationReadStateResult> {
+  const syncedAt = options.syncedAt ?? new Date().toISOString();
+  const notificationRecordRepository =
+    options.notificationRecordRepository ?? new NotificationRecordRepository(database);
+  const pullRequestRepository = options.pullRequestRepository ?? new PullRequestRepository(database);
+
+  let pushedCount = 0;
+  const pendingRecordIdsByPullRequestId = new Map<number, number[]>();
+
+  for (const record of notificationRecordRepository.listReadNotificationRecordsPendingGitHubSync()) {
+    const recordIds = pendingRecordIdsByPullRequestId.get(record.pullRequestId) ?? [];
+    recordIds.push(record.id);
+    pendingRecordIdsByPullRequestId.set(record.pullRequestId, recordIds);
+  }
+
+  for (const [pullRequestId, recordIds] of pendingRecordIdsByPullRequestId) {
+    const pullRequest = pullRequestRepository.getPullRequestById(pullRequestId);
+
+    if (pullRequest !== undefined) {
+      await readStateClient.markPullRequestThreadsRead(pullRequest);
+    }
+
+    notificationRecordRepository.markNotificationRecordsGitHubReadSynced(recordIds, syncedAt);
+    pushedCount += recordIds.length;
+  }
+
+  let pulledCount = 0;
+  const unreadRecords = notificationRecordRepository.listUnreadDeliveredNotificationRecords();
+  const oldestUnreadDeliveredAt = unreadRecords[0]?.deliveredAt;
+
+  if (oldestUnreadDeliveredAt) {
+    const unreadPullRequestIds = new Set(unreadRecords.map((record) => record.pullRequestId));
+    const threadReadStates =
+      await readStateClient.listPullRequestThreadReadStates(oldestUnreadDeliveredAt);
+
+    for (const threadReadState of threadReadStates) {
+      if (threadReadState.unread || threadReadState.lastReadAt === null) {
+        continue;
+      }
+
+      const pullRequest = pullRequestRepository.getPullRequestByRepositoryCoordinates(
+        threadReadState.coordinates.repositoryOwner,
+        threadReadState.coordinates.repositoryName,
+        threadReadState.coordinates.number,
+      );
+
+      if (pullRequest === undefined || !unreadPullRequestIds.has(pullRequest.id)) {
+        continue;
+      }
+
+      pulledCount += notificationRecordRepository.markPullRequestNotificationRecordsReadFromGitHub(
+        pullRequest.id,
+        threadReadState.lastReadAt,
+        syncedAt,
+      );
+    }
+  }
+
+  return {
+    pushedCount,
+    pulledCount,
+  };
+}
+
+export function startRecurringNotificationReadStateSync(
+  database: DatabaseSync,
+  readStateClient: GitHubNotificationReadStateClient,
+  options: StartRecurringNotificationReadStateSyncOptions,
+): RecurringNotificationReadStateSyncHandle {
+  const { intervalMs, ...syncOptions } = options;
+
+  if (!Number.isFinite(intervalMs) || intervalMs <= 0) {
+    throw new NotificationReadStateSyncError(
+      "Recurring notification read-state sync interval must be greater than zero",
+    );
+  }
+
+  let isStopped = false;
+  let timer: ReturnType<typeof setTimeout> | undefined;
+
+  scheduleNextCycle();
+
+  return {
+    stop(): void {
+      if (isStopped) {
+        return;
+      }
+
+      isStopped = true;
+      clearTimeout(timer);
+    },
+  };
+
+  // GitHub's X-Poll-Interval can ask for a longer wait than the configured interval.
+  function scheduleNextCycle(): void {
+    const delayMs = Math.max(intervalMs, readStateClient.readPollIntervalMs?.() ?? 0);
+
+    timer = setTimeout(() => {
+      void runSyncCycle();
+    }, delayMs);
+    timer.unref?.();
+  }
+
+  async function runSyncCycle(): Promise<void> {
+    if (isStopped) {
+      return;
+    }
+
+    try {
+      const result = await syncNotificationReadState(database, readStateClient, syncOptions);
+
+      if (result.pushedCount > 0 || result.pulledCount > 0) {
+        getLogger().info("Synced notification read state with GitHub", { ...result });
+      }
+    } catch (error) {
+      getLogger().error("Octopulse notification read-state sync failed", {
+        error: new NotificationReadStateSyncError(
+          `Failed to sync notification read state: ${getErrorMessage(error)}`,
+        ),
+      });
+    } finally {
+      if (!isStopped) {
+        scheduleNextCycle();
+      }
+    }
+  }
+}
+
+function getErrorMessage(error: unknown): string {
+  if (error instanceof Error) {
+    return error.message;
+  }
+
+  return String(error);
+}
@@ -5,6 +5,7 @@ import {
   DEFAULT_ACTIVITY_FEED_FILTERS,
   type ActivityFeedFilters,
 } from "./activity-feed.js";
+import { buildNotificationSearchQuery } from "./notification-search.js";
 
 export type NotificationDeliveryStatus = "pending" | "sent" | "failed";
 
@@ -19,6 +20,8 @@ export interface NotificationRecord {
   deliveryStatus: NotificationDeliveryStatus;
   createdAt: string;
   deliveredAt: string | null;
+  readAt: string | null;
+  githubReadSyncedAt: string | null;
 }
 
 export interface CreateNotificationRecordInput {
@@ -39,10 +42,15 @@ export interface UpdateNotificationRecordDeliveryInput {
 
 export interface ListNotificationRecordsOptions {
   filters?: ActivityFeedFilters;
+  unreadOnly?: boolean;
   limit?: number;
   offset?: number;
 }
 
+export interface CountNotificationRecordsOptions {
+  unreadOnly?: boolean;
+}
+
 export class NotificationRecordRepositoryError extends Error {
   constructor(message: string) {
     super(message);
@@ -54,8 +62,8 @@ export class NotificationRecordRepository {
   constructor(private readonly database: DatabaseSync) {}
 
   listNotificationRecords(options: ListNotificationRecordsOptions = {}): NotificationRecord[] {
-    const { filters = DEFAULT_ACTIVITY_FEED_FILTERS, limit, offset = 0 } = options;
-    const { whereClause, parameters } = buildNotificationRecordFilterClause(filters);
+    const { filters = DEFAULT_ACTIVITY_FEED_FILTERS, unreadOnly = false, limit, offset = 0 } = options;
+    const { whereClause, parameters } = buildNotificationRecordFilterClause(filters, unreadOnly);
     const query = [
       "SELECT notification_record.*",
       "FROM NotificationRecord notification_record",
@@ -74,8 +82,14 @@ export class NotificationRecordRepository {
     return rows.map((row) => mapNotificationRecordRow(row));
   }
 
-  countNotificationRecords(filters: ActivityFeedFilters = DEFAULT_ACTIVITY_FEED_FILTERS): number {
-    const { whereClause, parameters } = buildNotificationRecordFilterClause(filters);
+  countNotificationRecords(
+    filters: ActivityFeedFilters = DEFAULT_ACTIVITY_FEED_FILTERS,
+    options: CountNotificationRecordsOptions = {},
+  ): number {
+    const { whereClause, parameters } = buildNotificationRecordFilterClause(
+      filters,
+      options.unreadOnly ?? false,
+    );
     const row = this.database
       .prepare(
         [
@@ -242,6 +256,118 @@ export class NotificationRecordRepository {
     }
   }
 
+  markNotificationRecordRead(id: number, readAt: string): NotificationRecord {
+    try {
+      this.database
+        .prepare(
+          `
+            UPDATE NotificationRecord
+            SET read_at = ?, github_read_synced_at = NULL
+            WHERE id = ?
+              AND read_at IS NULL
+          `,
+        )
+        .run(readAt, id);
+
+      return this.requireNotificationRecordById(id);
+    } catch (error) {
+      if (error instanceof NotificationRecordRepositoryError) {
+        throw error;
+      }
+
+      throw new NotificationRecordRepositoryError(
+        `Failed to mark notification record ${id} read: ${getErrorMessage(error)}`,
+      );
+    }
+  }
+
+  markNotificationRecordUnread(id: number): NotificationRecord {
+    try {
+      this.database
+        .prepare(
+          `
+            UPDATE NotificationRecord
+            SET read_at = NULL, github_read_synced_at = NULL
+            WHERE id = ?
+          `,
+        )
+        .run(id);
+
+      return this.requireNotificationRecordById(id);
+    } catch (error) {
+      if (error instanceof NotificationRecordRepositoryError) {
+        throw error;
+      }
+
+      throw new NotificationRecordRepositoryError(
+        `Failed to mark notification record ${id} unread: ${getErrorMessage(error)}`,
+      );
+    }
+  }
+
+  listReadNotificationRecordsPendingGitHubSync(): NotificationRecord[] {
+    const rows = this.database
+      .prepare(
+        `
+          SELECT *
+          FROM NotificationRecord
+          WHERE read_at IS NOT NULL
+            AND github_read_synced_at IS NULL
+          ORDER BY read_at ASC, id ASC
+        `,
+      )
+      .all();
+
+    return rows.map((row) => mapNotificationRecordRow(row));
+  }
+
+  listUnreadDeliveredNotificationRecords(): NotificationRecord[] {
+    const rows = this.database
+      .prepare(
+        `
+          SELECT *
+          FROM NotificationRecord
+          WHERE read_at IS NULL
+            AND delivered_at IS NOT NULL
+          ORDER BY delivered_at ASC, id ASC
+        `,
+      )
+      .all();
+
+    return rows.map((row) => mapNotificationRecordRow(row));
+  }
+
+  markNotificationRecordsGitHubReadSynced(ids: readonly number[], syncedAt: string): void {
+    const statement = this.database.prepare(
+      "UPDATE NotificationRecord SET github_read_synced_at = ? WHERE id = ?",
+    );
+
+    for (const id of ids) {
+      statement.run(syncedAt, id);
+    }
+  }
+
+  markPullRequestNotificationRecordsReadFromGitHub(
+    pullRequestId: number,
+    lastReadAt: string,
+    syncedAt: string,
+  ): number {
+    const result = this.database
+      .prepare(
+        `
+          UPDATE NotificationRecord
+          SET read_at = ?, github_read_synced_at = ?
+          WHERE pull_request_id = ?
+            AND read_at IS NULL
+            AND delivered_at IS NOT NULL
+            AND delivered_at <= ?
+        `,
+      )
+      .run(lastReadAt, syncedAt, pullRequestId, lastReadAt);
+
+    return readInteger(result.changes, "NotificationRecord.changes");
+  }
+
   private requireNotificationRecordById(id: number): NotificationRecord {
     const row = this.database.prepare("SELECT * FROM NotificationRecord WHERE id = ?").get(id);
 
@@ -267,7 +393,10 @@ function validateNotificationTarget(input: CreateNotificationRecordInput): void
   }
 }
 
-function buildNotificationRecordFilterClause(filters: ActivityFeedFilters): {
+function buildNotificationRecordFilterClause(
+  filters: ActivityFeedFilters,
+  unreadOnly: boolean,
+): {
   whereClause: string;
   parameters: Array<number | string>;
 } {
@@ -308,6 +437,23 @@ function buildNotificationRecordFilterClause(filters: ActivityFeedFilters): {
     parameters.push(filters.actorClass);
   }
 
+  const searchQuery = buildNotificationSearchQuery(filters.search);
+
+  if (searchQuery !== null) {
+    clauses.push(`
+      notification_record.id IN (
+        SELECT rowid
+        FROM NotificationSearch
+        WHERE NotificationSearch MATCH ?
+      )
+    `);
+    parameters.push(searchQuery);
+  }
+
+  if (unreadOnly) {
+    clauses.push("notification_record.read_at IS NULL");
+  }
+
   return {
     whereClause: clauses.length > 0 ? `WHERE ${clauses.join("\n  AND ")}` : "",
     parameters,
@@ -338,6 +484,11 @@ function mapNotificationRecordRow(row: unknown): NotificationRecord {
     ),
     createdAt: readString(value.created_at, "NotificationRecord.created_at"),
     deliveredAt: readNullableString(value.delivered_at, "NotificationRecord.delivered_at"),
+    readAt: readNullableString(value.read_at, "NotificationRecord.read_at"),
+    githubReadSyncedAt: readNullableString(
+      value.github_read_synced_at,
+      "NotificationRecord.github_read_synced_at",
+    ),
   };
 }
 
@@ -33,7 +33,25 @@ type NotificationPullRequest = Pick<
 type NotificationMarkupPullRequest = Pick<
   PullRequestRecord,
   "repositoryName" | "title" | "authorLogin" | "authorAvatarUrl" | "state" | "isDraft" | "mergedAt"
->;
+> &
+  Partial<
+    Pick<
+      PullRequestRecord,
+      | "autoMergeEnabled"
+      | "mergedByLogin"
+      | "headBranch"
+      | "baseBranch"
+      | "openedAt"
+      | "lastActivityAt"
+      | "additions"
+      | "deletions"
+      | "projectStatus"
+    >
+  >;
+
+export interface RenderNotificationMarkupOptions {
+  now?: Date;
+}
 
 type NotificationEvent = Pick<
   NormalizedEventRecord,
@@ -47,8 +65,15 @@ const TEXT_EVENT_TYPES = new Set([
   "review_approved",
   "review_changes_requested",
 ]);
+const COMMENT_EVENT_TYPES = new Set(["issue_comment", "review_inline_comment"]);
 const EVENT_TYPES_WITHOUT_ACTOR_ATTRIBUTION = new Set(["ci_failed", "ci_succeeded"]);
+// These name the actor inside the text ("review requested by alice") but keep their avatar.
+const EVENT_TYPES_WITH_INLINE_ACTOR = new Set(["review_requested", "pr_assigned"]);
 const MAX_EVENT_TEXT_LENGTH = 100;
+const MAX_DESCRIPTION_TEXT_LENGTH = 200;
+// Past this many comments the popup shows a per-author summary plus only the newest few.
+const COMMENT_SUMMARY_THRESHOLD = 5;
+const MAX_SUMMARIZED_COMMENT_EVENTS = 3;
 
 export function renderNotification(
   pullRequest: NotificationPullRequest,
@@ -58,7 +83,7 @@ export function renderNotification(
     throw new Error("Cannot render notification without events");
   }
 
-  const displayableEvents = filterDisplayableNotificationEvents(events);
+  const displayableEvents = collapseCommitsSinceReview(filterDisplayableNotificationEvents(events));
 
   if (displayableEvents.length === 0) {
     throw new Error("Cannot render notification without displayable events");
@@ -73,38 +98,146 @@ export function renderNotification(
   return {
     title,
     body: renderNotificationBody(displayableEvents),
-    clickUrl: pullRequest.url,
+    clickUrl: resolveClickUrl(pullRequest, displayableEvents),
     summary,
   };
 }
 
+// A lone CI failure links straight to the failing workflow run's logs.
+function resolveClickUrl(
+  pullRequest: Pick<NotificationPullRequest, "url">,
+  events: readonly NotificationEvent[],
+): string {
+  const [event] = events;
+
+  if (events.length !== 1 || event?.eventType !== "ci_failed") {
+    return pullRequest.url;
+  }
+
+  const url = parsePayload(event.payloadJson)?.url;
+
+  return typeof url === "string" && url.length > 0 ? url : pullRequest.url;
+}
+
 export function renderNotificationMarkup(
   pullRequest: NotificationMarkupPullRequest,
   events: readonly NotificationEvent[],
+  options: RenderNotificationMarkupOptions = {},
 ): NotificationMarkup {
   if (events.length === 0) {
     throw new Error("Cannot render notification markup without events");
   }
 
-  const displayableEvents = filterDisplayableNotificationEvents(events);
+  const displayableEvents = collapseCommitsSinceReview(filterDisplayableNotificationEvents(events));
+  const { keptEvents, skippedCommentCount } = limitCommentEvents(displayableEvents);
+  const headerText = [
+    `[${pullRequest.repositoryName}] ${formatSizeBadgeText(pullRequest)}${pullRequest.title} (${formatHeaderStateText(pullRequest)})`,
+    formatHeaderBranchText(pullRequest),
+    pullRequest.projectStatus ? `📋 ${pullRequest.projectStatus}` : "",
+    formatHeaderAgeText(pullRequest, options.now ?? new Date()),
+  ]
+    .filter((part) => part.length > 0)
+    .join(" · ");
 
   return {
-    headerText: `[${pullRequest.repositoryName}] ${pullRequest.title} (${formatPullRequestStateLabel(pullRequest).toLowerCase()})`,
+    headerText,
     headerAvatarKey: pullRequest.authorLogin,
     headerAvatarUrl: pullRequest.authorAvatarUrl,
-    paragraphs: displayableEvents.map((event) => buildNotificationParagraph(event)),
+    paragraphs: [
+      ...(skippedCommentCount === 0
+        ? []
+        : [
+            {
+              actorLogin: null,
+              actorAvatarKey: null,
+              actorAvatarUrl: null,
+              text: renderCommentSummaryText(displayableEvents, skippedCommentCount),
+            },
+          ]),
+      ...keptEvents.map((event) => buildNotificationParagraph(event)),
+    ],
   };
 }
 
+// Sized by changed lines (additions + deletions), using the common XS/S/M/L/XL label cut-offs.
+function classifyPullRequestSize(additions: number, deletions: number): string {
+  const changedLines = additions + deletions;
+
+  if (changedLines < 10) {
+    return "XS";
+  }
+
+  if (changedLines < 30) {
+    return "S";
+  }
+
+  if (changedLines < 100) {
+    return "M";
+  }
+
+  return changedLines < 500 ? "L" : "XL";
+}
+
+function formatSizeBadgeText(pullRequest: NotificationMarkupPullRequest): string {
+  if (
+    pullRequest.additions === undefined ||
+    pullRequest.additions === null ||
+    pullRequest.deletions === undefined ||
+    pullRequest.deletions === null
+  ) {
+    return "";
+  }
+
+  return `[${classifyPullRequestSize(pullRequest.additions, pullRequest.deletions)}] `;
+}
+
+function formatHeaderBranchText(pullRequest: NotificationMarkupPullRequest): string {
+  if (!pullRequest.headBranch || !pullRequest.baseBranch) {
+    return "";
+  }
+
+  return `${pullRequest.headBranch} → ${pullRequest.baseBranch}`;
+}
+
+// "opened 6d ago, last activity 3h ago" helps tell a fresh pull request from a stale one.
+function formatHeaderAgeText(pullRequest: NotificationMarkupPullRequest, now: Date): string {
+  const nowText = now.toISOString();
+  const openedAge = pullRequest.openedAt ? formatElapsedTime(pullRequest.openedAt, nowText) : null;
+  const lastActivityAge = pullRequest.lastActivityAt
+    ? formatElapsedTime(pullRequest.lastActivityAt, nowText)
+    : null;
+
+  return [
+    ...(openedAge === null ? [] : [`opened ${openedAge} ago`]),
+    ...(lastActivityAge === null ? [] : [`last activity ${lastActivityAge} ago`]),
+  ].join(", ");
+}
+
+function formatHeaderStateText(pullRequest: NotificationMarkupPullRequest): string {
+  const stateLabel = formatPullRequestStateLabel(pullRequest).toLowerCase();
+
+  if (pullRequest.mergedAt !== null && pullRequest.mergedByLogin) {
+    const baseText = pullRequest.baseBranch ? ` into ${pullRequest.baseBranch}` : "";
+    return `merged by ${pullRequest.mergedByLogin}${baseText}`;
+  }
+
+  if (pullRequest.mergedAt === null && pullRequest.autoMergeEnabled === true) {
+    return `${stateLabel}, auto-merge on`;
+  }
+
+  return stateLabel;
+}
+
 export function buildNotificationParagraph(
   event: Pick<NormalizedEventRecord, "actorLogin" | "eventType" | "payloadJson" | "id" | "occurredAt">,
 ): NotificationMarkupParagraph {
   const actorLogin = readDisplayedActorLogin(event);
+  const avatarLogin = EVENT_TYPES_WITH_INLINE_ACTOR.has(event.eventType) ? event.actorLogin : actorLogin;
 
   return {
     actorLogin,
-    actorAvatarKey: actorLogin,
-    actorAvatarUrl: actorLogin === null ? null : readEventActorAvatarUrl(event),
+    actorAvatarKey: avatarLogin,
+    actorAvatarUrl: avatarLogin === null ? null : readEventActorAvatarUrl(event),
     text: renderEventText(event),
   };
 }
@@ -134,6 +267,8 @@ function renderEventFallbackText(event: NotificationEvent): string {
       return "requested changes";
     case "review_requested":
       return "review requested";
+    case "pr_assigned":
+      return "you were assigned";
     case "ci_failed":
       return "CI failed";
     case "ci_succeeded":
@@ -150,6 +285,10 @@ function renderEventFallbackText(event: NotificationEvent): string {
       return "converted PR to draft";
     case "commit_pushed":
       return "pushed commits";
+    case "force_pushed_after_review":
+      return "force-pushed after your review";
+    case "review_dismissed":
+      return "dismissed a review";
     default:
       return "updated PR";
   }
@@ -172,6 +311,7 @@ function renderBundleSummary(events: readonly NotificationEvent[]): string {
   appendCount(primaryParts, countEvents(events, "review_submitted"), "review");
   appendCount(primaryParts, countEvents(events, "review_approved"), "approval");
   appendCount(primaryParts, countEvents(events, "review_changes_requested"), "change request");
+  appendCount(primaryParts, countEvents(events, "review_dismissed"), "dismissed review");
 
   if (countEvents(events, "ci_failed") > 0) {
     primaryParts.push("CI failed");
@@ -181,6 +321,10 @@ function renderBundleSummary(events: readonly NotificationEvent[]): string {
     primaryParts.push("CI passed");
   }
 
+  if (countEvents(events, "force_pushed_after_review") > 0) {
+    primaryParts.push("force-pushed after your review");
+  }
+
   appendCount(primaryParts, countEvents(events, "commit_pushed"), "commit push");
   appendCount(primaryParts, countEvents(events, "pr_reopened"), "reopen");
   appendCount(primaryParts, countEvents(events, "ready_for_review"), "ready-for-review update");
@@ -219,7 +363,55 @@ function appendCount(parts: string[], count: number, label: string): void {
 }
 
 function renderNotificationBody(events: readonly NotificationEvent[]): string {
-  return events.map((event) => renderPlainEventLine(event)).join("\n\n");
+  const { keptEvents, skippedCommentCount } = limitCommentEvents(events);
+
+  return [
+    ...(skippedCommentCount === 0 ? [] : [renderCommentSummaryText(events, skippedCommentCount)]),
+    ...keptEvents.map((event) => renderPlainEventLine(event)),
+  ].join("\n\n");
+}
+
+// Keeps every non-comment event but only the newest comments, so a busy thread stays readable.
+function limitCommentEvents<TEvent extends Pick<NotificationEvent, "eventType">>(
+  events: readonly TEvent[],
+): { keptEvents: TEvent[]; skippedCommentCount: number } {
+  const commentCount = events.filter((event) => COMMENT_EVENT_TYPES.has(event.eventType)).length;
+  let skippedCommentCount =
+    commentCount > COMMENT_SUMMARY_THRESHOLD ? commentCount - MAX_SUMMARIZED_COMMENT_EVENTS : 0;
+  const totalSkippedCommentCount = skippedCommentCount;
+  const keptEvents = events.filter((event) => {
+    if (skippedCommentCount > 0 && COMMENT_EVENT_TYPES.has(event.eventType)) {
+      skippedCommentCount -= 1;
+      return false;
+    }
+
+    return true;
+  });
+
+  return { keptEvents, skippedCommentCount: totalSkippedCommentCount };
+}
+
+function renderCommentSummaryText(
+  events: readonly Pick<NotificationEvent, "actorLogin" | "eventType">[],
+  skippedCommentCount: number,
+): string {
+  const countsByAuthor = new Map<string, number>();
+
+  for (const event of events) {
+    if (COMMENT_EVENT_TYPES.has(event.eventType)) {
+      const authorLogin = event.actorLogin ?? "someone";
+      countsByAuthor.set(authorLogin, (countsByAuthor.get(authorLogin) ?? 0) + 1);
+    }
+  }
+
+  const authorSummary = [...countsByAuthor.entries()]
+    .sort(([leftLogin, leftCount], [rightLogin, rightCount]) =>
+      rightCount - leftCount || leftLogin.localeCompare(rightLogin),
+    )
+    .map(([authorLogin, count]) => `${authorLogin} ×${count}`)
+    .join(", ");
+
+  return `${authorSummary} commented; …and ${skippedCommentCount} earlier comment${skippedCommentCount === 1 ? "" : "s"}`;
 }
 
 function renderPlainEventLine(event: NotificationEvent): string {
@@ -240,15 +432,26 @@ function renderEventText(event: NotificationEvent): string {
     case "review_changes_requested":
       return renderEmojiText("❗", readEventText(event) ?? "changes requested");
     case "review_requested":
-      return renderEmojiText("👀", "review requested");
+      return renderEmojiText("👀", renderReviewRequestedText(event));
+    case "pr_assigned":
+      return renderEmojiText(
+        "📌",
+        event.actorLogin === null ? "You were assigned" : `You were assigned by ${event.actorLogin}`,
+      );
     case "pr_merged":
     case "pr_closed":
     case "pr_reopened":
     case "ready_for_review":
     case "converted_to_draft":
-    case "commit_pushed":
       return renderEventFallbackText(event);
+    case "commit_pushed":
+      return renderCommitPushedText(event);
+    case "force_pushed_after_review":
+      return renderEmojiText("⚠️", "force-pushed after your review, which may no longer apply");
+    case "review_dismissed":
+      return renderEmojiText("🚫", renderReviewDismissedText(event));
     case "ci_failed":
+      return renderCiFailedText(event);
     case "ci_succeeded":
       return renderEventFallbackText(event);
     default:
@@ -256,6 +459,101 @@ function renderEventText(event: NotificationEvent): string {
   }
 }
 
+function renderCommitPushedText(event: NotificationEvent): string {
+  const commitsSinceReview = readCommitsSinceReview(event);
+
+  if (commitsSinceReview === null) {
+    return renderEventFallbackText(event);
+  }
+
+  return renderEmojiText(
+    "🔨",
+    `${commitsSinceReview} new commit${commitsSinceReview === 1 ? "" : "s"} pushed since your review`,
+  );
+}
+
+function readCommitsSinceReview(event: Pick<NotificationEvent, "eventType" | "payloadJson">): number | null {
+  if (event.eventType !== "commit_pushed") {
+    return null;
+  }
+
+  const commitsSinceReview = parsePayload(event.payloadJson)?.commitsSinceReview;
+
+  return typeof commitsSinceReview === "number" && commitsSinceReview > 0 ? commitsSinceReview : null;
+}
+
+// Commits after your review collapse into their newest one, which carries the running count.
+function collapseCommitsSinceReview<TEvent extends Pick<NotificationEvent, "eventType" | "payloadJson">>(
+  events: readonly TEvent[],
+): TEvent[] {
+  const lastIndex = events.findLastIndex((event) => readCommitsSinceReview(event) !== null);
+
+  return events.filter((event, index) => index === lastIndex || readCommitsSinceReview(event) === null);
+}
+
+// Names whose review was dismissed and quotes the dismissal message when GitHub provides one.
+function renderReviewDismissedText(event: NotificationEvent): string {
+  const payload = parsePayload(event.payloadJson);
+  const reviewerLogin = typeof payload?.reviewerLogin === "string" ? payload.reviewerLogin : null;
+  const dismissalMessage =
+    typeof payload?.dismissalMessage === "string"
+      ? truncateText(normalizeNotificationBodyText(payload.dismissalMessage), MAX_EVENT_TEXT_LENGTH)
+      : "";
+  const headline = reviewerLogin === null ? "dismissed a review" : `dismissed ${reviewerLogin}'s review`;
+
+  return dismissalMessage.length === 0 ? headline : `${headline}: ${dismissalMessage}`;
+}
+
+function renderCiFailedText(event: NotificationEvent): string {
+  const workflowName = parsePayload(event.payloadJson)?.workflowName;
+
+  return typeof workflowName === "string" && workflowName.length > 0
+    ? `${renderEventFallbackText(event)}: ${workflowName}`
+    : renderEventFallbackText(event);
+}
+
+// Discovery stores the requester and description on the first review request for a pull request;
+// the branches already appear in the popup header.
+function renderReviewRequestedText(event: NotificationEvent): string {
+  const payload = parsePayload(event.payloadJson);
+  const requestedAt = typeof payload?.requestedAt === "string" ? payload.requestedAt : null;
+  const descriptionText =
+    typeof payload?.descriptionText === "string"
+      ? normalizeNotificationBodyText(payload.descriptionText)
+      : "";
+  const requesterText = event.actorLogin === null ? "" : ` by ${event.actorLogin}`;
+  const ageText = requestedAt === null ? "" : formatRequestAge(requestedAt, event.occurredAt);
+  const headline = `review requested${requesterText}${ageText}`;
+
+  if (descriptionText.length === 0) {
+    return headline;
+  }
+
+  return `${headline}: ${truncateText(descriptionText, MAX_DESCRIPTION_TEXT_LENGTH)}`;
+}
+
+function formatRequestAge(requestedAt: string, observedAt: string): string {
+  const age = formatElapsedTime(requestedAt, observedAt);
+
+  return age === null ? "" : ` ${age} ago`;
+}
+
+function formatElapsedTime(from: string, to: string): string | null {
+  const ageMinutes = Math.floor((Date.parse(to) - Date.parse(from)) / 60_000);
+
+  if (!Number.isFinite(ageMinutes) || ageMinutes < 1) {
+    return null;
+  }
+
+  if (ageMinutes < 60) {
+    return `${ageMinutes}m`;
+  }
+
+  const ageHours = Math.floor(ageMinutes / 60);
+
+  return ageHours < 24 ? `${ageHours}h` : `${Math.floor(ageHours / 24)}d`;
+}
+
 function renderEmojiText(emoji: string, text: string): string {
   return `${emoji} ${text}`;
 }
@@ -278,11 +576,15 @@ function readEventText(event: NotificationEvent): string | null {
     return null;
   }
 
-  if (normalizedText.length <= MAX_EVENT_TEXT_LENGTH) {
-    return normalizedText;
+  return truncateText(normalizedText, MAX_EVENT_TEXT_LENGTH);
+}
+
+function truncateText(text: string, maxLength: number): string {
+  if (text.length <= maxLength) {
+    return text;
   }
 
-  return `${normalizedText.slice(0, MAX_EVENT_TEXT_LENGTH - 3).trimEnd()}...`;
+  return `${text.slice(0, maxLength - 3).trimEnd()}...`;
 }
 
 function readEventActorAvatarUrl(event: NotificationEvent): string | null {
@@ -299,7 +601,10 @@ function readDisplayedActorLogin(
 }
 
 function shouldSuppressActorAttribution(event: Pick<NormalizedEventRecord, "eventType">): boolean {
-  return EVENT_TYPES_WITHOUT_ACTOR_ATTRIBUTION.has(event.eventType);
+  return (
+    EVENT_TYPES_WITHOUT_ACTOR_ATTRIBUTION.has(event.eventType) ||
+    EVENT_TYPES_WITH_INLINE_ACTOR.has(event.eventType)
+  );
 }
 
 function parsePayload(payloadJson: string): Record<string, unknown> | null {
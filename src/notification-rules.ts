@@ -0,0 +1,131 @@
+import { DatabaseSync } from "node:sqlite";
+
+import { touchesOwnedPaths, type CodeownersRule } from "./codeowners.js";
+import {
+  NormalizedEventRepository,
+  type NormalizedEventRecord,
+} from "./normalized-event-repository.js";
+import { compilePathPattern } from "./path-patterns.js";
+import type { PullRequestRecord } from "./pull-request-repository.js";
+
+// Being assigned is addressed to you directly, so no relevance rule hides it.
+const RULE_EXEMPT_EVENT_TYPES = new Set(["pr_assigned"]);
+
+export interface NotificationRules {
+  // Only notify about pull requests you did not author when they touch files that CODEOWNERS
+  // assigns to you or to one of `ownerTeams`.
+  ownedPathsOnly: boolean;
+  ownerTeams: string[];
+  // Only notify about pull requests you did not author when they change a file matching one of
+  // these gitignore-style globs, such as "services/payments/**".
+  paths: string[];
+}
+
+export interface ApplyNotificationRulesOptions {
+  rules: NotificationRules;
+  currentUserLogin: string;
+  loadCodeowners: (pullRequest: PullRequestRecord) => Promise<CodeownersRule[] | null>;
+  normalizedEventRepository?: Pick<
+    NormalizedEventRepository,
+    | "listBundleEligibleUnbundledEventsForPullRequest"
+    | "listImmediateEligibleUnnotifiedEventsForPullRequest"
+    | "updateNormalizedEventDecision"
+  >;
+}
+
+export interface ApplyNotificationRulesResult {
+  suppressedCount: number;
+}
+
+export async function applyNotificationRules(
+  database: DatabaseSync,
+  pullRequest: PullRequestRecord,
+  options: ApplyNotificationRulesOptions,
+): Promise<ApplyNotificationRulesResult> {
+  if (
+    !hasPathRules(options.rules) ||
+    pullRequest.authorLogin.toLowerCase() === options.currentUserLogin.toLowerCase()
+  ) {
+    return { suppressedCount: 0 };
+  }
+
+  const normalizedEventRepository =
+    options.normalizedEventRepository ?? new NormalizedEventRepository(database);
+  const pendingEvents = [
+    ...normalizedEventRepository.listBundleEligibleUnbundledEventsForPullRequest(pullRequest.id),
+    ...normalizedEventRepository.listImmediateEligibleUnnotifiedEventsForPullRequest(pullRequest.id),
+  ].filter((event) => !RULE_EXEMPT_EVENT_TYPES.has(event.eventType));
+
+  if (pendingEvents.length === 0) {
+    return { suppressedCount: 0 };
+  }
+
+  const failedRule = await findFailedRule(pullRequest, options);
+
+  if (failedRule === null) {
+    return { suppressedCount: 0 };
+  }
+
+  for (const event of pendingEvents) {
+    normalizedEventRepository.updateNormalizedEventDecision(event.id, {
+      decisionState: "suppressed_rule",
+      payloadJson: JSON.stringify({ ...parsePayload(event), suppressedByRule: failedRule }),
+    });
+  }
+
+  return { suppressedCount: pendingEvents.length };
+}
+
+export function hasPathRules(rules: NotificationRules): boolean {
+  return rules.ownedPathsOnly || rules.paths.length > 0;
+}
+
+// Without a file list (or CODEOWNERS) there is nothing to judge relevance by, so keep notifying.
+async function findFailedRule(
+  pullRequest: PullRequestRecord,
+  options: ApplyNotificationRulesOptions,
+): Promise<"paths" | "owned_paths_only" | null> {
+  const { changedFilePaths } = pullRequest;
+
+  if (changedFilePaths.length === 0) {
+    return null;
+  }
+
+  if (options.rules.paths.length > 0) {
+    const patterns = options.rules.paths.map((pattern) => compilePathPattern(pattern));
+
+    if (!changedFilePaths.some((filePath) => patterns.some((pattern) => pattern.test(filePath)))) {
+      return "paths";
+    }
+  }
+
+  if (!options.rules.ownedPathsOnly) {
+    return null;
+  }
+
+  const codeowners = await options.loadCodeowners(pullRequest);
+
+  if (
+    codeowners === null ||
+    touchesOwnedPaths(codeowners, changedFilePaths, [
+      `@${options.currentUserLogin}`,
+      ...options.rules.ownerTeams,
+    ])
+  ) {
+    return null;
+  }
+
+  return "owned_paths_only";
+}
+
+function parsePayload(event: Pick<NormalizedEventRecord, "payloadJson">): Record<string, unknown> {
+  try {
+    const parsed = JSON.parse(event.payloadJson) as unknown;
+
+    return typeof parsed === "object" && parsed !== null && !Array.isArray(parsed)
+      ? (parsed as Record<string, unknown>)
+      : {};
+  } catch {
+    return {};
+  }
+}
@@ -0,0 +1,147 @@
+import { DatabaseSync } from "node:sqlite";
+
+import { resolveAppPaths } from "./config.js";
+import { initializeDatabase } from "./database.js";
+
+const SEARCH_USAGE = 'Usage: octopulse search "<text>" [--limit <count>]';
+const DEFAULT_SEARCH_LIMIT = 20;
+const SNIPPET_TOKEN_COUNT = 12;
+
+export interface NotificationSearchResult {
+  id: number;
+  createdAt: string;
+  repository: string;
+  pullRequestNumber: number;
+  title: string;
+  snippet: string;
+  clickUrl: string | null;
+}
+
+export interface SearchNotificationsOptions {
+  limit?: number;
+}
+
+export class NotificationSearchError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "NotificationSearchError";
+  }
+}
+
+// Quotes every word so punctuation in the search text is matched literally instead of being read
+// as FTS5 query syntax. Words are ANDed together, matching anywhere in a notification.
+export function buildNotificationSearchQuery(text: string): string | null {
+  const terms = text
+    .split(/\s+/)
+    .filter((term) => term.length > 0)
+    .map((term) => `"${term.replaceAll('"', '""')}"`);
+
+  return terms.length === 0 ? null : terms.join(" ");
+}
+
+export function searchNotifications(
+  database: DatabaseSync,
+  text: string,
+  options: SearchNotificationsOptions = {},
+): NotificationSearchResult[] {
+  const query = buildNotificationSearchQuery(text);
+
+  if (query === null) {
+    return [];
+  }
+
+  const rows = database
+    .prepare(
+      [
+        "SELECT",
+        "  notification_record.id,",
+        "  notification_record.created_at,",
+        "  notification_record.title,",
+        "  notification_record.click_url,",
+        "  pull_request.repository_owner || '/' || pull_request.repository_name AS repository,",
+        "  pull_request.number AS pull_request_number,",
+        `  snippet(NotificationSearch, -1, '[', ']', '…', ${SNIPPET_TOKEN_COUNT}) AS snippet`,
+        "FROM NotificationSearch",
+        "INNER JOIN NotificationRecord notification_record",
+        "  ON notification_record.id = NotificationSearch.rowid",
+        "INNER JOIN PullRequest pull_request",
+        "  ON pull_request.id = notification_record.pull_request_id",
+        "WHERE NotificationSearch MATCH ?",
+        "ORDER BY NotificationSearch.rank, notification_record.created_at DESC, notification_record.id DESC",
+        "LIMIT ?",
+      ].join("\n"),
+    )
+    .all(query, options.limit ?? DEFAULT_SEARCH_LIMIT);
+
+  return rows.map((row) => ({
+    id: Number(row.id),
+    createdAt: String(row.created_at),
+    repository: String(row.repository),
+    pullRequestNumber: Number(row.pull_request_number),
+    title: String(row.title),
+    snippet: String(row.snippet ?? ""),
+    clickUrl: typeof row.click_url === "string" ? row.click_url : null,
+  }));
+}
+
+export function renderNotificationSearchResults(results: readonly NotificationSearchResult[]): string {
+  if (results.length === 0) {
+    return "No notifications match.";
+  }
+
+  return results
+    .map((result) =>
+      [
+        `${result.createdAt.slice(0, 16)}  ${result.repository}#${result.pullRequestNumber}  ${result.title}`,
+        `  ${result.snippet.replaceAll("\n", " ")}`,
+        ...(result.clickUrl === null ? [] : [`  ${result.clickUrl}`]),
+      ].join("\n"),
+    )
+    .join("\n\n");
+}
+
+export function runSearchCommand(args: readonly string[], database: DatabaseSync): string {
+  const words: string[] = [];
+  let limit = DEFAULT_SEARCH_LIMIT;
+
+  for (let index = 0; index < args.length; index += 1) {
+    const arg = args[index]!;
+
+    if (arg === "--limit") {
+      const value = args[index + 1];
+
+      if (value === undefined || !/^[1-9]\d*$/.test(value)) {
+        throw new NotificationSearchError(SEARCH_USAGE);
+      }
+
+      limit = Number(value);
+      index += 1;
+    } else if (arg.startsWith("--")) {
+      throw new NotificationSearchError(SEARCH_USAGE);
+    } else {
+      words.push(arg);
+    }
+  }
+
+  const text = words.join(" ");
+
+  if (buildNotificationSearchQuery(text) === null) {
+    throw new NotificationSearchError(SEARCH_USAGE);
+  }
+
+  return renderNotificationSearchResults(searchNotifications(database, text, { limit }));
+}
+
+if (import.meta.url === new URL(process.argv[1] ?? "", "file:").href) {
+  let database: DatabaseSync | undefined;
+
+  try {
+    database = initializeDatabase(resolveAppPaths());
+    console.log(runSearchCommand(process.argv.slice(2), database));
+  } catch (error) {
+    console.error(error instanceof Error ? error.message : String(error));
+    process.exitCode = 1;
+  } finally {
+    database?.close();
+  }
+}
@@ -0,0 +1,38 @@
+import type { LinuxNotification } from "./linux-notification-adapter.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+
+export function withNotificationSourceLabel(
+  notificationDispatcher: NotificationDispatcher,
+  sourceLabel: string | undefined,
+): NotificationDispatcher {
+  if (sourceLabel === undefined || sourceLabel.trim().length === 0) {
+    return notificationDispatcher;
+  }
+
+  return {
+    dispatchNotification: (notification) =>
+      notificationDispatcher.dispatchNotification(
+        applyNotificationSourceLabel(notification, sourceLabel),
+      ),
+  };
+}
+
+export function applyNotificationSourceLabel(
+  notification: LinuxNotification,
+  sourceLabel: string,
+): LinuxNotification {
+  const prefix = `[${sourceLabel.trim()}] `;
+
+  return {
+    ...notification,
+    title: `${prefix}${notification.title}`,
+    ...(notification.markup
+      ? {
+          markup: {
+            ...notification.markup,
+            headerText: `${prefix}${notification.markup.headerText}`,
+          },
+        }
+      : {}),
+  };
+}
@@ -0,0 +1,192 @@
+import { DatabaseSync } from "node:sqlite";
+
+import { parseDuration, resolveAppPaths } from "./config.js";
+import { initializeDatabase } from "./database.js";
+import {
+  listNotificationExportRows,
+  type NotificationExportRow,
+} from "./notification-export.js";
+import {
+  formatResponseDuration,
+  listReviewResponses,
+  summarizeResponseTimesByRepository,
+  type RepositoryResponseTimes,
+} from "./review-response-times.js";
+
+const STATS_USAGE = "Usage: octopulse stats [--since <duration>] [--top <count>]";
+const DEFAULT_TOP_COUNT = 10;
+const SPARKLINE_CHARACTERS = "▁▂▃▄▅▆▇█";
+
+export interface NotificationStats {
+  totalCount: number;
+  byRepository: Map<string, number>;
+  byAuthor: Map<string, number>;
+  byReason: Map<string, number>;
+  byHourOfDay: number[];
+}
+
+export class NotificationStatsError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "NotificationStatsError";
+  }
+}
+
+export function aggregateNotificationStats(
+  rows: readonly Pick<NotificationExportRow, "createdAt" | "repository" | "actors" | "eventTypes">[],
+  readHour: (date: Date) => number = (date) => date.getHours(),
+): NotificationStats {
+  const stats: NotificationStats = {
+    totalCount: rows.length,
+    byRepository: new Map(),
+    byAuthor: new Map(),
+    byReason: new Map(),
+    byHourOfDay: Array.from({ length: 24 }, () => 0),
+  };
+
+  for (const row of rows) {
+    increment(stats.byRepository, row.repository);
+
+    for (const actor of row.actors.length > 0 ? row.actors : ["unknown"]) {
+      increment(stats.byAuthor, actor);
+    }
+
+    for (const eventType of row.eventTypes.length > 0 ? row.eventTypes : ["unknown"]) {
+      increment(stats.byReason, eventType);
+    }
+
+    const hour = readHour(parseStoredTimestamp(row.createdAt));
+    stats.byHourOfDay[hour] = (stats.byHourOfDay[hour] ?? 0) + 1;
+  }
+
+  return stats;
+}
+
+export function renderNotificationStats(
+  stats: NotificationStats,
+  topCount = DEFAULT_TOP_COUNT,
+): string {
+  if (stats.totalCount === 0) {
+    return "No notifications recorded.";
+  }
+
+  return [
+    `${stats.totalCount} notification${stats.totalCount === 1 ? "" : "s"}`,
+    "",
+    ...renderCountTable("Repository", stats.byRepository, topCount),
+    "",
+    ...renderCountTable("Author", stats.byAuthor, topCount),
+    "",
+    ...renderCountTable("Reason", stats.byReason, topCount),
+    "",
+    "Hour of day",
+    renderSparkline(stats.byHourOfDay),
+    "0     6     12    18   23",
+  ].join("\n");
+}
+
+export function renderReviewResponseTimes(
+  summaries: readonly RepositoryResponseTimes[],
+): string {
+  if (summaries.length === 0) {
+    return "";
+  }
+
+  const width = Math.max("Repository".length, ...summaries.map((summary) => summary.repository.length));
+
+  return [
+    "Review response time",
+    `${"Repository".padEnd(width)}  Requests  Answered  Median`,
+    ...summaries.map(
+      (summary) =>
+        `${summary.repository.padEnd(width)}  ${String(summary.requestCount).padEnd(8)}  ${String(summary.respondedCount).padEnd(8)}  ${summary.medianResponseMs === null ? "-" : formatResponseDuration(summary.medianResponseMs)}`,
+    ),
+  ].join("\n");
+}
+
+export function renderSparkline(values: readonly number[]): string {
+  const maximum = Math.max(0, ...values);
+
+  return values
+    .map((value) => {
+      if (maximum === 0 || value === 0) {
+        return " ";
+      }
+
+      const index = Math.ceil((value / maximum) * SPARKLINE_CHARACTERS.length) - 1;
+      return SPARKLINE_CHARACTERS[Math.max(0, index)] ?? " ";
+    })
+    .join("");
+}
+
+export function runStatsCommand(
+  args: readonly string[],
+  database: DatabaseSync,
+  now: Date = new Date(),
+): string {
+  let sinceMs: number | null = null;
+  let topCount = DEFAULT_TOP_COUNT;
+
+  for (let index = 0; index < args.length; index += 2) {
+    const flag = args[index];
+    const value = args[index + 1];
+
+    if (flag === "--since" && value !== undefined) {
+      sinceMs = parseDuration(value, "--since");
+    } else if (flag === "--top" && value !== undefined && /^[1-9]\d*$/.test(value)) {
+      topCount = Number(value);
+    } else {
+      throw new NotificationStatsError(STATS_USAGE);
+    }
+  }
+
+  const windowOptions = sinceMs === null ? {} : { since: new Date(now.getTime() - sinceMs) };
+  const rows = listNotificationExportRows(database, windowOptions);
+  const responseTimes = renderReviewResponseTimes(
+    summarizeResponseTimesByRepository(listReviewResponses(database, windowOptions)),
+  );
+
+  return [renderNotificationStats(aggregateNotificationStats(rows), topCount), responseTimes]
+    .filter((section) => section.length > 0)
+    .join("\n\n");
+}
+
+function renderCountTable(label: string, counts: Map<string, number>, topCount: number): string[] {
+  const entries = [...counts.entries()].sort(
+    ([leftKey, leftCount], [rightKey, rightCount]) =>
+      rightCount - leftCount || leftKey.localeCompare(rightKey),
+  );
+  const shownEntries = entries.slice(0, topCount);
+  const width = Math.max(label.length, ...shownEntries.map(([key]) => key.length));
+
+  return [
+    `${label.padEnd(width)}  Count`,
+    ...shownEntries.map(([key, count]) => `${key.padEnd(width)}  ${count}`),
+    ...(entries.length > shownEntries.length
+      ? [`…and ${entries.length - shownEntries.length} more`]
+      : []),
+  ];
+}
+
+// SQLite's CURRENT_TIMESTAMP is UTC without a zone designator.
+function parseStoredTimestamp(value: string): Date {
+  return new Date(/[zZ]|[+-]\d{2}:\d{2}$/.test(value) ? value : `${value.replace(" ", "T")}Z`);
+}
+
+function increment(counts: Map<string, number>, key: string): void {
+  counts.set(key, (counts.get(key) ?? 0) + 1);
+}
+
+if (import.meta.url === new URL(process.argv[1] ?? "", "file:").href) {
+  let database: DatabaseSync | undefined;
+
+  try {
+    database = initializeDatabase(resolveAppPaths());
+    console.log(runStatsCommand(process.argv.slice(2), database));
+  } catch (error) {
+    console.error(error instanceof Error ? error.message : String(error));
+    process.exitCode = 1;
+  } finally {
+    database?.close();
+  }
+}
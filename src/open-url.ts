@@ -1,8 +1,58 @@
 import { spawn } from "node:child_process";
+import { existsSync } from "node:fs";
 
-export async function openUrl(url: string): Promise<void> {
+export type RunOpenCommand = (command: string, args: readonly string[]) => Promise<void>;
+
+export interface OpenUrlOptions {
+  // Defaults to detecting a Flatpak or Snap sandbox.
+  sandboxed?: boolean;
+  runCommand?: RunOpenCommand;
+}
+
+// Inside a Flatpak or Snap sandbox xdg-open can't reach the host's browser, so URLs go through the
+// xdg-desktop-portal OpenURI interface instead, with xdg-open kept as the fallback.
+export async function openUrl(url: string, options: OpenUrlOptions = {}): Promise<void> {
+  const runCommand = options.runCommand ?? runOpenCommand;
+
+  if (options.sandboxed ?? isSandboxed()) {
+    try {
+      await runCommand("gdbus", buildOpenUriPortalArgs(url));
+      return;
+    } catch {
+      // The portal may be missing or refuse the request; xdg-open still works in some sandboxes.
+    }
+  }
+
+  await runCommand("xdg-open", [url]);
+}
+
+export function isSandboxed(
+  env: NodeJS.ProcessEnv = process.env,
+  fileExists: (filePath: string) => boolean = existsSync,
+): boolean {
+  return env.FLATPAK_ID !== undefined || env.SNAP !== undefined || fileExists("/.flatpak-info");
+}
+
+export function buildOpenUriPortalArgs(url: string): string[] {
+  return [
+    "call",
+    "--session",
+    "--dest",
+    "org.freedesktop.portal.Desktop",
+    "--object-path",
+    "/org/freedesktop/portal/desktop",
+    "--method",
+    "org.freedesktop.portal.OpenURI.OpenURI",
+    "",
+    // A quoted GVariant string, so the URL is never parsed as another value type.
+    `"${url.replaceAll("\\", "\\\\").replaceAll('"', '\\"')}"`,
+    "{}",
+  ];
+}
+
+function runOpenCommand(command: string, args: readonly string[]): Promise<void> {
   return new Promise((resolve, reject) => {
-    const child = spawn("xdg-open", [url], {
+    const child = spawn(command, [...args], {
       stdio: ["ignore", "pipe", "pipe"],
     });
 
@@ -11,7 +61,7 @@ export async function openUrl(url: string): Promise<void> {
       if (code === 0) {
         resolve();
       } else {
-        reject(new Error(`xdg-open exited with code ${code}`));
+        reject(new Error(`${command} exited with code ${code}`));
       }
     });
   });
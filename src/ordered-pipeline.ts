@@ -0,0 +1,33 @@
+export interface OrderedPipelineOptions<TInput, TOutput> {
+  concurrency: number;
+  process(item: TInput, index: number): Promise<TOutput>;
+  consume(output: TOutput, item: TInput, index: number): Promise<void>;
+}
+
+// Runs `process` for up to `concurrency` items at once while `consume` sees results one at a
+// time in input order. Items only start once they are within `concurrency` of the item being
+// consumed, so a slow consumer holds back new work instead of buffering unbounded results.
+export async function runOrderedPipeline<TInput, TOutput>(
+  items: readonly TInput[],
+  options: OrderedPipelineOptions<TInput, TOutput>,
+): Promise<void> {
+  const concurrency = Math.max(1, Math.floor(options.concurrency));
+  const inFlight = new Map<number, Promise<TOutput>>();
+  let nextIndex = 0;
+
+  for (let index = 0; index < items.length; index += 1) {
+    while (nextIndex < items.length && nextIndex < index + concurrency) {
+      const output = options.process(items[nextIndex] as TInput, nextIndex);
+
+      // Rejections are rethrown when the item reaches the consumer; avoid unhandled warnings.
+      output.catch(() => undefined);
+      inFlight.set(nextIndex, output);
+      nextIndex += 1;
+    }
+
+    const output = await (inFlight.get(index) as Promise<TOutput>);
+
+    inFlight.delete(index);
+    await options.consume(output, items[index] as TInput, index);
+  }
+}
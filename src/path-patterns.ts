@@ -0,0 +1,56 @@
+// Matches repository file paths against gitignore-style patterns, the syntax CODEOWNERS uses:
+// a leading or inner "/" anchors the pattern to the repository root, a trailing "/" only
+// matches directories, and a pattern that names a directory also matches everything inside it.
+export function matchesPathPattern(pattern: string, filePath: string): boolean {
+  return compilePathPattern(pattern).test(filePath.replace(/^\/+/, ""));
+}
+
+export function compilePathPattern(pattern: string): RegExp {
+  let body = pattern.trim();
+  const directoryOnly = body.endsWith("/");
+
+  if (directoryOnly) {
+    body = body.slice(0, -1);
+  }
+
+  const anchored = body.includes("/");
+  body = body.replace(/^\/+/, "");
+
+  const prefix = anchored ? "^" : "^(?:.*/)?";
+  const suffix = directoryOnly ? "/.*$" : "(?:/.*)?$";
+
+  return new RegExp(`${prefix}${translateGlob(body)}${suffix}`);
+}
+
+function translateGlob(glob: string): string {
+  let source = "";
+  let index = 0;
+
+  while (index < glob.length) {
+    if (glob.startsWith("**/", index)) {
+      source += "(?:.*/)?";
+      index += 3;
+      continue;
+    }
+
+    if (glob.startsWith("**", index)) {
+      source += ".*";
+      index += 2;
+      continue;
+    }
+
+    const character = glob.charAt(index);
+
+    if (character === "*") {
+      source += "[^/]*";
+    } else if (character === "?") {
+      source += "[^/]";
+    } else {
+      source += character.replace(/[.+^${}()|[\]\\]/g, "\\$&");
+    }
+
+    index += 1;
+  }
+
+  return source;
+}
@@ -0,0 +1,198 @@
+import type { LinuxNotification } from "./linux-notification-adapter.js";
+import { getLogger } from "./logger.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+
+const DEFAULT_FAILURE_THRESHOLD = 3;
+const DEFAULT_NOTIFY_INTERVAL_MS = 60 * 60_000;
+const AUTH_FAILURE_PATTERN = /bad credentials|requires authentication|\b401\b/i;
+const TIMEOUT_FAILURE_PATTERN = /ETIMEDOUT|UND_ERR_(?:CONNECT|HEADERS|BODY)_TIMEOUT|timed? ?out/i;
+const NETWORK_FAILURE_PATTERN =
+  /fetch failed|socket hang up|ENOTFOUND|EAI_AGAIN|ECONNREFUSED|ECONNRESET|ENETUNREACH/i;
+// Wrapped errors only keep the message, so the cause chain is searched, but not forever.
+const MAX_CAUSE_DEPTH = 5;
+
+export type PollerFailureKind = "auth" | "network" | "timeout" | "server" | "unknown";
+
+export interface PollerHealthMonitorOptions {
+  // Consecutive failed cycles of one poller before a popup is shown.
+  failureThreshold?: number;
+  // Minimum time between failure popups, however many pollers keep failing.
+  notifyIntervalMs?: number;
+  // Where the popup links, e.g. the local log viewer.
+  clickUrl?: () => string | null;
+  now?: () => number;
+}
+
+export interface PollerHealthMonitor {
+  // Records the outcome of one poller cycle: the error that failed it, or null when it succeeded.
+  recordCycle(source: string, failure: Error | null): void;
+}
+
+// Tracks consecutive failures per poller and turns a run of them into a single, throttled popup, so
+// an expired token or a dead network doesn't go unnoticed in the logs. Once every poller that
+// triggered a popup succeeds again, one more popup says notifications are flowing again.
+export function createPollerHealthMonitor(
+  notificationDispatcher: NotificationDispatcher,
+  options: PollerHealthMonitorOptions = {},
+): PollerHealthMonitor {
+  const failureThreshold = options.failureThreshold ?? DEFAULT_FAILURE_THRESHOLD;
+  const notifyIntervalMs = options.notifyIntervalMs ?? DEFAULT_NOTIFY_INTERVAL_MS;
+  const now = options.now ?? Date.now;
+  const consecutiveFailures = new Map<string, number>();
+  const alertedSources = new Set<string>();
+  let lastNotifiedAt: number | undefined;
+
+  return {
+    recordCycle(source, failure) {
+      if (failure === null) {
+        recordSuccess(source);
+      } else {
+        recordFailure(source, failure);
+      }
+    },
+  };
+
+  function recordSuccess(source: string): void {
+    consecutiveFailures.delete(source);
+
+    if (!alertedSources.delete(source) || alertedSources.size > 0) {
+      return;
+    }
+
+    getLogger().info("Polling recovered after repeated failures", { source });
+    show({
+      title: "Octopulse is reaching GitHub again",
+      body: "Polling has recovered and pull request notifications are flowing again.",
+    });
+  }
+
+  function recordFailure(source: string, error: Error): void {
+    const failureCount = (consecutiveFailures.get(source) ?? 0) + 1;
+
+    consecutiveFailures.set(source, failureCount);
+
+    if (failureCount < failureThreshold) {
+      return;
+    }
+
+    alertedSources.add(source);
+    const currentTime = now();
+
+    if (lastNotifiedAt !== undefined && currentTime - lastNotifiedAt < notifyIntervalMs) {
+      return;
+    }
+
+    lastNotifiedAt = currentTime;
+    getLogger().warn("Showing popup for repeated polling failures", {
+      source,
+      failureCount,
+      kind: classifyPollerFailure(error),
+    });
+    show(buildPollerFailureNotification(error, failureCount));
+  }
+
+  function show(notification: Omit<LinuxNotification, "clickUrl">): void {
+    const clickUrl = options.clickUrl?.() ?? null;
+
+    notificationDispatcher
+      .dispatchNotification({ ...notification, ...(clickUrl === null ? {} : { clickUrl }) })
+      .catch((error: unknown) => {
+        getLogger().warn("Failed to show polling health popup", { error });
+      });
+  }
+}
+
+// Octokit reports a request that never got an answer as a 500, so network errors are looked for
+// before GitHub's own 5xx statuses.
+export function classifyPollerFailure(error: Error): PollerFailureKind {
+  const causes = listErrorCauses(error);
+  const details = causes.map(({ message, code }) => `${message} ${code ?? ""}`).join("\n");
+  const statuses = causes.flatMap(({ status }) => (status === undefined ? [] : [status]));
+
+  if (statuses.includes(401) || AUTH_FAILURE_PATTERN.test(details)) {
+    return "auth";
+  }
+
+  if (TIMEOUT_FAILURE_PATTERN.test(details)) {
+    return "timeout";
+  }
+
+  if (NETWORK_FAILURE_PATTERN.test(details)) {
+    return "network";
+  }
+
+  if (statuses.some((status) => status >= 500)) {
+    return "server";
+  }
+
+  return "unknown";
+}
+
+interface ErrorCause {
+  message: string;
+  code: string | undefined;
+  status: number | undefined;
+}
+
+function listErrorCauses(error: Error): ErrorCause[] {
+  const causes: ErrorCause[] = [];
+  let current: unknown = error;
+
+  while (causes.length < MAX_CAUSE_DEPTH && typeof current === "object" && current !== null) {
+    const { message, code, status, cause } = current as {
+      message?: unknown;
+      code?: unknown;
+      status?: unknown;
+      cause?: unknown;
+    };
+
+    causes.push({
+      message: typeof message === "string" ? message : "",
+      code: typeof code === "string" ? code : undefined,
+      status: typeof status === "number" ? status : undefined,
+    });
+    current = cause;
+  }
+
+  return causes;
+}
+
+export function buildPollerFailureNotification(
+  error: Error,
+  failureCount: number,
+): Omit<LinuxNotification, "clickUrl"> {
+  const failedTimes = `Polling has failed ${failureCount} times in a row, so pull request notifications may be missing.`;
+
+  switch (classifyPollerFailure(error)) {
+    case "auth":
+      return {
+        title: "GitHub rejected Octopulse's token",
+        body: `${failedTimes} The token may have expired or been revoked; update github.token in your config and restart Octopulse.`,
+        urgency: "critical",
+      };
+    case "network":
+      return {
+        title: "Octopulse can't reach GitHub",
+        body: `${failedTimes} Check your network connection; Octopulse keeps retrying in the background.`,
+        urgency: "critical",
+      };
+    case "timeout":
+      return {
+        title: "GitHub requests are timing out",
+        body: `${failedTimes} The network or GitHub is slow to respond; Octopulse keeps retrying in the background.`,
+        urgency: "critical",
+      };
+    case "server":
+      return {
+        title: "GitHub is having problems",
+        body: `${failedTimes} GitHub is answering with server errors; check githubstatus.com. Octopulse keeps retrying, less often the longer it lasts.`,
+        urgency: "critical",
+      };
+    case "unknown":
+      return {
+        title: "Octopulse polling is failing",
+        body: `${failedTimes} Latest error: ${error.message}`,
+        urgency: "critical",
+      };
+  }
+}
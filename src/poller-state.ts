@@ -0,0 +1,309 @@
+import { DatabaseSync } from "node:sqlite";
+
+import type { GitHubRequestHookClient } from "./github-token-guard.js";
+import { getLogger } from "./logger.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+import { classifyPollerFailure, type PollerFailureKind } from "./poller-health.js";
+
+const POLLER_STATE_KEY_PREFIX = "poller_state";
+const RATE_LIMIT_RESET_KEY = "github_rate_limit_reset_at";
+const RATE_LIMIT_SLOWDOWN_KEY = "github_rate_limit_slowdown_until";
+// A dropped connection usually comes back soon, so those waits stay short; a GitHub outage can
+// last a while.
+const MAX_BACKOFF_MS: Record<Exclude<PollerFailureKind, "auth">, number> = {
+  network: 5 * 60_000,
+  timeout: 5 * 60_000,
+  server: 30 * 60_000,
+  unknown: 30 * 60_000,
+};
+// Each wait is shortened by up to this share, so pollers of several accounts, or several
+// Octopulse instances, don't retry in lockstep.
+const BACKOFF_JITTER_FRACTION = 0.5;
+// Below this share of the quota left, pollers run this many times less often until it resets.
+const LOW_RATE_LIMIT_FRACTION = 0.2;
+const RATE_LIMIT_SLOWDOWN_FACTOR = 4;
+
+// What a poller needs to resume politely after a restart, kept in AppState as JSON.
+export interface PollerNetworkState {
+  consecutiveFailures: number;
+  backoffUntil: string | null;
+}
+
+export interface PollerDeferral {
+  until: string;
+  reason: "backoff" | "rate_limit" | "rate_limit_low";
+}
+
+// What GitHub's X-RateLimit headers reported when the quota ran low.
+export interface GitHubRateLimitBudget {
+  remaining: number;
+  limit: number;
+  resetAt: string;
+}
+
+export interface WatchGitHubRateLimitOptions {
+  now?: () => number;
+  // Shows a warning once each time the quota runs low.
+  notificationDispatcher?: NotificationDispatcher;
+}
+
+export interface PollerBackoff {
+  // When the next cycle should wait instead of calling GitHub, or null when it may run.
+  readDeferral(): PollerDeferral | null;
+  recordCycle(failure: Error | null): void;
+}
+
+export interface PersistentPollerBackoffOptions {
+  intervalMs: number;
+  now?: () => number;
+  random?: () => number;
+}
+
+export class PollerStateRepository {
+  constructor(private readonly database: DatabaseSync) {}
+
+  getPollerState(poller: string): PollerNetworkState {
+    const value = this.readValue(`${POLLER_STATE_KEY_PREFIX}:${poller}`);
+
+    if (value === undefined) {
+      return { consecutiveFailures: 0, backoffUntil: null };
+    }
+
+    try {
+      const parsed = JSON.parse(value) as Partial<PollerNetworkState>;
+
+      return {
+        consecutiveFailures:
+          typeof parsed.consecutiveFailures === "number" ? parsed.consecutiveFailures : 0,
+        backoffUntil: typeof parsed.backoffUntil === "string" ? parsed.backoffUntil : null,
+      };
+    } catch {
+      return { consecutiveFailures: 0, backoffUntil: null };
+    }
+  }
+
+  savePollerState(poller: string, state: PollerNetworkState): void {
+    this.writeValue(`${POLLER_STATE_KEY_PREFIX}:${poller}`, JSON.stringify(state));
+  }
+
+  // The rate limit belongs to the token, so every poller shares one reset time.
+  getRateLimitResetAt(): string | null {
+    return this.readValue(RATE_LIMIT_RESET_KEY) ?? null;
+  }
+
+  saveRateLimitResetAt(resetAt: string): void {
+    this.writeValue(RATE_LIMIT_RESET_KEY, resetAt);
+  }
+
+  // Until when pollers stretch their interval because little of the rate limit is left.
+  getRateLimitSlowdownUntil(): string | null {
+    return this.readValue(RATE_LIMIT_SLOWDOWN_KEY) ?? null;
+  }
+
+  saveRateLimitSlowdownUntil(until: string): void {
+    this.writeValue(RATE_LIMIT_SLOWDOWN_KEY, until);
+  }
+
+  private readValue(key: string): string | undefined {
+    const row = this.database.prepare("SELECT value FROM AppState WHERE key = ?").get(key);
+
+    return typeof row?.value === "string" ? row.value : undefined;
+  }
+
+  private writeValue(key: string, value: string): void {
+    this.database
+      .prepare(
+        `
+          INSERT INTO AppState (key, value)
+          VALUES (?, ?)
+          ON CONFLICT(key) DO UPDATE SET
+            value = excluded.value,
+            updated_at = CURRENT_TIMESTAMP
+        `,
+      )
+      .run(key, value);
+  }
+}
+
+// Skips cycles after repeated failures, doubling the wait each time with some jitter, while
+// GitHub's rate limit is exhausted, and all but every RATE_LIMIT_SLOWDOWN_FACTOR-th cycle while it
+// runs low. The state is persisted, so restarting Octopulse doesn't reset the wait and hit GitHub
+// straight away.
+export function createPersistentPollerBackoff(
+  repository: PollerStateRepository,
+  poller: string,
+  options: PersistentPollerBackoffOptions,
+): PollerBackoff {
+  const now = options.now ?? Date.now;
+  const random = options.random ?? Math.random;
+  let lastCycleEndedAt: number | undefined;
+
+  return {
+    readDeferral() {
+      const currentTime = now();
+      const rateLimitResetAt = repository.getRateLimitResetAt();
+
+      if (rateLimitResetAt !== null && Date.parse(rateLimitResetAt) > currentTime) {
+        return { until: rateLimitResetAt, reason: "rate_limit" };
+      }
+
+      const slowdownUntil = repository.getRateLimitSlowdownUntil();
+
+      if (
+        slowdownUntil !== null &&
+        lastCycleEndedAt !== undefined &&
+        Date.parse(slowdownUntil) > currentTime
+      ) {
+        // The interval timer keeps ticking, so waiting this long after the last cycle ended
+        // leaves RATE_LIMIT_SLOWDOWN_FACTOR intervals between cycle starts.
+        const stretchedUntil = Math.min(
+          lastCycleEndedAt + options.intervalMs * (RATE_LIMIT_SLOWDOWN_FACTOR - 1),
+          Date.parse(slowdownUntil),
+        );
+
+        if (stretchedUntil > currentTime) {
+          return { until: new Date(stretchedUntil).toISOString(), reason: "rate_limit_low" };
+        }
+      }
+
+      const { backoffUntil } = repository.getPollerState(poller);
+
+      if (backoffUntil !== null && Date.parse(backoffUntil) > currentTime) {
+        return { until: backoffUntil, reason: "backoff" };
+      }
+
+      return null;
+    },
+    recordCycle(failure) {
+      lastCycleEndedAt = now();
+
+      if (failure === null) {
+        repository.savePollerState(poller, { consecutiveFailures: 0, backoffUntil: null });
+        return;
+      }
+
+      const consecutiveFailures = repository.getPollerState(poller).consecutiveFailures + 1;
+      const failureKind = classifyPollerFailure(failure);
+
+      // A rejected token doesn't get better by waiting, and the token guard pauses polling until a
+      // replacement is accepted, after which the next regular cycle should run.
+      if (failureKind === "auth") {
+        repository.savePollerState(poller, { consecutiveFailures, backoffUntil: null });
+        return;
+      }
+
+      // One failure waits for the next regular cycle; after that the wait keeps doubling.
+      const backoffMs =
+        Math.min(
+          options.intervalMs * (2 ** (consecutiveFailures - 1) - 1),
+          MAX_BACKOFF_MS[failureKind],
+        ) *
+        (1 - BACKOFF_JITTER_FRACTION * random());
+
+      repository.savePollerState(poller, {
+        consecutiveFailures,
+        backoffUntil: backoffMs > 0 ? new Date(now() + backoffMs).toISOString() : null,
+      });
+    },
+  };
+}
+
+// Records when GitHub says the token's rate limit resets, so pollers wait for it instead of
+// failing every cycle, including across a restart. Successful responses report how much of the
+// quota is left, and pollers slow down once it runs low.
+export function watchGitHubRateLimit(
+  client: GitHubRequestHookClient,
+  repository: PollerStateRepository,
+  options: WatchGitHubRateLimitOptions = {},
+): void {
+  const now = options.now ?? Date.now;
+  let reportedSlowdownUntil = repository.getRateLimitSlowdownUntil();
+
+  client.hook.after("request", (response) => {
+    const budget = readLowRateLimitBudget(response.headers);
+
+    if (budget === null || budget.resetAt === reportedSlowdownUntil) {
+      return;
+    }
+
+    reportedSlowdownUntil = budget.resetAt;
+    getLogger().warn("GitHub rate limit running low; slowing down polling until it resets", {
+      ...budget,
+    });
+    repository.saveRateLimitSlowdownUntil(budget.resetAt);
+    options.notificationDispatcher
+      ?.dispatchNotification({
+        title: "GitHub rate limit running low",
+        body: `${budget.remaining} of ${budget.limit} GitHub requests are left for the next ${formatMinutesUntil(budget.resetAt, now())}, so Octopulse polls ${RATE_LIMIT_SLOWDOWN_FACTOR} times less often until the limit resets.`,
+      })
+      .catch((error: unknown) => {
+        getLogger().warn("Failed to show GitHub rate limit popup", { error });
+      });
+  });
+
+  client.hook.error("request", (error) => {
+    const resetAt = readRateLimitResetAt(error, now());
+
+    if (resetAt !== null) {
+      getLogger().warn("GitHub rate limit exhausted; pausing polling until it resets", { resetAt });
+      repository.saveRateLimitResetAt(resetAt);
+    }
+
+    throw error;
+  });
+}
+
+export function readRateLimitResetAt(error: unknown, currentTime: number): string | null {
+  if (typeof error !== "object" || error === null) {
+    return null;
+  }
+
+  const { status, response } = error as { status?: unknown; response?: { headers?: unknown } };
+  const headers = (response?.headers ?? {}) as Record<string, unknown>;
+
+  if (status !== 403 && status !== 429) {
+    return null;
+  }
+
+  const retryAfterSeconds = Number(headers["retry-after"]);
+
+  if (Number.isFinite(retryAfterSeconds) && retryAfterSeconds > 0) {
+    return new Date(currentTime + retryAfterSeconds * 1_000).toISOString();
+  }
+
+  const resetEpochSeconds = Number(headers["x-ratelimit-reset"]);
+
+  if (headers["x-ratelimit-remaining"] === "0" && Number.isFinite(resetEpochSeconds)) {
+    return new Date(resetEpochSeconds * 1_000).toISOString();
+  }
+
+  return null;
+}
+
+// Search and GraphQL requests have quotas of their own; only the core REST quota paces polling.
+export function readLowRateLimitBudget(
+  headers: Record<string, unknown>,
+): GitHubRateLimitBudget | null {
+  const resource = headers["x-ratelimit-resource"];
+  const remaining = Number(headers["x-ratelimit-remaining"]);
+  const limit = Number(headers["x-ratelimit-limit"]);
+  const resetEpochSeconds = Number(headers["x-ratelimit-reset"]);
+
+  if (
+    (resource !== undefined && resource !== "core") ||
+    !Number.isFinite(remaining) ||
+    !Number.isFinite(limit) ||
+    !Number.isFinite(resetEpochSeconds) ||
+    remaining >= limit * LOW_RATE_LIMIT_FRACTION
+  ) {
+    return null;
+  }
+
+  return { remaining, limit, resetAt: new Date(resetEpochSeconds * 1_000).toISOString() };
+}
+
+function formatMinutesUntil(timestamp: string, currentTime: number): string {
+  const minutes = Math.max(1, Math.ceil((Date.parse(timestamp) - currentTime) / 60_000));
+
+  return minutes === 1 ? "minute" : `${minutes} minutes`;
+}
@@ -0,0 +1,93 @@
+import { buildHistoryWindowUrl } from "./history-window.js";
+import { openUrl } from "./open-url.js";
+import { DEFAULT_SERVER_HOST, DEFAULT_SERVER_PORT } from "./server.js";
+import { runShortcutCommand } from "./shortcut-command.js";
+
+export const PROTOCOL_SCHEME = "octopulse";
+
+export type ProtocolAction =
+  | { kind: "open"; url: string }
+  | { kind: "history" }
+  | { kind: "shortcut"; shortcut: "open-latest" | "open-inbox" | "toggle-pause" };
+
+export interface RunProtocolHandlerOptions {
+  serverOrigin?: string;
+  openUrl?: (url: string) => Promise<void>;
+  runShortcut?: (args: readonly string[]) => Promise<string>;
+}
+
+export class ProtocolHandlerError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "ProtocolHandlerError";
+  }
+}
+
+export function buildProtocolUrl(action: ProtocolAction): string {
+  switch (action.kind) {
+    case "open":
+      return `${PROTOCOL_SCHEME}://open?url=${encodeURIComponent(action.url)}`;
+    case "history":
+      return `${PROTOCOL_SCHEME}://history`;
+    case "shortcut":
+      return `${PROTOCOL_SCHEME}://${action.shortcut}`;
+  }
+}
+
+export function parseProtocolUrl(value: string): ProtocolAction {
+  let url: URL;
+
+  try {
+    url = new URL(value);
+  } catch {
+    throw new ProtocolHandlerError(`Invalid ${PROTOCOL_SCHEME}:// URL: ${value}`);
+  }
+
+  if (url.protocol !== `${PROTOCOL_SCHEME}:`) {
+    throw new ProtocolHandlerError(`Expected a ${PROTOCOL_SCHEME}:// URL, received ${value}`);
+  }
+
+  switch (url.hostname) {
+    case "open": {
+      const target = url.searchParams.get("url") ?? "";
+
+      // Only web URLs are opened so other apps can't use the scheme to launch arbitrary handlers.
+      if (!/^https?:\/\//i.test(target)) {
+        throw new ProtocolHandlerError("open requires an http or https url parameter");
+      }
+
+      return { kind: "open", url: target };
+    }
+    case "history":
+      return { kind: "history" };
+    case "open-latest":
+    case "open-inbox":
+    case "toggle-pause":
+      return { kind: "shortcut", shortcut: url.hostname };
+    default:
+      throw new ProtocolHandlerError(`Unsupported ${PROTOCOL_SCHEME}:// action: ${url.hostname}`);
+  }
+}
+
+export async function runProtocolHandler(
+  value: string,
+  options: RunProtocolHandlerOptions = {},
+): Promise<string> {
+  const action = parseProtocolUrl(value);
+  const serverOrigin =
+    options.serverOrigin ?? `http://${DEFAULT_SERVER_HOST}:${DEFAULT_SERVER_PORT}`;
+  const openUrlImpl = options.openUrl ?? openUrl;
+
+  switch (action.kind) {
+    case "open":
+      await openUrlImpl(action.url);
+      return `Opened ${action.url}`;
+    case "history":
+      await openUrlImpl(buildHistoryWindowUrl(serverOrigin));
+      return "Opened notification history";
+    case "shortcut":
+      return (options.runShortcut ?? ((args) => runShortcutCommand(args, { serverOrigin })))([
+        action.shortcut,
+      ]);
+  }
+}
@@ -1,10 +1,19 @@
+import { createHash } from "node:crypto";
 import { DatabaseSync } from "node:sqlite";
 
 import { Octokit } from "octokit";
 
+import type { GitHubGraphqlClient } from "./github.js";
+import { getLogger } from "./logger.js";
 import {
   PullRequestCiJobStateRepository,
 } from "./pull-request-ci-job-state-repository.js";
+import {
+  fetchPullRequestActivityFromGraphql,
+  filterUpdatedSince,
+  type PullRequestActivityApi,
+  type PullRequestGraphqlActivity,
+} from "./pull-request-graphql-activity.js";
 import type { PullRequestRecord } from "./pull-request-repository.js";
 import {
   PullRequestReviewStateRepository,
@@ -20,6 +29,8 @@ const GITHUB_API_HEADERS = {
   "X-GitHub-Api-Version": "2022-11-28",
 };
 const GITHUB_PAGE_SIZE = 100;
+// Busy pull requests can carry thousands of comments; only the newest are worth turning into events.
+const DEFAULT_MAX_FETCHED_COMMENTS = 300;
 const ISSUE_COMMENT_SOURCE = "github_issue_comment";
 const PULL_REQUEST_REVIEW_SOURCE = "github_pull_request_review";
 const PULL_REQUEST_REVIEW_COMMENT_SOURCE = "github_pull_request_review_comment";
@@ -33,6 +44,9 @@ const SUPPORTED_TIMELINE_EVENT_TYPES = new Set([
   "ready_for_review",
   "convert_to_draft",
   "committed",
+  "assigned",
+  "head_ref_force_pushed",
+  "review_dismissed",
 ]);
 
 type ActivityFetchCursorSource =
@@ -44,6 +58,11 @@ export interface IngestPullRequestActivityOptions<TClient = Octokit> {
   pullRequestReviewStateRepository?: Pick<PullRequestReviewStateRepository, "upsertReviewState">;
   ciJobStateRepository?: Pick<PullRequestCiJobStateRepository, "upsertCiJobState" | "hasJobsForWorkflowRun">;
   requiredChecksCache?: Pick<RequiredChecksCache, "getRequiredChecks">;
+  // Caps issue comments, reviews, and review comments each; every page is read, keeping the newest.
+  maxFetchedComments?: number;
+  // "graphql" reads comments, reviews, and review comments in one request instead of three REST
+  // listings, at most 100 of each.
+  activityApi?: PullRequestActivityApi;
   fetchIssueComments?: (
     client: TClient,
     pullRequest: PullRequestRecord,
@@ -80,8 +99,8 @@ export interface IngestPullRequestActivityResult {
 }
 
 export class PullRequestActivityIngestionError extends Error {
-  constructor(message: string) {
-    super(message);
+  constructor(message: string, options?: ErrorOptions) {
+    super(message, options);
     this.name = "PullRequestActivityIngestionError";
   }
 }
@@ -107,29 +126,51 @@ export async function ingestPullRequestActivity<TClient>(
     pullRequest.id,
     PULL_REQUEST_REVIEW_COMMENT_SOURCE,
   );
+  const maxFetchedComments = options.maxFetchedComments ?? DEFAULT_MAX_FETCHED_COMMENTS;
+  // The three fetchers below share the one GraphQL request.
+  let graphqlActivity: Promise<PullRequestGraphqlActivity> | undefined;
+  const loadGraphqlActivity = (client: TClient, pullRequest: PullRequestRecord) =>
+    (graphqlActivity ??= fetchPullRequestActivityFromGraphql(
+      client as unknown as GitHubGraphqlClient,
+      pullRequest,
+      maxFetchedComments,
+    ));
+  const useGraphql = options.activityApi === "graphql";
   const fetchIssueComments =
     options.fetchIssueComments ??
-    ((client: TClient, pullRequest: PullRequestRecord, since?: string) =>
-      fetchIssueCommentsFromGitHub(
-        client as unknown as Octokit,
-        pullRequest,
-        since,
-      ) as Promise<unknown[]>);
+    (useGraphql
+      ? async (client: TClient, pullRequest: PullRequestRecord, since?: string) =>
+          filterUpdatedSince((await loadGraphqlActivity(client, pullRequest)).issueComments, since)
+      : (client: TClient, pullRequest: PullRequestRecord, since?: string) =>
+          fetchIssueCommentsFromGitHub(
+            client as unknown as Octokit,
+            pullRequest,
+            since,
+            maxFetchedComments,
+          ) as Promise<unknown[]>);
   const fetchPullRequestReviews =
     options.fetchPullRequestReviews ??
-    ((client: TClient, pullRequest: PullRequestRecord) =>
-      fetchPullRequestReviewsFromGitHub(
-        client as unknown as Octokit,
-        pullRequest,
-      ) as Promise<unknown[]>);
+    (useGraphql
+      ? async (client: TClient, pullRequest: PullRequestRecord) =>
+          (await loadGraphqlActivity(client, pullRequest)).reviews
+      : (client: TClient, pullRequest: PullRequestRecord) =>
+          fetchPullRequestReviewsFromGitHub(
+            client as unknown as Octokit,
+            pullRequest,
+            maxFetchedComments,
+          ) as Promise<unknown[]>);
   const fetchPullRequestReviewComments =
     options.fetchPullRequestReviewComments ??
-    ((client: TClient, pullRequest: PullRequestRecord, since?: string) =>
-      fetchPullRequestReviewCommentsFromGitHub(
-        client as unknown as Octokit,
-        pullRequest,
-        since,
-      ) as Promise<unknown[]>);
+    (useGraphql
+      ? async (client: TClient, pullRequest: PullRequestRecord, since?: string) =>
+          filterUpdatedSince((await loadGraphqlActivity(client, pullRequest)).reviewComments, since)
+      : (client: TClient, pullRequest: PullRequestRecord, since?: string) =>
+          fetchPullRequestReviewCommentsFromGitHub(
+            client as unknown as Octokit,
+            pullRequest,
+            since,
+            maxFetchedComments,
+          ) as Promise<unknown[]>);
   const fetchPullRequestTimeline =
     options.fetchPullRequestTimeline ??
     ((client: TClient, pullRequest: PullRequestRecord) =>
@@ -172,8 +213,9 @@ export async function ingestPullRequestActivity<TClient>(
     workflowRunsPromise,
   ]);
   const filteredTimelineEvents = filterRedundantMergedClosedTimelineEvents(timelineEvents);
+  const filteredIssueComments = filterIssueCommentsDuplicatingReviews(issueComments, reviews);
   const rawEvents = [
-    ...issueComments.map((comment) => mapIssueCommentRawEvent(comment, pullRequest.id)),
+    ...filteredIssueComments.map((comment) => mapIssueCommentRawEvent(comment, pullRequest.id)),
     ...reviews.flatMap((review) => {
       const rawEvent = mapPullRequestReviewRawEvent(review, pullRequest.id);
       return rawEvent ? [rawEvent] : [];
@@ -262,6 +304,7 @@ async function fetchIssueCommentsFromGitHub(
   client: Octokit,
   pullRequest: Pick<PullRequestRecord, "repositoryOwner" | "repositoryName" | "number">,
   since?: string,
+  maxItems: number,
 ): Promise<unknown[]> {
   return fetchAllPagesFromGitHub(
     client,
@@ -273,12 +316,14 @@ async function fetchIssueCommentsFromGitHub(
       ...(since === undefined ? {} : { since }),
     },
     "issue comments response",
+    maxItems,
   );
 }
 
 async function fetchPullRequestReviewsFromGitHub(
   client: Octokit,
   pullRequest: Pick<PullRequestRecord, "repositoryOwner" | "repositoryName" | "number">,
+  maxItems: number,
 ): Promise<unknown[]> {
   return fetchAllPagesFromGitHub(
     client,
@@ -289,6 +334,7 @@ async function fetchPullRequestReviewsFromGitHub(
       pull_number: pullRequest.number,
     },
     "pull request reviews response",
+    maxItems,
   );
 }
 
@@ -296,6 +342,7 @@ async function fetchPullRequestReviewCommentsFromGitHub(
   client: Octokit,
   pullRequest: Pick<PullRequestRecord, "repositoryOwner" | "repositoryName" | "number">,
   since?: string,
+  maxItems: number,
 ): Promise<unknown[]> {
   return fetchAllPagesFromGitHub(
     client,
@@ -307,6 +354,7 @@ async function fetchPullRequestReviewCommentsFromGitHub(
       ...(since === undefined ? {} : { since }),
     },
     "pull request review comments response",
+    maxItems,
   );
 }
 
@@ -389,8 +437,10 @@ async function fetchAllPagesFromGitHub(
   route: string,
   parameters: Record<string, unknown>,
   responseFieldName: string,
+  maxItems = Number.POSITIVE_INFINITY,
 ): Promise<unknown[]> {
   const items: unknown[] = [];
+  let skippedCount = 0;
 
   for (let page = 1; ; page += 1) {
     const response = await client.request(route, {
@@ -402,7 +452,20 @@ async function fetchAllPagesFromGitHub(
     const pageItems = readArray(response.data as unknown, responseFieldName);
     items.push(...pageItems);
 
+    // Pages arrive oldest-first, so dropping from the front keeps the newest items.
+    if (items.length > maxItems) {
+      skippedCount += items.splice(0, items.length - maxItems).length;
+    }
+
     if (pageItems.length < GITHUB_PAGE_SIZE) {
+      if (skippedCount > 0) {
+        getLogger().debug("Skipped earlier GitHub items beyond the fetch cap", {
+          route,
+          skippedCount,
+          keptCount: items.length,
+        });
+      }
+
       return items;
     }
   }
@@ -422,6 +485,7 @@ async function loadActivity(
 
     throw new PullRequestActivityIngestionError(
       `Failed to fetch ${description} for ${formatPullRequestLabel(pullRequest)}: ${getErrorMessage(error)}`,
+      { cause: error },
     );
   }
 }
@@ -625,6 +689,49 @@ function filterRedundantMergedClosedTimelineEvents(events: readonly unknown[]):
   });
 }
 
+function filterIssueCommentsDuplicatingReviews(
+  issueComments: readonly unknown[],
+  reviews: readonly unknown[],
+): unknown[] {
+  const reviewKeys = new Set(
+    reviews.flatMap((review) => {
+      const key = readAuthoredBodyKey(review, "submitted_at");
+      return key === null ? [] : [key];
+    }),
+  );
+
+  if (reviewKeys.size === 0) {
+    return [...issueComments];
+  }
+
+  return issueComments.filter((comment) => {
+    const key = readAuthoredBodyKey(comment, "created_at");
+    return key === null || !reviewKeys.has(key);
+  });
+}
+
+function readAuthoredBodyKey(value: unknown, timestampFieldName: string): string | null {
+  const record = readOptionalRecord(value);
+
+  if (record === null) {
+    return null;
+  }
+
+  const actorLogin = readOptionalIdentityLogin(record.user);
+  const body = typeof record.body === "string" ? record.body.trim() : "";
+  const timestamp = record[timestampFieldName];
+  const occurredAtMs = typeof timestamp === "string" ? Date.parse(timestamp) : Number.NaN;
+
+  if (actorLogin === null || body.length === 0 || !Number.isFinite(occurredAtMs)) {
+    return null;
+  }
+
+  const bodyHash = createHash("sha256").update(body).digest("hex");
+
+  // Compare at second precision; the review and its mirrored comment are stamped separately.
+  return `${actorLogin.toLowerCase()}:${Math.floor(occurredAtMs / 1_000)}:${bodyHash}`;
+}
+
 function readTimelineMergeCloseCandidate(
   event: unknown,
 ): { eventType: string; actorLogin: string | null; occurredAtMs: number } | null {
@@ -20,6 +20,8 @@ export interface NormalizePullRequestActivityOptions {
     NormalizedEventRepository,
     "insertNormalizedEvent" | "listNormalizedEventsForPullRequest"
   >;
+  // Send CI failures on your own pull requests immediately instead of bundling them.
+  immediateCiFailures?: boolean;
 }
 
 export interface NormalizePullRequestActivityResult {
@@ -59,6 +61,18 @@ interface DerivedCiOutcomeEvent extends InsertNormalizedEventInput {
   eventType: CiOutcomeEventType;
 }
 
+// What happened after the current user's latest review, keyed by raw event id.
+interface ActivitySinceReview {
+  commitsSinceReview: Map<number, number>;
+  forcePushesSinceReview: Set<number>;
+}
+
+// Earlier pull request history that individual raw events need to be described properly.
+interface RawEventHistoryContext {
+  activitySinceReview: ActivitySinceReview;
+  reviewerLoginsByReviewId: Map<number, string>;
+}
+
 interface WorkflowRunHistoryEntry {
   rawEvent: RawEventRecord;
   snapshot: WorkflowRunSnapshot;
@@ -79,6 +93,11 @@ export function normalizePullRequestActivity(
 
   try {
     const workflowRawEventIds = new Set<number>();
+    const historyContext = readRawEventHistoryContext(
+      rawEvents,
+      () => rawEventRepository.listRawEventsForPullRequest(pullRequest.id),
+      currentUserLogin,
+    );
 
     for (const rawEvent of rawEvents) {
       if (rawEvent.eventType === "workflow_run") {
@@ -86,7 +105,7 @@ export function normalizePullRequestActivity(
         continue;
       }
 
-      const normalizedEvent = normalizeRawEvent(rawEvent, currentUserLogin);
+      const normalizedEvent = normalizeRawEvent(rawEvent, currentUserLogin, historyContext);
 
       if (normalizedEvent === undefined) {
         skippedCount += 1;
@@ -103,6 +122,7 @@ export function normalizePullRequestActivity(
       for (const ciOutcomeEvent of deriveMissingCiOutcomeEvents({
         pullRequest,
         currentUserLogin,
+        immediateCiFailures: options.immediateCiFailures ?? false,
         rawEvents: rawEventRepository.listRawEventsForPullRequest(pullRequest.id),
         normalizedEvents: normalizedEventRepository.listNormalizedEventsForPullRequest(
           pullRequest.id,
@@ -154,12 +174,90 @@ export function classifyActor(input: ActorClassificationInput): ActorClass {
   return "human_other";
 }
 
+// Only reads the full raw history when a new event actually depends on it.
+function readRawEventHistoryContext(
+  rawEvents: readonly RawEventRecord[],
+  listAllRawEvents: () => RawEventRecord[],
+  currentUserLogin: string,
+): RawEventHistoryContext {
+  const needsHistory = rawEvents.some(
+    (rawEvent) =>
+      rawEvent.eventType === "committed" ||
+      rawEvent.eventType === "head_ref_force_pushed" ||
+      rawEvent.eventType === "review_dismissed",
+  );
+
+  if (!needsHistory) {
+    return {
+      activitySinceReview: { commitsSinceReview: new Map(), forcePushesSinceReview: new Set() },
+      reviewerLoginsByReviewId: new Map(),
+    };
+  }
+
+  const allRawEvents = listAllRawEvents();
+
+  return {
+    activitySinceReview: readActivitySinceCurrentUserReview(allRawEvents, currentUserLogin),
+    reviewerLoginsByReviewId: new Map(
+      allRawEvents.flatMap((rawEvent) => {
+        const reviewId = Number(rawEvent.sourceId);
+
+        return rawEvent.eventType === "pull_request_review" &&
+          rawEvent.actorLogin !== null &&
+          Number.isSafeInteger(reviewId)
+          ? [[reviewId, rawEvent.actorLogin] as const]
+          : [];
+      }),
+    ),
+  };
+}
+
+// GitHub does not reliably notify reviewers about pushes after their review, so each commit
+// records how many commits have landed since the current user's latest review, and force
+// pushes after that review are singled out.
+function readActivitySinceCurrentUserReview(
+  rawEvents: readonly RawEventRecord[],
+  currentUserLogin: string,
+): ActivitySinceReview {
+  const commitsSinceReview = new Map<number, number>();
+  const forcePushesSinceReview = new Set<number>();
+  const orderedEvents = [...rawEvents].sort(
+    (left, right) => left.occurredAt.localeCompare(right.occurredAt) || left.id - right.id,
+  );
+  let commitCount: number | null = null;
+
+  for (const rawEvent of orderedEvents) {
+    if (
+      rawEvent.eventType === "pull_request_review" &&
+      rawEvent.actorLogin !== null &&
+      normalizeLogin(rawEvent.actorLogin) === normalizeLogin(currentUserLogin)
+    ) {
+      commitCount = 0;
+    } else if (commitCount === null) {
+      continue;
+    } else if (rawEvent.eventType === "committed") {
+      commitCount += 1;
+      commitsSinceReview.set(rawEvent.id, commitCount);
+    } else if (rawEvent.eventType === "head_ref_force_pushed") {
+      forcePushesSinceReview.add(rawEvent.id);
+    }
+  }
+
+  return { commitsSinceReview, forcePushesSinceReview };
+}
+
 function normalizeRawEvent(
   rawEvent: RawEventRecord,
   currentUserLogin: string,
+  historyContext: RawEventHistoryContext,
 ): InsertNormalizedEventInput | undefined {
   const payload = parseRawPayload(rawEvent);
-  const eventType = mapNormalizedEventType(rawEvent, payload);
+  const eventType = mapNormalizedEventType(
+    rawEvent,
+    payload,
+    currentUserLogin,
+    historyContext.activitySinceReview,
+  );
 
   if (eventType === undefined) {
     return undefined;
@@ -179,7 +277,10 @@ function normalizeRawEvent(
     actorClass,
     decisionState: resolveDecisionState(eventType, actorClass),
     notificationTiming: resolveNotificationTiming(eventType, actorClass),
-    payloadJson: serializeNormalizedPayload(rawEvent, buildNormalizedPayload(rawEvent, payload)),
+    payloadJson: serializeNormalizedPayload(
+      rawEvent,
+      buildNormalizedPayload(rawEvent, payload, historyContext),
+    ),
     occurredAt: rawEvent.occurredAt,
   };
 }
@@ -187,8 +288,10 @@ function normalizeRawEvent(
 function buildNormalizedPayload(
   rawEvent: RawEventRecord,
   payload: Record<string, unknown>,
+  historyContext: RawEventHistoryContext,
 ): Record<string, number | string | null> | undefined {
   const actorAvatarUrl = readActorAvatarUrl(rawEvent, payload);
+  const commitsSinceReview = historyContext.activitySinceReview.commitsSinceReview.get(rawEvent.id);
 
   switch (rawEvent.eventType) {
     case "issue_comment":
@@ -216,13 +319,31 @@ function buildNormalizedPayload(
         path: readOptionalString(payload.path),
         url: readOptionalString(payload.html_url),
       };
+    case "assigned":
+      return {
+        ...(actorAvatarUrl === null ? {} : { actorAvatarUrl }),
+        assigneeLogin: readAssigneeLogin(payload),
+      };
     case "committed":
       return {
         ...(actorAvatarUrl === null ? {} : { actorAvatarUrl }),
         commitSha: readOptionalString(payload.sha),
         messageHeadline: readCommitMessageHeadline(payload),
         url: readOptionalString(payload.html_url),
+        ...(commitsSinceReview === undefined ? {} : { commitsSinceReview }),
+      };
+    case "review_dismissed": {
+      const dismissedReview = readOptionalRecord(payload.dismissed_review);
+      const reviewId = readOptionalInteger(dismissedReview?.review_id);
+
+      return {
+        ...(actorAvatarUrl === null ? {} : { actorAvatarUrl }),
+        reviewId,
+        reviewerLogin: reviewId === null ? null : (historyContext.reviewerLoginsByReviewId.get(reviewId) ?? null),
+        reviewState: normalizeReviewState(readOptionalString(dismissedReview?.state)),
+        dismissalMessage: readOptionalString(dismissedReview?.dismissal_message),
       };
+    }
     default:
       return actorAvatarUrl === null ? undefined : { actorAvatarUrl };
   }
@@ -231,6 +352,8 @@ function buildNormalizedPayload(
 function mapNormalizedEventType(
   rawEvent: RawEventRecord,
   payload: Record<string, unknown>,
+  currentUserLogin: string,
+  activitySinceReview: ActivitySinceReview,
 ): string | undefined {
   switch (rawEvent.eventType) {
     case "issue_comment":
@@ -252,6 +375,14 @@ function mapNormalizedEventType(
       return "converted_to_draft";
     case "committed":
       return "commit_pushed";
+    case "review_dismissed":
+      return "review_dismissed";
+    case "head_ref_force_pushed":
+      // A force push only matters to someone whose review it may have invalidated.
+      return activitySinceReview.forcePushesSinceReview.has(rawEvent.id) ? "force_pushed_after_review" : undefined;
+    case "assigned":
+      // Only assignments of the current user are worth a notification.
+      return isCurrentUserAssignee(payload, currentUserLogin) ? "pr_assigned" : undefined;
     case "workflow_run":
       // CI outcomes derive from workflow history, not single raw events.
       return undefined;
@@ -260,6 +391,16 @@ function mapNormalizedEventType(
   }
 }
 
+function isCurrentUserAssignee(payload: Record<string, unknown>, currentUserLogin: string): boolean {
+  const assigneeLogin = readAssigneeLogin(payload);
+  return assigneeLogin !== null && normalizeLogin(assigneeLogin) === normalizeLogin(currentUserLogin);
+}
+
+function readAssigneeLogin(payload: Record<string, unknown>): string | null {
+  const login = readOptionalRecord(payload.assignee)?.login;
+  return typeof login === "string" && login.length > 0 ? login : null;
+}
+
 function mapReviewEventType(payload: Record<string, unknown>): string {
   const state = normalizeReviewState(readOptionalString(payload.state));
 
@@ -277,6 +418,7 @@ function mapReviewEventType(payload: Record<string, unknown>): string {
 function deriveMissingCiOutcomeEvents(input: {
   pullRequest: Pick<PullRequestRecord, "id" | "lastSeenHeadSha" | "authorLogin">;
   currentUserLogin: string;
+  immediateCiFailures: boolean;
   rawEvents: RawEventRecord[];
   normalizedEvents: NormalizedEventRecord[];
 }): DerivedCiOutcomeEvent[] {
@@ -349,7 +491,10 @@ function deriveMissingCiOutcomeEvents(input: {
         currentUserLogin: input.currentUserLogin,
         pullRequestAuthorLogin: input.pullRequest.authorLogin,
       }),
-      notificationTiming: resolveNotificationTiming(nextOutcome, actorClass),
+      notificationTiming:
+        input.immediateCiFailures && nextOutcome === "ci_failed"
+          ? "immediate"
+          : resolveNotificationTiming(nextOutcome, actorClass),
       payloadJson: serializeNormalizedPayload(
         workflowRun.rawEvent,
         buildCiOutcomePayload(workflowRun.snapshot),
@@ -472,7 +617,10 @@ function resolveNotificationTiming(
 ): NotificationTiming | null {
   if (
     actorClass === "human_other" &&
-    (eventType === "review_approved" || eventType === "review_changes_requested")
+    (eventType === "review_approved" ||
+      eventType === "review_changes_requested" ||
+      eventType === "pr_assigned" ||
+      eventType === "force_pushed_after_review")
   ) {
     return "immediate";
   }
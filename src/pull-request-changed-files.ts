@@ -0,0 +1,68 @@
+import { Octokit } from "octokit";
+
+import type { PullRequestCoordinates } from "./pull-request-snapshot.js";
+
+const GITHUB_API_HEADERS = {
+  "X-GitHub-Api-Version": "2022-11-28",
+};
+const GITHUB_PAGE_SIZE = 100;
+// GitHub stops listing files after 3000, so there is nothing past the 30th page.
+const MAX_CHANGED_FILE_PAGES = 30;
+
+export class PullRequestChangedFilesError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "PullRequestChangedFilesError";
+  }
+}
+
+// Lists the paths a pull request touches; renamed files contribute both their old and new path
+// so that moving a file out of an owned directory still counts as touching it.
+export async function fetchChangedFilePathsFromGitHub(
+  client: Octokit,
+  coordinates: PullRequestCoordinates,
+): Promise<string[]> {
+  const paths: string[] = [];
+
+  for (let page = 1; page <= MAX_CHANGED_FILE_PAGES; page += 1) {
+    const response = await client.request("GET /repos/{owner}/{repo}/pulls/{pull_number}/files", {
+      owner: coordinates.repositoryOwner,
+      repo: coordinates.repositoryName,
+      pull_number: coordinates.number,
+      per_page: GITHUB_PAGE_SIZE,
+      page,
+      headers: GITHUB_API_HEADERS,
+    });
+    const files = response.data as unknown;
+
+    if (!Array.isArray(files)) {
+      throw new PullRequestChangedFilesError("pull request files response must be an array");
+    }
+
+    for (const [index, file] of files.entries()) {
+      paths.push(...readChangedFilePaths(file, `files[${(page - 1) * GITHUB_PAGE_SIZE + index}]`));
+    }
+
+    if (files.length < GITHUB_PAGE_SIZE) {
+      break;
+    }
+  }
+
+  return paths;
+}
+
+function readChangedFilePaths(file: unknown, fieldName: string): string[] {
+  if (typeof file !== "object" || file === null || Array.isArray(file)) {
+    throw new PullRequestChangedFilesError(`${fieldName} must be an object`);
+  }
+
+  const value = file as Record<string, unknown>;
+
+  if (typeof value.filename !== "string") {
+    throw new PullRequestChangedFilesError(`${fieldName}.filename must be a string`);
+  }
+
+  return typeof value.previous_filename === "string"
+    ? [value.filename, value.previous_filename]
+    : [value.filename];
+}
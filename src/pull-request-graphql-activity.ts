@@ -0,0 +1,206 @@
+import type { GitHubGraphqlClient } from "./github.js";
+import type { PullRequestCoordinates } from "./pull-request-snapshot.js";
+
+export const PULL_REQUEST_ACTIVITY_APIS = ["rest", "graphql"] as const;
+
+// GraphQL connections return at most 100 nodes per request.
+const MAX_CONNECTION_SIZE = 100;
+const ACTOR_FIELDS = `
+  __typename
+  login
+  avatarUrl
+`;
+const PULL_REQUEST_ACTIVITY_QUERY = `
+  query ($owner: String!, $repo: String!, $number: Int!, $last: Int!) {
+    repository(owner: $owner, name: $repo) {
+      pullRequest(number: $number) {
+        comments(last: $last) {
+          nodes {
+            id
+            databaseId
+            url
+            body
+            createdAt
+            updatedAt
+            author { ${ACTOR_FIELDS} }
+          }
+        }
+        reviews(last: $last) {
+          nodes {
+            id
+            databaseId
+            url
+            body
+            state
+            submittedAt
+            commit { oid }
+            author { ${ACTOR_FIELDS} }
+            comments(first: ${MAX_CONNECTION_SIZE}) {
+              nodes {
+                id
+                databaseId
+                url
+                body
+                path
+                createdAt
+                updatedAt
+                replyTo { databaseId }
+                author { ${ACTOR_FIELDS} }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+`;
+
+export type PullRequestActivityApi = (typeof PULL_REQUEST_ACTIVITY_APIS)[number];
+
+// Shaped like the REST payloads, so ingestion, normalization, and stored history can't tell which
+// API the activity came from.
+export interface PullRequestGraphqlActivity {
+  issueComments: Record<string, unknown>[];
+  reviews: Record<string, unknown>[];
+  reviewComments: Record<string, unknown>[];
+}
+
+export class PullRequestGraphqlActivityError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "PullRequestGraphqlActivityError";
+  }
+}
+
+// Reads a pull request's newest issue comments, reviews, and review comments in one GraphQL
+// request instead of three paginated REST listings. Each list keeps at most `maxItems`, capped at
+// GraphQL's 100 nodes, oldest first like the REST pages.
+export async function fetchPullRequestActivityFromGraphql(
+  client: GitHubGraphqlClient,
+  coordinates: PullRequestCoordinates,
+  maxItems: number,
+): Promise<PullRequestGraphqlActivity> {
+  const data = await client.graphql(PULL_REQUEST_ACTIVITY_QUERY, {
+    owner: coordinates.repositoryOwner,
+    repo: coordinates.repositoryName,
+    number: coordinates.number,
+    last: Math.min(maxItems, MAX_CONNECTION_SIZE),
+  });
+  const pullRequest = readRecord(readRecord(readRecord(data)?.repository)?.pullRequest);
+
+  if (pullRequest === undefined) {
+    throw new PullRequestGraphqlActivityError("repository.pullRequest must be an object");
+  }
+
+  const reviewNodes = readNodes(pullRequest.reviews, "reviews");
+  const reviewComments = reviewNodes.flatMap((review, reviewIndex) =>
+    readNodes(review.comments, `reviews.nodes[${reviewIndex}].comments`).map((comment) =>
+      mapReviewComment(comment, review),
+    ),
+  );
+
+  return {
+    issueComments: readNodes(pullRequest.comments, "comments").map(mapIssueComment),
+    reviews: reviewNodes.map(mapReview),
+    reviewComments: reviewComments.slice(Math.max(0, reviewComments.length - maxItems)),
+  };
+}
+
+// Matches the REST listings' `since`, which keeps items updated at or after it.
+export function filterUpdatedSince(
+  items: readonly Record<string, unknown>[],
+  since: string | undefined,
+): Record<string, unknown>[] {
+  if (since === undefined) {
+    return [...items];
+  }
+
+  const sinceMs = Date.parse(since);
+
+  return items.filter((item) => {
+    const updatedAt = item.updated_at ?? item.created_at;
+    return typeof updatedAt === "string" && Date.parse(updatedAt) >= sinceMs;
+  });
+}
+
+function mapIssueComment(node: Record<string, unknown>): Record<string, unknown> {
+  return {
+    id: node.databaseId,
+    node_id: node.id,
+    html_url: node.url,
+    body: node.body,
+    created_at: node.createdAt,
+    updated_at: node.updatedAt,
+    user: mapActor(node.author),
+  };
+}
+
+function mapReview(node: Record<string, unknown>): Record<string, unknown> {
+  return {
+    id: node.databaseId,
+    node_id: node.id,
+    html_url: node.url,
+    body: node.body,
+    state: node.state,
+    submitted_at: node.submittedAt ?? null,
+    commit_id: readRecord(node.commit)?.oid ?? null,
+    user: mapActor(node.author),
+  };
+}
+
+function mapReviewComment(
+  node: Record<string, unknown>,
+  review: Record<string, unknown>,
+): Record<string, unknown> {
+  const inReplyToId = readRecord(node.replyTo)?.databaseId;
+
+  return {
+    id: node.databaseId,
+    node_id: node.id,
+    pull_request_review_id: review.databaseId,
+    html_url: node.url,
+    body: node.body,
+    path: node.path,
+    created_at: node.createdAt,
+    updated_at: node.updatedAt,
+    ...(typeof inReplyToId === "number" ? { in_reply_to_id: inReplyToId } : {}),
+    user: mapActor(node.author),
+  };
+}
+
+// REST names GitHub App accounts "name[bot]", which bot detection relies on; GraphQL drops the
+// suffix and reports the account type as the typename instead.
+function mapActor(value: unknown): Record<string, unknown> | null {
+  const actor = readRecord(value);
+
+  if (actor === undefined || typeof actor.login !== "string") {
+    return null;
+  }
+
+  const isBot = actor.__typename === "Bot";
+
+  return {
+    login: isBot && !actor.login.endsWith("[bot]") ? `${actor.login}[bot]` : actor.login,
+    avatar_url: actor.avatarUrl ?? null,
+    type: isBot ? "Bot" : "User",
+  };
+}
+
+function readNodes(connection: unknown, fieldName: string): Record<string, unknown>[] {
+  const nodes = readRecord(connection)?.nodes;
+
+  if (!Array.isArray(nodes)) {
+    throw new PullRequestGraphqlActivityError(`${fieldName}.nodes must be an array`);
+  }
+
+  return nodes.flatMap((node) => {
+    const record = readRecord(node);
+    return record === undefined ? [] : [record];
+  });
+}
+
+function readRecord(value: unknown): Record<string, unknown> | undefined {
+  return typeof value === "object" && value !== null && !Array.isArray(value)
+    ? (value as Record<string, unknown>)
+    : undefined;
+}
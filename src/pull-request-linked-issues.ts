@@ -0,0 +1,109 @@
+import type { GitHubGraphqlClient } from "./github.js";
+import type { PullRequestCoordinates } from "./pull-request-snapshot.js";
+
+// Only the first few closing references fit in a popup anyway.
+const MAX_LINKED_ISSUES = 10;
+const LINKED_ISSUES_QUERY = `
+  query ($owner: String!, $repo: String!, $number: Int!, $first: Int!) {
+    repository(owner: $owner, name: $repo) {
+      pullRequest(number: $number) {
+        closingIssuesReferences(first: $first) {
+          nodes {
+            number
+            title
+            repository {
+              name
+              owner {
+                login
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+`;
+
+export interface LinkedIssue {
+  repositoryOwner: string;
+  repositoryName: string;
+  number: number;
+  title: string;
+}
+
+export class PullRequestLinkedIssuesError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "PullRequestLinkedIssuesError";
+  }
+}
+
+// Resolves the issues a pull request will close ("Closes #123") through GraphQL
+// `closingIssuesReferences`, which the REST pull request payload does not expose.
+export async function fetchLinkedIssuesFromGitHub(
+  client: GitHubGraphqlClient,
+  coordinates: PullRequestCoordinates,
+): Promise<LinkedIssue[]> {
+  const data = await client.graphql(LINKED_ISSUES_QUERY, {
+    owner: coordinates.repositoryOwner,
+    repo: coordinates.repositoryName,
+    number: coordinates.number,
+    first: MAX_LINKED_ISSUES,
+  });
+  const pullRequest = readRecord(readRecord(readRecord(data)?.repository)?.pullRequest);
+  const nodes = readRecord(pullRequest?.closingIssuesReferences)?.nodes;
+
+  if (!Array.isArray(nodes)) {
+    throw new PullRequestLinkedIssuesError("closingIssuesReferences.nodes must be an array");
+  }
+
+  return nodes.map((node, index) => readLinkedIssueNode(node, `closingIssuesReferences.nodes[${index}]`));
+}
+
+export function formatLinkedIssuesText(
+  pullRequest: Pick<PullRequestCoordinates, "repositoryOwner" | "repositoryName">,
+  linkedIssues: readonly LinkedIssue[],
+): string | null {
+  if (linkedIssues.length === 0) {
+    return null;
+  }
+
+  const references = linkedIssues.map((issue) => {
+    const isSameRepository =
+      issue.repositoryOwner === pullRequest.repositoryOwner &&
+      issue.repositoryName === pullRequest.repositoryName;
+    const prefix = isSameRepository ? "" : `${issue.repositoryOwner}/${issue.repositoryName}`;
+
+    return `${prefix}#${issue.number} ${issue.title}`;
+  });
+
+  return `🔗 Closes ${references.join(", ")}`;
+}
+
+function readLinkedIssueNode(node: unknown, fieldName: string): LinkedIssue {
+  const value = readRecord(node);
+  const repository = readRecord(value?.repository);
+  const owner = readRecord(repository?.owner);
+
+  if (
+    typeof value?.number !== "number" ||
+    typeof value.title !== "string" ||
+    typeof repository?.name !== "string" ||
+    typeof owner?.login !== "string"
+  ) {
+    throw new PullRequestLinkedIssuesError(`${fieldName} is missing its number, title, or repository`);
+  }
+
+  return {
+    repositoryOwner: owner.login,
+    repositoryName: repository.name,
+    number: value.number,
+    title: value.title,
+  };
+}
+
+function readRecord(value: unknown): Record<string, unknown> | undefined {
+  return typeof value === "object" && value !== null && !Array.isArray(value)
+    ? (value as Record<string, unknown>)
+    : undefined;
+}
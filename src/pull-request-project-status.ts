@@ -0,0 +1,93 @@
+import type { GitHubGraphqlClient } from "./github.js";
+import type { PullRequestCoordinates } from "./pull-request-snapshot.js";
+
+const PROJECT_ITEMS_PAGE_SIZE = 20;
+const PROJECT_STATUS_QUERY = `
+  query ($owner: String!, $repo: String!, $number: Int!, $first: Int!, $field: String!) {
+    repository(owner: $owner, name: $repo) {
+      pullRequest(number: $number) {
+        projectItems(first: $first) {
+          nodes {
+            project {
+              number
+              owner {
+                ... on Organization {
+                  login
+                }
+                ... on User {
+                  login
+                }
+              }
+            }
+            fieldValueByName(name: $field) {
+              ... on ProjectV2ItemFieldSingleSelectValue {
+                name
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+`;
+
+export interface GitHubProjectConfig {
+  owner: string;
+  number: number;
+  statusField: string;
+}
+
+export class PullRequestProjectStatusError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "PullRequestProjectStatusError";
+  }
+}
+
+// Reads the pull request's single-select status ("In review", "Blocked") on the configured
+// Projects (v2) board; null when the pull request is not on that board or has no status yet.
+export async function fetchProjectStatusFromGitHub(
+  client: GitHubGraphqlClient,
+  coordinates: PullRequestCoordinates,
+  project: GitHubProjectConfig,
+): Promise<string | null> {
+  const data = await client.graphql(PROJECT_STATUS_QUERY, {
+    owner: coordinates.repositoryOwner,
+    repo: coordinates.repositoryName,
+    number: coordinates.number,
+    first: PROJECT_ITEMS_PAGE_SIZE,
+    field: project.statusField,
+  });
+  const pullRequest = readRecord(readRecord(readRecord(data)?.repository)?.pullRequest);
+  const nodes = readRecord(pullRequest?.projectItems)?.nodes;
+
+  if (!Array.isArray(nodes)) {
+    throw new PullRequestProjectStatusError("projectItems.nodes must be an array");
+  }
+
+  for (const node of nodes) {
+    const item = readRecord(node);
+    const itemProject = readRecord(item?.project);
+    const ownerLogin = readRecord(itemProject?.owner)?.login;
+
+    if (
+      itemProject?.number !== project.number ||
+      typeof ownerLogin !== "string" ||
+      ownerLogin.toLowerCase() !== project.owner.toLowerCase()
+    ) {
+      continue;
+    }
+
+    const status = readRecord(item?.fieldValueByName)?.name;
+
+    return typeof status === "string" && status.length > 0 ? status : null;
+  }
+
+  return null;
+}
+
+function readRecord(value: unknown): Record<string, unknown> | undefined {
+  return typeof value === "object" && value !== null && !Array.isArray(value)
+    ? (value as Record<string, unknown>)
+    : undefined;
+}
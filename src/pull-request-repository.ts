@@ -1,5 +1,7 @@
 import { DatabaseSync } from "node:sqlite";
 
+import type { LinkedIssue } from "./pull-request-linked-issues.js";
+
 const DEFAULT_TRACKING_REASON = "auto";
 
 export interface PullRequestRecord {
@@ -22,10 +24,20 @@ export interface PullRequestRecord {
   mergedAt: string | null;
   graceUntil: string | null;
   lastSeenHeadSha: string | null;
+  headBranch: string | null;
+  openedAt: string | null;
+  lastActivityAt: string | null;
+  additions: number | null;
+  deletions: number | null;
+  linkedIssues: LinkedIssue[];
+  projectStatus: string | null;
+  changedFilePaths: string[];
   baseBranch: string | null;
   mergeable: boolean | null;
   mergeableState: string | null;
   requestedReviewTeamSlugs: string[];
+  autoMergeEnabled: boolean | null;
+  mergedByLogin: string | null;
   createdAt: string;
   updatedAt: string;
 }
@@ -52,10 +64,20 @@ export interface UpsertPullRequestInput {
   mergedAt?: string | null;
   graceUntil?: string | null;
   lastSeenHeadSha?: string | null;
+  headBranch?: string | null;
+  openedAt?: string | null;
+  lastActivityAt?: string | null;
+  additions?: number | null;
+  deletions?: number | null;
+  linkedIssues?: LinkedIssue[];
+  projectStatus?: string | null;
+  changedFilePaths?: string[];
   baseBranch?: string | null;
   mergeable?: boolean | null;
   mergeableState?: string | null;
   requestedReviewTeamSlugs?: string[];
+  autoMergeEnabled?: boolean | null;
+  mergedByLogin?: string | null;
   tracking?: PullRequestTrackingState;
 }
 
@@ -118,6 +140,16 @@ export class PullRequestRepository {
                     mergeable = ?,
                     mergeable_state = ?,
                     requested_review_team_slugs_json = ?,
+                    auto_merge_enabled = ?,
+                    merged_by_login = ?,
+                    head_branch = ?,
+                    opened_at = ?,
+                    last_activity_at = ?,
+                    additions = ?,
+                    deletions = ?,
+                    linked_issues_json = ?,
+                    project_status = ?,
+                    changed_file_paths_json = ?,
                     updated_at = CURRENT_TIMESTAMP
                 WHERE id = ?
               `,
@@ -147,6 +179,18 @@ export class PullRequestRepository {
               writeStringArray(
                 resolveStringArrayField(input.requestedReviewTeamSlugs, existing.requestedReviewTeamSlugs),
               ),
+              writeNullableBoolean(
+                resolveNullableBooleanField(input.autoMergeEnabled, existing.autoMergeEnabled),
+              ),
+              resolveNullableField(input.mergedByLogin, existing.mergedByLogin),
+              resolveNullableField(input.headBranch, existing.headBranch),
+              resolveNullableField(input.openedAt, existing.openedAt),
+              resolveNullableField(input.lastActivityAt, existing.lastActivityAt),
+              resolveNullableField(input.additions, existing.additions),
+              resolveNullableField(input.deletions, existing.deletions),
+              JSON.stringify(input.linkedIssues ?? existing.linkedIssues),
+              resolveNullableField(input.projectStatus, existing.projectStatus),
+              writeStringArray(resolveStringArrayField(input.changedFilePaths, existing.changedFilePaths)),
               existing.id,
             );
 
@@ -178,8 +222,18 @@ export class PullRequestRepository {
                 base_branch,
                 mergeable,
                 mergeable_state,
-                requested_review_team_slugs_json
-              ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                requested_review_team_slugs_json,
+                auto_merge_enabled,
+                merged_by_login,
+                head_branch,
+                opened_at,
+                last_activity_at,
+                additions,
+                deletions,
+                linked_issues_json,
+                project_status,
+                changed_file_paths_json
+              ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             `,
           )
           .run(
@@ -205,6 +259,16 @@ export class PullRequestRepository {
             writeNullableBoolean(input.mergeable ?? null),
             input.mergeableState ?? null,
             writeStringArray(input.requestedReviewTeamSlugs ?? []),
+            writeNullableBoolean(input.autoMergeEnabled ?? null),
+            input.mergedByLogin ?? null,
+            input.headBranch ?? null,
+            input.openedAt ?? null,
+            input.lastActivityAt ?? null,
+            input.additions ?? null,
+            input.deletions ?? null,
+            JSON.stringify(input.linkedIssues ?? []),
+            input.projectStatus ?? null,
+            writeStringArray(input.changedFilePaths ?? []),
           );
 
         return this.requirePullRequestById(readInteger(result.lastInsertRowid, "lastInsertRowid"));
@@ -412,6 +476,17 @@ function mapPullRequestRow(row: unknown): PullRequestRecord {
       value.last_seen_head_sha,
       "PullRequest.last_seen_head_sha",
     ),
+    headBranch: readNullableString(value.head_branch, "PullRequest.head_branch"),
+    openedAt: readNullableString(value.opened_at, "PullRequest.opened_at"),
+    lastActivityAt: readNullableString(value.last_activity_at, "PullRequest.last_activity_at"),
+    additions: readNullableInteger(value.additions, "PullRequest.additions"),
+    deletions: readNullableInteger(value.deletions, "PullRequest.deletions"),
+    linkedIssues: readLinkedIssues(value.linked_issues_json, "PullRequest.linked_issues_json"),
+    projectStatus: readNullableString(value.project_status, "PullRequest.project_status"),
+    changedFilePaths: readStringArray(
+      value.changed_file_paths_json,
+      "PullRequest.changed_file_paths_json",
+    ),
     baseBranch: readNullableString(value.base_branch, "PullRequest.base_branch"),
     mergeable: readNullableBoolean(value.mergeable, "PullRequest.mergeable"),
     mergeableState: readNullableString(value.mergeable_state, "PullRequest.mergeable_state"),
@@ -419,6 +494,8 @@ function mapPullRequestRow(row: unknown): PullRequestRecord {
       value.requested_review_team_slugs_json,
       "PullRequest.requested_review_team_slugs_json",
     ),
+    autoMergeEnabled: readNullableBoolean(value.auto_merge_enabled, "PullRequest.auto_merge_enabled"),
+    mergedByLogin: readNullableString(value.merged_by_login, "PullRequest.merged_by_login"),
     createdAt: readString(value.created_at, "PullRequest.created_at"),
     updatedAt: readString(value.updated_at, "PullRequest.updated_at"),
   };
@@ -440,10 +517,7 @@ function readTrackingState(pullRequest: PullRequestRecord | undefined): PullRequ
   };
 }
 
-function resolveNullableField(
-  nextValue: string | null | undefined,
-  existingValue: string | null,
-): string | null {
+function resolveNullableField<T>(nextValue: T | null | undefined, existingValue: T | null): T | null {
   return nextValue === undefined ? existingValue : nextValue;
 }
 
@@ -503,6 +577,14 @@ function readInteger(value: unknown, fieldName: string): number {
   throw new PullRequestRepositoryError(`${fieldName} must be a safe integer`);
 }
 
+function readNullableInteger(value: unknown, fieldName: string): number | null {
+  if (value === null) {
+    return null;
+  }
+
+  return readInteger(value, fieldName);
+}
+
 function readString(value: unknown, fieldName: string): string {
   if (typeof value !== "string") {
     throw new PullRequestRepositoryError(`${fieldName} must be a string`);
@@ -560,6 +642,40 @@ function readStringArray(value: unknown, fieldName: string): string[] {
   return [...parsed];
 }
 
+function readLinkedIssues(value: unknown, fieldName: string): LinkedIssue[] {
+  const raw = readString(value, fieldName);
+  let parsed: unknown;
+
+  try {
+    parsed = JSON.parse(raw);
+  } catch (error) {
+    throw new PullRequestRepositoryError(
+      `${fieldName} must be valid JSON: ${getErrorMessage(error)}`,
+    );
+  }
+
+  if (!Array.isArray(parsed) || !parsed.every((entry) => isLinkedIssue(entry))) {
+    throw new PullRequestRepositoryError(`${fieldName} must be a JSON array of linked issues`);
+  }
+
+  return parsed;
+}
+
+function isLinkedIssue(value: unknown): value is LinkedIssue {
+  if (typeof value !== "object" || value === null) {
+    return false;
+  }
+
+  const entry = value as Record<string, unknown>;
+
+  return (
+    typeof entry.repositoryOwner === "string" &&
+    typeof entry.repositoryName === "string" &&
+    typeof entry.number === "number" &&
+    typeof entry.title === "string"
+  );
+}
+
 function writeBoolean(value: boolean): number {
   return value ? 1 : 0;
 }
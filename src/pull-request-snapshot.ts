@@ -18,9 +18,18 @@ export interface PullRequestSnapshot extends PullRequestCoordinates {
   mergedAt: string | null;
   lastSeenHeadSha: string | null;
   baseBranch: string | null;
+  headBranch?: string | null;
+  // Not persisted; only used to give first-time review request popups some context.
+  bodyText?: string | null;
   mergeable: boolean | null;
   mergeableState: string | null;
   requestedReviewTeamSlugs: string[];
+  autoMergeEnabled?: boolean;
+  mergedByLogin?: string | null;
+  openedAt?: string | null;
+  lastActivityAt?: string | null;
+  additions?: number | null;
+  deletions?: number | null;
 }
 
 export function mapPullRequestSnapshot(
@@ -59,6 +68,8 @@ export function mapPullRequestSnapshot(
     mergedAt: readNullableString(value.merged_at, "pull request response.merged_at", createError),
     lastSeenHeadSha: readNullableString(head.sha, "pull request response.head.sha", createError),
     baseBranch: readNullableString(base.ref, "pull request response.base.ref", createError),
+    headBranch: readOptionalString(head.ref, "pull request response.head.ref", createError),
+    bodyText: readOptionalString(value.body, "pull request response.body", createError),
     mergeable: readNullableBoolean(value.mergeable, "pull request response.mergeable", createError),
     mergeableState: readNullableString(
       value.mergeable_state,
@@ -70,6 +81,12 @@ export function mapPullRequestSnapshot(
       "pull request response.requested_teams",
       createError,
     ),
+    autoMergeEnabled: value.auto_merge !== undefined && value.auto_merge !== null,
+    mergedByLogin: readOptionalLogin(value.merged_by, "pull request response.merged_by", createError),
+    openedAt: readOptionalString(value.created_at, "pull request response.created_at", createError),
+    lastActivityAt: readOptionalString(value.updated_at, "pull request response.updated_at", createError),
+    additions: readOptionalInteger(value.additions, "pull request response.additions", createError),
+    deletions: readOptionalInteger(value.deletions, "pull request response.deletions", createError),
   };
 }
 
@@ -92,9 +109,16 @@ export function createPullRequestUpsertInput(
     mergedAt: snapshot.mergedAt,
     lastSeenHeadSha: snapshot.lastSeenHeadSha,
     baseBranch: snapshot.baseBranch,
+    ...(snapshot.headBranch === undefined ? {} : { headBranch: snapshot.headBranch }),
     mergeable: snapshot.mergeable,
     mergeableState: snapshot.mergeableState,
     requestedReviewTeamSlugs: snapshot.requestedReviewTeamSlugs,
+    ...(snapshot.autoMergeEnabled === undefined ? {} : { autoMergeEnabled: snapshot.autoMergeEnabled }),
+    ...(snapshot.mergedByLogin === undefined ? {} : { mergedByLogin: snapshot.mergedByLogin }),
+    ...(snapshot.openedAt === undefined ? {} : { openedAt: snapshot.openedAt }),
+    ...(snapshot.lastActivityAt === undefined ? {} : { lastActivityAt: snapshot.lastActivityAt }),
+    ...(snapshot.additions === undefined ? {} : { additions: snapshot.additions }),
+    ...(snapshot.deletions === undefined ? {} : { deletions: snapshot.deletions }),
     ...(overrides.lastSeenAt === undefined ? {} : { lastSeenAt: overrides.lastSeenAt }),
     ...(overrides.graceUntil === undefined ? {} : { graceUntil: overrides.graceUntil }),
     ...(overrides.tracking === undefined ? {} : { tracking: overrides.tracking }),
@@ -137,6 +161,14 @@ function readInteger(
   throw createError(`${fieldName} must be a safe integer`);
 }
 
+function readOptionalInteger(
+  value: unknown,
+  fieldName: string,
+  createError: (message: string) => Error,
+): number | null {
+  return value === undefined || value === null ? null : readInteger(value, fieldName, createError);
+}
+
 function readString(
   value: unknown,
   fieldName: string,
@@ -161,6 +193,26 @@ function readNullableString(
   return readString(value, fieldName, createError);
 }
 
+function readOptionalString(
+  value: unknown,
+  fieldName: string,
+  createError: (message: string) => Error,
+): string | null {
+  return value === undefined ? null : readNullableString(value, fieldName, createError);
+}
+
+function readOptionalLogin(
+  value: unknown,
+  fieldName: string,
+  createError: (message: string) => Error,
+): string | null {
+  if (value === undefined || value === null) {
+    return null;
+  }
+
+  return readString(requireRecord(value, fieldName, createError).login, `${fieldName}.login`, createError);
+}
+
 function readBoolean(
   value: unknown,
   fieldName: string,
@@ -0,0 +1,66 @@
+import type { LinuxNotification } from "./linux-notification-adapter.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+
+const DEFAULT_RECENT_NOTIFICATION_LIMIT = 10;
+
+export interface RecentNotification {
+  title: string;
+  clickUrl: string | null;
+  shownAt: string;
+}
+
+type RecentNotificationListener = (notifications: readonly RecentNotification[]) => void;
+
+export class RecentNotificationList {
+  private readonly limit: number;
+  private notifications: RecentNotification[] = [];
+  private readonly listeners = new Set<RecentNotificationListener>();
+
+  constructor(limit = DEFAULT_RECENT_NOTIFICATION_LIMIT) {
+    this.limit = limit;
+  }
+
+  record(notification: Pick<LinuxNotification, "title" | "clickUrl">, shownAt = new Date()): void {
+    this.notifications = [
+      {
+        title: notification.title,
+        clickUrl: notification.clickUrl ?? null,
+        shownAt: shownAt.toISOString(),
+      },
+      ...this.notifications,
+    ].slice(0, this.limit);
+
+    for (const listener of this.listeners) {
+      listener(this.notifications);
+    }
+  }
+
+  list(): readonly RecentNotification[] {
+    return this.notifications;
+  }
+
+  latest(): RecentNotification | undefined {
+    return this.notifications[0];
+  }
+
+  subscribe(listener: RecentNotificationListener): () => void {
+    this.listeners.add(listener);
+
+    return () => {
+      this.listeners.delete(listener);
+    };
+  }
+}
+
+export function withRecentNotificationTracking(
+  notificationDispatcher: NotificationDispatcher,
+  recentNotifications: Pick<RecentNotificationList, "record">,
+): NotificationDispatcher {
+  return {
+    async dispatchNotification(notification) {
+      const result = await notificationDispatcher.dispatchNotification(notification);
+      recentNotifications.record(notification);
+      return result;
+    },
+  };
+}
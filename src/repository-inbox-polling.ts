@@ -0,0 +1,247 @@
+import { DatabaseSync } from "node:sqlite";
+
+import { Octokit } from "octokit";
+
+import {
+  fetchPullRequestDetailFromGitHub,
+  GITHUB_INBOX_TRACKING_REASON,
+  type DiscoveredPullRequest,
+} from "./authored-pull-request-discovery.js";
+import {
+  readUnreadPullRequestNotifications,
+  type GitHubInboxSettings,
+  type UnreadPullRequestNotifications,
+} from "./github-notification-read-state.js";
+import { getLogger } from "./logger.js";
+import type { PollerBackoff } from "./poller-state.js";
+import {
+  createPullRequestUpsertInput,
+  type PullRequestCoordinates,
+} from "./pull-request-snapshot.js";
+import { PullRequestRepository } from "./pull-request-repository.js";
+
+export interface PollRepositoryInboxesOptions {
+  repositories: readonly string[];
+  inbox: GitHubInboxSettings;
+  pullRequestRepository?: PullRequestRepository;
+  listRepositoryPullRequests?: (
+    repository: string,
+    inbox: GitHubInboxSettings,
+    ifModifiedSince: string | undefined,
+  ) => Promise<UnreadPullRequestNotifications | "not_modified">;
+  fetchPullRequestDetail?: (coordinates: PullRequestCoordinates) => Promise<DiscoveredPullRequest>;
+  // Carried from one cycle to the next, so unchanged repositories cost a 304 each.
+  cache?: RepositoryInboxCache;
+  observedAt?: string;
+}
+
+export interface RepositoryInboxCache {
+  lastModifiedByRepository: Map<string, string>;
+  // The longest X-Poll-Interval any repository answered with last cycle.
+  pollIntervalMs: number | undefined;
+}
+
+export interface PollRepositoryInboxesResult {
+  trackedCount: number;
+}
+
+export interface StartRecurringRepositoryInboxPollingOptions extends Omit<
+  PollRepositoryInboxesOptions,
+  "cache"
+> {
+  intervalMs: number;
+  backoff?: PollerBackoff;
+  // Called after a cycle that started tracking pull requests, e.g. to poll them right away.
+  onTracked?: () => void;
+}
+
+export interface RecurringRepositoryInboxPollingHandle {
+  // Skips cycles until resumed, e.g. while GitHub rejects the token.
+  pause(): void;
+  resume(): void;
+  stop(): void;
+}
+
+export class RepositoryInboxError extends Error {
+  constructor(message: string, options?: ErrorOptions) {
+    super(message, options);
+    this.name = "RepositoryInboxError";
+  }
+}
+
+// Reads each configured repository's notifications through the repository-scoped endpoint, which
+// is cheap enough to poll far more often than discovery's searches. Pull requests it turns up that
+// aren't stored yet start being tracked; the ones already stored are left to the tracked poller.
+export async function pollRepositoryInboxes(
+  database: DatabaseSync,
+  client: Octokit,
+  options: PollRepositoryInboxesOptions,
+): Promise<PollRepositoryInboxesResult> {
+  const pullRequestRepository = options.pullRequestRepository ?? new PullRequestRepository(database);
+  const listRepositoryPullRequests =
+    options.listRepositoryPullRequests ??
+    ((repository: string, inbox: GitHubInboxSettings, ifModifiedSince: string | undefined) =>
+      readUnreadPullRequestNotifications(client, inbox, repository, ifModifiedSince));
+  const fetchPullRequestDetail =
+    options.fetchPullRequestDetail ??
+    ((coordinates: PullRequestCoordinates) =>
+      fetchPullRequestDetailFromGitHub(client, coordinates));
+  const cache: RepositoryInboxCache = options.cache ?? {
+    lastModifiedByRepository: new Map(),
+    pollIntervalMs: undefined,
+  };
+  const observedAt = options.observedAt ?? new Date().toISOString();
+  let trackedCount = 0;
+  let pollIntervalMs: number | undefined;
+
+  for (const repository of options.repositories) {
+    const result = await listRepositoryPullRequests(
+      repository,
+      options.inbox,
+      cache.lastModifiedByRepository.get(repository),
+    );
+
+    if (result === "not_modified") {
+      continue;
+    }
+
+    if (result.pollIntervalMs !== undefined) {
+      pollIntervalMs = Math.max(pollIntervalMs ?? 0, result.pollIntervalMs);
+    }
+
+    for (const coordinates of result.coordinates) {
+      const existingPullRequest = pullRequestRepository.getPullRequestByRepositoryCoordinates(
+        coordinates.repositoryOwner,
+        coordinates.repositoryName,
+        coordinates.number,
+      );
+
+      if (existingPullRequest !== undefined) {
+        continue;
+      }
+
+      pullRequestRepository.upsertPullRequest(
+        createPullRequestUpsertInput(await fetchPullRequestDetail(coordinates), {
+          lastSeenAt: observedAt,
+          graceUntil: null,
+          tracking: {
+            isTracked: true,
+            trackingReason: GITHUB_INBOX_TRACKING_REASON,
+            isStickyUntracked: false,
+          },
+        }),
+      );
+      trackedCount += 1;
+    }
+
+    // Only remembered once every pull request in the list is stored, so a failed fetch isn't
+    // skipped next cycle with a 304.
+    if (result.lastModified === undefined) {
+      cache.lastModifiedByRepository.delete(repository);
+    } else {
+      cache.lastModifiedByRepository.set(repository, result.lastModified);
+    }
+  }
+
+  cache.pollIntervalMs = pollIntervalMs ?? cache.pollIntervalMs;
+
+  return { trackedCount };
+}
+
+export function startRecurringRepositoryInboxPolling(
+  database: DatabaseSync,
+  client: Octokit,
+  options: StartRecurringRepositoryInboxPollingOptions,
+): RecurringRepositoryInboxPollingHandle {
+  const { intervalMs, backoff, onTracked, ...pollOptions } = options;
+
+  if (!Number.isFinite(intervalMs) || intervalMs <= 0) {
+    throw new RepositoryInboxError(
+      "Recurring repository inbox polling interval must be greater than zero",
+    );
+  }
+
+  const cache: RepositoryInboxCache = {
+    lastModifiedByRepository: new Map(),
+    pollIntervalMs: undefined,
+  };
+  let isStopped = false;
+  let isPaused = false;
+  let timer: ReturnType<typeof setTimeout> | undefined;
+
+  void runPollCycle();
+
+  return {
+    pause(): void {
+      isPaused = true;
+    },
+    resume(): void {
+      isPaused = false;
+    },
+    stop(): void {
+      if (isStopped) {
+        return;
+      }
+
+      isStopped = true;
+      clearTimeout(timer);
+    },
+  };
+
+  // GitHub's X-Poll-Interval can ask for a longer wait than the configured interval.
+  function scheduleNextCycle(): void {
+    timer = setTimeout(
+      () => {
+        void runPollCycle();
+      },
+      Math.max(intervalMs, cache.pollIntervalMs ?? 0),
+    );
+    timer.unref?.();
+  }
+
+  async function runPollCycle(): Promise<void> {
+    if (isStopped) {
+      return;
+    }
+
+    if (isPaused) {
+      scheduleNextCycle();
+      return;
+    }
+
+    const deferral = backoff?.readDeferral() ?? null;
+
+    if (deferral !== null) {
+      getLogger().debug("Deferred repository inbox polling cycle", { ...deferral });
+      scheduleNextCycle();
+      return;
+    }
+
+    try {
+      const result = await pollRepositoryInboxes(database, client, { ...pollOptions, cache });
+
+      if (result.trackedCount > 0) {
+        getLogger().info("Tracked pull requests from repository notifications", { ...result });
+        onTracked?.();
+      }
+
+      backoff?.recordCycle(null);
+    } catch (error) {
+      const inboxError = new RepositoryInboxError(
+        `Failed to poll repository notifications: ${getErrorMessage(error)}`,
+        { cause: error },
+      );
+
+      getLogger().error("Octopulse repository inbox polling failed", { error: inboxError });
+      backoff?.recordCycle(inboxError);
+    } finally {
+      if (!isStopped) {
+        scheduleNextCycle();
+      }
+    }
+  }
+}
+
+function getErrorMessage(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
@@ -0,0 +1,47 @@
+import type { LinuxNotification, NotificationUrgency } from "./linux-notification-adapter.js";
+import type { PullRequestRecord } from "./pull-request-repository.js";
+
+// Notification settings for one repository ("acme/api") or every repository of an owner ("acme").
+export interface RepositoryOverride {
+  repository: string;
+  // Keeps the repository's notifications in history and the inbox without showing popups.
+  mute?: boolean;
+  // null plays no sound.
+  soundFile?: string | null;
+  urgency?: NotificationUrgency;
+}
+
+// A repository's own override wins over its owner's, whatever order they're listed in.
+export function resolveRepositoryOverride(
+  pullRequest: Pick<PullRequestRecord, "repositoryOwner" | "repositoryName">,
+  overrides: readonly RepositoryOverride[],
+): RepositoryOverride | null {
+  const owner = pullRequest.repositoryOwner.toLowerCase();
+  const repository = `${owner}/${pullRequest.repositoryName.toLowerCase()}`;
+
+  return (
+    overrides.find((override) => override.repository.toLowerCase() === repository) ??
+    overrides.find((override) => override.repository.toLowerCase() === owner) ??
+    null
+  );
+}
+
+export function applyRepositoryOverride(
+  notification: LinuxNotification,
+  override: RepositoryOverride,
+): LinuxNotification {
+  const { soundFile: _soundFile, ...silentNotification } = notification;
+  const withSound =
+    override.soundFile === undefined
+      ? notification
+      : override.soundFile === null
+        ? silentNotification
+        : { ...notification, soundFile: override.soundFile };
+
+  return {
+    ...withSound,
+    ...(override.urgency === undefined
+      ? {}
+      : { urgency: override.urgency, sticky: override.urgency === "critical" }),
+  };
+}
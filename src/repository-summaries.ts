@@ -0,0 +1,150 @@
+import { parsePullRequestHtmlUrl } from "./github-notification-read-state.js";
+import type { LinuxNotification, NotificationUrgency } from "./linux-notification-adapter.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+
+const MAX_SUMMARY_LINES = 5;
+const URGENCY_ORDER: readonly NotificationUrgency[] = ["low", "normal", "critical"];
+// Outcomes worth calling out in a summary title, in the order they are listed.
+const SUMMARY_OUTCOMES: ReadonlyArray<readonly [eventType: string, phrase: string]> = [
+  ["pr_merged", "merged"],
+  ["review_approved", "approved"],
+  ["review_changes_requested", "changes requested"],
+  ["ci_failed", "CI failed"],
+  ["review_requested", "review requested"],
+];
+
+// Holds a poll cycle's popups and, on flush, shows one summary per repository instead.
+export interface RepositorySummaryDispatcher extends NotificationDispatcher {
+  flush(): Promise<void>;
+}
+
+export function collectRepositorySummaries(
+  notificationDispatcher: NotificationDispatcher,
+): RepositorySummaryDispatcher {
+  const notificationsByRepository = new Map<string, LinuxNotification[]>();
+
+  return {
+    async dispatchNotification(notification) {
+      const repository = readRepository(notification);
+
+      // Popups that don't belong to a pull request have nothing to be grouped with.
+      if (repository === null) {
+        return notificationDispatcher.dispatchNotification(notification);
+      }
+
+      notificationsByRepository.set(repository, [
+        ...(notificationsByRepository.get(repository) ?? []),
+        notification,
+      ]);
+      return { queued: true };
+    },
+    async flush(): Promise<void> {
+      const groups = [...notificationsByRepository.entries()];
+
+      notificationsByRepository.clear();
+
+      for (const [repository, notifications] of groups) {
+        const [onlyNotification] = notifications;
+
+        await notificationDispatcher.dispatchNotification(
+          notifications.length === 1 && onlyNotification !== undefined
+            ? onlyNotification
+            : buildRepositorySummaryNotification(repository, notifications),
+        );
+      }
+    },
+  };
+}
+
+export function buildRepositorySummaryNotification(
+  repository: string,
+  notifications: readonly LinuxNotification[],
+): LinuxNotification {
+  const pullRequestCount = new Set(notifications.map(readPullRequestNumber)).size;
+  const outcomes = SUMMARY_OUTCOMES.flatMap(([eventType, phrase]) => {
+    const count = new Set(
+      notifications
+        .filter((notification) => notification.eventTypes?.includes(eventType))
+        .map(readPullRequestNumber),
+    ).size;
+
+    return count === 0 ? [] : [`${count} ${phrase}`];
+  });
+  const lines = notifications.slice(0, MAX_SUMMARY_LINES).map((notification) =>
+    [notification.title, notification.body.split("\n")[0]].filter(Boolean).join(" — "),
+  );
+  const hiddenCount = notifications.length - lines.length;
+  const firstClickUrl = notifications.find((notification) => notification.clickUrl)?.clickUrl;
+  const soundFile = notifications.find((notification) => notification.soundFile !== undefined)?.soundFile;
+  const urgencies = notifications.flatMap((notification) =>
+    notification.urgency === undefined ? [] : [URGENCY_ORDER.indexOf(notification.urgency)],
+  );
+  const urgency = urgencies.length === 0 ? undefined : URGENCY_ORDER[Math.max(...urgencies)];
+
+  if (hiddenCount > 0) {
+    lines.push(`…and ${hiddenCount} more`);
+  }
+
+  return {
+    title: [
+      `${repository}: ${pullRequestCount} ${pullRequestCount === 1 ? "PR" : "PRs"} updated`,
+      ...outcomes,
+    ].join(", "),
+    body: lines.join("\n"),
+    clickUrl:
+      pullRequestCount === 1 && firstClickUrl
+        ? firstClickUrl
+        : `${readGitHubOrigin(firstClickUrl)}/${repository}/pulls`,
+    expandedHtml: renderRepositorySummaryDocument(repository, notifications),
+    eventTypes: [...new Set(notifications.flatMap((notification) => notification.eventTypes ?? []))],
+    ...(soundFile === undefined ? {} : { soundFile }),
+    ...(urgency === undefined ? {} : { urgency }),
+    sticky: notifications.some((notification) => notification.sticky === true),
+  };
+}
+
+function renderRepositorySummaryDocument(
+  repository: string,
+  notifications: readonly LinuxNotification[],
+): string {
+  const entries = notifications.map((notification) => {
+    const title = escapeHtml(notification.title);
+    const heading = notification.clickUrl
+      ? `<a href="${escapeHtml(notification.clickUrl)}">${title}</a>`
+      : title;
+
+    return `<li><h2>${heading}</h2><p>${escapeHtml(notification.body).replaceAll("\n", "<br>")}</p></li>`;
+  });
+
+  return [
+    "<!doctype html>",
+    '<html lang="en">',
+    `<head><meta charset="utf-8"><title>${escapeHtml(repository)} activity</title></head>`,
+    `<body><h1>${escapeHtml(repository)}</h1><ul>${entries.join("")}</ul></body>`,
+    "</html>",
+  ].join("\n");
+}
+
+function readRepository(notification: LinuxNotification): string | null {
+  const coordinates = notification.clickUrl ? parsePullRequestHtmlUrl(notification.clickUrl) : null;
+
+  return coordinates === null ? null : `${coordinates.repositoryOwner}/${coordinates.repositoryName}`;
+}
+
+// Summaries only hold pull request links, whose host says which GitHub instance they came from.
+function readGitHubOrigin(clickUrl: string | undefined): string {
+  return clickUrl ? new URL(clickUrl).origin : "https://github.com";
+}
+
+function readPullRequestNumber(notification: LinuxNotification): number | null {
+  return notification.clickUrl ? (parsePullRequestHtmlUrl(notification.clickUrl)?.number ?? null) : null;
+}
+
+function escapeHtml(value: string): string {
+  return value
+    .replaceAll("&", "&amp;")
+    .replaceAll("<", "&lt;")
+    .replaceAll(">", "&gt;")
+    .replaceAll('"', "&quot;")
+    .replaceAll("'", "&#39;");
+}
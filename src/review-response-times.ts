@@ -0,0 +1,166 @@
+import { DatabaseSync } from "node:sqlite";
+
+const OWN_REVIEW_EVENT_TYPES = [
+  "review_approved",
+  "review_changes_requested",
+  "review_submitted",
+  "review_inline_comment",
+] as const;
+
+export interface ReviewResponse {
+  repository: string;
+  pullRequestNumber: number;
+  requestedAt: string;
+  openedAt: string | null;
+  reviewedAt: string | null;
+}
+
+export interface RepositoryResponseTimes {
+  repository: string;
+  requestCount: number;
+  respondedCount: number;
+  medianResponseMs: number | null;
+}
+
+export interface ListReviewResponsesOptions {
+  since?: Date;
+}
+
+// A review request counts as answered once its notification was opened or I reviewed the PR,
+// whichever happened first.
+export function listReviewResponses(
+  database: DatabaseSync,
+  options: ListReviewResponsesOptions = {},
+): ReviewResponse[] {
+  const rows = database
+    .prepare(
+      [
+        "SELECT",
+        "  request.repository_owner,",
+        "  request.repository_name,",
+        "  request.number,",
+        "  request.requested_at,",
+        "  request.opened_at,",
+        "  (",
+        "    SELECT MIN(own_event.occurred_at)",
+        "    FROM NormalizedEvent own_event",
+        "    WHERE own_event.pull_request_id = request.pull_request_id",
+        "      AND own_event.actor_class = 'self'",
+        `      AND own_event.event_type IN (${OWN_REVIEW_EVENT_TYPES.map(() => "?").join(", ")})`,
+        "      AND julianday(own_event.occurred_at) >= julianday(request.requested_at)",
+        "  ) AS reviewed_at",
+        "FROM (",
+        "  SELECT",
+        "    notification_record.id,",
+        "    notification_record.pull_request_id,",
+        "    notification_record.read_at AS opened_at,",
+        "    pull_request.repository_owner,",
+        "    pull_request.repository_name,",
+        "    pull_request.number,",
+        "    MIN(normalized_event.occurred_at) AS requested_at",
+        "  FROM NotificationRecord notification_record",
+        "  INNER JOIN PullRequest pull_request",
+        "    ON pull_request.id = notification_record.pull_request_id",
+        "  INNER JOIN NormalizedEvent normalized_event",
+        "    ON normalized_event.id = notification_record.normalized_event_id",
+        "      OR normalized_event.event_bundle_id = notification_record.event_bundle_id",
+        "  WHERE normalized_event.event_type = 'review_requested'",
+        "  GROUP BY notification_record.id",
+        ") request",
+        options.since === undefined ? "" : "WHERE julianday(request.requested_at) >= julianday(?)",
+        "ORDER BY request.requested_at ASC, request.id ASC",
+      ]
+        .filter((line) => line.length > 0)
+        .join("\n"),
+    )
+    .all(
+      ...OWN_REVIEW_EVENT_TYPES,
+      ...(options.since === undefined ? [] : [options.since.toISOString()]),
+    );
+
+  return rows.map((row) => {
+    const value = row as Record<string, unknown>;
+
+    return {
+      repository: `${String(value.repository_owner)}/${String(value.repository_name)}`,
+      pullRequestNumber: Number(value.number),
+      requestedAt: String(value.requested_at),
+      openedAt: typeof value.opened_at === "string" ? value.opened_at : null,
+      reviewedAt: typeof value.reviewed_at === "string" ? value.reviewed_at : null,
+    };
+  });
+}
+
+export function summarizeResponseTimesByRepository(
+  responses: readonly ReviewResponse[],
+): RepositoryResponseTimes[] {
+  const durationsByRepository = new Map<string, { requestCount: number; durations: number[] }>();
+
+  for (const response of responses) {
+    const summary = durationsByRepository.get(response.repository) ?? {
+      requestCount: 0,
+      durations: [],
+    };
+    const responseMs = readResponseMs(response);
+
+    summary.requestCount += 1;
+
+    if (responseMs !== null) {
+      summary.durations.push(responseMs);
+    }
+
+    durationsByRepository.set(response.repository, summary);
+  }
+
+  return [...durationsByRepository.entries()]
+    .map(([repository, summary]) => ({
+      repository,
+      requestCount: summary.requestCount,
+      respondedCount: summary.durations.length,
+      medianResponseMs: median(summary.durations),
+    }))
+    .sort(
+      (left, right) =>
+        right.requestCount - left.requestCount || left.repository.localeCompare(right.repository),
+    );
+}
+
+export function formatResponseDuration(durationMs: number): string {
+  const totalMinutes = Math.round(durationMs / 60_000);
+
+  if (totalMinutes < 60) {
+    return `${totalMinutes}m`;
+  }
+
+  const totalHours = Math.floor(totalMinutes / 60);
+
+  if (totalHours < 24) {
+    return `${totalHours}h ${totalMinutes % 60}m`;
+  }
+
+  return `${Math.floor(totalHours / 24)}d ${totalHours % 24}h`;
+}
+
+function readResponseMs(response: ReviewResponse): number | null {
+  const requestedAtMs = Date.parse(response.requestedAt);
+  const respondedAtMs = Math.min(
+    ...[response.openedAt, response.reviewedAt]
+      .map((value) => (value === null ? Number.NaN : Date.parse(value)))
+      .filter((value) => Number.isFinite(value) && value >= requestedAtMs),
+  );
+
+  return Number.isFinite(respondedAtMs) ? respondedAtMs - requestedAtMs : null;
+}
+
+function median(values: readonly number[]): number | null {
+  if (values.length === 0) {
+    return null;
+  }
+
+  const sorted = [...values].sort((left, right) => left - right);
+  const middle = Math.floor(sorted.length / 2);
+
+  return sorted.length % 2 === 1
+    ? (sorted[middle] ?? null)
+    : ((sorted[middle - 1] ?? 0) + (sorted[middle] ?? 0)) / 2;
+}
@@ -0,0 +1,322 @@
+import { DatabaseSync } from "node:sqlite";
+
+import { Octokit } from "octokit";
+
+import { getLogger } from "./logger.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+
+const GITHUB_API_HEADERS = {
+  "X-GitHub-Api-Version": "2022-11-28",
+};
+// Only the newest open alerts are checked each poll; anything older was seen on an earlier one.
+const SECURITY_ALERTS_PAGE_SIZE = 100;
+const HIGHEST_SEEN_ALERT_KEY_PREFIX = "security_alerts_highest_seen";
+
+export type SecurityAlertKind = "code_scanning" | "secret_scanning";
+
+export interface SecurityAlert {
+  kind: SecurityAlertKind;
+  number: number;
+  ruleName: string;
+  severity: string | null;
+  location: string | null;
+  url: string;
+}
+
+export interface PollSecurityAlertsOptions {
+  repositories: readonly string[];
+  notificationDispatcher: NotificationDispatcher;
+  fetchSecurityAlerts?: (repository: string, kind: SecurityAlertKind) => Promise<SecurityAlert[]>;
+}
+
+export interface PollSecurityAlertsResult {
+  notifiedCount: number;
+}
+
+export interface StartRecurringSecurityAlertPollingOptions extends PollSecurityAlertsOptions {
+  intervalMs: number;
+}
+
+export interface RecurringSecurityAlertPollingHandle {
+  stop(): void;
+}
+
+export class SecurityAlertError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "SecurityAlertError";
+  }
+}
+
+// Code-scanning and secret-scanning alerts rarely reach the notifications inbox, so each configured
+// repository is polled directly. The first poll of a repository only records its existing alerts.
+// Alert numbers only grow within a repository, so just the highest one seen is kept: an older alert
+// that moves into the fetched page once newer ones are fixed isn't mistaken for a new one.
+export async function pollSecurityAlerts(
+  database: DatabaseSync,
+  client: Octokit,
+  options: PollSecurityAlertsOptions,
+): Promise<PollSecurityAlertsResult> {
+  const fetchSecurityAlerts =
+    options.fetchSecurityAlerts ??
+    ((repository: string, kind: SecurityAlertKind) =>
+      fetchSecurityAlertsFromGitHub(client, repository, kind));
+  let notifiedCount = 0;
+
+  for (const repository of options.repositories) {
+    for (const kind of ["code_scanning", "secret_scanning"] as const) {
+      let alerts: SecurityAlert[];
+
+      try {
+        alerts = await fetchSecurityAlerts(repository, kind);
+      } catch (error) {
+        if (isUnavailableError(error)) {
+          getLogger().debug("Security alerts are unavailable for repository", { repository, kind });
+          continue;
+        }
+
+        throw error;
+      }
+
+      const key = `${HIGHEST_SEEN_ALERT_KEY_PREFIX}:${kind}:${repository}`;
+      const highestSeenNumber = readHighestSeenAlertNumber(database, key);
+
+      if (highestSeenNumber === undefined) {
+        const alertNumbers = alerts.map((alert) => alert.number);
+
+        writeHighestSeenAlertNumber(database, key, Math.max(0, ...alertNumbers));
+        continue;
+      }
+
+      const newAlerts = alerts
+        .filter((alert) => alert.number > highestSeenNumber)
+        .sort((left, right) => left.number - right.number);
+
+      for (const alert of newAlerts) {
+        await options.notificationDispatcher.dispatchNotification({
+          title: `🚨 ${repository} ${kind === "code_scanning" ? "code" : "secret"} scanning alert`,
+          body: formatSecurityAlertBody(alert),
+          clickUrl: alert.url,
+          sticky: true,
+          urgency: "critical",
+        });
+        notifiedCount += 1;
+        // Saved per alert, oldest first, so after a failed dispatch the next poll picks up from the
+        // alert that failed without repeating the ones already shown.
+        writeHighestSeenAlertNumber(database, key, alert.number);
+      }
+    }
+  }
+
+  return { notifiedCount };
+}
+
+export function startRecurringSecurityAlertPolling(
+  database: DatabaseSync,
+  client: Octokit,
+  options: StartRecurringSecurityAlertPollingOptions,
+): RecurringSecurityAlertPollingHandle {
+  const { intervalMs, ...pollOptions } = options;
+
+  if (!Number.isFinite(intervalMs) || intervalMs <= 0) {
+    throw new SecurityAlertError("Recurring security alert polling interval must be greater than zero");
+  }
+
+  let isStopped = false;
+  let isRunning = false;
+  const timer = setInterval(() => {
+    void runPollCycle();
+  }, intervalMs);
+
+  timer.unref?.();
+  void runPollCycle();
+
+  return {
+    stop(): void {
+      if (isStopped) {
+        return;
+      }
+
+      isStopped = true;
+      clearInterval(timer);
+    },
+  };
+
+  async function runPollCycle(): Promise<void> {
+    if (isStopped || isRunning) {
+      return;
+    }
+
+    isRunning = true;
+
+    try {
+      const result = await pollSecurityAlerts(database, client, pollOptions);
+
+      if (result.notifiedCount > 0) {
+        getLogger().info("Notified new security alerts", { ...result });
+      }
+    } catch (error) {
+      getLogger().error("Octopulse security alert polling failed", {
+        error: new SecurityAlertError(`Failed to poll security alerts: ${getErrorMessage(error)}`),
+      });
+    } finally {
+      isRunning = false;
+    }
+  }
+}
+
+export function formatSecurityAlertBody(alert: SecurityAlert): string {
+  const rule = alert.severity === null ? alert.ruleName : `${alert.ruleName} (${alert.severity})`;
+
+  return alert.location === null ? rule : `${rule}\n${alert.location}`;
+}
+
+export async function fetchSecurityAlertsFromGitHub(
+  client: Octokit,
+  repository: string,
+  kind: SecurityAlertKind,
+): Promise<SecurityAlert[]> {
+  const [owner = "", repo = ""] = repository.split("/");
+  const route =
+    kind === "code_scanning"
+      ? "GET /repos/{owner}/{repo}/code-scanning/alerts"
+      : "GET /repos/{owner}/{repo}/secret-scanning/alerts";
+  const response = await client.request(route, {
+    owner,
+    repo,
+    state: "open",
+    sort: "created",
+    direction: "desc",
+    per_page: SECURITY_ALERTS_PAGE_SIZE,
+    headers: GITHUB_API_HEADERS,
+  });
+  const data = response.data as unknown;
+
+  if (!Array.isArray(data)) {
+    throw new SecurityAlertError(`${repository} ${kind} alerts response must be an array`);
+  }
+
+  return data.map((entry, index) =>
+    kind === "code_scanning"
+      ? readCodeScanningAlert(entry, `code scanning alerts[${index}]`)
+      : readSecretScanningAlert(entry, `secret scanning alerts[${index}]`),
+  );
+}
+
+function readCodeScanningAlert(value: unknown, fieldName: string): SecurityAlert {
+  const alert = requireRecord(value, fieldName);
+  const rule = readRecord(alert.rule);
+  const location = readRecord(readRecord(alert.most_recent_instance)?.location);
+  const ruleName = readOptionalString(rule?.description) ?? readOptionalString(rule?.id);
+
+  if (typeof alert.number !== "number" || typeof alert.html_url !== "string" || ruleName === null) {
+    throw new SecurityAlertError(`${fieldName} is missing its number, URL, or rule`);
+  }
+
+  return {
+    kind: "code_scanning",
+    number: alert.number,
+    ruleName,
+    severity:
+      readOptionalString(rule?.security_severity_level) ?? readOptionalString(rule?.severity),
+    location: formatLocation(location),
+    url: alert.html_url,
+  };
+}
+
+function readSecretScanningAlert(value: unknown, fieldName: string): SecurityAlert {
+  const alert = requireRecord(value, fieldName);
+  const ruleName =
+    readOptionalString(alert.secret_type_display_name) ?? readOptionalString(alert.secret_type);
+
+  if (typeof alert.number !== "number" || typeof alert.html_url !== "string" || ruleName === null) {
+    throw new SecurityAlertError(`${fieldName} is missing its number, URL, or secret type`);
+  }
+
+  return {
+    kind: "secret_scanning",
+    number: alert.number,
+    ruleName,
+    severity: null,
+    location: formatLocation(readRecord(alert.first_location_detected)),
+    url: alert.html_url,
+  };
+}
+
+function formatLocation(location: Record<string, unknown> | undefined): string | null {
+  const path = readOptionalString(location?.path);
+
+  if (path === null) {
+    return null;
+  }
+
+  return typeof location?.start_line === "number" ? `${path}:${location.start_line}` : path;
+}
+
+function readHighestSeenAlertNumber(database: DatabaseSync, key: string): number | undefined {
+  const row = database.prepare("SELECT value FROM AppState WHERE key = ?").get(key);
+
+  if (row === undefined) {
+    return undefined;
+  }
+
+  const value = Number((row as Record<string, unknown>).value);
+  return Number.isInteger(value) ? value : 0;
+}
+
+function writeHighestSeenAlertNumber(
+  database: DatabaseSync,
+  key: string,
+  alertNumber: number,
+): void {
+  database
+    .prepare(
+      `
+        INSERT INTO AppState (key, value)
+        VALUES (?, ?)
+        ON CONFLICT(key) DO UPDATE SET
+          value = excluded.value,
+          updated_at = CURRENT_TIMESTAMP
+      `,
+    )
+    .run(key, String(alertNumber));
+}
+
+// Repositories without Advanced Security, or tokens without the security_events scope, answer
+// 403 or 404; those repositories are skipped rather than failing the whole poll.
+function isUnavailableError(error: unknown): boolean {
+  if (typeof error !== "object" || error === null) {
+    return false;
+  }
+
+  const status = (error as Record<string, unknown>).status;
+  return status === 403 || status === 404;
+}
+
+function requireRecord(value: unknown, fieldName: string): Record<string, unknown> {
+  const record = readRecord(value);
+
+  if (record === undefined) {
+    throw new SecurityAlertError(`${fieldName} must be an object`);
+  }
+
+  return record;
+}
+
+function readRecord(value: unknown): Record<string, unknown> | undefined {
+  return typeof value === "object" && value !== null && !Array.isArray(value)
+    ? (value as Record<string, unknown>)
+    : undefined;
+}
+
+function readOptionalString(value: unknown): string | null {
+  return typeof value === "string" && value.length > 0 ? value : null;
+}
+
+function getErrorMessage(error: unknown): string {
+  if (error instanceof Error) {
+    return error.message;
+  }
+
+  return String(error);
+}
@@ -0,0 +1,296 @@
+import { execFile } from "node:child_process";
+import { createHash } from "node:crypto";
+import { existsSync } from "node:fs";
+import { mkdtemp, readFile, rename, rm, writeFile } from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { fileURLToPath } from "node:url";
+
+import type { RunCommand } from "./session-presence-buffer.js";
+
+const RELEASES_REPOSITORY = "tradiff/octopulse";
+const CHECKSUMS_ASSET_NAME = "SHA256SUMS";
+const SIGNATURE_ASSET_NAME = "SHA256SUMS.sig";
+// The release key's entry in release-allowed-signers, and the namespace releases are signed in:
+// ssh-keygen -Y sign -f <key> -n octopulse-release SHA256SUMS
+const RELEASE_SIGNER_IDENTITY = "releases@octopulse";
+const RELEASE_SIGNATURE_NAMESPACE = "octopulse-release";
+const ALLOWED_SIGNERS_FILE_NAME = "release-allowed-signers";
+const DEFAULT_REPO_ROOT = fileURLToPath(new URL("..", import.meta.url));
+
+export interface SelfUpdateRelease {
+  version: string;
+  tarballName: string;
+  tarballUrl: string;
+  checksumsUrl: string;
+  signatureUrl: string;
+}
+
+export interface SelfUpdateOptions {
+  repoRoot?: string;
+  currentVersion?: string;
+  checkOnly?: boolean;
+  fetchImpl?: typeof fetch;
+  runCommand?: RunCommand;
+  // Throws unless `signature` is the release key's signature of `message`.
+  verifySignature?: (message: string, signature: string) => Promise<void>;
+}
+
+export class SelfUpdateError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "SelfUpdateError";
+  }
+}
+
+// Unpacks the release next to the install and swaps the folders, so a failed or partial unpack
+// never leaves a mix of old and new files behind. The previous install is kept beside it. Nothing
+// is unpacked until SHA256SUMS carries a valid signature from the key pinned in
+// release-allowed-signers and the tarball matches it.
+export async function runSelfUpdate(options: SelfUpdateOptions = {}): Promise<string> {
+  const repoRoot = path.resolve(options.repoRoot ?? DEFAULT_REPO_ROOT);
+  const fetchImpl = options.fetchImpl ?? fetch;
+  const verifySignature =
+    options.verifySignature ??
+    ((message: string, signature: string) =>
+      verifyReleaseSignature(path.join(repoRoot, ALLOWED_SIGNERS_FILE_NAME), message, signature));
+  const currentVersion = options.currentVersion ?? (await readPackageVersion(repoRoot));
+  const release = await fetchLatestRelease(fetchImpl);
+
+  if (compareVersions(release.version, currentVersion) <= 0) {
+    return `Octopulse ${currentVersion} is up to date.`;
+  }
+
+  if (options.checkOnly) {
+    return `Octopulse ${release.version} is available (installed: ${currentVersion}).`;
+  }
+
+  // The swap moves the whole folder, which would take the git history, local branches and
+  // node_modules along with it.
+  if (existsSync(path.join(repoRoot, ".git"))) {
+    throw new SelfUpdateError(
+      `${repoRoot} is a git checkout; update it with git pull instead of self-update`,
+    );
+  }
+
+  const tarball = Buffer.from(await (await fetchOk(fetchImpl, release.tarballUrl)).arrayBuffer());
+  const checksums = await (await fetchOk(fetchImpl, release.checksumsUrl)).text();
+  const signature = await (await fetchOk(fetchImpl, release.signatureUrl)).text();
+
+  await verifySignature(checksums, signature);
+  checkDownloadIntegrity(tarball, release.tarballName, checksums);
+
+  const downloadDirPath = await mkdtemp(path.join(os.tmpdir(), "octopulse-update-"));
+  // A sibling of the install, so the swap below is a rename on the same filesystem.
+  const stagingPath = await mkdtemp(`${repoRoot}.update-`);
+  const previousPath = `${repoRoot}.previous-${currentVersion}`;
+
+  try {
+    const tarballPath = path.join(downloadDirPath, release.tarballName);
+    await writeFile(tarballPath, tarball);
+    await (options.runCommand ?? runCommandForOutput)("tar", [
+      "-xzf",
+      tarballPath,
+      "-C",
+      stagingPath,
+      "--strip-components=1",
+    ]);
+    await swapInstall(repoRoot, stagingPath, previousPath);
+  } catch (error) {
+    await rm(stagingPath, { recursive: true, force: true });
+    throw error;
+  } finally {
+    await rm(downloadDirPath, { recursive: true, force: true });
+  }
+
+  return [
+    `Updated Octopulse ${currentVersion} -> ${release.version} in ${repoRoot}`,
+    `The previous install was moved to ${previousPath}.`,
+    "",
+    "Next steps:",
+    "1. Run: npm install",
+    "2. Run: npm run deploy:user-service",
+  ].join("\n");
+}
+
+export async function fetchLatestRelease(
+  fetchImpl: typeof fetch = fetch,
+): Promise<SelfUpdateRelease> {
+  const response = await fetchOk(
+    fetchImpl,
+    `https://api.github.com/repos/${RELEASES_REPOSITORY}/releases/latest`,
+  );
+  const payload = (await response.json()) as {
+    tag_name?: unknown;
+    assets?: Array<{ name?: unknown; browser_download_url?: unknown }>;
+  };
+  const version = typeof payload.tag_name === "string" ? payload.tag_name.replace(/^v/, "") : "";
+  const assets = payload.assets ?? [];
+  const findAssetUrl = (name: string) => {
+    const url = assets.find((asset) => asset.name === name)?.browser_download_url;
+    return typeof url === "string" ? url : undefined;
+  };
+  const tarballName = `octopulse-${version}.tar.gz`;
+  const tarballUrl = findAssetUrl(tarballName);
+  const checksumsUrl = findAssetUrl(CHECKSUMS_ASSET_NAME);
+  const signatureUrl = findAssetUrl(SIGNATURE_ASSET_NAME);
+
+  if (!/^\d+\.\d+\.\d+$/.test(version)) {
+    throw new SelfUpdateError(`Latest release has an unexpected tag: ${String(payload.tag_name)}`);
+  }
+
+  if (tarballUrl === undefined || checksumsUrl === undefined || signatureUrl === undefined) {
+    throw new SelfUpdateError(
+      `Release ${version} is missing ${tarballName}, ${CHECKSUMS_ASSET_NAME} or ${SIGNATURE_ASSET_NAME}`,
+    );
+  }
+
+  return { version, tarballName, tarballUrl, checksumsUrl, signatureUrl };
+}
+
+// Checks an SSH signature made with `ssh-keygen -Y sign` against the allowed signers file, so a
+// release only installs if it was signed with the pinned key, whoever controls the download.
+export async function verifyReleaseSignature(
+  allowedSignersPath: string,
+  message: string,
+  signature: string,
+): Promise<void> {
+  const allowedSigners = await readFile(allowedSignersPath, "utf8").catch(() => "");
+
+  if (!allowedSigners.split("\n").some((line) => /^[^#\s]/.test(line))) {
+    throw new SelfUpdateError(`No release signing key is pinned in ${allowedSignersPath}`);
+  }
+
+  const signatureDirPath = await mkdtemp(path.join(os.tmpdir(), "octopulse-update-signature-"));
+  const signaturePath = path.join(signatureDirPath, SIGNATURE_ASSET_NAME);
+
+  try {
+    await writeFile(signaturePath, signature);
+    await new Promise<void>((resolve, reject) => {
+      const child = execFile(
+        "ssh-keygen",
+        [
+          "-Y",
+          "verify",
+          "-f",
+          allowedSignersPath,
+          "-I",
+          RELEASE_SIGNER_IDENTITY,
+          "-n",
+          RELEASE_SIGNATURE_NAMESPACE,
+          "-s",
+          signaturePath,
+        ],
+        (error, _stdout, stderr) => {
+          if (error) {
+            reject(
+              new SelfUpdateError(
+                `${SIGNATURE_ASSET_NAME} is not a valid release signature: ${stderr.trim() || error.message}`,
+              ),
+            );
+            return;
+          }
+
+          resolve();
+        },
+      );
+
+      child.stdin?.end(message);
+    });
+  } finally {
+    await rm(signatureDirPath, { recursive: true, force: true });
+  }
+}
+
+// Ties the tarball to SHA256SUMS, whose signature is checked first, so a corrupted or swapped
+// tarball is refused.
+export function checkDownloadIntegrity(
+  contents: Buffer,
+  fileName: string,
+  checksums: string,
+): void {
+  const expected = checksums
+    .split("\n")
+    .map((line) => line.trim().match(/^([a-f0-9]{64})\s+\*?(.+)$/i))
+    .find((match) => match?.[2] === fileName)?.[1];
+
+  if (expected === undefined) {
+    throw new SelfUpdateError(`${CHECKSUMS_ASSET_NAME} has no entry for ${fileName}`);
+  }
+
+  const actual = createHash("sha256").update(contents).digest("hex");
+
+  if (actual !== expected.toLowerCase()) {
+    throw new SelfUpdateError(
+      `Checksum mismatch for ${fileName}: expected ${expected}, got ${actual}`,
+    );
+  }
+}
+
+export function compareVersions(left: string, right: string): number {
+  const leftParts = left.split(".").map(Number);
+  const rightParts = right.split(".").map(Number);
+
+  for (let index = 0; index < Math.max(leftParts.length, rightParts.length); index += 1) {
+    const difference = (leftParts[index] ?? 0) - (rightParts[index] ?? 0);
+
+    if (difference !== 0) {
+      return Math.sign(difference);
+    }
+  }
+
+  return 0;
+}
+
+async function swapInstall(
+  repoRoot: string,
+  stagingPath: string,
+  previousPath: string,
+): Promise<void> {
+  await rm(previousPath, { recursive: true, force: true });
+  await rename(repoRoot, previousPath);
+
+  try {
+    await rename(stagingPath, repoRoot);
+  } catch (error) {
+    await rename(previousPath, repoRoot);
+    throw new SelfUpdateError(`Failed to move the new release into ${repoRoot}: ${String(error)}`);
+  }
+}
+
+async function readPackageVersion(repoRoot: string): Promise<string> {
+  const packageJson = JSON.parse(await readFile(path.join(repoRoot, "package.json"), "utf8")) as {
+    version?: unknown;
+  };
+
+  if (typeof packageJson.version !== "string") {
+    throw new SelfUpdateError(`package.json in ${repoRoot} has no version`);
+  }
+
+  return packageJson.version;
+}
+
+async function fetchOk(fetchImpl: typeof fetch, url: string): Promise<Response> {
+  const response = await fetchImpl(url, {
+    headers: { "User-Agent": "octopulse-self-update" },
+  });
+
+  if (!response.ok) {
+    throw new SelfUpdateError(`GET ${url} failed with HTTP ${response.status}`);
+  }
+
+  return response;
+}
+
+function runCommandForOutput(command: string, args: readonly string[]): Promise<string> {
+  return new Promise((resolve, reject) => {
+    execFile(command, [...args], (error, stdout) => {
+      if (error) {
+        reject(new SelfUpdateError(`${command} failed: ${error.message}`));
+        return;
+      }
+
+      resolve(stdout);
+    });
+  });
+}
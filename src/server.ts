@@ -1,6 +1,6 @@
 import { createServer, type IncomingMessage, type Server, type ServerResponse } from "node:http";
 import type { AddressInfo } from "node:net";
-import { existsSync, readFileSync } from "node:fs";
+import { readFile } from "node:fs/promises";
 
 import {
   DEFAULT_ACTIVITY_PAGE_SIZE,
@@ -51,7 +51,7 @@ export const DEFAULT_SERVER_HOST = "127.0.0.1";
 export const DEFAULT_SERVER_PORT = 3000;
 
 type SyncOrPromise<T> = T | Promise<T>;
-type AppPage = "pull-requests" | "logs" | "notification-history";
+type AppPage = "pull-requests" | "inbox" | "logs" | "notification-history";
 
 export interface StartServerOptions {
   host?: string;
@@ -62,6 +62,9 @@ export interface StartServerOptions {
   listNotificationHistory?: (
     options: ListActivityFeedOptions,
   ) => SyncOrPromise<PaginatedEntries<NotificationHistoryEntry>>;
+  listNotificationInbox?: (
+    options: ListActivityFeedOptions,
+  ) => SyncOrPromise<PaginatedEntries<NotificationHistoryEntry>>;
   listRecentLogs?: (options: {
     level?: LogLevel;
     limit?: number;
@@ -71,6 +74,11 @@ export interface StartServerOptions {
     githubPullRequestId: number,
   ) => Promise<UntrackPullRequestResult>;
   resendNotificationRecord?: (notificationRecordId: number) => Promise<void>;
+  markNotificationRecordRead?: (notificationRecordId: number) => SyncOrPromise<void>;
+  markNotificationRecordUnread?: (notificationRecordId: number) => SyncOrPromise<void>;
+  openLatestNotification?: () => Promise<string | null>;
+  openInbox?: () => Promise<string>;
+  toggleNotificationPause?: () => Promise<boolean>;
   getCurrentUserLogin?: () => string;
 }
 
@@ -140,19 +148,20 @@ async function handleRequest(
   const logLevelFilter = readLogLevelFilter(searchParams);
   const trackedPullRequestMatch = pathname.match(/^\/api\/tracked-pull-requests\/(\d+)$/);
   const notificationRecordResendMatch = pathname.match(/^\/api\/notification-records\/(\d+)\/resend$/);
+  const notificationRecordReadMatch = pathname.match(/^\/api\/notification-records\/(\d+)\/read$/);
 
   if (supportsDocumentResponse(request) && pathname === "/app.js") {
-    handleClientBundleRequest(request, response);
+    await handleClientBundleRequest(request, response);
     return;
   }
 
   if (supportsDocumentResponse(request) && (pathname === "/favicon.png" || pathname === "/favicon.ico")) {
-    handleFaviconRequest(request, response);
+    await handleFaviconRequest(request, response);
     return;
   }
 
   if (request.method === "GET" && pathname.startsWith("/assets/")) {
-    handleAssetRequest(request, response, pathname.slice("/assets/".length));
+    await handleAssetRequest(request, response, pathname.slice("/assets/".length));
     return;
   }
 
@@ -193,6 +202,18 @@ async function handleRequest(
       response,
       options.listNotificationHistory,
       searchParams,
+      "Notification history listing is not configured",
+    );
+    return;
+  }
+
+  if (request.method === "GET" && pathname === "/api/notification-inbox") {
+    await handleNotificationHistoryRequest(
+      request,
+      response,
+      options.listNotificationInbox,
+      searchParams,
+      "Notification inbox listing is not configured",
     );
     return;
   }
@@ -231,6 +252,57 @@ async function handleRequest(
     return;
   }
 
+  if (request.method === "POST" && notificationRecordReadMatch) {
+    await handleNotificationRecordReadRequest(
+      request,
+      response,
+      options.markNotificationRecordRead,
+      notificationRecordReadMatch[1]!,
+    );
+    return;
+  }
+
+  if (request.method === "DELETE" && notificationRecordReadMatch) {
+    await handleNotificationRecordReadRequest(
+      request,
+      response,
+      options.markNotificationRecordUnread,
+      notificationRecordReadMatch[1]!,
+    );
+    return;
+  }
+
+  if (request.method === "POST" && pathname === "/api/shortcuts/open-latest") {
+    await handleShortcutRequest(
+      request,
+      response,
+      options.openLatestNotification
+        ? async () => ({ openedUrl: await options.openLatestNotification!() })
+        : undefined,
+    );
+    return;
+  }
+
+  if (request.method === "POST" && pathname === "/api/shortcuts/open-inbox") {
+    await handleShortcutRequest(
+      request,
+      response,
+      options.openInbox ? async () => ({ openedUrl: await options.openInbox!() }) : undefined,
+    );
+    return;
+  }
+
+  if (request.method === "POST" && pathname === "/api/shortcuts/toggle-pause") {
+    await handleShortcutRequest(
+      request,
+      response,
+      options.toggleNotificationPause
+        ? async () => ({ paused: await options.toggleNotificationPause!() })
+        : undefined,
+    );
+    return;
+  }
+
   if (supportsDocumentResponse(request) && pathname === "/health") {
     respond(
       response,
@@ -250,8 +322,13 @@ async function handleRequest(
   respond(response, request.method, 404, "text/plain; charset=utf-8", "Not Found");
 }
 
-function handleClientBundleRequest(request: IncomingMessage, response: ServerResponse): void {
-  if (!existsSync(CLIENT_BUNDLE_PATH)) {
+async function handleClientBundleRequest(
+  request: IncomingMessage,
+  response: ServerResponse,
+): Promise<void> {
+  const source = await readStaticFile(CLIENT_BUNDLE_PATH);
+
+  if (source === null) {
     respond(
       response,
       request.method,
@@ -262,12 +339,16 @@ function handleClientBundleRequest(request: IncomingMessage, response: ServerRes
     return;
   }
 
-  const source = readFileSync(CLIENT_BUNDLE_PATH, "utf8");
   respond(response, request.method, 200, "application/javascript; charset=utf-8", source);
 }
 
-function handleFaviconRequest(request: IncomingMessage, response: ServerResponse): void {
-  if (!existsSync(APP_ICON_PNG_URL)) {
+async function handleFaviconRequest(
+  request: IncomingMessage,
+  response: ServerResponse,
+): Promise<void> {
+  const source = await readStaticFile(APP_ICON_PNG_URL);
+
+  if (source === null) {
     respond(
       response,
       request.method,
@@ -278,28 +359,40 @@ function handleFaviconRequest(request: IncomingMessage, response: ServerResponse
     return;
   }
 
-  const source = readFileSync(APP_ICON_PNG_URL);
   respond(response, request.method, 200, "image/png", source);
 }
 
-function handleAssetRequest(
+async function handleAssetRequest(
   request: IncomingMessage,
   response: ServerResponse,
   filename: string,
-): void {
+): Promise<void> {
   if (!isPullRequestStateAssetFilename(filename)) {
     respond(response, request.method, 404, "text/plain; charset=utf-8", "Not Found");
     return;
   }
 
-  const assetPath = resolvePullRequestStateAssetFilePathByFilename(filename);
+  const source = await readStaticFile(resolvePullRequestStateAssetFilePathByFilename(filename));
 
-  if (!existsSync(assetPath)) {
+  if (source === null) {
     respond(response, request.method, 503, "text/plain; charset=utf-8", `Asset not found: ${filename}`);
     return;
   }
 
-  respond(response, request.method, 200, "image/svg+xml; charset=utf-8", readFileSync(assetPath));
+  respond(response, request.method, 200, "image/svg+xml; charset=utf-8", source);
+}
+
+// Read asynchronously so serving assets never stalls polling or notification work.
+async function readStaticFile(filePath: string | URL): Promise<Buffer | null> {
+  try {
+    return await readFile(filePath);
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code === "ENOENT") {
+      return null;
+    }
+
+    throw error;
+  }
 }
 
 async function handleManualTrackPullRequestRequest(
@@ -471,6 +564,7 @@ async function handleNotificationHistoryRequest(
   response: ServerResponse,
   listNotificationHistory: StartServerOptions["listNotificationHistory"],
   searchParams: URLSearchParams,
+  unavailableMessage: string,
 ): Promise<void> {
   if (!listNotificationHistory) {
     respond(
@@ -478,7 +572,7 @@ async function handleNotificationHistoryRequest(
       request.method,
       503,
       "application/json; charset=utf-8",
-      JSON.stringify({ error: "Notification history listing is not configured" }),
+      JSON.stringify({ error: unavailableMessage }),
     );
     return;
   }
@@ -613,6 +707,10 @@ function readDocumentPage(pathname: string): AppPage | undefined {
     return "pull-requests";
   }
 
+  if (pathname === "/inbox") {
+    return "inbox";
+  }
+
   if (pathname === "/logs") {
     return "logs";
   }
@@ -710,6 +808,95 @@ async function handleNotificationRecordResendRequest(
   }
 }
 
+async function handleNotificationRecordReadRequest(
+  request: IncomingMessage,
+  response: ServerResponse,
+  updateReadState: StartServerOptions["markNotificationRecordRead"],
+  notificationRecordIdSegment: string,
+): Promise<void> {
+  if (!updateReadState) {
+    respond(
+      response,
+      request.method,
+      503,
+      "application/json; charset=utf-8",
+      JSON.stringify({ error: "Notification read state is not configured" }),
+    );
+    return;
+  }
+
+  try {
+    const notificationRecordId = readPositiveInteger(notificationRecordIdSegment, "Notification record id");
+    await updateReadState(notificationRecordId);
+
+    respond(
+      response,
+      request.method,
+      200,
+      "application/json; charset=utf-8",
+      JSON.stringify({ success: true }),
+    );
+  } catch (error) {
+    getLogger().warn("Failed to update notification record read state via API", {
+      method: request.method,
+      notificationRecordId: notificationRecordIdSegment,
+      error,
+    });
+
+    respond(
+      response,
+      request.method,
+      error instanceof ServerError ? 400 : 500,
+      "application/json; charset=utf-8",
+      JSON.stringify({
+        error: getErrorMessage(error),
+      }),
+    );
+  }
+}
+
+async function handleShortcutRequest(
+  request: IncomingMessage,
+  response: ServerResponse,
+  runShortcut: (() => Promise<Record<string, unknown>>) | undefined,
+): Promise<void> {
+  if (!runShortcut) {
+    respond(
+      response,
+      request.method,
+      503,
+      "application/json; charset=utf-8",
+      JSON.stringify({ error: "Shortcut is not configured" }),
+    );
+    return;
+  }
+
+  try {
+    respond(
+      response,
+      request.method,
+      200,
+      "application/json; charset=utf-8",
+      JSON.stringify(await runShortcut()),
+    );
+  } catch (error) {
+    getLogger().warn("Shortcut request failed", {
+      path: request.url,
+      error,
+    });
+
+    respond(
+      response,
+      request.method,
+      500,
+      "application/json; charset=utf-8",
+      JSON.stringify({
+        error: getErrorMessage(error),
+      }),
+    );
+  }
+}
+
 function readPositiveInteger(value: string, fieldName: string): number {
   const numericValue = Number(value);
 
@@ -0,0 +1,413 @@
+import { execFile } from "node:child_process";
+import type { DatabaseSync } from "node:sqlite";
+
+import type { LinuxNotification } from "./linux-notification-adapter.js";
+import { getLogger } from "./logger.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+
+const DEFAULT_PRESENCE_CHECK_INTERVAL_MS = 15_000;
+const DEFAULT_PRESENCE_CACHE_MS = 5_000;
+const DEFAULT_DIGEST_THRESHOLD = 3;
+const MAX_DIGEST_LINES = 5;
+
+export const GITHUB_NOTIFICATIONS_URL = "https://github.com/notifications";
+
+export type RunCommand = (command: string, args: readonly string[]) => Promise<string>;
+
+export type ScreenSharePolicy = "show" | "redact" | "suppress";
+
+export interface SessionPresence {
+  locked: boolean;
+  idleMs: number;
+  screenShared: boolean;
+}
+
+// Held popups outlive the process: their history records were already written as sent, so a
+// queue lost on restart would mean popups that never show.
+export interface HeldNotificationStore {
+  read(): LinuxNotification[];
+  write(notifications: readonly LinuxNotification[]): void;
+}
+
+export interface SessionPresenceBufferOptions {
+  readSessionPresence?: () => Promise<SessionPresence>;
+  bufferWhileLocked?: boolean;
+  holdWhenIdleMs?: number;
+  whileScreenShared?: ScreenSharePolicy;
+  isCalendarBusy?: () => Promise<boolean>;
+  presenceCheckIntervalMs?: number;
+  // How long a presence reading serves further popups, so a burst doesn't spawn loginctl, pw-dump
+  // and xprop for each one. The periodic flush check always reads afresh.
+  presenceCacheMs?: number;
+  digestThreshold?: number;
+  // Where digests of held popups link when they span several pull requests.
+  overflowClickUrl?: () => string;
+  heldNotificationStore?: HeldNotificationStore;
+}
+
+export interface SessionPresenceBufferedNotificationDispatcher extends NotificationDispatcher {
+  stop(): void;
+}
+
+export function withSessionPresenceBuffer(
+  notificationDispatcher: NotificationDispatcher,
+  options: SessionPresenceBufferOptions = {},
+): SessionPresenceBufferedNotificationDispatcher {
+  const readSessionPresence = options.readSessionPresence ?? (() => readDesktopSessionPresence());
+  const bufferWhileLocked = options.bufferWhileLocked ?? true;
+  const holdWhenIdleMs = options.holdWhenIdleMs;
+  const whileScreenShared = options.whileScreenShared ?? "show";
+  const isCalendarBusy = options.isCalendarBusy ?? (async () => false);
+  const presenceCheckIntervalMs =
+    options.presenceCheckIntervalMs ?? DEFAULT_PRESENCE_CHECK_INTERVAL_MS;
+  const presenceCacheMs = options.presenceCacheMs ?? DEFAULT_PRESENCE_CACHE_MS;
+  const digestThreshold = options.digestThreshold ?? DEFAULT_DIGEST_THRESHOLD;
+  const heldNotificationStore = options.heldNotificationStore;
+  const queuedNotifications: LinuxNotification[] = heldNotificationStore?.read() ?? [];
+  let cachedPresence: { readAt: number; presence: Promise<SessionPresence> } | undefined;
+  let isStopped = false;
+  let isFlushing = false;
+  let heldDuringCalendarBusy = false;
+
+  const timer = setInterval(() => {
+    void flushIfPresent();
+  }, presenceCheckIntervalMs);
+
+  timer.unref?.();
+
+  return {
+    async dispatchNotification(notification) {
+      if (isStopped) {
+        return notificationDispatcher.dispatchNotification(notification);
+      }
+
+      const presence = await readCachedSessionPresence();
+
+      // Busy calendar events downgrade popups to silent history entries; the queued
+      // notifications come back as one catch-up digest once the event ends. Critical ones, like
+      // security alerts, still show.
+      if (notification.urgency !== "critical" && (await isCalendarBusy())) {
+        queuedNotifications.push(notification);
+        heldNotificationStore?.write(queuedNotifications);
+        heldDuringCalendarBusy = true;
+        getLogger().debug("Queued notification during a busy calendar event", {
+          queuedCount: queuedNotifications.length,
+        });
+        return { queued: true };
+      }
+
+      if (shouldHold(presence, notification)) {
+        queuedNotifications.push(notification);
+        heldNotificationStore?.write(queuedNotifications);
+        getLogger().debug("Queued notification while the user is away", {
+          queuedCount: queuedNotifications.length,
+        });
+        return { queued: true };
+      }
+
+      return notificationDispatcher.dispatchNotification(
+        presence.screenShared && whileScreenShared === "redact"
+          ? redactNotification(notification)
+          : notification,
+      );
+    },
+    stop(): void {
+      if (isStopped) {
+        return;
+      }
+
+      isStopped = true;
+      clearInterval(timer);
+    },
+  };
+
+  function readCachedSessionPresence(): Promise<SessionPresence> {
+    const now = Date.now();
+
+    if (cachedPresence === undefined || now - cachedPresence.readAt >= presenceCacheMs) {
+      cachedPresence = { readAt: now, presence: readSessionPresence() };
+    }
+
+    return cachedPresence.presence;
+  }
+
+  function readFreshSessionPresence(): Promise<SessionPresence> {
+    cachedPresence = { readAt: Date.now(), presence: readSessionPresence() };
+    return cachedPresence.presence;
+  }
+
+  function shouldHold(presence: SessionPresence, notification: LinuxNotification | null): boolean {
+    if (bufferWhileLocked && presence.locked) {
+      return true;
+    }
+
+    if (presence.screenShared && whileScreenShared === "suppress") {
+      return true;
+    }
+
+    // Sticky popups (review requests, approvals, change requests) are the critical ones; they
+    // stay on screen until dismissed, so there is no reason to hold them back for idleness.
+    return (
+      holdWhenIdleMs !== undefined &&
+      presence.idleMs >= holdWhenIdleMs &&
+      notification?.sticky !== true
+    );
+  }
+
+  async function flushIfPresent(): Promise<void> {
+    if (isStopped || isFlushing || queuedNotifications.length === 0) {
+      return;
+    }
+
+    isFlushing = true;
+
+    try {
+      if (shouldHold(await readFreshSessionPresence(), null) || (await isCalendarBusy())) {
+        return;
+      }
+
+      const notifications = queuedNotifications.splice(0, queuedNotifications.length);
+      heldNotificationStore?.write(queuedNotifications);
+      const deliveries =
+        heldDuringCalendarBusy || notifications.length > digestThreshold
+          ? [buildAwayDigestNotification(notifications, options.overflowClickUrl?.())]
+          : notifications;
+      heldDuringCalendarBusy = false;
+
+      getLogger().info("Delivering notifications queued while the user was away", {
+        queuedCount: notifications.length,
+        digest: deliveries.length !== notifications.length,
+      });
+
+      for (const notification of deliveries) {
+        try {
+          await notificationDispatcher.dispatchNotification(notification);
+        } catch (error) {
+          getLogger().warn("Failed to deliver notification queued while away", { error });
+        }
+      }
+    } finally {
+      isFlushing = false;
+    }
+  }
+}
+
+// Actions close over live clients and can't be saved, so restored popups come back without them.
+export function createAppStateHeldNotificationStore(
+  database: DatabaseSync,
+  key: string,
+): HeldNotificationStore {
+  return {
+    read() {
+      const row = database.prepare("SELECT value FROM AppState WHERE key = ?").get(key);
+
+      if (row === undefined) {
+        return [];
+      }
+
+      try {
+        const value: unknown = JSON.parse(String((row as Record<string, unknown>).value));
+
+        return Array.isArray(value) ? value.filter(isStoredNotification) : [];
+      } catch (error) {
+        getLogger().warn("Discarding unreadable held notifications", { key, error });
+        return [];
+      }
+    },
+    write(notifications) {
+      const value = JSON.stringify(
+        notifications.map((notification) => ({ ...notification, actions: undefined })),
+      );
+
+      database
+        .prepare(
+          `
+            INSERT INTO AppState (key, value)
+            VALUES (?, ?)
+            ON CONFLICT(key) DO UPDATE SET
+              value = excluded.value,
+              updated_at = CURRENT_TIMESTAMP
+          `,
+        )
+        .run(key, value);
+    },
+  };
+}
+
+function isStoredNotification(value: unknown): value is LinuxNotification {
+  if (typeof value !== "object" || value === null) {
+    return false;
+  }
+
+  const notification = value as Record<string, unknown>;
+  return typeof notification.title === "string" && typeof notification.body === "string";
+}
+
+// Keeps everything that isn't shown as text, so the redacted popup still offers its actions and
+// lands in history under the same record.
+export function redactNotification(notification: LinuxNotification): LinuxNotification {
+  return {
+    title: notification.title,
+    body: "Details hidden while your screen is shared",
+    ...(notification.actions === undefined ? {} : { actions: notification.actions }),
+    ...(notification.clickUrl === undefined ? {} : { clickUrl: notification.clickUrl }),
+    ...(notification.icon === undefined ? {} : { icon: notification.icon }),
+    ...(notification.notificationRecordId === undefined
+      ? {}
+      : { notificationRecordId: notification.notificationRecordId }),
+    ...(notification.sticky === undefined ? {} : { sticky: notification.sticky }),
+    ...(notification.urgency === undefined ? {} : { urgency: notification.urgency }),
+  };
+}
+
+// Digests always link somewhere: the one pull request they cover, or else the overflow target
+// (local notification history when the UI is running, github.com/notifications otherwise).
+export function buildAwayDigestNotification(
+  notifications: readonly LinuxNotification[],
+  overflowClickUrl: string = GITHUB_NOTIFICATIONS_URL,
+): LinuxNotification {
+  const lines = notifications.slice(0, MAX_DIGEST_LINES).map((notification) => notification.title);
+  const hiddenCount = notifications.length - lines.length;
+
+  if (hiddenCount > 0) {
+    lines.push(`…and ${hiddenCount} more`);
+  }
+
+  const clickUrls = new Set(notifications.map((notification) => notification.clickUrl ?? null));
+  const [sharedClickUrl] = clickUrls;
+
+  return {
+    title: `${notifications.length} ${notifications.length === 1 ? "notification" : "notifications"} while you were away`,
+    body: lines.join("\n"),
+    clickUrl: clickUrls.size === 1 && sharedClickUrl ? sharedClickUrl : overflowClickUrl,
+    sticky: true,
+  };
+}
+
+export async function readDesktopSessionPresence(
+  runCommand: RunCommand = runCommandForOutput,
+): Promise<SessionPresence> {
+  const [logindPresence, screenCastActive, fullscreenActive] = await Promise.all([
+    readLogindSessionPresence(runCommand),
+    readScreenCastActive(runCommand),
+    readX11FullscreenActive(runCommand),
+  ]);
+
+  return {
+    ...logindPresence,
+    screenShared: screenCastActive || fullscreenActive,
+  };
+}
+
+export async function readLogindSessionPresence(
+  runCommand: RunCommand = runCommandForOutput,
+  sessionId = process.env.XDG_SESSION_ID ?? "auto",
+  now = Date.now(),
+): Promise<Omit<SessionPresence, "screenShared">> {
+  try {
+    const output = await runCommand("loginctl", [
+      "show-session",
+      sessionId,
+      "--property=LockedHint",
+      "--property=IdleHint",
+      "--property=IdleSinceHint",
+    ]);
+    const properties = new Map(
+      output
+        .split("\n")
+        .map((line) => line.trim().split("="))
+        .filter((parts): parts is [string, string] => parts.length === 2),
+    );
+    // IdleSinceHint is a CLOCK_REALTIME timestamp in microseconds.
+    const idleSinceMs = Number(properties.get("IdleSinceHint") ?? 0) / 1000;
+
+    return {
+      locked: properties.get("LockedHint") === "yes",
+      idleMs:
+        properties.get("IdleHint") === "yes" && idleSinceMs > 0
+          ? Math.max(0, now - idleSinceMs)
+          : 0,
+    };
+  } catch (error) {
+    getLogger().debug("Unable to read logind session presence", { error });
+    return { locked: false, idleMs: 0 };
+  }
+}
+
+// Portal screen casts (xdg-desktop-portal ScreenCast) surface as PipeWire video sources that,
+// unlike webcams, are not backed by a capture device.
+export async function readScreenCastActive(
+  runCommand: RunCommand = runCommandForOutput,
+): Promise<boolean> {
+  try {
+    const objects: unknown = JSON.parse(await runCommand("pw-dump", []));
+
+    return (
+      Array.isArray(objects) &&
+      objects.some((object) => {
+        const props = readPipeWireNodeProps(object);
+
+        return (
+          props !== null &&
+          props["media.class"] === "Video/Source" &&
+          props["device.api"] === undefined
+        );
+      })
+    );
+  } catch (error) {
+    getLogger().debug("Unable to read PipeWire screen-cast state", { error });
+    return false;
+  }
+}
+
+export async function readX11FullscreenActive(
+  runCommand: RunCommand = runCommandForOutput,
+): Promise<boolean> {
+  if (!process.env.DISPLAY) {
+    return false;
+  }
+
+  try {
+    const activeWindowOutput = await runCommand("xprop", ["-root", "_NET_ACTIVE_WINDOW"]);
+    const windowId = activeWindowOutput.match(/window id # (0x[0-9a-f]+)/i)?.[1];
+
+    if (!windowId || Number(windowId) === 0) {
+      return false;
+    }
+
+    const stateOutput = await runCommand("xprop", ["-id", windowId, "_NET_WM_STATE"]);
+
+    return stateOutput.includes("_NET_WM_STATE_FULLSCREEN");
+  } catch (error) {
+    getLogger().debug("Unable to read X11 fullscreen state", { error });
+    return false;
+  }
+}
+
+function readPipeWireNodeProps(value: unknown): Record<string, unknown> | null {
+  if (typeof value !== "object" || value === null) {
+    return null;
+  }
+
+  const object = value as { type?: unknown; info?: { props?: unknown } };
+
+  if (object.type !== "PipeWire:Interface:Node") {
+    return null;
+  }
+
+  const props = object.info?.props;
+
+  return typeof props === "object" && props !== null ? (props as Record<string, unknown>) : null;
+}
+
+function runCommandForOutput(command: string, args: readonly string[]): Promise<string> {
+  return new Promise((resolve, reject) => {
+    execFile(command, [...args], { timeout: 5_000 }, (error, stdout) => {
+      if (error) {
+        reject(error);
+      } else {
+        resolve(stdout);
+      }
+    });
+  });
+}
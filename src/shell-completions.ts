@@ -0,0 +1,172 @@
+export const COMPLETION_SHELLS = ["bash", "zsh", "fish"] as const;
+
+export type CompletionShell = (typeof COMPLETION_SHELLS)[number];
+
+// What completions and the man page describe: the usage line, then one entry per command and per
+// option, each with the term shown in help ("completions <shell>", "--config <path>").
+export interface CommandLineReference {
+  synopsis: string;
+  commands: readonly CommandReference[];
+  options: readonly OptionReference[];
+}
+
+export interface CommandReference {
+  name: string;
+  term: string;
+  description: string;
+}
+
+export interface OptionReference {
+  term: string;
+  description: string;
+}
+
+interface CompletionOption extends OptionReference {
+  flags: string[];
+  takesValue: boolean;
+}
+
+export function isCompletionShell(value: string): value is CompletionShell {
+  return COMPLETION_SHELLS.some((shell) => shell === value);
+}
+
+export function renderShellCompletions(
+  shell: CompletionShell,
+  reference: CommandLineReference,
+): string {
+  const options = reference.options.map((option) => ({
+    ...option,
+    flags: option.term.match(/-{1,2}[\w-]+/g) ?? [],
+    takesValue: option.term.includes("<"),
+  }));
+
+  switch (shell) {
+    case "bash":
+      return renderBashCompletions(
+        reference.commands.map((command) => command.name),
+        options.flatMap((option) => option.flags),
+      );
+    case "zsh":
+      return renderZshCompletions(reference.commands, options);
+    case "fish":
+      return renderFishCompletions(reference.commands, options);
+  }
+}
+
+export function renderManPage(reference: CommandLineReference): string {
+  const renderEntries = (entries: readonly { term: string; description: string }[]) =>
+    entries.flatMap((entry) => [
+      ".TP",
+      `\\fB${escapeRoff(entry.term)}\\fR`,
+      escapeRoff(entry.description),
+    ]);
+
+  return [
+    '.TH OCTOPULSE 1 "" "octopulse" "User Commands"',
+    ".SH NAME",
+    "octopulse \\- GitHub pull request notifications for the Linux desktop",
+    ".SH SYNOPSIS",
+    escapeRoff(reference.synopsis),
+    ".SH COMMANDS",
+    ...renderEntries(reference.commands),
+    ...(reference.options.length === 0
+      ? []
+      : [".SH OPTIONS", ...renderEntries(reference.options)]),
+    ".SH FILES",
+    ".TP",
+    escapeRoff("~/.config/octopulse/config.toml"),
+    "Default config file",
+  ].join("\n");
+}
+
+function renderBashCompletions(commands: readonly string[], flags: readonly string[]): string {
+  return [
+    "_octopulse() {",
+    '  local cur="${COMP_WORDS[COMP_CWORD]}"',
+    `  local words="${[...commands, ...flags].join(" ")}"`,
+    '  COMPREPLY=($(compgen -W "$words" -- "$cur"))',
+    "}",
+    "complete -o default -F _octopulse octopulse",
+  ].join("\n");
+}
+
+function renderZshCompletions(
+  commands: readonly CommandReference[],
+  options: readonly CompletionOption[],
+): string {
+  const optionSpecs = options.flatMap((option) =>
+    option.flags.map((flag) => {
+      const argumentSpec = option.takesValue ? ":value:_files" : "";
+      const spec = `${flag}[${escapeZshDescription(option.description)}]${argumentSpec}`;
+      return `    ${quoteShell(spec)} \\`;
+    }),
+  );
+
+  return [
+    "#compdef octopulse",
+    "",
+    "_octopulse() {",
+    "  local -a commands",
+    "  commands=(",
+    ...commands.map((command) => `    ${quoteShell(`${command.name}:${command.description}`)}`),
+    "  )",
+    "",
+    "  _arguments -C \\",
+    ...optionSpecs,
+    "    '1: :->command' \\",
+    "    '*:: :_files'",
+    "",
+    "  if [[ $state == command ]]; then",
+    "    _describe command commands",
+    "  fi",
+    "}",
+    "",
+    '_octopulse "$@"',
+  ].join("\n");
+}
+
+function renderFishCompletions(
+  commands: readonly CommandReference[],
+  options: readonly CompletionOption[],
+): string {
+  return [
+    "complete -c octopulse -f",
+    ...commands.map((command) =>
+      [
+        "complete -c octopulse -n __fish_use_subcommand",
+        `-a ${command.name}`,
+        `-d ${quoteFish(command.description)}`,
+      ].join(" "),
+    ),
+    ...options.map((option) =>
+      [
+        "complete -c octopulse",
+        ...option.flags.map((flag) =>
+          flag.startsWith("--") ? `-l ${flag.slice(2)}` : `-s ${flag.slice(1)}`,
+        ),
+        ...(option.takesValue ? ["-r -F"] : []),
+        `-d ${quoteFish(option.description)}`,
+      ].join(" "),
+    ),
+  ].join("\n");
+}
+
+// Brackets and colons would end an _arguments option description early.
+function escapeZshDescription(value: string): string {
+  return value.replace(/([[\]:])/g, "\\$1");
+}
+
+function quoteShell(value: string): string {
+  return `'${value.replace(/'/g, "'\\''")}'`;
+}
+
+function quoteFish(value: string): string {
+  return `'${value.replace(/\\/g, "\\\\").replace(/'/g, "\\'")}'`;
+}
+
+function escapeRoff(value: string): string {
+  return value
+    .replace(/\\/g, "\\e")
+    .replace(/-/g, "\\-")
+    .replace(/^([.'])/, "\\&$1");
+}
@@ -0,0 +1,70 @@
+import { DEFAULT_SERVER_HOST, DEFAULT_SERVER_PORT } from "./server.js";
+
+const SHORTCUT_USAGE =
+  "Usage: octopulse shortcut open-latest | octopulse shortcut open-inbox | octopulse shortcut toggle-pause";
+const SHORTCUT_ACTIONS = ["open-latest", "open-inbox", "toggle-pause"] as const;
+
+export type ShortcutAction = (typeof SHORTCUT_ACTIONS)[number];
+
+export interface RunShortcutCommandOptions {
+  serverOrigin?: string;
+  fetchImpl?: typeof fetch;
+}
+
+export class ShortcutCommandError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "ShortcutCommandError";
+  }
+}
+
+// Desktop environments bind global keys to commands, so each shortcut is a tiny client that
+// asks the running service to act.
+export async function runShortcutCommand(
+  args: readonly string[],
+  options: RunShortcutCommandOptions = {},
+): Promise<string> {
+  const [action, ...extraArgs] = args;
+
+  if (!isShortcutAction(action) || extraArgs.length > 0) {
+    throw new ShortcutCommandError(SHORTCUT_USAGE);
+  }
+
+  const serverOrigin =
+    options.serverOrigin ?? `http://${DEFAULT_SERVER_HOST}:${DEFAULT_SERVER_PORT}`;
+  const response = await (options.fetchImpl ?? fetch)(`${serverOrigin}/api/shortcuts/${action}`, {
+    method: "POST",
+  });
+  const payload = (await response.json()) as {
+    error?: string;
+    openedUrl?: string | null;
+    paused?: boolean;
+  };
+
+  if (!response.ok) {
+    throw new ShortcutCommandError(payload.error ?? `Shortcut failed with status ${response.status}`);
+  }
+
+  if (action === "open-latest") {
+    return payload.openedUrl ? `Opened ${payload.openedUrl}` : "No recent notification to open.";
+  }
+
+  if (action === "open-inbox") {
+    return `Opened ${payload.openedUrl ?? "inbox"}`;
+  }
+
+  return payload.paused ? "Notifications paused." : "Notifications resumed.";
+}
+
+function isShortcutAction(value: string | undefined): value is ShortcutAction {
+  return SHORTCUT_ACTIONS.some((action) => action === value);
+}
+
+if (import.meta.url === new URL(process.argv[1] ?? "", "file:").href) {
+  try {
+    console.log(await runShortcutCommand(process.argv.slice(2)));
+  } catch (error) {
+    console.error(error instanceof Error ? error.message : String(error));
+    process.exitCode = 1;
+  }
+}
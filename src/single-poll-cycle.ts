@@ -0,0 +1,99 @@
+import type { DatabaseSync } from "node:sqlite";
+
+import type { Octokit } from "octokit";
+
+import {
+  discoverOpenAuthoredPullRequests,
+  type DiscoverOpenAuthoredPullRequestsOptions,
+} from "./authored-pull-request-discovery.js";
+import type { GitHubAuthContext } from "./github.js";
+import { getLogger } from "./logger.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+import {
+  pollTrackedPullRequests,
+  type PollTrackedPullRequestsOptions,
+} from "./tracked-pull-request-polling.js";
+
+// Exit statuses for `--once`, so cron jobs and scripts can tell a quiet cycle from a broken one.
+// 2 stays reserved for command-line usage errors.
+export const SINGLE_POLL_CYCLE_EXIT_CODES = {
+  delivered: 0,
+  failed: 1,
+  nothingNew: 3,
+} as const;
+
+export interface RunSinglePollCycleOptions<TClient = Octokit> {
+  notificationDispatcher: NotificationDispatcher;
+  discovery?: Omit<DiscoverOpenAuthoredPullRequestsOptions<TClient>, "notificationDispatcher">;
+  polling?: Omit<PollTrackedPullRequestsOptions<TClient>, "notificationDispatcher">;
+}
+
+export interface SinglePollCycleResult {
+  discoveredCount: number;
+  discoveryFailed: boolean;
+  polledCount: number;
+  failedCount: number;
+  deliveredCount: number;
+}
+
+// Runs one discovery pass and one tracked pull request poll, then returns. A discovery failure
+// doesn't stop the poll, so pull requests that are already tracked are still checked.
+export async function runSinglePollCycle<TClient>(
+  database: DatabaseSync,
+  githubAuth: GitHubAuthContext<TClient>,
+  options: RunSinglePollCycleOptions<TClient>,
+): Promise<SinglePollCycleResult> {
+  let deliveredCount = 0;
+  const notificationDispatcher: NotificationDispatcher = {
+    dispatchNotification: async (notification) => {
+      const result = await options.notificationDispatcher.dispatchNotification(notification);
+      deliveredCount += 1;
+      return result;
+    },
+  };
+  let discoveredCount = 0;
+  let discoveryFailed = false;
+
+  try {
+    ({ discoveredCount } = await discoverOpenAuthoredPullRequests(database, githubAuth, {
+      ...options.discovery,
+      notificationDispatcher,
+    }));
+  } catch (error) {
+    discoveryFailed = true;
+    getLogger().error("Pull request discovery failed", { error });
+  }
+
+  const { polledCount, failedCount } = await pollTrackedPullRequests(database, githubAuth, {
+    ...options.polling,
+    notificationDispatcher,
+  });
+
+  return { discoveredCount, discoveryFailed, polledCount, failedCount, deliveredCount };
+}
+
+// A failure wins over deliveries, so a cron job notices pull requests that weren't checked.
+export function readSinglePollCycleExitCode(result: SinglePollCycleResult): number {
+  if (result.discoveryFailed || result.failedCount > 0) {
+    return SINGLE_POLL_CYCLE_EXIT_CODES.failed;
+  }
+
+  return result.deliveredCount > 0
+    ? SINGLE_POLL_CYCLE_EXIT_CODES.delivered
+    : SINGLE_POLL_CYCLE_EXIT_CODES.nothingNew;
+}
+
+export function formatSinglePollCycleResult(result: SinglePollCycleResult): string {
+  const failures = [
+    ...(result.discoveryFailed ? ["discovery failed"] : []),
+    ...(result.failedCount > 0 ? [`${result.failedCount} failed`] : []),
+  ];
+
+  return [
+    `Delivered ${result.deliveredCount} notification${result.deliveredCount === 1 ? "" : "s"}`,
+    `from ${result.polledCount} polled pull request${result.polledCount === 1 ? "" : "s"}`,
+    failures.length > 0 ? `(${failures.join(", ")})` : "",
+  ]
+    .filter((part) => part.length > 0)
+    .join(" ");
+}
@@ -0,0 +1,35 @@
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+
+export interface SoundCooldownOptions {
+  cooldownMs: number;
+  now?: () => number;
+}
+
+// Plays at most one notification sound per cooldown so a burst of popups doesn't become a burst of
+// chimes. Popups inside the cooldown are still shown, just without their sound.
+export function withSoundCooldown(
+  notificationDispatcher: NotificationDispatcher,
+  options: SoundCooldownOptions,
+): NotificationDispatcher {
+  const now = options.now ?? Date.now;
+  let lastSoundAt: number | undefined;
+
+  return {
+    async dispatchNotification(notification) {
+      if (notification.soundFile === undefined) {
+        return notificationDispatcher.dispatchNotification(notification);
+      }
+
+      const currentTime = now();
+
+      if (lastSoundAt !== undefined && currentTime - lastSoundAt < options.cooldownMs) {
+        const { soundFile: _soundFile, ...silentNotification } = notification;
+
+        return notificationDispatcher.dispatchNotification(silentNotification);
+      }
+
+      lastSoundAt = currentTime;
+      return notificationDispatcher.dispatchNotification(notification);
+    },
+  };
+}
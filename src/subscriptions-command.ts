@@ -0,0 +1,109 @@
+import { Octokit } from "octokit";
+
+const GITHUB_API_HEADERS = {
+  "X-GitHub-Api-Version": "2022-11-28",
+};
+const SUBSCRIPTIONS_PAGE_SIZE = 100;
+const SUBSCRIPTIONS_USAGE = "Usage: octopulse subs list | octopulse subs unsubscribe <owner/repo|thread-id>";
+
+export type SubscriptionTarget =
+  | { kind: "repository"; repositoryOwner: string; repositoryName: string }
+  | { kind: "thread"; threadId: number };
+
+export interface SubscriptionsClient {
+  listWatchedRepositories(): Promise<string[]>;
+  unsubscribe(target: SubscriptionTarget): Promise<void>;
+}
+
+export class SubscriptionsCommandError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "SubscriptionsCommandError";
+  }
+}
+
+export async function runSubscriptionsCommand(
+  args: readonly string[],
+  client: SubscriptionsClient,
+): Promise<string> {
+  const [command, targetValue, ...extraArgs] = args;
+
+  if (command === "list" && targetValue === undefined) {
+    const repositories = await client.listWatchedRepositories();
+
+    return repositories.length === 0
+      ? "No watched repositories."
+      : repositories.sort((left, right) => left.localeCompare(right)).join("\n");
+  }
+
+  if (command === "unsubscribe" && targetValue !== undefined && extraArgs.length === 0) {
+    const target = parseSubscriptionTarget(targetValue);
+    await client.unsubscribe(target);
+
+    return target.kind === "repository"
+      ? `Stopped watching ${target.repositoryOwner}/${target.repositoryName}`
+      : `Unsubscribed from notification thread ${target.threadId}`;
+  }
+
+  throw new SubscriptionsCommandError(SUBSCRIPTIONS_USAGE);
+}
+
+export function parseSubscriptionTarget(value: string): SubscriptionTarget {
+  const trimmedValue = value.trim();
+
+  if (/^\d+$/.test(trimmedValue)) {
+    return { kind: "thread", threadId: Number(trimmedValue) };
+  }
+
+  const match = trimmedValue.match(/^([\w.-]+)\/([\w.-]+)$/);
+  const repositoryOwner = match?.[1];
+  const repositoryName = match?.[2];
+
+  if (!repositoryOwner || !repositoryName) {
+    throw new SubscriptionsCommandError(
+      `Expected owner/repo or a numeric thread id, received "${value}"`,
+    );
+  }
+
+  return { kind: "repository", repositoryOwner, repositoryName };
+}
+
+export function createGitHubSubscriptionsClient(client: Octokit): SubscriptionsClient {
+  return {
+    async listWatchedRepositories(): Promise<string[]> {
+      const repositories: string[] = [];
+
+      for (let page = 1; ; page += 1) {
+        const response = await client.request("GET /user/subscriptions", {
+          per_page: SUBSCRIPTIONS_PAGE_SIZE,
+          page,
+          headers: GITHUB_API_HEADERS,
+        });
+
+        for (const repository of response.data) {
+          repositories.push(repository.full_name);
+        }
+
+        if (response.data.length < SUBSCRIPTIONS_PAGE_SIZE) {
+          return repositories;
+        }
+      }
+    },
+    async unsubscribe(target: SubscriptionTarget): Promise<void> {
+      if (target.kind === "repository") {
+        await client.request("DELETE /repos/{owner}/{repo}/subscription", {
+          owner: target.repositoryOwner,
+          repo: target.repositoryName,
+          headers: GITHUB_API_HEADERS,
+        });
+        return;
+      }
+
+      await client.request("DELETE /notifications/threads/{thread_id}/subscription", {
+        thread_id: target.threadId,
+        headers: GITHUB_API_HEADERS,
+      });
+    },
+  };
+}
+
@@ -0,0 +1,104 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { resolveAppPaths } from "./config.js";
+import { initializeDatabase } from "./database.js";
+import { LinuxNotificationAdapter } from "./linux-notification-adapter.js";
+import { NormalizedEventRepository } from "./normalized-event-repository.js";
+import {
+  dispatchPullRequestNotifications,
+  type NotificationDispatcher,
+  type NotificationSounds,
+} from "./notification-dispatch.js";
+import { PullRequestRepository } from "./pull-request-repository.js";
+
+const TEST_USER_LOGIN = "octocat";
+const TEST_PULL_REQUEST_URL = "https://github.com/octocat/Hello-World/pull/1";
+
+export interface RunTestNotificationCommandOptions {
+  notificationDispatcher?: NotificationDispatcher;
+  notificationSounds?: NotificationSounds;
+}
+
+export class TestNotificationCommandError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "TestNotificationCommandError";
+  }
+}
+
+// Shows an approval on a made-up pull request, built by the same code as real popups, then a plain
+// notification, so the notification server, icons, avatars, and sounds can be checked without
+// waiting for GitHub activity. Nothing is written to the real database.
+export async function runTestNotificationCommand(
+  args: readonly string[],
+  options: RunTestNotificationCommandOptions = {},
+): Promise<string> {
+  if (args.length > 0) {
+    throw new TestNotificationCommandError("Usage: octopulse test-notification");
+  }
+
+  const notificationDispatcher = options.notificationDispatcher ?? new LinuxNotificationAdapter();
+  const homeDirPath = mkdtempSync(path.join(os.tmpdir(), "octopulse-test-notification-"));
+  const database = initializeDatabase(resolveAppPaths({ homeDir: homeDirPath }));
+
+  try {
+    const pullRequest = new PullRequestRepository(database).upsertPullRequest({
+      githubPullRequestId: 1,
+      repositoryOwner: "octocat",
+      repositoryName: "Hello-World",
+      number: 1,
+      url: TEST_PULL_REQUEST_URL,
+      authorLogin: TEST_USER_LOGIN,
+      authorAvatarUrl: "https://avatars.githubusercontent.com/u/583231",
+      title: "Octopulse test notification",
+      state: "open",
+      isDraft: false,
+      headBranch: "octopulse-test",
+      baseBranch: "main",
+    });
+    new NormalizedEventRepository(database).insertNormalizedEvent({
+      pullRequestId: pullRequest.id,
+      eventType: "review_approved",
+      actorLogin: "github",
+      actorClass: "human_other",
+      decisionState: "notified",
+      notificationTiming: "immediate",
+      payloadJson: JSON.stringify({
+        actorAvatarUrl: "https://avatars.githubusercontent.com/u/9919",
+      }),
+      occurredAt: new Date().toISOString(),
+    });
+
+    const errors: Error[] = [];
+    const result = await dispatchPullRequestNotifications(database, pullRequest, {
+      currentUserLogin: TEST_USER_LOGIN,
+      notificationDispatcher,
+      ...(options.notificationSounds ? { notificationSounds: options.notificationSounds } : {}),
+      onError: (error) => {
+        errors.push(error);
+      },
+    });
+
+    if (errors.length > 0 || result.dispatchedCount === 0) {
+      const reason = errors.map((error) => error.message).join("; ") || "nothing was sent";
+
+      throw new TestNotificationCommandError(`The pull request notification failed: ${reason}`);
+    }
+
+    await notificationDispatcher.dispatchNotification({
+      title: "Octopulse test notification",
+      body: "Popups from Octopulse show up here. Click to open your GitHub notifications.",
+      clickUrl: "https://github.com/notifications",
+    });
+
+    return [
+      "Sent a pull request approval and a plain notification.",
+      "The approval should show the pull request icon and avatars, and play the approval sound.",
+    ].join("\n");
+  } finally {
+    database.close();
+    rmSync(homeDirPath, { recursive: true, force: true });
+  }
+}
@@ -6,18 +6,32 @@ import {
   classifyBotPullRequestActivity,
   type BotActivityClassifier,
 } from "./bot-activity-classification.js";
+import { CodeownersCache, type CodeownersRule } from "./codeowners.js";
 import { bundlePullRequestEvents } from "./event-bundling.js";
 import { getLogger } from "./logger.js";
+import type { PullRequestActionProvider } from "./notification-actions.js";
+import type { NotificationChannel } from "./notification-channels.js";
 import {
   dispatchPullRequestNotifications,
   type NotificationDispatcher,
+  type NotificationSounds,
 } from "./notification-dispatch.js";
 import { preparePullRequestNotifications } from "./notification-preparation.js";
+import {
+  applyNotificationRules,
+  hasPathRules,
+  type NotificationRules,
+} from "./notification-rules.js";
 import {
   ingestPullRequestActivity,
   type IngestPullRequestActivityOptions,
 } from "./pull-request-activity-ingestion.js";
 import { normalizePullRequestActivity } from "./pull-request-activity-normalization.js";
+import { fetchChangedFilePathsFromGitHub } from "./pull-request-changed-files.js";
+import {
+  fetchLinkedIssuesFromGitHub,
+  type LinkedIssue,
+} from "./pull-request-linked-issues.js";
 import {
   createPullRequestUpsertInput,
   mapPullRequestSnapshot,
@@ -27,6 +41,7 @@ import {
   type PullRequestRecord,
 } from "./pull-request-repository.js";
 import { PullRequestReviewStateRepository } from "./pull-request-review-state-repository.js";
+import type { RepositoryOverride } from "./repository-overrides.js";
 
 const GITHUB_API_HEADERS = {
   "X-GitHub-Api-Version": "2022-11-28",
@@ -39,7 +54,20 @@ export interface ProcessTrackedPullRequestActivityOptions<TClient = Octokit> {
   botActivityClassifier?: BotActivityClassifier;
   notificationDispatcher?: NotificationDispatcher;
   notificationDispatchedAt?: string;
+  immediateCiFailures?: boolean;
+  maxFetchedComments?: IngestPullRequestActivityOptions<TClient>["maxFetchedComments"];
+  activityApi?: IngestPullRequestActivityOptions<TClient>["activityApi"];
   fetchJobsForWorkflowRun?: IngestPullRequestActivityOptions<TClient>["fetchJobsForWorkflowRun"];
+  fetchLinkedIssues?: (client: TClient, pullRequest: PullRequestRecord) => Promise<LinkedIssue[]>;
+  // Only set when a GitHub Project board is configured.
+  fetchProjectStatus?: (client: TClient, pullRequest: PullRequestRecord) => Promise<string | null>;
+  notificationRules?: NotificationRules;
+  notificationChannels?: NotificationChannel[];
+  notificationSounds?: NotificationSounds;
+  repositoryOverrides?: RepositoryOverride[];
+  pullRequestActions?: PullRequestActionProvider;
+  fetchChangedFilePaths?: (client: TClient, pullRequest: PullRequestRecord) => Promise<string[]>;
+  loadCodeowners?: (client: TClient, pullRequest: PullRequestRecord) => Promise<CodeownersRule[] | null>;
 }
 
 export interface ProcessTrackedPullRequestActivityResult {
@@ -54,16 +82,36 @@ export async function processTrackedPullRequestActivity<TClient>(
   options: ProcessTrackedPullRequestActivityOptions<TClient>,
 ): Promise<ProcessTrackedPullRequestActivityResult> {
   const pullRequestRepository = options.pullRequestRepository ?? new PullRequestRepository(database);
+  const fetchLinkedIssues =
+    options.fetchLinkedIssues ??
+    ((linkedIssuesClient: TClient, linkedIssuesPullRequest: PullRequestRecord) =>
+      fetchLinkedIssuesFromGitHub(linkedIssuesClient as unknown as Octokit, linkedIssuesPullRequest));
+  const notificationRules = options.notificationRules;
+  const notificationChannels = options.notificationChannels ?? [];
+  // Changed files only matter to path-based rules and channels, so skip the extra requests otherwise.
+  const fetchChangedFilePaths =
+    (notificationRules && hasPathRules(notificationRules)) || notificationChannels.length > 0
+      ? (options.fetchChangedFilePaths ??
+        ((changedFilesClient: TClient, changedFilesPullRequest: PullRequestRecord) =>
+          fetchChangedFilePathsFromGitHub(changedFilesClient as unknown as Octokit, changedFilesPullRequest)))
+      : undefined;
   const { pullRequest: refreshedPullRequest, skipActivityFanout } = await refreshPullRequestForActivity(
     database,
     client,
     pullRequest,
     pullRequestRepository,
+    fetchLinkedIssues,
+    options.fetchProjectStatus,
+    fetchChangedFilePaths,
   );
   const activityIngestionOptions: IngestPullRequestActivityOptions<TClient> = {
     ...(skipActivityFanout
       ? buildSkippedPullRequestActivityFetchOptions<TClient>(database, refreshedPullRequest.id)
       : {}),
+    ...(options.maxFetchedComments === undefined
+      ? {}
+      : { maxFetchedComments: options.maxFetchedComments }),
+    ...(options.activityApi ? { activityApi: options.activityApi } : {}),
     ...(options.fetchJobsForWorkflowRun
       ? { fetchJobsForWorkflowRun: options.fetchJobsForWorkflowRun }
       : {}),
@@ -75,7 +123,26 @@ export async function processTrackedPullRequestActivity<TClient>(
     refreshedPullRequest,
     activityIngestionOptions,
   );
-  normalizePullRequestActivity(database, refreshedPullRequest, options.currentUserLogin);
+  normalizePullRequestActivity(database, refreshedPullRequest, options.currentUserLogin, {
+    ...(options.immediateCiFailures ? { immediateCiFailures: true } : {}),
+  });
+
+  if (notificationRules) {
+    const loadCodeowners = options.loadCodeowners ?? loadCodeownersFromGitHub(database);
+
+    try {
+      await applyNotificationRules(database, refreshedPullRequest, {
+        rules: notificationRules,
+        currentUserLogin: options.currentUserLogin,
+        loadCodeowners: (rulesPullRequest) => loadCodeowners(client, rulesPullRequest),
+      });
+    } catch (error) {
+      getLogger().warn("Notification rules failed", {
+        pullRequest: formatPullRequestLabel(refreshedPullRequest),
+        error,
+      });
+    }
+  }
 
   try {
     await classifyBotPullRequestActivity(database, refreshedPullRequest.id, {
@@ -99,9 +166,15 @@ export async function processTrackedPullRequestActivity<TClient>(
         : {}),
       currentUserLogin: options.currentUserLogin,
       notificationDispatcher: options.notificationDispatcher,
+      ...(notificationChannels.length > 0 ? { notificationChannels } : {}),
+      ...(options.notificationSounds ? { notificationSounds: options.notificationSounds } : {}),
+      ...(options.repositoryOverrides ? { repositoryOverrides: options.repositoryOverrides } : {}),
+      ...(options.pullRequestActions ? { pullRequestActions: options.pullRequestActions } : {}),
     });
   } else {
-    preparePullRequestNotifications(database, refreshedPullRequest);
+    preparePullRequestNotifications(database, refreshedPullRequest, {
+      currentUserLogin: options.currentUserLogin,
+    });
   }
 
   return {
@@ -115,6 +188,9 @@ async function refreshPullRequestForActivity<TClient>(
   client: TClient,
   pullRequest: PullRequestRecord,
   pullRequestRepository: Pick<PullRequestRepository, "upsertPullRequest">,
+  fetchLinkedIssues: (client: TClient, pullRequest: PullRequestRecord) => Promise<LinkedIssue[]>,
+  fetchProjectStatus: ((client: TClient, pullRequest: PullRequestRecord) => Promise<string | null>) | undefined,
+  fetchChangedFilePaths: ((client: TClient, pullRequest: PullRequestRecord) => Promise<string[]>) | undefined,
 ): Promise<ProcessTrackedPullRequestActivityResult> {
   const storedEtag = readPullRequestDetailEtag(database, pullRequest.id);
   const response = await requestPullRequestDetailFromGitHub(
@@ -149,9 +225,33 @@ async function refreshPullRequestForActivity<TClient>(
     },
     (message) => new Error(message),
   );
-  const refreshedPullRequest = pullRequestRepository.upsertPullRequest(
-    createPullRequestUpsertInput(detail),
+  const linkedIssues = await loadPullRequestDecoration(
+    () => fetchLinkedIssues(client, pullRequest),
+    pullRequest,
+    "Failed to resolve linked issues",
   );
+  const projectStatus =
+    fetchProjectStatus === undefined
+      ? undefined
+      : await loadPullRequestDecoration(
+          () => fetchProjectStatus(client, pullRequest),
+          pullRequest,
+          "Failed to read project board status",
+        );
+  const changedFilePaths =
+    fetchChangedFilePaths === undefined
+      ? undefined
+      : await loadPullRequestDecoration(
+          () => fetchChangedFilePaths(client, pullRequest),
+          pullRequest,
+          "Failed to list changed files",
+        );
+  const refreshedPullRequest = pullRequestRepository.upsertPullRequest({
+    ...createPullRequestUpsertInput(detail),
+    ...(linkedIssues === undefined ? {} : { linkedIssues }),
+    ...(projectStatus === undefined ? {} : { projectStatus }),
+    ...(changedFilePaths === undefined ? {} : { changedFilePaths }),
+  });
 
   writePullRequestDetailEtag(database, refreshedPullRequest.id, response.etag);
 
@@ -161,6 +261,40 @@ async function refreshPullRequestForActivity<TClient>(
   };
 }
 
+// Linked issues, board status, and changed files are best-effort; a failed lookup keeps whatever
+// was stored last time.
+async function loadPullRequestDecoration<T>(
+  load: () => Promise<T>,
+  pullRequest: PullRequestRecord,
+  failureMessage: string,
+): Promise<T | undefined> {
+  try {
+    return await load();
+  } catch (error) {
+    getLogger().warn(failureMessage, {
+      pullRequest: formatPullRequestLabel(pullRequest),
+      error,
+    });
+    return undefined;
+  }
+}
+
+function loadCodeownersFromGitHub<TClient>(
+  database: DatabaseSync,
+): (client: TClient, pullRequest: PullRequestRecord) => Promise<CodeownersRule[] | null> {
+  return async (client, pullRequest) => {
+    if (pullRequest.baseBranch === null) {
+      return null;
+    }
+
+    return new CodeownersCache(database, client as unknown as Octokit).getRules(
+      pullRequest.repositoryOwner,
+      pullRequest.repositoryName,
+      pullRequest.baseBranch,
+    );
+  };
+}
+
 function buildSkippedPullRequestActivityFetchOptions<TClient>(
   database: DatabaseSync,
   pullRequestId: number,
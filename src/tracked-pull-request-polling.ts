@@ -2,14 +2,21 @@ import { DatabaseSync } from "node:sqlite";
 
 import { Octokit } from "octokit";
 
+import type { AvatarImageCache } from "./avatar-cache.js";
 import {
   type BotActivityClassifier,
 } from "./bot-activity-classification.js";
 import type { GitHubAuthContext } from "./github.js";
 import { getLogger } from "./logger.js";
+import { NormalizedEventRepository } from "./normalized-event-repository.js";
 import {
+  dispatchPullRequestNotifications,
   type NotificationDispatcher,
 } from "./notification-dispatch.js";
+import { buildNotificationParagraph } from "./notification-rendering.js";
+import { runOrderedPipeline } from "./ordered-pipeline.js";
+import type { PollerBackoff } from "./poller-state.js";
+import { collectRepositorySummaries } from "./repository-summaries.js";
 import {
   processTrackedPullRequestActivity,
   type ProcessTrackedPullRequestActivityOptions,
@@ -19,6 +26,8 @@ import {
   type PullRequestRecord,
 } from "./pull-request-repository.js";
 
+const DEFAULT_POLL_CONCURRENCY = 4;
+
 export interface PollTrackedPullRequestsOptions<TClient = Octokit> {
   pullRequestRepository?: Pick<PullRequestRepository, "listPullRequestsForPolling" | "upsertPullRequest">;
   pollPullRequest?: (client: TClient, pullRequest: PullRequestRecord) => Promise<void>;
@@ -26,8 +35,24 @@ export interface PollTrackedPullRequestsOptions<TClient = Octokit> {
   notificationDispatcher?: NotificationDispatcher;
   observedAt?: string;
   notificationDispatchedAt?: string;
+  concurrency?: number;
+  immediateCiFailures?: boolean;
+  maxFetchedComments?: ProcessTrackedPullRequestActivityOptions<TClient>["maxFetchedComments"];
+  activityApi?: ProcessTrackedPullRequestActivityOptions<TClient>["activityApi"];
   onError?: (error: PullRequestPollingError) => void;
   fetchJobsForWorkflowRun?: ProcessTrackedPullRequestActivityOptions<TClient>["fetchJobsForWorkflowRun"];
+  fetchLinkedIssues?: ProcessTrackedPullRequestActivityOptions<TClient>["fetchLinkedIssues"];
+  fetchProjectStatus?: ProcessTrackedPullRequestActivityOptions<TClient>["fetchProjectStatus"];
+  notificationRules?: ProcessTrackedPullRequestActivityOptions<TClient>["notificationRules"];
+  notificationChannels?: ProcessTrackedPullRequestActivityOptions<TClient>["notificationChannels"];
+  notificationSounds?: ProcessTrackedPullRequestActivityOptions<TClient>["notificationSounds"];
+  repositoryOverrides?: ProcessTrackedPullRequestActivityOptions<TClient>["repositoryOverrides"];
+  pullRequestActions?: ProcessTrackedPullRequestActivityOptions<TClient>["pullRequestActions"];
+  // Show one summary popup per repository for everything a poll cycle found.
+  groupByRepository?: boolean;
+  // The desktop adapter's cache. Avatars for the author and the actors about to be notified are
+  // downloaded alongside the GitHub fetches, so popups don't wait on them one by one.
+  avatarCache?: AvatarImageCache;
 }
 
 export interface PollTrackedPullRequestsResult {
@@ -39,15 +64,33 @@ export interface PollTrackedPullRequestsResult {
 export interface StartRecurringTrackedPullRequestPollingOptions<TClient = Octokit>
   extends PollTrackedPullRequestsOptions<TClient> {
   intervalMs: number;
+  // Called after every cycle with the error that failed it, or null when it succeeded. A cycle in
+  // which every eligible pull request failed counts as failed.
+  onCycleSettled?: (failure: PullRequestPollingError | null) => void;
+  // Defers cycles after repeated failures or while the rate limit is exhausted.
+  backoff?: PollerBackoff;
 }
 
-export interface RecurringTrackedPullRequestPollingHandle {
+// The settings a config file reload can change while polling keeps running.
+export type TrackedPullRequestNotificationSettings<TClient = Octokit> = Pick<
+  PollTrackedPullRequestsOptions<TClient>,
+  "notificationRules" | "notificationChannels" | "notificationSounds" | "repositoryOverrides"
+>;
+
+export interface RecurringTrackedPullRequestPollingHandle<TClient = Octokit> {
+  pollNow(): void;
+  // Both apply from the next cycle on.
+  setIntervalMs(intervalMs: number): void;
+  setNotificationSettings(settings: TrackedPullRequestNotificationSettings<TClient>): void;
+  // Skips cycles until resumed, e.g. while GitHub rejects the token.
+  pause(): void;
+  resume(): void;
   stop(): void;
 }
 
 export class PullRequestPollingError extends Error {
-  constructor(message: string) {
-    super(message);
+  constructor(message: string, options?: ErrorOptions) {
+    super(message, options);
     this.name = "PullRequestPollingError";
   }
 }
@@ -59,28 +102,44 @@ export async function pollTrackedPullRequests<TClient>(
 ): Promise<PollTrackedPullRequestsResult> {
   const pullRequestRepository = options.pullRequestRepository ?? new PullRequestRepository(database);
   const botActivityClassifier = options.botActivityClassifier;
-  const notificationDispatcher = options.notificationDispatcher;
+  const repositorySummaries =
+    options.notificationDispatcher && options.groupByRepository
+      ? collectRepositorySummaries(options.notificationDispatcher)
+      : undefined;
+  const notificationDispatcher = repositorySummaries ?? options.notificationDispatcher;
   const observedAt = options.observedAt ?? new Date().toISOString();
   const notificationDispatchedAt = options.notificationDispatchedAt ?? new Date().toISOString();
-  const defaultPollPullRequest =
-    options.pollPullRequest ??
-    (async (client: TClient, pullRequest: PullRequestRecord) => {
-      await processTrackedPullRequestActivity(database, client, pullRequest, {
-        currentUserLogin: githubAuth.currentUserLogin,
-        pullRequestRepository,
-        ...(botActivityClassifier ? { botActivityClassifier } : {}),
-        ...(notificationDispatcher
-          ? {
-              notificationDispatcher,
-              notificationDispatchedAt,
-            }
-          : {}),
-        ...(options.fetchJobsForWorkflowRun
-          ? { fetchJobsForWorkflowRun: options.fetchJobsForWorkflowRun }
-          : {}),
-      });
-    });
-  const pollPullRequest = defaultPollPullRequest;
+  // GitHub fetches and avatar downloads run concurrently; notifications are dispatched afterwards
+  // one pull request at a time, in polling order, so popups never interleave.
+  const fetchPullRequest =
+    options.pollPullRequest === undefined
+      ? async (client: TClient, pullRequest: PullRequestRecord) => {
+          const result = await processTrackedPullRequestActivity(database, client, pullRequest, {
+            currentUserLogin: githubAuth.currentUserLogin,
+            pullRequestRepository,
+            ...(botActivityClassifier ? { botActivityClassifier } : {}),
+            ...(options.immediateCiFailures ? { immediateCiFailures: true } : {}),
+            ...(options.maxFetchedComments === undefined
+              ? {}
+              : { maxFetchedComments: options.maxFetchedComments }),
+            ...(options.activityApi ? { activityApi: options.activityApi } : {}),
+            ...(options.fetchJobsForWorkflowRun
+              ? { fetchJobsForWorkflowRun: options.fetchJobsForWorkflowRun }
+              : {}),
+            ...(options.fetchLinkedIssues ? { fetchLinkedIssues: options.fetchLinkedIssues } : {}),
+            ...(options.fetchProjectStatus ? { fetchProjectStatus: options.fetchProjectStatus } : {}),
+            ...(options.notificationRules ? { notificationRules: options.notificationRules } : {}),
+            ...(options.notificationChannels
+              ? { notificationChannels: options.notificationChannels }
+              : {}),
+          });
+
+          return result.pullRequest;
+        }
+      : async (client: TClient, pullRequest: PullRequestRecord) => {
+          await options.pollPullRequest?.(client, pullRequest);
+          return null;
+        };
   const onError = options.onError ?? logTrackedPullRequestPollingError;
 
   let pullRequests: PullRequestRecord[];
@@ -105,21 +164,65 @@ export async function pollTrackedPullRequests<TClient>(
   let polledCount = 0;
   let failedCount = 0;
 
-  for (const pullRequest of pullRequests) {
-    try {
-      await pollPullRequest(githubAuth.client, pullRequest);
-      polledCount += 1;
-      getLogger().debug("Polled tracked pull request", {
-        pullRequest: formatPullRequestLabel(pullRequest),
-      });
-    } catch (error) {
-      failedCount += 1;
-      onError(
-        new PullRequestPollingError(
-          `Failed to poll pull request ${formatPullRequestLabel(pullRequest)}: ${getErrorMessage(error)}`,
-        ),
-      );
-    }
+  await runOrderedPipeline(pullRequests, {
+    concurrency: options.concurrency ?? DEFAULT_POLL_CONCURRENCY,
+    process: async (pullRequest) => {
+      try {
+        const refreshedPullRequest = await fetchPullRequest(githubAuth.client, pullRequest);
+
+        if (options.avatarCache && refreshedPullRequest !== null) {
+          await warmNotificationAvatars(database, options.avatarCache, refreshedPullRequest);
+        }
+
+        return { ok: true as const, refreshedPullRequest };
+      } catch (error) {
+        return { ok: false as const, error };
+      }
+    },
+    consume: async (result, pullRequest) => {
+      try {
+        if (!result.ok) {
+          throw result.error;
+        }
+
+        if (notificationDispatcher && result.refreshedPullRequest !== null) {
+          await dispatchPullRequestNotifications(database, result.refreshedPullRequest, {
+            dispatchedAt: notificationDispatchedAt,
+            currentUserLogin: githubAuth.currentUserLogin,
+            notificationDispatcher,
+            ...(options.notificationChannels
+              ? { notificationChannels: options.notificationChannels }
+              : {}),
+            ...(options.notificationSounds ? { notificationSounds: options.notificationSounds } : {}),
+            ...(options.repositoryOverrides
+              ? { repositoryOverrides: options.repositoryOverrides }
+              : {}),
+            ...(options.pullRequestActions ? { pullRequestActions: options.pullRequestActions } : {}),
+          });
+        }
+
+        polledCount += 1;
+        getLogger().debug("Polled tracked pull request", {
+          pullRequest: formatPullRequestLabel(pullRequest),
+        });
+      } catch (error) {
+        failedCount += 1;
+        onError(
+          new PullRequestPollingError(
+            `Failed to poll pull request ${formatPullRequestLabel(pullRequest)}: ${getErrorMessage(error)}`,
+            { cause: error },
+          ),
+        );
+      }
+    },
+  });
+
+  try {
+    await repositorySummaries?.flush();
+  } catch (error) {
+    onError(
+      new PullRequestPollingError(`Failed to show repository summaries: ${getErrorMessage(error)}`),
+    );
   }
 
   return {
@@ -133,24 +236,56 @@ export function startRecurringTrackedPullRequestPolling<TClient>(
   database: DatabaseSync,
   githubAuth: GitHubAuthContext<TClient>,
   options: StartRecurringTrackedPullRequestPollingOptions<TClient>,
-): RecurringTrackedPullRequestPollingHandle {
-  const { intervalMs, onError, ...pollOptions } = options;
+): RecurringTrackedPullRequestPollingHandle<TClient> {
+  const { intervalMs, onError, onCycleSettled, backoff, ...initialPollOptions } = options;
 
-  if (!Number.isFinite(intervalMs) || intervalMs <= 0) {
-    throw new PullRequestPollingError(
-      "Recurring tracked pull request polling interval must be greater than zero",
-    );
-  }
+  assertValidPollingInterval(intervalMs);
 
+  let pollOptions: PollTrackedPullRequestsOptions<TClient> = initialPollOptions;
   let isStopped = false;
+  let isPaused = false;
   let isRunning = false;
-  const timer = setInterval(() => {
-    void runPollingCycle();
-  }, intervalMs);
-
-  timer.unref?.();
+  let hasQueuedCycle = false;
+  let timer = startTimer(intervalMs);
 
   return {
+    pollNow(): void {
+      if (isRunning) {
+        hasQueuedCycle = true;
+        return;
+      }
+
+      void runPollingCycle();
+    },
+    setIntervalMs(nextIntervalMs: number): void {
+      assertValidPollingInterval(nextIntervalMs);
+
+      if (isStopped) {
+        return;
+      }
+
+      clearInterval(timer);
+      timer = startTimer(nextIntervalMs);
+      getLogger().info("Changed tracked pull request polling interval", { intervalMs: nextIntervalMs });
+    },
+    setNotificationSettings(settings): void {
+      const {
+        notificationRules: _notificationRules,
+        notificationChannels: _notificationChannels,
+        notificationSounds: _notificationSounds,
+        repositoryOverrides: _repositoryOverrides,
+        ...otherPollOptions
+      } = pollOptions;
+
+      pollOptions = { ...otherPollOptions, ...settings };
+    },
+    pause(): void {
+      isPaused = true;
+      hasQueuedCycle = false;
+    },
+    resume(): void {
+      isPaused = false;
+    },
     stop(): void {
       if (isStopped) {
         return;
@@ -162,19 +297,31 @@ export function startRecurringTrackedPullRequestPolling<TClient>(
   };
 
   async function runPollingCycle(): Promise<void> {
-    if (isStopped || isRunning) {
+    if (isStopped || isPaused || isRunning) {
+      return;
+    }
+
+    const deferral = backoff?.readDeferral() ?? null;
+
+    if (deferral !== null) {
+      getLogger().debug("Deferred tracked pull request polling cycle", { ...deferral });
       return;
     }
 
     isRunning = true;
+    let lastError: PullRequestPollingError | null = null;
 
     try {
-      const cycleOptions: PollTrackedPullRequestsOptions<TClient> = onError
-        ? {
-            ...pollOptions,
-            onError,
-          }
-        : pollOptions;
+      const cycleOptions: PollTrackedPullRequestsOptions<TClient> =
+        onError || onCycleSettled
+          ? {
+              ...pollOptions,
+              onError: (error) => {
+                lastError = error;
+                (onError ?? logTrackedPullRequestPollingError)(error);
+              },
+            }
+          : pollOptions;
 
       const result = await pollTrackedPullRequests(database, githubAuth, cycleOptions);
 
@@ -183,18 +330,49 @@ export function startRecurringTrackedPullRequestPolling<TClient>(
       } else {
         getLogger().debug("Tracked pull request polling cycle found no eligible work", result);
       }
+
+      settleCycle(result.failedCount > 0 && result.polledCount === 0 ? lastError : null);
     } catch (error) {
       const pollingError =
         error instanceof PullRequestPollingError
           ? error
           : new PullRequestPollingError(
               `Failed to poll tracked pull requests: ${getErrorMessage(error)}`,
+              { cause: error },
             );
 
       (onError ?? logTrackedPullRequestPollingError)(pollingError);
+      settleCycle(pollingError);
     } finally {
       isRunning = false;
     }
+
+    if (hasQueuedCycle) {
+      hasQueuedCycle = false;
+      await runPollingCycle();
+    }
+  }
+
+  function startTimer(timerIntervalMs: number): ReturnType<typeof setInterval> {
+    const nextTimer = setInterval(() => {
+      void runPollingCycle();
+    }, timerIntervalMs);
+
+    nextTimer.unref?.();
+    return nextTimer;
+  }
+
+  function settleCycle(failure: PullRequestPollingError | null): void {
+    backoff?.recordCycle(failure);
+    onCycleSettled?.(failure);
+  }
+}
+
+function assertValidPollingInterval(intervalMs: number): void {
+  if (!Number.isFinite(intervalMs) || intervalMs <= 0) {
+    throw new PullRequestPollingError(
+      "Recurring tracked pull request polling interval must be greater than zero",
+    );
   }
 }
 
@@ -210,6 +388,41 @@ function logTrackedPullRequestPollingError(error: PullRequestPollingError): void
   });
 }
 
+async function warmNotificationAvatars(
+  database: DatabaseSync,
+  avatarCache: AvatarImageCache,
+  pullRequest: PullRequestRecord,
+): Promise<void> {
+  const eventRepository = new NormalizedEventRepository(database);
+  const pendingEvents = [
+    ...eventRepository.listImmediateEligibleUnnotifiedEventsForPullRequest(pullRequest.id),
+    ...eventRepository.listBundleEligibleUnbundledEventsForPullRequest(pullRequest.id),
+  ];
+  const avatars = new Map<string, string>();
+
+  if (pullRequest.authorAvatarUrl !== null) {
+    avatars.set(pullRequest.authorLogin, pullRequest.authorAvatarUrl);
+  }
+
+  for (const event of pendingEvents) {
+    const paragraph = buildNotificationParagraph(event);
+
+    if (paragraph.actorAvatarKey !== null && paragraph.actorAvatarUrl !== null) {
+      avatars.set(paragraph.actorAvatarKey, paragraph.actorAvatarUrl);
+    }
+  }
+
+  await Promise.all(
+    [...avatars].map(async ([key, avatarUrl]) => {
+      try {
+        await avatarCache.resolveAvatarFileUri({ key, avatarUrl });
+      } catch (error) {
+        getLogger().debug("Failed to prefetch avatar", { key, error });
+      }
+    }),
+  );
+}
+
 function getErrorMessage(error: unknown): string {
   if (error instanceof Error) {
     return error.message;
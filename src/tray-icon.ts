@@ -5,11 +5,15 @@ import { SysTray, type ClickEvent, type Conf, type Menu } from "node-systray-v2"
 import { APP_ICON_PNG_URL } from "./app-icon.js";
 import { getLogger } from "./logger.js";
 import { openUrl } from "./open-url.js";
+import type { RecentNotification, RecentNotificationList } from "./recent-notifications.js";
 
 const OPEN_APP_TITLE = "Open Octopulse";
+const OPEN_INBOX_TITLE = "Inbox";
+const OPEN_HISTORY_TITLE = "Notification History";
 const OPEN_LOGS_TITLE = "Open Logs";
 const QUIT_TITLE = "Quit";
 const TRAY_TOOLTIP = "Octopulse";
+const MAX_RECENT_NOTIFICATION_TITLE_LENGTH = 60;
 
 let trayIconBase64Promise: Promise<string> | undefined;
 
@@ -18,6 +22,7 @@ type TrayRuntime = {
   onClick(listener: (action: ClickEvent) => void | Promise<void>): void;
   onError(listener: (error: Error) => void): void;
   onExit(listener: (code: number | null, signal: string | null) => void): void;
+  sendAction(action: { type: "update-menu"; menu: Menu; seq_id: number }): void;
   kill(): void;
 };
 
@@ -34,6 +39,7 @@ export interface StartTrayIconOptions {
   environment?: NodeJS.ProcessEnv;
   openUrl?: (url: string) => Promise<void>;
   createTray?: CreateTray;
+  recentNotifications?: Pick<RecentNotificationList, "list" | "subscribe">;
 }
 
 export async function startTrayIcon(options: StartTrayIconOptions): Promise<TrayIconHandle> {
@@ -49,10 +55,13 @@ export async function startTrayIcon(options: StartTrayIconOptions): Promise<Tray
   const createTray = options.createTray ?? createDefaultTray;
   const openUrlImpl = options.openUrl ?? openUrl;
   let isStopping = false;
+  let unsubscribeRecentNotifications: (() => void) | undefined;
 
   try {
+    const icon = await readTrayIconBase64();
+    let recentNotifications = options.recentNotifications?.list() ?? [];
     const tray = createTray({
-      menu: createTrayMenu(await readTrayIconBase64()),
+      menu: createTrayMenu(icon, recentNotifications),
       debug: false,
       copyDir: false,
     });
@@ -78,6 +87,7 @@ export async function startTrayIcon(options: StartTrayIconOptions): Promise<Tray
     tray.onClick(async (action) => {
       try {
         await handleTrayAction(action, {
+          recentNotifications,
           serverOrigin: options.serverOrigin,
           openUrl: openUrlImpl,
           onQuitRequested: options.onQuitRequested,
@@ -91,6 +101,23 @@ export async function startTrayIcon(options: StartTrayIconOptions): Promise<Tray
       }
     });
 
+    unsubscribeRecentNotifications = options.recentNotifications?.subscribe((notifications) => {
+      recentNotifications = notifications;
+
+      try {
+        tray.sendAction({
+          type: "update-menu",
+          menu: createTrayMenu(icon, notifications),
+          seq_id: -1,
+        });
+      } catch (error) {
+        getLogger().warn("Failed to update tray menu", {
+          message: getErrorMessage(error),
+          error,
+        });
+      }
+    });
+
     getLogger().info("Tray icon started", {
       serverOrigin: options.serverOrigin,
     });
@@ -103,10 +130,12 @@ export async function startTrayIcon(options: StartTrayIconOptions): Promise<Tray
         }
 
         isStopping = true;
+        unsubscribeRecentNotifications?.();
         tray.kill();
       },
     };
   } catch (error) {
+    unsubscribeRecentNotifications?.();
     getLogger().warn("Tray icon unavailable", {
       message: getErrorMessage(error),
       error,
@@ -119,18 +148,37 @@ function hasGraphicalSession(environment: NodeJS.ProcessEnv): boolean {
   return Boolean(environment.DISPLAY || environment.WAYLAND_DISPLAY);
 }
 
-function createTrayMenu(icon: string): Menu {
+function createTrayMenu(icon: string, recentNotifications: readonly RecentNotification[]): Menu {
   return {
     icon,
     title: "",
     tooltip: TRAY_TOOLTIP,
     items: [
+      // Recent notifications come first so their seq_id matches their list index.
+      ...recentNotifications.map((notification) => ({
+        title: truncateMenuTitle(notification.title),
+        tooltip: notification.clickUrl ?? notification.title,
+        checked: false,
+        enabled: notification.clickUrl !== null,
+      })),
       {
         title: OPEN_APP_TITLE,
         tooltip: "Open Octopulse UI",
         checked: false,
         enabled: true,
       },
+      {
+        title: OPEN_INBOX_TITLE,
+        tooltip: "Open unread Octopulse notifications",
+        checked: false,
+        enabled: true,
+      },
+      {
+        title: OPEN_HISTORY_TITLE,
+        tooltip: "Open recent Octopulse notifications",
+        checked: false,
+        enabled: true,
+      },
       {
         title: OPEN_LOGS_TITLE,
         tooltip: "Open Octopulse logs",
@@ -147,18 +195,42 @@ function createTrayMenu(icon: string): Menu {
   };
 }
 
+function truncateMenuTitle(title: string): string {
+  return title.length <= MAX_RECENT_NOTIFICATION_TITLE_LENGTH
+    ? title
+    : `${title.slice(0, MAX_RECENT_NOTIFICATION_TITLE_LENGTH - 1).trimEnd()}…`;
+}
+
 async function handleTrayAction(
   action: ClickEvent,
   options: {
+    recentNotifications: readonly RecentNotification[];
     serverOrigin: string;
     openUrl: (url: string) => Promise<void>;
     onQuitRequested: () => Promise<void>;
   },
 ): Promise<void> {
+  const recentNotification =
+    typeof action.seq_id === "number" ? options.recentNotifications[action.seq_id] : undefined;
+
+  if (recentNotification !== undefined) {
+    if (recentNotification.clickUrl !== null) {
+      await options.openUrl(recentNotification.clickUrl);
+    }
+
+    return;
+  }
+
   switch (action.item.title) {
     case OPEN_APP_TITLE:
       await options.openUrl(options.serverOrigin);
       return;
+    case OPEN_INBOX_TITLE:
+      await options.openUrl(`${options.serverOrigin}/inbox`);
+      return;
+    case OPEN_HISTORY_TITLE:
+      await options.openUrl(`${options.serverOrigin}/notification-history`);
+      return;
     case OPEN_LOGS_TITLE:
       await options.openUrl(`${options.serverOrigin}/logs`);
       return;
@@ -38,7 +38,7 @@ export const DEFAULT_LANDING_UI_FILTERS: UiFilterValues = {
 const PULL_REQUEST_STATE_FILTERS = new Set<PullRequestStateFilter>(PULL_REQUEST_STATE_FILTER_VALUES);
 const ACTOR_CLASSES = new Set<ActorClass>(["self", "human_other", "bot"]);
 const ALL_ACTIVITY_ACTOR_CLASSES = ["self", "human_other", "bot"] satisfies ActorClass[];
-const UI_FILTER_SEARCH_PARAM_KEYS = ["pr-state", "repo", "actor-type"] as const;
+const UI_FILTER_SEARCH_PARAM_KEYS = ["pr-state", "repo", "actor-type", "q"] as const;
 
 export function readUiFilterValues(
   searchParams: URLSearchParams,
@@ -59,6 +59,7 @@ export function readUiFilterValues(
     actorClass: ACTOR_CLASSES.has(actorClass as ActorClass)
       ? (actorClass as ActorClass)
       : DEFAULT_UI_FILTERS.actorClass,
+    search: readTrimmedSearchParam(searchParams, "q"),
   };
 }
 
@@ -131,6 +132,10 @@ export function countActiveUiFilters(filters: UiFilterValues): number {
     count += 1;
   }
 
+  if (filters.search.length > 0) {
+    count += 1;
+  }
+
   return count;
 }
 
@@ -0,0 +1,243 @@
+import { getLogger } from "./logger.js";
+
+const DEFAULT_RECONNECT_DELAY_MS = 30_000;
+const PULL_REQUEST_WEBHOOK_EVENTS = new Set([
+  "check_suite",
+  "issue_comment",
+  "pull_request",
+  "pull_request_review",
+  "pull_request_review_comment",
+  "pull_request_review_thread",
+  "workflow_run",
+]);
+
+export interface WebhookRelayDelivery {
+  eventName: string;
+  deliveryId: string | null;
+  repositoryFullName: string | null;
+  pullRequestNumber: number | null;
+}
+
+export interface ServerSentEvent {
+  event: string;
+  data: string;
+}
+
+export interface StartWebhookRelayClientOptions {
+  relayUrl: string;
+  onDelivery: (delivery: WebhookRelayDelivery) => void;
+  fetchImpl?: typeof fetch;
+  reconnectDelayMs?: number;
+}
+
+export interface WebhookRelayClientHandle {
+  stop(): void;
+}
+
+export class WebhookRelayError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "WebhookRelayError";
+  }
+}
+
+export function startWebhookRelayClient(
+  options: StartWebhookRelayClientOptions,
+): WebhookRelayClientHandle {
+  const fetchImpl = options.fetchImpl ?? fetch;
+  const reconnectDelayMs = options.reconnectDelayMs ?? DEFAULT_RECONNECT_DELAY_MS;
+  let isStopped = false;
+  let abortController: AbortController | undefined;
+  let reconnectTimer: NodeJS.Timeout | undefined;
+
+  void connect();
+
+  return {
+    stop(): void {
+      if (isStopped) {
+        return;
+      }
+
+      isStopped = true;
+      abortController?.abort();
+
+      if (reconnectTimer) {
+        clearTimeout(reconnectTimer);
+      }
+    },
+  };
+
+  async function connect(): Promise<void> {
+    abortController = new AbortController();
+
+    try {
+      const response = await fetchImpl(options.relayUrl, {
+        headers: { Accept: "text/event-stream" },
+        signal: abortController.signal,
+      });
+
+      if (!response.ok || !response.body) {
+        throw new WebhookRelayError(`Webhook relay responded with status ${response.status}`);
+      }
+
+      getLogger().info("Connected to webhook relay", { relayUrl: options.relayUrl });
+
+      for await (const event of readServerSentEvents(response.body)) {
+        const delivery = readWebhookRelayDelivery(event);
+
+        if (delivery === null || !PULL_REQUEST_WEBHOOK_EVENTS.has(delivery.eventName)) {
+          continue;
+        }
+
+        getLogger().debug("Received webhook relay delivery", { ...delivery });
+        options.onDelivery(delivery);
+      }
+
+      if (!isStopped) {
+        throw new WebhookRelayError("Webhook relay closed the event stream");
+      }
+    } catch (error) {
+      if (isStopped) {
+        return;
+      }
+
+      getLogger().warn("Webhook relay connection failed", {
+        relayUrl: options.relayUrl,
+        reconnectDelayMs,
+        error,
+      });
+      reconnectTimer = setTimeout(() => {
+        void connect();
+      }, reconnectDelayMs);
+      reconnectTimer.unref?.();
+    }
+  }
+}
+
+export async function* readServerSentEvents(
+  stream: ReadableStream<Uint8Array>,
+): AsyncGenerator<ServerSentEvent> {
+  const decoder = new TextDecoder();
+  const reader = stream.getReader();
+  let buffer = "";
+
+  while (true) {
+    const { done, value } = await reader.read();
+
+    if (done) {
+      return;
+    }
+
+    buffer += decoder.decode(value, { stream: true });
+
+    let separatorIndex = buffer.search(/\r?\n\r?\n/);
+
+    while (separatorIndex !== -1) {
+      const block = buffer.slice(0, separatorIndex);
+      const separator = buffer.slice(separatorIndex).match(/^\r?\n\r?\n/)?.[0] ?? "\n\n";
+      buffer = buffer.slice(separatorIndex + separator.length);
+
+      const event = parseServerSentEventBlock(block);
+
+      if (event !== null) {
+        yield event;
+      }
+
+      separatorIndex = buffer.search(/\r?\n\r?\n/);
+    }
+  }
+}
+
+export function readWebhookRelayDelivery(event: ServerSentEvent): WebhookRelayDelivery | null {
+  if (event.event !== "message") {
+    return null;
+  }
+
+  let data: unknown;
+
+  try {
+    data = JSON.parse(event.data) as unknown;
+  } catch {
+    return null;
+  }
+
+  const record = readRecord(data);
+  const eventName = record?.["x-github-event"];
+
+  if (record === undefined || typeof eventName !== "string") {
+    return null;
+  }
+
+  const deliveryId = record["x-github-delivery"];
+  const body = readRecord(record.body);
+
+  return {
+    eventName,
+    deliveryId: typeof deliveryId === "string" ? deliveryId : null,
+    repositoryFullName: readStringField(readRecord(body?.repository), "full_name"),
+    pullRequestNumber: readPullRequestNumber(body),
+  };
+}
+
+function parseServerSentEventBlock(block: string): ServerSentEvent | null {
+  let event = "message";
+  const dataLines: string[] = [];
+
+  for (const line of block.split(/\r?\n/)) {
+    if (line.length === 0 || line.startsWith(":")) {
+      continue;
+    }
+
+    const colonIndex = line.indexOf(":");
+    const field = colonIndex === -1 ? line : line.slice(0, colonIndex);
+    const value = colonIndex === -1 ? "" : line.slice(colonIndex + 1).replace(/^ /, "");
+
+    if (field === "event") {
+      event = value;
+    } else if (field === "data") {
+      dataLines.push(value);
+    }
+  }
+
+  if (dataLines.length === 0) {
+    return null;
+  }
+
+  return {
+    event,
+    data: dataLines.join("\n"),
+  };
+}
+
+function readPullRequestNumber(body: Record<string, unknown> | undefined): number | null {
+  if (body === undefined) {
+    return null;
+  }
+
+  const pullRequestNumber = readRecord(body.pull_request)?.number;
+
+  if (typeof pullRequestNumber === "number") {
+    return pullRequestNumber;
+  }
+
+  const issue = readRecord(body.issue);
+
+  if (issue?.pull_request !== undefined && typeof issue.number === "number") {
+    return issue.number;
+  }
+
+  return null;
+}
+
+function readStringField(record: Record<string, unknown> | undefined, key: string): string | null {
+  const value = record?.[key];
+  return typeof value === "string" ? value : null;
+}
+
+function readRecord(value: unknown): Record<string, unknown> | undefined {
+  if (typeof value !== "object" || value === null || Array.isArray(value)) {
+    return undefined;
+  }
+
+  return value as Record<string, unknown>;
+}
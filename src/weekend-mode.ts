@@ -0,0 +1,133 @@
+import { parsePullRequestHtmlUrl } from "./github-notification-read-state.js";
+import type { LinuxNotification } from "./linux-notification-adapter.js";
+import { getLogger } from "./logger.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+import {
+  buildAwayDigestNotification,
+  type HeldNotificationStore,
+} from "./session-presence-buffer.js";
+import { readZonedWeekdayTime } from "./working-hours.js";
+
+const DEFAULT_WEEKEND_CHECK_INTERVAL_MS = 5 * 60_000;
+
+export interface WeekendModeOptions {
+  timeZone: string;
+  repositories: readonly string[];
+  currentUserLogin?: string;
+  now?: () => Date;
+  checkIntervalMs?: number;
+  overflowClickUrl?: () => string;
+  heldNotificationStore?: HeldNotificationStore;
+}
+
+export interface WeekendCatchUpNotificationDispatcher extends NotificationDispatcher {
+  stop(): void;
+}
+
+export function withWeekendCatchUp(
+  notificationDispatcher: NotificationDispatcher,
+  options: WeekendModeOptions,
+): WeekendCatchUpNotificationDispatcher {
+  const now = options.now ?? (() => new Date());
+  const heldNotificationStore = options.heldNotificationStore;
+  const queuedNotifications: LinuxNotification[] = heldNotificationStore?.read() ?? [];
+  let isStopped = false;
+
+  const timer = setInterval(() => {
+    void flushAfterWeekend();
+  }, options.checkIntervalMs ?? DEFAULT_WEEKEND_CHECK_INTERVAL_MS);
+
+  timer.unref?.();
+
+  return {
+    async dispatchNotification(notification) {
+      if (
+        !isStopped &&
+        isWeekend(now(), options.timeZone) &&
+        !isWeekendBreakthrough(notification, options)
+      ) {
+        queuedNotifications.push(notification);
+        heldNotificationStore?.write(queuedNotifications);
+        getLogger().debug("Held notification for the weekend catch-up summary", {
+          queuedCount: queuedNotifications.length,
+        });
+        return { queued: true };
+      }
+
+      return notificationDispatcher.dispatchNotification(notification);
+    },
+    stop(): void {
+      if (isStopped) {
+        return;
+      }
+
+      isStopped = true;
+      clearInterval(timer);
+    },
+  };
+
+  async function flushAfterWeekend(): Promise<void> {
+    if (isStopped || queuedNotifications.length === 0 || isWeekend(now(), options.timeZone)) {
+      return;
+    }
+
+    const notifications = queuedNotifications.splice(0, queuedNotifications.length);
+    heldNotificationStore?.write(queuedNotifications);
+
+    getLogger().info("Delivering weekend catch-up summary", {
+      queuedCount: notifications.length,
+    });
+
+    try {
+      await notificationDispatcher.dispatchNotification(
+        buildAwayDigestNotification(notifications, options.overflowClickUrl?.()),
+      );
+    } catch (error) {
+      getLogger().warn("Failed to deliver weekend catch-up summary", { error });
+    }
+  }
+}
+
+export function isWeekendBreakthrough(
+  notification: LinuxNotification,
+  options: Pick<WeekendModeOptions, "repositories" | "currentUserLogin">,
+): boolean {
+  // Critical popups, like security alerts, can't wait until Monday.
+  if (notification.urgency === "critical") {
+    return true;
+  }
+
+  const coordinates = notification.clickUrl ? parsePullRequestHtmlUrl(notification.clickUrl) : null;
+  const repositoryFullName = coordinates
+    ? `${coordinates.repositoryOwner}/${coordinates.repositoryName}`.toLowerCase()
+    : null;
+
+  if (
+    repositoryFullName !== null &&
+    options.repositories.some((repository) => repository.toLowerCase() === repositoryFullName)
+  ) {
+    return true;
+  }
+
+  if (!options.currentUserLogin) {
+    return false;
+  }
+
+  const mentionPattern = new RegExp(`(^|[^\\w-])@${escapeRegExp(options.currentUserLogin)}\\b`, "i");
+  const texts = [
+    notification.body,
+    ...(notification.markup?.paragraphs.map((paragraph) => paragraph.text) ?? []),
+  ];
+
+  return texts.some((text) => mentionPattern.test(text));
+}
+
+function isWeekend(date: Date, timeZone: string): boolean {
+  const { weekday } = readZonedWeekdayTime(date, timeZone);
+
+  return weekday === "saturday" || weekday === "sunday";
+}
+
+function escapeRegExp(value: string): string {
+  return value.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+}
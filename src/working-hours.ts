@@ -0,0 +1,115 @@
+import { getLogger } from "./logger.js";
+import type { NotificationDispatcher } from "./notification-dispatch.js";
+import { GITHUB_NOTIFICATIONS_URL } from "./session-presence-buffer.js";
+
+export const WEEKDAYS = [
+  "sunday",
+  "monday",
+  "tuesday",
+  "wednesday",
+  "thursday",
+  "friday",
+  "saturday",
+] as const;
+
+export type Weekday = (typeof WEEKDAYS)[number];
+export type NotificationMode = "full" | "silent" | "off";
+
+export interface WorkingHoursRange {
+  startMinute: number;
+  endMinute: number;
+}
+
+export interface WorkingHoursSchedule {
+  timeZone: string;
+  outsideHoursMode: Exclude<NotificationMode, "full">;
+  days: Partial<Record<Weekday, WorkingHoursRange[]>>;
+}
+
+export interface ZonedWeekdayTime {
+  weekday: Weekday;
+  minuteOfDay: number;
+}
+
+// Popups skipped in "off" mode are announced by one summary popup ahead of the first popup shown
+// once notifications are back on, so nothing skipped goes unnoticed.
+export function withWorkingHoursSchedule(
+  notificationDispatcher: NotificationDispatcher,
+  schedule: WorkingHoursSchedule,
+  now: () => Date = () => new Date(),
+  options: { overflowClickUrl?: () => string } = {},
+): NotificationDispatcher {
+  let skippedCount = 0;
+
+  return {
+    async dispatchNotification(notification) {
+      const mode = resolveNotificationMode(schedule, now());
+
+      // Critical popups, like security alerts, show outside working hours too.
+      if (mode === "off" && notification.urgency !== "critical") {
+        skippedCount += 1;
+        getLogger().debug("Skipped notification popup outside working hours", {
+          title: notification.title,
+        });
+        return { skipped: true };
+      }
+
+      if (skippedCount > 0) {
+        const count = skippedCount;
+
+        skippedCount = 0;
+        await notificationDispatcher.dispatchNotification({
+          title: `${count} ${count === 1 ? "notification" : "notifications"} skipped outside working hours`,
+          body: "They are listed in notification history.",
+          clickUrl: options.overflowClickUrl?.() ?? GITHUB_NOTIFICATIONS_URL,
+        });
+      }
+
+      if (mode === "silent") {
+        const { soundFile: _soundFile, ...silentNotification } = notification;
+        return notificationDispatcher.dispatchNotification({ ...silentNotification, sticky: false });
+      }
+
+      return notificationDispatcher.dispatchNotification(notification);
+    },
+  };
+}
+
+export function resolveNotificationMode(
+  schedule: WorkingHoursSchedule,
+  date: Date,
+): NotificationMode {
+  const { weekday, minuteOfDay } = readZonedWeekdayTime(date, schedule.timeZone);
+  const ranges = schedule.days[weekday] ?? [];
+
+  return ranges.some((range) => range.startMinute <= minuteOfDay && minuteOfDay < range.endMinute)
+    ? "full"
+    : schedule.outsideHoursMode;
+}
+
+export function readZonedWeekdayTime(date: Date, timeZone: string): ZonedWeekdayTime {
+  const parts = new Intl.DateTimeFormat("en-US", {
+    timeZone,
+    weekday: "long",
+    hour: "2-digit",
+    minute: "2-digit",
+    hourCycle: "h23",
+  }).formatToParts(date);
+  const readPart = (type: Intl.DateTimeFormatPartTypes) =>
+    parts.find((part) => part.type === type)?.value ?? "";
+  const weekday = readPart("weekday").toLowerCase() as Weekday;
+
+  return {
+    weekday,
+    minuteOfDay: Number(readPart("hour")) * 60 + Number(readPart("minute")),
+  };
+}
+
+export function isValidTimeZone(timeZone: string): boolean {
+  try {
+    new Intl.DateTimeFormat("en-US", { timeZone });
+    return true;
+  } catch {
+    return false;
+  }
+}
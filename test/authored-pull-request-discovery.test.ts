@@ -9,6 +9,8 @@ import { initializeDatabase } from "../src/database.js";
 import { NormalizedEventRepository } from "../src/normalized-event-repository.js";
 import { NotificationRecordRepository } from "../src/notification-record-repository.js";
 import {
+  discoverOpenAuthoredPullRequests,
+  readReceivedEventPullRequests,
   runFirstRunAuthoredPullRequestDiscovery,
   startRecurringAuthoredPullRequestDiscovery,
   type DiscoveredPullRequest,
@@ -125,7 +127,10 @@ describe("runFirstRunAuthoredPullRequestDiscovery", () => {
     };
     const fetchPullRequestDetail = vi.fn(
       async (_client: typeof client, coordinates: PullRequestCoordinates) =>
-        createDiscoveredPullRequest(coordinates),
+        createDiscoveredPullRequest(coordinates, {
+          headBranch: "feature/charts",
+          bodyText: "## Summary\n\nAdds **charts** to the dashboard.",
+        }),
     );
 
     try {
@@ -158,6 +163,10 @@ describe("runFirstRunAuthoredPullRequestDiscovery", () => {
               },
             ],
             fetchPullRequestDetail,
+            fetchReviewRequest: async () => ({
+              requesterLogin: "alice",
+              requestedAt: "2026-04-10T10:00:00.000Z",
+            }),
             observedAt: OBSERVED_AT,
             notificationDispatcher,
             notificationDispatchedAt: OBSERVED_AT,
@@ -174,14 +183,14 @@ describe("runFirstRunAuthoredPullRequestDiscovery", () => {
       expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledWith(
         expect.objectContaining({
           title: "widgets/dashboard #42 Pull request 42",
-          body: "👀 review requested",
+          body: "👀 review requested by alice 2h ago: Summary Adds charts to the dashboard.",
           clickUrl: "https://github.com/widgets/dashboard/pull/42",
           markup: expect.objectContaining({
-            headerText: "[dashboard] Pull request 42 (open)",
+            headerText: "[dashboard] Pull request 42 (open) · feature/charts → main",
             paragraphs: [
               expect.objectContaining({
                 actorLogin: null,
-                text: "👀 review requested",
+                text: "👀 review requested by alice 2h ago: Summary Adds charts to the dashboard.",
               }),
             ],
           }),
@@ -199,7 +208,7 @@ describe("runFirstRunAuthoredPullRequestDiscovery", () => {
           eventType: "review_requested",
           decisionState: "notified",
           notificationTiming: "immediate",
-          actorLogin: null,
+          actorLogin: "alice",
           occurredAt: OBSERVED_AT,
         }),
       ]);
@@ -213,7 +222,7 @@ describe("runFirstRunAuthoredPullRequestDiscovery", () => {
           normalizedEventId: normalizedEvents[0]?.id,
           deliveryStatus: "sent",
           deliveredAt: OBSERVED_AT,
-          body: "👀 review requested",
+          body: "👀 review requested by alice 2h ago: Summary Adds charts to the dashboard.",
         }),
       ]);
     } finally {
@@ -564,6 +573,238 @@ describe("startRecurringAuthoredPullRequestDiscovery", () => {
   });
 });
 
+describe("discoverOpenAuthoredPullRequests", () => {
+  it("falls back to received events after review-requested search keeps failing", async () => {
+    const { database, repository } = createRepository();
+    const client = { kind: "fake-client" };
+    let isSearchDegraded = false;
+    const listPullRequestsFromReceivedEvents = vi.fn(async () => ({
+      reviewRequested: [
+        {
+          repositoryOwner: "widgets",
+          repositoryName: "dashboard",
+          number: 42,
+        },
+      ],
+      mentioned: [
+        {
+          repositoryOwner: "widgets",
+          repositoryName: "api",
+          number: 7,
+        },
+      ],
+    }));
+    const discover = (observedAt: string) =>
+      discoverOpenAuthoredPullRequests(
+        database,
+        {
+          client,
+          currentUserLogin: "octocat",
+        },
+        {
+          pullRequestRepository: repository,
+          searchOpenAuthoredPullRequests: async () => [],
+          searchOpenReviewRequestedPullRequests: async () => {
+            if (isSearchDegraded) {
+              throw new Error("search is degraded");
+            }
+
+            return [];
+          },
+          listPullRequestsFromReceivedEvents,
+          fetchPullRequestDetail: async (_client, coordinates) =>
+            createDiscoveredPullRequest(coordinates),
+          observedAt,
+        },
+      );
+
+    try {
+      await expect(discover(OBSERVED_AT)).resolves.toEqual({ discoveredCount: 0 });
+      isSearchDegraded = true;
+      await expect(discover("2026-04-10T12:05:00.000Z")).rejects.toThrowError(
+        "search is degraded",
+      );
+      await expect(discover("2026-04-10T12:10:00.000Z")).rejects.toThrowError(
+        "search is degraded",
+      );
+      await expect(discover("2026-04-10T12:15:00.000Z")).resolves.toEqual({
+        discoveredCount: 2,
+      });
+
+      expect(listPullRequestsFromReceivedEvents).toHaveBeenCalledTimes(1);
+      expect(listPullRequestsFromReceivedEvents).toHaveBeenCalledWith(
+        client,
+        "octocat",
+        OBSERVED_AT,
+      );
+      expect(repository.getPullRequestByGitHubPullRequestId(4201)).toMatchObject({
+        repositoryOwner: "widgets",
+        repositoryName: "dashboard",
+      });
+      expect(repository.getPullRequestByGitHubPullRequestId(101)).toMatchObject({
+        repositoryName: "api",
+        isTracked: true,
+        trackingReason: "mentioned",
+      });
+    } finally {
+      database.close();
+    }
+  });
+
+  it("tracks pull requests from the GitHub notification inbox when enabled", async () => {
+    const { database, repository } = createRepository();
+    const client = { kind: "fake-client" };
+    const githubInbox = { participating: false, reasons: ["team_mention" as const] };
+    const listInboxPullRequests = vi.fn(async () => [
+      { repositoryOwner: "acme", repositoryName: "octopulse", number: 7 },
+      { repositoryOwner: "widgets", repositoryName: "dashboard", number: 42 },
+    ]);
+
+    try {
+      await expect(
+        discoverOpenAuthoredPullRequests(
+          database,
+          { client, currentUserLogin: "octocat" },
+          {
+            pullRequestRepository: repository,
+            searchOpenAuthoredPullRequests: async () => [
+              { repositoryOwner: "acme", repositoryName: "octopulse", number: 7 },
+            ],
+            searchOpenReviewRequestedPullRequests: async () => [],
+            listInboxPullRequests,
+            githubInbox,
+            fetchPullRequestDetail: async (_client, coordinates) =>
+              createDiscoveredPullRequest(coordinates),
+            observedAt: OBSERVED_AT,
+          },
+        ),
+      ).resolves.toEqual({ discoveredCount: 2 });
+
+      expect(listInboxPullRequests).toHaveBeenCalledWith(client, githubInbox);
+      expect(repository.getPullRequestByGitHubPullRequestId(101)?.trackingReason).toBe("auto");
+      expect(repository.getPullRequestByGitHubPullRequestId(4201)).toMatchObject({
+        isTracked: true,
+        trackingReason: "github_inbox",
+      });
+    } finally {
+      database.close();
+    }
+  });
+
+  it("fetches details concurrently but stores pull requests in discovery order", async () => {
+    const { database, repository } = createRepository();
+    const upsertedNumbers: number[] = [];
+    let inFlightCount = 0;
+    let maxInFlightCount = 0;
+
+    try {
+      await expect(
+        discoverOpenAuthoredPullRequests(
+          database,
+          { client: { kind: "fake-client" }, currentUserLogin: "octocat" },
+          {
+            pullRequestRepository: {
+              getPullRequestByRepositoryCoordinates: (owner, name, number) =>
+                repository.getPullRequestByRepositoryCoordinates(owner, name, number),
+              upsertPullRequest: (input) => {
+                upsertedNumbers.push(input.number);
+                return repository.upsertPullRequest(input);
+              },
+            },
+            searchOpenAuthoredPullRequests: async () =>
+              [7, 8, 9, 10].map((number) => ({
+                repositoryOwner: "acme",
+                repositoryName: "octopulse",
+                number,
+              })),
+            searchOpenReviewRequestedPullRequests: async () => [],
+            fetchPullRequestDetail: async (_client, coordinates) => {
+              inFlightCount += 1;
+              maxInFlightCount = Math.max(maxInFlightCount, inFlightCount);
+              // Later pull requests answer first.
+              await new Promise((resolve) => setTimeout(resolve, 20 - coordinates.number));
+              inFlightCount -= 1;
+
+              return createDiscoveredPullRequest(coordinates, {
+                githubPullRequestId: 1000 + coordinates.number,
+              });
+            },
+            concurrency: 2,
+            observedAt: OBSERVED_AT,
+          },
+        ),
+      ).resolves.toEqual({ discoveredCount: 4 });
+
+      expect(upsertedNumbers).toEqual([7, 8, 9, 10]);
+      expect(maxInFlightCount).toBe(2);
+    } finally {
+      database.close();
+    }
+  });
+});
+
+describe("readReceivedEventPullRequests", () => {
+  it("reads review requests and mentions, skipping events older than the last search", () => {
+    const events = [
+      {
+        type: "PullRequestEvent",
+        created_at: "2026-04-10T12:01:00Z",
+        repo: { name: "widgets/dashboard" },
+        actor: { login: "hubot" },
+        payload: {
+          action: "review_requested",
+          number: 42,
+          requested_reviewer: { login: "OctoCat" },
+        },
+      },
+      {
+        type: "IssueCommentEvent",
+        created_at: "2026-04-10T12:02:00Z",
+        repo: { name: "widgets/api" },
+        actor: { login: "hubot" },
+        payload: {
+          issue: { number: 7, pull_request: {} },
+          comment: { body: "@octocat can you take a look?" },
+        },
+      },
+      {
+        type: "PullRequestReviewCommentEvent",
+        created_at: "2026-04-10T12:03:00Z",
+        repo: { name: "widgets/api" },
+        actor: { login: "hubot" },
+        payload: { pull_request: { number: 8 }, comment: { body: "cc @octocat-bot" } },
+      },
+      {
+        type: "PullRequestReviewEvent",
+        created_at: "2026-04-10T12:04:00Z",
+        repo: { name: "widgets/api" },
+        actor: { login: "octocat" },
+        payload: { pull_request: { number: 9 }, review: { body: "Thanks @octocat" } },
+      },
+      {
+        type: "IssueCommentEvent",
+        created_at: "2026-04-10T11:59:00Z",
+        repo: { name: "widgets/api" },
+        actor: { login: "hubot" },
+        payload: {
+          issue: { number: 10, pull_request: {} },
+          comment: { body: "@octocat stale" },
+        },
+      },
+    ];
+
+    expect(readReceivedEventPullRequests(events, "octocat", OBSERVED_AT)).toEqual({
+      reviewRequested: [{ repositoryOwner: "widgets", repositoryName: "dashboard", number: 42 }],
+      mentioned: [{ repositoryOwner: "widgets", repositoryName: "api", number: 7 }],
+    });
+    expect(readReceivedEventPullRequests(events, "octocat", undefined).mentioned).toContainEqual({
+      repositoryOwner: "widgets",
+      repositoryName: "api",
+      number: 10,
+    });
+  });
+});
+
 function createRepository(): {
   database: ReturnType<typeof initializeDatabase>;
   repository: PullRequestRepository;
@@ -0,0 +1,186 @@
+import { describe, expect, it, vi } from "vitest";
+
+import { createCalendarBusyChecker, parseIcsBusyIntervals } from "../src/calendar-busy.js";
+import { getLogger } from "../src/logger.js";
+
+const WINDOW = {
+  fromMs: Date.parse("2026-04-01T00:00:00.000Z"),
+  toMs: Date.parse("2026-04-30T00:00:00.000Z"),
+};
+
+function buildCalendar(...events: string[][]): string {
+  return [
+    "BEGIN:VCALENDAR",
+    "VERSION:2.0",
+    ...events.flatMap((lines) => ["BEGIN:VEVENT", ...lines, "END:VEVENT"]),
+    "END:VCALENDAR",
+  ].join("\r\n");
+}
+
+describe("parseIcsBusyIntervals", () => {
+  it("keeps opaque events and skips free or cancelled ones", () => {
+    const calendar = buildCalendar(
+      ["SUMMARY:Planning", "DTSTART:20260410T150000Z", "DTEND:20260410T160000Z"],
+      ["SUMMARY:Focus", "TRANSP:TRANSPARENT", "DTSTART:20260410T170000Z", "DTEND:20260410T180000Z"],
+      ["SUMMARY:Moved", "STATUS:CANCELLED", "DTSTART:20260410T190000Z", "DTEND:20260410T200000Z"],
+    );
+
+    expect(parseIcsBusyIntervals(calendar, WINDOW)).toEqual([
+      {
+        startMs: Date.parse("2026-04-10T15:00:00.000Z"),
+        endMs: Date.parse("2026-04-10T16:00:00.000Z"),
+      },
+    ]);
+  });
+
+  it("unfolds long lines and expands weekly recurrences with exclusions", () => {
+    const calendar = buildCalendar([
+      "SUMMARY:Stand",
+      " up",
+      "DTSTART:20260406T090000Z",
+      "DTEND:20260406T091500Z",
+      "RRULE:FREQ=WEEKLY;BYDAY=MO,WE;COUNT=4",
+      "EXDATE:20260408T090000Z",
+    ]);
+
+    expect(
+      parseIcsBusyIntervals(calendar, WINDOW).map((interval) =>
+        new Date(interval.startMs).toISOString(),
+      ),
+    ).toEqual([
+      "2026-04-06T09:00:00.000Z",
+      "2026-04-13T09:00:00.000Z",
+      "2026-04-15T09:00:00.000Z",
+    ]);
+  });
+
+  it("converts TZID times from their timezone across DST changes", () => {
+    const calendar = buildCalendar(
+      [
+        "DTSTART;TZID=America/New_York:20260306T090000",
+        "DTEND;TZID=America/New_York:20260306T093000",
+        "RRULE:FREQ=DAILY;COUNT=4",
+        "EXDATE;TZID=America/New_York:20260307T090000",
+      ],
+    );
+
+    expect(
+      parseIcsBusyIntervals(calendar, {
+        fromMs: Date.parse("2026-03-01T00:00:00.000Z"),
+        toMs: Date.parse("2026-03-31T00:00:00.000Z"),
+      }).map((interval) => new Date(interval.startMs).toISOString()),
+    ).toEqual([
+      "2026-03-06T14:00:00.000Z",
+      "2026-03-08T13:00:00.000Z",
+      "2026-03-09T13:00:00.000Z",
+    ]);
+  });
+
+  it("maps Windows zone names, unquotes TZIDs and warns once per unknown zone", () => {
+    const warn = vi.spyOn(getLogger(), "warn");
+    const calendar = buildCalendar(
+      [
+        'DTSTART;TZID="W. Europe Standard Time":20260410T090000',
+        'DTEND;TZID="W. Europe Standard Time":20260410T100000',
+      ],
+      [
+        'DTSTART;TZID="Europe/Berlin";X-NOTE="a;b:c":20260410T110000',
+        'DTEND;TZID="Europe/Berlin":20260410T120000',
+      ],
+      ["DTSTART;TZID=Mars Standard Time:20260410T130000", "DTEND:20260410T140000Z"],
+      ["DTSTART;TZID=Mars Standard Time:20260411T130000", "DTEND:20260411T140000Z"],
+    );
+
+    try {
+      expect(
+        parseIcsBusyIntervals(calendar, WINDOW).map((interval) =>
+          new Date(interval.startMs).toISOString(),
+        ),
+      ).toEqual(["2026-04-10T07:00:00.000Z", "2026-04-10T09:00:00.000Z"]);
+      parseIcsBusyIntervals(calendar, WINDOW);
+
+      expect(warn).toHaveBeenCalledTimes(1);
+      expect(warn).toHaveBeenCalledWith(expect.any(String), { timeZone: "Mars Standard Time" });
+    } finally {
+      warn.mockRestore();
+    }
+  });
+
+  it("counts weekly intervals in weeks starting on WKST", () => {
+    // 2026-04-01 is a Wednesday, so its Monday falls in the week before the one it starts.
+    const calendar = buildCalendar([
+      "DTSTART:20260401T090000Z",
+      "DTEND:20260401T093000Z",
+      "RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;WKST=MO",
+    ]);
+
+    expect(
+      parseIcsBusyIntervals(calendar, WINDOW).map((interval) =>
+        new Date(interval.startMs).toISOString(),
+      ),
+    ).toEqual([
+      "2026-04-01T09:00:00.000Z",
+      "2026-04-13T09:00:00.000Z",
+      "2026-04-15T09:00:00.000Z",
+      "2026-04-27T09:00:00.000Z",
+      "2026-04-29T09:00:00.000Z",
+    ]);
+  });
+
+  it("replaces recurring occurrences with their RECURRENCE-ID overrides", () => {
+    const calendar = buildCalendar(
+      [
+        "UID:standup@example.com",
+        "DTSTART:20260406T090000Z",
+        "DTEND:20260406T091500Z",
+        "RRULE:FREQ=DAILY;COUNT=3",
+      ],
+      [
+        "UID:standup@example.com",
+        "RECURRENCE-ID:20260407T090000Z",
+        "DTSTART:20260407T110000Z",
+        "DTEND:20260407T111500Z",
+      ],
+      [
+        "UID:standup@example.com",
+        "RECURRENCE-ID:20260408T090000Z",
+        "STATUS:CANCELLED",
+        "DTSTART:20260408T090000Z",
+        "DTEND:20260408T091500Z",
+      ],
+    );
+
+    expect(
+      parseIcsBusyIntervals(calendar, WINDOW).map((interval) =>
+        new Date(interval.startMs).toISOString(),
+      ),
+    ).toEqual(["2026-04-06T09:00:00.000Z", "2026-04-07T11:00:00.000Z"]);
+  });
+});
+
+describe("createCalendarBusyChecker", () => {
+  it("reports busy during an event and caches the calendar between refreshes", async () => {
+    const readTextFile = vi.fn().mockResolvedValue(
+      buildCalendar(["DTSTART:20260410T150000Z", "DTEND:20260410T160000Z"]),
+    );
+    const checker = createCalendarBusyChecker({
+      source: "/home/me/calendar.ics",
+      readTextFile,
+    });
+
+    await expect(checker.isBusy(Date.parse("2026-04-10T15:30:00.000Z"))).resolves.toBe(true);
+    await expect(checker.isBusy(Date.parse("2026-04-10T16:01:00.000Z"))).resolves.toBe(false);
+    expect(readTextFile).toHaveBeenCalledTimes(1);
+    expect(readTextFile).toHaveBeenCalledWith("/home/me/calendar.ics");
+  });
+
+  it("treats an unreachable calendar as free", async () => {
+    const fetchImpl = vi.fn().mockResolvedValue(new Response("nope", { status: 503 }));
+    const checker = createCalendarBusyChecker({
+      source: "https://calendar.example.com/me.ics",
+      fetchImpl,
+    });
+
+    await expect(checker.isBusy(Date.parse("2026-04-10T15:30:00.000Z"))).resolves.toBe(false);
+  });
+});
@@ -0,0 +1,227 @@
+import { mkdtempSync, rmSync, writeFileSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it } from "vitest";
+
+import {
+  CommandLineError,
+  isNetworkCommand,
+  loadCommandLineConfig,
+  parseCommandLine,
+  readCommandLineReference,
+  runSubcommand,
+} from "../src/cli.js";
+
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("parseCommandLine", () => {
+  it("runs the notifier when no command is given", () => {
+    expect(parseCommandLine([])).toEqual({ command: "run", options: {}, args: [] });
+  });
+
+  it("parses global options in both flag forms", () => {
+    expect(
+      parseCommandLine([
+        "--config",
+        "/etc/octopulse.toml",
+        "--profile=oss",
+        "--token-file=/run/secrets/github-token",
+        "--poll-interval",
+        "30s",
+        "--log-level=debug",
+        "--log-format",
+        "json",
+        "run",
+      ]),
+    ).toEqual({
+      command: "run",
+      options: {
+        configPath: "/etc/octopulse.toml",
+        profile: "oss",
+        githubTokenFilePath: "/run/secrets/github-token",
+        trackedPullRequestPollMs: 30_000,
+        logLevel: "debug",
+        logFormat: "json",
+      },
+      args: [],
+    });
+  });
+
+  it("takes --once, --dry-run, --no-log-file, and --all-notifications without a value", () => {
+    expect(
+      parseCommandLine([
+        "--once",
+        "--dry-run",
+        "--no-log-file",
+        "--all-notifications",
+        "--log-level",
+        "debug",
+      ]),
+    ).toEqual({
+      command: "run",
+      options: {
+        once: true,
+        dryRun: true,
+        noLogFile: true,
+        allNotifications: true,
+        logLevel: "debug",
+      },
+      args: [],
+    });
+  });
+
+  it("hands everything after a subcommand to that command", () => {
+    expect(parseCommandLine(["search", "flaky test", "--limit", "5"])).toEqual({
+      command: "search",
+      options: {},
+      args: ["flaky test", "--limit", "5"],
+    });
+  });
+
+  it("takes the window, protocol, and self-update commands with global options", () => {
+    expect(parseCommandLine(["--profile", "work", "window", "inbox"])).toEqual({
+      command: "window",
+      options: { profile: "work" },
+      args: ["inbox"],
+    });
+    expect(parseCommandLine(["protocol", "octopulse://history"])).toEqual({
+      command: "protocol",
+      options: {},
+      args: ["octopulse://history"],
+    });
+    expect(parseCommandLine(["self-update", "--check"])).toEqual({
+      command: "self-update",
+      options: {},
+      args: ["--check"],
+    });
+  });
+
+  it("shows help", () => {
+    expect(parseCommandLine(["--log-level", "warn", "--help"])).toEqual({ command: "help" });
+  });
+
+  it.each([
+    [["--verbose"], "Unknown option --verbose"],
+    [["deploy"], 'Unknown command "deploy"'],
+    [["--config"], "--config needs a value"],
+    [["--config", "--log-level", "debug"], "--config needs a value"],
+    [["--log-level", "loud"], "--log-level must be one of debug, info, warn, or error"],
+    [["--profile", "../work"], '--profile must only use letters, digits, "-", and "_"'],
+    [["--log-format", "xml"], "--log-format must be one of text or json"],
+    [["--poll-interval", "soon"], "--poll-interval must use a supported duration"],
+    [["--poll-interval", "5s"], "--poll-interval must be at least 15s"],
+    [["--once", "stats"], "--once only applies to the run command"],
+    [["--dry-run", "export"], "--dry-run only applies to the run command"],
+  ])("rejects %j", (args, message) => {
+    expect(() => parseCommandLine(args)).toThrowError(CommandLineError);
+    expect(() => parseCommandLine(args)).toThrowError(message);
+  });
+});
+
+describe("isNetworkCommand", () => {
+  it("only picks the commands that go online", () => {
+    const networkCommands = ["run", "init", "login", "config", "subs", "self-update"] as const;
+    const localCommands = ["help", "stats", "export", "window", "completions"] as const;
+
+    expect(networkCommands.every((command) => isNetworkCommand(command))).toBe(true);
+    expect(localCommands.some((command) => isNetworkCommand(command))).toBe(false);
+  });
+});
+
+describe("readCommandLineReference", () => {
+  it("reads every command and option from the usage text", () => {
+    const reference = readCommandLineReference();
+
+    expect(reference.synopsis).toBe("octopulse [options] [command] [command args]");
+    expect(reference.commands).toContainEqual({
+      name: "completions",
+      term: "completions <shell>",
+      description: "Print bash, zsh, or fish completions",
+    });
+    expect(reference.options).toContainEqual({
+      term: "--once",
+      description:
+        "Poll GitHub once and exit: 0 if notifications were shown, " +
+        "3 if there was nothing new, 1 if polling failed",
+    });
+  });
+});
+
+describe("completions and manpage commands", () => {
+  it.each(["bash", "zsh", "fish"] as const)(
+    "completes every command and option in %s",
+    async (shell) => {
+      const completions = await runSubcommand("completions", {}, [shell]);
+
+      for (const word of ["test-notification", "window", "manpage", "--poll-interval"]) {
+        expect(completions).toContain(word.replace(/^--/, shell === "fish" ? "-l " : "--"));
+      }
+    },
+  );
+
+  it("needs a supported shell", async () => {
+    await expect(runSubcommand("completions", {}, ["tcsh"])).rejects.toThrowError(
+      CommandLineError,
+    );
+  });
+
+  it("prints the man page", async () => {
+    await expect(runSubcommand("manpage", {}, [])).resolves.toContain(
+      ".TP\n\\fBself\\-update [\\-\\-check]\\fR\nCheck for, or install, a newer Octopulse release",
+    );
+  });
+});
+
+describe("loadCommandLineConfig", () => {
+  it("applies command-line overrides on top of the config file", () => {
+    const tempDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-cli-"));
+    tempDirs.push(tempDir);
+    const configPath = path.join(tempDir, "config.toml");
+    const tokenFilePath = path.join(tempDir, "github-token");
+    writeFileSync(
+      configPath,
+      [
+        "[github]",
+        'login = "octocat"',
+        "",
+        "[logging]",
+        'level = "warn"',
+        'file_level = "error"',
+        "",
+      ].join("\n"),
+    );
+    writeFileSync(tokenFilePath, "ghp_from_file_123\n");
+
+    const config = loadCommandLineConfig({
+      configPath,
+      githubTokenFilePath: tokenFilePath,
+      trackedPullRequestPollMs: 30_000,
+      logLevel: "debug",
+      logFormat: "json",
+      noLogFile: true,
+      allNotifications: true,
+    });
+
+    expect(config.paths.configPath).toBe(configPath);
+    expect(config.githubToken).toBe("ghp_from_file_123");
+    expect(config.githubLogin).toBe("octocat");
+    expect(config.logging).toMatchObject({
+      consoleLevel: "debug",
+      fileLevel: "debug",
+      format: "json",
+      fileEnabled: false,
+    });
+    expect(config.timings).toMatchObject({
+      trackedPullRequestPollMs: 30_000,
+      discoveryPollMs: 5 * 60_000,
+    });
+    expect(config.notifications.githubInbox).toBe("all");
+  });
+});
@@ -13,7 +13,7 @@ describe("client route state helpers", () => {
     expect(
       readRouteState(
         new URL(
-          "http://127.0.0.1/notification-history?pr-state=tracked&repo=acme%2Foctopulse&actor-type=human_other&page=3",
+          "http://127.0.0.1/notification-history?pr-state=tracked&repo=acme%2Foctopulse&actor-type=human_other&q=flaky+test&page=3",
         ),
       ),
     ).toEqual({
@@ -22,6 +22,7 @@ describe("client route state helpers", () => {
         pullRequestStates: ["tracked"],
         repository: "acme/octopulse",
         actorClass: "human_other",
+        search: "flaky test",
       },
       logLevelFilter: "all",
       activityPage: 3,
@@ -45,10 +46,13 @@ describe("client route state helpers", () => {
         {
           ...uiFilters,
           actorClass: "human_other",
+          search: "flaky test",
         },
         2,
       ),
-    ).toBe("/notification-history?pr-state=tracked&repo=acme%2Foctopulse&actor-type=human_other&page=2");
+    ).toBe(
+      "/notification-history?pr-state=tracked&repo=acme%2Foctopulse&actor-type=human_other&q=flaky+test&page=2",
+    );
     expect(countActivePageFilters(uiFilters, "pull-requests", "all")).toBe(2);
   });
 });
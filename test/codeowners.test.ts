@@ -0,0 +1,54 @@
+import { describe, expect, it } from "vitest";
+
+import { findCodeOwners, parseCodeowners, touchesOwnedPaths } from "../src/codeowners.js";
+import { matchesPathPattern } from "../src/path-patterns.js";
+
+describe("matchesPathPattern", () => {
+  it("follows gitignore-style anchoring, directory, and wildcard rules", () => {
+    expect(matchesPathPattern("*.js", "web/app.js")).toBe(true);
+    expect(matchesPathPattern("*.js", "web/app.ts")).toBe(false);
+    expect(matchesPathPattern("/docs/", "docs/guide/setup.md")).toBe(true);
+    expect(matchesPathPattern("/docs/", "web/docs/setup.md")).toBe(false);
+    expect(matchesPathPattern("apps/", "web/apps/index.ts")).toBe(true);
+    expect(matchesPathPattern("services/payments", "services/payments/api.ts")).toBe(true);
+    expect(matchesPathPattern("services/payments/**", "services/payments/v2/api.ts")).toBe(true);
+    expect(matchesPathPattern("services/payments/**", "services/billing/api.ts")).toBe(false);
+    expect(matchesPathPattern("**/migrations/*.sql", "db/migrations/0001.sql")).toBe(true);
+  });
+});
+
+describe("parseCodeowners", () => {
+  it("reads patterns and owners while skipping comments and blank lines", () => {
+    const rules = parseCodeowners(
+      [
+        "# Default owners",
+        "*       @acme/platform",
+        "",
+        "/services/payments/ @acme/payments @alice # payments team",
+        "/services/payments/README.md",
+      ].join("\n"),
+    );
+
+    expect(rules).toEqual([
+      { pattern: "*", owners: ["@acme/platform"] },
+      { pattern: "/services/payments/", owners: ["@acme/payments", "@alice"] },
+      { pattern: "/services/payments/README.md", owners: [] },
+    ]);
+    expect(findCodeOwners(rules, "services/payments/api.ts")).toEqual(["@acme/payments", "@alice"]);
+    expect(findCodeOwners(rules, "services/payments/README.md")).toEqual([]);
+    expect(findCodeOwners(rules, "web/index.ts")).toEqual(["@acme/platform"]);
+  });
+});
+
+describe("touchesOwnedPaths", () => {
+  it("matches the user's handle or any of their teams case-insensitively", () => {
+    const rules = parseCodeowners(
+      ["/services/payments/ @Acme/Payments", "/web/ @bob"].join("\n"),
+    );
+
+    expect(touchesOwnedPaths(rules, ["web/index.ts", "services/payments/api.ts"], ["@alice", "@acme/payments"])).toBe(
+      true,
+    );
+    expect(touchesOwnedPaths(rules, ["web/index.ts"], ["@alice", "@acme/payments"])).toBe(false);
+  });
+});
@@ -0,0 +1,58 @@
+import { describe, expect, it } from "vitest";
+
+import {
+  renderCommentThreadDocument,
+  shouldOfferCommentThreadView,
+} from "../src/comment-thread-document.js";
+
+const PULL_REQUEST = {
+  repositoryOwner: "acme",
+  repositoryName: "octopulse",
+  number: 7,
+  title: "Add notifications",
+  url: "https://github.com/acme/octopulse/pull/7",
+};
+
+function createCommentEvent(actorLogin: string, bodyText: string) {
+  return {
+    actorLogin,
+    eventType: "issue_comment",
+    occurredAt: "2026-04-10T12:00:00.000Z",
+    payloadJson: JSON.stringify({
+      bodyText,
+      actorAvatarUrl: `https://avatars.example.com/${actorLogin}`,
+    }),
+  };
+}
+
+describe("shouldOfferCommentThreadView", () => {
+  it("offers the full thread once several comments arrive together", () => {
+    expect(shouldOfferCommentThreadView([createCommentEvent("alice", "nit")])).toBe(false);
+    expect(
+      shouldOfferCommentThreadView([
+        createCommentEvent("alice", "nit"),
+        createCommentEvent("bob", "agreed"),
+        createCommentEvent("alice", "fixed"),
+      ]),
+    ).toBe(true);
+    expect(shouldOfferCommentThreadView([createCommentEvent("alice", "x".repeat(300))])).toBe(
+      true,
+    );
+  });
+});
+
+describe("renderCommentThreadDocument", () => {
+  it("renders every comment in full with avatars and basic markdown", () => {
+    const html = renderCommentThreadDocument(PULL_REQUEST, [
+      createCommentEvent("alice", "Please use **strict** mode in `tsconfig.json`\n\n```ts\nconst a = 1 < 2;\n```"),
+      createCommentEvent("bob", "See [docs](https://example.com/docs) <script>"),
+    ]);
+
+    expect(html).toContain("<title>acme/octopulse #7 Add notifications</title>");
+    expect(html).toContain('<img src="https://avatars.example.com/alice"');
+    expect(html).toContain("<strong>strict</strong>");
+    expect(html).toContain("<code>tsconfig.json</code>");
+    expect(html).toContain("<pre><code>const a = 1 &lt; 2;</code></pre>");
+    expect(html).toContain('<a href="https://example.com/docs">docs</a> &lt;script&gt;');
+  });
+});
@@ -0,0 +1,128 @@
+import { mkdtempSync, rmSync, writeFileSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import { ConfigCommandError, runConfigCommand } from "../src/config-command.js";
+import { loadConfig } from "../src/config.js";
+import { formatMissingTokenScopesWarning, GitHubAuthError } from "../src/github.js";
+
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("runConfigCommand", () => {
+  it("lists passing checks and warns about a mismatched login and missing scopes", async () => {
+    const tempDir = createTempDir();
+    const soundPath = path.join(tempDir, "approved.oga");
+    writeFileSync(soundPath, "");
+    const configPath = writeConfig(tempDir, [
+      "[github]",
+      'token = "ghp_test_123"',
+      'login = "octocat"',
+      "",
+      "[sounds]",
+      `approved = "${soundPath}"`,
+      'comment = ""',
+      "",
+      "[notifications]",
+      "mark_github_read_after_display = true",
+    ]);
+    const checkGitHubAuth = vi
+      .fn()
+      .mockResolvedValue({ currentUserLogin: "hubot", tokenScopes: ["public_repo"] });
+
+    const report = await runConfigCommand(["validate"], {
+      configPath,
+      loadConfig: () => loadConfig({ configPath }),
+      checkGitHubAuth,
+    });
+
+    expect(report.split("\n")).toEqual([
+      `Checked ${configPath}`,
+      "ok    Config file parses",
+      `ok    sounds.approved: ${soundPath}`,
+      "ok    GitHub accepts the token for hubot",
+      "warn  github.login is octocat, but the token belongs to hubot",
+      `warn  ${formatMissingTokenScopesWarning(["repo", "notifications"])}`,
+      "The config looks good.",
+    ]);
+    expect(checkGitHubAuth).toHaveBeenCalledWith(
+      expect.objectContaining({ githubToken: "ghp_test_123" }),
+    );
+  });
+
+  it("fails with every problem it found", async () => {
+    const tempDir = createTempDir();
+    const configPath = writeConfig(tempDir, [
+      "[github]",
+      'token = "ghp_test_123"',
+      "",
+      "[sounds]",
+      `changes_requested = "${path.join(tempDir, "missing.oga")}"`,
+      "",
+      "[calendar]",
+      `ics = "${tempDir}"`,
+    ]);
+
+    const result = runConfigCommand(["validate"], {
+      configPath,
+      loadConfig: () => loadConfig({ configPath }),
+      checkGitHubAuth: vi
+        .fn()
+        .mockRejectedValue(
+          new GitHubAuthError(
+            "GitHub authentication failed: invalid token or insufficient github.com access",
+          ),
+        ),
+    });
+
+    await expect(result).rejects.toThrowError(ConfigCommandError);
+    await expect(result).rejects.toThrowError(
+      [
+        `error sounds.changes_requested: ${path.join(tempDir, "missing.oga")} does not exist`,
+        `error calendar.ics: ${tempDir} is not a file`,
+        "error GitHub authentication failed: invalid token or insufficient github.com access",
+        "Found 3 problems.",
+      ].join("\n"),
+    );
+  });
+
+  it("reports a config file that doesn't load without checking the token", async () => {
+    const tempDir = createTempDir();
+    const configPath = writeConfig(tempDir, ["[github]", 'token = "ghp_test_123"', "colour = 1"]);
+    const checkGitHubAuth = vi.fn();
+
+    await expect(
+      runConfigCommand(["validate"], {
+        configPath,
+        loadConfig: () => loadConfig({ configPath }),
+        checkGitHubAuth,
+      }),
+    ).rejects.toThrowError(`Checked ${configPath}\nerror `);
+    expect(checkGitHubAuth).not.toHaveBeenCalled();
+  });
+
+  it("only knows the validate action", async () => {
+    await expect(
+      runConfigCommand(["check"], { configPath: "config.toml", loadConfig: vi.fn() }),
+    ).rejects.toThrowError("Usage: octopulse config validate");
+  });
+});
+
+function createTempDir(): string {
+  const tempDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-config-command-"));
+  tempDirs.push(tempDir);
+  return tempDir;
+}
+
+function writeConfig(tempDir: string, lines: readonly string[]): string {
+  const configPath = path.join(tempDir, "config.toml");
+  writeFileSync(configPath, [...lines, ""].join("\n"));
+  return configPath;
+}
@@ -0,0 +1,164 @@
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import { listChangedConfigSections, watchConfigFile } from "../src/config-watcher.js";
+import { resolveAppPaths, type AppConfig } from "../src/config.js";
+
+afterEach(() => {
+  vi.useRealTimers();
+});
+
+describe("watchConfigFile", () => {
+  it("reloads once edits to the config file settle and reports the previous config", () => {
+    vi.useFakeTimers();
+    const watcher = createFakeWatcher();
+    const initialConfig = createConfig(60_000);
+    const editedConfig = createConfig(30_000);
+    const load = vi.fn(() => editedConfig);
+    const onChange = vi.fn();
+
+    watchConfigFile(initialConfig, {
+      load,
+      onChange,
+      debounceMs: 500,
+      watchDirectory: watcher.watchDirectory,
+    });
+
+    expect(watcher.watchedDirectoryPath).toBe("/home/me/.config/octopulse");
+
+    watcher.emit("config.toml.swp");
+    watcher.emit("config.toml");
+    vi.advanceTimersByTime(300);
+    watcher.emit("config.toml");
+    vi.advanceTimersByTime(499);
+
+    expect(load).not.toHaveBeenCalled();
+
+    vi.advanceTimersByTime(1);
+
+    expect(load).toHaveBeenCalledTimes(1);
+    expect(onChange).toHaveBeenCalledWith(editedConfig, initialConfig);
+  });
+
+  it("keeps the running settings when the edited file is invalid or unchanged", () => {
+    vi.useFakeTimers();
+    const watcher = createFakeWatcher();
+    const initialConfig = createConfig(60_000);
+    const load = vi
+      .fn<() => AppConfig>()
+      .mockImplementationOnce(() => {
+        throw new Error("Invalid TOML");
+      })
+      .mockImplementationOnce(() => createConfig(60_000))
+      .mockImplementationOnce(() => createConfig(30_000));
+    const onChange = vi.fn();
+
+    watchConfigFile(initialConfig, {
+      load,
+      onChange,
+      debounceMs: 10,
+      watchDirectory: watcher.watchDirectory,
+    });
+
+    for (let reload = 0; reload < 3; reload += 1) {
+      watcher.emit(null);
+      vi.advanceTimersByTime(10);
+    }
+
+    expect(load).toHaveBeenCalledTimes(3);
+    expect(onChange).toHaveBeenCalledTimes(1);
+    expect(onChange).toHaveBeenCalledWith(
+      expect.objectContaining({
+        timings: expect.objectContaining({ trackedPullRequestPollMs: 30_000 }),
+      }),
+      initialConfig,
+    );
+  });
+
+  it("stops reloading once stopped", () => {
+    vi.useFakeTimers();
+    const watcher = createFakeWatcher();
+    const load = vi.fn(() => createConfig(30_000));
+    const handle = watchConfigFile(createConfig(60_000), {
+      load,
+      onChange: vi.fn(),
+      watchDirectory: watcher.watchDirectory,
+    });
+
+    watcher.emit("config.toml");
+    handle.stop();
+    vi.runAllTimers();
+
+    expect(load).not.toHaveBeenCalled();
+    expect(watcher.close).toHaveBeenCalledTimes(1);
+  });
+});
+
+describe("listChangedConfigSections", () => {
+  it("lists the top-level sections whose values differ", () => {
+    const nextConfig = { ...createConfig(30_000), sounds: { comment: null } };
+
+    expect(listChangedConfigSections(createConfig(60_000), nextConfig)).toEqual([
+      "sounds",
+      "timings",
+    ]);
+  });
+});
+
+function createConfig(trackedPullRequestPollMs: number): AppConfig {
+  return {
+    paths: resolveAppPaths({ homeDir: "/home/me" }),
+    githubToken: "ghp_test_123",
+    githubWebUrl: "https://github.com",
+    githubAccounts: [],
+    channels: [],
+    repositoryOverrides: [],
+    securityAlertRepositories: [],
+    inboxRepositories: { repositories: [], pollIntervalMs: 60_000 },
+    actions: { confirm: [], mergeMethod: "merge", reactions: [], replies: [] },
+    notifications: {
+      markGitHubReadAfterDisplay: false,
+      syncGitHubReadState: false,
+      bufferWhileLocked: true,
+      whileScreenShared: "redact",
+      ciFailureAlerts: false,
+      groupByRepository: false,
+      githubInbox: "off",
+      githubInboxReasons: [],
+    },
+    sounds: {},
+    logging: {
+      consoleLevel: "info",
+      fileLevel: "info",
+      format: "text",
+      fileEnabled: true,
+      retentionMs: 14 * 24 * 60 * 60_000,
+    },
+    timings: {
+      trackedPullRequestPollMs,
+      discoveryPollMs: 5 * 60_000,
+      gracePeriodMs: 7 * 24 * 60 * 60_000,
+      avatarCacheTtlMs: 24 * 60 * 60_000,
+    },
+    performance: { pollConcurrency: 4, maxFetchedComments: 300, activityApi: "rest" },
+    server: { port: 3000 },
+    network: { noProxy: [] },
+  };
+}
+
+function createFakeWatcher() {
+  let listener: ((fileName: string | null) => void) | undefined;
+  const fake = {
+    watchedDirectoryPath: undefined as string | undefined,
+    close: vi.fn(),
+    watchDirectory: (directoryPath: string, nextListener: (fileName: string | null) => void) => {
+      fake.watchedDirectoryPath = directoryPath;
+      listener = nextListener;
+      return { on: vi.fn(), close: fake.close };
+    },
+    emit(fileName: string | null): void {
+      listener?.(fileName);
+    },
+  };
+
+  return fake;
+}
@@ -4,7 +4,12 @@ import path from "node:path";
 
 import { afterEach, describe, expect, it } from "vitest";
 
-import { ConfigError, loadConfig, resolveAppPaths } from "../src/config.js";
+import {
+  ConfigError,
+  loadConfig,
+  resolveAppPaths,
+  resolveGitHubAccountPaths,
+} from "../src/config.js";
 
 const tempDirs: string[] = [];
 
@@ -24,6 +29,28 @@ describe("resolveAppPaths", () => {
       stateDirPath: path.join(homeDir, ".local", "state", "octopulse"),
       databasePath: path.join(homeDir, ".local", "state", "octopulse", "octopulse.db"),
       logsDirPath: path.join(homeDir, ".local", "state", "octopulse", "logs"),
+      cacheDirPath: path.join(homeDir, ".cache", "octopulse"),
+      githubTokenPath: path.join(homeDir, ".local", "state", "octopulse", "github-token"),
+    });
+  });
+
+  it("follows absolute XDG base directory variables and ignores relative ones", () => {
+    const paths = resolveAppPaths({
+      homeDir: "/home/me",
+      env: {
+        XDG_CONFIG_HOME: "/etc/xdg-me",
+        XDG_STATE_HOME: "relative/state",
+        XDG_CACHE_HOME: "/var/cache/me",
+      },
+    });
+
+    expect(paths).toEqual({
+      configPath: "/etc/xdg-me/octopulse/config.toml",
+      stateDirPath: "/home/me/.local/state/octopulse",
+      databasePath: "/home/me/.local/state/octopulse/octopulse.db",
+      logsDirPath: "/home/me/.local/state/octopulse/logs",
+      cacheDirPath: "/var/cache/me/octopulse",
+      githubTokenPath: "/home/me/.local/state/octopulse/github-token",
     });
   });
 });
@@ -42,17 +69,377 @@ describe("loadConfig", () => {
     expect(config.githubToken).toBe("ghp_test_123");
     expect(config.openAiApiKey).toBeUndefined();
     expect(config.logging).toEqual({
-      level: "info",
+      consoleLevel: "info",
+      fileLevel: "info",
+      format: "text",
+      fileEnabled: true,
       retentionMs: 14 * 24 * 60 * 60_000,
     });
     expect(config.timings).toEqual({
       trackedPullRequestPollMs: 60_000,
       discoveryPollMs: 5 * 60_000,
       gracePeriodMs: 7 * 24 * 60 * 60_000,
+      avatarCacheTtlMs: 24 * 60 * 60_000,
+    });
+    expect(config.performance).toEqual({
+      pollConcurrency: 4,
+      maxFetchedComments: 300,
+      activityApi: "rest",
+    });
+    expect(config.channels).toEqual([]);
+    expect(config.repositoryOverrides).toEqual([]);
+    expect(config.securityAlertRepositories).toEqual([]);
+    expect(config.inboxRepositories).toEqual({ repositories: [], pollIntervalMs: 60_000 });
+    expect(config.actions).toEqual({
+      confirm: ["approve", "merge"],
+      mergeMethod: "merge",
+      reactions: ["+1", "hooray"],
+      replies: [],
     });
+    expect(config.notifications).toEqual({
+      markGitHubReadAfterDisplay: false,
+      syncGitHubReadState: false,
+      bufferWhileLocked: true,
+      whileScreenShared: "redact",
+      ciFailureAlerts: false,
+      groupByRepository: false,
+      githubInbox: "off",
+      githubInboxReasons: [],
+    });
+    expect(config.sounds).toEqual({});
     expect(config.paths).toEqual(resolveAppPaths({ homeDir }));
   });
 
+  it("enables marking GitHub threads read after display", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[notifications]",
+        "mark_github_read_after_display = true",
+        "",
+      ].join("\n"),
+    );
+
+    expect(loadConfig({ homeDir }).notifications.markGitHubReadAfterDisplay).toBe(true);
+  });
+
+  it("parses the idle hold threshold", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[notifications]",
+        "buffer_while_locked = false",
+        'hold_when_idle_for = "10 minutes"',
+        'aggregation_window = "20s"',
+        "max_popups_per_minute = 6",
+        'sound_cooldown = "30s"',
+        "",
+      ].join("\n"),
+    );
+
+    expect(loadConfig({ homeDir }).notifications).toMatchObject({
+      bufferWhileLocked: false,
+      holdWhenIdleMs: 10 * 60_000,
+      aggregationWindowMs: 20_000,
+      maxPopupsPerMinute: 6,
+      soundCooldownMs: 30_000,
+    });
+  });
+
+  it("reads a popup body template and rejects one that doesn't parse", () => {
+    const homeDir = createTempHome();
+    const writeTemplate = (template: string) =>
+      writeConfig(
+        homeDir,
+        [
+          "[github]",
+          'token = "ghp_test_123"',
+          "",
+          "[notifications]",
+          `body_template = ${JSON.stringify(template)}`,
+          "",
+        ].join("\n"),
+      );
+
+    writeTemplate("<b>{{pullRequest.title}}</b>{{#paragraphs}}\n{{text}}{{/paragraphs}}");
+    expect(loadConfig({ homeDir }).notifications.bodyTemplate).toBe(
+      "<b>{{pullRequest.title}}</b>{{#paragraphs}}\n{{text}}{{/paragraphs}}",
+    );
+
+    writeTemplate("{{#paragraphs}}{{text}}");
+    expect(() => loadConfig({ homeDir })).toThrowError(
+      "notifications.body_template is invalid: {{#paragraphs}} is never closed",
+    );
+  });
+
+  it("reads replacement and silenced notification sounds", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[sounds]",
+        'approved = "/usr/share/sounds/complete.oga"',
+        'comment = ""',
+        "",
+      ].join("\n"),
+    );
+
+    expect(loadConfig({ homeDir }).sounds).toEqual({
+      approved: "/usr/share/sounds/complete.oga",
+      comment: null,
+    });
+  });
+
+  it("rejects a non-string notification sound", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      ["[github]", 'token = "ghp_test_123"', "", "[sounds]", "approved = false", ""].join("\n"),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrowError(ConfigError);
+  });
+
+  it("reads per-repository and per-owner overrides", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        '[repositories."acme/api"]',
+        'sound = ""',
+        'urgency = "critical"',
+        "",
+        "[repositories.dependabot-org]",
+        "mute = true",
+        "",
+      ].join("\n"),
+    );
+
+    expect(loadConfig({ homeDir }).repositoryOverrides).toEqual([
+      { repository: "acme/api", soundFile: null, urgency: "critical" },
+      { repository: "dependabot-org", mute: true },
+    ]);
+  });
+
+  it("rejects a repository override that doesn't name a repository", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      ["[github]", 'token = "ghp_test_123"', "", '[repositories."acme/api/web"]', "mute = true", ""].join(
+        "\n",
+      ),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrowError(
+      'repositories."acme/api/web" must name a repository as owner/name or an owner',
+    );
+  });
+
+  it("reads an optional calendar ICS source", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[calendar]",
+        'ics = "/home/me/calendar.ics"',
+        "",
+      ].join("\n"),
+    );
+
+    expect(loadConfig({ homeDir }).calendarIcsSource).toBe("/home/me/calendar.ics");
+  });
+
+  it("parses a working-hours schedule", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[schedule]",
+        'timezone = "Europe/Berlin"',
+        'outside_hours = "off"',
+        'monday = "09:00-12:00, 13:00-17:30"',
+        'sunday = "off"',
+        "",
+      ].join("\n"),
+    );
+
+    expect(loadConfig({ homeDir }).workingHours).toEqual({
+      timeZone: "Europe/Berlin",
+      outsideHoursMode: "off",
+      days: {
+        monday: [
+          { startMinute: 540, endMinute: 720 },
+          { startMinute: 780, endMinute: 1050 },
+        ],
+        sunday: [],
+      },
+    });
+  });
+
+  it("enables weekend mode with breakthrough repositories", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[schedule]",
+        'timezone = "Europe/Berlin"',
+        'saturday = "10:00-11:00"',
+        "weekend_mode = true",
+        'weekend_repositories = ["acme/production"]',
+        "",
+      ].join("\n"),
+    );
+
+    const config = loadConfig({ homeDir });
+
+    expect(config.weekendMode).toEqual({
+      timeZone: "Europe/Berlin",
+      repositories: ["acme/production"],
+    });
+    expect(config.workingHours?.days.saturday).toEqual([{ startMinute: 0, endMinute: 1440 }]);
+  });
+
+  it("rejects invalid working-hours ranges and timezones", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      ["[github]", 'token = "ghp_test_123"', "", "[schedule]", 'monday = "17:00-09:00"', ""].join(
+        "\n",
+      ),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrow(
+      'schedule.monday must use ranges like "09:00-17:30" within a single day',
+    );
+
+    writeConfig(
+      homeDir,
+      ["[github]", 'token = "ghp_test_123"', "", "[schedule]", 'timezone = "Mars/Olympus"', ""].join(
+        "\n",
+      ),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrow("schedule.timezone must be an IANA timezone");
+  });
+
+  it("rejects unknown screen-share policies", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[notifications]",
+        'while_screen_shared = "blur"',
+        "",
+      ].join("\n"),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrow(
+      "notifications.while_screen_shared must be one of show, redact, or suppress",
+    );
+  });
+
+  it("reads the GitHub inbox mode and reasons", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[notifications]",
+        'github_inbox = "all"',
+        'github_inbox_reasons = ["team_mention", "subscribed"]',
+        "",
+      ].join("\n"),
+    );
+
+    expect(loadConfig({ homeDir }).notifications).toMatchObject({
+      githubInbox: "all",
+      githubInboxReasons: ["team_mention", "subscribed"],
+    });
+  });
+
+  it("rejects unknown GitHub notification reasons", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[notifications]",
+        'github_inbox = "all"',
+        'github_inbox_reasons = ["watching"]',
+        "",
+      ].join("\n"),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrow(
+      "notifications.github_inbox_reasons must be a list of: approval_requested, assign",
+    );
+  });
+
+  it("loads repositories whose notifications are polled on their own", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[inbox_repositories]",
+        'repositories = ["acme/production", "acme/payments"]',
+        'poll_interval = "20s"',
+        "",
+      ].join("\n"),
+    );
+
+    expect(loadConfig({ homeDir }).inboxRepositories).toEqual({
+      repositories: ["acme/production", "acme/payments"],
+      pollIntervalMs: 20_000,
+    });
+  });
+
   it("applies optional OpenAI and timing overrides", () => {
     const homeDir = createTempHome();
 
@@ -67,12 +454,20 @@ describe("loadConfig", () => {
         "",
         "[logging]",
         'level = "debug"',
+        'file_level = "warn"',
+        'format = "json"',
         'retention = "30 days"',
         "",
         "[timings]",
         'tracked_poll_interval = "2 minutes"',
         'discovery_poll_interval = "10m"',
         'grace_period = "3 days"',
+        'avatar_cache_ttl = "6h"',
+        "",
+        "[performance]",
+        "poll_concurrency = 8",
+        "max_fetched_comments = 1000",
+        'activity_api = "graphql"',
         "",
       ].join("\n"),
     );
@@ -82,14 +477,391 @@ describe("loadConfig", () => {
     expect(config.githubToken).toBe("ghp_override_123");
     expect(config.openAiApiKey).toBe("sk-test-456");
     expect(config.logging).toEqual({
-      level: "debug",
+      consoleLevel: "debug",
+      fileLevel: "warn",
+      format: "json",
+      fileEnabled: true,
       retentionMs: 30 * 24 * 60 * 60_000,
     });
     expect(config.timings).toEqual({
       trackedPullRequestPollMs: 2 * 60_000,
       discoveryPollMs: 10 * 60_000,
       gracePeriodMs: 3 * 24 * 60 * 60_000,
+      avatarCacheTtlMs: 6 * 60 * 60_000,
+    });
+    expect(config.performance).toEqual({
+      pollConcurrency: 8,
+      maxFetchedComments: 1000,
+      activityApi: "graphql",
+    });
+  });
+
+  it.each([
+    ['tracked_poll_interval = "10s"', "timings.tracked_poll_interval must be at least 15s"],
+    ['discovery_poll_interval = "30s"', "timings.discovery_poll_interval must be at least 60s"],
+  ])("rejects a poll interval below the minimum: %s", (line, message) => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      ["[github]", 'token = "ghp_test_123"', "", "[timings]", line, ""].join("\n"),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrowError(message);
+  });
+
+  it.each([
+    ['format = "xml"', "logging.format must be one of text or json"],
+    ['file = "no"', "logging.file must be true or false"],
+    ['console_level = "trace"', "logging.console_level must be one of debug, info, warn, or error"],
+  ])("rejects invalid logging setting %s", (line, message) => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      ["[github]", 'token = "ghp_test_123"', "", "[logging]", line, ""].join("\n"),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrowError(message);
+  });
+
+  it("reads the GitHub token from a token file instead of the config", () => {
+    const homeDir = createTempHome();
+    const tokenFilePath = path.join(homeDir, "github-token");
+
+    writeConfig(homeDir, ["[timings]", 'tracked_poll_interval = "2m"', ""].join("\n"));
+    writeFileSync(tokenFilePath, "ghp_from_file_123\n");
+
+    const config = loadConfig({ homeDir, githubTokenFilePath: tokenFilePath });
+
+    expect(config.githubToken).toBe("ghp_from_file_123");
+    expect(() => loadConfig({ homeDir })).toThrowError('Missing required config section "github"');
+  });
+
+  it("falls back to the token saved by login when github.token isn't set", () => {
+    const homeDir = createTempHome();
+    const { githubTokenPath } = resolveAppPaths({ homeDir });
+
+    mkdirSync(path.dirname(githubTokenPath), { recursive: true });
+    writeFileSync(githubTokenPath, "gho_device_123\n");
+    writeConfig(homeDir, ["[github]", 'label = "work"', ""].join("\n"));
+
+    expect(loadConfig({ homeDir }).githubToken).toBe("gho_device_123");
+
+    writeConfig(homeDir, ["[github]", 'token = "ghp_config_123"', ""].join("\n"));
+
+    expect(loadConfig({ homeDir }).githubToken).toBe("ghp_config_123");
+  });
+
+  it("falls back to GITHUB_TOKEN, then the GitHub CLI's token", () => {
+    const homeDir = createTempHome();
+    const ghConfigDirPath = path.join(homeDir, ".config", "gh");
+
+    mkdirSync(ghConfigDirPath, { recursive: true });
+    writeFileSync(
+      path.join(ghConfigDirPath, "hosts.yml"),
+      ["github.com:", "    oauth_token: gho_cli_123", "    user: octocat", ""].join("\n"),
+    );
+    writeConfig(homeDir, ["[timings]", 'tracked_poll_interval = "2m"', ""].join("\n"));
+
+    expect(loadConfig({ homeDir, env: { GITHUB_TOKEN: "ghp_env_123" } }).githubToken).toBe(
+      "ghp_env_123",
+    );
+    expect(loadConfig({ homeDir }).githubToken).toBe("gho_cli_123");
+
+    writeConfig(homeDir, ["[github]", 'token = "ghp_config_123"', ""].join("\n"));
+
+    expect(loadConfig({ homeDir, env: { GITHUB_TOKEN: "ghp_env_123" } }).githubToken).toBe(
+      "ghp_config_123",
+    );
+  });
+
+  it("reads GitHub App credentials instead of a token", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'login = "octopulse-bot"',
+        "",
+        "[github.app]",
+        "id = 12345",
+        "installation_id = 678",
+        `private_key_path = "${path.join(homeDir, "app.pem")}"`,
+        "",
+      ].join("\n"),
+    );
+
+    const config = loadConfig({ homeDir });
+
+    expect(config.githubToken).toBeUndefined();
+    expect(config.githubApp).toEqual({
+      appId: 12345,
+      installationId: 678,
+      privateKeyPath: path.join(homeDir, "app.pem"),
+    });
+  });
+
+  it.each([
+    [['token = "ghp_test_123"', 'login = "bot"'], "github.token and github.app can't both be set"],
+    [[], "github.login is required with github.app"],
+  ])("rejects GitHub App settings with %j", (githubLines, message) => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        ...githubLines,
+        "",
+        "[github.app]",
+        "id = 1",
+        "installation_id = 2",
+        'private_key_path = "/etc/octopulse/app.pem"',
+        "",
+      ].join("\n"),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrowError(message);
+  });
+
+  it("reads extra GitHub accounts with a state directory each", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_work_123"',
+        'label = "work"',
+        "",
+        "[[github.accounts]]",
+        'label = "personal"',
+        'token = "ghp_personal_456"',
+        'login = "octocat"',
+        "",
+      ].join("\n"),
+    );
+
+    const config = loadConfig({ homeDir });
+
+    expect(config.githubAccounts).toEqual([
+      { label: "personal", token: "ghp_personal_456", login: "octocat" },
+    ]);
+    expect(resolveGitHubAccountPaths(config.paths, "personal")).toEqual({
+      ...config.paths,
+      stateDirPath: path.join(config.paths.stateDirPath, "accounts", "personal"),
+      databasePath: path.join(config.paths.stateDirPath, "accounts", "personal", "octopulse.db"),
+    });
+  });
+
+  it.each([
+    [['label = "work"', 'token = "ghp_1"'], 'github.accounts[0].label "work" is already used'],
+    [['label = "my account"', 'token = "ghp_1"'], "github.accounts[0].label must only use"],
+    [['label = "personal"'], "github.accounts[0].token"],
+  ])("rejects the extra GitHub account %j", (accountLines, message) => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_work_123"',
+        'label = "work"',
+        "",
+        "[[github.accounts]]",
+        ...accountLines,
+        "",
+      ].join("\n"),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrowError(message);
+  });
+
+  it("points at GitHub Enterprise Server when github.url is set", () => {
+    const homeDir = createTempHome();
+    const writeGitHubSection = (lines: readonly string[]) =>
+      writeConfig(homeDir, ["[github]", 'token = "ghp_test_123"', ...lines, ""].join("\n"));
+
+    writeGitHubSection([]);
+    expect(loadConfig({ homeDir })).toMatchObject({ githubWebUrl: "https://github.com" });
+    expect(loadConfig({ homeDir }).githubApiUrl).toBeUndefined();
+
+    writeGitHubSection(['url = "https://github.example.com/"']);
+    expect(loadConfig({ homeDir })).toMatchObject({
+      githubWebUrl: "https://github.example.com",
+      githubApiUrl: "https://github.example.com/api/v3",
     });
+
+    writeGitHubSection([
+      'url = "https://github.example.com"',
+      'api_url = "https://api.github.example.com"',
+    ]);
+    expect(loadConfig({ homeDir }).githubApiUrl).toBe("https://api.github.example.com");
+
+    writeGitHubSection(['url = "github.example.com"']);
+    expect(() => loadConfig({ homeDir })).toThrowError("github.url must be an http or https URL");
+  });
+
+  it("reads proxy settings and rejects SOCKS proxies", () => {
+    const homeDir = createTempHome();
+    const writeNetworkSection = (lines: readonly string[]) =>
+      writeConfig(
+        homeDir,
+        ["[github]", 'token = "ghp_test_123"', "", "[network]", ...lines, ""].join("\n"),
+      );
+
+    writeNetworkSection(['proxy = "http://proxy.example.com:3128"', 'no_proxy = [".corp"]']);
+    expect(loadConfig({ homeDir }).network).toEqual({
+      proxy: "http://proxy.example.com:3128",
+      noProxy: [".corp"],
+    });
+
+    writeNetworkSection(['proxy = "socks5://127.0.0.1:1080"']);
+    expect(() => loadConfig({ homeDir })).toThrowError(
+      "network.proxy must be an http or https URL; SOCKS proxies aren't supported",
+    );
+
+    writeNetworkSection(['no_proxy = "a.corp,b.corp"']);
+    expect(() => loadConfig({ homeDir })).toThrowError(
+      "network.no_proxy must be a list of host names or domain suffixes",
+    );
+  });
+
+  it("reads extra root certificates and a client certificate", () => {
+    const homeDir = createTempHome();
+    const writeNetworkSection = (lines: readonly string[]) =>
+      writeConfig(
+        homeDir,
+        ["[github]", 'token = "ghp_test_123"', "", "[network]", ...lines, ""].join("\n"),
+      );
+
+    writeNetworkSection([
+      'ca_certificates_path = "/etc/octopulse/corporate-ca.pem"',
+      'client_certificate_path = "/etc/octopulse/client.pem"',
+      'client_key_path = "/etc/octopulse/client.key"',
+    ]);
+    expect(loadConfig({ homeDir }).network).toEqual({
+      noProxy: [],
+      caCertificatesPath: "/etc/octopulse/corporate-ca.pem",
+      clientCertificate: {
+        certificatePath: "/etc/octopulse/client.pem",
+        keyPath: "/etc/octopulse/client.key",
+      },
+    });
+
+    writeNetworkSection(['client_certificate_path = "/etc/octopulse/client.pem"']);
+    expect(() => loadConfig({ homeDir })).toThrowError(
+      "network.client_certificate_path and network.client_key_path must be set together",
+    );
+
+    writeNetworkSection([
+      'proxy = "http://proxy.example.com:3128"',
+      'client_certificate_path = "/etc/octopulse/client.pem"',
+      'client_key_path = "/etc/octopulse/client.key"',
+    ]);
+    expect(loadConfig({ homeDir }).network).toEqual({
+      proxy: "http://proxy.example.com:3128",
+      noProxy: [],
+      clientCertificate: {
+        certificatePath: "/etc/octopulse/client.pem",
+        keyPath: "/etc/octopulse/client.key",
+      },
+    });
+  });
+
+  it("rejects an empty GitHub token file", () => {
+    const homeDir = createTempHome();
+    const tokenFilePath = path.join(homeDir, "github-token");
+
+    writeConfig(homeDir, ["[github]", 'token = "ghp_test_123"', ""].join("\n"));
+    writeFileSync(tokenFilePath, "  \n");
+
+    expect(() => loadConfig({ homeDir, githubTokenFilePath: tokenFilePath })).toThrowError(
+      `GitHub token file at ${tokenFilePath} is empty`,
+    );
+  });
+
+  it("lets a profile replace whole sections and keep its own state", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_work_123"',
+        'login = "octocat-work"',
+        "",
+        "[sounds]",
+        'approved = "/usr/share/sounds/work.oga"',
+        "",
+        "[performance]",
+        "poll_concurrency = 8",
+        "",
+        "[profiles.oss.github]",
+        'token = "ghp_oss_456"',
+        "",
+        "[profiles.oss.filters]",
+        'paths = ["docs/"]',
+        "",
+        "[profiles.oss.server]",
+        "port = 3001",
+        "",
+      ].join("\n"),
+    );
+
+    const workConfig = loadConfig({ homeDir });
+    const ossConfig = loadConfig({ homeDir, profile: "oss" });
+
+    expect(workConfig.profile).toBeUndefined();
+    expect(workConfig.githubToken).toBe("ghp_work_123");
+    expect(workConfig.server).toEqual({ port: 3000 });
+    expect(ossConfig.profile).toBe("oss");
+    expect(ossConfig.githubToken).toBe("ghp_oss_456");
+    expect(ossConfig.githubLogin).toBeUndefined();
+    expect(ossConfig.filters?.paths).toEqual(["docs/"]);
+    expect(ossConfig.sounds).toEqual({ approved: "/usr/share/sounds/work.oga" });
+    expect(ossConfig.performance).toEqual({
+      pollConcurrency: 8,
+      maxFetchedComments: 300,
+      activityApi: "rest",
+    });
+    expect(ossConfig.server).toEqual({ port: 3001 });
+    expect(ossConfig.paths.configPath).toBe(workConfig.paths.configPath);
+    expect(ossConfig.paths.databasePath).toBe(
+      path.join(workConfig.paths.stateDirPath, "profiles", "oss", "octopulse.db"),
+    );
+    expect(ossConfig.paths.cacheDirPath).toBe(workConfig.paths.cacheDirPath);
+  });
+
+  it("names the profile in profile errors", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[profiles.oss.timings]",
+        'tracked_poll_interval = "5s"',
+        "",
+        "[profiles.home]",
+        "colour = 1",
+        "",
+      ].join("\n"),
+    );
+
+    expect(() => loadConfig({ homeDir, profile: "work" })).toThrowError(
+      'Profile "work" is not defined; the config file defines oss, home',
+    );
+    expect(() => loadConfig({ homeDir, profile: "oss" })).toThrowError(
+      'Profile "oss": timings.tracked_poll_interval must be at least 15s',
+    );
+    expect(() => loadConfig({ homeDir, profile: "home" })).toThrowError(
+      'Unsupported config key "profiles.home.colour"',
+    );
   });
 
   it("accepts an optional GitHub login override", () => {
@@ -106,6 +878,184 @@ describe("loadConfig", () => {
     expect(config.githubLogin).toBe("octocat");
   });
 
+  it("accepts an optional GitHub source label", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      ["[github]", 'token = "ghp_test_123"', 'label = "work"', ""].join("\n"),
+    );
+
+    expect(loadConfig({ homeDir }).githubSourceLabel).toBe("work");
+  });
+
+  it("accepts an optional GitHub Projects board for status display", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      ["[github]", 'token = "ghp_test_123"', "", "[github_project]", 'owner = "acme"', "number = 5", ""].join(
+        "\n",
+      ),
+    );
+
+    expect(loadConfig({ homeDir }).githubProject).toEqual({
+      owner: "acme",
+      number: 5,
+      statusField: "Status",
+    });
+  });
+
+  it("accepts ownership and path filters", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[filters]",
+        "owned_paths_only = true",
+        'owner_teams = ["@acme/payments"]',
+        'paths = ["services/payments/**"]',
+        "",
+      ].join("\n"),
+    );
+
+    expect(loadConfig({ homeDir }).filters).toEqual({
+      ownedPathsOnly: true,
+      ownerTeams: ["@acme/payments"],
+      paths: ["services/payments/**"],
+    });
+  });
+
+  it("rejects owner teams that are not @org/team handles", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      ["[github]", 'token = "ghp_test_123"', "", "[filters]", 'owner_teams = ["payments"]', ""].join("\n"),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrowError(
+      new ConfigError('filters.owner_teams must be a list of "@org/team" strings'),
+    );
+  });
+
+  it("reads path-routed notification channels", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[[channels]]",
+        'name = "frontend"',
+        'paths = ["web/**"]',
+        'urgency = "low"',
+        "",
+        "[[channels]]",
+        'name = "backend"',
+        'paths = ["services/**"]',
+        'sound = "/usr/share/sounds/bell.oga"',
+        "",
+      ].join("\n"),
+    );
+
+    expect(loadConfig({ homeDir }).channels).toEqual([
+      { name: "frontend", paths: ["web/**"], urgency: "low" },
+      { name: "backend", paths: ["services/**"], soundFile: "/usr/share/sounds/bell.oga" },
+    ]);
+  });
+
+  it("lets write actions skip confirmation", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[actions]",
+        "confirm = []",
+        'merge_method = "squash"',
+        'reactions = ["heart"]',
+        'replies = ["Will look today"]',
+        "",
+      ].join("\n"),
+    );
+
+    expect(loadConfig({ homeDir }).actions).toEqual({
+      confirm: [],
+      mergeMethod: "squash",
+      reactions: ["heart"],
+      replies: ["Will look today"],
+    });
+  });
+
+  it("rejects unknown merge methods", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      ["[github]", 'token = "ghp_test_123"', "", "[actions]", 'merge_method = "fast-forward"', ""].join("\n"),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrowError(
+      "actions.merge_method must be one of merge, squash, or rebase",
+    );
+  });
+
+  it("rejects channels without path patterns", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      ["[github]", 'token = "ghp_test_123"', "", "[[channels]]", 'name = "frontend"', ""].join("\n"),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrowError(
+      new ConfigError("channels[0].paths must list at least one path pattern"),
+    );
+  });
+
+  it("accepts an optional webhook relay URL", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      [
+        "[github]",
+        'token = "ghp_test_123"',
+        "",
+        "[webhooks]",
+        'relay_url = "https://smee.io/abc123"',
+        "",
+      ].join("\n"),
+    );
+
+    expect(loadConfig({ homeDir }).webhookRelayUrl).toBe("https://smee.io/abc123");
+  });
+
+  it("rejects webhook relay URLs that are not http or https", () => {
+    const homeDir = createTempHome();
+
+    writeConfig(
+      homeDir,
+      ["[github]", 'token = "ghp_test_123"', "", "[webhooks]", 'relay_url = "ftp://smee.io/abc"', ""].join(
+        "\n",
+      ),
+    );
+
+    expect(() => loadConfig({ homeDir })).toThrowError(
+      new ConfigError("webhooks.relay_url must be an http or https URL"),
+    );
+  });
+
   it("rejects invalid config without echoing secret values", () => {
     const homeDir = createTempHome();
 
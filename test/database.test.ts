@@ -29,6 +29,12 @@ describe("initializeDatabase", () => {
         "EventBundle",
         "NormalizedEvent",
         "NotificationRecord",
+        "NotificationSearch",
+        "NotificationSearch_config",
+        "NotificationSearch_content",
+        "NotificationSearch_data",
+        "NotificationSearch_docsize",
+        "NotificationSearch_idx",
         "PullRequest",
         "PullRequestCiJobState",
         "PullRequestReviewState",
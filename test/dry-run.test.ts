@@ -0,0 +1,84 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import { resolveAppPaths } from "../src/config.js";
+import { initializeDatabase } from "../src/database.js";
+import { createDryRunNotificationDispatcher, openDryRunDatabase } from "../src/dry-run.js";
+
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("createDryRunNotificationDispatcher", () => {
+  it("logs the title, repository, and sound instead of showing a popup", async () => {
+    const logger = { info: vi.fn() };
+    const notificationDispatcher = createDryRunNotificationDispatcher(logger);
+
+    await expect(
+      notificationDispatcher.dispatchNotification({
+        title: "acme/api #7 Add retries",
+        body: "✅ approved by alice",
+        clickUrl: "https://github.com/acme/api/pull/7#pullrequestreview-1",
+        soundFile: "/usr/share/sounds/approved.oga",
+      }),
+    ).resolves.toEqual({ openedClickUrl: false });
+    await notificationDispatcher.dispatchNotification({ title: "Octopulse", body: "Paused" });
+
+    expect(logger.info.mock.calls).toEqual([
+      [
+        "Dry run: would show notification",
+        {
+          title: "acme/api #7 Add retries",
+          repository: "acme/api",
+          sound: "/usr/share/sounds/approved.oga",
+        },
+      ],
+      ["Dry run: would show notification", { title: "Octopulse", repository: null, sound: "none" }],
+    ]);
+  });
+});
+
+describe("openDryRunDatabase", () => {
+  it("reads the saved state but keeps its own writes away from it", () => {
+    const homeDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-dry-run-home-"));
+    tempDirs.push(homeDir);
+    const paths = resolveAppPaths({ homeDir });
+    const database = initializeDatabase(paths);
+
+    database
+      .prepare("INSERT INTO AppState (key, value) VALUES (?, ?)")
+      .run("last_poll", "before");
+    database.close();
+
+    const snapshot = openDryRunDatabase(paths);
+
+    try {
+      expect(readLastPoll(snapshot)).toBe("before");
+      snapshot.prepare("UPDATE AppState SET value = ? WHERE key = ?").run("after", "last_poll");
+      expect(readLastPoll(snapshot)).toBe("after");
+    } finally {
+      snapshot.close();
+    }
+
+    const reopened = initializeDatabase(paths);
+
+    try {
+      expect(readLastPoll(reopened)).toBe("before");
+    } finally {
+      reopened.close();
+    }
+  });
+});
+
+function readLastPoll(database: ReturnType<typeof initializeDatabase>): string | undefined {
+  const row = database.prepare("SELECT value FROM AppState WHERE key = ?").get("last_poll");
+
+  return row?.value === undefined ? undefined : String(row.value);
+}
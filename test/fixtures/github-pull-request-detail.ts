@@ -6,7 +6,13 @@ interface PullRequestDetailFixtureOverrides {
   draft?: boolean;
   closedAt?: string | null;
   mergedAt?: string | null;
+  createdAt?: string;
+  updatedAt?: string;
+  additions?: number;
+  deletions?: number;
   headSha?: string | null;
+  headBranch?: string;
+  body?: string | null;
   baseBranch?: string | null;
   mergeable?: boolean | null;
   mergeableState?: string | null;
@@ -32,10 +38,16 @@ export function createPullRequestDetailFixture(
     title: overrides.title ?? "Refresh pull request polling",
     state: overrides.state ?? "open",
     draft: overrides.draft ?? false,
+    body: overrides.body === undefined ? null : overrides.body,
     closed_at: overrides.closedAt ?? null,
     merged_at: overrides.mergedAt ?? null,
+    ...(overrides.createdAt === undefined ? {} : { created_at: overrides.createdAt }),
+    ...(overrides.updatedAt === undefined ? {} : { updated_at: overrides.updatedAt }),
+    ...(overrides.additions === undefined ? {} : { additions: overrides.additions }),
+    ...(overrides.deletions === undefined ? {} : { deletions: overrides.deletions }),
     head: {
       sha: overrides.headSha ?? "def456",
+      ref: overrides.headBranch ?? "feature/polling",
     },
     base: {
       ref: overrides.baseBranch ?? "main",
@@ -0,0 +1,70 @@
+import { mkdtempSync, rmSync, writeFileSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import { readGhCliToken } from "../src/gh-cli-token.js";
+
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("readGhCliToken", () => {
+  it("reads the token gh keeps in hosts.yml for the host", () => {
+    const configDirPath = writeHostsFile([
+      "github.example.com:",
+      "    oauth_token: ghp_enterprise_123",
+      "github.com:",
+      "    users:",
+      "        octocat:",
+      "            oauth_token: gho_nested_456",
+      '    oauth_token: "gho_cli_789"',
+      "    git_protocol: https",
+    ]);
+    const runGhAuthToken = vi.fn();
+
+    expect(readGhCliToken("github.com", { configDirPath, env: {}, runGhAuthToken })).toBe(
+      "gho_cli_789",
+    );
+    expect(runGhAuthToken).not.toHaveBeenCalled();
+  });
+
+  it("asks gh for a token kept in the keyring", () => {
+    const configDirPath = writeHostsFile(["github.com:", "    user: octocat", ""]);
+    const runGhAuthToken = vi.fn(() => "gho_keyring_123");
+
+    expect(readGhCliToken("github.com", { configDirPath, env: {}, runGhAuthToken })).toBe(
+      "gho_keyring_123",
+    );
+    expect(runGhAuthToken).toHaveBeenCalledWith("github.com", {});
+  });
+
+  it("doesn't run gh for a host it isn't signed in to", () => {
+    const configDirPath = writeHostsFile(["github.example.com:", "    user: octocat", ""]);
+    const runGhAuthToken = vi.fn(() => "gho_keyring_123");
+
+    expect(
+      readGhCliToken("github.com", { configDirPath, env: {}, runGhAuthToken }),
+    ).toBeUndefined();
+    expect(
+      readGhCliToken("github.com", {
+        configDirPath: path.join(configDirPath, "missing"),
+        env: {},
+        runGhAuthToken,
+      }),
+    ).toBeUndefined();
+    expect(runGhAuthToken).not.toHaveBeenCalled();
+  });
+});
+
+function writeHostsFile(lines: readonly string[]): string {
+  const configDirPath = mkdtempSync(path.join(os.tmpdir(), "octopulse-gh-cli-token-"));
+  tempDirs.push(configDirPath);
+  writeFileSync(path.join(configDirPath, "hosts.yml"), lines.join("\n"));
+  return configDirPath;
+}
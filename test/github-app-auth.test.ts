@@ -0,0 +1,108 @@
+import { generateKeyPairSync, verify } from "node:crypto";
+
+import { describe, expect, it, vi } from "vitest";
+
+import {
+  createGitHubAppJwt,
+  createGitHubAppTokenProvider,
+  GitHubAppAuthError,
+} from "../src/github-app-auth.js";
+
+const { privateKey, publicKey } = generateKeyPairSync("rsa", { modulusLength: 2048 });
+const PRIVATE_KEY_PEM = privateKey.export({ type: "pkcs8", format: "pem" }).toString();
+const CREDENTIALS = { appId: 12345, installationId: 678, privateKeyPath: "/etc/app.pem" };
+const NOW_MS = Date.parse("2026-03-01T12:00:00Z");
+
+describe("createGitHubAppJwt", () => {
+  it("signs a short-lived JWT issued by the app", () => {
+    const jwt = createGitHubAppJwt(12345, privateKey, NOW_MS);
+    const [header = "", claims = "", signature = ""] = jwt.split(".");
+
+    expect(JSON.parse(Buffer.from(header, "base64url").toString())).toEqual({
+      alg: "RS256",
+      typ: "JWT",
+    });
+    expect(JSON.parse(Buffer.from(claims, "base64url").toString())).toEqual({
+      iat: NOW_MS / 1000 - 60,
+      exp: NOW_MS / 1000 - 60 + 540,
+      iss: "12345",
+    });
+    expect(
+      verify(
+        "RSA-SHA256",
+        Buffer.from(`${header}.${claims}`),
+        publicKey,
+        Buffer.from(signature, "base64url"),
+      ),
+    ).toBe(true);
+  });
+});
+
+describe("createGitHubAppTokenProvider", () => {
+  it("reuses an installation token until shortly before it expires", async () => {
+    let nowMs = NOW_MS;
+    const fetchImpl = vi
+      .fn()
+      .mockResolvedValueOnce(
+        Response.json({ token: "ghs_first", expires_at: "2026-03-01T13:00:00Z" }, { status: 201 }),
+      )
+      .mockResolvedValueOnce(
+        Response.json({ token: "ghs_second", expires_at: "2026-03-01T14:00:00Z" }, { status: 201 }),
+      );
+    const readToken = createGitHubAppTokenProvider(CREDENTIALS, {
+      fetchImpl,
+      now: () => nowMs,
+      readPrivateKey: () => PRIVATE_KEY_PEM,
+    });
+
+    await expect(Promise.all([readToken(), readToken()])).resolves.toEqual([
+      "ghs_first",
+      "ghs_first",
+    ]);
+    nowMs = Date.parse("2026-03-01T12:54:00Z");
+    await expect(readToken()).resolves.toBe("ghs_first");
+    nowMs = Date.parse("2026-03-01T12:56:00Z");
+    await expect(readToken()).resolves.toBe("ghs_second");
+
+    expect(fetchImpl).toHaveBeenCalledTimes(2);
+    expect(fetchImpl).toHaveBeenCalledWith(
+      "https://api.github.com/app/installations/678/access_tokens",
+      expect.objectContaining({
+        method: "POST",
+        headers: expect.objectContaining({
+          Authorization: expect.stringMatching(/^Bearer [\w-]+\.[\w-]+\.[\w-]+$/),
+        }),
+      }),
+    );
+  });
+
+  it("reports a rejected app with GitHub's status and message", async () => {
+    const readToken = createGitHubAppTokenProvider(CREDENTIALS, {
+      fetchImpl: vi
+        .fn()
+        .mockResolvedValue(Response.json({ message: "Bad credentials" }, { status: 401 })),
+      readPrivateKey: () => PRIVATE_KEY_PEM,
+    });
+
+    const result = readToken();
+
+    await expect(result).rejects.toThrowError(GitHubAppAuthError);
+    await expect(result).rejects.toMatchObject({
+      message: "GitHub App authentication failed with status 401: Bad credentials",
+      status: 401,
+    });
+  });
+
+  it("reports a private key that can't be read", async () => {
+    const fetchImpl = vi.fn();
+    const readToken = createGitHubAppTokenProvider(CREDENTIALS, {
+      fetchImpl,
+      readPrivateKey: () => "not a key",
+    });
+
+    await expect(readToken()).rejects.toThrowError(
+      "Failed to read the GitHub App private key at /etc/app.pem",
+    );
+    expect(fetchImpl).not.toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,31 @@
+import { describe, expect, it } from "vitest";
+
+import { GitHubClockSkewEstimator } from "../src/github-clock.js";
+
+describe("GitHubClockSkewEstimator", () => {
+  it("shifts local since timestamps onto GitHub's clock using Date headers", () => {
+    const localNow = Date.parse("2026-04-10T12:00:30.000Z");
+    const clock = new GitHubClockSkewEstimator(() => localNow);
+
+    expect(clock.toGitHubSince("2026-04-10T12:00:00.000Z")).toBe("2026-04-10T11:59:59.000Z");
+
+    // The local clock runs 30 seconds ahead of GitHub's.
+    clock.observeDateHeader("Fri, 10 Apr 2026 12:00:00 GMT");
+    clock.observeDateHeader("Fri, 10 Apr 2026 12:00:00 GMT");
+    clock.observeDateHeader("not a date");
+
+    expect(clock.skewMs).toBe(-29_500);
+    expect(clock.toGitHubSince("2026-04-10T12:00:30.000Z")).toBe("2026-04-10T11:59:59.500Z");
+  });
+
+  it("raises the estimate when GitHub reports a timestamp in the estimated future", () => {
+    const localNow = Date.parse("2026-04-10T12:00:00.000Z");
+    const clock = new GitHubClockSkewEstimator(() => localNow);
+
+    clock.observeServerTimestamp("2026-04-10T11:59:00Z");
+    expect(clock.skewMs).toBe(0);
+
+    clock.observeServerTimestamp("2026-04-10T12:00:20Z");
+    expect(clock.skewMs).toBe(20_000);
+  });
+});
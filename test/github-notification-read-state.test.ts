@@ -0,0 +1,282 @@
+import type { Octokit } from "octokit";
+import { describe, expect, it, vi } from "vitest";
+
+import {
+  createGitHubNotificationReadStateClient,
+  listUnreadPullRequestNotifications,
+  readUnreadPullRequestNotifications,
+  parsePullRequestHtmlUrl,
+  withGitHubMarkReadAfterDisplay,
+} from "../src/github-notification-read-state.js";
+
+describe("withGitHubMarkReadAfterDisplay", () => {
+  it("marks the pull request's GitHub threads read after dispatching", async () => {
+    const dispatchNotification = vi.fn().mockResolvedValue({ openedClickUrl: false });
+    const readStateClient = {
+      markPullRequestThreadsRead: vi.fn().mockResolvedValue(1),
+    };
+    const dispatcher = withGitHubMarkReadAfterDisplay({ dispatchNotification }, readStateClient);
+
+    await expect(
+      dispatcher.dispatchNotification({
+        title: "acme/octopulse #7 Add notifications",
+        body: "alice: ✅ LGTM",
+        clickUrl: "https://github.com/acme/octopulse/pull/7",
+      }),
+    ).resolves.toEqual({ openedClickUrl: false });
+
+    expect(readStateClient.markPullRequestThreadsRead).toHaveBeenCalledWith({
+      repositoryOwner: "acme",
+      repositoryName: "octopulse",
+      number: 7,
+    });
+  });
+
+  it("leaves the threads unread when the popup was held back", async () => {
+    const readStateClient = {
+      markPullRequestThreadsRead: vi.fn().mockResolvedValue(1),
+    };
+    const dispatcher = withGitHubMarkReadAfterDisplay(
+      { dispatchNotification: vi.fn().mockResolvedValue({ suppressed: true }) },
+      readStateClient,
+    );
+
+    await expect(
+      dispatcher.dispatchNotification({
+        title: "acme/octopulse #7 Add notifications",
+        body: "alice: ✅ LGTM",
+        clickUrl: "https://github.com/acme/octopulse/pull/7",
+      }),
+    ).resolves.toEqual({ suppressed: true });
+
+    expect(readStateClient.markPullRequestThreadsRead).not.toHaveBeenCalled();
+  });
+
+  it("keeps the dispatch successful when marking threads read fails", async () => {
+    const dispatcher = withGitHubMarkReadAfterDisplay(
+      { dispatchNotification: vi.fn().mockResolvedValue(undefined) },
+      { markPullRequestThreadsRead: vi.fn().mockRejectedValue(new Error("missing scope")) },
+    );
+
+    await expect(
+      dispatcher.dispatchNotification({
+        title: "acme/octopulse #7 Add notifications",
+        body: "alice: ✅ LGTM",
+        clickUrl: "https://github.com/acme/octopulse/pull/7",
+      }),
+    ).resolves.toBeUndefined();
+  });
+});
+
+describe("createGitHubNotificationReadStateClient", () => {
+  it("asks GitHub only for changes and follows its poll interval", async () => {
+    const lastModified = "Fri, 10 Apr 2026 12:50:00 GMT";
+    const request = vi
+      .fn()
+      .mockResolvedValueOnce({
+        headers: { "last-modified": lastModified, "x-poll-interval": "120" },
+        data: [
+          {
+            unread: false,
+            last_read_at: "2026-04-10T12:45:00Z",
+            updated_at: "2026-04-10T12:40:00Z",
+            subject: {
+              type: "PullRequest",
+              url: "https://api.github.com/repos/acme/octopulse/pulls/7",
+            },
+          },
+        ],
+      })
+      .mockRejectedValueOnce(Object.assign(new Error("Not Modified"), { status: 304 }));
+    const readStateClient = createGitHubNotificationReadStateClient({
+      request,
+    } as unknown as Octokit);
+
+    await expect(
+      readStateClient.listPullRequestThreadReadStates("2026-04-10T12:00:00.000Z"),
+    ).resolves.toEqual([
+      {
+        coordinates: { repositoryOwner: "acme", repositoryName: "octopulse", number: 7 },
+        unread: false,
+        lastReadAt: "2026-04-10T12:45:00Z",
+      },
+    ]);
+    await expect(
+      readStateClient.listPullRequestThreadReadStates("2026-04-10T12:00:00.000Z"),
+    ).resolves.toEqual([]);
+
+    expect(request).toHaveBeenLastCalledWith(
+      "GET /notifications",
+      expect.objectContaining({
+        headers: expect.objectContaining({ "If-Modified-Since": lastModified }),
+      }),
+    );
+    expect(readStateClient.readPollIntervalMs?.()).toBe(120_000);
+  });
+});
+
+describe("createGitHubNotificationReadStateClient pagination", () => {
+  it("reads every page of notification threads", async () => {
+    const request = vi
+      .fn()
+      .mockResolvedValueOnce({ headers: {}, data: createPullRequestThreads(1, 50) })
+      .mockResolvedValueOnce({ headers: {}, data: createPullRequestThreads(51, 1) });
+    const readStateClient = createGitHubNotificationReadStateClient({
+      request,
+    } as unknown as Octokit);
+
+    const readStates = await readStateClient.listPullRequestThreadReadStates(
+      "2026-04-10T12:00:00.000Z",
+    );
+
+    expect(readStates).toHaveLength(51);
+    expect(readStates.at(-1)?.coordinates.number).toBe(51);
+    expect(request).toHaveBeenLastCalledWith(
+      "GET /notifications",
+      expect.objectContaining({ page: 2, per_page: 50 }),
+    );
+  });
+
+  it("marks a pull request's unread threads read across pages", async () => {
+    const request = vi.fn(async (route: string, parameters: { page?: number }) => {
+      if (route.startsWith("PATCH")) {
+        return { headers: {}, data: null };
+      }
+
+      return parameters.page === 1
+        ? { headers: {}, data: createPullRequestThreads(1, 50, { unread: true }) }
+        : { headers: {}, data: createPullRequestThreads(7, 1, { unread: true, id: 999 }) };
+    });
+    const readStateClient = createGitHubNotificationReadStateClient({
+      request,
+    } as unknown as Octokit);
+
+    await expect(
+      readStateClient.markPullRequestThreadsRead({
+        repositoryOwner: "acme",
+        repositoryName: "octopulse",
+        number: 7,
+      }),
+    ).resolves.toBe(2);
+    expect(request).toHaveBeenCalledWith(
+      "PATCH /notifications/threads/{thread_id}",
+      expect.objectContaining({ thread_id: 999 }),
+    );
+  });
+});
+
+describe("listUnreadPullRequestNotifications", () => {
+  it("lists pull requests from every page of threads with the chosen reasons", async () => {
+    const request = vi
+      .fn()
+      .mockResolvedValueOnce({
+        headers: {},
+        data: [
+          ...createPullRequestThreads(1, 49, { unread: true }),
+          ...createPullRequestThreads(7, 1, { unread: true, reason: "team_mention" }),
+        ],
+      })
+      .mockResolvedValueOnce({
+        headers: {},
+        data: [
+          ...createPullRequestThreads(9, 1, { unread: true, reason: "team_mention" }),
+          ...createPullRequestThreads(7, 1, { unread: true, reason: "team_mention" }),
+        ],
+      });
+
+    await expect(
+      listUnreadPullRequestNotifications({ request } as unknown as Octokit, {
+        participating: false,
+        reasons: ["team_mention"],
+      }),
+    ).resolves.toEqual([
+      { repositoryOwner: "acme", repositoryName: "octopulse", number: 7 },
+      { repositoryOwner: "acme", repositoryName: "octopulse", number: 9 },
+    ]);
+    expect(request).toHaveBeenCalledWith(
+      "GET /notifications",
+      expect.objectContaining({ participating: false, page: 1, per_page: 50 }),
+    );
+    expect(request).toHaveBeenCalledTimes(2);
+  });
+
+  it("reads one repository's threads from its own endpoint", async () => {
+    const request = vi.fn().mockResolvedValueOnce({
+      headers: {},
+      data: createPullRequestThreads(12, 1, { unread: true }),
+    });
+
+    await expect(
+      listUnreadPullRequestNotifications(
+        { request } as unknown as Octokit,
+        { participating: true, reasons: [] },
+        "acme/octopulse",
+      ),
+    ).resolves.toEqual([{ repositoryOwner: "acme", repositoryName: "octopulse", number: 12 }]);
+    expect(request).toHaveBeenCalledWith(
+      "GET /repos/{owner}/{repo}/notifications",
+      expect.objectContaining({ owner: "acme", repo: "octopulse", participating: true }),
+    );
+  });
+});
+
+describe("readUnreadPullRequestNotifications", () => {
+  it("asks GitHub only for changes to a repository's threads", async () => {
+    const lastModified = "Fri, 10 Apr 2026 12:50:00 GMT";
+    const request = vi
+      .fn()
+      .mockResolvedValueOnce({
+        headers: { "last-modified": lastModified, "x-poll-interval": "60" },
+        data: createPullRequestThreads(12, 1, { unread: true }),
+      })
+      .mockRejectedValueOnce(Object.assign(new Error("Not Modified"), { status: 304 }));
+    const client = { request } as unknown as Octokit;
+    const settings = { participating: false, reasons: [] };
+
+    await expect(
+      readUnreadPullRequestNotifications(client, settings, "acme/octopulse"),
+    ).resolves.toEqual({
+      coordinates: [{ repositoryOwner: "acme", repositoryName: "octopulse", number: 12 }],
+      lastModified,
+      pollIntervalMs: 60_000,
+    });
+    await expect(
+      readUnreadPullRequestNotifications(client, settings, "acme/octopulse", lastModified),
+    ).resolves.toBe("not_modified");
+    expect(request).toHaveBeenLastCalledWith(
+      "GET /repos/{owner}/{repo}/notifications",
+      expect.objectContaining({
+        headers: expect.objectContaining({ "If-Modified-Since": lastModified }),
+      }),
+    );
+  });
+});
+
+describe("parsePullRequestHtmlUrl", () => {
+  it("parses pull request URLs and ignores other links", () => {
+    expect(parsePullRequestHtmlUrl("https://github.com/acme/octopulse/pull/7/files")).toEqual({
+      repositoryOwner: "acme",
+      repositoryName: "octopulse",
+      number: 7,
+    });
+    expect(parsePullRequestHtmlUrl("https://github.com/acme/octopulse/issues/7")).toBeNull();
+  });
+});
+
+function createPullRequestThreads(
+  firstNumber: number,
+  count: number,
+  options: { unread?: boolean; id?: number; reason?: string } = {},
+) {
+  return Array.from({ length: count }, (_, index) => ({
+    id: String(options.id ?? firstNumber + index),
+    reason: options.reason ?? "subscribed",
+    unread: options.unread ?? false,
+    last_read_at: options.unread ? null : "2026-04-10T12:45:00Z",
+    updated_at: "2026-04-10T12:40:00Z",
+    subject: {
+      type: "PullRequest",
+      url: `https://api.github.com/repos/acme/octopulse/pulls/${firstNumber + index}`,
+    },
+  }));
+}
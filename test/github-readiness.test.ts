@@ -0,0 +1,64 @@
+import { describe, expect, it, vi } from "vitest";
+
+import { GitHubAuthError } from "../src/github.js";
+import {
+  classifyGitHubConnectivityFailure,
+  describeGitHubConnectivityProblem,
+  waitForGitHubReadiness,
+} from "../src/github-readiness.js";
+
+describe("waitForGitHubReadiness", () => {
+  it("retries the startup check until it passes, reporting each problem", async () => {
+    const offline = new GitHubAuthError("GitHub authentication failed: getaddrinfo ENOTFOUND", {
+      cause: Object.assign(new Error("getaddrinfo ENOTFOUND api.github.com"), {
+        status: 500,
+        cause: Object.assign(new TypeError("fetch failed"), { code: "ENOTFOUND" }),
+      }),
+    });
+    const outage = new GitHubAuthError("GitHub authentication failed: Service Unavailable", {
+      cause: Object.assign(new Error("Service Unavailable"), { status: 503 }),
+    });
+    const check = vi
+      .fn()
+      .mockRejectedValueOnce(offline)
+      .mockRejectedValueOnce(outage)
+      .mockResolvedValueOnce("ready");
+    const onProblem = vi.fn();
+    const sleep = vi.fn().mockResolvedValue(undefined);
+
+    await expect(
+      waitForGitHubReadiness(check, { onProblem, retryDelaysMs: [1_000, 5_000], sleep }),
+    ).resolves.toBe("ready");
+
+    expect(onProblem.mock.calls).toEqual([
+      ["no_network", offline, 1],
+      ["github_down", outage, 2],
+    ]);
+    expect(sleep.mock.calls).toEqual([[1_000], [5_000]]);
+  });
+
+  it("does not retry errors that are not about reaching GitHub", async () => {
+    const onProblem = vi.fn();
+
+    await expect(
+      waitForGitHubReadiness(() => Promise.reject(new Error("database is locked")), { onProblem }),
+    ).rejects.toThrow("database is locked");
+    expect(onProblem).not.toHaveBeenCalled();
+  });
+});
+
+describe("classifyGitHubConnectivityFailure", () => {
+  it("tells a rejected token apart from network and GitHub failures", () => {
+    const badToken = new GitHubAuthError("GitHub authentication failed: invalid token", {
+      cause: Object.assign(new Error("Bad credentials"), { status: 401 }),
+    });
+
+    expect(classifyGitHubConnectivityFailure(badToken)).toBe("bad_token");
+    expect(
+      describeGitHubConnectivityProblem("bad_token", "/home/octocat/.config/octopulse/config.toml"),
+    ).toEqual({
+      title: "GitHub rejected Octopulse's token",
+      body: "Check github.token in /home/octocat/.config/octopulse/config.toml. Octopulse keeps retrying and picks up a corrected token on its own.",
+    });
+  });
+});
@@ -0,0 +1,142 @@
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import {
+  GITHUB_TOKEN_SETTINGS_URL,
+  parseTokenExpiration,
+  startGitHubTokenGuard,
+  type GitHubRequestHookClient,
+} from "../src/github-token-guard.js";
+
+const RECHECK_INTERVAL_MS = 60_000;
+
+afterEach(() => {
+  vi.useRealTimers();
+});
+
+describe("startGitHubTokenGuard", () => {
+  it("pauses polling on a 401 and resumes once a replacement token is accepted", async () => {
+    vi.useFakeTimers();
+
+    const client = createHookClient();
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const onAuthenticationLost = vi.fn();
+    const onAuthenticationRestored = vi.fn();
+    const reauthenticate = vi
+      .fn()
+      .mockRejectedValueOnce(new Error("Bad credentials"))
+      .mockResolvedValueOnce(undefined);
+    const guard = startGitHubTokenGuard(client, {
+      notificationDispatcher: { dispatchNotification },
+      onAuthenticationLost,
+      onAuthenticationRestored,
+      reauthenticate,
+      configPath: "/home/octocat/.config/octopulse/config.toml",
+      recheckIntervalMs: RECHECK_INTERVAL_MS,
+    });
+    const unauthorized = Object.assign(new Error("Bad credentials"), { status: 401 });
+
+    try {
+      expect(() => client.failRequest(unauthorized)).toThrow(unauthorized);
+      expect(() => client.failRequest(unauthorized)).toThrow(unauthorized);
+
+      expect(guard.isAuthenticationLost()).toBe(true);
+      expect(onAuthenticationLost).toHaveBeenCalledTimes(1);
+      expect(dispatchNotification).toHaveBeenCalledTimes(1);
+      expect(dispatchNotification).toHaveBeenLastCalledWith(
+        expect.objectContaining({
+          title: "GitHub token expired — click to re-authenticate",
+          clickUrl: GITHUB_TOKEN_SETTINGS_URL,
+          sticky: true,
+        }),
+      );
+
+      await vi.advanceTimersByTimeAsync(RECHECK_INTERVAL_MS);
+
+      expect(onAuthenticationRestored).not.toHaveBeenCalled();
+
+      await vi.advanceTimersByTimeAsync(RECHECK_INTERVAL_MS);
+
+      expect(onAuthenticationRestored).toHaveBeenCalledTimes(1);
+      expect(guard.isAuthenticationLost()).toBe(false);
+      expect(dispatchNotification).toHaveBeenLastCalledWith(
+        expect.objectContaining({ title: "GitHub token accepted" }),
+      );
+
+      await vi.advanceTimersByTimeAsync(RECHECK_INTERVAL_MS);
+
+      expect(reauthenticate).toHaveBeenCalledTimes(2);
+    } finally {
+      guard.stop();
+    }
+  });
+
+  it("warns once when the token expiration header is within the warning window", () => {
+    const client = createHookClient();
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const guard = startGitHubTokenGuard(client, {
+      notificationDispatcher: { dispatchNotification },
+      onAuthenticationLost: vi.fn(),
+      onAuthenticationRestored: vi.fn(),
+      reauthenticate: vi.fn(),
+      configPath: "/home/octocat/.config/octopulse/config.toml",
+      now: () => Date.parse("2026-04-10T12:00:00.000Z"),
+    });
+
+    try {
+      client.completeRequest({ "github-authentication-token-expiration": "2026-05-10 12:00:00 UTC" });
+      client.completeRequest({ "github-authentication-token-expiration": "2026-04-13 09:00:00 UTC" });
+      client.completeRequest({ "github-authentication-token-expiration": "2026-04-13 09:00:00 UTC" });
+
+      expect(dispatchNotification).toHaveBeenCalledTimes(1);
+      expect(dispatchNotification).toHaveBeenCalledWith(
+        expect.objectContaining({
+          title: "GitHub token expires in 3 days",
+          clickUrl: GITHUB_TOKEN_SETTINGS_URL,
+        }),
+      );
+    } finally {
+      guard.stop();
+    }
+  });
+});
+
+describe("parseTokenExpiration", () => {
+  it("reads GitHub's expiration header format", () => {
+    expect(parseTokenExpiration("2026-04-13 09:00:00 UTC")?.toISOString()).toBe(
+      "2026-04-13T09:00:00.000Z",
+    );
+    expect(parseTokenExpiration("2026-04-13 09:00:00 -0700")?.toISOString()).toBe(
+      "2026-04-13T16:00:00.000Z",
+    );
+    expect(parseTokenExpiration("next week")).toBeNull();
+  });
+});
+
+function createHookClient(): GitHubRequestHookClient & {
+  completeRequest(headers: Record<string, unknown>): void;
+  failRequest(error: Error): void;
+} {
+  const afterHooks: Array<(response: { headers: Record<string, unknown> }) => void> = [];
+  const errorHooks: Array<(error: Error) => unknown> = [];
+
+  return {
+    hook: {
+      after: (_name, hook) => {
+        afterHooks.push(hook);
+      },
+      error: (_name, hook) => {
+        errorHooks.push(hook);
+      },
+    },
+    completeRequest(headers) {
+      for (const hook of afterHooks) {
+        hook({ headers });
+      }
+    },
+    failRequest(error) {
+      for (const hook of errorHooks) {
+        hook(error);
+      }
+    },
+  };
+}
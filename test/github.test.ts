@@ -1,9 +1,18 @@
+import type { Octokit } from "octokit";
 import { describe, expect, it, vi } from "vitest";
 
 import type { AppConfig } from "../src/config.js";
-import { GitHubAuthError, initializeGitHubAuth } from "../src/github.js";
+import {
+  checkGitHubConnectivity,
+  findMissingTokenScopes,
+  formatMissingTokenScopesWarning,
+  GitHubAuthError,
+  initializeGitHubAuth,
+  listRequiredTokenScopes,
+  parseTokenScopesHeader,
+} from "../src/github.js";
 
-const TEST_CONFIG: Pick<AppConfig, "githubToken" | "githubLogin"> = {
+const TEST_CONFIG: Required<Pick<AppConfig, "githubToken">> = {
   githubToken: "ghp_test_secret_123",
 };
 
@@ -20,7 +29,7 @@ describe("initializeGitHubAuth", () => {
       currentUserLogin: "octocat",
     });
 
-    expect(clientFactory).toHaveBeenCalledWith(TEST_CONFIG.githubToken);
+    expect(clientFactory).toHaveBeenCalledWith(TEST_CONFIG);
     expect(currentUserResolver).toHaveBeenCalledWith(client);
   });
 
@@ -68,7 +77,69 @@ describe("initializeGitHubAuth", () => {
       currentUserLogin: "octocat",
     });
 
-    expect(clientFactory).toHaveBeenCalledWith(TEST_CONFIG.githubToken);
+    expect(clientFactory).toHaveBeenCalledWith({
+      githubToken: TEST_CONFIG.githubToken,
+      githubLogin: "octocat",
+    });
     expect(currentUserResolver).not.toHaveBeenCalled();
   });
+
+  it("keeps the token scopes GitHub reports for classic tokens", async () => {
+    const client = { kind: "fake-client" };
+
+    await expect(
+      initializeGitHubAuth(TEST_CONFIG, {
+        clientFactory: () => client,
+        currentUserResolver: async () => ({ login: "octocat", scopes: ["public_repo"] }),
+      }),
+    ).resolves.toEqual({
+      client,
+      currentUserLogin: "octocat",
+      tokenScopes: ["public_repo"],
+    });
+  });
+});
+
+describe("findMissingTokenScopes", () => {
+  it("reports required scopes a classic token lacks, counting scopes broader ones imply", () => {
+    const requiredScopes = listRequiredTokenScopes({
+      notifications: {
+        markGitHubReadAfterDisplay: false,
+        syncGitHubReadState: true,
+        githubInbox: "off",
+      },
+      securityAlertRepositories: ["acme/api"],
+      inboxRepositories: { repositories: [], pollIntervalMs: 60_000 },
+    });
+
+    expect(requiredScopes).toEqual(["repo", "notifications", "security_events"]);
+    expect(findMissingTokenScopes(["repo"], requiredScopes)).toEqual([]);
+    expect(
+      findMissingTokenScopes(parseTokenScopesHeader("public_repo, notifications")!, requiredScopes),
+    ).toEqual(["repo", "security_events"]);
+    expect(parseTokenScopesHeader("")).toEqual([]);
+    expect(parseTokenScopesHeader(undefined)).toBeUndefined();
+    expect(formatMissingTokenScopesWarning(["notifications"])).toBe(
+      "The GitHub token is missing the scope notifications (github.com notifications can't be read or marked read). Regenerate it with this scope and update github.token.",
+    );
+  });
+});
+
+describe("checkGitHubConnectivity", () => {
+  it("reports the token's scopes for when GET /user is skipped", async () => {
+    const request = vi.fn(async () => ({ headers: { "x-oauth-scopes": "public_repo, gist" } }));
+
+    await expect(
+      checkGitHubConnectivity({ request } as unknown as Octokit, TEST_CONFIG.githubToken),
+    ).resolves.toEqual(["public_repo", "gist"]);
+    expect(request).toHaveBeenCalledWith("GET /rate_limit", expect.anything());
+  });
+
+  it("reports no scopes for fine-grained and installation tokens", async () => {
+    const request = vi.fn(async () => ({ headers: {} }));
+
+    await expect(
+      checkGitHubConnectivity({ request } as unknown as Octokit, undefined),
+    ).resolves.toBeUndefined();
+  });
 });
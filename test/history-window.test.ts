@@ -0,0 +1,42 @@
+import { describe, expect, it, vi } from "vitest";
+
+import { openHistoryWindow } from "../src/history-window.js";
+
+describe("openHistoryWindow", () => {
+  it("opens notification history in the first available app-mode browser", async () => {
+    const launchBrowser = vi
+      .fn()
+      .mockResolvedValueOnce(false)
+      .mockResolvedValueOnce(true);
+    const openUrl = vi.fn().mockResolvedValue(undefined);
+
+    await expect(
+      openHistoryWindow({ serverOrigin: "http://127.0.0.1:3000", launchBrowser, openUrl }),
+    ).resolves.toBe("http://127.0.0.1:3000/notification-history");
+    expect(launchBrowser).toHaveBeenLastCalledWith("chromium-browser", [
+      "--app=http://127.0.0.1:3000/notification-history",
+    ]);
+    expect(openUrl).not.toHaveBeenCalled();
+  });
+
+  it("falls back to the default browser", async () => {
+    const openUrl = vi.fn().mockResolvedValue(undefined);
+
+    await openHistoryWindow({
+      serverOrigin: "http://127.0.0.1:3000",
+      launchBrowser: vi.fn().mockResolvedValue(false),
+      openUrl,
+    });
+
+    expect(openUrl).toHaveBeenCalledWith("http://127.0.0.1:3000/notification-history");
+  });
+
+  it("opens the inbox page when asked", async () => {
+    const launchBrowser = vi.fn().mockResolvedValue(true);
+
+    await expect(
+      openHistoryWindow({ serverOrigin: "http://127.0.0.1:3000", page: "inbox", launchBrowser }),
+    ).resolves.toBe("http://127.0.0.1:3000/inbox");
+    expect(launchBrowser).toHaveBeenCalledWith("chromium", ["--app=http://127.0.0.1:3000/inbox"]);
+  });
+});
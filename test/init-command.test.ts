@@ -0,0 +1,118 @@
+import { mkdtempSync, readFileSync, rmSync, statSync, writeFileSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import { loadConfig } from "../src/config.js";
+import { GitHubAuthError } from "../src/github.js";
+import { runInitCommand, type InitPrompter } from "../src/init-command.js";
+
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  vi.restoreAllMocks();
+
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("runInitCommand", () => {
+  it("retries a rejected token and writes a config with the chosen preferences", async () => {
+    const configPath = path.join(createTempDir(), "octopulse", "config.toml");
+    const prompter = createPrompter([
+      "ghp_wrong",
+      "ghp_right",
+      "n",
+      "9:00-17:00",
+      "09:00-17:00",
+      "",
+      "n",
+      "y",
+    ]);
+    const verifyToken = vi.fn(async (token: string) => {
+      if (token === "ghp_wrong") {
+        throw new GitHubAuthError("GitHub authentication failed: invalid token");
+      }
+
+      return "octocat";
+    });
+    const installAutostart = vi.fn((installedConfigPath: string) => ({
+      paths: {
+        repoRoot: "/opt/octopulse",
+        servicePath: "/home/me/.config/systemd/user/octopulse.service",
+        desktopEntryPath: "/home/me/.local/share/applications/octopulse.desktop",
+        configPath: installedConfigPath,
+        stateDirPath: "/home/me/.local/state/octopulse",
+        databasePath: "/home/me/.local/state/octopulse/octopulse.db",
+      },
+      createdConfig: false,
+    }));
+    vi.spyOn(console, "error").mockImplementation(() => undefined);
+
+    const output = await runInitCommand({
+      configPath,
+      prompter,
+      verifyToken,
+      installAutostart,
+      defaultTimeZone: "Europe/Berlin",
+    });
+
+    expect(verifyToken).toHaveBeenCalledTimes(2);
+    expect(output).toContain("Authenticated as octocat.");
+    expect(output).toContain(
+      "Installed user service at /home/me/.config/systemd/user/octopulse.service",
+    );
+    expect(installAutostart).toHaveBeenCalledWith(configPath);
+    expect(prompter.close).toHaveBeenCalledTimes(1);
+    expect(statSync(configPath).mode & 0o777).toBe(0o600);
+
+    const config = loadConfig({ configPath });
+
+    expect(config.githubToken).toBe("ghp_right");
+    expect(config.sounds).toEqual({ approved: null, changesRequested: null, comment: null });
+    expect(config.workingHours).toMatchObject({ timeZone: "Europe/Berlin", outsideHoursMode: "off" });
+  });
+
+  it("keeps an existing config unless told to replace it", async () => {
+    const configPath = path.join(createTempDir(), "config.toml");
+    writeFileSync(configPath, "[github]\ntoken = \"ghp_existing\"\n");
+
+    await expect(
+      runInitCommand({ configPath, prompter: createPrompter([""]), verifyToken: vi.fn() }),
+    ).resolves.toBe(`Kept existing config at ${configPath}`);
+    expect(readFileSync(configPath, "utf8")).toContain("ghp_existing");
+  });
+
+  it("writes nothing when no working token is entered", async () => {
+    const configPath = path.join(createTempDir(), "config.toml");
+    vi.spyOn(console, "error").mockImplementation(() => undefined);
+
+    await expect(
+      runInitCommand({
+        configPath,
+        prompter: createPrompter(["", "ghp_a", "ghp_b"]),
+        verifyToken: async () => {
+          throw new GitHubAuthError("GitHub authentication failed: invalid token");
+        },
+      }),
+    ).rejects.toThrowError("No working GitHub token was entered; nothing was written.");
+    expect(() => statSync(configPath)).toThrowError();
+  });
+});
+
+function createPrompter(answers: string[]): InitPrompter & { close: ReturnType<typeof vi.fn> } {
+  const remainingAnswers = [...answers];
+
+  return {
+    ask: async () => remainingAnswers.shift() ?? "",
+    close: vi.fn(),
+  };
+}
+
+function createTempDir(): string {
+  const tempDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-init-"));
+  tempDirs.push(tempDir);
+  return tempDir;
+}
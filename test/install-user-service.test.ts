@@ -52,6 +52,12 @@ describe("installUserService", () => {
     expect(readFileSync(result.paths.desktopEntryPath, "utf8")).toContain("Name=Octopulse");
     expect(readFileSync(result.paths.desktopEntryPath, "utf8")).toContain("Icon=");
     expect(readFileSync(result.paths.desktopEntryPath, "utf8")).toContain("X-GNOME-UsesNotifications=true");
+    expect(readFileSync(result.paths.desktopEntryPath, "utf8")).toContain(
+      `Exec=/usr/bin/node ${path.join(repoRoot, "dist", "main.js")} protocol %u`,
+    );
+    expect(readFileSync(result.paths.desktopEntryPath, "utf8")).toContain(
+      "MimeType=x-scheme-handler/octopulse;",
+    );
     expect(existsSync(result.paths.configPath)).toBe(true);
     expect(readFileSync(result.paths.configPath, "utf8")).toContain('[github]');
     expect(readFileSync(result.paths.configPath, "utf8")).toContain('token = "ghp_replace_with_your_token"');
@@ -80,6 +86,7 @@ describe("installUserService", () => {
     expect(summary).toContain("systemctl --user daemon-reload");
     expect(summary).toContain("systemctl --user enable --now octopulse.service");
     expect(summary).toContain("journalctl --user -u octopulse.service -f");
+    expect(summary).toContain("xdg-mime default octopulse.desktop x-scheme-handler/octopulse");
   });
 });
 
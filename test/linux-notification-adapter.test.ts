@@ -113,7 +113,8 @@ describe("LinuxNotificationAdapter", () => {
   });
 
   it("does not wait for clickable notifications to close before resolving", async () => {
-    const adapter = new LinuxNotificationAdapter();
+    const onNotificationOpened = vi.fn();
+    const adapter = new LinuxNotificationAdapter({ onNotificationOpened });
     const clickUrl = "https://github.com/acme/octopulse/pull/7";
 
     await expect(
@@ -122,6 +123,7 @@ describe("LinuxNotificationAdapter", () => {
         body: "alice approved review\nShip notifications",
         icon: "/tmp/pull-request-open.svg",
         clickUrl,
+        notificationRecordId: 12,
       }),
     ).resolves.toEqual({
       openedClickUrl: false,
@@ -138,14 +140,67 @@ describe("LinuxNotificationAdapter", () => {
       "desktop-entry": DESKTOP_ENTRY_ID,
     });
     expect(spawn).not.toHaveBeenCalled();
+    expect(onNotificationOpened).not.toHaveBeenCalled();
 
     freedesktopMocks.instances[0]?.emit("action", "default");
 
+    expect(onNotificationOpened).toHaveBeenCalledWith(
+      expect.objectContaining({ notificationRecordId: 12 }),
+    );
     expect(spawn).toHaveBeenCalledWith("xdg-open", [clickUrl], {
       stdio: ["ignore", "pipe", "pipe"],
     });
   });
 
+  it("asks for confirmation before running a write action", async () => {
+    const run = vi.fn().mockResolvedValue(undefined);
+    const adapter = new LinuxNotificationAdapter();
+
+    await adapter.dispatchNotification({
+      title: "acme/octopulse PR #7",
+      body: "alice requested your review\nShip notifications",
+      actions: [
+        { key: "approve", label: "Approve", confirmation: 'Approve acme/octopulse#7 "Ship"?', run },
+      ],
+    });
+
+    expect(freedesktopMocks.Notification).toHaveBeenCalledWith(
+      expect.objectContaining({ actions: { approve: "Approve" } }),
+    );
+
+    freedesktopMocks.instances[0]?.emit("action", "approve");
+    await vi.waitFor(() => expect(freedesktopMocks.instances).toHaveLength(2));
+
+    expect(freedesktopMocks.Notification).toHaveBeenLastCalledWith(
+      expect.objectContaining({
+        summary: "Confirm: Approve",
+        body: 'Approve acme/octopulse#7 "Ship"?',
+        urgency: "critical",
+      }),
+    );
+    expect(run).not.toHaveBeenCalled();
+
+    freedesktopMocks.instances[1]?.emit("action", "confirm");
+
+    await vi.waitFor(() => expect(run).toHaveBeenCalledTimes(1));
+  });
+
+  it("runs write actions without confirmation when none is configured", async () => {
+    const run = vi.fn().mockResolvedValue(undefined);
+    const adapter = new LinuxNotificationAdapter();
+
+    await adapter.dispatchNotification({
+      title: "acme/octopulse PR #7",
+      body: "alice requested your review\nShip notifications",
+      actions: [{ key: "approve", label: "Approve", run }],
+    });
+
+    freedesktopMocks.instances[0]?.emit("action", "approve");
+
+    await vi.waitFor(() => expect(run).toHaveBeenCalledTimes(1));
+    expect(freedesktopMocks.instances).toHaveLength(1);
+  });
+
   it("wraps custom dispatch errors in LinuxNotificationAdapterError", async () => {
     const dispatchNotification = vi
       .fn<(_notification: LinuxNotification) => Promise<LinuxNotificationDispatchResult>>()
@@ -263,6 +318,61 @@ describe("LinuxNotificationAdapter", () => {
     });
   });
 
+  it("renders a configured body template with escaped values", async () => {
+    const avatarCache = {
+      resolveAvatarFileUri: vi.fn().mockResolvedValue("file:///tmp/alice.png"),
+    };
+    freedesktopMocks.getCapabilities.mockResolvedValue(["body-markup", "body-images"]);
+
+    const adapter = new LinuxNotificationAdapter({
+      avatarCache,
+      bodyTemplate:
+        "<b>{{pullRequest.repository}} #{{pullRequest.number}}</b>" +
+        "{{#paragraphs}}\n" +
+        '{{#avatar}}<img src="{{.}}"/> {{/avatar}}{{actor}}: {{text}}' +
+        "{{/paragraphs}}" +
+        "{{^reason}}\n(no reason){{/reason}}",
+    });
+
+    await adapter.dispatchNotification({
+      title: "acme/octopulse #7 Add <notifications>",
+      body: "alice: ✅ LGTM",
+      pullRequest: {
+        repository: "acme/octopulse",
+        number: 7,
+        title: "Add <notifications>",
+        url: "https://github.com/acme/octopulse/pull/7",
+        author: "octocat",
+        state: "open",
+        draft: false,
+        headBranch: null,
+        baseBranch: "main",
+        additions: 12,
+        deletions: 3,
+      },
+      markup: {
+        headerText: "[octopulse] Add <notifications> (open)",
+        headerAvatarKey: "octocat",
+        headerAvatarUrl: null,
+        paragraphs: [
+          {
+            actorLogin: "alice",
+            actorAvatarKey: "alice",
+            actorAvatarUrl: "https://avatars.example.test/alice.png",
+            text: "✅ LGTM & ship it",
+          },
+        ],
+      },
+    });
+
+    expect(freedesktopMocks.Notification).toHaveBeenCalledWith(
+      expect.objectContaining({
+        summary: "",
+        body: '<b>acme/octopulse #7</b>\n<img src="file:///tmp/alice.png"/> alice: ✅ LGTM &amp; ship it\n(no reason)',
+      }),
+    );
+  });
+
   it("falls back to plain body when markup is unsupported", async () => {
     const adapter = new LinuxNotificationAdapter();
 
@@ -286,6 +396,22 @@ describe("LinuxNotificationAdapter", () => {
     });
   });
 
+  it("appends the notification reason to plain bodies", async () => {
+    const adapter = new LinuxNotificationAdapter();
+
+    await adapter.dispatchNotification({
+      title: "acme/octopulse #7 Add notifications",
+      body: "👀 review requested",
+      reason: "You were requested to review",
+    });
+
+    expect(freedesktopMocks.Notification).toHaveBeenCalledWith(
+      expect.objectContaining({
+        body: "👀 review requested\n\nYou were requested to review",
+      }),
+    );
+  });
+
   it("uses markup without images when body-images capability is absent", async () => {
     freedesktopMocks.getCapabilities.mockResolvedValue(["body-markup"]);
 
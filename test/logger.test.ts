@@ -2,7 +2,7 @@ import { mkdtempSync, readdirSync, rmSync, writeFileSync } from "node:fs";
 import os from "node:os";
 import path from "node:path";
 
-import { afterEach, describe, expect, it } from "vitest";
+import { afterEach, describe, expect, it, vi } from "vitest";
 
 import {
   configureAppLogger,
@@ -17,6 +17,7 @@ const tempDirs: string[] = [];
 
 afterEach(() => {
   resetAppLoggerForTesting();
+  vi.restoreAllMocks();
 
   for (const tempDir of tempDirs.splice(0)) {
     rmSync(tempDir, { recursive: true, force: true });
@@ -24,7 +25,7 @@ afterEach(() => {
 });
 
 describe("logger", () => {
-  it("writes JSONL entries and respects the configured minimum level", () => {
+  it("writes JSONL entries and respects the configured minimum level", async () => {
     const logsDirPath = createTempDir("octopulse-logs-");
 
     configureAppLogger({
@@ -40,7 +41,7 @@ describe("logger", () => {
     logger.error("Octopulse failed", { error: new Error("boom") });
 
     const logFiles = readdirSync(logsDirPath);
-    const entries = readRecentLogEntries({ logsDirPath, limit: 10 });
+    const entries = await readRecentLogEntries({ logsDirPath, limit: 10 });
     const errorEntry = entries[0]!;
     const infoEntry = entries[1]!;
 
@@ -63,7 +64,60 @@ describe("logger", () => {
     });
   });
 
-  it("reads recent log entries with an exact level filter across files", () => {
+  it("filters console and file output separately and prints JSON lines to the console", async () => {
+    const logsDirPath = createTempDir("octopulse-log-layers-");
+    const consoleLog = vi.spyOn(console, "log").mockImplementation(() => {});
+    const consoleDebug = vi.spyOn(console, "debug").mockImplementation(() => {});
+
+    configureAppLogger({
+      logsDirPath,
+      minimumLevel: "info",
+      consoleLevel: "debug",
+      fileLevel: "warn",
+      consoleFormat: "json",
+      retentionMs: DEFAULT_LOG_RETENTION_MS,
+    });
+
+    const logger = getLogger();
+    logger.debug("Polling acme/api", { pullRequestCount: 2 });
+    logger.info("Octopulse started");
+
+    expect(consoleDebug).toHaveBeenCalledTimes(1);
+    expect(JSON.parse(String(consoleDebug.mock.calls[0]?.[0]))).toMatchObject({
+      level: "debug",
+      message: "Polling acme/api",
+      context: { pullRequestCount: 2 },
+    });
+    expect(JSON.parse(String(consoleLog.mock.calls[0]?.[0]))).toMatchObject({
+      level: "info",
+      message: "Octopulse started",
+    });
+    await expect(readRecentLogEntries({ logsDirPath })).resolves.toEqual([]);
+
+    logger.warn("GitHub rate limit is low");
+
+    await expect(readRecentLogEntries({ logsDirPath })).resolves.toEqual([
+      expect.objectContaining({ level: "warn", message: "GitHub rate limit is low" }),
+    ]);
+  });
+
+  it("writes no files when the file layer is turned off", () => {
+    const parentDirPath = createTempDir("octopulse-no-log-file-");
+    const logsDirPath = path.join(parentDirPath, "logs");
+
+    configureAppLogger({
+      logsDirPath,
+      minimumLevel: "debug",
+      retentionMs: DEFAULT_LOG_RETENTION_MS,
+      mirrorToConsole: false,
+      writeToFile: false,
+    });
+    getLogger().error("Octopulse failed");
+
+    expect(readdirSync(parentDirPath)).toEqual([]);
+  });
+
+  it("reads recent log entries with an exact level filter across files", async () => {
     const logsDirPath = createTempDir("octopulse-log-reader-");
 
     writeLogFile(logsDirPath, "2026-04-10", [
@@ -91,7 +145,7 @@ describe("logger", () => {
       },
     ]);
 
-    const warnEntries = readRecentLogEntries({
+    const warnEntries = await readRecentLogEntries({
       logsDirPath,
       level: "warn",
       limit: 10,
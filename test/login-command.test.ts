@@ -0,0 +1,132 @@
+import { mkdtempSync, readFileSync, rmSync, statSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import { LoginCommandError, runLoginCommand } from "../src/login-command.js";
+
+const tempDirs: string[] = [];
+const DEVICE_CODE_RESPONSE = {
+  device_code: "3584d83530557fdd1f46af8289938c8ef79f9dc5",
+  user_code: "WDJB-MJHT",
+  verification_uri: "https://github.com/login/device",
+  expires_in: 900,
+  interval: 5,
+};
+
+afterEach(() => {
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("runLoginCommand", () => {
+  it("shows the code, polls until it is entered, and saves a private token file", async () => {
+    const githubTokenPath = path.join(createTempDir(), "state", "github-token");
+    const fetchImpl = createFakeFetch([
+      DEVICE_CODE_RESPONSE,
+      { error: "authorization_pending" },
+      { error: "slow_down", interval: 10 },
+      { access_token: "gho_device_123", token_type: "bearer", scope: "repo,read:project" },
+    ]);
+    const sleep = vi.fn().mockResolvedValue(undefined);
+    const openUrl = vi.fn().mockRejectedValue(new Error("xdg-open not found"));
+    const print = vi.fn();
+    const verifyToken = vi.fn().mockResolvedValue("octocat");
+
+    const output = await runLoginCommand(["--client-id", "Iv1.abc123"], {
+      githubTokenPath,
+      fetchImpl,
+      sleep,
+      openUrl,
+      print,
+      verifyToken,
+    });
+
+    expect(print).toHaveBeenCalledWith(
+      "Open https://github.com/login/device and enter the code WDJB-MJHT",
+    );
+    expect(openUrl).toHaveBeenCalledWith("https://github.com/login/device");
+    expect(sleep.mock.calls).toEqual([[5_000], [5_000], [10_000]]);
+    expect(fetchImpl.mock.calls[0]?.[0]).toBe("https://github.com/login/device/code");
+    expect(readRequestBody(fetchImpl, 0)).toEqual({
+      client_id: "Iv1.abc123",
+      scope: "repo read:project",
+    });
+    expect(readRequestBody(fetchImpl, 3)).toEqual({
+      client_id: "Iv1.abc123",
+      device_code: DEVICE_CODE_RESPONSE.device_code,
+      grant_type: "urn:ietf:params:oauth:grant-type:device_code",
+    });
+    expect(verifyToken).toHaveBeenCalledWith("gho_device_123");
+    expect(readFileSync(githubTokenPath, "utf8")).toBe("gho_device_123\n");
+    expect(statSync(githubTokenPath).mode & 0o777).toBe(0o600);
+    expect(output).toContain("Authenticated as octocat.");
+  });
+
+  it("stops when the login is cancelled on GitHub", async () => {
+    const githubTokenPath = path.join(createTempDir(), "github-token");
+
+    await expect(
+      runLoginCommand([], {
+        githubTokenPath,
+        env: { OCTOPULSE_GITHUB_CLIENT_ID: "Iv1.abc123" },
+        fetchImpl: createFakeFetch([DEVICE_CODE_RESPONSE, { error: "access_denied" }]),
+        sleep: vi.fn().mockResolvedValue(undefined),
+        openUrl: vi.fn().mockResolvedValue(undefined),
+        print: vi.fn(),
+      }),
+    ).rejects.toThrowError("The login was cancelled on GitHub.");
+    expect(() => statSync(githubTokenPath)).toThrowError();
+  });
+
+  it("reports an OAuth app without device flow", async () => {
+    await expect(
+      runLoginCommand(["--client-id=Iv1.abc123"], {
+        githubTokenPath: path.join(createTempDir(), "github-token"),
+        fetchImpl: createFakeFetch([
+          {
+            error: "device_flow_disabled",
+            error_description: "Device Flow must be explicitly enabled for this App",
+          },
+        ]),
+        print: vi.fn(),
+      }),
+    ).rejects.toThrowError(
+      "GitHub didn't return a device code: Device Flow must be explicitly enabled for this App",
+    );
+  });
+
+  it("needs an OAuth app client ID", async () => {
+    const result = runLoginCommand([], { githubTokenPath: "github-token", env: {} });
+
+    await expect(result).rejects.toThrowError(LoginCommandError);
+    await expect(result).rejects.toThrowError("Usage: octopulse login [--client-id <id>]");
+  });
+});
+
+function createFakeFetch(payloads: readonly unknown[]) {
+  const responses = [...payloads];
+
+  return vi.fn(async (_url: string | URL | Request, _init?: RequestInit) => {
+    return new Response(JSON.stringify(responses.shift()), {
+      headers: { "Content-Type": "application/json" },
+    });
+  });
+}
+
+function readRequestBody(
+  fetchImpl: ReturnType<typeof createFakeFetch>,
+  callIndex: number,
+): Record<string, string> {
+  const body = fetchImpl.mock.calls[callIndex]?.[1]?.body;
+
+  return Object.fromEntries(new URLSearchParams(String(body)));
+}
+
+function createTempDir(): string {
+  const tempDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-login-"));
+  tempDirs.push(tempDir);
+  return tempDir;
+}
@@ -45,6 +45,21 @@ describe("parseGitHubPullRequestUrl", () => {
       ),
     );
   });
+
+  it("parses pull request URLs on a GitHub Enterprise Server instance", () => {
+    expect(
+      parseGitHubPullRequestUrl(
+        "https://github.example.com/acme/octopulse/pull/7",
+        "https://github.example.com",
+      ),
+    ).toEqual({ repositoryOwner: "acme", repositoryName: "octopulse", number: 7 });
+    expect(() =>
+      parseGitHubPullRequestUrl(
+        "https://github.com/acme/octopulse/pull/7",
+        "https://github.example.com",
+      ),
+    ).toThrow("Manual tracking only supports github.example.com pull request URLs");
+  });
 });
 
 describe("trackPullRequestByUrl", () => {
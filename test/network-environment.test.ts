@@ -0,0 +1,91 @@
+import type { spawn } from "node:child_process";
+import { EventEmitter } from "node:events";
+
+import { describe, expect, it, vi } from "vitest";
+
+import { relaunchWithEnvironment, resolveNetworkEnvironment } from "../src/network-environment.js";
+
+describe("resolveNetworkEnvironment", () => {
+  it("prefers the configured proxy and keeps local hosts direct", () => {
+    expect(
+      resolveNetworkEnvironment(
+        { proxy: "http://proxy.example.com:3128", noProxy: [".corp"] },
+        { HTTPS_PROXY: "http://other.example.com:8080", NO_PROXY: "ignored.example.com" },
+      ),
+    ).toEqual({
+      NODE_USE_ENV_PROXY: "1",
+      HTTPS_PROXY: "http://proxy.example.com:3128",
+      HTTP_PROXY: "http://proxy.example.com:3128",
+      NO_PROXY: ".corp,localhost,127.0.0.1,::1",
+    });
+  });
+
+  it("falls back to the proxy variables already in the environment", () => {
+    expect(
+      resolveNetworkEnvironment(
+        { noProxy: [] },
+        { http_proxy: "http://proxy.example.com:3128", no_proxy: "github.example.com, localhost" },
+      ),
+    ).toEqual({
+      NODE_USE_ENV_PROXY: "1",
+      HTTPS_PROXY: "http://proxy.example.com:3128",
+      HTTP_PROXY: "http://proxy.example.com:3128",
+      NO_PROXY: "github.example.com,localhost,127.0.0.1,::1",
+    });
+  });
+
+  it("trusts the configured root certificates with or without a proxy", () => {
+    expect(
+      resolveNetworkEnvironment(
+        { noProxy: [], caCertificatesPath: "/etc/octopulse/corporate-ca.pem" },
+        {},
+      ),
+    ).toEqual({ NODE_EXTRA_CA_CERTS: "/etc/octopulse/corporate-ca.pem" });
+    expect(
+      resolveNetworkEnvironment(
+        { noProxy: [], caCertificatesPath: "/etc/octopulse/corporate-ca.pem" },
+        { NODE_EXTRA_CA_CERTS: "/etc/octopulse/corporate-ca.pem" },
+      ),
+    ).toBeUndefined();
+  });
+
+  it("returns nothing without a proxy or once the environment is already set up", () => {
+    expect(resolveNetworkEnvironment({ noProxy: [".corp"] }, {})).toBeUndefined();
+    expect(
+      resolveNetworkEnvironment(
+        { proxy: "http://proxy.example.com:3128", noProxy: [] },
+        {
+          NODE_USE_ENV_PROXY: "1",
+          HTTPS_PROXY: "http://proxy.example.com:3128",
+          HTTP_PROXY: "http://proxy.example.com:3128",
+          NO_PROXY: "localhost,127.0.0.1,::1",
+        },
+      ),
+    ).toBeUndefined();
+  });
+});
+
+describe("relaunchWithEnvironment", () => {
+  it("runs the same command with the extra variables and resolves with its exit code", async () => {
+    const child = Object.assign(new EventEmitter(), { kill: vi.fn() });
+    const spawnImpl = vi.fn(() => child);
+
+    const exitCode = relaunchWithEnvironment(
+      { NODE_USE_ENV_PROXY: "1" },
+      { spawnImpl: spawnImpl as unknown as typeof spawn },
+    );
+    process.emit("SIGTERM", "SIGTERM");
+    child.emit("exit", 3);
+
+    await expect(exitCode).resolves.toBe(3);
+    expect(spawnImpl).toHaveBeenCalledWith(
+      process.execPath,
+      [...process.execArgv, ...process.argv.slice(1)],
+      expect.objectContaining({
+        stdio: "inherit",
+        env: expect.objectContaining({ NODE_USE_ENV_PROXY: "1" }),
+      }),
+    );
+    expect(child.kill).toHaveBeenCalledWith("SIGTERM");
+  });
+});
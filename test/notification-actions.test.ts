@@ -0,0 +1,332 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import type { Octokit } from "octokit";
+import { afterAll, describe, expect, it, vi } from "vitest";
+
+import { resolveAppPaths } from "../src/config.js";
+import { initializeDatabase } from "../src/database.js";
+import {
+  createPullRequestActionProvider,
+  listReviewersAwaitingRerequest,
+} from "../src/notification-actions.js";
+import {
+  NormalizedEventRepository,
+  type NormalizedEventRecord,
+} from "../src/normalized-event-repository.js";
+import {
+  PullRequestRepository,
+  type PullRequestRecord,
+  type UpsertPullRequestInput,
+} from "../src/pull-request-repository.js";
+import {
+  PullRequestReviewStateRepository,
+  type PullRequestReviewStateRecord,
+  type ReviewState,
+} from "../src/pull-request-review-state-repository.js";
+import { RawEventRepository, type RawEventRecord } from "../src/raw-event-repository.js";
+
+const tempDirs: string[] = [];
+
+afterAll(() => {
+  for (const tempDir of tempDirs) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("createPullRequestActionProvider", () => {
+  it("offers approval on review requests and asks for confirmation when configured", async () => {
+    const request = vi.fn().mockResolvedValue({ data: {} });
+    const context = { ...createPullRequest({}, ["review_requested"]), currentUserLogin: "alice" };
+    const client = { request } as unknown as Octokit;
+
+    const confirmed = createPullRequestActionProvider(client, { confirm: ["approve"] })(context);
+    const unconfirmed = createPullRequestActionProvider(client, { confirm: [] })(context);
+
+    expect(confirmed).toEqual([
+      {
+        key: "approve",
+        label: "Approve",
+        confirmation: 'Approve acme/octopulse#7 "Ship notifications"?',
+        run: expect.any(Function),
+      },
+    ]);
+    expect(unconfirmed[0]).not.toHaveProperty("confirmation");
+
+    await unconfirmed[0]?.run();
+
+    expect(request).toHaveBeenCalledWith("POST /repos/{owner}/{repo}/pulls/{pull_number}/reviews", {
+      owner: "acme",
+      repo: "octopulse",
+      pull_number: 7,
+      event: "APPROVE",
+      headers: { "X-GitHub-Api-Version": "2022-11-28" },
+    });
+  });
+
+  it("does not offer approval on authored or closed pull requests", () => {
+    const provider = createPullRequestActionProvider({} as Octokit, { confirm: ["approve"] });
+    const authored = createPullRequest({ authorLogin: "Alice" }, ["review_requested"]);
+    const closed = createPullRequest({ state: "closed" }, ["review_requested"]);
+    const commented = createPullRequest({}, ["issue_comment"]);
+
+    for (const context of [authored, closed, commented]) {
+      expect(provider({ ...context, currentUserLogin: "alice" })).toEqual([]);
+    }
+  });
+
+  it("offers merging approved, green pull requests you authored with the configured method", async () => {
+    const request = vi.fn().mockResolvedValue({ data: { merged: true } });
+    const provider = createPullRequestActionProvider({ request } as unknown as Octokit, {
+      confirm: ["merge"],
+      mergeMethod: "squash",
+    });
+    const ready = createPullRequest(
+      { authorLogin: "alice", mergeableState: "clean", lastSeenHeadSha: "abc123" },
+      ["ci_succeeded"],
+      ["APPROVED"],
+    );
+    const pendingChecks = createPullRequest(
+      { authorLogin: "alice", mergeableState: "blocked" },
+      ["ci_succeeded"],
+      ["APPROVED"],
+    );
+    const changesRequested = createPullRequest(
+      { authorLogin: "alice", mergeableState: "clean" },
+      ["ci_succeeded"],
+      ["APPROVED", "CHANGES_REQUESTED"],
+    );
+    const unapproved = createPullRequest({ authorLogin: "alice", mergeableState: "clean" }, ["ci_succeeded"]);
+
+    const actions = provider({ ...ready, currentUserLogin: "alice" });
+
+    expect(actions).toEqual([
+      {
+        key: "merge",
+        label: "Merge (squash)",
+        confirmation: 'Merge acme/octopulse#7 "Ship notifications" using squash?',
+        run: expect.any(Function),
+      },
+    ]);
+    for (const context of [pendingChecks, changesRequested, unapproved]) {
+      expect(provider({ ...context, currentUserLogin: "alice" })).toEqual([]);
+    }
+
+    await actions[0]?.run();
+
+    expect(request).toHaveBeenCalledWith("PUT /repos/{owner}/{repo}/pulls/{pull_number}/merge", {
+      owner: "acme",
+      repo: "octopulse",
+      pull_number: 7,
+      merge_method: "squash",
+      sha: "abc123",
+      headers: { "X-GitHub-Api-Version": "2022-11-28" },
+    });
+  });
+
+  it("offers re-requesting review from reviewers who requested changes before a push", async () => {
+    const request = vi.fn().mockResolvedValue({ data: {} });
+    const provider = createPullRequestActionProvider({ request } as unknown as Octokit, {
+      confirm: [],
+    });
+    const context = createPullRequest(
+      { authorLogin: "alice" },
+      ["ci_succeeded"],
+      ["CHANGES_REQUESTED"],
+      [
+        { eventType: "pull_request_review", actorLogin: "bob", state: "changes_requested" },
+        { eventType: "committed", actorLogin: "alice" },
+      ],
+    );
+
+    const actions = provider({ ...context, currentUserLogin: "alice" });
+
+    expect(actions).toEqual([
+      {
+        key: "rerequest_review:bob",
+        label: "Re-request review from bob",
+        run: expect.any(Function),
+      },
+    ]);
+
+    await actions[0]?.run();
+
+    expect(request).toHaveBeenCalledWith(
+      "POST /repos/{owner}/{repo}/pulls/{pull_number}/requested_reviewers",
+      {
+        owner: "acme",
+        repo: "octopulse",
+        pull_number: 7,
+        reviewers: ["bob"],
+        headers: { "X-GitHub-Api-Version": "2022-11-28" },
+      },
+    );
+  });
+
+  it("reacts to the newest comment from someone else with the configured reactions", async () => {
+    const request = vi.fn().mockResolvedValue({ data: {} });
+    const provider = createPullRequestActionProvider({ request } as unknown as Octokit, {
+      confirm: [],
+      reactions: ["+1", "rocket"],
+    });
+    const context = createPullRequest({}, ["issue_comment", "review_inline_comment"]);
+    const events = context.events.map((event, index) => ({
+      ...event,
+      payloadJson: JSON.stringify({ commentId: 501 + index }),
+      occurredAt: `2026-04-10T12:0${index}:00.000Z`,
+    }));
+
+    const actions = provider({ ...context, events, currentUserLogin: "alice" });
+
+    expect(actions.map(({ key, label }) => ({ key, label }))).toEqual([
+      { key: "react:+1", label: "👍" },
+      { key: "react:rocket", label: "🚀" },
+    ]);
+
+    await actions[1]?.run();
+
+    expect(request).toHaveBeenCalledWith(
+      "POST /repos/{owner}/{repo}/pulls/comments/{comment_id}/reactions",
+      {
+        owner: "acme",
+        repo: "octopulse",
+        comment_id: 502,
+        content: "rocket",
+        headers: { "X-GitHub-Api-Version": "2022-11-28" },
+      },
+    );
+  });
+
+  it("posts canned replies in the triggering inline thread or the conversation", async () => {
+    const request = vi.fn().mockResolvedValue({ data: {} });
+    const provider = createPullRequestActionProvider({ request } as unknown as Octokit, {
+      confirm: ["reply"],
+      reactions: [],
+      replies: ["Will look today", "Thanks for the thorough review, addressing everything now"],
+    });
+    const inline = createPullRequest({}, ["review_inline_comment"]);
+    const inlineEvents = inline.events.map((event) => ({
+      ...event,
+      payloadJson: JSON.stringify({ commentId: 777 }),
+    }));
+
+    const actions = provider({ ...inline, events: inlineEvents, currentUserLogin: "alice" });
+
+    expect(actions.map(({ key, label, confirmation }) => ({ key, label, confirmation }))).toEqual([
+      {
+        key: "reply:0",
+        label: "Will look today",
+        confirmation: 'Post "Will look today" on acme/octopulse#7?',
+      },
+      {
+        key: "reply:1",
+        label: "Thanks for the thorough review,…",
+        confirmation:
+          'Post "Thanks for the thorough review, addressing everything now" on acme/octopulse#7?',
+      },
+    ]);
+
+    await actions[0]?.run();
+    await provider({ ...createPullRequest({}, ["review_requested"]), currentUserLogin: "alice" })
+      .find((action) => action.key === "reply:0")
+      ?.run();
+
+    expect(request).toHaveBeenNthCalledWith(
+      1,
+      "POST /repos/{owner}/{repo}/pulls/{pull_number}/comments/{comment_id}/replies",
+      expect.objectContaining({ pull_number: 7, comment_id: 777, body: "Will look today" }),
+    );
+    expect(request).toHaveBeenNthCalledWith(
+      2,
+      "POST /repos/{owner}/{repo}/issues/{issue_number}/comments",
+      expect.objectContaining({ issue_number: 7, body: "Will look today" }),
+    );
+  });
+});
+
+describe("listReviewersAwaitingRerequest", () => {
+  it("skips reviewers who have not seen a push or have since approved", () => {
+    const { listRawEvents } = createPullRequest(
+      { authorLogin: "alice" },
+      [],
+      [],
+      [
+        { eventType: "pull_request_review", actorLogin: "bob", state: "changes_requested" },
+        { eventType: "pull_request_review", actorLogin: "carol", state: "changes_requested" },
+        { eventType: "head_ref_force_pushed", actorLogin: "alice" },
+        { eventType: "pull_request_review", actorLogin: "bob", state: "commented" },
+        { eventType: "pull_request_review", actorLogin: "carol", state: "approved" },
+        { eventType: "pull_request_review", actorLogin: "dave", state: "changes_requested" },
+      ],
+    );
+
+    expect(listReviewersAwaitingRerequest(listRawEvents(), "alice")).toEqual(["bob"]);
+  });
+});
+
+function createPullRequest(
+  overrides: Partial<UpsertPullRequestInput>,
+  eventTypes: string[],
+  reviewStates: ReviewState[] = [],
+  rawEvents: Array<{ eventType: string; actorLogin: string; state?: string }> = [],
+): {
+  pullRequest: PullRequestRecord;
+  events: NormalizedEventRecord[];
+  reviewStates: PullRequestReviewStateRecord[];
+  listRawEvents: () => RawEventRecord[];
+} {
+  const homeDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-notification-actions-home-"));
+  tempDirs.push(homeDir);
+  const database = initializeDatabase(resolveAppPaths({ homeDir }));
+  const pullRequest = new PullRequestRepository(database).upsertPullRequest({
+    githubPullRequestId: 101,
+    repositoryOwner: "acme",
+    repositoryName: "octopulse",
+    number: 7,
+    url: "https://github.com/acme/octopulse/pull/7",
+    authorLogin: "bob",
+    title: "Ship notifications",
+    state: "open",
+    isDraft: false,
+    ...overrides,
+  });
+  const normalizedEventRepository = new NormalizedEventRepository(database);
+  const reviewStateRepository = new PullRequestReviewStateRepository(database);
+  const rawEventRepository = new RawEventRepository(database);
+
+  reviewStates.forEach((reviewState, index) => {
+    reviewStateRepository.upsertReviewState({
+      pullRequestId: pullRequest.id,
+      reviewerLogin: `reviewer-${index}`,
+      reviewState,
+    });
+  });
+  rawEvents.forEach((rawEvent, index) => {
+    rawEventRepository.insertRawEvent({
+      pullRequestId: pullRequest.id,
+      source: "timeline",
+      sourceId: String(index + 1),
+      eventType: rawEvent.eventType,
+      actorLogin: rawEvent.actorLogin,
+      payloadJson: JSON.stringify(rawEvent.state === undefined ? {} : { state: rawEvent.state }),
+      occurredAt: `2026-04-10T12:0${index}:00.000Z`,
+    });
+  });
+
+  return {
+    pullRequest,
+    events: eventTypes.map((eventType) =>
+      normalizedEventRepository.insertNormalizedEvent({
+        pullRequestId: pullRequest.id,
+        eventType,
+        actorLogin: "bob",
+        actorClass: "human_other",
+        payloadJson: "{}",
+        occurredAt: "2026-04-10T12:00:00.000Z",
+      }),
+    ),
+    reviewStates: reviewStateRepository.listReviewStatesForPullRequest(pullRequest.id),
+    listRawEvents: () => rawEventRepository.listRawEventsForPullRequest(pullRequest.id),
+  };
+}
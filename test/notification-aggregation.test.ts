@@ -0,0 +1,75 @@
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import { withAggregationWindow } from "../src/notification-aggregation.js";
+
+const WINDOW_MS = 20_000;
+
+afterEach(() => {
+  vi.useRealTimers();
+});
+
+describe("withAggregationWindow", () => {
+  it("merges rapid-fire popups for one pull request into a single notification", async () => {
+    vi.useFakeTimers();
+
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const aggregated = withAggregationWindow({ dispatchNotification }, { windowMs: WINDOW_MS });
+
+    await aggregated.dispatchNotification({
+      title: "acme/api PR #7",
+      body: "alice commented\nFirst thought",
+      clickUrl: "https://github.com/acme/api/pull/7#issuecomment-1",
+    });
+    await vi.advanceTimersByTimeAsync(5_000);
+    await aggregated.dispatchNotification({
+      title: "acme/api PR #7",
+      body: "alice commented\nSecond thought",
+      clickUrl: "https://github.com/acme/api/pull/7#issuecomment-2",
+      sticky: true,
+    });
+    await aggregated.dispatchNotification({
+      title: "acme/api PR #8",
+      body: "bob commented",
+      clickUrl: "https://github.com/acme/api/pull/8",
+    });
+    await aggregated.dispatchNotification({ title: "Octopulse", body: "Token expires soon" });
+
+    expect(dispatchNotification).toHaveBeenCalledTimes(1);
+    expect(dispatchNotification).toHaveBeenCalledWith({ title: "Octopulse", body: "Token expires soon" });
+
+    await vi.advanceTimersByTimeAsync(WINDOW_MS - 5_000);
+
+    expect(dispatchNotification).toHaveBeenCalledTimes(2);
+    expect(dispatchNotification).toHaveBeenLastCalledWith(
+      expect.objectContaining({
+        title: "acme/api PR #7",
+        body: "alice commented\nFirst thought\n\nalice commented\nSecond thought",
+        clickUrl: "https://github.com/acme/api/pull/7#issuecomment-1",
+        sticky: true,
+      }),
+    );
+
+    await vi.advanceTimersByTimeAsync(5_000);
+
+    expect(dispatchNotification).toHaveBeenCalledTimes(3);
+    expect(dispatchNotification).toHaveBeenLastCalledWith(
+      expect.objectContaining({ title: "acme/api PR #8", body: "bob commented" }),
+    );
+  });
+
+  it("delivers held popups right away when stopped", async () => {
+    vi.useFakeTimers();
+
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const aggregated = withAggregationWindow({ dispatchNotification }, { windowMs: WINDOW_MS });
+
+    await aggregated.dispatchNotification({
+      title: "acme/api PR #7",
+      body: "alice commented",
+      clickUrl: "https://github.com/acme/api/pull/7",
+    });
+    aggregated.stop();
+
+    await vi.waitFor(() => expect(dispatchNotification).toHaveBeenCalledTimes(1));
+  });
+});
@@ -0,0 +1,82 @@
+import { describe, expect, it } from "vitest";
+
+import {
+  compileNotificationBodyTemplate,
+  NotificationBodyTemplateError,
+  type NotificationBodyTemplateContext,
+} from "../src/notification-body-template.js";
+
+const CONTEXT: NotificationBodyTemplateContext = {
+  title: "acme/api #7 Add retries",
+  body: "alice: ✅ approved\n\nbob: 💬 commented",
+  reason: null,
+  url: "https://github.com/acme/api/pull/7",
+  header: { text: "[api] Add retries (open)", avatar: null },
+  paragraphs: [
+    { actor: "alice", text: "✅ approved", avatar: "file:///tmp/alice.png" },
+    { actor: null, text: "CI failed", avatar: null },
+  ],
+  pullRequest: {
+    repository: "acme/api",
+    number: 7,
+    title: "Add retries",
+    url: "https://github.com/acme/api/pull/7",
+    author: "octocat",
+    state: "open",
+    draft: false,
+    headBranch: "retries",
+    baseBranch: "main",
+    additions: 40,
+    deletions: 0,
+  },
+};
+
+describe("compileNotificationBodyTemplate", () => {
+  it("fills in values, repeats lists, and reads nested fields", () => {
+    const render = compileNotificationBodyTemplate(
+      "<b>{{ pullRequest.repository }} #{{pullRequest.number}}</b> " +
+        "{{pullRequest.headBranch}} → {{pullRequest.baseBranch}}" +
+        "{{#paragraphs}}\n{{#actor}}<b>{{.}}</b> {{/actor}}{{text}}{{/paragraphs}}",
+    );
+
+    expect(render(CONTEXT)).toBe(
+      "<b>acme/api #7</b> retries → main\n<b>alice</b> ✅ approved\nCI failed",
+    );
+  });
+
+  it("shows sections only when their value is set", () => {
+    const render = compileNotificationBodyTemplate(
+      "{{#reason}}why: {{reason}}{{/reason}}{{^reason}}no reason{{/reason}}" +
+        "{{#pullRequest.draft}} (draft){{/pullRequest.draft}}" +
+        "{{#pullRequest.deletions}} -{{pullRequest.deletions}}{{/pullRequest.deletions}}" +
+        "{{#header.avatar}}<img/>{{/header.avatar}}{{missing}}",
+    );
+
+    expect(render(CONTEXT)).toBe("no reason");
+    expect(render({ ...CONTEXT, reason: "You authored this pull request" })).toBe(
+      "why: You authored this pull request",
+    );
+  });
+
+  it("looks names up on the list item first, then further out", () => {
+    const render = compileNotificationBodyTemplate(
+      "{{#paragraphs}}[{{avatar}}|{{title}}]{{/paragraphs}}",
+    );
+
+    expect(render(CONTEXT)).toBe(
+      "[file:///tmp/alice.png|acme/api #7 Add retries][|acme/api #7 Add retries]",
+    );
+  });
+
+  it.each([
+    ["{{#paragraphs}}{{text}}", "{{#paragraphs}} is never closed"],
+    ["{{text}}{{/paragraphs}}", "{{/paragraphs}} closes a section that was never opened"],
+    ["{{#paragraphs}}{{/reason}}", "{{/reason}} closes {{#paragraphs}}"],
+    ["{{> partial}}", "Unsupported template tag {{> partial}}"],
+  ])("rejects %j", (source, message) => {
+    expect(() => compileNotificationBodyTemplate(source)).toThrowError(
+      NotificationBodyTemplateError,
+    );
+    expect(() => compileNotificationBodyTemplate(source)).toThrowError(message);
+  });
+});
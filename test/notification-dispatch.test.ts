@@ -15,6 +15,7 @@ import {
   type PullRequestRecord,
   type UpsertPullRequestInput,
 } from "../src/pull-request-repository.js";
+import { PullRequestReviewStateRepository } from "../src/pull-request-review-state-repository.js";
 
 const tempDirs: string[] = [];
 
@@ -25,7 +26,7 @@ afterEach(() => {
 });
 
 describe("dispatchPullRequestNotifications", () => {
-  it("dispatches immediate and bundled notifications through the same path", async () => {
+  it("merges immediate and bundled notifications for a pull request into one popup", async () => {
     const { database, pullRequest } = createPullRequest();
     const normalizedEventRepository = new NormalizedEventRepository(database);
     const notificationRecordRepository = new NotificationRecordRepository(database);
@@ -74,10 +75,11 @@ describe("dispatchPullRequestNotifications", () => {
         failedCount: 0,
       });
 
-      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledTimes(2);
-      expect(notificationDispatcher.dispatchNotification).toHaveBeenNthCalledWith(1, expect.objectContaining({
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledTimes(1);
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledWith(expect.objectContaining({
         title: "acme/octopulse #7 Add notifications",
-        body: "alice: ✅ approved",
+        body: "alice: ✅ approved\n\nbob: 💬 commented",
+        reason: "You authored this pull request",
         clickUrl: "https://github.com/acme/octopulse/pull/7",
         icon: expect.stringContaining("pull-request-open.svg"),
         soundFile: expect.stringContaining("approved.wav"),
@@ -85,18 +87,10 @@ describe("dispatchPullRequestNotifications", () => {
         markup: expect.objectContaining({
           headerText: "[octopulse] Add notifications (open)",
           headerAvatarUrl: "https://avatars.example.test/octocat.png",
-        }),
-      }));
-      expect(notificationDispatcher.dispatchNotification).toHaveBeenNthCalledWith(2, expect.objectContaining({
-        title: "acme/octopulse #7 Add notifications",
-        body: "bob: 💬 commented",
-        clickUrl: "https://github.com/acme/octopulse/pull/7",
-        icon: expect.stringContaining("pull-request-open.svg"),
-        soundFile: expect.stringContaining("comment.wav"),
-        sticky: true,
-        markup: expect.objectContaining({
-          headerText: "[octopulse] Add notifications (open)",
-          headerAvatarUrl: "https://avatars.example.test/octocat.png",
+          paragraphs: [
+            expect.objectContaining({ actorLogin: "alice" }),
+            expect.objectContaining({ actorLogin: "bob" }),
+          ],
         }),
       }));
       expect(notificationRecordRepository.listNotificationRecordsForPullRequest(pullRequest.id)).toEqual([
@@ -118,6 +112,152 @@ describe("dispatchPullRequestNotifications", () => {
     }
   });
 
+  it("plays the changes-requested sound over the approval sound on authored pull requests", async () => {
+    const { database, pullRequest } = createPullRequest();
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+    const notificationDispatcher = {
+      dispatchNotification: vi.fn().mockResolvedValue(undefined),
+    };
+
+    try {
+      for (const [actorLogin, eventType] of [
+        ["alice", "review_approved"],
+        ["bob", "review_changes_requested"],
+      ] as const) {
+        normalizedEventRepository.insertNormalizedEvent({
+          pullRequestId: pullRequest.id,
+          eventType,
+          actorLogin,
+          actorClass: "human_other",
+          decisionState: "notified",
+          notificationTiming: "immediate",
+          occurredAt: "2026-04-10T12:00:00.000Z",
+        });
+      }
+
+      await dispatchPullRequestNotifications(database, pullRequest, {
+        currentUserLogin: "octocat",
+        dispatchedAt: "2026-04-10T12:02:45.000Z",
+        notificationDispatcher,
+      });
+
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledWith(
+        expect.objectContaining({
+          soundFile: expect.stringContaining("changes-requested.wav"),
+          sticky: true,
+        }),
+      );
+    } finally {
+      database.close();
+    }
+  });
+
+  it("plays configured sounds and leaves silenced ones out", async () => {
+    const { database, pullRequest } = createPullRequest();
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+    const notificationDispatcher = {
+      dispatchNotification: vi.fn().mockResolvedValue(undefined),
+    };
+
+    try {
+      normalizedEventRepository.insertNormalizedEvent({
+        pullRequestId: pullRequest.id,
+        eventType: "review_changes_requested",
+        actorLogin: "bob",
+        actorClass: "human_other",
+        decisionState: "notified",
+        notificationTiming: "immediate",
+        occurredAt: "2026-04-10T12:00:00.000Z",
+      });
+
+      await dispatchPullRequestNotifications(database, pullRequest, {
+        currentUserLogin: "octocat",
+        dispatchedAt: "2026-04-10T12:02:45.000Z",
+        notificationDispatcher,
+        notificationSounds: { changesRequested: "/sounds/warning.oga" },
+      });
+
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenLastCalledWith(
+        expect.objectContaining({ soundFile: "/sounds/warning.oga" }),
+      );
+
+      normalizedEventRepository.insertNormalizedEvent({
+        pullRequestId: pullRequest.id,
+        eventType: "review_approved",
+        actorLogin: "alice",
+        actorClass: "human_other",
+        decisionState: "notified",
+        notificationTiming: "immediate",
+        occurredAt: "2026-04-10T12:05:00.000Z",
+      });
+
+      await dispatchPullRequestNotifications(database, pullRequest, {
+        currentUserLogin: "octocat",
+        dispatchedAt: "2026-04-10T12:06:00.000Z",
+        notificationDispatcher,
+        notificationSounds: { approved: null },
+      });
+
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledTimes(2);
+      expect(notificationDispatcher.dispatchNotification.mock.calls[1]?.[0]).not.toHaveProperty(
+        "soundFile",
+      );
+    } finally {
+      database.close();
+    }
+  });
+
+  it("lists reviewers whose latest review still requests changes", async () => {
+    const { database, pullRequest } = createPullRequest();
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+    const reviewStateRepository = new PullRequestReviewStateRepository(database);
+    const notificationDispatcher = {
+      dispatchNotification: vi.fn().mockResolvedValue(undefined),
+    };
+
+    try {
+      reviewStateRepository.upsertReviewState({
+        pullRequestId: pullRequest.id,
+        reviewerLogin: "alice",
+        reviewState: "APPROVED",
+      });
+      reviewStateRepository.upsertReviewState({
+        pullRequestId: pullRequest.id,
+        reviewerLogin: "carol",
+        reviewState: "CHANGES_REQUESTED",
+      });
+      normalizedEventRepository.insertNormalizedEvent({
+        pullRequestId: pullRequest.id,
+        eventType: "review_approved",
+        actorLogin: "alice",
+        actorClass: "human_other",
+        decisionState: "notified",
+        notificationTiming: "immediate",
+        payloadJson: "{}",
+        occurredAt: "2026-04-10T12:00:00.000Z",
+      });
+
+      await dispatchPullRequestNotifications(database, pullRequest, {
+        currentUserLogin: "octocat",
+        notificationDispatcher,
+      });
+
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledWith(
+        expect.objectContaining({
+          body: "alice: ✅ approved\n\n⛔ Changes requested by carol",
+          markup: expect.objectContaining({
+            paragraphs: [
+              expect.objectContaining({ actorLogin: "alice" }),
+              expect.objectContaining({ actorLogin: null, text: "⛔ Changes requested by carol" }),
+            ],
+          }),
+        }),
+      );
+    } finally {
+      database.close();
+    }
+  });
+
   it("marks notification records failed when dispatch errors occur", async () => {
     const { database, pullRequest } = createPullRequest();
     const normalizedEventRepository = new NormalizedEventRepository(database);
@@ -249,6 +389,100 @@ describe("dispatchPullRequestNotifications", () => {
     }
   });
 
+  it("applies the sound and urgency of the first channel matching a changed file", async () => {
+    const { database, pullRequest } = createPullRequest({
+      authorLogin: "alice",
+      changedFilePaths: ["services/payments/api.ts"],
+    });
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+    const notificationDispatcher = {
+      dispatchNotification: vi.fn().mockResolvedValue(undefined),
+    };
+
+    try {
+      normalizedEventRepository.insertNormalizedEvent({
+        pullRequestId: pullRequest.id,
+        eventType: "issue_comment",
+        actorLogin: "bob",
+        actorClass: "human_other",
+        decisionState: "notified",
+        payloadJson: JSON.stringify({}),
+        occurredAt: "2026-04-10T12:01:00.000Z",
+      });
+      bundlePullRequestEvents(database, pullRequest.id);
+
+      await dispatchPullRequestNotifications(database, pullRequest, {
+        currentUserLogin: "octocat",
+        notificationDispatcher,
+        notificationChannels: [
+          { name: "frontend", paths: ["web/**"], urgency: "low" },
+          { name: "backend", paths: ["services/**"], soundFile: "/sounds/bell.oga", urgency: "critical" },
+        ],
+      });
+
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledWith(expect.objectContaining({
+        body: "bob: 💬 commented",
+        soundFile: "/sounds/bell.oga",
+        urgency: "critical",
+        sticky: true,
+      }));
+    } finally {
+      database.close();
+    }
+  });
+
+  it("applies the repository's own override over its owner's and skips popups for muted ones", async () => {
+    const { database, pullRequest } = createPullRequest();
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+    const notificationRecordRepository = new NotificationRecordRepository(database);
+    const notificationDispatcher = {
+      dispatchNotification: vi.fn().mockResolvedValue(undefined),
+    };
+
+    try {
+      for (const occurredAt of ["2026-04-10T12:00:00.000Z", "2026-04-10T12:05:00.000Z"]) {
+        normalizedEventRepository.insertNormalizedEvent({
+          pullRequestId: pullRequest.id,
+          eventType: "review_approved",
+          actorLogin: "alice",
+          actorClass: "human_other",
+          decisionState: "notified",
+          notificationTiming: "immediate",
+          occurredAt,
+        });
+
+        await dispatchPullRequestNotifications(database, pullRequest, {
+          currentUserLogin: "octocat",
+          dispatchedAt: occurredAt,
+          notificationDispatcher,
+          repositoryOverrides:
+            notificationDispatcher.dispatchNotification.mock.calls.length === 0
+              ? [
+                  { repository: "acme", mute: true },
+                  { repository: "Acme/Octopulse", soundFile: null, urgency: "low" },
+                ]
+              : [{ repository: "acme", mute: true }],
+        });
+      }
+
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledTimes(1);
+      expect(notificationDispatcher.dispatchNotification.mock.calls[0]?.[0]).toMatchObject({
+        urgency: "low",
+        sticky: false,
+      });
+      expect(notificationDispatcher.dispatchNotification.mock.calls[0]?.[0]).not.toHaveProperty(
+        "soundFile",
+      );
+      expect(
+        notificationRecordRepository
+          .listNotificationRecordsForPullRequest(pullRequest.id)
+          .map((record) => record.deliveryStatus),
+      ).toEqual(["sent", "sent"]);
+    } finally {
+      database.close();
+    }
+  });
+
   it("does not attach sounds to unrelated own pull request notifications", async () => {
     const { database, pullRequest } = createPullRequest();
     const normalizedEventRepository = new NormalizedEventRepository(database);
@@ -325,6 +559,7 @@ describe("dispatchPullRequestNotifications", () => {
 
       expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledWith(expect.objectContaining({
         body: "👀 review requested",
+        reason: "You were requested to review",
         sticky: true,
       }));
     } finally {
@@ -332,6 +567,38 @@ describe("dispatchPullRequestNotifications", () => {
     }
   });
 
+  it("names the team only when a review request went to one of the user's teams", async () => {
+    const { database, pullRequest } = createPullRequest({ authorLogin: "alice" });
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+    const notificationDispatcher = {
+      dispatchNotification: vi.fn().mockResolvedValue(undefined),
+    };
+
+    try {
+      normalizedEventRepository.insertNormalizedEvent({
+        pullRequestId: pullRequest.id,
+        eventType: "review_requested",
+        decisionState: "notified",
+        notificationTiming: "immediate",
+        payloadJson: JSON.stringify({ requestedTeamSlug: "frontend" }),
+        occurredAt: "2026-04-10T12:01:00.000Z",
+      });
+
+      await dispatchPullRequestNotifications(database, pullRequest, {
+        currentUserLogin: "octocat",
+        notificationDispatcher,
+      });
+
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledWith(
+        expect.objectContaining({
+          reason: "Your team @acme/frontend was requested to review",
+        }),
+      );
+    } finally {
+      database.close();
+    }
+  });
+
   it("keeps ready-for-review notifications sticky even when the pull request is not authored by the current user", async () => {
     const { database, pullRequest } = createPullRequest({ authorLogin: "alice" });
     const normalizedEventRepository = new NormalizedEventRepository(database);
@@ -0,0 +1,115 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it } from "vitest";
+
+import { resolveAppPaths } from "../src/config.js";
+import { initializeDatabase } from "../src/database.js";
+import { NormalizedEventRepository } from "../src/normalized-event-repository.js";
+import {
+  NotificationExportError,
+  parseExportCommandArgs,
+  runExportCommand,
+} from "../src/notification-export.js";
+import { NotificationRecordRepository } from "../src/notification-record-repository.js";
+import { PullRequestRepository } from "../src/pull-request-repository.js";
+
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("runExportCommand", () => {
+  it("exports notification history as CSV and JSON within the requested window", () => {
+    const database = createDatabase();
+
+    try {
+      const pullRequest = new PullRequestRepository(database).upsertPullRequest({
+        githubPullRequestId: 101,
+        repositoryOwner: "acme",
+        repositoryName: "octopulse",
+        number: 7,
+        url: "https://github.com/acme/octopulse/pull/7",
+        authorLogin: "octocat",
+        title: "Add notifications",
+        state: "open",
+        isDraft: false,
+        lastSeenAt: "2026-04-10T12:00:00.000Z",
+        lastSeenHeadSha: "abc123",
+      });
+      const normalizedEventRepository = new NormalizedEventRepository(database);
+      const event = normalizedEventRepository.insertNormalizedEvent({
+        pullRequestId: pullRequest.id,
+        eventType: "issue_comment",
+        actorLogin: "alice",
+        actorClass: "human_other",
+        decisionState: "notified",
+        notificationTiming: "immediate",
+        occurredAt: "2026-04-10T12:00:00.000Z",
+      });
+      const oldEvent = normalizedEventRepository.insertNormalizedEvent({
+        pullRequestId: pullRequest.id,
+        eventType: "review_approved",
+        actorLogin: "bob",
+        actorClass: "human_other",
+        decisionState: "notified",
+        notificationTiming: "immediate",
+        occurredAt: "2026-02-01T08:00:00.000Z",
+      });
+      const notificationRecordRepository = new NotificationRecordRepository(database);
+      const recent = notificationRecordRepository.createNotificationRecord({
+        normalizedEventId: event.id,
+        pullRequestId: pullRequest.id,
+        title: "acme/octopulse PR #7",
+        body: 'alice commented: "looks good, ship it"',
+        clickUrl: pullRequest.url,
+      });
+      const old = notificationRecordRepository.createNotificationRecord({
+        pullRequestId: pullRequest.id,
+        normalizedEventId: oldEvent.id,
+        title: "old",
+        body: "old",
+      });
+
+      database
+        .prepare("UPDATE NotificationRecord SET created_at = ? WHERE id = ?")
+        .run("2026-04-10 12:00:05", recent.id);
+      database
+        .prepare("UPDATE NotificationRecord SET created_at = ? WHERE id = ?")
+        .run("2026-02-01 08:00:00", old.id);
+
+      const now = new Date("2026-04-20T00:00:00.000Z");
+      const csv = runExportCommand(["--format", "csv", "--since", "30d"], database, now);
+
+      expect(csv.split("\n")).toEqual([
+        "id,created_at,delivered_at,read_at,delivery_status,repository,pull_request_number,pull_request_title,pull_request_author,actors,event_types,title,body,click_url",
+        `${recent.id},2026-04-10 12:00:05,,,pending,acme/octopulse,7,Add notifications,octocat,alice,issue_comment,acme/octopulse PR #7,"alice commented: ""looks good, ship it""",https://github.com/acme/octopulse/pull/7`,
+      ]);
+
+      const json = JSON.parse(runExportCommand([], database, now)) as Array<{ title: string }>;
+
+      expect(json.map((row) => row.title)).toEqual(["old", "acme/octopulse PR #7"]);
+    } finally {
+      database.close();
+    }
+  });
+});
+
+describe("parseExportCommandArgs", () => {
+  it("rejects unknown flags and formats", () => {
+    expect(() => parseExportCommandArgs(["--verbose"])).toThrow(NotificationExportError);
+    expect(() => parseExportCommandArgs(["--format", "xml"])).toThrow(NotificationExportError);
+    expect(() => parseExportCommandArgs(["--since"])).toThrow(NotificationExportError);
+  });
+});
+
+function createDatabase(): ReturnType<typeof initializeDatabase> {
+  const tempDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-export-"));
+  tempDirs.push(tempDir);
+
+  return initializeDatabase(resolveAppPaths({ homeDir: tempDir }));
+}
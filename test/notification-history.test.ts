@@ -189,6 +189,7 @@ describe("listNotificationHistory", () => {
               pullRequestStates: ["tracked"],
               repository: "acme/octopulse",
               actorClass: "human_other",
+              search: "",
             },
           }).entries,
         ).toEqual([
@@ -203,6 +204,7 @@ describe("listNotificationHistory", () => {
               pullRequestStates: ["open"],
               repository: "",
               actorClass: "",
+              search: "",
             },
           }).entries,
         ).toEqual([
@@ -280,6 +282,52 @@ describe("listNotificationHistory", () => {
       database.close();
     }
   });
+
+  it("lists only unread records for the inbox until they are marked read", () => {
+    const { database, pullRequest } = createPullRequest();
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+    const notificationRecordRepository = new NotificationRecordRepository(database);
+
+    try {
+      const [firstRecord, secondRecord] = ["alice", "bob"].map((actorLogin, index) =>
+        notificationRecordRepository.createNotificationRecord({
+          normalizedEventId: normalizedEventRepository.insertNormalizedEvent({
+            pullRequestId: pullRequest.id,
+            eventType: "issue_comment",
+            actorLogin,
+            actorClass: "human_other",
+            decisionState: "notified",
+            occurredAt: `2026-04-10T12:0${index}:00.000Z`,
+          }).id,
+          pullRequestId: pullRequest.id,
+          title: "acme/octopulse PR #7",
+          body: `${actorLogin} commented`,
+          clickUrl: pullRequest.url,
+          deliveryStatus: "sent",
+          deliveredAt: `2026-04-10T12:0${index}:05.000Z`,
+        }),
+      );
+
+      notificationRecordRepository.markNotificationRecordRead(firstRecord!.id, "2026-04-10T12:05:00.000Z");
+
+      expect(listNotificationHistory(database, { unreadOnly: true })).toEqual(
+        expect.objectContaining({
+          entries: [expect.objectContaining({ id: secondRecord!.id, readAt: null })],
+          totalCount: 1,
+        }),
+      );
+
+      notificationRecordRepository.markNotificationRecordRead(secondRecord!.id, "2026-04-10T12:06:00.000Z");
+      notificationRecordRepository.markNotificationRecordUnread(firstRecord!.id);
+
+      expect(
+        listNotificationHistory(database, { unreadOnly: true }).entries.map((entry) => entry.id),
+      ).toEqual([firstRecord!.id]);
+      expect(listNotificationHistory(database).totalCount).toBe(2);
+    } finally {
+      database.close();
+    }
+  });
 });
 
 function createRepository(): {
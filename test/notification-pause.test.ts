@@ -0,0 +1,45 @@
+import { describe, expect, it, vi } from "vitest";
+
+import { withNotificationPause } from "../src/notification-pause.js";
+
+const HISTORY_URL = "http://127.0.0.1:3000/notification-history";
+
+describe("withNotificationPause", () => {
+  it("holds notifications while paused and summarizes them on resume", async () => {
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const pausableDispatcher = withNotificationPause(
+      { dispatchNotification },
+      { overflowClickUrl: () => HISTORY_URL },
+    );
+
+    await expect(pausableDispatcher.togglePaused()).resolves.toBe(true);
+    await pausableDispatcher.dispatchNotification({ title: "first", body: "one" });
+    await pausableDispatcher.dispatchNotification({ title: "second", body: "two" });
+
+    expect(dispatchNotification).not.toHaveBeenCalled();
+
+    await expect(pausableDispatcher.togglePaused()).resolves.toBe(false);
+
+    expect(dispatchNotification).toHaveBeenCalledWith({
+      title: "2 notifications while you were away",
+      body: "first\nsecond",
+      clickUrl: HISTORY_URL,
+      sticky: true,
+    });
+  });
+
+  it("still shows critical notifications while paused", async () => {
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const pausableDispatcher = withNotificationPause({ dispatchNotification });
+    const alert = {
+      title: "🚨 acme/api code scanning alert",
+      body: "SQL injection (high)",
+      urgency: "critical" as const,
+    };
+
+    await pausableDispatcher.togglePaused();
+    await pausableDispatcher.dispatchNotification(alert);
+
+    expect(dispatchNotification).toHaveBeenCalledWith(alert);
+  });
+});
@@ -200,6 +200,41 @@ describe("preparePullRequestNotifications", () => {
       database.close();
     }
   });
+
+  it("skips bundles whose only activity is the current user's own", () => {
+    const { database, pullRequest } = createPullRequest();
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+    const notificationRecordRepository = new NotificationRecordRepository(database);
+
+    try {
+      normalizedEventRepository.insertNormalizedEvent({
+        pullRequestId: pullRequest.id,
+        eventType: "issue_comment",
+        actorLogin: "OctoCat",
+        actorClass: "human_other",
+        decisionState: "notified",
+        payloadJson: JSON.stringify({ bodyText: "Addressed the feedback" }),
+        occurredAt: "2026-04-10T12:01:00.000Z",
+      });
+
+      expect(bundlePullRequestEvents(database, pullRequest.id)).toEqual({
+        eligibleCount: 1,
+        bundledCount: 1,
+        createdBundleCount: 1,
+      });
+
+      expect(
+        preparePullRequestNotifications(database, pullRequest, { currentUserLogin: "octocat" }),
+      ).toEqual({
+        immediateCount: 0,
+        bundledCount: 0,
+        createdCount: 0,
+      });
+      expect(notificationRecordRepository.listNotificationRecordsForPullRequest(pullRequest.id)).toEqual([]);
+    } finally {
+      database.close();
+    }
+  });
 });
 
 function createRepository(): {
@@ -0,0 +1,53 @@
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import { withDisplayRateLimit } from "../src/notification-rate-limit.js";
+
+afterEach(() => {
+  vi.useRealTimers();
+});
+
+describe("withDisplayRateLimit", () => {
+  it("holds popups over the per-minute cap and summarizes them once there is room", async () => {
+    vi.useFakeTimers();
+    vi.setSystemTime(new Date("2026-04-10T12:00:00.000Z"));
+
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const onSuppressed = vi.fn();
+    const rateLimited = withDisplayRateLimit(
+      { dispatchNotification },
+      { maxPerMinute: 2, onSuppressed },
+    );
+
+    await rateLimited.dispatchNotification({ title: "acme/api PR #1", body: "one" });
+    await vi.advanceTimersByTimeAsync(10_000);
+    await rateLimited.dispatchNotification({ title: "acme/api PR #2", body: "two" });
+    await rateLimited.dispatchNotification({ title: "acme/api PR #3", body: "three" });
+    await rateLimited.dispatchNotification({ title: "acme/api PR #4", body: "four" });
+    await rateLimited.dispatchNotification({
+      title: "🚨 acme/api code scanning alert",
+      body: "SQL injection",
+      urgency: "critical",
+    });
+
+    expect(dispatchNotification.mock.calls.map(([notification]) => notification.title)).toEqual([
+      "acme/api PR #1",
+      "acme/api PR #2",
+      "🚨 acme/api code scanning alert",
+    ]);
+    expect(onSuppressed.mock.calls.map(([notification]) => notification.title)).toEqual([
+      "acme/api PR #3",
+      "acme/api PR #4",
+    ]);
+
+    await vi.advanceTimersByTimeAsync(50_000);
+
+    expect(dispatchNotification).toHaveBeenCalledTimes(4);
+    expect(dispatchNotification).toHaveBeenLastCalledWith({
+      title: "2 more notifications suppressed",
+      body: "acme/api PR #3\nacme/api PR #4",
+      clickUrl: "https://github.com/notifications",
+    });
+
+    rateLimited.stop();
+  });
+});
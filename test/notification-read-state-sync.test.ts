@@ -0,0 +1,143 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import { resolveAppPaths } from "../src/config.js";
+import { initializeDatabase } from "../src/database.js";
+import { NormalizedEventRepository } from "../src/normalized-event-repository.js";
+import { NotificationRecordRepository } from "../src/notification-record-repository.js";
+import { syncNotificationReadState } from "../src/notification-read-state-sync.js";
+import {
+  PullRequestRepository,
+  type PullRequestRecord,
+} from "../src/pull-request-repository.js";
+
+const SYNCED_AT = "2026-04-10T13:00:00.000Z";
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("syncNotificationReadState", () => {
+  it("pushes locally read notifications to GitHub once", async () => {
+    const { database, pullRequest, notificationRecordRepository } = createDeliveredNotification();
+    const readStateClient = {
+      markPullRequestThreadsRead: vi.fn().mockResolvedValue(1),
+      listPullRequestThreadReadStates: vi.fn().mockResolvedValue([]),
+    };
+
+    try {
+      const [record] = notificationRecordRepository.listNotificationRecordsForPullRequest(
+        pullRequest.id,
+      );
+      notificationRecordRepository.markNotificationRecordRead(record!.id, "2026-04-10T12:30:00.000Z");
+
+      await expect(
+        syncNotificationReadState(database, readStateClient, { syncedAt: SYNCED_AT }),
+      ).resolves.toEqual({ pushedCount: 1, pulledCount: 0 });
+      await expect(
+        syncNotificationReadState(database, readStateClient, { syncedAt: SYNCED_AT }),
+      ).resolves.toEqual({ pushedCount: 0, pulledCount: 0 });
+
+      expect(readStateClient.markPullRequestThreadsRead).toHaveBeenCalledTimes(1);
+      expect(readStateClient.markPullRequestThreadsRead).toHaveBeenCalledWith(
+        expect.objectContaining({
+          repositoryOwner: "acme",
+          repositoryName: "octopulse",
+          number: 7,
+        }),
+      );
+      expect(readStateClient.listPullRequestThreadReadStates).not.toHaveBeenCalled();
+    } finally {
+      database.close();
+    }
+  });
+
+  it("marks local notifications read when GitHub threads were read after delivery", async () => {
+    const { database, pullRequest, notificationRecordRepository } = createDeliveredNotification();
+    const readStateClient = {
+      markPullRequestThreadsRead: vi.fn(),
+      listPullRequestThreadReadStates: vi.fn().mockResolvedValue([
+        {
+          coordinates: { repositoryOwner: "acme", repositoryName: "octopulse", number: 7 },
+          unread: false,
+          lastReadAt: "2026-04-10T12:45:00Z",
+        },
+      ]),
+    };
+
+    try {
+      await expect(
+        syncNotificationReadState(database, readStateClient, { syncedAt: SYNCED_AT }),
+      ).resolves.toEqual({ pushedCount: 0, pulledCount: 1 });
+
+      expect(readStateClient.listPullRequestThreadReadStates).toHaveBeenCalledWith(
+        "2026-04-10T12:02:45.000Z",
+      );
+      expect(
+        notificationRecordRepository.listNotificationRecordsForPullRequest(pullRequest.id),
+      ).toEqual([
+        expect.objectContaining({
+          readAt: "2026-04-10T12:45:00Z",
+          githubReadSyncedAt: SYNCED_AT,
+        }),
+      ]);
+      expect(readStateClient.markPullRequestThreadsRead).not.toHaveBeenCalled();
+    } finally {
+      database.close();
+    }
+  });
+});
+
+function createDeliveredNotification(): {
+  database: ReturnType<typeof initializeDatabase>;
+  pullRequest: PullRequestRecord;
+  notificationRecordRepository: NotificationRecordRepository;
+} {
+  const homeDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-read-state-sync-home-"));
+  tempDirs.push(homeDir);
+  const database = initializeDatabase(resolveAppPaths({ homeDir }));
+  const pullRequest = new PullRequestRepository(database).upsertPullRequest({
+    githubPullRequestId: 101,
+    repositoryOwner: "acme",
+    repositoryName: "octopulse",
+    number: 7,
+    url: "https://github.com/acme/octopulse/pull/7",
+    authorLogin: "octocat",
+    title: "Add notifications",
+    state: "open",
+    isDraft: false,
+    lastSeenAt: "2026-04-10T12:00:00.000Z",
+    lastSeenHeadSha: "abc123",
+  });
+  const event = new NormalizedEventRepository(database).insertNormalizedEvent({
+    pullRequestId: pullRequest.id,
+    eventType: "review_approved",
+    actorLogin: "alice",
+    decisionState: "notified",
+    notificationTiming: "immediate",
+    occurredAt: "2026-04-10T12:01:00.000Z",
+  });
+  const notificationRecordRepository = new NotificationRecordRepository(database);
+
+  notificationRecordRepository.createNotificationRecord({
+    normalizedEventId: event.id,
+    pullRequestId: pullRequest.id,
+    title: "acme/octopulse #7 Add notifications",
+    body: "alice: ✅ approved",
+    clickUrl: pullRequest.url,
+    deliveryStatus: "sent",
+    deliveredAt: "2026-04-10T12:02:45.000Z",
+  });
+
+  return {
+    database,
+    pullRequest,
+    notificationRecordRepository,
+  };
+}
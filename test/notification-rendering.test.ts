@@ -34,6 +34,34 @@ describe("renderNotification", () => {
     });
   });
 
+  it("summarizes busy threads by author and shows only the newest comments", () => {
+    const comments = Array.from({ length: 12 }, (_, index) => ({
+      id: 500 + index,
+      eventType: "issue_comment",
+      actorLogin: index % 3 === 0 ? "bob" : "alice",
+      occurredAt: `2026-04-10T12:${String(index).padStart(2, "0")}:00.000Z`,
+      payloadJson: JSON.stringify({ bodyText: `comment ${index + 1}` }),
+    }));
+
+    const body = renderNotification(
+      {
+        repositoryOwner: "acme",
+        repositoryName: "octopulse",
+        number: 7,
+        title: "Ship notifications",
+        url: "https://github.com/acme/octopulse/pull/7",
+      },
+      comments,
+    ).body.split("\n\n");
+
+    expect(body).toEqual([
+      "alice ×8, bob ×4 commented; …and 9 earlier comments",
+      "bob: 💬 comment 10",
+      "alice: 💬 comment 11",
+      "alice: 💬 comment 12",
+    ]);
+  });
+
   it("renders review-requested notifications without actor metadata", () => {
     expect(
       renderNotification(
@@ -62,6 +90,37 @@ describe("renderNotification", () => {
     });
   });
 
+  it("links lone CI failures to the failing workflow run", () => {
+    expect(
+      renderNotification(
+        {
+          repositoryOwner: "acme",
+          repositoryName: "octopulse",
+          number: 7,
+          title: "Ship notifications",
+          url: "https://github.com/acme/octopulse/pull/7",
+        },
+        [
+          {
+            id: 112,
+            eventType: "ci_failed",
+            actorLogin: "octocat",
+            occurredAt: "2026-04-10T12:00:00.000Z",
+            payloadJson: JSON.stringify({
+              workflowName: "CI",
+              url: "https://github.com/acme/octopulse/actions/runs/5102",
+            }),
+          },
+        ],
+      ),
+    ).toEqual({
+      title: "acme/octopulse #7 Ship notifications",
+      body: "CI failed: CI",
+      clickUrl: "https://github.com/acme/octopulse/actions/runs/5102",
+      summary: "CI failed",
+    });
+  });
+
   it("renders bundled summaries with primary events before comments and quoted snippets", () => {
     expect(
       renderNotification(
@@ -344,4 +403,226 @@ describe("renderNotification", () => {
       ],
     });
   });
+
+  it("renders assignments with the assigner's avatar and name in the text", () => {
+    expect(
+      renderNotificationMarkup(
+        {
+          repositoryName: "octopulse",
+          title: "Ship notifications",
+          authorLogin: "octocat",
+          authorAvatarUrl: "https://avatars.example.test/octocat.png",
+          state: "open",
+          isDraft: false,
+          mergedAt: null,
+        },
+        [
+          {
+            id: 403,
+            eventType: "pr_assigned",
+            actorLogin: "alice",
+            occurredAt: "2026-04-10T12:00:00.000Z",
+            payloadJson: JSON.stringify({
+              assigneeLogin: "octocat",
+              actorAvatarUrl: "https://avatars.example.test/alice.png",
+            }),
+          },
+        ],
+      ).paragraphs,
+    ).toEqual([
+      {
+        actorLogin: null,
+        actorAvatarKey: "alice",
+        actorAvatarUrl: "https://avatars.example.test/alice.png",
+        text: "📌 You were assigned by alice",
+      },
+    ]);
+  });
+
+  it("names who merged a pull request and flags auto-merge in the header", () => {
+    const pullRequest = {
+      repositoryName: "octopulse",
+      title: "Ship notifications",
+      authorLogin: "octocat",
+      authorAvatarUrl: null,
+      state: "open",
+      isDraft: false,
+      mergedAt: null,
+      baseBranch: "release/2.x",
+    };
+    const events = [
+      {
+        id: 404,
+        eventType: "pr_merged",
+        actorLogin: "bob",
+        occurredAt: "2026-04-10T12:00:00.000Z",
+        payloadJson: "{}",
+      },
+    ];
+
+    expect(
+      renderNotificationMarkup({ ...pullRequest, autoMergeEnabled: true }, events).headerText,
+    ).toBe("[octopulse] Ship notifications (open, auto-merge on)");
+    expect(
+      renderNotificationMarkup(
+        { ...pullRequest, state: "closed", mergedAt: "2026-04-10T12:00:00.000Z", mergedByLogin: "bob" },
+        events,
+      ).headerText,
+    ).toBe("[octopulse] Ship notifications (merged by bob into release/2.x)");
+  });
+
+  it("shows branches and project board status in the header when known", () => {
+    const pullRequest = {
+      repositoryName: "octopulse",
+      title: "Ship notifications",
+      authorLogin: "octocat",
+      authorAvatarUrl: null,
+      state: "open",
+      isDraft: false,
+      mergedAt: null,
+      headBranch: "feature/notifications",
+      baseBranch: "main",
+    };
+    const events = [
+      {
+        id: 405,
+        eventType: "issue_comment",
+        actorLogin: "bob",
+        occurredAt: "2026-04-10T12:00:00.000Z",
+        payloadJson: JSON.stringify({ bodyText: "Nice" }),
+      },
+    ];
+
+    expect(renderNotificationMarkup(pullRequest, events).headerText).toBe(
+      "[octopulse] Ship notifications (open) · feature/notifications → main",
+    );
+    expect(renderNotificationMarkup({ ...pullRequest, projectStatus: "In review" }, events).headerText).toBe(
+      "[octopulse] Ship notifications (open) · feature/notifications → main · 📋 In review",
+    );
+    expect(renderNotificationMarkup({ ...pullRequest, headBranch: null }, events).headerText).toBe(
+      "[octopulse] Ship notifications (open)",
+    );
+  });
+
+  it("shows how old the pull request is and when it last saw activity", () => {
+    const pullRequest = {
+      repositoryName: "octopulse",
+      title: "Ship notifications",
+      authorLogin: "octocat",
+      authorAvatarUrl: null,
+      state: "open",
+      isDraft: false,
+      mergedAt: null,
+      openedAt: "2026-04-04T09:00:00.000Z",
+      lastActivityAt: "2026-04-10T09:00:00.000Z",
+    };
+    const events = [
+      {
+        id: 406,
+        eventType: "issue_comment",
+        actorLogin: "bob",
+        occurredAt: "2026-04-10T12:00:00.000Z",
+        payloadJson: JSON.stringify({ bodyText: "Still needed?" }),
+      },
+    ];
+    const now = new Date("2026-04-10T12:00:00.000Z");
+
+    expect(renderNotificationMarkup(pullRequest, events, { now }).headerText).toBe(
+      "[octopulse] Ship notifications (open) · opened 6d ago, last activity 3h ago",
+    );
+    expect(
+      renderNotificationMarkup({ ...pullRequest, lastActivityAt: now.toISOString() }, events, { now })
+        .headerText,
+    ).toBe("[octopulse] Ship notifications (open) · opened 6d ago");
+  });
+
+  it("collapses commits pushed after your review into one counted line", () => {
+    const commits = [1, 2, 3].map((commitsSinceReview) => ({
+      id: 600 + commitsSinceReview,
+      eventType: "commit_pushed",
+      actorLogin: "frank",
+      occurredAt: `2026-04-10T12:0${commitsSinceReview}:00.000Z`,
+      payloadJson: JSON.stringify({ commitSha: `sha${commitsSinceReview}`, commitsSinceReview }),
+    }));
+
+    expect(
+      renderNotificationMarkup(
+        {
+          repositoryName: "octopulse",
+          title: "Ship notifications",
+          authorLogin: "frank",
+          authorAvatarUrl: null,
+          state: "open",
+          isDraft: false,
+          mergedAt: null,
+        },
+        commits,
+      ).paragraphs.map((paragraph) => paragraph.text),
+    ).toEqual(["🔨 3 new commits pushed since your review"]);
+  });
+
+  it("names whose review was dismissed and quotes the dismissal message", () => {
+    expect(
+      renderNotificationMarkup(
+        {
+          repositoryName: "octopulse",
+          title: "Ship notifications",
+          authorLogin: "octocat",
+          authorAvatarUrl: null,
+          state: "open",
+          isDraft: false,
+          mergedAt: null,
+        },
+        [
+          {
+            id: 701,
+            eventType: "review_dismissed",
+            actorLogin: "alice",
+            occurredAt: "2026-04-10T12:30:00.000Z",
+            payloadJson: JSON.stringify({
+              reviewerLogin: "bob",
+              dismissalMessage: "Tests were added in the follow-up commit",
+            }),
+          },
+        ],
+      ).paragraphs,
+    ).toEqual([
+      expect.objectContaining({
+        actorLogin: "alice",
+        text: "🚫 dismissed bob's review: Tests were added in the follow-up commit",
+      }),
+    ]);
+  });
+
+  it("adds a size badge from the pull request diff stats", () => {
+    const pullRequest = {
+      repositoryName: "octopulse",
+      title: "Ship notifications",
+      authorLogin: "octocat",
+      authorAvatarUrl: null,
+      state: "open",
+      isDraft: false,
+      mergedAt: null,
+    };
+    const events = [
+      {
+        id: 407,
+        eventType: "issue_comment",
+        actorLogin: "bob",
+        occurredAt: "2026-04-10T12:00:00.000Z",
+        payloadJson: JSON.stringify({ bodyText: "Big one" }),
+      },
+    ];
+    const renderHeader = (additions: number, deletions: number) =>
+      renderNotificationMarkup({ ...pullRequest, additions, deletions }, events).headerText;
+
+    expect(renderHeader(3, 2)).toBe("[octopulse] [XS] Ship notifications (open)");
+    expect(renderHeader(20, 5)).toBe("[octopulse] [S] Ship notifications (open)");
+    expect(renderHeader(60, 20)).toBe("[octopulse] [M] Ship notifications (open)");
+    expect(renderHeader(300, 50)).toBe("[octopulse] [L] Ship notifications (open)");
+    expect(renderHeader(900, 400)).toBe("[octopulse] [XL] Ship notifications (open)");
+    expect(renderNotificationMarkup(pullRequest, events).headerText).toBe(
+      "[octopulse] Ship notifications (open)",
+    );
+  });
 });
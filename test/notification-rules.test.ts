@@ -0,0 +1,155 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterAll, describe, expect, it, vi } from "vitest";
+
+import { parseCodeowners } from "../src/codeowners.js";
+import { resolveAppPaths } from "../src/config.js";
+import { initializeDatabase } from "../src/database.js";
+import { NormalizedEventRepository } from "../src/normalized-event-repository.js";
+import { applyNotificationRules } from "../src/notification-rules.js";
+import {
+  PullRequestRepository,
+  type PullRequestRecord,
+  type UpsertPullRequestInput,
+} from "../src/pull-request-repository.js";
+
+const tempDirs: string[] = [];
+const CODEOWNERS = parseCodeowners(["* @acme/platform", "/services/payments/ @acme/payments"].join("\n"));
+
+afterAll(() => {
+  for (const tempDir of tempDirs) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("applyNotificationRules", () => {
+  it("suppresses pending activity on pull requests that touch no owned paths", async () => {
+    const { database, pullRequest } = createPullRequest({ changedFilePaths: ["web/index.ts"] });
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+    insertEvent(normalizedEventRepository, pullRequest, "issue_comment");
+    insertEvent(normalizedEventRepository, pullRequest, "pr_assigned", "immediate");
+
+    const result = await applyNotificationRules(database, pullRequest, {
+      rules: { ownedPathsOnly: true, ownerTeams: ["@acme/payments"], paths: [] },
+      currentUserLogin: "alice",
+      loadCodeowners: async () => CODEOWNERS,
+    });
+
+    expect(result).toEqual({ suppressedCount: 1 });
+    expect(
+      normalizedEventRepository.listNormalizedEventsForPullRequest(pullRequest.id).map((event) => ({
+        eventType: event.eventType,
+        decisionState: event.decisionState,
+      })),
+    ).toEqual([
+      { eventType: "issue_comment", decisionState: "suppressed_rule" },
+      { eventType: "pr_assigned", decisionState: "notified" },
+    ]);
+  });
+
+  it("keeps activity on owned paths, on authored pull requests, and when ownership is unknown", async () => {
+    const owned = createPullRequest({ changedFilePaths: ["services/payments/api.ts"] });
+    const authored = createPullRequest({ authorLogin: "alice", changedFilePaths: ["web/index.ts"] });
+    const withoutCodeowners = createPullRequest({ changedFilePaths: ["web/index.ts"] });
+    const loadCodeowners = vi.fn(async () => CODEOWNERS);
+
+    for (const { database, pullRequest } of [owned, authored, withoutCodeowners]) {
+      insertEvent(new NormalizedEventRepository(database), pullRequest, "issue_comment");
+    }
+
+    const options = {
+      rules: { ownedPathsOnly: true, ownerTeams: ["@acme/payments"], paths: [] },
+      currentUserLogin: "alice",
+      loadCodeowners,
+    };
+
+    await expect(applyNotificationRules(owned.database, owned.pullRequest, options)).resolves.toEqual({
+      suppressedCount: 0,
+    });
+    await expect(applyNotificationRules(authored.database, authored.pullRequest, options)).resolves.toEqual({
+      suppressedCount: 0,
+    });
+    await expect(
+      applyNotificationRules(withoutCodeowners.database, withoutCodeowners.pullRequest, {
+        ...options,
+        loadCodeowners: async () => null,
+      }),
+    ).resolves.toEqual({ suppressedCount: 0 });
+    expect(loadCodeowners).toHaveBeenCalledTimes(1);
+  });
+
+  it("suppresses pending activity when no changed file matches the configured paths", async () => {
+    const matching = createPullRequest({ changedFilePaths: ["web/index.ts", "services/payments/api/charge.ts"] });
+    const unrelated = createPullRequest({ changedFilePaths: ["services/billing/invoice.ts"] });
+    const loadCodeowners = vi.fn(async () => CODEOWNERS);
+    const options = {
+      rules: { ownedPathsOnly: false, ownerTeams: [], paths: ["services/payments/**"] },
+      currentUserLogin: "alice",
+      loadCodeowners,
+    };
+
+    for (const { database, pullRequest } of [matching, unrelated]) {
+      insertEvent(new NormalizedEventRepository(database), pullRequest, "review_inline_comment");
+    }
+
+    await expect(applyNotificationRules(matching.database, matching.pullRequest, options)).resolves.toEqual({
+      suppressedCount: 0,
+    });
+    await expect(applyNotificationRules(unrelated.database, unrelated.pullRequest, options)).resolves.toEqual({
+      suppressedCount: 1,
+    });
+    expect(
+      new NormalizedEventRepository(unrelated.database)
+        .listNormalizedEventsForPullRequest(unrelated.pullRequest.id)
+        .map((event) => JSON.parse(event.payloadJson) as Record<string, unknown>),
+    ).toEqual([{ bodyText: "Looks good", suppressedByRule: "paths" }]);
+    expect(loadCodeowners).not.toHaveBeenCalled();
+  });
+});
+
+function insertEvent(
+  repository: NormalizedEventRepository,
+  pullRequest: PullRequestRecord,
+  eventType: string,
+  notificationTiming: "immediate" | null = null,
+): void {
+  repository.insertNormalizedEvent({
+    pullRequestId: pullRequest.id,
+    eventType,
+    actorLogin: "bob",
+    actorClass: "human_other",
+    decisionState: "notified",
+    notificationTiming,
+    payloadJson: JSON.stringify({ bodyText: "Looks good" }),
+    occurredAt: "2026-04-10T12:00:00.000Z",
+  });
+}
+
+function createPullRequest(overrides: Partial<UpsertPullRequestInput> = {}): {
+  database: ReturnType<typeof initializeDatabase>;
+  pullRequest: PullRequestRecord;
+} {
+  const homeDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-notification-rules-home-"));
+  tempDirs.push(homeDir);
+  const database = initializeDatabase(resolveAppPaths({ homeDir }));
+  const repository = new PullRequestRepository(database);
+
+  return {
+    database,
+    pullRequest: repository.upsertPullRequest({
+      githubPullRequestId: 101,
+      repositoryOwner: "acme",
+      repositoryName: "monorepo",
+      number: 7,
+      url: "https://github.com/acme/monorepo/pull/7",
+      authorLogin: "bob",
+      title: "Tweak landing page",
+      state: "open",
+      isDraft: false,
+      baseBranch: "main",
+      ...overrides,
+    }),
+  };
+}
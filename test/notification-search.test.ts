@@ -0,0 +1,131 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it } from "vitest";
+
+import { DEFAULT_ACTIVITY_FEED_FILTERS } from "../src/activity-feed.js";
+import { resolveAppPaths } from "../src/config.js";
+import { initializeDatabase } from "../src/database.js";
+import { listNotificationHistory } from "../src/notification-history.js";
+import { NormalizedEventRepository } from "../src/normalized-event-repository.js";
+import { NotificationRecordRepository } from "../src/notification-record-repository.js";
+import {
+  buildNotificationSearchQuery,
+  NotificationSearchError,
+  runSearchCommand,
+  searchNotifications,
+} from "../src/notification-search.js";
+import { PullRequestRepository } from "../src/pull-request-repository.js";
+
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("searchNotifications", () => {
+  it("finds notifications by title, body, or comment text", () => {
+    const database = createDatabase();
+
+    try {
+      const pullRequest = new PullRequestRepository(database).upsertPullRequest({
+        githubPullRequestId: 101,
+        repositoryOwner: "acme",
+        repositoryName: "octopulse",
+        number: 7,
+        url: "https://github.com/acme/octopulse/pull/7",
+        authorLogin: "octocat",
+        title: "Add notifications",
+        state: "open",
+        isDraft: false,
+        lastSeenAt: "2026-04-10T12:00:00.000Z",
+        lastSeenHeadSha: "abc123",
+      });
+      const normalizedEventRepository = new NormalizedEventRepository(database);
+      const notificationRecordRepository = new NotificationRecordRepository(database);
+      const comment = normalizedEventRepository.insertNormalizedEvent({
+        pullRequestId: pullRequest.id,
+        eventType: "issue_comment",
+        actorLogin: "alice",
+        actorClass: "human_other",
+        decisionState: "notified",
+        payloadJson: JSON.stringify({ bodyText: "The integration test is flaky on CI again" }),
+        occurredAt: "2026-04-10T12:00:00.000Z",
+      });
+      const approval = normalizedEventRepository.insertNormalizedEvent({
+        pullRequestId: pullRequest.id,
+        eventType: "review_approved",
+        actorLogin: "bob",
+        actorClass: "human_other",
+        decisionState: "notified",
+        occurredAt: "2026-04-10T12:05:00.000Z",
+      });
+      const commentRecord = notificationRecordRepository.createNotificationRecord({
+        normalizedEventId: comment.id,
+        pullRequestId: pullRequest.id,
+        title: "acme/octopulse PR #7",
+        body: "alice commented",
+        clickUrl: "https://github.com/acme/octopulse/pull/7#issuecomment-1",
+      });
+      notificationRecordRepository.createNotificationRecord({
+        normalizedEventId: approval.id,
+        pullRequestId: pullRequest.id,
+        title: "acme/octopulse PR #7",
+        body: "bob approved review",
+        clickUrl: pullRequest.url,
+      });
+
+      expect(searchNotifications(database, "flaky test")).toEqual([
+        expect.objectContaining({
+          id: commentRecord.id,
+          repository: "acme/octopulse",
+          pullRequestNumber: 7,
+          snippet: expect.stringContaining("[flaky]"),
+        }),
+      ]);
+      expect(searchNotifications(database, "approved")).toHaveLength(1);
+      expect(searchNotifications(database, 'flaky" OR (')).toEqual([]);
+      expect(
+        listNotificationHistory(database, {
+          filters: { ...DEFAULT_ACTIVITY_FEED_FILTERS, search: "flaky" },
+        }).entries.map((entry) => entry.id),
+      ).toEqual([commentRecord.id]);
+      expect(runSearchCommand(["flaky", "--limit", "5"], database)).toContain(
+        "acme/octopulse#7  acme/octopulse PR #7",
+      );
+    } finally {
+      database.close();
+    }
+  });
+});
+
+describe("buildNotificationSearchQuery", () => {
+  it("quotes each word so search text is never parsed as query syntax", () => {
+    expect(buildNotificationSearchQuery('  flaky "test" ')).toBe('"flaky" """test"""');
+    expect(buildNotificationSearchQuery("   ")).toBeNull();
+  });
+});
+
+describe("runSearchCommand", () => {
+  it("rejects missing search text and unknown flags", () => {
+    const database = createDatabase();
+
+    try {
+      expect(() => runSearchCommand([], database)).toThrow(NotificationSearchError);
+      expect(() => runSearchCommand(["flaky", "--verbose"], database)).toThrow(NotificationSearchError);
+      expect(() => runSearchCommand(["flaky", "--limit", "0"], database)).toThrow(NotificationSearchError);
+    } finally {
+      database.close();
+    }
+  });
+});
+
+function createDatabase(): ReturnType<typeof initializeDatabase> {
+  const tempDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-search-"));
+  tempDirs.push(tempDir);
+
+  return initializeDatabase(resolveAppPaths({ homeDir: tempDir }));
+}
@@ -0,0 +1,42 @@
+import { describe, expect, it, vi } from "vitest";
+
+import { withNotificationSourceLabel } from "../src/notification-source-label.js";
+
+describe("withNotificationSourceLabel", () => {
+  it("returns the original dispatcher when no label is configured", () => {
+    const notificationDispatcher = {
+      dispatchNotification: vi.fn().mockResolvedValue(undefined),
+    };
+
+    expect(withNotificationSourceLabel(notificationDispatcher, undefined)).toBe(
+      notificationDispatcher,
+    );
+  });
+
+  it("prefixes titles and markup headers with the source label", async () => {
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const labeledDispatcher = withNotificationSourceLabel({ dispatchNotification }, "work");
+
+    await labeledDispatcher.dispatchNotification({
+      title: "acme/octopulse #7 Add notifications",
+      body: "alice: ✅ LGTM",
+      markup: {
+        headerText: "[octopulse] Add notifications (open)",
+        headerAvatarKey: "octocat",
+        headerAvatarUrl: null,
+        paragraphs: [],
+      },
+    });
+
+    expect(dispatchNotification).toHaveBeenCalledWith({
+      title: "[work] acme/octopulse #7 Add notifications",
+      body: "alice: ✅ LGTM",
+      markup: {
+        headerText: "[work] [octopulse] Add notifications (open)",
+        headerAvatarKey: "octocat",
+        headerAvatarUrl: null,
+        paragraphs: [],
+      },
+    });
+  });
+});
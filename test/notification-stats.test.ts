@@ -0,0 +1,60 @@
+import { describe, expect, it } from "vitest";
+
+import {
+  aggregateNotificationStats,
+  renderNotificationStats,
+  renderSparkline,
+} from "../src/notification-stats.js";
+
+describe("aggregateNotificationStats", () => {
+  it("counts notifications per repository, author, reason, and hour", () => {
+    const stats = aggregateNotificationStats(
+      [
+        {
+          createdAt: "2026-04-10 09:15:00",
+          repository: "acme/octopulse",
+          actors: ["alice"],
+          eventTypes: ["issue_comment"],
+        },
+        {
+          createdAt: "2026-04-10T09:45:00.000Z",
+          repository: "acme/octopulse",
+          actors: ["alice", "bob"],
+          eventTypes: ["issue_comment", "review_approved"],
+        },
+        {
+          createdAt: "2026-04-11 17:00:00",
+          repository: "widgets/dashboard",
+          actors: [],
+          eventTypes: ["ci_failed"],
+        },
+      ],
+      (date) => date.getUTCHours(),
+    );
+
+    expect(stats.totalCount).toBe(3);
+    expect(stats.byRepository).toEqual(
+      new Map([
+        ["acme/octopulse", 2],
+        ["widgets/dashboard", 1],
+      ]),
+    );
+    expect(stats.byAuthor.get("alice")).toBe(2);
+    expect(stats.byAuthor.get("unknown")).toBe(1);
+    expect(stats.byReason.get("issue_comment")).toBe(2);
+    expect(stats.byHourOfDay[9]).toBe(2);
+    expect(stats.byHourOfDay[17]).toBe(1);
+
+    const output = renderNotificationStats(stats, 1);
+
+    expect(output).toContain("3 notifications");
+    expect(output).toContain("acme/octopulse  2");
+    expect(output).toContain("…and 1 more");
+  });
+});
+
+describe("renderSparkline", () => {
+  it("scales counts to block characters and leaves empty hours blank", () => {
+    expect(renderSparkline([0, 1, 4, 8])).toBe(" ▁▄█");
+  });
+});
@@ -0,0 +1,53 @@
+import { describe, expect, it, vi } from "vitest";
+
+import { buildOpenUriPortalArgs, isSandboxed, openUrl } from "../src/open-url.js";
+
+const PULL_REQUEST_URL = "https://github.com/acme/octopulse/pull/7";
+
+describe("openUrl", () => {
+  it("opens URLs through the OpenURI portal inside a sandbox", async () => {
+    const runCommand = vi.fn().mockResolvedValue(undefined);
+
+    await openUrl(PULL_REQUEST_URL, { sandboxed: true, runCommand });
+
+    expect(runCommand).toHaveBeenCalledTimes(1);
+    expect(runCommand).toHaveBeenCalledWith("gdbus", buildOpenUriPortalArgs(PULL_REQUEST_URL));
+  });
+
+  it("falls back to xdg-open when the portal fails or there is no sandbox", async () => {
+    const runCommand = vi
+      .fn()
+      .mockRejectedValueOnce(new Error("gdbus exited with code 1"))
+      .mockResolvedValue(undefined);
+
+    await openUrl(PULL_REQUEST_URL, { sandboxed: true, runCommand });
+    await openUrl(PULL_REQUEST_URL, { sandboxed: false, runCommand });
+
+    expect(runCommand.mock.calls.map(([command]) => command)).toEqual([
+      "gdbus",
+      "xdg-open",
+      "xdg-open",
+    ]);
+  });
+});
+
+describe("buildOpenUriPortalArgs", () => {
+  it("passes the PULL_REQUEST_URL as a quoted GVariant string", () => {
+    expect(buildOpenUriPortalArgs('https://example.test/?q="a\\b"').slice(-3)).toEqual([
+      "",
+      '"https://example.test/?q=\\"a\\\\b\\""',
+      "{}",
+    ]);
+  });
+});
+
+describe("isSandboxed", () => {
+  it("detects Flatpak and Snap installs", () => {
+    const noFile = () => false;
+
+    expect(isSandboxed({ FLATPAK_ID: "io.github.octopulse" }, noFile)).toBe(true);
+    expect(isSandboxed({ SNAP: "/snap/octopulse/1" }, noFile)).toBe(true);
+    expect(isSandboxed({}, (filePath) => filePath === "/.flatpak-info")).toBe(true);
+    expect(isSandboxed({}, noFile)).toBe(false);
+  });
+});
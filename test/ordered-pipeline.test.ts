@@ -0,0 +1,50 @@
+import { describe, expect, it } from "vitest";
+
+import { runOrderedPipeline } from "../src/ordered-pipeline.js";
+
+describe("runOrderedPipeline", () => {
+  it("processes items concurrently but consumes them in input order", async () => {
+    const delaysMs = [30, 5, 15, 0];
+    const events: string[] = [];
+    let active = 0;
+    let maxActive = 0;
+
+    await runOrderedPipeline(delaysMs, {
+      concurrency: 2,
+      process: async (delayMs, index) => {
+        active += 1;
+        maxActive = Math.max(maxActive, active);
+        await new Promise((resolve) => setTimeout(resolve, delayMs));
+        active -= 1;
+        return `item-${index}`;
+      },
+      consume: async (output) => {
+        events.push(output);
+      },
+    });
+
+    expect(events).toEqual(["item-0", "item-1", "item-2", "item-3"]);
+    expect(maxActive).toBe(2);
+  });
+
+  it("surfaces processing failures when the failed item is consumed", async () => {
+    const consumed: number[] = [];
+
+    await expect(
+      runOrderedPipeline([1, 2, 3], {
+        concurrency: 3,
+        process: async (item) => {
+          if (item === 2) {
+            throw new Error("boom");
+          }
+
+          return item;
+        },
+        consume: async (output) => {
+          consumed.push(output);
+        },
+      }),
+    ).rejects.toThrow("boom");
+    expect(consumed).toEqual([1]);
+  });
+});
@@ -0,0 +1,85 @@
+import { describe, expect, it, vi } from "vitest";
+
+import {
+  buildPollerFailureNotification,
+  classifyPollerFailure,
+  createPollerHealthMonitor,
+} from "../src/poller-health.js";
+
+describe("createPollerHealthMonitor", () => {
+  it("shows one throttled popup after repeated failures and one when polling recovers", () => {
+    let currentTime = Date.parse("2026-04-10T12:00:00.000Z");
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const monitor = createPollerHealthMonitor(
+      { dispatchNotification },
+      {
+        failureThreshold: 2,
+        notifyIntervalMs: 60 * 60_000,
+        clickUrl: () => "http://127.0.0.1:4000/logs",
+        now: () => currentTime,
+      },
+    );
+    const outage = new Error("Failed to poll tracked pull requests: fetch failed");
+
+    monitor.recordCycle("tracked_pull_requests", outage);
+    monitor.recordCycle("discovery", outage);
+    expect(dispatchNotification).not.toHaveBeenCalled();
+
+    monitor.recordCycle("tracked_pull_requests", outage);
+    monitor.recordCycle("discovery", outage);
+    currentTime += 30 * 60_000;
+    monitor.recordCycle("tracked_pull_requests", outage);
+
+    expect(dispatchNotification).toHaveBeenCalledTimes(1);
+    expect(dispatchNotification).toHaveBeenLastCalledWith(
+      expect.objectContaining({
+        title: "Octopulse can't reach GitHub",
+        clickUrl: "http://127.0.0.1:4000/logs",
+        urgency: "critical",
+      }),
+    );
+
+    monitor.recordCycle("tracked_pull_requests", null);
+    expect(dispatchNotification).toHaveBeenCalledTimes(1);
+
+    monitor.recordCycle("discovery", null);
+    expect(dispatchNotification).toHaveBeenCalledTimes(2);
+    expect(dispatchNotification).toHaveBeenLastCalledWith(
+      expect.objectContaining({ title: "Octopulse is reaching GitHub again" }),
+    );
+  });
+});
+
+describe("classifyPollerFailure", () => {
+  it("distinguishes rejected tokens, network outages, timeouts, GitHub errors, and others", () => {
+    expect(
+      classifyPollerFailure(new Error("Failed to discover pull requests: Bad credentials")),
+    ).toBe("auth");
+    expect(classifyPollerFailure(new Error("getaddrinfo ENOTFOUND api.github.com"))).toBe(
+      "network",
+    );
+    expect(
+      classifyPollerFailure(
+        new Error("Failed to poll tracked pull requests: Server Error", {
+          cause: Object.assign(new Error("Server Error"), { status: 503 }),
+        }),
+      ),
+    ).toBe("server");
+    expect(
+      classifyPollerFailure(
+        Object.assign(new Error("fetch failed"), {
+          status: 500,
+          cause: Object.assign(new Error("Connect Timeout Error"), {
+            code: "UND_ERR_CONNECT_TIMEOUT",
+          }),
+        }),
+      ),
+    ).toBe("timeout");
+    expect(classifyPollerFailure(new Error("Unexpected response"))).toBe("unknown");
+    expect(buildPollerFailureNotification(new Error("Bad credentials"), 3)).toEqual({
+      title: "GitHub rejected Octopulse's token",
+      body: "Polling has failed 3 times in a row, so pull request notifications may be missing. The token may have expired or been revoked; update github.token in your config and restart Octopulse.",
+      urgency: "critical",
+    });
+  });
+});
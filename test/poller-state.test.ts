@@ -0,0 +1,229 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it } from "vitest";
+
+import { resolveAppPaths } from "../src/config.js";
+import { initializeDatabase } from "../src/database.js";
+import {
+  createPersistentPollerBackoff,
+  PollerStateRepository,
+  readLowRateLimitBudget,
+  readRateLimitResetAt,
+} from "../src/poller-state.js";
+
+const INTERVAL_MS = 60_000;
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("createPersistentPollerBackoff", () => {
+  it("doubles the wait after repeated failures and keeps it across a restart", () => {
+    const homeDir = createTempDir();
+    let currentTime = Date.parse("2026-04-10T12:00:00.000Z");
+    const database = initializeDatabase(resolveAppPaths({ homeDir }));
+
+    try {
+      const backoff = createPersistentPollerBackoff(
+        new PollerStateRepository(database),
+        "tracked_pull_requests",
+        { intervalMs: INTERVAL_MS, now: () => currentTime, random: () => 0 },
+      );
+
+      backoff.recordCycle(new Error("fetch failed"));
+      expect(backoff.readDeferral()).toBeNull();
+
+      backoff.recordCycle(new Error("fetch failed"));
+      backoff.recordCycle(new Error("fetch failed"));
+      expect(backoff.readDeferral()).toEqual({
+        until: "2026-04-10T12:03:00.000Z",
+        reason: "backoff",
+      });
+    } finally {
+      database.close();
+    }
+
+    const reopenedDatabase = initializeDatabase(resolveAppPaths({ homeDir }));
+
+    try {
+      const backoff = createPersistentPollerBackoff(
+        new PollerStateRepository(reopenedDatabase),
+        "tracked_pull_requests",
+        { intervalMs: INTERVAL_MS, now: () => currentTime },
+      );
+
+      expect(backoff.readDeferral()?.reason).toBe("backoff");
+
+      currentTime += 3 * INTERVAL_MS;
+      expect(backoff.readDeferral()).toBeNull();
+
+      backoff.recordCycle(null);
+      expect(new PollerStateRepository(reopenedDatabase).getPollerState("tracked_pull_requests")).toEqual({
+        consecutiveFailures: 0,
+        backoffUntil: null,
+      });
+    } finally {
+      reopenedDatabase.close();
+    }
+  });
+
+  it("jitters the wait, caps it by failure kind, and doesn't back off on a rejected token", () => {
+    const database = initializeDatabase(resolveAppPaths({ homeDir: createTempDir() }));
+    const now = () => Date.parse("2026-04-10T12:00:00.000Z");
+
+    try {
+      const repository = new PollerStateRepository(database);
+      const recordFailures = (poller: string, error: Error, count: number) => {
+        const backoff = createPersistentPollerBackoff(repository, poller, {
+          intervalMs: INTERVAL_MS,
+          now,
+          random: () => 0.5,
+        });
+
+        for (let failure = 0; failure < count; failure += 1) {
+          backoff.recordCycle(error);
+        }
+
+        return repository.getPollerState(poller).backoffUntil;
+      };
+      const serverError = Object.assign(new Error("Server Error"), { status: 502 });
+
+      // 7 minutes, less a quarter of jitter.
+      expect(recordFailures("discovery", new Error("wrapped", { cause: serverError }), 4)).toBe(
+        "2026-04-10T12:05:15.000Z",
+      );
+      // 31 minutes, capped at 5 for a network outage, less a quarter.
+      expect(recordFailures("tracked_pull_requests", new Error("fetch failed"), 6)).toBe(
+        "2026-04-10T12:03:45.000Z",
+      );
+      expect(recordFailures("security_alerts", new Error("Bad credentials"), 6)).toBeNull();
+      expect(repository.getPollerState("security_alerts").consecutiveFailures).toBe(6);
+    } finally {
+      database.close();
+    }
+  });
+
+  it("defers every poller until a recorded rate limit resets", () => {
+    const database = initializeDatabase(resolveAppPaths({ homeDir: createTempDir() }));
+    const now = () => Date.parse("2026-04-10T12:00:00.000Z");
+
+    try {
+      const repository = new PollerStateRepository(database);
+      repository.saveRateLimitResetAt("2026-04-10T12:20:00.000Z");
+
+      expect(
+        createPersistentPollerBackoff(repository, "discovery", { intervalMs: INTERVAL_MS, now })
+          .readDeferral(),
+      ).toEqual({ until: "2026-04-10T12:20:00.000Z", reason: "rate_limit" });
+    } finally {
+      database.close();
+    }
+  });
+});
+
+describe("rate limit slowdown", () => {
+  it("runs every fourth cycle while the rate limit is low, until it resets", () => {
+    const database = initializeDatabase(resolveAppPaths({ homeDir: createTempDir() }));
+    let currentTime = Date.parse("2026-04-10T12:00:00.000Z");
+
+    try {
+      const repository = new PollerStateRepository(database);
+      const backoff = createPersistentPollerBackoff(repository, "tracked_pull_requests", {
+        intervalMs: INTERVAL_MS,
+        now: () => currentTime,
+      });
+
+      repository.saveRateLimitSlowdownUntil("2026-04-10T12:30:00.000Z");
+      expect(backoff.readDeferral()).toBeNull();
+
+      backoff.recordCycle(null);
+      currentTime += INTERVAL_MS;
+      expect(backoff.readDeferral()).toEqual({
+        until: "2026-04-10T12:03:00.000Z",
+        reason: "rate_limit_low",
+      });
+
+      currentTime += 3 * INTERVAL_MS;
+      expect(backoff.readDeferral()).toBeNull();
+
+      backoff.recordCycle(null);
+      currentTime = Date.parse("2026-04-10T12:30:00.000Z");
+      expect(backoff.readDeferral()).toBeNull();
+    } finally {
+      database.close();
+    }
+  });
+
+  it("reads a low core rate limit from response headers", () => {
+    const resetEpochSeconds = String(Date.parse("2026-04-10T12:30:00.000Z") / 1_000);
+
+    expect(
+      readLowRateLimitBudget({
+        "x-ratelimit-resource": "core",
+        "x-ratelimit-remaining": "812",
+        "x-ratelimit-limit": "5000",
+        "x-ratelimit-reset": resetEpochSeconds,
+      }),
+    ).toEqual({ remaining: 812, limit: 5000, resetAt: "2026-04-10T12:30:00.000Z" });
+    expect(
+      readLowRateLimitBudget({
+        "x-ratelimit-resource": "core",
+        "x-ratelimit-remaining": "4200",
+        "x-ratelimit-limit": "5000",
+        "x-ratelimit-reset": resetEpochSeconds,
+      }),
+    ).toBeNull();
+    expect(
+      readLowRateLimitBudget({
+        "x-ratelimit-resource": "search",
+        "x-ratelimit-remaining": "2",
+        "x-ratelimit-limit": "30",
+        "x-ratelimit-reset": resetEpochSeconds,
+      }),
+    ).toBeNull();
+  });
+});
+
+describe("readRateLimitResetAt", () => {
+  it("reads Retry-After or an exhausted rate limit's reset time", () => {
+    const currentTime = Date.parse("2026-04-10T12:00:00.000Z");
+
+    expect(
+      readRateLimitResetAt(
+        { status: 429, response: { headers: { "retry-after": "120" } } },
+        currentTime,
+      ),
+    ).toBe("2026-04-10T12:02:00.000Z");
+    expect(
+      readRateLimitResetAt(
+        {
+          status: 403,
+          response: {
+            headers: {
+              "x-ratelimit-remaining": "0",
+              "x-ratelimit-reset": String(Date.parse("2026-04-10T12:30:00.000Z") / 1_000),
+            },
+          },
+        },
+        currentTime,
+      ),
+    ).toBe("2026-04-10T12:30:00.000Z");
+    expect(
+      readRateLimitResetAt(
+        { status: 403, response: { headers: { "x-ratelimit-remaining": "42" } } },
+        currentTime,
+      ),
+    ).toBeNull();
+  });
+});
+
+function createTempDir(): string {
+  const tempDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-poller-state-"));
+  tempDirs.push(tempDir);
+  return tempDir;
+}
@@ -0,0 +1,57 @@
+import { describe, expect, it, vi } from "vitest";
+
+import {
+  buildProtocolUrl,
+  parseProtocolUrl,
+  ProtocolHandlerError,
+  runProtocolHandler,
+} from "../src/protocol-handler.js";
+
+describe("parseProtocolUrl", () => {
+  it("round-trips actions built with buildProtocolUrl", () => {
+    const actions = [
+      { kind: "open", url: "https://github.com/acme/octopulse/pull/7#issuecomment-1" },
+      { kind: "history" },
+      { kind: "shortcut", shortcut: "open-latest" },
+      { kind: "shortcut", shortcut: "open-inbox" },
+      { kind: "shortcut", shortcut: "toggle-pause" },
+    ] as const;
+
+    for (const action of actions) {
+      expect(parseProtocolUrl(buildProtocolUrl(action))).toEqual(action);
+    }
+  });
+
+  it("rejects other schemes, unknown actions, and non-web open targets", () => {
+    expect(() => parseProtocolUrl("https://github.com")).toThrow(ProtocolHandlerError);
+    expect(() => parseProtocolUrl("octopulse://dance")).toThrow(ProtocolHandlerError);
+    expect(() => parseProtocolUrl("octopulse://open?url=file%3A%2F%2F%2Fetc%2Fpasswd")).toThrow(
+      ProtocolHandlerError,
+    );
+  });
+});
+
+describe("runProtocolHandler", () => {
+  it("opens links locally and forwards shortcuts to the running service", async () => {
+    const openUrl = vi.fn().mockResolvedValue(undefined);
+    const runShortcut = vi.fn().mockResolvedValue("Notifications paused.");
+    const options = { serverOrigin: "http://127.0.0.1:3000", openUrl, runShortcut };
+
+    await expect(
+      runProtocolHandler(
+        "octopulse://open?url=https%3A%2F%2Fgithub.com%2Facme%2Foctopulse%2Fpull%2F7",
+        options,
+      ),
+    ).resolves.toBe("Opened https://github.com/acme/octopulse/pull/7");
+    await runProtocolHandler("octopulse://history", options);
+    await expect(runProtocolHandler("octopulse://toggle-pause", options)).resolves.toBe(
+      "Notifications paused.",
+    );
+
+    expect(openUrl.mock.calls).toEqual([
+      ["https://github.com/acme/octopulse/pull/7"],
+      ["http://127.0.0.1:3000/notification-history"],
+    ]);
+    expect(runShortcut).toHaveBeenCalledWith(["toggle-pause"]);
+  });
+});
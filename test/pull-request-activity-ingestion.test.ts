@@ -2,7 +2,7 @@ import { mkdtempSync, rmSync } from "node:fs";
 import os from "node:os";
 import path from "node:path";
 
-import { afterEach, describe, expect, it } from "vitest";
+import { afterEach, describe, expect, it, vi } from "vitest";
 
 import { resolveAppPaths } from "../src/config.js";
 import { initializeDatabase } from "../src/database.js";
@@ -310,6 +310,68 @@ describe("ingestPullRequestActivity", () => {
     }
   });
 
+  it("drops issue comments that mirror a submitted review body", async () => {
+    const { database, pullRequest } = createPullRequest();
+    const rawEventRepository = new RawEventRepository(database);
+
+    try {
+      await expect(
+        ingestPullRequestActivity(database, { kind: "fake-client" }, pullRequest, {
+          fetchIssueComments: async () => [
+            createIssueCommentFixture({
+              id: 1101,
+              actorLogin: "bob",
+              createdAt: "2026-04-10T12:02:00.000Z",
+              body: "Looks good to me\n",
+            }),
+            createIssueCommentFixture({
+              id: 1102,
+              actorLogin: "alice",
+              createdAt: "2026-04-10T12:02:00.000Z",
+              body: "Looks good to me",
+            }),
+          ],
+          fetchPullRequestReviews: async () => [
+            createReviewFixture({
+              id: 2101,
+              actorLogin: "bob",
+              submittedAt: "2026-04-10T12:02:00.000Z",
+              body: "Looks good to me",
+            }),
+          ],
+          fetchPullRequestReviewComments: async () => [],
+          fetchPullRequestTimeline: async () => [],
+          fetchWorkflowRuns: async () => [],
+        }),
+      ).resolves.toEqual({
+        processedCount: 2,
+        insertedCount: 2,
+        duplicateCount: 0,
+      });
+
+      expect(
+        rawEventRepository.listRawEventsForPullRequest(pullRequest.id).map((rawEvent) => ({
+          sourceId: rawEvent.sourceId,
+          eventType: rawEvent.eventType,
+          actorLogin: rawEvent.actorLogin,
+        })),
+      ).toEqual([
+        {
+          sourceId: "1102",
+          eventType: "issue_comment",
+          actorLogin: "alice",
+        },
+        {
+          sourceId: "2101",
+          eventType: "pull_request_review",
+          actorLogin: "bob",
+        },
+      ]);
+    } finally {
+      database.close();
+    }
+  });
+
   it("dedupes repeated ingestion and reuses comment fetch cursors", async () => {
     const { database, pullRequest } = createPullRequest();
     const rawEventRepository = new RawEventRepository(database);
@@ -385,6 +447,104 @@ describe("ingestPullRequestActivity", () => {
     }
   });
 
+  it("reads every page of reviews and keeps the newest up to the fetch cap", async () => {
+    const { database, pullRequest } = createPullRequest();
+    const rawEventRepository = new RawEventRepository(database);
+    const request = vi.fn(async (_route: string, parameters: { page: number }) => ({
+      data:
+        parameters.page === 1
+          ? Array.from({ length: 100 }, (_, index) => createReviewFixture({ id: 2001 + index }))
+          : [createReviewFixture({ id: 2101 }), createReviewFixture({ id: 2102 })],
+    }));
+
+    try {
+      await expect(
+        ingestPullRequestActivity(database, { request }, pullRequest, {
+          maxFetchedComments: 3,
+          fetchIssueComments: async () => [],
+          fetchPullRequestReviewComments: async () => [],
+          fetchPullRequestTimeline: async () => [],
+          fetchWorkflowRuns: async () => [],
+        }),
+      ).resolves.toMatchObject({ insertedCount: 3 });
+
+      expect(request).toHaveBeenCalledTimes(2);
+      expect(request).toHaveBeenLastCalledWith(
+        "GET /repos/{owner}/{repo}/pulls/{pull_number}/reviews",
+        expect.objectContaining({ page: 2, per_page: 100 }),
+      );
+      expect(
+        rawEventRepository
+          .listRawEventsForPullRequest(pullRequest.id)
+          .map(({ sourceId }) => sourceId),
+      ).toEqual(["2100", "2101", "2102"]);
+    } finally {
+      database.close();
+    }
+  });
+
+  it("reads comments and reviews through one GraphQL request when configured", async () => {
+    const { database, pullRequest } = createPullRequest();
+    const rawEventRepository = new RawEventRepository(database);
+    const author = { __typename: "User", login: "bob", avatarUrl: null };
+    const graphql = vi.fn(async () => ({
+      repository: {
+        pullRequest: {
+          comments: {
+            nodes: [
+              {
+                id: "IC_1",
+                databaseId: 1001,
+                url: "https://github.com/acme/octopulse/pull/7#issuecomment-1001",
+                body: "Ping",
+                createdAt: "2026-04-10T12:01:00Z",
+                updatedAt: "2026-04-10T12:01:00Z",
+                author,
+              },
+            ],
+          },
+          reviews: {
+            nodes: [
+              {
+                id: "PRR_1",
+                databaseId: 2001,
+                url: "https://github.com/acme/octopulse/pull/7#pullrequestreview-2001",
+                body: "Looks good to me",
+                state: "APPROVED",
+                submittedAt: "2026-04-10T12:02:00Z",
+                commit: { oid: "abc123" },
+                author,
+                comments: { nodes: [] },
+              },
+            ],
+          },
+        },
+      },
+    }));
+
+    try {
+      await expect(
+        ingestPullRequestActivity(database, { graphql }, pullRequest, {
+          activityApi: "graphql",
+          fetchPullRequestTimeline: async () => [],
+          fetchWorkflowRuns: async () => [],
+        }),
+      ).resolves.toMatchObject({ insertedCount: 2 });
+
+      expect(graphql).toHaveBeenCalledTimes(1);
+      expect(
+        rawEventRepository
+          .listRawEventsForPullRequest(pullRequest.id)
+          .map(({ sourceId, eventType, actorLogin }) => [sourceId, eventType, actorLogin]),
+      ).toEqual([
+        ["1001", "issue_comment", "bob"],
+        ["2001", "pull_request_review", "bob"],
+      ]);
+    } finally {
+      database.close();
+    }
+  });
+
   it("persists workflow runs that only expose node_id", async () => {
     const { database, pullRequest } = createPullRequest();
     const rawEventRepository = new RawEventRepository(database);
@@ -524,6 +524,46 @@ describe("normalizePullRequestActivity", () => {
     }
   });
 
+  it("marks ci failures immediate when CI failure alerts are enabled", () => {
+    const { database, pullRequest } = createPullRequest();
+    const rawEventRepository = new RawEventRepository(database);
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+
+    try {
+      rawEventRepository.insertRawEvent({
+        pullRequestId: pullRequest.id,
+        source: "github_actions_workflow_run",
+        sourceId: "5111:2026-04-10T12:31:00.000Z",
+        eventType: "workflow_run",
+        actorLogin: "octocat",
+        payloadJson: JSON.stringify(
+          createWorkflowRunFixture({
+            id: 5111,
+            actorLogin: "octocat",
+            actorType: "User",
+            headSha: "abc123",
+            status: "completed",
+            conclusion: "failure",
+            updatedAt: "2026-04-10T12:31:00.000Z",
+          }),
+        ),
+        occurredAt: "2026-04-10T12:31:00.000Z",
+      });
+
+      normalizePullRequestActivity(database, pullRequest, "octocat", { immediateCiFailures: true });
+
+      expect(normalizedEventRepository.listNormalizedEventsForPullRequest(pullRequest.id)).toEqual([
+        expect.objectContaining({
+          eventType: "ci_failed",
+          decisionState: "notified",
+          notificationTiming: "immediate",
+        }),
+      ]);
+    } finally {
+      database.close();
+    }
+  });
+
   it("suppresses ci outcomes on other people's pull requests", () => {
     const { database, pullRequest } = createPullRequest();
     const rawEventRepository = new RawEventRepository(database);
@@ -995,6 +1035,250 @@ describe("normalizePullRequestActivity", () => {
     }
   });
 
+  it("maps assignments of the current user to immediate pr_assigned events", () => {
+    const { database, pullRequest } = createPullRequest();
+    const rawEventRepository = new RawEventRepository(database);
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+
+    try {
+      for (const [id, assigneeLogin] of [
+        [4201, "OctoCat"],
+        [4202, "bob"],
+      ] as const) {
+        rawEventRepository.insertRawEvent({
+          pullRequestId: pullRequest.id,
+          source: "github_issue_timeline",
+          sourceId: String(id),
+          eventType: "assigned",
+          actorLogin: "alice",
+          payloadJson: JSON.stringify({
+            ...createTimelineEventFixture({
+              id,
+              actorLogin: "alice",
+              actorType: "User",
+              event: "assigned",
+              createdAt: "2026-04-10T12:30:00.000Z",
+            }),
+            assignee: { login: assigneeLogin },
+          }),
+          occurredAt: "2026-04-10T12:30:00.000Z",
+        });
+      }
+
+      expect(normalizePullRequestActivity(database, pullRequest, "octocat")).toEqual({
+        processedCount: 2,
+        normalizedCount: 1,
+        skippedCount: 1,
+      });
+
+      expect(normalizedEventRepository.listNormalizedEventsForPullRequest(pullRequest.id)).toEqual([
+        expect.objectContaining({
+          eventType: "pr_assigned",
+          actorLogin: "alice",
+          actorClass: "human_other",
+          decisionState: "notified",
+          notificationTiming: "immediate",
+        }),
+      ]);
+    } finally {
+      database.close();
+    }
+  });
+
+  it("counts commits pushed since the current user's latest review", () => {
+    const { database, pullRequest } = createPullRequest();
+    const rawEventRepository = new RawEventRepository(database);
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+
+    try {
+      rawEventRepository.insertRawEvent({
+        pullRequestId: pullRequest.id,
+        source: "github_pull_request_review",
+        sourceId: "2201",
+        eventType: "pull_request_review",
+        actorLogin: "Bob",
+        payloadJson: JSON.stringify(
+          createReviewFixture({
+            id: 2201,
+            actorLogin: "Bob",
+            actorType: "User",
+            state: "CHANGES_REQUESTED",
+            body: "Please split this up",
+            submittedAt: "2026-04-10T12:10:00.000Z",
+          }),
+        ),
+        occurredAt: "2026-04-10T12:10:00.000Z",
+      });
+
+      for (const [sha, committedAt] of [
+        ["0ld5ha", "2026-04-10T12:05:00.000Z"],
+        ["n3w5ha", "2026-04-10T12:20:00.000Z"],
+        ["n3x75ha", "2026-04-10T12:30:00.000Z"],
+      ] as const) {
+        rawEventRepository.insertRawEvent({
+          pullRequestId: pullRequest.id,
+          source: "github_issue_timeline",
+          sourceId: sha,
+          eventType: "committed",
+          actorLogin: "frank",
+          payloadJson: JSON.stringify(
+            createCommittedTimelineEventFixture({
+              actorLogin: "frank",
+              actorType: "User",
+              sha,
+              message: `Commit ${sha}`,
+              committedAt,
+            }),
+          ),
+          occurredAt: committedAt,
+        });
+      }
+
+      normalizePullRequestActivity(database, pullRequest, "bob");
+
+      expect(
+        normalizedEventRepository
+          .listNormalizedEventsForPullRequest(pullRequest.id)
+          .filter((event) => event.eventType === "commit_pushed")
+          .map((event) => parseNormalizedPayload(event.payloadJson).commitsSinceReview),
+      ).toEqual([undefined, 1, 2]);
+    } finally {
+      database.close();
+    }
+  });
+
+  it("warns immediately about force pushes after the current user's review", () => {
+    const { database, pullRequest } = createPullRequest();
+    const rawEventRepository = new RawEventRepository(database);
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+
+    try {
+      for (const [id, createdAt] of [
+        [4301, "2026-04-10T12:05:00.000Z"],
+        [4302, "2026-04-10T12:20:00.000Z"],
+      ] as const) {
+        rawEventRepository.insertRawEvent({
+          pullRequestId: pullRequest.id,
+          source: "github_issue_timeline",
+          sourceId: String(id),
+          eventType: "head_ref_force_pushed",
+          actorLogin: "frank",
+          payloadJson: JSON.stringify(
+            createTimelineEventFixture({
+              id,
+              actorLogin: "frank",
+              actorType: "User",
+              event: "head_ref_force_pushed",
+              createdAt,
+            }),
+          ),
+          occurredAt: createdAt,
+        });
+      }
+
+      rawEventRepository.insertRawEvent({
+        pullRequestId: pullRequest.id,
+        source: "github_pull_request_review",
+        sourceId: "2301",
+        eventType: "pull_request_review",
+        actorLogin: "bob",
+        payloadJson: JSON.stringify(
+          createReviewFixture({
+            id: 2301,
+            actorLogin: "bob",
+            actorType: "User",
+            state: "APPROVED",
+            body: "Ship it",
+            submittedAt: "2026-04-10T12:10:00.000Z",
+          }),
+        ),
+        occurredAt: "2026-04-10T12:10:00.000Z",
+      });
+
+      normalizePullRequestActivity(database, pullRequest, "bob");
+
+      expect(
+        normalizedEventRepository
+          .listNormalizedEventsForPullRequest(pullRequest.id)
+          .filter((event) => event.eventType === "force_pushed_after_review"),
+      ).toEqual([
+        expect.objectContaining({
+          actorLogin: "frank",
+          occurredAt: "2026-04-10T12:20:00.000Z",
+          decisionState: "notified",
+          notificationTiming: "immediate",
+        }),
+      ]);
+    } finally {
+      database.close();
+    }
+  });
+
+  it("records whose review was dismissed and why", () => {
+    const { database, pullRequest } = createPullRequest();
+    const rawEventRepository = new RawEventRepository(database);
+    const normalizedEventRepository = new NormalizedEventRepository(database);
+
+    try {
+      rawEventRepository.insertRawEvent({
+        pullRequestId: pullRequest.id,
+        source: "github_pull_request_review",
+        sourceId: "2401",
+        eventType: "pull_request_review",
+        actorLogin: "bob",
+        payloadJson: JSON.stringify(
+          createReviewFixture({
+            id: 2401,
+            actorLogin: "bob",
+            actorType: "User",
+            state: "CHANGES_REQUESTED",
+            body: "Needs tests",
+            submittedAt: "2026-04-10T12:10:00.000Z",
+          }),
+        ),
+        occurredAt: "2026-04-10T12:10:00.000Z",
+      });
+      rawEventRepository.insertRawEvent({
+        pullRequestId: pullRequest.id,
+        source: "github_issue_timeline",
+        sourceId: "4401",
+        eventType: "review_dismissed",
+        actorLogin: "alice",
+        payloadJson: JSON.stringify({
+          ...createTimelineEventFixture({
+            id: 4401,
+            actorLogin: "alice",
+            actorType: "User",
+            event: "review_dismissed",
+            createdAt: "2026-04-10T12:30:00.000Z",
+          }),
+          dismissed_review: {
+            state: "changes_requested",
+            review_id: 2401,
+            dismissal_message: "Tests were added in the follow-up commit",
+          },
+        }),
+        occurredAt: "2026-04-10T12:30:00.000Z",
+      });
+
+      normalizePullRequestActivity(database, pullRequest, "octocat");
+
+      const dismissal = normalizedEventRepository
+        .listNormalizedEventsForPullRequest(pullRequest.id)
+        .find((event) => event.eventType === "review_dismissed");
+
+      expect(dismissal).toMatchObject({ actorLogin: "alice", decisionState: "notified" });
+      expect(parseNormalizedPayload(dismissal?.payloadJson ?? "{}")).toMatchObject({
+        reviewId: 2401,
+        reviewerLogin: "bob",
+        reviewState: "CHANGES_REQUESTED",
+        dismissalMessage: "Tests were added in the follow-up commit",
+      });
+    } finally {
+      database.close();
+    }
+  });
+
   it("maps review states to normalized review event types", () => {
     const { database, pullRequest } = createPullRequest();
     const rawEventRepository = new RawEventRepository(database);
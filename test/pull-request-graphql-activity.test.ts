@@ -0,0 +1,132 @@
+import { describe, expect, it, vi } from "vitest";
+
+import {
+  fetchPullRequestActivityFromGraphql,
+  filterUpdatedSince,
+  PullRequestGraphqlActivityError,
+} from "../src/pull-request-graphql-activity.js";
+
+const COORDINATES = { repositoryOwner: "acme", repositoryName: "octopulse", number: 7 };
+const BOB = { __typename: "User", login: "bob", avatarUrl: "https://avatars/b" };
+
+describe("fetchPullRequestActivityFromGraphql", () => {
+  it("maps comments, reviews, and review comments to their REST shapes", async () => {
+    const client = {
+      graphql: vi.fn(async () => ({
+        repository: {
+          pullRequest: {
+            comments: {
+              nodes: [
+                {
+                  id: "IC_1",
+                  databaseId: 1001,
+                  url: "https://github.com/acme/octopulse/pull/7#issuecomment-1001",
+                  body: "Ping",
+                  createdAt: "2026-04-10T12:01:00Z",
+                  updatedAt: "2026-04-10T12:01:00Z",
+                  author: { __typename: "Bot", login: "renovate", avatarUrl: "https://avatars/r" },
+                },
+              ],
+            },
+            reviews: {
+              nodes: [
+                {
+                  id: "PRR_1",
+                  databaseId: 2001,
+                  url: "https://github.com/acme/octopulse/pull/7#pullrequestreview-2001",
+                  body: "",
+                  state: "CHANGES_REQUESTED",
+                  submittedAt: "2026-04-10T12:02:00Z",
+                  commit: { oid: "abc123" },
+                  author: BOB,
+                  comments: {
+                    nodes: [
+                      {
+                        id: "PRRC_1",
+                        databaseId: 3001,
+                        url: "https://github.com/acme/octopulse/pull/7#discussion_r3001",
+                        body: "Rename this",
+                        path: "src/main.ts",
+                        createdAt: "2026-04-10T12:02:00Z",
+                        updatedAt: "2026-04-10T12:03:00Z",
+                        replyTo: null,
+                        author: BOB,
+                      },
+                    ],
+                  },
+                },
+              ],
+            },
+          },
+        },
+      })),
+    };
+
+    await expect(fetchPullRequestActivityFromGraphql(client, COORDINATES, 300)).resolves.toEqual({
+      issueComments: [
+        {
+          id: 1001,
+          node_id: "IC_1",
+          html_url: "https://github.com/acme/octopulse/pull/7#issuecomment-1001",
+          body: "Ping",
+          created_at: "2026-04-10T12:01:00Z",
+          updated_at: "2026-04-10T12:01:00Z",
+          user: { login: "renovate[bot]", avatar_url: "https://avatars/r", type: "Bot" },
+        },
+      ],
+      reviews: [
+        {
+          id: 2001,
+          node_id: "PRR_1",
+          html_url: "https://github.com/acme/octopulse/pull/7#pullrequestreview-2001",
+          body: "",
+          state: "CHANGES_REQUESTED",
+          submitted_at: "2026-04-10T12:02:00Z",
+          commit_id: "abc123",
+          user: { login: "bob", avatar_url: "https://avatars/b", type: "User" },
+        },
+      ],
+      reviewComments: [
+        {
+          id: 3001,
+          node_id: "PRRC_1",
+          pull_request_review_id: 2001,
+          html_url: "https://github.com/acme/octopulse/pull/7#discussion_r3001",
+          body: "Rename this",
+          path: "src/main.ts",
+          created_at: "2026-04-10T12:02:00Z",
+          updated_at: "2026-04-10T12:03:00Z",
+          user: { login: "bob", avatar_url: "https://avatars/b", type: "User" },
+        },
+      ],
+    });
+    expect(client.graphql).toHaveBeenCalledTimes(1);
+    expect(client.graphql).toHaveBeenCalledWith(
+      expect.stringContaining("reviews(last: $last)"),
+      { owner: "acme", repo: "octopulse", number: 7, last: 100 },
+    );
+  });
+
+  it("reports a pull request GitHub didn't return", async () => {
+    const client = { graphql: vi.fn(async () => ({ repository: { pullRequest: null } })) };
+
+    await expect(fetchPullRequestActivityFromGraphql(client, COORDINATES, 50)).rejects.toThrowError(
+      new PullRequestGraphqlActivityError("repository.pullRequest must be an object"),
+    );
+  });
+});
+
+describe("filterUpdatedSince", () => {
+  it("keeps items updated at or after the cursor", () => {
+    const items = [
+      { id: 1, created_at: "2026-04-10T12:00:00Z", updated_at: "2026-04-10T12:00:00Z" },
+      { id: 2, created_at: "2026-04-10T12:00:00Z", updated_at: "2026-04-10T12:05:00Z" },
+      { id: 3, created_at: "2026-04-10T12:06:00Z" },
+    ];
+
+    expect(filterUpdatedSince(items, "2026-04-10T12:05:00.000Z").map(({ id }) => id)).toEqual([
+      2, 3,
+    ]);
+    expect(filterUpdatedSince(items, undefined)).toHaveLength(3);
+  });
+});
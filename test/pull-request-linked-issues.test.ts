@@ -0,0 +1,46 @@
+import { describe, expect, it, vi } from "vitest";
+
+import {
+  fetchLinkedIssuesFromGitHub,
+  formatLinkedIssuesText,
+} from "../src/pull-request-linked-issues.js";
+
+describe("pull request linked issues", () => {
+  it("reads closing issue references from GraphQL", async () => {
+    const client = {
+      graphql: vi.fn(async () => ({
+        repository: {
+          pullRequest: {
+            closingIssuesReferences: {
+              nodes: [
+                { number: 12, title: "Polling stalls", repository: { name: "octopulse", owner: { login: "acme" } } },
+                { number: 4, title: "Handle nulls", repository: { name: "api", owner: { login: "acme" } } },
+              ],
+            },
+          },
+        },
+      })),
+    };
+
+    await expect(
+      fetchLinkedIssuesFromGitHub(client, { repositoryOwner: "acme", repositoryName: "octopulse", number: 7 }),
+    ).resolves.toEqual([
+      { repositoryOwner: "acme", repositoryName: "octopulse", number: 12, title: "Polling stalls" },
+      { repositoryOwner: "acme", repositoryName: "api", number: 4, title: "Handle nulls" },
+    ]);
+    expect(client.graphql).toHaveBeenCalledWith(
+      expect.stringContaining("closingIssuesReferences"),
+      expect.objectContaining({ owner: "acme", repo: "octopulse", number: 7 }),
+    );
+  });
+
+  it("qualifies issues from other repositories", () => {
+    expect(
+      formatLinkedIssuesText({ repositoryOwner: "acme", repositoryName: "octopulse" }, [
+        { repositoryOwner: "acme", repositoryName: "octopulse", number: 12, title: "Polling stalls" },
+        { repositoryOwner: "acme", repositoryName: "api", number: 4, title: "Handle nulls" },
+      ]),
+    ).toBe("🔗 Closes #12 Polling stalls, acme/api#4 Handle nulls");
+    expect(formatLinkedIssuesText({ repositoryOwner: "acme", repositoryName: "octopulse" }, [])).toBeNull();
+  });
+});
@@ -0,0 +1,34 @@
+import { describe, expect, it, vi } from "vitest";
+
+import { fetchProjectStatusFromGitHub } from "../src/pull-request-project-status.js";
+
+describe("fetchProjectStatusFromGitHub", () => {
+  it("reads the status field from the configured project board only", async () => {
+    const client = {
+      graphql: vi.fn(async () => ({
+        repository: {
+          pullRequest: {
+            projectItems: {
+              nodes: [
+                { project: { number: 2, owner: { login: "acme" } }, fieldValueByName: { name: "Done" } },
+                { project: { number: 5, owner: { login: "Acme" } }, fieldValueByName: { name: "In review" } },
+              ],
+            },
+          },
+        },
+      })),
+    };
+    const coordinates = { repositoryOwner: "acme", repositoryName: "octopulse", number: 7 };
+
+    await expect(
+      fetchProjectStatusFromGitHub(client, coordinates, { owner: "acme", number: 5, statusField: "Status" }),
+    ).resolves.toBe("In review");
+    await expect(
+      fetchProjectStatusFromGitHub(client, coordinates, { owner: "acme", number: 9, statusField: "Status" }),
+    ).resolves.toBeNull();
+    expect(client.graphql).toHaveBeenCalledWith(
+      expect.stringContaining("projectItems"),
+      expect.objectContaining({ owner: "acme", repo: "octopulse", number: 7, field: "Status" }),
+    );
+  });
+});
@@ -18,7 +18,13 @@ describe("pull-request snapshot helpers", () => {
           draft: false,
           closedAt: null,
           mergedAt: null,
+          createdAt: "2026-04-01T09:00:00Z",
+          updatedAt: "2026-04-09T15:00:00Z",
+          additions: 42,
+          deletions: 7,
           headSha: "def456",
+          headBranch: "feature/polling",
+          body: "Polls tracked pull requests.",
           baseBranch: "main",
           mergeable: true,
           mergeableState: "clean",
@@ -46,9 +52,17 @@ describe("pull-request snapshot helpers", () => {
       mergedAt: null,
       lastSeenHeadSha: "def456",
       baseBranch: "main",
+      headBranch: "feature/polling",
+      bodyText: "Polls tracked pull requests.",
       mergeable: true,
       mergeableState: "clean",
       requestedReviewTeamSlugs: ["quality-processing-squad"],
+      autoMergeEnabled: false,
+      mergedByLogin: null,
+      openedAt: "2026-04-01T09:00:00Z",
+      lastActivityAt: "2026-04-09T15:00:00Z",
+      additions: 42,
+      deletions: 7,
     });
   });
 
@@ -0,0 +1,200 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import type { Octokit } from "octokit";
+import { afterAll, afterEach, describe, expect, it, vi } from "vitest";
+
+import type { DiscoveredPullRequest } from "../src/authored-pull-request-discovery.js";
+import { resolveAppPaths } from "../src/config.js";
+import { initializeDatabase } from "../src/database.js";
+import { PullRequestRepository } from "../src/pull-request-repository.js";
+import type { PullRequestCoordinates } from "../src/pull-request-snapshot.js";
+import type { PollerBackoff } from "../src/poller-state.js";
+import {
+  pollRepositoryInboxes,
+  startRecurringRepositoryInboxPolling,
+  type RepositoryInboxCache,
+} from "../src/repository-inbox-polling.js";
+
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  vi.useRealTimers();
+});
+
+afterAll(() => {
+  for (const tempDir of tempDirs) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("pollRepositoryInboxes", () => {
+  it("tracks new pull requests from each repository's notifications", async () => {
+    const database = createDatabase();
+    const pullRequestRepository = new PullRequestRepository(database);
+    const inbox = { participating: false, reasons: [] };
+    const listRepositoryPullRequests = vi.fn(async (repository: string) => ({
+      coordinates:
+        repository === "acme/production"
+          ? [{ repositoryOwner: "acme", repositoryName: "production", number: 12 }]
+          : [],
+    }));
+    const fetchPullRequestDetail = vi.fn(async (coordinates: PullRequestCoordinates) =>
+      createDiscoveredPullRequest(coordinates),
+    );
+    const options = {
+      repositories: ["acme/production", "acme/payments"],
+      inbox,
+      pullRequestRepository,
+      listRepositoryPullRequests,
+      fetchPullRequestDetail,
+      observedAt: "2026-04-10T12:00:00.000Z",
+    };
+
+    try {
+      await expect(pollRepositoryInboxes(database, {} as Octokit, options)).resolves.toEqual({
+        trackedCount: 1,
+      });
+      await expect(pollRepositoryInboxes(database, {} as Octokit, options)).resolves.toEqual({
+        trackedCount: 0,
+      });
+
+      expect(listRepositoryPullRequests).toHaveBeenCalledWith("acme/payments", inbox, undefined);
+      expect(fetchPullRequestDetail).toHaveBeenCalledTimes(1);
+      expect(pullRequestRepository.getPullRequestByGitHubPullRequestId(1201)).toMatchObject({
+        repositoryName: "production",
+        isTracked: true,
+        trackingReason: "github_inbox",
+      });
+    } finally {
+      database.close();
+    }
+  });
+});
+
+describe("pollRepositoryInboxes with a cache", () => {
+  it("asks GitHub only for changes once a repository's list was fully stored", async () => {
+    const database = createDatabase();
+    const coordinates = { repositoryOwner: "acme", repositoryName: "production", number: 12 };
+    const lastModified = "Fri, 10 Apr 2026 12:50:00 GMT";
+    const listRepositoryPullRequests = vi
+      .fn()
+      .mockResolvedValueOnce({ coordinates: [coordinates], lastModified, pollIntervalMs: 120_000 })
+      .mockResolvedValueOnce({ coordinates: [coordinates], lastModified, pollIntervalMs: 120_000 })
+      .mockResolvedValueOnce("not_modified");
+    const fetchPullRequestDetail = vi
+      .fn()
+      .mockRejectedValueOnce(new Error("Bad gateway"))
+      .mockResolvedValueOnce(createDiscoveredPullRequest(coordinates));
+    const cache: RepositoryInboxCache = {
+      lastModifiedByRepository: new Map(),
+      pollIntervalMs: undefined,
+    };
+    const options = {
+      repositories: ["acme/production"],
+      inbox: { participating: false, reasons: [] },
+      listRepositoryPullRequests,
+      fetchPullRequestDetail,
+      cache,
+    };
+
+    try {
+      await expect(pollRepositoryInboxes(database, {} as Octokit, options)).rejects.toThrow(
+        "Bad gateway",
+      );
+      await expect(pollRepositoryInboxes(database, {} as Octokit, options)).resolves.toEqual({
+        trackedCount: 1,
+      });
+      await expect(pollRepositoryInboxes(database, {} as Octokit, options)).resolves.toEqual({
+        trackedCount: 0,
+      });
+
+      expect(listRepositoryPullRequests.mock.calls.map((call) => call[2])).toEqual([
+        undefined,
+        undefined,
+        lastModified,
+      ]);
+      expect(cache.pollIntervalMs).toBe(120_000);
+    } finally {
+      database.close();
+    }
+  });
+});
+
+describe("startRecurringRepositoryInboxPolling", () => {
+  it("skips cycles while paused or backed off and follows GitHub's poll interval", async () => {
+    vi.useFakeTimers();
+
+    const database = createDatabase();
+    const listRepositoryPullRequests = vi.fn(async () => ({
+      coordinates: [],
+      pollIntervalMs: 5_000,
+    }));
+    let deferral: ReturnType<PollerBackoff["readDeferral"]> = null;
+    const backoff: PollerBackoff = {
+      readDeferral: () => deferral,
+      recordCycle: vi.fn(),
+    };
+    const polling = startRecurringRepositoryInboxPolling(database, {} as Octokit, {
+      intervalMs: 1_000,
+      backoff,
+      repositories: ["acme/production"],
+      inbox: { participating: false, reasons: [] },
+      listRepositoryPullRequests,
+    });
+
+    try {
+      await vi.advanceTimersByTimeAsync(0);
+      expect(listRepositoryPullRequests).toHaveBeenCalledTimes(1);
+      expect(backoff.recordCycle).toHaveBeenCalledWith(null);
+
+      await vi.advanceTimersByTimeAsync(1_000);
+      expect(listRepositoryPullRequests).toHaveBeenCalledTimes(1);
+
+      polling.pause();
+      await vi.advanceTimersByTimeAsync(4_000);
+      expect(listRepositoryPullRequests).toHaveBeenCalledTimes(1);
+
+      polling.resume();
+      deferral = { until: "2026-04-10T13:00:00.000Z", reason: "rate_limit" };
+      await vi.advanceTimersByTimeAsync(5_000);
+      expect(listRepositoryPullRequests).toHaveBeenCalledTimes(1);
+
+      deferral = null;
+      await vi.advanceTimersByTimeAsync(5_000);
+      expect(listRepositoryPullRequests).toHaveBeenCalledTimes(2);
+    } finally {
+      polling.stop();
+      database.close();
+    }
+  });
+});
+
+function createDiscoveredPullRequest(coordinates: PullRequestCoordinates): DiscoveredPullRequest {
+  return {
+    githubPullRequestId: coordinates.number * 100 + 1,
+    repositoryOwner: coordinates.repositoryOwner,
+    repositoryName: coordinates.repositoryName,
+    number: coordinates.number,
+    url: `https://github.com/acme/production/pull/${coordinates.number}`,
+    authorLogin: "octocat",
+    authorAvatarUrl: "https://avatars.example.test/octocat.png",
+    title: "Roll back the payments migration",
+    state: "open",
+    isDraft: false,
+    closedAt: null,
+    mergedAt: null,
+    lastSeenHeadSha: "abc123",
+    baseBranch: "main",
+    mergeable: true,
+    mergeableState: "clean",
+    requestedReviewTeamSlugs: [],
+  };
+}
+
+function createDatabase(): ReturnType<typeof initializeDatabase> {
+  const homeDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-repository-inbox-home-"));
+  tempDirs.push(homeDir);
+  return initializeDatabase(resolveAppPaths({ homeDir }));
+}
@@ -0,0 +1,58 @@
+import { describe, expect, it, vi } from "vitest";
+
+import { collectRepositorySummaries } from "../src/repository-summaries.js";
+
+describe("collectRepositorySummaries", () => {
+  it("shows one summary per repository when flushed", async () => {
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const summaries = collectRepositorySummaries({ dispatchNotification });
+
+    await summaries.dispatchNotification({
+      title: "acme/api PR #7",
+      body: "alice approved review\nAdd rate limiting",
+      clickUrl: "https://github.com/acme/api/pull/7",
+      eventTypes: ["review_approved"],
+    });
+    await summaries.dispatchNotification({
+      title: "acme/api PR #9",
+      body: "bob commented\nFix <script> escaping",
+      clickUrl: "https://github.com/acme/api/pull/9#issuecomment-1",
+      eventTypes: ["issue_comment"],
+      soundFile: "/tmp/comment.oga",
+    });
+    await summaries.dispatchNotification({
+      title: "acme/web PR #3",
+      body: "carol commented",
+      clickUrl: "https://github.com/acme/web/pull/3",
+    });
+    await summaries.dispatchNotification({ title: "Octopulse", body: "Token expires soon" });
+
+    expect(dispatchNotification).toHaveBeenCalledTimes(1);
+    expect(dispatchNotification).toHaveBeenCalledWith({ title: "Octopulse", body: "Token expires soon" });
+
+    await summaries.flush();
+
+    expect(dispatchNotification).toHaveBeenCalledTimes(3);
+    expect(dispatchNotification).toHaveBeenNthCalledWith(2, {
+      title: "acme/api: 2 PRs updated, 1 approved",
+      body: [
+        "acme/api PR #7 — alice approved review",
+        "acme/api PR #9 — bob commented",
+      ].join("\n"),
+      clickUrl: "https://github.com/acme/api/pulls",
+      expandedHtml: expect.stringContaining("Fix &lt;script&gt; escaping"),
+      eventTypes: ["review_approved", "issue_comment"],
+      soundFile: "/tmp/comment.oga",
+      sticky: false,
+    });
+    expect(dispatchNotification).toHaveBeenNthCalledWith(3, {
+      title: "acme/web PR #3",
+      body: "carol commented",
+      clickUrl: "https://github.com/acme/web/pull/3",
+    });
+
+    await summaries.flush();
+
+    expect(dispatchNotification).toHaveBeenCalledTimes(3);
+  });
+});
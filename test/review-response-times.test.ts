@@ -0,0 +1,118 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it } from "vitest";
+
+import { resolveAppPaths } from "../src/config.js";
+import { initializeDatabase } from "../src/database.js";
+import { NormalizedEventRepository } from "../src/normalized-event-repository.js";
+import { NotificationRecordRepository } from "../src/notification-record-repository.js";
+import { PullRequestRepository } from "../src/pull-request-repository.js";
+import {
+  formatResponseDuration,
+  listReviewResponses,
+  summarizeResponseTimesByRepository,
+} from "../src/review-response-times.js";
+
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("listReviewResponses", () => {
+  it("measures review requests until the notification was opened or I reviewed", () => {
+    const database = createDatabase();
+
+    try {
+      const pullRequestRepository = new PullRequestRepository(database);
+      const normalizedEventRepository = new NormalizedEventRepository(database);
+      const notificationRecordRepository = new NotificationRecordRepository(database);
+      const requestReview = (number: number, occurredAt: string) => {
+        const pullRequest = pullRequestRepository.upsertPullRequest({
+          githubPullRequestId: 100 + number,
+          repositoryOwner: "acme",
+          repositoryName: "octopulse",
+          number,
+          url: `https://github.com/acme/octopulse/pull/${number}`,
+          authorLogin: "octocat",
+          title: "Add notifications",
+          state: "open",
+          isDraft: false,
+          lastSeenAt: occurredAt,
+          lastSeenHeadSha: "abc123",
+        });
+        const event = normalizedEventRepository.insertNormalizedEvent({
+          pullRequestId: pullRequest.id,
+          eventType: "review_requested",
+          actorLogin: "octocat",
+          actorClass: "human_other",
+          decisionState: "notified",
+          notificationTiming: "immediate",
+          occurredAt,
+        });
+        const record = notificationRecordRepository.createNotificationRecord({
+          normalizedEventId: event.id,
+          pullRequestId: pullRequest.id,
+          title: "acme/octopulse #7 Add notifications",
+          body: "octocat requested your review",
+        });
+
+        return { pullRequest, record };
+      };
+
+      const opened = requestReview(7, "2026-04-10T10:00:00.000Z");
+      const reviewed = requestReview(8, "2026-04-10T10:00:00.000Z");
+      requestReview(9, "2026-04-10T10:00:00.000Z");
+
+      notificationRecordRepository.markNotificationRecordRead(
+        opened.record.id,
+        "2026-04-10T10:30:00.000Z",
+      );
+      normalizedEventRepository.insertNormalizedEvent({
+        pullRequestId: reviewed.pullRequest.id,
+        eventType: "review_approved",
+        actorLogin: "me",
+        actorClass: "self",
+        decisionState: "suppressed_self_action",
+        occurredAt: "2026-04-10T12:00:00.000Z",
+      });
+
+      const responses = listReviewResponses(database);
+
+      expect(responses).toEqual([
+        expect.objectContaining({ pullRequestNumber: 7, openedAt: "2026-04-10T10:30:00.000Z" }),
+        expect.objectContaining({ pullRequestNumber: 8, reviewedAt: "2026-04-10T12:00:00.000Z" }),
+        expect.objectContaining({ pullRequestNumber: 9, openedAt: null, reviewedAt: null }),
+      ]);
+      expect(summarizeResponseTimesByRepository(responses)).toEqual([
+        {
+          repository: "acme/octopulse",
+          requestCount: 3,
+          respondedCount: 2,
+          medianResponseMs: 75 * 60_000,
+        },
+      ]);
+    } finally {
+      database.close();
+    }
+  });
+});
+
+describe("formatResponseDuration", () => {
+  it("uses the largest sensible units", () => {
+    expect(formatResponseDuration(75 * 60_000)).toBe("1h 15m");
+    expect(formatResponseDuration(26 * 60 * 60_000)).toBe("1d 2h");
+    expect(formatResponseDuration(5 * 60_000)).toBe("5m");
+  });
+});
+
+function createDatabase(): ReturnType<typeof initializeDatabase> {
+  const tempDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-response-times-"));
+  tempDirs.push(tempDir);
+
+  return initializeDatabase(resolveAppPaths({ homeDir: tempDir }));
+}
@@ -0,0 +1,191 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import type { Octokit } from "octokit";
+import { afterAll, describe, expect, it, vi } from "vitest";
+
+import { resolveAppPaths } from "../src/config.js";
+import { initializeDatabase } from "../src/database.js";
+import {
+  fetchSecurityAlertsFromGitHub,
+  pollSecurityAlerts,
+  type SecurityAlert,
+  type SecurityAlertKind,
+} from "../src/security-alerts.js";
+
+const tempDirs: string[] = [];
+
+afterAll(() => {
+  for (const tempDir of tempDirs) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("pollSecurityAlerts", () => {
+  it("records existing alerts on the first poll and raises critical popups for new ones", async () => {
+    const database = createDatabase();
+    const notificationDispatcher = { dispatchNotification: vi.fn().mockResolvedValue(undefined) };
+    const codeScanningAlerts: SecurityAlert[] = [createAlert(1, "Unused variable")];
+    const fetchSecurityAlerts = vi.fn(async (_repository: string, kind: SecurityAlertKind) => {
+      if (kind === "secret_scanning") {
+        throw Object.assign(new Error("Secret scanning is disabled"), { status: 404 });
+      }
+
+      return codeScanningAlerts;
+    });
+    const options = {
+      repositories: ["acme/api"],
+      notificationDispatcher,
+      fetchSecurityAlerts,
+    };
+
+    try {
+      await expect(pollSecurityAlerts(database, {} as Octokit, options)).resolves.toEqual({
+        notifiedCount: 0,
+      });
+
+      codeScanningAlerts.unshift(createAlert(2, "SQL injection"));
+
+      await expect(pollSecurityAlerts(database, {} as Octokit, options)).resolves.toEqual({
+        notifiedCount: 1,
+      });
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledTimes(1);
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledWith({
+        title: "🚨 acme/api code scanning alert",
+        body: "SQL injection (high)\nsrc/db.ts:42",
+        clickUrl: "https://github.com/acme/api/security/code-scanning/2",
+        sticky: true,
+        urgency: "critical",
+      });
+    } finally {
+      database.close();
+    }
+  });
+
+  it("remembers each alert as soon as its popup was shown", async () => {
+    const database = createDatabase();
+    const notificationDispatcher = {
+      dispatchNotification: vi
+        .fn()
+        .mockResolvedValueOnce(undefined)
+        .mockRejectedValueOnce(new Error("session bus went away"))
+        .mockResolvedValue(undefined),
+    };
+    const codeScanningAlerts: SecurityAlert[] = [];
+    const options = {
+      repositories: ["acme/api"],
+      notificationDispatcher,
+      fetchSecurityAlerts: async (_repository: string, kind: SecurityAlertKind) =>
+        kind === "code_scanning" ? codeScanningAlerts : [],
+    };
+
+    try {
+      await pollSecurityAlerts(database, {} as Octokit, options);
+      codeScanningAlerts.push(createAlert(1, "SQL injection"), createAlert(2, "Path traversal"));
+
+      await expect(pollSecurityAlerts(database, {} as Octokit, options)).rejects.toThrowError(
+        "session bus went away",
+      );
+      await expect(pollSecurityAlerts(database, {} as Octokit, options)).resolves.toEqual({
+        notifiedCount: 1,
+      });
+      expect(
+        notificationDispatcher.dispatchNotification.mock.calls.map(
+          ([notification]) => notification.clickUrl,
+        ),
+      ).toEqual([
+        "https://github.com/acme/api/security/code-scanning/1",
+        "https://github.com/acme/api/security/code-scanning/2",
+        "https://github.com/acme/api/security/code-scanning/2",
+      ]);
+    } finally {
+      database.close();
+    }
+  });
+
+  it("ignores older alerts that move into the fetched page once newer ones are fixed", async () => {
+    const database = createDatabase();
+    const notificationDispatcher = { dispatchNotification: vi.fn().mockResolvedValue(undefined) };
+    // 150 open alerts, newest first; each poll only sees the newest 100.
+    let openAlerts = Array.from({ length: 150 }, (_, index) => createAlert(150 - index, "XSS"));
+    const options = {
+      repositories: ["acme/api"],
+      notificationDispatcher,
+      fetchSecurityAlerts: async (_repository: string, kind: SecurityAlertKind) =>
+        kind === "code_scanning" ? openAlerts.slice(0, 100) : [],
+    };
+
+    try {
+      await pollSecurityAlerts(database, {} as Octokit, options);
+      openAlerts = openAlerts.filter((alert) => alert.number < 140);
+
+      await expect(pollSecurityAlerts(database, {} as Octokit, options)).resolves.toEqual({
+        notifiedCount: 0,
+      });
+
+      openAlerts.unshift(createAlert(151, "SQL injection"));
+
+      await expect(pollSecurityAlerts(database, {} as Octokit, options)).resolves.toEqual({
+        notifiedCount: 1,
+      });
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledWith(
+        expect.objectContaining({
+          clickUrl: "https://github.com/acme/api/security/code-scanning/151",
+        }),
+      );
+    } finally {
+      database.close();
+    }
+  });
+});
+
+describe("fetchSecurityAlertsFromGitHub", () => {
+  it("reads the secret type and first detected location of secret-scanning alerts", async () => {
+    const request = vi.fn(async () => ({
+      data: [
+        {
+          number: 3,
+          html_url: "https://github.com/acme/api/security/secret-scanning/3",
+          secret_type: "github_personal_access_token",
+          secret_type_display_name: "GitHub Personal Access Token",
+          first_location_detected: { path: "config/dev.env", start_line: 4 },
+        },
+      ],
+    }));
+
+    await expect(
+      fetchSecurityAlertsFromGitHub({ request } as unknown as Octokit, "acme/api", "secret_scanning"),
+    ).resolves.toEqual([
+      {
+        kind: "secret_scanning",
+        number: 3,
+        ruleName: "GitHub Personal Access Token",
+        severity: null,
+        location: "config/dev.env:4",
+        url: "https://github.com/acme/api/security/secret-scanning/3",
+      },
+    ]);
+    expect(request).toHaveBeenCalledWith(
+      "GET /repos/{owner}/{repo}/secret-scanning/alerts",
+      expect.objectContaining({ owner: "acme", repo: "api", state: "open" }),
+    );
+  });
+});
+
+function createAlert(number: number, ruleName: string): SecurityAlert {
+  return {
+    kind: "code_scanning",
+    number,
+    ruleName,
+    severity: "high",
+    location: "src/db.ts:42",
+    url: `https://github.com/acme/api/security/code-scanning/${number}`,
+  };
+}
+
+function createDatabase(): ReturnType<typeof initializeDatabase> {
+  const homeDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-security-alerts-home-"));
+  tempDirs.push(homeDir);
+  return initializeDatabase(resolveAppPaths({ homeDir }));
+}
@@ -0,0 +1,198 @@
+import { createHash } from "node:crypto";
+import { existsSync, mkdirSync, mkdtempSync, readdirSync, rmSync, writeFileSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import {
+  checkDownloadIntegrity,
+  compareVersions,
+  runSelfUpdate,
+  SelfUpdateError,
+  verifyReleaseSignature,
+} from "../src/self-update.js";
+
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("runSelfUpdate", () => {
+  it("downloads a newer release, unpacks it beside the checkout and swaps it in", async () => {
+    const repoRoot = mkdtempSync(path.join(os.tmpdir(), "octopulse-self-update-"));
+    tempDirs.push(repoRoot, `${repoRoot}.previous-0.1.0`);
+    writeFileSync(path.join(repoRoot, "package.json"), JSON.stringify({ version: "0.1.0" }));
+    const tarball = Buffer.from("release tarball");
+    const fetchImpl = createReleaseFetch(
+      "v0.2.0",
+      tarball,
+      `${createHash("sha256").update(tarball).digest("hex")}  octopulse-0.2.0.tar.gz\n`,
+    );
+    const runCommand = vi.fn().mockResolvedValue("");
+    const verifySignature = vi.fn().mockResolvedValue(undefined);
+
+    await expect(
+      runSelfUpdate({ repoRoot, fetchImpl, runCommand, verifySignature }),
+    ).resolves.toContain(`Updated Octopulse 0.1.0 -> 0.2.0 in ${repoRoot}`);
+    expect(verifySignature).toHaveBeenCalledWith(
+      expect.stringContaining("octopulse-0.2.0.tar.gz"),
+      "release signature",
+    );
+    expect(runCommand).toHaveBeenCalledWith("tar", [
+      "-xzf",
+      expect.stringContaining("octopulse-0.2.0.tar.gz"),
+      "-C",
+      expect.stringContaining(`${repoRoot}.update-`),
+      "--strip-components=1",
+    ]);
+    expect(readdirSync(repoRoot)).toEqual([]);
+    expect(existsSync(path.join(`${repoRoot}.previous-0.1.0`, "package.json"))).toBe(true);
+  });
+
+  it("leaves the checkout in place when unpacking fails", async () => {
+    const repoRoot = mkdtempSync(path.join(os.tmpdir(), "octopulse-self-update-"));
+    tempDirs.push(repoRoot);
+    writeFileSync(path.join(repoRoot, "package.json"), JSON.stringify({ version: "0.1.0" }));
+    const tarball = Buffer.from("release tarball");
+    const fetchImpl = createReleaseFetch(
+      "v0.2.0",
+      tarball,
+      `${createHash("sha256").update(tarball).digest("hex")}  octopulse-0.2.0.tar.gz\n`,
+    );
+    const runCommand = vi.fn().mockRejectedValue(new SelfUpdateError("tar failed"));
+    const verifySignature = vi.fn().mockResolvedValue(undefined);
+
+    await expect(
+      runSelfUpdate({ repoRoot, fetchImpl, runCommand, verifySignature }),
+    ).rejects.toThrow("tar failed");
+    expect(existsSync(path.join(repoRoot, "package.json"))).toBe(true);
+    expect(readdirSync(path.dirname(repoRoot))).not.toContainEqual(
+      expect.stringContaining(`${path.basename(repoRoot)}.update-`),
+    );
+  });
+
+  it("refuses to unpack a tarball whose checksum does not match", async () => {
+    const repoRoot = mkdtempSync(path.join(os.tmpdir(), "octopulse-self-update-"));
+    tempDirs.push(repoRoot);
+    const runCommand = vi.fn();
+    const fetchImpl = createReleaseFetch(
+      "v0.2.0",
+      Buffer.from("tampered"),
+      `${"0".repeat(64)}  octopulse-0.2.0.tar.gz\n`,
+    );
+
+    await expect(
+      runSelfUpdate({
+        repoRoot,
+        currentVersion: "0.1.0",
+        fetchImpl,
+        runCommand,
+        verifySignature: vi.fn().mockResolvedValue(undefined),
+      }),
+    ).rejects.toThrow("Checksum mismatch");
+    expect(runCommand).not.toHaveBeenCalled();
+  });
+
+  it("refuses to unpack a release whose checksums are not signed by the pinned key", async () => {
+    const repoRoot = mkdtempSync(path.join(os.tmpdir(), "octopulse-self-update-"));
+    tempDirs.push(repoRoot);
+    const tarball = Buffer.from("release tarball");
+    const fetchImpl = createReleaseFetch(
+      "v0.2.0",
+      tarball,
+      `${createHash("sha256").update(tarball).digest("hex")}  octopulse-0.2.0.tar.gz\n`,
+    );
+    const runCommand = vi.fn();
+    const verifySignature = vi.fn().mockRejectedValue(new SelfUpdateError("bad signature"));
+
+    await expect(
+      runSelfUpdate({ repoRoot, currentVersion: "0.1.0", fetchImpl, runCommand, verifySignature }),
+    ).rejects.toThrow("bad signature");
+    expect(runCommand).not.toHaveBeenCalled();
+  });
+
+  it("leaves git checkouts to git", async () => {
+    const repoRoot = mkdtempSync(path.join(os.tmpdir(), "octopulse-self-update-"));
+    tempDirs.push(repoRoot);
+    mkdirSync(path.join(repoRoot, ".git"));
+    const fetchImpl = createReleaseFetch("v0.2.0", Buffer.from(""), "");
+
+    await expect(runSelfUpdate({ repoRoot, currentVersion: "0.1.0", fetchImpl })).rejects.toThrow(
+      "is a git checkout",
+    );
+    expect(fetchImpl).toHaveBeenCalledTimes(1);
+    expect(existsSync(path.join(repoRoot, ".git"))).toBe(true);
+  });
+
+  it("reports when the installed version is current", async () => {
+    const fetchImpl = createReleaseFetch("v0.1.0", Buffer.from(""), "");
+
+    await expect(runSelfUpdate({ currentVersion: "0.1.0", fetchImpl })).resolves.toBe(
+      "Octopulse 0.1.0 is up to date.",
+    );
+  });
+});
+
+describe("verifyReleaseSignature", () => {
+  it("refuses every signature while no release key is pinned", async () => {
+    const dirPath = mkdtempSync(path.join(os.tmpdir(), "octopulse-self-update-"));
+    tempDirs.push(dirPath);
+    const allowedSignersPath = path.join(dirPath, "release-allowed-signers");
+    writeFileSync(allowedSignersPath, "# releases@octopulse ssh-ed25519 AAAA...\n");
+
+    await expect(verifyReleaseSignature(allowedSignersPath, "SHA256SUMS", "")).rejects.toThrow(
+      `No release signing key is pinned in ${allowedSignersPath}`,
+    );
+  });
+});
+
+describe("checkDownloadIntegrity", () => {
+  it("requires an entry for the file", () => {
+    expect(() => checkDownloadIntegrity(Buffer.from(""), "octopulse-0.2.0.tar.gz", "")).toThrow(
+      SelfUpdateError,
+    );
+  });
+});
+
+describe("compareVersions", () => {
+  it("compares numeric version segments", () => {
+    expect(compareVersions("0.10.0", "0.9.1")).toBe(1);
+    expect(compareVersions("1.0.0", "1.0.0")).toBe(0);
+    expect(compareVersions("1.0.0", "1.0.1")).toBe(-1);
+  });
+});
+
+function createReleaseFetch(tagName: string, tarball: Buffer, checksums: string): typeof fetch {
+  const version = tagName.replace(/^v/, "");
+
+  return vi.fn(async (input: string | URL | Request) => {
+    const url = String(input);
+
+    if (url.endsWith("/releases/latest")) {
+      return Response.json({
+        tag_name: tagName,
+        assets: [
+          {
+            name: `octopulse-${version}.tar.gz`,
+            browser_download_url: "https://example.test/octopulse.tar.gz",
+          },
+          { name: "SHA256SUMS", browser_download_url: "https://example.test/SHA256SUMS" },
+          {
+            name: "SHA256SUMS.sig",
+            browser_download_url: "https://example.test/SHA256SUMS.sig",
+          },
+        ],
+      });
+    }
+
+    if (url.endsWith(".sig")) {
+      return new Response("release signature");
+    }
+
+    return new Response(url.endsWith("SHA256SUMS") ? checksums : tarball);
+  }) as typeof fetch;
+}
@@ -177,6 +177,7 @@ describe("startServer", () => {
               deliveryStatus: "sent" as const,
               createdAt: "2026-04-10 12:03:00",
               deliveredAt: "2026-04-10T12:03:02.000Z",
+              readAt: null,
               decisionStates: ["notified" as const],
               eventTypes: ["review_approved"],
               actorClasses: ["human_other" as const],
@@ -245,6 +246,7 @@ describe("startServer", () => {
         pullRequestStates: ["tracked", "merged"],
         repository: "acme/octopulse",
         actorClass: "human_other",
+        search: "",
       },
       page: 2,
       pageSize: DEFAULT_ACTIVITY_PAGE_SIZE,
@@ -306,6 +308,49 @@ describe("startServer", () => {
       ],
     });
   });
+
+  it("serves the inbox page and toggles notification read state", async () => {
+    let inboxOptions: unknown;
+    const readStateChanges: string[] = [];
+
+    const server = await startServer({
+      host: "127.0.0.1",
+      port: 0,
+      listNotificationInbox: async (options) => {
+        inboxOptions = options;
+
+        return { entries: [], page: 1, pageSize: DEFAULT_ACTIVITY_PAGE_SIZE, totalCount: 0, totalPages: 1 };
+      },
+      markNotificationRecordRead: (notificationRecordId) => {
+        readStateChanges.push(`read ${notificationRecordId}`);
+      },
+      markNotificationRecordUnread: (notificationRecordId) => {
+        readStateChanges.push(`unread ${notificationRecordId}`);
+      },
+      openInbox: async () => "http://127.0.0.1:3000/inbox",
+    });
+    servers.push(server);
+
+    const origin = readServerOrigin(server);
+    const documentResponse = await fetch(`${origin}/inbox`);
+    const inboxResponse = await fetch(`${origin}/api/notification-inbox?repo=acme%2Foctopulse`);
+    const readResponse = await fetch(`${origin}/api/notification-records/4/read`, { method: "POST" });
+    const unreadResponse = await fetch(`${origin}/api/notification-records/4/read`, { method: "DELETE" });
+    const shortcutResponse = await fetch(`${origin}/api/shortcuts/open-inbox`, { method: "POST" });
+
+    expect(documentResponse.status).toBe(200);
+    expect(documentResponse.headers.get("content-type")).toContain("text/html");
+    expect(inboxResponse.status).toBe(200);
+    expect(inboxOptions).toEqual({
+      filters: expect.objectContaining({ repository: "acme/octopulse", actorClass: "" }),
+      page: 1,
+      pageSize: DEFAULT_ACTIVITY_PAGE_SIZE,
+    });
+    expect(readResponse.status).toBe(200);
+    expect(unreadResponse.status).toBe(200);
+    expect(readStateChanges).toEqual(["read 4", "unread 4"]);
+    expect(await shortcutResponse.json()).toEqual({ openedUrl: "http://127.0.0.1:3000/inbox" });
+  });
 });
 
 function createPullRequestResponseRecord(
@@ -329,10 +374,20 @@ function createPullRequestResponseRecord(
     mergedAt: string | null;
     graceUntil: string | null;
     lastSeenHeadSha: string | null;
+    headBranch: string | null;
+    openedAt: string | null;
+    lastActivityAt: string | null;
+    additions: number | null;
+    deletions: number | null;
+    linkedIssues: Array<{ repositoryOwner: string; repositoryName: string; number: number; title: string }>;
+    projectStatus: string | null;
+    changedFilePaths: string[];
     baseBranch: string | null;
     mergeable: boolean | null;
     mergeableState: string | null;
     requestedReviewTeamSlugs: string[];
+    autoMergeEnabled: boolean | null;
+    mergedByLogin: string | null;
     createdAt: string;
     updatedAt: string;
   }> = {},
@@ -357,10 +412,20 @@ function createPullRequestResponseRecord(
     mergedAt: null,
     graceUntil: null,
     lastSeenHeadSha: "abc123",
+    headBranch: null,
+    openedAt: null,
+    lastActivityAt: null,
+    additions: null,
+    deletions: null,
+    linkedIssues: [],
+    projectStatus: null,
+    changedFilePaths: [],
     baseBranch: "main",
     mergeable: null,
     mergeableState: null,
     requestedReviewTeamSlugs: [],
+    autoMergeEnabled: null,
+    mergedByLogin: null,
     createdAt: "2026-04-10 12:00:00",
     updatedAt: "2026-04-10 12:00:00",
     ...overrides,
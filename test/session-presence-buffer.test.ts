@@ -0,0 +1,472 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import { resolveAppPaths } from "../src/config.js";
+import { initializeDatabase } from "../src/database.js";
+import {
+  buildAwayDigestNotification,
+  createAppStateHeldNotificationStore,
+  readLogindSessionPresence,
+  readScreenCastActive,
+  withSessionPresenceBuffer,
+  type SessionPresence,
+} from "../src/session-presence-buffer.js";
+
+const PRESENCE_CHECK_INTERVAL_MS = 1_000;
+const PRESENT: SessionPresence = { locked: false, idleMs: 0, screenShared: false };
+const LOCKED: SessionPresence = { locked: true, idleMs: 0, screenShared: false };
+const SHARING: SessionPresence = { locked: false, idleMs: 0, screenShared: true };
+
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  vi.useRealTimers();
+
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("withSessionPresenceBuffer", () => {
+  it("dispatches immediately while the user is present", async () => {
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const bufferedDispatcher = withSessionPresenceBuffer(
+      { dispatchNotification },
+      { readSessionPresence: async () => PRESENT },
+    );
+
+    try {
+      await bufferedDispatcher.dispatchNotification({ title: "acme/octopulse #7", body: "LGTM" });
+
+      expect(dispatchNotification).toHaveBeenCalledWith({
+        title: "acme/octopulse #7",
+        body: "LGTM",
+      });
+    } finally {
+      bufferedDispatcher.stop();
+    }
+  });
+
+  it("queues notifications while locked and delivers them individually on unlock", async () => {
+    vi.useFakeTimers();
+
+    let presence = LOCKED;
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const bufferedDispatcher = withSessionPresenceBuffer(
+      { dispatchNotification },
+      {
+        readSessionPresence: async () => presence,
+        presenceCheckIntervalMs: PRESENCE_CHECK_INTERVAL_MS,
+        digestThreshold: 3,
+      },
+    );
+
+    try {
+      await bufferedDispatcher.dispatchNotification({ title: "first", body: "one" });
+      await bufferedDispatcher.dispatchNotification({ title: "second", body: "two" });
+      await vi.advanceTimersByTimeAsync(PRESENCE_CHECK_INTERVAL_MS);
+
+      expect(dispatchNotification).not.toHaveBeenCalled();
+
+      presence = PRESENT;
+      await vi.advanceTimersByTimeAsync(PRESENCE_CHECK_INTERVAL_MS);
+
+      expect(dispatchNotification.mock.calls.map(([notification]) => notification.title)).toEqual([
+        "first",
+        "second",
+      ]);
+    } finally {
+      bufferedDispatcher.stop();
+    }
+  });
+
+  it("delivers a digest on unlock when too many notifications were queued", async () => {
+    vi.useFakeTimers();
+
+    let presence = LOCKED;
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const bufferedDispatcher = withSessionPresenceBuffer(
+      { dispatchNotification },
+      {
+        readSessionPresence: async () => presence,
+        presenceCheckIntervalMs: PRESENCE_CHECK_INTERVAL_MS,
+        digestThreshold: 1,
+      },
+    );
+
+    try {
+      await bufferedDispatcher.dispatchNotification({ title: "first", body: "one" });
+      await bufferedDispatcher.dispatchNotification({ title: "second", body: "two" });
+
+      presence = PRESENT;
+      await vi.advanceTimersByTimeAsync(PRESENCE_CHECK_INTERVAL_MS);
+
+      expect(dispatchNotification).toHaveBeenCalledTimes(1);
+      expect(dispatchNotification).toHaveBeenCalledWith({
+        title: "2 notifications while you were away",
+        body: "first\nsecond",
+        clickUrl: "https://github.com/notifications",
+        sticky: true,
+      });
+    } finally {
+      bufferedDispatcher.stop();
+    }
+  });
+
+  it("holds only non-sticky notifications once the user has been idle long enough", async () => {
+    vi.useFakeTimers();
+
+    let presence: SessionPresence = { ...PRESENT, idleMs: 11 * 60_000 };
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const bufferedDispatcher = withSessionPresenceBuffer(
+      { dispatchNotification },
+      {
+        readSessionPresence: async () => presence,
+        holdWhenIdleMs: 10 * 60_000,
+        presenceCheckIntervalMs: PRESENCE_CHECK_INTERVAL_MS,
+      },
+    );
+
+    try {
+      await bufferedDispatcher.dispatchNotification({ title: "comment", body: "nit" });
+      await bufferedDispatcher.dispatchNotification({
+        title: "review requested",
+        body: "",
+        sticky: true,
+      });
+
+      expect(dispatchNotification.mock.calls.map(([notification]) => notification.title)).toEqual([
+        "review requested",
+      ]);
+
+      presence = PRESENT;
+      await vi.advanceTimersByTimeAsync(PRESENCE_CHECK_INTERVAL_MS);
+
+      expect(dispatchNotification.mock.calls.map(([notification]) => notification.title)).toEqual([
+        "review requested",
+        "comment",
+      ]);
+    } finally {
+      bufferedDispatcher.stop();
+    }
+  });
+
+  it("reads presence once for a burst of popups", async () => {
+    vi.useFakeTimers();
+
+    const readSessionPresence = vi.fn(async () => PRESENT);
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const bufferedDispatcher = withSessionPresenceBuffer(
+      { dispatchNotification },
+      { readSessionPresence, presenceCacheMs: 5_000 },
+    );
+
+    try {
+      await bufferedDispatcher.dispatchNotification({ title: "first", body: "one" });
+      await bufferedDispatcher.dispatchNotification({ title: "second", body: "two" });
+
+      expect(readSessionPresence).toHaveBeenCalledTimes(1);
+
+      vi.setSystemTime(Date.now() + 5_000);
+      await bufferedDispatcher.dispatchNotification({ title: "third", body: "three" });
+
+      expect(readSessionPresence).toHaveBeenCalledTimes(2);
+      expect(dispatchNotification).toHaveBeenCalledTimes(3);
+    } finally {
+      bufferedDispatcher.stop();
+    }
+  });
+
+  it("ignores the lock state when buffering while locked is disabled", async () => {
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const bufferedDispatcher = withSessionPresenceBuffer(
+      { dispatchNotification },
+      { readSessionPresence: async () => LOCKED, bufferWhileLocked: false },
+    );
+
+    try {
+      await bufferedDispatcher.dispatchNotification({ title: "first", body: "one" });
+
+      expect(dispatchNotification).toHaveBeenCalledTimes(1);
+    } finally {
+      bufferedDispatcher.stop();
+    }
+  });
+});
+
+describe("withSessionPresenceBuffer across restarts", () => {
+  it("delivers notifications held before a restart once the user is back", async () => {
+    vi.useFakeTimers();
+
+    const database = createDatabase();
+    const run = vi.fn().mockResolvedValue(undefined);
+    const lockedDispatcher = withSessionPresenceBuffer(
+      { dispatchNotification: vi.fn().mockResolvedValue(undefined) },
+      {
+        readSessionPresence: async () => LOCKED,
+        heldNotificationStore: createAppStateHeldNotificationStore(database, "held"),
+      },
+    );
+
+    try {
+      await lockedDispatcher.dispatchNotification({
+        title: "acme/octopulse #7",
+        body: "LGTM",
+        clickUrl: "https://github.com/acme/octopulse/pull/7",
+        notificationRecordId: 12,
+        actions: [{ key: "merge", label: "Merge", run }],
+      });
+    } finally {
+      lockedDispatcher.stop();
+    }
+
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const restartedDispatcher = withSessionPresenceBuffer(
+      { dispatchNotification },
+      {
+        readSessionPresence: async () => PRESENT,
+        presenceCheckIntervalMs: PRESENCE_CHECK_INTERVAL_MS,
+        heldNotificationStore: createAppStateHeldNotificationStore(database, "held"),
+      },
+    );
+
+    try {
+      await vi.advanceTimersByTimeAsync(PRESENCE_CHECK_INTERVAL_MS);
+
+      expect(dispatchNotification).toHaveBeenCalledWith({
+        title: "acme/octopulse #7",
+        body: "LGTM",
+        clickUrl: "https://github.com/acme/octopulse/pull/7",
+        notificationRecordId: 12,
+      });
+      expect(createAppStateHeldNotificationStore(database, "held").read()).toEqual([]);
+    } finally {
+      restartedDispatcher.stop();
+      database.close();
+    }
+  });
+});
+
+describe("withSessionPresenceBuffer during busy calendar events", () => {
+  it("holds notifications and delivers a catch-up digest when the event ends", async () => {
+    vi.useFakeTimers();
+
+    let calendarBusy = true;
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const bufferedDispatcher = withSessionPresenceBuffer(
+      { dispatchNotification },
+      {
+        readSessionPresence: async () => PRESENT,
+        isCalendarBusy: async () => calendarBusy,
+        presenceCheckIntervalMs: PRESENCE_CHECK_INTERVAL_MS,
+      },
+    );
+
+    try {
+      await bufferedDispatcher.dispatchNotification({
+        title: "review requested",
+        body: "",
+        soundFile: "/tmp/comment.wav",
+        sticky: true,
+      });
+      await vi.advanceTimersByTimeAsync(PRESENCE_CHECK_INTERVAL_MS);
+
+      expect(dispatchNotification).not.toHaveBeenCalled();
+
+      calendarBusy = false;
+      await vi.advanceTimersByTimeAsync(PRESENCE_CHECK_INTERVAL_MS);
+
+      expect(dispatchNotification).toHaveBeenCalledWith({
+        title: "1 notification while you were away",
+        body: "review requested",
+        clickUrl: "https://github.com/notifications",
+        sticky: true,
+      });
+    } finally {
+      bufferedDispatcher.stop();
+    }
+  });
+
+  it("still shows critical notifications", async () => {
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const bufferedDispatcher = withSessionPresenceBuffer(
+      { dispatchNotification },
+      {
+        readSessionPresence: async () => PRESENT,
+        isCalendarBusy: async () => true,
+      },
+    );
+
+    try {
+      await bufferedDispatcher.dispatchNotification({
+        title: "acme/octopulse Dependabot alert #3",
+        body: "critical severity",
+        urgency: "critical",
+      });
+
+      expect(dispatchNotification).toHaveBeenCalledTimes(1);
+    } finally {
+      bufferedDispatcher.stop();
+    }
+  });
+});
+
+describe("withSessionPresenceBuffer while the screen is shared", () => {
+  it("redacts notification bodies by policy", async () => {
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const bufferedDispatcher = withSessionPresenceBuffer(
+      { dispatchNotification },
+      { readSessionPresence: async () => SHARING, whileScreenShared: "redact" },
+    );
+
+    try {
+      await bufferedDispatcher.dispatchNotification({
+        title: "acme/octopulse #7 Add notifications",
+        body: "alice: this leaks the secret plan",
+        clickUrl: "https://github.com/acme/octopulse/pull/7",
+        reason: "You authored this pull request",
+      });
+
+      expect(dispatchNotification).toHaveBeenCalledWith({
+        title: "acme/octopulse #7 Add notifications",
+        body: "Details hidden while your screen is shared",
+        clickUrl: "https://github.com/acme/octopulse/pull/7",
+      });
+    } finally {
+      bufferedDispatcher.stop();
+    }
+  });
+
+  it("keeps actions, the icon and the history record when redacting", async () => {
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const bufferedDispatcher = withSessionPresenceBuffer(
+      { dispatchNotification },
+      { readSessionPresence: async () => SHARING, whileScreenShared: "redact" },
+    );
+    const actions = [{ key: "approve", label: "Approve", run: vi.fn(async () => undefined) }];
+
+    try {
+      await bufferedDispatcher.dispatchNotification({
+        title: "acme/octopulse #7 Add notifications",
+        body: "alice: this leaks the secret plan",
+        actions,
+        icon: "/opt/octopulse/assets/pr-open.svg",
+        notificationRecordId: 12,
+      });
+
+      expect(dispatchNotification).toHaveBeenCalledWith({
+        title: "acme/octopulse #7 Add notifications",
+        body: "Details hidden while your screen is shared",
+        actions,
+        icon: "/opt/octopulse/assets/pr-open.svg",
+        notificationRecordId: 12,
+      });
+    } finally {
+      bufferedDispatcher.stop();
+    }
+  });
+
+  it("suppresses notifications until sharing ends by policy", async () => {
+    vi.useFakeTimers();
+
+    let presence = SHARING;
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const bufferedDispatcher = withSessionPresenceBuffer(
+      { dispatchNotification },
+      {
+        readSessionPresence: async () => presence,
+        whileScreenShared: "suppress",
+        presenceCheckIntervalMs: PRESENCE_CHECK_INTERVAL_MS,
+      },
+    );
+
+    try {
+      await bufferedDispatcher.dispatchNotification({ title: "first", body: "one" });
+      await vi.advanceTimersByTimeAsync(PRESENCE_CHECK_INTERVAL_MS);
+
+      expect(dispatchNotification).not.toHaveBeenCalled();
+
+      presence = PRESENT;
+      await vi.advanceTimersByTimeAsync(PRESENCE_CHECK_INTERVAL_MS);
+
+      expect(dispatchNotification).toHaveBeenCalledWith({ title: "first", body: "one" });
+    } finally {
+      bufferedDispatcher.stop();
+    }
+  });
+});
+
+describe("readScreenCastActive", () => {
+  it("detects device-less PipeWire video sources as screen casts", async () => {
+    const webcam = {
+      type: "PipeWire:Interface:Node",
+      info: { props: { "media.class": "Video/Source", "device.api": "v4l2" } },
+    };
+    const screenCast = {
+      type: "PipeWire:Interface:Node",
+      info: { props: { "media.class": "Video/Source", "node.name": "gnome-shell-screencast" } },
+    };
+
+    await expect(readScreenCastActive(async () => JSON.stringify([webcam]))).resolves.toBe(false);
+    await expect(
+      readScreenCastActive(async () => JSON.stringify([webcam, screenCast])),
+    ).resolves.toBe(true);
+  });
+});
+
+describe("buildAwayDigestNotification", () => {
+  it("truncates long digests and keeps a shared click target", () => {
+    const notifications = Array.from({ length: 7 }, (_, index) => ({
+      title: `update ${index + 1}`,
+      body: "",
+      clickUrl: "https://github.com/acme/octopulse/pull/7",
+    }));
+
+    expect(buildAwayDigestNotification(notifications)).toEqual({
+      title: "7 notifications while you were away",
+      body: "update 1\nupdate 2\nupdate 3\nupdate 4\nupdate 5\n…and 2 more",
+      clickUrl: "https://github.com/acme/octopulse/pull/7",
+      sticky: true,
+    });
+  });
+});
+
+describe("readLogindSessionPresence", () => {
+  it("reads lock and idle hints from logind", async () => {
+    const now = Date.parse("2026-04-10T12:00:00.000Z");
+    const idleSinceMicros = (now - 5 * 60_000) * 1000;
+    const runCommand = vi
+      .fn()
+      .mockResolvedValue(`IdleHint=yes\nIdleSinceHint=${idleSinceMicros}\nLockedHint=yes\n`);
+
+    await expect(readLogindSessionPresence(runCommand, "3", now)).resolves.toEqual({
+      locked: true,
+      idleMs: 5 * 60_000,
+    });
+    expect(runCommand).toHaveBeenCalledWith("loginctl", [
+      "show-session",
+      "3",
+      "--property=LockedHint",
+      "--property=IdleHint",
+      "--property=IdleSinceHint",
+    ]);
+  });
+
+  it("treats an unavailable logind as present", async () => {
+    const runCommand = vi.fn().mockRejectedValue(new Error("loginctl: not found"));
+
+    await expect(readLogindSessionPresence(runCommand, "3")).resolves.toEqual({
+      locked: false,
+      idleMs: 0,
+    });
+  });
+});
+
+function createDatabase(): ReturnType<typeof initializeDatabase> {
+  const homeDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-session-presence-home-"));
+  tempDirs.push(homeDir);
+  return initializeDatabase(resolveAppPaths({ homeDir }));
+}
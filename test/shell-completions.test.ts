@@ -0,0 +1,42 @@
+import { describe, expect, it } from "vitest";
+
+import {
+  renderManPage,
+  renderShellCompletions,
+  type CommandLineReference,
+} from "../src/shell-completions.js";
+
+const REFERENCE: CommandLineReference = {
+  synopsis: "octopulse [options] [command]",
+  commands: [
+    { name: "stats", term: "stats", description: "Summarize notification load" },
+    { name: "completions", term: "completions <shell>", description: "Print completions" },
+  ],
+  options: [{ term: "--config <path>", description: "Config file [default: ~/.config]" }],
+};
+
+describe("renderShellCompletions", () => {
+  it.each(["bash", "zsh", "fish"] as const)("offers every command and option for %s", (shell) => {
+    const completions = renderShellCompletions(shell, REFERENCE);
+
+    expect(completions).toContain("stats");
+    expect(completions).toContain("completions");
+    expect(completions).toContain(shell === "fish" ? "-l config -r -F" : "--config");
+  });
+
+  it("escapes option descriptions for zsh", () => {
+    expect(renderShellCompletions("zsh", REFERENCE)).toContain(
+      "'--config[Config file \\[default\\: ~/.config\\]]:value:_files' \\",
+    );
+  });
+});
+
+describe("renderManPage", () => {
+  it("lists the commands and options", () => {
+    const manPage = renderManPage(REFERENCE);
+
+    expect(manPage).toMatch(/^\.TH OCTOPULSE 1/);
+    expect(manPage).toContain(".TP\n\\fBcompletions <shell>\\fR\nPrint completions");
+    expect(manPage).toContain(".SH OPTIONS\n.TP\n\\fB\\-\\-config <path>\\fR\n");
+  });
+});
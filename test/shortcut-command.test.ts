@@ -0,0 +1,61 @@
+import type { Server } from "node:http";
+
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import { readServerOrigin, startServer } from "../src/server.js";
+import { runShortcutCommand, ShortcutCommandError } from "../src/shortcut-command.js";
+
+const servers: Server[] = [];
+
+afterEach(async () => {
+  await Promise.all(servers.splice(0).map((server) => closeServer(server)));
+});
+
+describe("runShortcutCommand", () => {
+  it("asks the running service to open the latest notification, open the inbox, and toggle pause", async () => {
+    const openLatestNotification = vi
+      .fn()
+      .mockResolvedValue("https://github.com/acme/octopulse/pull/7");
+    const openInbox = vi.fn().mockResolvedValue("http://127.0.0.1:3000/inbox");
+    const toggleNotificationPause = vi.fn().mockResolvedValue(true);
+    const server = await startServer({
+      host: "127.0.0.1",
+      port: 0,
+      openLatestNotification,
+      openInbox,
+      toggleNotificationPause,
+    });
+    servers.push(server);
+    const serverOrigin = readServerOrigin(server);
+
+    await expect(runShortcutCommand(["open-latest"], { serverOrigin })).resolves.toBe(
+      "Opened https://github.com/acme/octopulse/pull/7",
+    );
+    await expect(runShortcutCommand(["open-inbox"], { serverOrigin })).resolves.toBe(
+      "Opened http://127.0.0.1:3000/inbox",
+    );
+    await expect(runShortcutCommand(["toggle-pause"], { serverOrigin })).resolves.toBe(
+      "Notifications paused.",
+    );
+    expect(openLatestNotification).toHaveBeenCalledTimes(1);
+    expect(openInbox).toHaveBeenCalledTimes(1);
+    expect(toggleNotificationPause).toHaveBeenCalledTimes(1);
+  });
+
+  it("rejects unknown shortcuts", async () => {
+    await expect(runShortcutCommand(["dance"])).rejects.toBeInstanceOf(ShortcutCommandError);
+  });
+});
+
+async function closeServer(server: Server): Promise<void> {
+  await new Promise<void>((resolve, reject) => {
+    server.close((error) => {
+      if (error) {
+        reject(error);
+        return;
+      }
+
+      resolve();
+    });
+  });
+}
@@ -0,0 +1,176 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import os from "node:os";
+import path from "node:path";
+
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import type {
+  DiscoveredPullRequest,
+  PullRequestCoordinates,
+} from "../src/authored-pull-request-discovery.js";
+import { resolveAppPaths } from "../src/config.js";
+import { initializeDatabase } from "../src/database.js";
+import { PullRequestRepository } from "../src/pull-request-repository.js";
+import {
+  formatSinglePollCycleResult,
+  readSinglePollCycleExitCode,
+  runSinglePollCycle,
+} from "../src/single-poll-cycle.js";
+
+const OBSERVED_AT = "2026-04-10T12:00:00.000Z";
+const tempDirs: string[] = [];
+
+afterEach(() => {
+  for (const tempDir of tempDirs.splice(0)) {
+    rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe("runSinglePollCycle", () => {
+  it("counts the notifications delivered by one discovery pass and poll", async () => {
+    const database = createDatabase();
+    const notificationDispatcher = {
+      dispatchNotification: vi.fn(async () => ({ openedClickUrl: false })),
+    };
+
+    try {
+      const result = await runSinglePollCycle(
+        database,
+        { client: {}, currentUserLogin: "octocat" },
+        {
+          notificationDispatcher,
+          discovery: {
+            searchOpenAuthoredPullRequests: async () => [],
+            searchOpenReviewRequestedPullRequests: async () => [
+              { repositoryOwner: "widgets", repositoryName: "dashboard", number: 42 },
+            ],
+            fetchPullRequestDetail: async (_client, coordinates) =>
+              createDiscoveredPullRequest(coordinates),
+            fetchReviewRequest: async () => ({
+              requesterLogin: "alice",
+              requestedAt: "2026-04-10T11:00:00.000Z",
+            }),
+            observedAt: OBSERVED_AT,
+            notificationDispatchedAt: OBSERVED_AT,
+          },
+          polling: {
+            observedAt: OBSERVED_AT,
+            pollPullRequest: async () => {},
+          },
+        },
+      );
+
+      expect(result).toEqual({
+        discoveredCount: 1,
+        discoveryFailed: false,
+        polledCount: 1,
+        failedCount: 0,
+        deliveredCount: 1,
+      });
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledTimes(1);
+      expect(readSinglePollCycleExitCode(result)).toBe(0);
+      expect(formatSinglePollCycleResult(result)).toBe(
+        "Delivered 1 notification from 1 polled pull request",
+      );
+    } finally {
+      database.close();
+    }
+  });
+
+  it("keeps polling tracked pull requests when discovery fails", async () => {
+    const database = createDatabase();
+    const pollPullRequest = vi.fn(async () => {
+      throw new Error("GitHub returned 502");
+    });
+
+    try {
+      new PullRequestRepository(database).upsertPullRequest({
+        ...createDiscoveredPullRequest({
+          repositoryOwner: "acme",
+          repositoryName: "octopulse",
+          number: 7,
+        }),
+        lastSeenAt: "2026-04-10T11:55:00.000Z",
+        graceUntil: null,
+      });
+
+      const result = await runSinglePollCycle(
+        database,
+        { client: {}, currentUserLogin: "octocat" },
+        {
+          notificationDispatcher: { dispatchNotification: vi.fn(async () => undefined) },
+          discovery: {
+            searchOpenAuthoredPullRequests: async () => {
+              throw new Error("search is down");
+            },
+          },
+          polling: { observedAt: OBSERVED_AT, pollPullRequest, onError: () => {} },
+        },
+      );
+
+      expect(pollPullRequest).toHaveBeenCalledTimes(1);
+      expect(result).toEqual({
+        discoveredCount: 0,
+        discoveryFailed: true,
+        polledCount: 0,
+        failedCount: 1,
+        deliveredCount: 0,
+      });
+      expect(readSinglePollCycleExitCode(result)).toBe(1);
+      expect(formatSinglePollCycleResult(result)).toBe(
+        "Delivered 0 notifications from 0 polled pull requests (discovery failed, 1 failed)",
+      );
+    } finally {
+      database.close();
+    }
+  });
+});
+
+describe("readSinglePollCycleExitCode", () => {
+  it("tells a quiet cycle apart from one that delivered notifications", () => {
+    const quietCycle = {
+      discoveredCount: 3,
+      discoveryFailed: false,
+      polledCount: 3,
+      failedCount: 0,
+      deliveredCount: 0,
+    };
+
+    expect(readSinglePollCycleExitCode(quietCycle)).toBe(3);
+    expect(readSinglePollCycleExitCode({ ...quietCycle, deliveredCount: 2 })).toBe(0);
+    expect(readSinglePollCycleExitCode({ ...quietCycle, deliveredCount: 2, failedCount: 1 })).toBe(
+      1,
+    );
+  });
+});
+
+function createDatabase(): ReturnType<typeof initializeDatabase> {
+  const homeDir = mkdtempSync(path.join(os.tmpdir(), "octopulse-single-poll-home-"));
+  tempDirs.push(homeDir);
+
+  return initializeDatabase(resolveAppPaths({ homeDir }));
+}
+
+function createDiscoveredPullRequest(coordinates: PullRequestCoordinates): DiscoveredPullRequest {
+  return {
+    githubPullRequestId: coordinates.number * 100 + 1,
+    repositoryOwner: coordinates.repositoryOwner,
+    repositoryName: coordinates.repositoryName,
+    number: coordinates.number,
+    url: `https://github.com/${coordinates.repositoryOwner}/${coordinates.repositoryName}/pull/${coordinates.number}`,
+    authorLogin: "octocat",
+    authorAvatarUrl: "https://avatars.example.test/octocat.png",
+    title: `Pull request ${coordinates.number}`,
+    state: "open",
+    isDraft: false,
+    closedAt: null,
+    mergedAt: null,
+    lastSeenHeadSha: "abc123",
+    baseBranch: "main",
+    headBranch: "feature/charts",
+    bodyText: "",
+    mergeable: true,
+    mergeableState: "clean",
+    requestedReviewTeamSlugs: [],
+  };
+}
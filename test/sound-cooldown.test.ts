@@ -0,0 +1,28 @@
+import { describe, expect, it, vi } from "vitest";
+
+import { withSoundCooldown } from "../src/sound-cooldown.js";
+
+describe("withSoundCooldown", () => {
+  it("drops sounds played within the cooldown but still shows the popups", async () => {
+    let currentTime = Date.parse("2026-04-10T09:00:00.000Z");
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const cooledDown = withSoundCooldown(
+      { dispatchNotification },
+      { cooldownMs: 30_000, now: () => currentTime },
+    );
+
+    await cooledDown.dispatchNotification({ title: "PR #1", body: "one", soundFile: "/tmp/chime.oga" });
+    currentTime += 10_000;
+    await cooledDown.dispatchNotification({ title: "PR #2", body: "two" });
+    await cooledDown.dispatchNotification({ title: "PR #3", body: "three", soundFile: "/tmp/chime.oga" });
+    currentTime += 20_000;
+    await cooledDown.dispatchNotification({ title: "PR #4", body: "four", soundFile: "/tmp/chime.oga" });
+
+    expect(dispatchNotification.mock.calls.map(([notification]) => notification)).toEqual([
+      { title: "PR #1", body: "one", soundFile: "/tmp/chime.oga" },
+      { title: "PR #2", body: "two" },
+      { title: "PR #3", body: "three" },
+      { title: "PR #4", body: "four", soundFile: "/tmp/chime.oga" },
+    ]);
+  });
+});
@@ -0,0 +1,54 @@
+import { describe, expect, it, vi } from "vitest";
+
+import {
+  parseSubscriptionTarget,
+  runSubscriptionsCommand,
+  SubscriptionsCommandError,
+} from "../src/subscriptions-command.js";
+
+describe("runSubscriptionsCommand", () => {
+  it("lists watched repositories in alphabetical order", async () => {
+    const client = {
+      listWatchedRepositories: vi.fn().mockResolvedValue(["widgets/dashboard", "acme/octopulse"]),
+      unsubscribe: vi.fn(),
+    };
+
+    await expect(runSubscriptionsCommand(["list"], client)).resolves.toBe(
+      "acme/octopulse\nwidgets/dashboard",
+    );
+  });
+
+  it("unsubscribes from repositories and threads", async () => {
+    const client = {
+      listWatchedRepositories: vi.fn(),
+      unsubscribe: vi.fn().mockResolvedValue(undefined),
+    };
+
+    await expect(runSubscriptionsCommand(["unsubscribe", "acme/noisy"], client)).resolves.toBe(
+      "Stopped watching acme/noisy",
+    );
+    await expect(runSubscriptionsCommand(["unsubscribe", "42"], client)).resolves.toBe(
+      "Unsubscribed from notification thread 42",
+    );
+    expect(client.unsubscribe).toHaveBeenNthCalledWith(1, {
+      kind: "repository",
+      repositoryOwner: "acme",
+      repositoryName: "noisy",
+    });
+    expect(client.unsubscribe).toHaveBeenNthCalledWith(2, { kind: "thread", threadId: 42 });
+  });
+
+  it("rejects unknown subcommands with usage", async () => {
+    const client = {
+      listWatchedRepositories: vi.fn(),
+      unsubscribe: vi.fn(),
+    };
+
+    await expect(runSubscriptionsCommand(["prune"], client)).rejects.toBeInstanceOf(
+      SubscriptionsCommandError,
+    );
+    expect(() => parseSubscriptionTarget("not a repo")).toThrowError(
+      'Expected owner/repo or a numeric thread id, received "not a repo"',
+    );
+  });
+});
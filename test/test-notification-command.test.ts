@@ -0,0 +1,60 @@
+import { describe, expect, it, vi } from "vitest";
+
+import {
+  runTestNotificationCommand,
+  TestNotificationCommandError,
+} from "../src/test-notification-command.js";
+
+describe("runTestNotificationCommand", () => {
+  it("shows a pull request popup built like real ones, then a plain notification", async () => {
+    const notificationDispatcher = {
+      dispatchNotification: vi.fn().mockResolvedValue({ openedClickUrl: false }),
+    };
+
+    await expect(
+      runTestNotificationCommand([], {
+        notificationDispatcher,
+        notificationSounds: { approved: "/home/me/sounds/ship-it.oga" },
+      }),
+    ).resolves.toContain("Sent a pull request approval and a plain notification.");
+
+    expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledTimes(2);
+    expect(notificationDispatcher.dispatchNotification).toHaveBeenNthCalledWith(
+      1,
+      expect.objectContaining({
+        title: "octocat/Hello-World #1 Octopulse test notification",
+        clickUrl: "https://github.com/octocat/Hello-World/pull/1",
+        icon: expect.stringContaining("pull-request-open.svg"),
+        soundFile: "/home/me/sounds/ship-it.oga",
+        markup: expect.objectContaining({
+          headerAvatarUrl: "https://avatars.githubusercontent.com/u/583231",
+          paragraphs: [expect.objectContaining({ actorLogin: "github" })],
+        }),
+      }),
+    );
+    expect(notificationDispatcher.dispatchNotification).toHaveBeenNthCalledWith(2, {
+      title: "Octopulse test notification",
+      body: "Popups from Octopulse show up here. Click to open your GitHub notifications.",
+      clickUrl: "https://github.com/notifications",
+    });
+  });
+
+  it("reports a notification server that rejects the popup", async () => {
+    const notificationDispatcher = {
+      dispatchNotification: vi
+        .fn()
+        .mockRejectedValue(new Error("org.freedesktop.Notifications is not available")),
+    };
+
+    await expect(runTestNotificationCommand([], { notificationDispatcher })).rejects.toThrowError(
+      TestNotificationCommandError,
+    );
+    expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledTimes(1);
+  });
+
+  it("takes no arguments", async () => {
+    await expect(runTestNotificationCommand(["--loud"])).rejects.toThrowError(
+      "Usage: octopulse test-notification",
+    );
+  });
+});
@@ -84,28 +84,26 @@ describe("processTrackedPullRequestActivity", () => {
           currentUserLogin: "octocat",
           notificationDispatcher,
           notificationDispatchedAt: "2026-04-10T12:03:00.000Z",
+          fetchLinkedIssues: async () => [
+            { repositoryOwner: "acme", repositoryName: "octopulse", number: 12, title: "Polling stalls" },
+          ],
         }),
       ).resolves.toEqual({
         pullRequest: expect.objectContaining({
           id: pullRequest.id,
           title: "Refresh pull request polling",
           lastSeenHeadSha: "def456",
+          linkedIssues: [
+            { repositoryOwner: "acme", repositoryName: "octopulse", number: 12, title: "Polling stalls" },
+          ],
         }),
         skipActivityFanout: false,
       });
 
-      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledTimes(2);
-      expect(notificationDispatcher.dispatchNotification).toHaveBeenNthCalledWith(
-        1,
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledTimes(1);
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledWith(
         expect.objectContaining({
-          body: "bob: ✅ Looks good to me",
-          sticky: true,
-        }),
-      );
-      expect(notificationDispatcher.dispatchNotification).toHaveBeenNthCalledWith(
-        2,
-        expect.objectContaining({
-          body: "alice: 💬 Need test coverage",
+          body: "bob: ✅ Looks good to me\n\nalice: 💬 Need test coverage\n\n🔗 Closes #12 Polling stalls",
           sticky: true,
         }),
       );
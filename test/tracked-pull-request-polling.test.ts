@@ -11,6 +11,7 @@ import { NormalizedEventRepository } from "../src/normalized-event-repository.js
 import { NotificationRecordRepository } from "../src/notification-record-repository.js";
 import {
   pollTrackedPullRequests,
+  PullRequestPollingError,
   startRecurringTrackedPullRequestPolling,
 } from "../src/tracked-pull-request-polling.js";
 import {
@@ -1287,6 +1288,9 @@ describe("pollTrackedPullRequests", () => {
     const notificationDispatcher = {
       dispatchNotification: vi.fn().mockResolvedValue(undefined),
     };
+    const avatarCache = {
+      resolveAvatarFileUri: vi.fn().mockResolvedValue("file:///tmp/avatar.png"),
+    };
     const request = vi.fn(async (route: string) => {
       switch (route) {
         case "GET /repos/{owner}/{repo}/pulls/{pull_number}":
@@ -1358,6 +1362,7 @@ describe("pollTrackedPullRequests", () => {
             observedAt: OBSERVED_AT,
             notificationDispatchedAt: "2026-04-10T12:00:30.000Z",
             notificationDispatcher,
+            avatarCache,
           },
         ),
       ).resolves.toEqual({
@@ -1368,7 +1373,18 @@ describe("pollTrackedPullRequests", () => {
 
       const pullRequest = repository.listTrackedPullRequests()[0];
 
-      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledTimes(2);
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledTimes(1);
+      expect(avatarCache.resolveAvatarFileUri).toHaveBeenCalledWith(
+        expect.objectContaining({ avatarUrl: "https://avatars.example.test/octocat.png" }),
+      );
+      expect(avatarCache.resolveAvatarFileUri.mock.invocationCallOrder[0]).toBeLessThan(
+        notificationDispatcher.dispatchNotification.mock.invocationCallOrder[0] ?? 0,
+      );
+      expect(notificationDispatcher.dispatchNotification).toHaveBeenCalledWith(
+        expect.objectContaining({
+          body: "bob: ✅ LGTM\n\nalice: 💬 Please fix lint\n\ncarol: 💬 Inline follow-up",
+        }),
+      );
       expect(notificationRecordRepository.listNotificationRecordsForPullRequest(pullRequest?.id ?? -1)).toEqual([
         expect.objectContaining({
           normalizedEventId: expect.any(Number),
@@ -1576,11 +1592,116 @@ describe("startRecurringTrackedPullRequestPolling", () => {
     }
   });
 
+  it("polls immediately when requested between intervals", async () => {
+    vi.useFakeTimers();
+
+    const { database, repository } = createRepository();
+    const pollPullRequest = vi.fn().mockResolvedValue(undefined);
+    repository.upsertPullRequest(createPullRequestInput());
+
+    const handle = startRecurringTrackedPullRequestPolling(
+      database,
+      {
+        client: {},
+        currentUserLogin: "octocat",
+      },
+      {
+        intervalMs: POLLING_INTERVAL_MS,
+        pullRequestRepository: repository,
+        pollPullRequest,
+      },
+    );
+
+    try {
+      handle.pollNow();
+      await vi.advanceTimersByTimeAsync(0);
+
+      expect(pollPullRequest).toHaveBeenCalledTimes(1);
+    } finally {
+      handle.stop();
+      database.close();
+    }
+  });
+
+  it("skips cycles while paused and polls again once resumed", async () => {
+    vi.useFakeTimers();
+
+    const { database, repository } = createRepository();
+    const pollPullRequest = vi.fn().mockResolvedValue(undefined);
+    repository.upsertPullRequest(createPullRequestInput());
+
+    const handle = startRecurringTrackedPullRequestPolling(
+      database,
+      {
+        client: {},
+        currentUserLogin: "octocat",
+      },
+      {
+        intervalMs: POLLING_INTERVAL_MS,
+        pullRequestRepository: repository,
+        pollPullRequest,
+      },
+    );
+
+    try {
+      handle.pause();
+      handle.pollNow();
+      await vi.advanceTimersByTimeAsync(POLLING_INTERVAL_MS);
+
+      expect(pollPullRequest).not.toHaveBeenCalled();
+
+      handle.resume();
+      await vi.advanceTimersByTimeAsync(POLLING_INTERVAL_MS);
+
+      expect(pollPullRequest).toHaveBeenCalledTimes(1);
+    } finally {
+      handle.stop();
+      database.close();
+    }
+  });
+
+  it("restarts its timer when the polling interval changes", async () => {
+    vi.useFakeTimers();
+
+    const { database, repository } = createRepository();
+    const pollPullRequest = vi.fn().mockResolvedValue(undefined);
+    repository.upsertPullRequest(createPullRequestInput());
+
+    const handle = startRecurringTrackedPullRequestPolling(
+      database,
+      {
+        client: {},
+        currentUserLogin: "octocat",
+      },
+      {
+        intervalMs: POLLING_INTERVAL_MS,
+        pullRequestRepository: repository,
+        pollPullRequest,
+      },
+    );
+
+    try {
+      handle.setIntervalMs(POLLING_INTERVAL_MS * 3);
+      await vi.advanceTimersByTimeAsync(POLLING_INTERVAL_MS * 2);
+
+      expect(pollPullRequest).not.toHaveBeenCalled();
+
+      await vi.advanceTimersByTimeAsync(POLLING_INTERVAL_MS);
+
+      expect(pollPullRequest).toHaveBeenCalledTimes(1);
+      expect(() => handle.setIntervalMs(0)).toThrowError(PullRequestPollingError);
+    } finally {
+      handle.stop();
+      database.close();
+    }
+  });
+
   it("reports polling failures and continues on the next interval", async () => {
     vi.useFakeTimers();
 
     const { database, repository } = createRepository();
     const onError = vi.fn();
+    const onCycleSettled = vi.fn();
     let shouldFail = true;
     repository.upsertPullRequest(createPullRequestInput());
 
@@ -1600,6 +1721,7 @@ describe("startRecurringTrackedPullRequestPolling", () => {
           }
         },
         onError,
+        onCycleSettled,
       },
     );
 
@@ -1610,10 +1732,12 @@ describe("startRecurringTrackedPullRequestPolling", () => {
       expect(onError.mock.calls[0]?.[0]).toMatchObject({
         message: "Failed to poll pull request acme/octopulse#7: temporary GitHub outage",
       });
+      expect(onCycleSettled).toHaveBeenLastCalledWith(onError.mock.calls[0]?.[0]);
 
       await vi.advanceTimersByTimeAsync(POLLING_INTERVAL_MS);
 
       expect(onError).toHaveBeenCalledTimes(1);
+      expect(onCycleSettled).toHaveBeenLastCalledWith(null);
     } finally {
       handle.stop();
       database.close();
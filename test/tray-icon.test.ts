@@ -1,6 +1,7 @@
 import { beforeEach, describe, expect, it, vi } from "vitest";
 
 import { resetAppLoggerForTesting } from "../src/logger.js";
+import { RecentNotificationList } from "../src/recent-notifications.js";
 import { startTrayIcon } from "../src/tray-icon.js";
 
 describe("startTrayIcon", () => {
@@ -66,6 +67,18 @@ describe("startTrayIcon", () => {
             checked: false,
             enabled: true,
           },
+          {
+            title: "Inbox",
+            tooltip: "Open unread Octopulse notifications",
+            checked: false,
+            enabled: true,
+          },
+          {
+            title: "Notification History",
+            tooltip: "Open recent Octopulse notifications",
+            checked: false,
+            enabled: true,
+          },
           {
             title: "Open Logs",
             tooltip: "Open Octopulse logs",
@@ -86,15 +99,83 @@ describe("startTrayIcon", () => {
     expect(clickListener).toBeTypeOf("function");
 
     await clickListener?.({ item: { title: "Open Octopulse" } });
+    await clickListener?.({ item: { title: "Inbox" } });
+    await clickListener?.({ item: { title: "Notification History" } });
     await clickListener?.({ item: { title: "Open Logs" } });
     await clickListener?.({ item: { title: "Quit" } });
 
     expect(openUrl).toHaveBeenNthCalledWith(1, "http://127.0.0.1:3000");
-    expect(openUrl).toHaveBeenNthCalledWith(2, "http://127.0.0.1:3000/logs");
+    expect(openUrl).toHaveBeenNthCalledWith(2, "http://127.0.0.1:3000/inbox");
+    expect(openUrl).toHaveBeenNthCalledWith(
+      3,
+      "http://127.0.0.1:3000/notification-history",
+    );
+    expect(openUrl).toHaveBeenNthCalledWith(4, "http://127.0.0.1:3000/logs");
     expect(onQuitRequested).toHaveBeenCalledTimes(1);
 
     await trayIcon.stop();
 
     expect(trayRuntime.kill).toHaveBeenCalledTimes(1);
   });
+
+  it("lists recent notifications in the menu and opens them when clicked", async () => {
+    let clickListener:
+      | ((action: { item: { title: string }; seq_id: number }) => void | Promise<void>)
+      | undefined;
+    const trayRuntime = {
+      onReady: vi.fn((listener: () => void) => {
+        listener();
+      }),
+      onClick: vi.fn(
+        (
+          listener: (action: { item: { title: string }; seq_id: number }) => void | Promise<void>,
+        ) => {
+          clickListener = listener;
+        },
+      ),
+      onError: vi.fn(),
+      onExit: vi.fn(),
+      sendAction: vi.fn(),
+      kill: vi.fn(),
+    };
+    const openUrl = vi.fn().mockResolvedValue(undefined);
+    const recentNotifications = new RecentNotificationList();
+
+    const trayIcon = await startTrayIcon({
+      serverOrigin: "http://127.0.0.1:3000",
+      onQuitRequested: vi.fn().mockResolvedValue(undefined),
+      environment: {
+        DISPLAY: ":1",
+      },
+      createTray: vi.fn().mockReturnValue(trayRuntime),
+      openUrl,
+      recentNotifications,
+    });
+
+    recentNotifications.record({
+      title: "acme/octopulse #7 Add notifications",
+      clickUrl: "https://github.com/acme/octopulse/pull/7",
+    });
+
+    expect(trayRuntime.sendAction).toHaveBeenCalledWith({
+      type: "update-menu",
+      menu: expect.objectContaining({
+        items: expect.arrayContaining([
+          {
+            title: "acme/octopulse #7 Add notifications",
+            tooltip: "https://github.com/acme/octopulse/pull/7",
+            checked: false,
+            enabled: true,
+          },
+        ]),
+      }),
+      seq_id: -1,
+    });
+
+    await clickListener?.({ item: { title: "acme/octopulse #7 Add notifications" }, seq_id: 0 });
+
+    expect(openUrl).toHaveBeenCalledWith("https://github.com/acme/octopulse/pull/7");
+
+    await trayIcon.stop();
+  });
 });
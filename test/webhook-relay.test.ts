@@ -0,0 +1,87 @@
+import { describe, expect, it, vi } from "vitest";
+
+import {
+  readWebhookRelayDelivery,
+  startWebhookRelayClient,
+  type WebhookRelayDelivery,
+} from "../src/webhook-relay.js";
+
+describe("readWebhookRelayDelivery", () => {
+  it("reads pull request details from relayed issue comment webhooks", () => {
+    expect(
+      readWebhookRelayDelivery({
+        event: "message",
+        data: JSON.stringify({
+          "x-github-event": "issue_comment",
+          "x-github-delivery": "delivery-1",
+          body: {
+            repository: { full_name: "acme/octopulse" },
+            issue: { number: 7, pull_request: { url: "https://api.github.com/x" } },
+          },
+        }),
+      }),
+    ).toEqual({
+      eventName: "issue_comment",
+      deliveryId: "delivery-1",
+      repositoryFullName: "acme/octopulse",
+      pullRequestNumber: 7,
+    });
+  });
+
+  it("ignores relay control events and malformed payloads", () => {
+    expect(readWebhookRelayDelivery({ event: "ready", data: "{}" })).toBeNull();
+    expect(readWebhookRelayDelivery({ event: "message", data: "not json" })).toBeNull();
+  });
+});
+
+describe("startWebhookRelayClient", () => {
+  it("reports pull request webhooks received from the event stream", async () => {
+    const encoder = new TextEncoder();
+    const message = JSON.stringify({
+      "x-github-event": "pull_request_review",
+      body: {
+        repository: { full_name: "acme/octopulse" },
+        pull_request: { number: 7 },
+      },
+    });
+    const fetchImpl = vi.fn().mockResolvedValue(
+      new Response(
+        new ReadableStream({
+          start(controller) {
+            controller.enqueue(encoder.encode("event: ready\ndata: {}\n\n"));
+            controller.enqueue(encoder.encode(`data: ${message.slice(0, 20)}`));
+            controller.enqueue(encoder.encode(`${message.slice(20)}\n\n`));
+          },
+        }),
+      ),
+    );
+    const deliveries: WebhookRelayDelivery[] = [];
+
+    const handle = startWebhookRelayClient({
+      relayUrl: "https://smee.io/test-channel",
+      fetchImpl,
+      onDelivery: (delivery) => {
+        deliveries.push(delivery);
+      },
+    });
+
+    try {
+      await vi.waitFor(() => {
+        expect(deliveries).toEqual([
+          {
+            eventName: "pull_request_review",
+            deliveryId: null,
+            repositoryFullName: "acme/octopulse",
+            pullRequestNumber: 7,
+          },
+        ]);
+      });
+      expect(fetchImpl).toHaveBeenCalledWith("https://smee.io/test-channel", {
+        headers: { Accept: "text/event-stream" },
+        signal: expect.any(AbortSignal),
+      });
+    } finally {
+      handle.stop();
+    }
+  });
+});
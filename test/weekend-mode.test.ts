@@ -0,0 +1,144 @@
+import { afterEach, describe, expect, it, vi } from "vitest";
+
+import type { LinuxNotification } from "../src/linux-notification-adapter.js";
+import type { HeldNotificationStore } from "../src/session-presence-buffer.js";
+import { isWeekendBreakthrough, withWeekendCatchUp } from "../src/weekend-mode.js";
+
+const SATURDAY = new Date("2026-04-11T12:00:00.000Z");
+const MONDAY = new Date("2026-04-13T08:00:00.000Z");
+const CHECK_INTERVAL_MS = 1_000;
+
+afterEach(() => {
+  vi.useRealTimers();
+});
+
+describe("isWeekendBreakthrough", () => {
+  it("lets direct mentions and configured repositories through", () => {
+    const options = { repositories: ["acme/production"], currentUserLogin: "octocat" };
+
+    expect(
+      isWeekendBreakthrough(
+        { title: "acme/octopulse #7", body: "alice: @octocat can you look?" },
+        options,
+      ),
+    ).toBe(true);
+    expect(
+      isWeekendBreakthrough(
+        {
+          title: "acme/production #3",
+          body: "deploy failed",
+          clickUrl: "https://github.com/acme/production/pull/3",
+        },
+        options,
+      ),
+    ).toBe(true);
+    expect(
+      isWeekendBreakthrough(
+        {
+          title: "acme/octopulse #7",
+          body: "alice: @octocatalog thoughts?",
+          clickUrl: "https://github.com/acme/octopulse/pull/7",
+        },
+        options,
+      ),
+    ).toBe(false);
+  });
+
+  it("lets critical notifications through", () => {
+    expect(
+      isWeekendBreakthrough(
+        {
+          title: "acme/octopulse Dependabot alert #3",
+          body: "critical severity",
+          urgency: "critical",
+        },
+        { repositories: [] },
+      ),
+    ).toBe(true);
+  });
+});
+
+describe("withWeekendCatchUp", () => {
+  it("holds routine weekend notifications for a Monday summary", async () => {
+    vi.useFakeTimers();
+
+    let currentDate = SATURDAY;
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const weekendDispatcher = withWeekendCatchUp(
+      { dispatchNotification },
+      {
+        timeZone: "UTC",
+        repositories: [],
+        currentUserLogin: "octocat",
+        now: () => currentDate,
+        checkIntervalMs: CHECK_INTERVAL_MS,
+      },
+    );
+
+    try {
+      await weekendDispatcher.dispatchNotification({ title: "routine", body: "nit" });
+      await weekendDispatcher.dispatchNotification({ title: "mention", body: "@octocat ping" });
+
+      expect(dispatchNotification.mock.calls.map(([notification]) => notification.title)).toEqual([
+        "mention",
+      ]);
+
+      currentDate = MONDAY;
+      await vi.advanceTimersByTimeAsync(CHECK_INTERVAL_MS);
+
+      expect(dispatchNotification).toHaveBeenLastCalledWith({
+        title: "1 notification while you were away",
+        body: "routine",
+        clickUrl: "https://github.com/notifications",
+        sticky: true,
+      });
+    } finally {
+      weekendDispatcher.stop();
+    }
+  });
+
+  it("delivers the Monday summary for notifications held before a restart", async () => {
+    vi.useFakeTimers();
+
+    let storedNotifications: LinuxNotification[] = [];
+    const heldNotificationStore: HeldNotificationStore = {
+      read: () => [...storedNotifications],
+      write: (notifications) => {
+        storedNotifications = [...notifications];
+      },
+    };
+    const saturdayDispatcher = withWeekendCatchUp(
+      { dispatchNotification: vi.fn().mockResolvedValue(undefined) },
+      { timeZone: "UTC", repositories: [], now: () => SATURDAY, heldNotificationStore },
+    );
+
+    try {
+      await saturdayDispatcher.dispatchNotification({ title: "routine", body: "nit" });
+    } finally {
+      saturdayDispatcher.stop();
+    }
+
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const mondayDispatcher = withWeekendCatchUp(
+      { dispatchNotification },
+      {
+        timeZone: "UTC",
+        repositories: [],
+        now: () => MONDAY,
+        checkIntervalMs: CHECK_INTERVAL_MS,
+        heldNotificationStore,
+      },
+    );
+
+    try {
+      await vi.advanceTimersByTimeAsync(CHECK_INTERVAL_MS);
+
+      expect(dispatchNotification).toHaveBeenCalledWith(
+        expect.objectContaining({ title: "1 notification while you were away", body: "routine" }),
+      );
+      expect(storedNotifications).toEqual([]);
+    } finally {
+      mondayDispatcher.stop();
+    }
+  });
+});
@@ -0,0 +1,99 @@
+import { describe, expect, it, vi } from "vitest";
+
+import {
+  resolveNotificationMode,
+  withWorkingHoursSchedule,
+  type WorkingHoursSchedule,
+} from "../src/working-hours.js";
+
+const SCHEDULE: WorkingHoursSchedule = {
+  timeZone: "America/New_York",
+  outsideHoursMode: "silent",
+  days: {
+    monday: [{ startMinute: 9 * 60, endMinute: 17 * 60 + 30 }],
+  },
+};
+
+describe("resolveNotificationMode", () => {
+  it("evaluates working hours in the configured timezone", () => {
+    // 2026-04-13 is a Monday; 13:00Z is 09:00 in New York.
+    expect(resolveNotificationMode(SCHEDULE, new Date("2026-04-13T13:00:00.000Z"))).toBe("full");
+    expect(resolveNotificationMode(SCHEDULE, new Date("2026-04-13T12:59:00.000Z"))).toBe(
+      "silent",
+    );
+    expect(resolveNotificationMode(SCHEDULE, new Date("2026-04-13T21:30:00.000Z"))).toBe(
+      "silent",
+    );
+  });
+
+  it("applies the outside-hours mode to days without ranges", () => {
+    expect(
+      resolveNotificationMode(
+        { ...SCHEDULE, outsideHoursMode: "off" },
+        new Date("2026-04-14T15:00:00.000Z"),
+      ),
+    ).toBe("off");
+  });
+});
+
+describe("withWorkingHoursSchedule", () => {
+  it("strips sound and stickiness in silent mode", async () => {
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const scheduledDispatcher = withWorkingHoursSchedule(
+      { dispatchNotification },
+      SCHEDULE,
+      () => new Date("2026-04-13T22:00:00.000Z"),
+    );
+
+    await scheduledDispatcher.dispatchNotification({
+      title: "acme/octopulse #7",
+      body: "LGTM",
+      soundFile: "/tmp/approved.wav",
+      sticky: true,
+    });
+
+    expect(dispatchNotification).toHaveBeenCalledWith({
+      title: "acme/octopulse #7",
+      body: "LGTM",
+      sticky: false,
+    });
+  });
+
+  it("skips popups in off mode", async () => {
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    const scheduledDispatcher = withWorkingHoursSchedule(
+      { dispatchNotification },
+      { ...SCHEDULE, outsideHoursMode: "off" },
+      () => new Date("2026-04-13T22:00:00.000Z"),
+    );
+
+    await scheduledDispatcher.dispatchNotification({ title: "acme/octopulse #7", body: "LGTM" });
+
+    expect(dispatchNotification).not.toHaveBeenCalled();
+  });
+
+  it("announces popups skipped in off mode once notifications are back on", async () => {
+    const dispatchNotification = vi.fn().mockResolvedValue(undefined);
+    let currentDate = new Date("2026-04-13T12:00:00.000Z");
+    const scheduledDispatcher = withWorkingHoursSchedule(
+      { dispatchNotification },
+      { ...SCHEDULE, outsideHoursMode: "off" },
+      () => currentDate,
+      { overflowClickUrl: () => "http://127.0.0.1:3000/notification-history" },
+    );
+
+    await scheduledDispatcher.dispatchNotification({ title: "acme/octopulse #7", body: "LGTM" });
+    await scheduledDispatcher.dispatchNotification({ title: "acme/octopulse #8", body: "nit" });
+    currentDate = new Date("2026-04-13T15:00:00.000Z");
+    await scheduledDispatcher.dispatchNotification({ title: "acme/octopulse #9", body: "ship it" });
+
+    expect(dispatchNotification.mock.calls.map(([notification]) => notification)).toEqual([
+      {
+        title: "2 notifications skipped outside working hours",
+        body: "They are listed in notification history.",
+        clickUrl: "http://127.0.0.1:3000/notification-history",
+      },
+      { title: "acme/octopulse #9", body: "ship it" },
+    ]);
+  });
+});